@@ -0,0 +1,142 @@
+//! Latency of skill-activation-prompt with and without the rules cache
+//!
+//! Runs the real binary against a generated skill-rules.json (many skills,
+//! several intent patterns each) the way Claude Code does: one process per
+//! prompt. Each mode is timed over the same prompts after a warm-up run,
+//! which is also what populates the cache.
+//!
+//! Run with `cargo bench --bench skill_activation`.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+const SKILLS: usize = 60;
+const RUNS: usize = 40;
+const PROMPTS: &[&str] = &[
+    "add a new route for the billing service",
+    "why is the login page slow",
+    "refactor the payment controller",
+    "write tests for the invoice parser",
+];
+
+fn main() {
+    let temp_dir = tempfile::TempDir::new().expect("temp dir");
+    let home = temp_dir.path().join("home");
+    let project = temp_dir.path().join("project");
+    fs::create_dir_all(&home).unwrap();
+    write_rules(&project);
+
+    let uncached = time_runs(&home, &project, "off");
+    let cached = time_runs(&home, &project, "on");
+
+    println!(
+        "skill-activation-prompt, {} skills, {} runs each",
+        SKILLS, RUNS
+    );
+    report("no cache", &uncached);
+    report("rules cache", &cached);
+    let speedup = median(&uncached).as_secs_f64() / median(&cached).as_secs_f64();
+    println!("median speedup: {:.2}x", speedup);
+}
+
+/// A rules file with `SKILLS` skills, each with keywords and intent patterns
+fn write_rules(project: &Path) {
+    let nouns = [
+        "route",
+        "controller",
+        "service",
+        "migration",
+        "component",
+        "query",
+    ];
+    let skills: serde_json::Map<String, serde_json::Value> = (0..SKILLS)
+        .map(|i| {
+            let noun = nouns[i % nouns.len()];
+            let topic = format!("topic{}", i);
+            let rule = serde_json::json!({
+                "type": "domain",
+                "enforcement": "suggest",
+                "priority": "medium",
+                "promptTriggers": {
+                    "keywords": [topic, format!("{} {}", topic, noun)],
+                    "intentPatterns": [
+                        format!("(?i)(create|add|build).*{}.*{}", topic, noun),
+                        format!("(?i)(fix|debug|repair).*{}", topic),
+                        format!("(?i)(explain|document).*{}\\s+\\w+", topic),
+                        format!("(?i)\\b{}s?\\b.*(slow|broken|failing)", topic),
+                        format!("(?i)(test|verify)\\w*.*{}", topic),
+                        format!("(?i)how (do|does|should).*{}", topic),
+                    ]
+                }
+            });
+            (format!("skill-{}", i), rule)
+        })
+        .collect();
+    let rules = serde_json::json!({ "version": "1.0", "skills": skills });
+
+    let rules_dir = project.join(".claude").join("skills");
+    fs::create_dir_all(&rules_dir).unwrap();
+    fs::write(
+        rules_dir.join("skill-rules.json"),
+        serde_json::to_string_pretty(&rules).unwrap(),
+    )
+    .unwrap();
+}
+
+fn time_runs(home: &Path, project: &Path, cache: &str) -> Vec<Duration> {
+    run_once(home, project, cache, PROMPTS[0]);
+    (0..RUNS)
+        .map(|i| {
+            let start = Instant::now();
+            run_once(home, project, cache, PROMPTS[i % PROMPTS.len()]);
+            start.elapsed()
+        })
+        .collect()
+}
+
+fn run_once(home: &Path, project: &Path, cache: &str, prompt: &str) {
+    let input = serde_json::json!({
+        "session_id": "bench",
+        "transcript_path": "",
+        "cwd": project,
+        "permission_mode": "default",
+        "prompt": prompt,
+    });
+    let mut child = Command::new(env!("CARGO_BIN_EXE_skill-activation-prompt"))
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CATALYST_RULES_CACHE", cache)
+        .env("CATALYST_ACTIVATION_DEBOUNCE_SECS", "0")
+        .env("RUST_LOG", "error")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("run skill-activation-prompt");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.to_string().as_bytes())
+        .unwrap();
+    assert!(child.wait().unwrap().success());
+}
+
+fn median(samples: &[Duration]) -> Duration {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    sorted[sorted.len() / 2]
+}
+
+fn report(label: &str, samples: &[Duration]) {
+    let mean = samples.iter().sum::<Duration>() / samples.len() as u32;
+    println!(
+        "{:<12} median {:>8.2?}  mean {:>8.2?}  min {:>8.2?}",
+        label,
+        median(samples),
+        mean,
+        samples.iter().min().unwrap()
+    );
+}
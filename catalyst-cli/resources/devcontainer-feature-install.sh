@@ -0,0 +1,17 @@
+#!/bin/sh
+# Dev container feature install step for Catalyst
+# Generated by `catalyst devcontainer generate`; regenerate rather than edit
+#
+# Runs as root while the image is built, before the workspace is mounted, so
+# it only stages the setup script. The feature's postCreateCommand runs it
+# as the container user once the workspace is available.
+
+set -e
+
+FEATURE_DIR="$(cd "$(dirname "$0")" && pwd)"
+TARGET="/usr/local/share/catalyst/setup.sh"
+
+mkdir -p "$(dirname "$TARGET")"
+sed "s|^CATALYST_VERSION=.*|CATALYST_VERSION=\"\${CATALYST_VERSION:-${VERSION:-{{CATALYST_VERSION}}}}\"|" \
+    "$FEATURE_DIR/setup.sh" > "$TARGET"
+chmod 755 "$TARGET"
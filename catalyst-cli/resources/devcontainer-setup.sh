@@ -0,0 +1,46 @@
+#!/bin/bash
+# Installs Catalyst in a dev container and sets up the project
+# Generated by `catalyst devcontainer generate`; regenerate rather than edit
+
+set -euo pipefail
+
+CATALYST_VERSION="${CATALYST_VERSION:-{{CATALYST_VERSION}}}"
+RELEASES_URL="{{RELEASES_URL}}"
+
+case "$(uname -m)" in
+    x86_64 | amd64) ARCH="x86_64" ;;
+    aarch64 | arm64) ARCH="aarch64" ;;
+    *)
+        echo "Error: no Catalyst release for $(uname -m)" >&2
+        exit 1
+        ;;
+esac
+
+# musl builds run on any Linux image, glibc or not
+ARCHIVE="catalyst-${ARCH}-unknown-linux-musl.tar.gz"
+INSTALL_DIR="$HOME/.claude-hooks/catalyst"
+
+WORK_DIR="$(mktemp -d)"
+trap 'rm -rf "$WORK_DIR"' EXIT
+
+echo "Downloading Catalyst ${CATALYST_VERSION} (${ARCHIVE})"
+curl -fsSL "${RELEASES_URL}/v${CATALYST_VERSION}/${ARCHIVE}" -o "$WORK_DIR/$ARCHIVE"
+curl -fsSL "${RELEASES_URL}/v${CATALYST_VERSION}/SHA256SUMS" -o "$WORK_DIR/SHA256SUMS"
+(cd "$WORK_DIR" && grep " ${ARCHIVE}\$" SHA256SUMS | sha256sum -c -)
+
+rm -rf "$INSTALL_DIR"
+mkdir -p "$INSTALL_DIR" "$HOME/.local/bin"
+tar -xzf "$WORK_DIR/$ARCHIVE" -C "$INSTALL_DIR"
+CATALYST="$(find "$INSTALL_DIR" -type f -name catalyst | head -n 1)"
+ln -sf "$CATALYST" "$HOME/.local/bin/catalyst"
+
+# Hook binaries ship next to catalyst in the release archive
+"$CATALYST" install-binaries --from "$(dirname "$CATALYST")"
+
+# The workspace usually already has .claude from the local checkout; then
+# only the container-side pieces (binaries, wrappers, settings) need fixing
+if [ -f .catalyst-version ]; then
+    "$CATALYST" status --fix --yes || echo "Warning: catalyst status reported issues" >&2
+else
+    "$CATALYST" init --profile ci --no-scripts
+fi
@@ -1,10 +1,12 @@
 # Auto-generated wrapper for {{BINARY_NAME}}
 # Created by Catalyst CLI
 
-$BinaryName = "{{BINARY_NAME}}.exe"
+{{PATH_PREAMBLE}}# Runs under Windows PowerShell or pwsh; only pwsh on Linux/macOS sets
+# $IsWindows to $false, and binaries there have no .exe suffix
+$BinaryName = if ($IsWindows -eq $false) { "{{BINARY_NAME}}" } else { "{{BINARY_NAME}}.exe" }
 
 # Try to find the binary in standard locations
-$BinaryPath = Join-Path $env:USERPROFILE ".claude-hooks\bin\$BinaryName"
+$BinaryPath = Join-Path $HOME ".claude-hooks\bin\$BinaryName"
 
 # If not found, try local project build
 if (-not (Test-Path $BinaryPath)) {
@@ -26,7 +28,7 @@ if (-not (Test-Path $BinaryPath)) {
 if (-not (Test-Path $BinaryPath)) {
     Write-Error "Error: $BinaryName binary not found"
     Write-Error "Searched locations:"
-    Write-Error "  - $env:USERPROFILE\.claude-hooks\bin\$BinaryName"
+    Write-Error "  - $HOME\.claude-hooks\bin\$BinaryName"
     Write-Error "  - `$env:CATALYST_PROJECT_DIR\target\release\$BinaryName"
     Write-Error ""
     Write-Error "Please run: .\install.ps1"
@@ -2,7 +2,7 @@
 # Auto-generated wrapper for {{BINARY_NAME}}
 # Created by Catalyst CLI
 
-# Try to find the binary in standard locations
+{{PATH_PREAMBLE}}# Try to find the binary in standard locations
 BINARY_NAME="{{BINARY_NAME}}"
 
 # Check standard installation location
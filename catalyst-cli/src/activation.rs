@@ -0,0 +1,210 @@
+//! Keyword matching modes for skill activation
+//!
+//! By default a skill-rules.json keyword matches when it appears in the
+//! prompt as a substring. A rule can set `"matchMode"` to loosen that:
+//!
+//! - `"exact"` (default): case-insensitive substring match
+//! - `"stemmed"`: words are compared after stripping common English endings,
+//!   so "testing routes" matches the keyword "test route"
+//! - `"fuzzy"`: like stemmed, but longer words may also differ by a typo
+//!
+//! Stemmed and fuzzy matching compare whole words in order, so a multi-word
+//! keyword still has to appear as a phrase.
+
+use crate::types::{CatalystError, Result};
+
+/// Shortest stem a suffix may be stripped down to
+const MIN_STEM_LEN: usize = 3;
+
+/// Shortest word fuzzy matching tolerates a typo in
+const MIN_FUZZY_LEN: usize = 5;
+
+/// How a rule's keywords are compared with the prompt
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatchMode {
+    #[default]
+    Exact,
+    Stemmed,
+    Fuzzy,
+}
+
+impl MatchMode {
+    /// Name as written in skill-rules.json
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchMode::Exact => "exact",
+            MatchMode::Stemmed => "stemmed",
+            MatchMode::Fuzzy => "fuzzy",
+        }
+    }
+}
+
+impl std::str::FromStr for MatchMode {
+    type Err = CatalystError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "exact" => Ok(MatchMode::Exact),
+            "stemmed" => Ok(MatchMode::Stemmed),
+            "fuzzy" => Ok(MatchMode::Fuzzy),
+            _ => Err(CatalystError::InvalidConfig(format!(
+                "Unknown matchMode '{}'. Expected exact, stemmed, or fuzzy",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+/// A prompt prepared for matching in any mode
+///
+/// Lowercasing and stemming happen once, however many keywords are checked.
+#[derive(Debug, Clone)]
+pub struct PromptText {
+    lower: String,
+    stems: Vec<String>,
+}
+
+impl PromptText {
+    pub fn new(prompt: &str) -> Self {
+        let lower = prompt.to_lowercase();
+        let stems = stem_words(&lower);
+        Self { lower, stems }
+    }
+
+    /// Whether a lowercase keyword matches the prompt in the given mode
+    pub fn contains_keyword(&self, keyword_lower: &str, mode: MatchMode) -> bool {
+        if self.lower.contains(keyword_lower) {
+            return true;
+        }
+        if mode == MatchMode::Exact {
+            return false;
+        }
+
+        let keyword = stem_words(keyword_lower);
+        if keyword.is_empty() || keyword.len() > self.stems.len() {
+            return false;
+        }
+        self.stems.windows(keyword.len()).any(|window| {
+            window
+                .iter()
+                .zip(&keyword)
+                .all(|(word, wanted)| words_match(word, wanted, mode))
+        })
+    }
+}
+
+fn words_match(word: &str, wanted: &str, mode: MatchMode) -> bool {
+    if word == wanted {
+        return true;
+    }
+    if mode != MatchMode::Fuzzy || word.len().min(wanted.len()) < MIN_FUZZY_LEN {
+        return false;
+    }
+    let allowed = if wanted.len() >= 8 { 2 } else { 1 };
+    strsim::levenshtein(word, wanted) <= allowed
+}
+
+/// Split lowercase text into words and stem each one
+fn stem_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(stem)
+        .collect()
+}
+
+/// Reduce a lowercase English word to a rough stem
+///
+/// A deliberately small suffix stripper: plurals, "-ing", and "-ed" are
+/// removed and a trailing "e" dropped, so "route", "routes", "routing", and
+/// "routed" all become "rout". Stems are only compared with other stems,
+/// so they don't need to be real words.
+pub fn stem(word: &str) -> String {
+    let mut stem = word.to_string();
+
+    // Plurals
+    if let Some(base) = stem
+        .strip_suffix("ies")
+        .filter(|b| b.len() >= MIN_STEM_LEN - 1)
+    {
+        stem = format!("{}y", base);
+    } else if stem.ends_with("sses") {
+        stem.truncate(stem.len() - 2);
+    } else if stem.ends_with('s')
+        && !["ss", "us", "is"].iter().any(|end| stem.ends_with(end))
+        && stem.len() > MIN_STEM_LEN
+    {
+        stem.pop();
+    }
+
+    // Verb forms, undoing a doubled final consonant ("running" -> "run")
+    let verb_suffix = ["ing", "ed"]
+        .into_iter()
+        .find(|suffix| stem.len() >= suffix.len() + MIN_STEM_LEN && stem.ends_with(suffix));
+    if let Some(suffix) = verb_suffix {
+        stem.truncate(stem.len() - suffix.len());
+        let bytes = stem.as_bytes();
+        let n = bytes.len();
+        if n >= 2
+            && bytes[n - 1] == bytes[n - 2]
+            && !b"aeiouylsz".contains(&bytes[n - 1])
+            && bytes[n - 1].is_ascii_alphabetic()
+        {
+            stem.pop();
+        }
+    }
+
+    if stem.len() > MIN_STEM_LEN && stem.ends_with('e') {
+        stem.pop();
+    }
+    stem
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_stem_plural_and_verb_forms() {
+        for group in [
+            &["test", "tests", "testing", "tested"][..],
+            &["route", "routes", "routing", "routed"],
+            &["cache", "caches", "caching", "cached"],
+            &["fix", "fixes", "fixing", "fixed"],
+            &["run", "runs", "running"],
+            &["query", "queries"],
+            &["class", "classes"],
+            &["migration", "migrations"],
+        ] {
+            let stems: Vec<String> = group.iter().map(|word| stem(word)).collect();
+            assert!(
+                stems.iter().all(|s| *s == stems[0]),
+                "{:?} -> {:?}",
+                group,
+                stems
+            );
+        }
+        assert_eq!(stem("status"), "status");
+        assert_eq!(stem("is"), "is");
+    }
+
+    #[test]
+    fn test_contains_keyword_by_mode() {
+        let prompt = PromptText::new("I'm testing Routes and new migrations");
+
+        assert!(!prompt.contains_keyword("test route", MatchMode::Exact));
+        assert!(prompt.contains_keyword("test route", MatchMode::Stemmed));
+        assert!(prompt.contains_keyword("migration", MatchMode::Exact));
+        // Words must be adjacent and in order
+        assert!(!prompt.contains_keyword("route test", MatchMode::Stemmed));
+
+        let typo = PromptText::new("add a databse migraton");
+        assert!(!typo.contains_keyword("database migration", MatchMode::Stemmed));
+        assert!(typo.contains_keyword("database migration", MatchMode::Fuzzy));
+        // Short words must match exactly even when fuzzy
+        assert!(!PromptText::new("fox the api").contains_keyword("fix", MatchMode::Fuzzy));
+
+        assert_eq!(MatchMode::from_str(" Fuzzy ").unwrap(), MatchMode::Fuzzy);
+        assert!(MatchMode::from_str("loose").is_err());
+    }
+}
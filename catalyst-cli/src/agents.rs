@@ -0,0 +1,464 @@
+//! Subagent definitions in `.claude/agents` (`catalyst agents`)
+//!
+//! Claude Code loads each `.claude/agents/<name>.md` as a subagent: front
+//! matter with `name` and `description` (and optionally `tools`, `model`,
+//! `color`), then the agent's system prompt. An agent whose front matter it
+//! can't read is skipped without a word, so every file catalyst writes or
+//! lists is checked here first.
+//!
+//! The shipped templates use `{{variable}}` placeholders: `name`, `project`
+//! (the project directory's name), and `model`, plus the variables every
+//! template gets (see the template module) and any passed to
+//! `catalyst agents add --var KEY=VALUE`.
+
+use crate::config::CatalystConfig;
+use crate::init::write_file_atomic;
+use crate::scaffold::validate_kebab_name;
+use crate::template;
+use crate::types::{CatalystError, Result, AGENTS_DIR};
+use catalyst_core::front_matter::{parse_fields, split_front_matter, unquote};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An agent definition shipped with catalyst
+#[derive(Debug, Clone, Copy)]
+pub struct AgentTemplate {
+    pub name: &'static str,
+    /// One line for `catalyst agents list`
+    pub summary: &'static str,
+    pub content: &'static str,
+}
+
+/// Templates `catalyst agents add` can install
+pub const AGENT_TEMPLATES: &[AgentTemplate] = &[
+    AgentTemplate {
+        name: "code-reviewer",
+        summary: "Reviews recent changes for bugs and consistency",
+        content: include_str!("../resources/agents/code-reviewer.md"),
+    },
+    AgentTemplate {
+        name: "test-writer",
+        summary: "Writes tests in the project's existing style",
+        content: include_str!("../resources/agents/test-writer.md"),
+    },
+    AgentTemplate {
+        name: "doc-writer",
+        summary: "Writes and updates documentation for changed features",
+        content: include_str!("../resources/agents/doc-writer.md"),
+    },
+];
+
+/// Model aliases Claude Code accepts in agent front matter
+pub const AGENT_MODELS: &[&str] = &["inherit", "sonnet", "opus", "haiku"];
+
+/// Model written into new agents unless another is chosen
+pub const DEFAULT_AGENT_MODEL: &str = "inherit";
+
+const FRONT_MATTER_FIELDS: &[&str] = &["name", "description", "tools", "model", "color"];
+
+/// Parsed agent front matter
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AgentFrontMatter {
+    pub name: String,
+    pub description: String,
+    /// Tools the agent may use; empty means all tools
+    pub tools: Vec<String>,
+    pub model: Option<String>,
+    pub color: Option<String>,
+}
+
+/// An agent file found in `.claude/agents`
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentInfo {
+    /// File name without `.md`
+    pub name: String,
+    pub path: PathBuf,
+    /// None when the front matter couldn't be read at all
+    pub front_matter: Option<AgentFrontMatter>,
+    /// Everything wrong with the file; empty if it's valid
+    pub problems: Vec<String>,
+}
+
+/// Options for `catalyst agents add`
+#[derive(Debug, Clone, Default)]
+pub struct AddAgent {
+    /// Name of a template in [`AGENT_TEMPLATES`]
+    pub template: String,
+    /// Name to install under; defaults to the template's
+    pub name: Option<String>,
+    /// Extra or overriding template variables
+    pub vars: Vec<(String, String)>,
+    /// Replace an existing agent with the same name
+    pub force: bool,
+}
+
+/// Options for `catalyst agents new`
+#[derive(Debug, Clone, Default)]
+pub struct NewAgent {
+    pub name: String,
+    pub description: String,
+    pub tools: Vec<String>,
+    pub model: Option<String>,
+    pub force: bool,
+}
+
+/// Look up a shipped template by name
+pub fn find_template(name: &str) -> Option<&'static AgentTemplate> {
+    AGENT_TEMPLATES
+        .iter()
+        .find(|template| template.name == name)
+}
+
+/// Check an agent file's contents
+///
+/// # Arguments
+///
+/// * `content` - The whole `.md` file
+/// * `file_name` - File name without `.md`, which `name` must match
+///
+/// # Returns
+///
+/// Returns the front matter if it was readable, and every problem found
+pub fn validate_agent(content: &str, file_name: &str) -> (Option<AgentFrontMatter>, Vec<String>) {
+    let (block, body) = match split_front_matter(content) {
+        Ok(Some(parts)) => parts,
+        Ok(None) => {
+            return (
+                None,
+                vec!["missing front matter: the file must start with a '---' line".to_string()],
+            )
+        }
+        Err(problem) => return (None, vec![problem]),
+    };
+
+    let mut problems = Vec::new();
+    let fields = parse_fields(&block, FRONT_MATTER_FIELDS, &mut problems);
+    let field = |key: &str| {
+        fields
+            .get(key)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    };
+
+    let mut front_matter = AgentFrontMatter::default();
+    match field("name") {
+        Some(name) => {
+            if let Err(e) = validate_kebab_name("agent", name) {
+                problems.push(e.to_string());
+            } else if name != file_name {
+                problems.push(format!(
+                    "name '{}' doesn't match the file name '{}.md'",
+                    name, file_name
+                ));
+            }
+            front_matter.name = name.to_string();
+        }
+        None => problems.push("missing required field 'name'".to_string()),
+    }
+    match field("description") {
+        Some(description) => front_matter.description = description.to_string(),
+        None => problems.push("missing required field 'description'".to_string()),
+    }
+    if let Some(tools) = field("tools") {
+        let list = tools.trim_start_matches('[').trim_end_matches(']');
+        front_matter.tools = list
+            .split(',')
+            .map(|tool| unquote(tool.trim()).to_string())
+            .collect();
+        if front_matter.tools.iter().any(String::is_empty) {
+            problems.push(format!("'tools' has an empty entry: {}", tools));
+        }
+    }
+    if let Some(model) = field("model") {
+        if !AGENT_MODELS.contains(&model) && !model.starts_with("claude-") {
+            problems.push(format!(
+                "unknown model '{}' (expected {} or a full claude-* model ID)",
+                model,
+                AGENT_MODELS.join(", ")
+            ));
+        }
+        front_matter.model = Some(model.to_string());
+    }
+    front_matter.color = field("color").map(str::to_string);
+
+    if body.trim().is_empty() {
+        problems.push("no system prompt after the front matter".to_string());
+    }
+    if content.contains("{{") {
+        problems.push(UNREPLACED_VARIABLE.to_string());
+    }
+    (Some(front_matter), problems)
+}
+
+/// Problem reported for a `{{variable}}` left in a rendered file
+pub(crate) const UNREPLACED_VARIABLE: &str = "unreplaced '{{...}}' template variable";
+
+/// Agent files in `.claude/agents`, sorted by name, each checked
+///
+/// `README.md` and other non-`.md` files are skipped.
+///
+/// # Errors
+///
+/// Returns `FileReadFailed` if the directory or a file can't be read
+pub fn list_agents(target_dir: &Path) -> Result<Vec<AgentInfo>> {
+    let dir = target_dir.join(AGENTS_DIR);
+    let read_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| CatalystError::FileReadFailed { path, source }
+    };
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => return Err(read_error(&dir)(source)),
+    };
+
+    let mut agents = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(read_error(&dir))?.path();
+        let is_agent = path.extension().is_some_and(|ext| ext == "md")
+            && path.is_file()
+            && !path
+                .file_name()
+                .is_some_and(|name| name.eq_ignore_ascii_case("README.md"));
+        if !is_agent {
+            continue;
+        }
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let content = fs::read_to_string(&path).map_err(read_error(&path))?;
+        let (front_matter, problems) = validate_agent(&content, &name);
+        agents.push(AgentInfo {
+            name,
+            path,
+            front_matter,
+            problems,
+        });
+    }
+    agents.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(agents)
+}
+
+/// Install a shipped template into `.claude/agents`
+///
+/// # Returns
+///
+/// Returns the path of the written agent file
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` for an unknown template, a bad name or variable,
+/// or an existing agent without `force`
+pub fn add_agent(target_dir: &Path, options: &AddAgent) -> Result<PathBuf> {
+    let template = find_template(&options.template).ok_or_else(|| {
+        CatalystError::InvalidConfig(format!(
+            "Unknown agent template '{}'. Available: {}",
+            options.template,
+            AGENT_TEMPLATES
+                .iter()
+                .map(|template| template.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    })?;
+    let name = options.name.as_deref().unwrap_or(template.name);
+
+    let project = dunce::canonicalize(target_dir)
+        .ok()
+        .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "this project".to_string());
+    let mut vars = template::project_vars(target_dir, &CatalystConfig::load(target_dir)?);
+    vars.extend([
+        ("name".to_string(), name.to_string()),
+        ("project".to_string(), project),
+        ("model".to_string(), DEFAULT_AGENT_MODEL.to_string()),
+    ]);
+    vars.extend(options.vars.iter().cloned());
+
+    let content = template::render(template.content, &vars)?;
+    write_agent(target_dir, name, &content, options.force)
+}
+
+/// Write a new agent with placeholder instructions
+///
+/// # Returns
+///
+/// Returns the path of the written agent file
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the front matter would be invalid or the agent
+/// exists without `force`
+pub fn create_agent(target_dir: &Path, agent: &NewAgent) -> Result<PathBuf> {
+    let mut content = format!(
+        "---\nname: {}\ndescription: {}\n",
+        agent.name,
+        agent.description.replace('\n', " ")
+    );
+    if !agent.tools.is_empty() {
+        content.push_str(&format!("tools: {}\n", agent.tools.join(", ")));
+    }
+    content.push_str(&format!(
+        "model: {}\n---\n\n\
+         You are a specialist agent for TODO: the task this agent handles.\n\n\
+         ## Process\n\n\
+         1. TODO: the steps to follow\n\n\
+         ## Report\n\n\
+         TODO: what to return when finished.\n",
+        agent.model.as_deref().unwrap_or(DEFAULT_AGENT_MODEL)
+    ));
+    write_agent(target_dir, &agent.name, &content, agent.force)
+}
+
+/// Validate and write `.claude/agents/<name>.md`
+fn write_agent(target_dir: &Path, name: &str, content: &str, force: bool) -> Result<PathBuf> {
+    validate_kebab_name("agent", name)?;
+    let (_, problems) = validate_agent(content, name);
+    if !problems.is_empty() {
+        return Err(CatalystError::InvalidConfig(format!(
+            "Agent '{}' would be invalid: {}",
+            name,
+            problems.join("; ")
+        )));
+    }
+
+    let dir = target_dir.join(AGENTS_DIR);
+    let path = dir.join(format!("{}.md", name));
+    if path.exists() && !force {
+        return Err(CatalystError::InvalidConfig(format!(
+            "Agent '{}' already exists at {}\nUse --force to overwrite.",
+            name,
+            path.display()
+        )));
+    }
+    fs::create_dir_all(&dir).map_err(|source| CatalystError::DirectoryCreationFailed {
+        path: dir.clone(),
+        source,
+    })?;
+    write_file_atomic(&path, content)?;
+    Ok(path)
+}
+
+/// Delete `.claude/agents/<name>.md`
+///
+/// # Returns
+///
+/// Returns the path that was removed
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the name is invalid or no such agent exists
+pub fn remove_agent(target_dir: &Path, name: &str) -> Result<PathBuf> {
+    validate_kebab_name("agent", name)?;
+    let path = target_dir.join(AGENTS_DIR).join(format!("{}.md", name));
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(path),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(CatalystError::InvalidConfig(
+            format!("No agent named '{}' in {}", name, AGENTS_DIR),
+        )),
+        Err(source) => Err(CatalystError::FileWriteFailed { path, source }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_agent_front_matter() {
+        let (front_matter, problems) = validate_agent(
+            "---\nname: reviewer\ndescription: |\n  Reviews code\n  carefully\ntools: [Read, \"Grep\"]\nmodel: opus\n---\n\nPrompt\n",
+            "reviewer",
+        );
+        assert!(problems.is_empty(), "{:?}", problems);
+        let front_matter = front_matter.unwrap();
+        assert_eq!(front_matter.description, "Reviews code carefully");
+        assert_eq!(front_matter.tools, vec!["Read", "Grep"]);
+
+        let (_, problems) = validate_agent(
+            "---\nname: Reviewer\ndescripton: typo\nmodel: gpt\nname: again\n---\n",
+            "reviewer",
+        );
+        let joined = problems.join("\n");
+        for expected in [
+            "unknown field 'descripton'",
+            "missing required field 'description'",
+            "unknown model 'gpt'",
+            "appears more than once",
+            "no system prompt",
+        ] {
+            assert!(
+                joined.contains(expected),
+                "missing {:?} in {}",
+                expected,
+                joined
+            );
+        }
+        assert_eq!(validate_agent("Prompt only", "x").1.len(), 1);
+        assert_eq!(validate_agent("---\nname: x\n", "x").1.len(), 1);
+    }
+
+    #[test]
+    fn test_add_new_list_remove() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        fs::create_dir_all(project.join(AGENTS_DIR)).unwrap();
+        fs::write(project.join(AGENTS_DIR).join("README.md"), "# Agents").unwrap();
+        fs::write(
+            project.join(AGENTS_DIR).join("broken.md"),
+            "no front matter",
+        )
+        .unwrap();
+
+        for template in AGENT_TEMPLATES {
+            let options = AddAgent {
+                template: template.name.to_string(),
+                ..AddAgent::default()
+            };
+            add_agent(project, &options).unwrap();
+        }
+        let renamed = AddAgent {
+            template: "code-reviewer".to_string(),
+            name: Some("api-reviewer".to_string()),
+            vars: vec![("model".to_string(), "opus".to_string())],
+            force: false,
+        };
+        let path = add_agent(project, &renamed).unwrap();
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("name: api-reviewer\n"));
+        assert!(add_agent(project, &renamed).is_err());
+
+        let new = NewAgent {
+            name: "migrations".to_string(),
+            description: "Writes database migrations".to_string(),
+            tools: vec!["Read".to_string(), "Edit".to_string()],
+            ..NewAgent::default()
+        };
+        create_agent(project, &new).unwrap();
+
+        let agents = list_agents(project).unwrap();
+        let names: Vec<&str> = agents.iter().map(|agent| agent.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "api-reviewer",
+                "broken",
+                "code-reviewer",
+                "doc-writer",
+                "migrations",
+                "test-writer"
+            ]
+        );
+        for agent in &agents {
+            assert_eq!(agent.problems.is_empty(), agent.name != "broken");
+        }
+
+        remove_agent(project, "migrations").unwrap();
+        assert!(remove_agent(project, "migrations").is_err());
+        assert!(remove_agent(project, "../escape").is_err());
+    }
+}
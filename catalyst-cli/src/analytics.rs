@@ -0,0 +1,475 @@
+//! Opt-in team analytics (`catalyst sessions push`)
+//!
+//! Teams that want numbers across developers can have each machine send
+//! session summaries to an HTTPS endpoint they run. Nothing leaves the
+//! machine unless the project's catalyst.toml turns it on:
+//!
+//! ```toml
+//! [analytics]
+//! enabled = true
+//! endpoint = "https://metrics.example.com/v1/catalyst"
+//! token_env = "CATALYST_ANALYTICS_TOKEN"   # sent as "Authorization: Bearer ..."
+//! include_paths = false    # also send the edited files' paths
+//! batch_size = 50          # sessions per request
+//! interval_minutes = 60    # file-change-tracker pushes at most this often (0: manual only)
+//! ```
+//!
+//! A summary holds counts only: a hash of the session ID, the first and
+//! last edit times, and edits by tool, files by category, and the number
+//! of risky files (async without try/catch). Summaries are built from the
+//! tracker's store, whichever backend it is, and POSTed as JSON:
+//!
+//! ```json
+//! { "schema": 1, "catalyst_version": "0.1.0", "sessions": [ ... ] }
+//! ```
+//!
+//! A session is sent again only when it has new edits, so the endpoint
+//! should treat `session` as a key and keep the latest summary.
+
+use crate::config::AnalyticsSettings;
+use crate::modification_store::{ModificationFilter, ModificationRow, TrackerBackend};
+use crate::types::{CatalystError, Result, CATALYST_VERSION};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Environment variable holding the endpoint's token, unless `token_env`
+/// names another
+pub const ANALYTICS_TOKEN_ENV: &str = "CATALYST_ANALYTICS_TOKEN";
+
+/// Push bookkeeping, inside the tracker state directory
+pub const PUSH_STATE_FILE: &str = "analytics-push.json";
+
+/// Version of the payload format
+pub const PAYLOAD_SCHEMA: u32 = 1;
+
+/// Seconds curl gets per request
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+
+/// Counts for one tracked session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionSummary {
+    /// SHA-256 of the session ID (first 16 hex digits), so summaries can be
+    /// updated without revealing the ID
+    pub session: String,
+    pub start_time: String,
+    pub last_activity: String,
+    pub edits: usize,
+    pub files: usize,
+    pub edits_by_tool: BTreeMap<String, usize>,
+    pub files_by_category: BTreeMap<String, usize>,
+    pub risky_files: usize,
+
+    /// Edited files, only with `include_paths`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paths: Option<Vec<String>>,
+}
+
+/// One request body
+#[derive(Debug, Clone, Serialize)]
+pub struct Batch {
+    pub schema: u32,
+    pub catalyst_version: String,
+    pub sessions: Vec<SessionSummary>,
+}
+
+/// What has been pushed from this machine
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PushState {
+    /// When a push was last started (RFC 3339), successful or not
+    pub last_attempt: Option<String>,
+
+    /// Session hash to the `last_activity` that was sent for it
+    pub pushed: BTreeMap<String, String>,
+}
+
+impl PushState {
+    fn path(state_dir: &Path) -> PathBuf {
+        state_dir.join(PUSH_STATE_FILE)
+    }
+
+    /// Load the push state, or an empty one if there is none
+    ///
+    /// A corrupt file is treated as empty; the worst case is sending
+    /// summaries the endpoint already has.
+    pub fn load(state_dir: &Path) -> Self {
+        fs::read_to_string(Self::path(state_dir))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the push state
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state directory can't be written
+    pub fn save(&self, state_dir: &Path) -> Result<()> {
+        fs::create_dir_all(state_dir).map_err(|source| CatalystError::DirectoryCreationFailed {
+            path: state_dir.to_path_buf(),
+            source,
+        })?;
+        let content = serde_json::to_string_pretty(self)?;
+        crate::init::write_file_atomic(&Self::path(state_dir), &content)?;
+        Ok(())
+    }
+
+    /// Whether an automatic push is due
+    pub fn is_due(&self, settings: &AnalyticsSettings, now: DateTime<Utc>) -> bool {
+        if !settings.enabled || settings.interval_minutes == 0 {
+            return false;
+        }
+        let interval = Duration::minutes(settings.interval_minutes as i64);
+        self.last_attempt
+            .as_deref()
+            .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+            .is_none_or(|last| now - last.with_timezone(&Utc) >= interval)
+    }
+}
+
+/// Summarize tracked edits per session, least recently active first
+pub fn summarize(rows: &[ModificationRow], include_paths: bool) -> Vec<SessionSummary> {
+    let mut by_session: BTreeMap<&str, Vec<&ModificationRow>> = BTreeMap::new();
+    for row in rows {
+        by_session.entry(&row.session_id).or_default().push(row);
+    }
+
+    let mut summaries: Vec<SessionSummary> = by_session
+        .into_iter()
+        .map(|(session_id, mut edits)| {
+            edits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+            // The latest edit of each file decides its category and risk
+            let mut latest: BTreeMap<&str, &ModificationRow> = BTreeMap::new();
+            let mut edits_by_tool = BTreeMap::new();
+            for edit in &edits {
+                latest.insert(&edit.file_path, edit);
+                *edits_by_tool.entry(edit.tool.clone()).or_insert(0) += 1;
+            }
+            let mut files_by_category = BTreeMap::new();
+            for edit in latest.values() {
+                *files_by_category.entry(edit.category.clone()).or_insert(0) += 1;
+            }
+
+            SessionSummary {
+                session: hash_session_id(session_id),
+                start_time: edits[0].timestamp.clone(),
+                last_activity: edits[edits.len() - 1].timestamp.clone(),
+                edits: edits.len(),
+                files: latest.len(),
+                edits_by_tool,
+                files_by_category,
+                risky_files: latest
+                    .values()
+                    .filter(|edit| edit.has_async && !edit.has_try_catch)
+                    .count(),
+                paths: include_paths.then(|| latest.keys().map(|path| path.to_string()).collect()),
+            }
+        })
+        .collect();
+    summaries.sort_by(|a, b| a.last_activity.cmp(&b.last_activity));
+    summaries
+}
+
+fn hash_session_id(session_id: &str) -> String {
+    format!("{:x}", Sha256::digest(session_id.as_bytes()))[..16].to_string()
+}
+
+/// Options for [`push`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PushOptions {
+    /// Send every session, not just those with new edits
+    pub all: bool,
+
+    /// Build the batches without sending them or recording anything
+    pub dry_run: bool,
+}
+
+/// Result of a push
+#[derive(Debug, Clone)]
+pub struct PushReport {
+    /// The batches sent (or, for a dry run, that would have been)
+    pub batches: Vec<Batch>,
+    pub endpoint: String,
+}
+
+impl PushReport {
+    /// Number of session summaries in all batches
+    pub fn sessions(&self) -> usize {
+        self.batches.iter().map(|batch| batch.sessions.len()).sum()
+    }
+}
+
+/// Send new or updated session summaries to the configured endpoint
+///
+/// # Arguments
+///
+/// * `settings` - The project's `[analytics]` section
+/// * `project_dir` - Project whose catalyst.toml picks the tracker backend
+/// * `state_dir` - Tracker state directory (edits and push state)
+/// * `options` - Whether to resend everything or only build the batches
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if analytics aren't enabled, `AnalyticsPush` if
+/// the token variable is unset or a request fails, or the tracker store's
+/// error if edits can't be read. Batches sent before a failure stay
+/// recorded as pushed.
+pub fn push(
+    settings: &AnalyticsSettings,
+    project_dir: &Path,
+    state_dir: &Path,
+    options: PushOptions,
+) -> Result<PushReport> {
+    let endpoint = match (&settings.endpoint, settings.enabled) {
+        (Some(endpoint), true) => endpoint.clone(),
+        _ => {
+            return Err(CatalystError::InvalidConfig(
+                "Analytics are off. Set analytics.enabled = true and analytics.endpoint \
+                 in catalyst.toml to push session summaries"
+                    .to_string(),
+            ))
+        }
+    };
+
+    let store = TrackerBackend::resolve(project_dir)?.open(state_dir)?;
+    let rows = store.query(&ModificationFilter::default())?;
+    let summaries = summarize(&rows, settings.include_paths);
+
+    let mut state = PushState::load(state_dir);
+    let pending: Vec<SessionSummary> = summaries
+        .iter()
+        .filter(|summary| {
+            options.all || state.pushed.get(&summary.session) != Some(&summary.last_activity)
+        })
+        .cloned()
+        .collect();
+    let batches: Vec<Batch> = pending
+        .chunks(settings.batch_size.max(1))
+        .map(|sessions| Batch {
+            schema: PAYLOAD_SCHEMA,
+            catalyst_version: CATALYST_VERSION.to_string(),
+            sessions: sessions.to_vec(),
+        })
+        .collect();
+
+    if options.dry_run {
+        return Ok(PushReport { batches, endpoint });
+    }
+
+    let token = std::env::var(&settings.token_env)
+        .ok()
+        .filter(|token| !token.trim().is_empty())
+        .ok_or_else(|| {
+            CatalystError::AnalyticsPush(format!(
+                "{} is not set; export the endpoint's token before pushing",
+                settings.token_env
+            ))
+        })?;
+
+    state.last_attempt = Some(Utc::now().to_rfc3339());
+    // Forget sessions the tracker no longer has
+    let current: BTreeSet<&String> = summaries.iter().map(|summary| &summary.session).collect();
+    state.pushed.retain(|session, _| current.contains(session));
+    state.save(state_dir)?;
+
+    for batch in &batches {
+        post_json(&endpoint, token.trim(), &serde_json::to_string(batch)?)?;
+        for summary in &batch.sessions {
+            state
+                .pushed
+                .insert(summary.session.clone(), summary.last_activity.clone());
+        }
+        state.save(state_dir)?;
+    }
+    Ok(PushReport { batches, endpoint })
+}
+
+/// POST a JSON body with curl
+///
+/// The token goes in on stdin (`-H @-`) rather than the command line, where
+/// other users could read it from the process list.
+fn post_json(endpoint: &str, token: &str, body: &str) -> Result<()> {
+    let mut body_file = tempfile::NamedTempFile::new()?;
+    body_file.write_all(body.as_bytes())?;
+    let data = format!("@{}", body_file.path().display());
+
+    let mut child = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--request", "POST"])
+        .args(["--max-time", &REQUEST_TIMEOUT_SECS.to_string()])
+        .args([
+            "--user-agent",
+            concat!("catalyst/", env!("CARGO_PKG_VERSION")),
+        ])
+        .args(["--header", "Content-Type: application/json"])
+        .args(["--header", "@-", "--data-binary", &data, endpoint])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| CatalystError::AnalyticsPush(format!("Could not run curl: {}", e)))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "Authorization: Bearer {}", token)?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(CatalystError::AnalyticsPush(format!(
+            "{}: {}",
+            endpoint,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Check an endpoint URL: HTTPS, or plain HTTP to this machine for testing
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` for any other URL
+pub fn validate_endpoint(endpoint: &str) -> Result<()> {
+    let local = ["http://localhost", "http://127.0.0.1", "http://[::1]"]
+        .iter()
+        .any(|prefix| {
+            endpoint
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with([':', '/']))
+        });
+    let https = endpoint
+        .strip_prefix("https://")
+        .is_some_and(|rest| !rest.is_empty());
+    if https || local {
+        Ok(())
+    } else {
+        Err(CatalystError::InvalidConfig(format!(
+            "analytics.endpoint '{}' must be an https:// URL",
+            endpoint
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn edit(session: &str, file: &str, tool: &str, timestamp: &str) -> ModificationRow {
+        let category = if file.ends_with(".tsx") {
+            "frontend"
+        } else {
+            "backend"
+        };
+        ModificationRow {
+            tool: tool.to_string(),
+            category: category.to_string(),
+            has_async: file.contains("api"),
+            ..ModificationRow::test_edit(session, file, timestamp)
+        }
+    }
+
+    #[test]
+    fn test_summaries_count_without_paths() {
+        let rows = vec![
+            edit("late", "src/api.ts", "Edit", "2026-03-02T10:00:00+00:00"),
+            edit("early", "src/api.ts", "Write", "2026-03-01T09:00:00+00:00"),
+            edit("early", "src/App.tsx", "Edit", "2026-03-01T09:05:00+00:00"),
+            edit("early", "src/api.ts", "Edit", "2026-03-01T09:10:00+00:00"),
+        ];
+
+        let summaries = summarize(&rows, false);
+        assert_eq!(summaries.len(), 2);
+        let early = &summaries[0];
+        assert_eq!(early.session, hash_session_id("early"));
+        assert_eq!(early.session.len(), 16);
+        assert_eq!(early.edits, 3);
+        assert_eq!(early.files, 2);
+        assert_eq!(early.edits_by_tool["Edit"], 2);
+        assert_eq!(early.files_by_category["frontend"], 1);
+        assert_eq!(early.risky_files, 1);
+        assert_eq!(early.last_activity, "2026-03-01T09:10:00+00:00");
+
+        let json = serde_json::to_string(&summaries).unwrap();
+        assert!(!json.contains("api.ts"));
+        assert!(!json.contains("early"));
+        assert_eq!(
+            summarize(&rows, true)[0].paths.as_deref().unwrap(),
+            ["src/App.tsx", "src/api.ts"]
+        );
+    }
+
+    #[test]
+    fn test_push_requires_opt_in_and_dry_run_batches() {
+        let temp_dir = TempDir::new().unwrap();
+        let state_dir = temp_dir.path().join("state");
+        fs::write(
+            temp_dir.path().join("catalyst.toml"),
+            "[tracker]\nbackend = \"jsonl\"\n",
+        )
+        .unwrap();
+        let mut store = TrackerBackend::Jsonl.open(&state_dir).unwrap();
+        for (i, session) in ["a", "b", "c"].iter().enumerate() {
+            store
+                .record(&edit(
+                    session,
+                    "src/lib.rs",
+                    "Edit",
+                    &format!("2026-03-0{}T10:00:00+00:00", i + 1),
+                ))
+                .unwrap();
+        }
+
+        let mut settings = AnalyticsSettings::default();
+        let dry_run = PushOptions {
+            dry_run: true,
+            ..PushOptions::default()
+        };
+        assert!(push(&settings, temp_dir.path(), &state_dir, dry_run).is_err());
+
+        settings.enabled = true;
+        settings.endpoint = Some("https://metrics.example.com/ingest".to_string());
+        settings.batch_size = 2;
+        let report = push(&settings, temp_dir.path(), &state_dir, dry_run).unwrap();
+        assert_eq!(report.batches.len(), 2);
+        assert_eq!(report.sessions(), 3);
+        assert_eq!(report.batches[0].schema, PAYLOAD_SCHEMA);
+
+        // Sessions already sent with the same last activity are skipped
+        let mut state = PushState::default();
+        let summary = &report.batches[0].sessions[0];
+        state
+            .pushed
+            .insert(summary.session.clone(), summary.last_activity.clone());
+        state.save(&state_dir).unwrap();
+        let report = push(&settings, temp_dir.path(), &state_dir, dry_run).unwrap();
+        assert_eq!(report.sessions(), 2);
+    }
+
+    #[test]
+    fn test_push_due_and_endpoint_validation() {
+        let mut settings = AnalyticsSettings {
+            enabled: true,
+            ..AnalyticsSettings::default()
+        };
+        let now = Utc::now();
+        let mut state = PushState::default();
+        assert!(state.is_due(&settings, now));
+
+        state.last_attempt = Some((now - Duration::minutes(10)).to_rfc3339());
+        assert!(!state.is_due(&settings, now));
+        assert!(state.is_due(&settings, now + Duration::minutes(50)));
+        settings.interval_minutes = 0;
+        assert!(!state.is_due(&settings, now + Duration::days(1)));
+
+        assert!(validate_endpoint("https://metrics.example.com/v1").is_ok());
+        assert!(validate_endpoint("http://localhost:8080/ingest").is_ok());
+        assert!(validate_endpoint("http://metrics.example.com").is_err());
+        assert!(validate_endpoint("http://localhost.evil.com").is_err());
+        assert!(validate_endpoint("https://").is_err());
+    }
+}
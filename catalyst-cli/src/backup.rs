@@ -0,0 +1,248 @@
+//! Backup and restore of installation files
+//!
+//! Operations that overwrite files in an existing installation (such as
+//! `catalyst update`) snapshot each path into a backup set under
+//! `.catalyst-backups/` before touching it, so the change can be rolled back
+//! if the operation is interrupted or fails.
+//!
+//! Each path is copied into a staging location first and renamed into the
+//! backup set, so a backup either exists completely or not at all.
+
+use crate::signals;
+use crate::types::{CatalystError, Result, BACKUP_DIR};
+use catalyst_core::fs_ops::Fs;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Create a new, empty backup set
+///
+/// # Arguments
+///
+/// * `target_dir` - Project directory being modified
+/// * `label` - Short operation name used in the directory name (e.g., "update")
+///
+/// # Returns
+///
+/// Returns the path of the backup set directory
+pub fn create_backup_set(target_dir: &Path, label: &str) -> Result<PathBuf> {
+    let timestamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ");
+    let backup_dir = target_dir
+        .join(BACKUP_DIR)
+        .join(format!("{}-{}", label, timestamp));
+
+    fs::create_dir_all(&backup_dir).map_err(|e| CatalystError::DirectoryCreationFailed {
+        path: backup_dir.clone(),
+        source: e,
+    })?;
+
+    Ok(backup_dir)
+}
+
+/// Back up a file or directory into a backup set
+///
+/// # Arguments
+///
+/// * `target_dir` - Project directory being modified
+/// * `backup_dir` - Backup set created by [`create_backup_set`]
+/// * `relative` - Path to back up, relative to `target_dir`
+///
+/// # Returns
+///
+/// Returns `Ok(true)` if the path existed and was backed up, `Ok(false)` if
+/// there was nothing to back up.
+pub fn backup_path(target_dir: &Path, backup_dir: &Path, relative: &Path) -> Result<bool> {
+    let source = target_dir.join(relative);
+    if fs::symlink_metadata(&source).is_err() {
+        return Ok(false);
+    }
+
+    let destination = backup_dir.join(relative);
+    let staging = staging_path(&destination);
+
+    // Clear leftovers from a previous interrupted attempt
+    remove_path(&staging)?;
+    let _cleanup = signals::CleanupGuard::new(&staging);
+    copy_recursive(&source, &staging)?;
+
+    remove_path(&destination)?;
+    Fs::read_write()
+        .rename(&staging, &destination)
+        .map_err(|e| CatalystError::FileWriteFailed {
+            path: destination.clone(),
+            source: e,
+        })?;
+
+    Ok(true)
+}
+
+/// Restore a path from a backup set
+///
+/// If `existed` is false the path did not exist before the operation, so it
+/// is removed instead of restored.
+///
+/// # Arguments
+///
+/// * `target_dir` - Project directory being restored
+/// * `backup_dir` - Backup set the path was saved into
+/// * `relative` - Path to restore, relative to `target_dir`
+/// * `existed` - Whether the path existed when it was backed up
+pub fn restore_path(
+    target_dir: &Path,
+    backup_dir: &Path,
+    relative: &Path,
+    existed: bool,
+) -> Result<()> {
+    let target = target_dir.join(relative);
+    remove_path(&target)?;
+
+    if existed {
+        let source = backup_dir.join(relative);
+        if fs::symlink_metadata(&source).is_err() {
+            return Err(CatalystError::PathNotFound(source));
+        }
+        copy_recursive(&source, &target)?;
+    }
+
+    Ok(())
+}
+
+/// Delete a backup set and, if it was the last one, the backup root
+pub fn remove_backup_set(backup_dir: &Path) -> Result<()> {
+    remove_path(backup_dir)?;
+
+    if let Some(root) = backup_dir.parent() {
+        // Only succeeds when empty, which is exactly what we want
+        let _ = fs::remove_dir(root);
+    }
+
+    Ok(())
+}
+
+/// Staging path used while a backup is being written
+fn staging_path(destination: &Path) -> PathBuf {
+    let mut name = destination
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_default();
+    name.push(".partial");
+    destination.with_file_name(name)
+}
+
+/// Remove a file or directory if it exists
+fn remove_path(path: &Path) -> Result<()> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(CatalystError::Io(e)),
+    };
+
+    if metadata.is_dir() {
+        fs::remove_dir_all(path).map_err(CatalystError::Io)
+    } else {
+        fs::remove_file(path).map_err(CatalystError::Io)
+    }
+}
+
+/// Copy a file or directory tree, preserving permissions
+fn copy_recursive(source: &Path, destination: &Path) -> Result<()> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|e| CatalystError::DirectoryCreationFailed {
+            path: parent.to_path_buf(),
+            source: e,
+        })?;
+    }
+
+    let metadata = fs::symlink_metadata(source).map_err(|e| CatalystError::FileReadFailed {
+        path: source.to_path_buf(),
+        source: e,
+    })?;
+
+    if metadata.file_type().is_symlink() {
+        // Keep links (e.g. skills linked from the skill library) as links
+        // rather than snapshotting what they point to
+        let target = fs::read_link(source).map_err(|e| CatalystError::FileReadFailed {
+            path: source.to_path_buf(),
+            source: e,
+        })?;
+        Fs::read_write()
+            .symlink(&target, destination)
+            .map_err(|e| CatalystError::FileWriteFailed {
+                path: destination.to_path_buf(),
+                source: e,
+            })?;
+    } else if metadata.is_dir() {
+        fs::create_dir_all(destination).map_err(|e| CatalystError::DirectoryCreationFailed {
+            path: destination.to_path_buf(),
+            source: e,
+        })?;
+
+        let entries = fs::read_dir(source).map_err(|e| CatalystError::FileReadFailed {
+            path: source.to_path_buf(),
+            source: e,
+        })?;
+        for entry in entries {
+            let entry = entry.map_err(CatalystError::Io)?;
+            copy_recursive(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+    } else {
+        // fs::copy preserves permission bits
+        fs::copy(source, destination).map_err(|e| CatalystError::FileWriteFailed {
+            path: destination.to_path_buf(),
+            source: e,
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backup_and_restore_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let skill = Path::new(".claude/skills/my-skill");
+        fs::create_dir_all(target.join(skill)).unwrap();
+        fs::write(target.join(skill).join("SKILL.md"), "original").unwrap();
+
+        let backup_dir = create_backup_set(target, "update").unwrap();
+        assert!(backup_path(target, &backup_dir, skill).unwrap());
+
+        // Simulate a partial overwrite
+        fs::write(target.join(skill).join("SKILL.md"), "half-written").unwrap();
+        fs::write(target.join(skill).join("extra.md"), "new").unwrap();
+
+        restore_path(target, &backup_dir, skill, true).unwrap();
+        assert_eq!(
+            fs::read_to_string(target.join(skill).join("SKILL.md")).unwrap(),
+            "original"
+        );
+        assert!(!target.join(skill).join("extra.md").exists());
+    }
+
+    #[test]
+    fn test_restore_removes_path_that_did_not_exist() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let file = Path::new(".catalyst-version");
+
+        let backup_dir = create_backup_set(target, "update").unwrap();
+        assert!(!backup_path(target, &backup_dir, file).unwrap());
+
+        fs::write(target.join(file), "0.1.0").unwrap();
+        restore_path(target, &backup_dir, file, false).unwrap();
+        assert!(!target.join(file).exists());
+    }
+
+    #[test]
+    fn test_remove_backup_set_cleans_empty_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let backup_dir = create_backup_set(temp_dir.path(), "update").unwrap();
+
+        remove_backup_set(&backup_dir).unwrap();
+        assert!(!temp_dir.path().join(BACKUP_DIR).exists());
+    }
+}
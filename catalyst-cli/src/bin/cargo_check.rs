@@ -1,18 +1,33 @@
-// Cargo check hook - automatically runs cargo check when editing Rust files
-use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+// Cargo check hook - automatically runs cargo check when editing Rust files,
+// and the language checkers from catalyst_cli::build_check for other files
+use catalyst_cli::build_check::{plan_checks, CheckHooksConfig, CheckOutcome};
+use catalyst_cli::cargo_check_config::{CargoCheckConfig, CargoCheckSettings};
+use catalyst_cli::cargo_diagnostics::Diagnostics;
+use catalyst_cli::check_log::{self, CheckRecord, CheckStatus};
+use catalyst_cli::hook_stats::{HookOutcome, HookTimer};
+use catalyst_cli::modification_store;
+use catalyst_cli::process::{self, WaitOutcome};
+use catalyst_cli::signals::INTERRUPTED_EXIT_CODE;
+use catalyst_core::hooks::{HookDecision, HookInput, HookResponse};
+use catalyst_core::settings::HookEvent;
+use clap::Parser;
+use std::collections::HashSet;
 use std::env;
 use std::fmt::Write as FmtWrite;
 use std::io::{self, BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use toml::Value;
 
 // Constants
-const DECISION_BLOCK: &str = "block";
 const MAX_OUTPUT_BYTES: usize = 50_000; // 50KB limit to prevent overwhelming Claude with massive error output
 
+/// Set by the SIGINT/SIGTERM handler so the running cargo command is killed
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
 #[derive(Error, Debug)]
 enum CargoCheckError {
     #[error("[CC001] Failed to read input from stdin")]
@@ -26,32 +41,19 @@ enum CargoCheckError {
 
     #[error("[CC004] Failed to execute cargo command: {0}")]
     CargoExecution(#[source] io::Error),
-}
 
-#[derive(Debug, Deserialize)]
-struct HookInput {
-    #[serde(rename = "session_id")]
-    _session_id: String,
-    tool_name: Option<String>,
-    tool_input: Option<HashMap<String, serde_json::Value>>,
-}
+    #[error(
+        "[CC005] Invalid check hooks configuration: {0}\nFix or remove .claude/check-hooks.toml"
+    )]
+    CheckHooksConfig(#[source] catalyst_cli::CatalystError),
 
-#[derive(Debug, Serialize)]
-struct HookSpecificOutput {
-    #[serde(rename = "hookEventName")]
-    hook_event_name: String,
-    #[serde(rename = "additionalContext")]
-    additional_context: String,
-}
+    #[error(
+        "[CC006] Invalid cargo check configuration: {0}\nFix or remove .claude/cargo-check.toml"
+    )]
+    CargoCheckConfig(#[source] catalyst_cli::CatalystError),
 
-#[derive(Debug, Serialize)]
-struct HookResponse {
-    decision: String,
-    reason: String,
-    #[serde(rename = "hookSpecificOutput")]
-    hook_specific_output: HookSpecificOutput,
-    #[serde(rename = "systemMessage", skip_serializing_if = "Option::is_none")]
-    system_message: Option<String>,
+    #[error("[CC007] Interrupted while running cargo {0}")]
+    Cancelled(String),
 }
 
 #[derive(Debug)]
@@ -59,6 +61,8 @@ struct CommandResult {
     success: bool,
     output: String,
     exit_code: i32,
+    /// The deadline passed and the command was killed or never started
+    timed_out: bool,
 }
 
 #[derive(Debug)]
@@ -82,6 +86,24 @@ impl CargoRoot {
     }
 }
 
+/// What a cargo command runs on: a root, narrowed to some workspace members
+/// when the edit only affects those
+struct CheckScope<'a> {
+    root: &'a CargoRoot,
+    /// Empty for the whole workspace (or a lone package)
+    packages: &'a [String],
+}
+
+impl CheckScope<'_> {
+    fn describe(&self) -> String {
+        if self.packages.is_empty() {
+            self.root.kind().to_string()
+        } else {
+            format!("{} ({})", self.root.kind(), self.packages.join(", "))
+        }
+    }
+}
+
 /// Checks if an environment variable is set to a truthy value
 /// Accepts: "1", "true", "yes", "on" (case-insensitive)
 fn env_is_enabled(var: &str) -> bool {
@@ -199,22 +221,37 @@ fn find_cargo_root(file_path: &Path) -> Result<CargoRoot, CargoCheckError> {
 
 /// Runs a cargo command and captures output
 fn run_cargo_command(
-    cargo_root: &CargoRoot,
+    scope: &CheckScope,
+    settings: &CargoCheckSettings,
+    deadline: Option<Instant>,
     command: &str,
     args: &[&str],
     emoji: &str,
     success_msg: &str,
 ) -> Result<CommandResult, CargoCheckError> {
-    let quiet = env_is_enabled("CARGO_CHECK_QUIET");
+    let quiet = settings.quiet;
     let mut output_buffer = String::new();
 
+    // Earlier commands used up the time budget
+    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+        if !quiet {
+            let _ = writeln!(output_buffer, "⏭️  Cargo {} skipped: out of time", command);
+        }
+        return Ok(CommandResult {
+            success: true,
+            output: output_buffer,
+            exit_code: 0,
+            timed_out: true,
+        });
+    }
+
     if !quiet {
         writeln!(
             output_buffer,
             "{} Running {} on {}...",
             emoji,
             command,
-            cargo_root.kind()
+            scope.describe()
         )
         .unwrap();
     }
@@ -225,13 +262,22 @@ fn run_cargo_command(
     let mut cmd = Command::new("cargo");
     cmd.arg(command);
 
-    // Add workspace/all flag for workspace roots BEFORE other args
+    // Add package selection for workspace roots BEFORE other args: the
+    // affected packages when known, otherwise the whole workspace
     // Note: cargo fmt uses --all instead of --workspace
-    if matches!(cargo_root, CargoRoot::Workspace(_)) {
-        if command == "fmt" {
+    if matches!(scope.root, CargoRoot::Workspace(_)) {
+        if !scope.packages.is_empty() {
+            for package in scope.packages {
+                cmd.arg("-p").arg(package);
+            }
+        } else if command == "fmt" {
             cmd.arg("--all");
         } else {
             cmd.arg("--workspace");
+            // --exclude only applies together with --workspace
+            for package in &settings.exclude {
+                cmd.arg("--exclude").arg(package);
+            }
         }
     }
 
@@ -241,13 +287,26 @@ fn run_cargo_command(
         cmd.arg("-q");
     }
 
+    // Compiler diagnostics come back as JSON and are summarized; rustfmt
+    // has no such format, so its diff is passed through as is
+    let structured = command != "fmt";
+    if structured {
+        cmd.arg("--message-format=json");
+    }
+
+    // Configured extra args go before the command's own args, which may
+    // start with "--"; rustfmt doesn't accept cargo build flags
+    if command != "fmt" {
+        cmd.args(&settings.extra_args);
+    }
+
     // Add additional args
     for arg in args {
         cmd.arg(arg);
     }
 
     // Set working directory
-    cmd.current_dir(cargo_root.path());
+    cmd.current_dir(scope.root.path());
 
     // Capture stdout and stderr
     cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
@@ -274,7 +333,9 @@ fn run_cargo_command(
         reader
             .lines()
             .map_while(Result::ok)
-            .filter(|line| !quiet || line.contains("error") || line.contains("warning"))
+            .filter(|line| {
+                structured || !quiet || line.contains("error") || line.contains("warning")
+            })
             .collect::<Vec<_>>()
     });
 
@@ -283,6 +344,32 @@ fn run_cargo_command(
         reader.lines().map_while(Result::ok).collect::<Vec<_>>()
     });
 
+    // Wait for the command to complete, killing it once the time budget
+    // runs out or the hook is interrupted
+    let status = match process::wait_until(&mut child, deadline, &CANCELLED)
+        .map_err(CargoCheckError::CargoExecution)?
+    {
+        WaitOutcome::Exited(status) => status,
+        WaitOutcome::Cancelled => return Err(CargoCheckError::Cancelled(command.to_string())),
+        WaitOutcome::TimedOut => {
+            // rustc processes cargo started may still hold the pipes open, so
+            // the reader threads are left to finish on their own
+            eprintln!(
+                "Warning: cargo {} did not finish in time and was stopped",
+                command
+            );
+            if !quiet {
+                let _ = writeln!(output_buffer, "⏱️  Cargo {} stopped: out of time", command);
+            }
+            return Ok(CommandResult {
+                success: true,
+                output: output_buffer,
+                exit_code: 0,
+                timed_out: true,
+            });
+        }
+    };
+
     // Join threads and collect output
     // Use unwrap_or_else to gracefully handle thread panics
     let stdout_lines = stdout_thread.join().unwrap_or_else(|_| {
@@ -294,18 +381,32 @@ fn run_cargo_command(
         Vec::new()
     });
 
-    // Add stdout lines to output buffer
-    for line in stdout_lines {
-        let _ = writeln!(output_buffer, "{}", line);
-    }
+    if structured {
+        let diagnostics = Diagnostics::parse(&stdout_lines.join("\n"));
+        if diagnostics.is_empty() {
+            // Cargo failed before rustc reported anything (bad manifest,
+            // failing build script): its own messages are all there is
+            if !status.success() {
+                for line in stderr_lines {
+                    let _ = writeln!(output_buffer, "{}", line);
+                }
+            }
+        } else if !quiet || !diagnostics.errors.is_empty() {
+            // Quiet mode still counts warnings but only lists errors
+            output_buffer.push_str(&diagnostics.summary(settings.max_diagnostics, !quiet));
+        }
+    } else {
+        // Add stdout lines to output buffer
+        for line in stdout_lines {
+            let _ = writeln!(output_buffer, "{}", line);
+        }
 
-    // Add stderr lines to output buffer (always included, even in quiet mode)
-    for line in stderr_lines {
-        let _ = writeln!(output_buffer, "{}", line);
+        // Add stderr lines to output buffer (always included, even in quiet mode)
+        for line in stderr_lines {
+            let _ = writeln!(output_buffer, "{}", line);
+        }
     }
 
-    // Wait for the command to complete
-    let status = child.wait().map_err(CargoCheckError::CargoExecution)?;
     // Exit code 101 is used by cargo for compilation errors
     // If status.code() is None (e.g., terminated by signal on Unix), use 101 as fallback
     let exit_code = status.code().unwrap_or_else(|| {
@@ -328,6 +429,7 @@ fn run_cargo_command(
             success: false,
             output: output_buffer,
             exit_code,
+            timed_out: false,
         });
     }
 
@@ -339,35 +441,161 @@ fn run_cargo_command(
         success: true,
         output: output_buffer,
         exit_code: 0,
+        timed_out: false,
     })
 }
 
+/// Asks `cargo metadata` which workspace packages the edited files affect
+/// Returns None, meaning "check the whole workspace", if the metadata isn't
+/// available or the files can't be mapped to packages
+fn targeted_packages(workspace_root: &Path, files: &[PathBuf]) -> Option<Vec<String>> {
+    let debug = env_is_enabled("CARGO_CHECK_DEBUG");
+    let output = Command::new("cargo")
+        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .current_dir(workspace_root)
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success());
+    let packages = output
+        .and_then(|output| serde_json::from_slice(&output.stdout).ok())
+        .and_then(|metadata: serde_json::Value| affected_packages(&metadata, files));
+
+    if debug {
+        match &packages {
+            Some(packages) => eprintln!("[DEBUG] Checking packages: {}", packages.join(", ")),
+            None => eprintln!("[DEBUG] Could not map edited files to packages, checking workspace"),
+        }
+    }
+    packages
+}
+
+/// Maps edited files to the workspace members that own them, plus every
+/// member depending on one of those through a path dependency
+/// Returns None if a file is outside every member or two members share a
+/// name (`-p` couldn't tell them apart)
+fn affected_packages(metadata: &serde_json::Value, files: &[PathBuf]) -> Option<Vec<String>> {
+    struct Member {
+        name: String,
+        dir: PathBuf,
+        path_deps: Vec<PathBuf>,
+    }
+
+    let members = metadata
+        .get("packages")?
+        .as_array()?
+        .iter()
+        .map(|package| {
+            let manifest = Path::new(package.get("manifest_path")?.as_str()?);
+            let path_deps = package
+                .get("dependencies")
+                .and_then(|deps| deps.as_array())
+                .map(|deps| {
+                    deps.iter()
+                        .filter_map(|dep| dep.get("path")?.as_str())
+                        .map(|path| canonical(Path::new(path)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(Member {
+                name: package.get("name")?.as_str()?.to_string(),
+                dir: canonical(manifest.parent()?),
+                path_deps,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut names = HashSet::new();
+    if !members
+        .iter()
+        .all(|member| names.insert(member.name.as_str()))
+    {
+        return None;
+    }
+
+    // The innermost member containing a file owns it
+    let mut affected = vec![false; members.len()];
+    for file in files {
+        let file = canonical(file);
+        let (owner, _) = members
+            .iter()
+            .enumerate()
+            .filter(|(_, member)| file.starts_with(&member.dir))
+            .max_by_key(|(_, member)| member.dir.components().count())?;
+        affected[owner] = true;
+    }
+
+    // Members that depend on an affected member may break too
+    while let Some(dependent) = (0..members.len()).find(|&i| {
+        !affected[i]
+            && members[i].path_deps.iter().any(|dep| {
+                members
+                    .iter()
+                    .zip(&affected)
+                    .any(|(member, &hit)| hit && member.dir == *dep)
+            })
+    }) {
+        affected[dependent] = true;
+    }
+
+    Some(
+        members
+            .into_iter()
+            .zip(affected)
+            .filter(|(_, hit)| *hit)
+            .map(|(member, _)| member.name)
+            .collect(),
+    )
+}
+
+/// Resolves symlinks so paths from cargo and from the hook compare equal
+fn canonical(path: &Path) -> PathBuf {
+    dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
 /// Runs cargo check and optional additional checks
 /// Returns accumulated output and whether all checks passed
-fn run_all_checks(cargo_root: &CargoRoot) -> Result<CommandResult, CargoCheckError> {
+fn run_all_checks(
+    scope: &CheckScope,
+    settings: &CargoCheckSettings,
+    deadline: Option<Instant>,
+) -> Result<CommandResult, CargoCheckError> {
     let mut accumulated_output = String::new();
     let mut all_success = true;
+    let mut timed_out = false;
     // Track exit code of first failure (if any) for error reporting
     let mut first_failure_exit_code = 0;
 
     // Always run cargo check
-    let result = run_cargo_command(cargo_root, "check", &[], "🦀", "✅ Cargo check passed")?;
+    let result = run_cargo_command(
+        scope,
+        settings,
+        deadline,
+        "check",
+        &[],
+        "🦀",
+        "✅ Cargo check passed",
+    )?;
     accumulated_output.push_str(&result.output);
+    timed_out |= result.timed_out;
     if !result.success {
         all_success = false;
         first_failure_exit_code = result.exit_code;
     }
 
-    // Optional: Run clippy if CARGO_CHECK_CLIPPY is enabled
-    if env_is_enabled("CARGO_CHECK_CLIPPY") {
+    // Optional: Run clippy if enabled in cargo-check.toml or CARGO_CHECK_CLIPPY
+    if settings.clippy {
         let result = run_cargo_command(
-            cargo_root,
+            scope,
+            settings,
+            deadline,
             "clippy",
             &["--", "-D", "warnings"],
             "📎",
             "✅ Clippy passed",
         )?;
         accumulated_output.push_str(&result.output);
+        timed_out |= result.timed_out;
         if !result.success {
             all_success = false;
             // Only set if not already set (preserve first failure)
@@ -377,16 +605,19 @@ fn run_all_checks(cargo_root: &CargoRoot) -> Result<CommandResult, CargoCheckErr
         }
     }
 
-    // Optional: Run tests (check only, don't execute) if CARGO_CHECK_TESTS is enabled
-    if env_is_enabled("CARGO_CHECK_TESTS") {
+    // Optional: Run tests (check only, don't execute) if enabled in cargo-check.toml or CARGO_CHECK_TESTS
+    if settings.tests {
         let result = run_cargo_command(
-            cargo_root,
+            scope,
+            settings,
+            deadline,
             "test",
             &["--no-run"],
             "🧪",
             "✅ Test compilation passed",
         )?;
         accumulated_output.push_str(&result.output);
+        timed_out |= result.timed_out;
         if !result.success {
             all_success = false;
             // Only set if not already set (preserve first failure)
@@ -396,16 +627,19 @@ fn run_all_checks(cargo_root: &CargoRoot) -> Result<CommandResult, CargoCheckErr
         }
     }
 
-    // Optional: Check formatting if CARGO_CHECK_FMT is enabled
-    if env_is_enabled("CARGO_CHECK_FMT") {
+    // Optional: Check formatting if enabled in cargo-check.toml or CARGO_CHECK_FMT
+    if settings.fmt {
         let result = run_cargo_command(
-            cargo_root,
+            scope,
+            settings,
+            deadline,
             "fmt",
             &["--", "--check"],
             "📝",
             "✅ Formatting check passed",
         )?;
         accumulated_output.push_str(&result.output);
+        timed_out |= result.timed_out;
         if !result.success {
             all_success = false;
             // Only set if not already set (preserve first failure)
@@ -419,6 +653,7 @@ fn run_all_checks(cargo_root: &CargoRoot) -> Result<CommandResult, CargoCheckErr
         success: all_success,
         output: accumulated_output,
         exit_code: first_failure_exit_code,
+        timed_out,
     })
 }
 
@@ -432,120 +667,312 @@ fn run() -> Result<Option<HookResponse>, CargoCheckError> {
         serde_json::from_str(&buffer).map_err(CargoCheckError::InvalidHookInput)?;
 
     // Check if this is a relevant tool (Edit, Write, MultiEdit)
-    let tool_name = match input.tool_name {
+    let tool_name = match input.tool_name.as_deref() {
         Some(name) => name,
         None => return Ok(None), // No tool name, skip
     };
 
-    if !matches!(tool_name.as_str(), "Edit" | "Write" | "MultiEdit") {
+    if !matches!(tool_name, "Edit" | "Write" | "MultiEdit") {
         return Ok(None); // Not a file editing tool, skip
     }
 
     // Extract tool_input
-    let tool_input = match input.tool_input {
+    let tool_input = match &input.tool_input {
         Some(input) => input,
         None => return Ok(None), // No tool input, skip
     };
 
-    // Collect all Rust file paths from the tool input
-    let mut rust_files = Vec::new();
+    // Collect all edited file paths from the tool input
+    let mut edited_files = Vec::new();
 
     // Handle MultiEdit tool - has edits array
     if tool_name == "MultiEdit" {
-        if let Some(edits_value) = tool_input.get("edits") {
-            if let Some(edits_array) = edits_value.as_array() {
-                for edit in edits_array {
-                    if let Some(file_path) = edit.get("file_path").and_then(|v| v.as_str()) {
-                        if file_path.ends_with(".rs") {
-                            rust_files.push(PathBuf::from(file_path));
-                        }
-                    }
+        if let Some(edits_array) = tool_input.get("edits").and_then(|v| v.as_array()) {
+            for edit in edits_array {
+                if let Some(file_path) = edit.get("file_path").and_then(|v| v.as_str()) {
+                    edited_files.push(PathBuf::from(file_path));
                 }
             }
         }
-    } else {
+    } else if let Some(file_path) = tool_input.get("file_path").and_then(|v| v.as_str()) {
         // Handle Edit and Write tools - has file_path
-        if let Some(file_path) = tool_input.get("file_path").and_then(|v| v.as_str()) {
-            if file_path.ends_with(".rs") {
-                rust_files.push(PathBuf::from(file_path));
-            }
-        }
+        edited_files.push(PathBuf::from(file_path));
     }
 
-    // If no Rust files, skip
-    if rust_files.is_empty() {
+    let (rust_files, other_files): (Vec<PathBuf>, Vec<PathBuf>) = edited_files
+        .into_iter()
+        .partition(|path| path.extension().is_some_and(|ext| ext == "rs"));
+
+    // If no files any checker handles, skip
+    if rust_files.is_empty() && other_files.is_empty() {
         return Ok(None);
     }
 
-    // Find all cargo roots and deduplicate
-    let mut processed_roots = HashSet::new();
-    let mut accumulated_output = String::new();
-    let mut any_failed = false;
+    // Per-project settings, falling back to CARGO_CHECK_* variables
+    let project_dir = input.project_dir();
+    let settings = CargoCheckConfig::load(&project_dir)
+        .map_err(CargoCheckError::CargoCheckConfig)?
+        .resolve(env_is_enabled);
 
+    // One time budget covers every cargo command in this run
+    let started = Instant::now();
+    let deadline = settings.timeout.map(|timeout| started + timeout);
+
+    // Group the edited files by cargo root, keeping the order they came in
+    let mut roots: Vec<(CargoRoot, Vec<PathBuf>)> = Vec::new();
     for file_path in rust_files {
         let cargo_root = find_cargo_root(&file_path)?;
-        let root_path = cargo_root.path().to_path_buf();
+        match roots
+            .iter_mut()
+            .find(|(root, _)| root.path() == cargo_root.path())
+        {
+            Some((_, files)) => files.push(file_path),
+            None => roots.push((cargo_root, vec![file_path])),
+        }
+    }
 
-        // Only run checks if we haven't processed this root yet
-        if processed_roots.insert(root_path) {
-            let result = run_all_checks(&cargo_root)?;
-            accumulated_output.push_str(&result.output);
+    let mut accumulated_output = String::new();
+    let mut any_failed = false;
+    let mut timed_out = false;
 
-            if !result.success {
-                any_failed = true;
+    for (cargo_root, files) in &roots {
+        // An empty list means the whole workspace (or a lone package)
+        let packages = match cargo_root {
+            CargoRoot::Workspace(root) if settings.targeted => {
+                targeted_packages(root, files).unwrap_or_default()
             }
+            _ => Vec::new(),
+        };
+
+        let scope = CheckScope {
+            root: cargo_root,
+            packages: &packages,
+        };
+        let result = run_all_checks(&scope, &settings, deadline)?;
+        accumulated_output.push_str(&result.output);
+        timed_out |= result.timed_out;
+
+        if !result.success {
+            any_failed = true;
         }
     }
 
+    if !other_files.is_empty() {
+        let result = run_language_checks(&project_dir, &other_files, settings.quiet)?;
+        accumulated_output.push_str(&result.output);
+        if !result.success {
+            any_failed = true;
+        }
+    }
+
+    let status = if any_failed {
+        CheckStatus::Failed
+    } else if timed_out {
+        CheckStatus::Incomplete
+    } else {
+        CheckStatus::Passed
+    };
+    record_run(
+        input.session_id.as_deref(),
+        &project_dir,
+        &describe_checks(&settings, !roots.is_empty(), !other_files.is_empty()),
+        status,
+    );
+
+    let elapsed = started.elapsed();
+    if timed_out || any_failed {
+        let _ = writeln!(accumulated_output);
+        let _ = writeln!(
+            accumulated_output,
+            "⏱️  Build checks took {}",
+            format_elapsed(elapsed)
+        );
+    }
+
     // If any checks failed, return a block response
     if any_failed {
-        Ok(Some(HookResponse {
-            decision: DECISION_BLOCK.to_string(),
-            reason: "Rust compilation checks failed - code contains errors that must be fixed before proceeding".to_string(),
-            hook_specific_output: HookSpecificOutput {
-                hook_event_name: "PostToolUse".to_string(),
-                additional_context: truncate_output(accumulated_output),
-            },
-            system_message: Some("Cargo check found compilation errors - see details below".to_string()),
-        }))
+        Ok(Some(
+            HookResponse::builder(HookEvent::PostToolUse)
+                .decision(HookDecision::Block)
+                .reason(
+                    "Build checks failed - code contains errors that must be fixed before proceeding",
+                )
+                .additional_context(truncate_output(accumulated_output))
+                .system_message("Build checks found errors - see details below")
+                .build(),
+        ))
+    } else if timed_out {
+        // Nothing failed, but the checks didn't finish: warn without blocking
+        let seconds = settings.timeout.map(|t| t.as_secs()).unwrap_or_default();
+        Ok(Some(
+            HookResponse::builder(HookEvent::PostToolUse)
+                .decision(HookDecision::Suggest)
+                .additional_context(truncate_output(accumulated_output))
+                .system_message(format!(
+                    "Build checks did not finish within {} seconds and were stopped - results are incomplete",
+                    seconds
+                ))
+                .build(),
+        ))
     } else {
         // All checks passed - no need to output anything
         Ok(None)
     }
 }
 
+/// What a run checked, for the check log: "cargo check + clippy", "language checks"
+fn describe_checks(settings: &CargoCheckSettings, rust: bool, other: bool) -> String {
+    let mut parts = Vec::new();
+    if rust {
+        let mut cargo = String::from("cargo check");
+        for (enabled, name) in [
+            (settings.clippy, "clippy"),
+            (settings.tests, "test"),
+            (settings.fmt, "fmt"),
+        ] {
+            if enabled {
+                cargo.push_str(" + ");
+                cargo.push_str(name);
+            }
+        }
+        parts.push(cargo);
+    }
+    if other {
+        parts.push("language checks".to_string());
+    }
+    parts.join(", ")
+}
+
+/// Add the run to the check log for `catalyst report`; a failure to write
+/// it never affects the hook
+fn record_run(session_id: Option<&str>, project_dir: &Path, command: &str, status: CheckStatus) {
+    let record = CheckRecord::now(session_id, project_dir, "cargo-check", command, status);
+    if let Err(e) = modification_store::state_dir().and_then(|dir| check_log::append(&dir, &record))
+    {
+        eprintln!("Warning: could not record check run: {}", e);
+    }
+}
+
+/// Formats a duration as seconds with one decimal place
+fn format_elapsed(elapsed: Duration) -> String {
+    format!("{:.1}s", elapsed.as_secs_f64())
+}
+
+/// Runs the configured language checkers for non-Rust files
+/// Checkers that aren't installed or time out are reported on stderr and
+/// never block
+fn run_language_checks(
+    project_dir: &Path,
+    files: &[PathBuf],
+    quiet: bool,
+) -> Result<CommandResult, CargoCheckError> {
+    let debug = env_is_enabled("CARGO_CHECK_DEBUG");
+    let config = CheckHooksConfig::load(project_dir).map_err(CargoCheckError::CheckHooksConfig)?;
+
+    let mut output_buffer = String::new();
+    let mut success = true;
+    let mut first_failure_exit_code = 0;
+
+    for check in plan_checks(&config.checkers(), files) {
+        let name = &check.checker.name;
+        if !quiet {
+            let _ = writeln!(
+                output_buffer,
+                "🔎 Running {} in {}...",
+                name,
+                check.root.display()
+            );
+        }
+        match check.run() {
+            CheckOutcome::Passed => {
+                if !quiet {
+                    let _ = writeln!(output_buffer, "✅ {} passed", name);
+                }
+            }
+            CheckOutcome::Failed { exit_code, output } => {
+                let exit_code = exit_code.unwrap_or(1);
+                let _ = write!(output_buffer, "{}", output);
+                if !output.is_empty() && !output.ends_with('\n') {
+                    let _ = writeln!(output_buffer);
+                }
+                let _ = writeln!(output_buffer);
+                let _ = writeln!(output_buffer, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+                let _ = writeln!(
+                    output_buffer,
+                    "❌ {} failed with exit code {}",
+                    name, exit_code
+                );
+                let _ = writeln!(output_buffer, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+                if success {
+                    first_failure_exit_code = exit_code;
+                }
+                success = false;
+            }
+            CheckOutcome::Skipped { reason } => {
+                if debug {
+                    eprintln!("[DEBUG] Skipped {}: {}", name, reason);
+                }
+                if !quiet {
+                    let _ = writeln!(output_buffer, "⏭️  {} skipped: {}", name, reason);
+                }
+            }
+        }
+    }
+
+    Ok(CommandResult {
+        success,
+        output: output_buffer,
+        exit_code: first_failure_exit_code,
+        timed_out: false,
+    })
+}
+
+/// Checks the project after an edit (PostToolUse hook)
+#[derive(Parser)]
+#[command(name = "cargo-check")]
+#[command(about = "Runs build checks on the file edited in the hook payload read from stdin", long_about = None)]
+#[command(version)]
+struct Args {}
+
 fn main() {
-    match run() {
+    Args::parse();
+    // Claude Code stops a hook with SIGTERM; kill cargo rather than leave it
+    // running after the hook exits
+    if let Err(e) = ctrlc::set_handler(|| CANCELLED.store(true, Ordering::SeqCst)) {
+        eprintln!("Warning: failed to install signal handler: {}", e);
+    }
+
+    let timer = HookTimer::start("cargo-check");
+    let result = run();
+    match &result {
+        Ok(response) => timer.finish(HookOutcome::of(response.as_ref()), None),
+        Err(e) => timer.finish(HookOutcome::Error, Some(e.to_string())),
+    }
+
+    match result {
         Ok(Some(response)) => {
-            // Output JSON response to stdout
-            // Serialization should never fail for our simple types - if it does, it's a bug
-            let json = serde_json::to_string_pretty(&response)
-                .expect("Failed to serialize hook response - this is a bug");
-            println!("{}", json);
-            // Exit with 0 - the JSON decision field indicates the block
-            std::process::exit(0);
+            // The JSON decision field indicates the block
+            std::process::exit(response.emit());
         }
         Ok(None) => {
             // Success, no output needed
             std::process::exit(0);
         }
+        Err(CargoCheckError::Cancelled(command)) => {
+            eprintln!("Build check hook interrupted; stopped cargo {}", command);
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
         Err(e) => {
             // Hook execution error (not cargo failure) - output as block with error
-            let response = HookResponse {
-                decision: DECISION_BLOCK.to_string(),
-                reason: format!("Cargo check hook error: {}", e),
-                hook_specific_output: HookSpecificOutput {
-                    hook_event_name: "PostToolUse".to_string(),
-                    additional_context: "The cargo check hook encountered an internal error. Please check your Rust project configuration.".to_string(),
-                },
-                system_message: Some("Cargo check hook encountered an error".to_string()),
-            };
-
-            // Serialization should never fail for our simple types - if it does, it's a bug
-            let json = serde_json::to_string_pretty(&response)
-                .expect("Failed to serialize error response - this is a bug");
-            println!("{}", json);
-            std::process::exit(0);
+            let response = HookResponse::builder(HookEvent::PostToolUse)
+                .decision(HookDecision::Block)
+                .reason(format!("Build check hook error: {}", e))
+                .additional_context(
+                    "The build check hook encountered an internal error. Please check your project configuration.",
+                )
+                .system_message("Build check hook encountered an error")
+                .build();
+            std::process::exit(response.emit());
         }
     }
 }
@@ -796,7 +1223,7 @@ version = "0.1.0"
         // The path should be "." not empty string
         let path = cargo_root.path();
         assert!(!path.as_os_str().is_empty(), "Path should not be empty");
-        assert!(path == PathBuf::from(".") || path.is_absolute());
+        assert!(path == "." || path.is_absolute());
     }
 
     #[test]
@@ -875,4 +1302,88 @@ version = "0.1.0"
         // Should find 2 Rust files (main.rs and lib.rs) but not README.md
         assert_eq!(rust_file_count, 2);
     }
+
+    #[test]
+    fn test_expired_deadline_skips_cargo_without_blocking() {
+        let temp_dir = TempDir::new().unwrap();
+        let cargo_root = CargoRoot::Package(temp_dir.path().to_path_buf());
+        let settings = CargoCheckSettings {
+            clippy: true,
+            ..CargoCheckSettings::default()
+        };
+
+        // No cargo process is started once the budget is spent
+        let scope = CheckScope {
+            root: &cargo_root,
+            packages: &[],
+        };
+        let result = run_all_checks(&scope, &settings, Some(Instant::now())).unwrap();
+        assert!(result.success);
+        assert!(result.timed_out);
+        assert!(result.output.contains("Cargo check skipped"));
+        assert!(result.output.contains("Cargo clippy skipped"));
+
+        // A warning-only response leaves out decision and reason
+        let response = HookResponse::builder(HookEvent::PostToolUse)
+            .decision(HookDecision::Suggest)
+            .additional_context(format!(
+                "⏱️  Build checks took {}",
+                format_elapsed(Duration::from_millis(1250))
+            ))
+            .system_message("timed out")
+            .build();
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("decision").is_none());
+        assert!(json["hookSpecificOutput"]["additionalContext"]
+            .as_str()
+            .unwrap()
+            .ends_with("took 1.2s"));
+    }
+
+    #[test]
+    fn test_affected_packages_from_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in ["core/src", "cli/src", "tools/gen/src"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let member = |name: &str, dir: &str, deps: &[&str]| {
+            serde_json::json!({
+                "name": name,
+                "manifest_path": root.join(dir).join("Cargo.toml"),
+                "dependencies": deps
+                    .iter()
+                    .map(|dep| serde_json::json!({ "name": dep, "path": root.join(dep) }))
+                    .collect::<Vec<_>>(),
+            })
+        };
+        let metadata = serde_json::json!({
+            "packages": [
+                member("core", "core", &[]),
+                member("cli", "cli", &["core"]),
+                member("gen", "tools/gen", &[]),
+            ]
+        });
+
+        // Editing a library also checks the members that depend on it
+        assert_eq!(
+            affected_packages(&metadata, &[root.join("core/src/lib.rs")]),
+            Some(vec!["core".to_string(), "cli".to_string()])
+        );
+        assert_eq!(
+            affected_packages(&metadata, &[root.join("tools/gen/src/main.rs")]),
+            Some(vec!["gen".to_string()])
+        );
+
+        // A file outside every member falls back to the whole workspace
+        assert_eq!(affected_packages(&metadata, &[root.join("build.rs")]), None);
+
+        let duplicate = serde_json::json!({
+            "packages": [member("core", "core", &[]), member("core", "cli", &[])]
+        });
+        assert_eq!(
+            affected_packages(&duplicate, &[root.join("core/src/lib.rs")]),
+            None
+        );
+    }
 }
@@ -6,8 +6,22 @@
 //!
 //! - `init` - Initialize a Claude Code project with hooks and skills
 //! - `status` - Validate installation and report issues
+//! - `doctor` - Walk through status issues and fix them interactively
+//! - `fix` - Fix the issues with one ID, for scripts and provisioning
 //! - `update` - Update hooks and skills to latest version
+//! - `install-binaries` - Install the hook binaries into ~/.claude-hooks/bin
+//! - `self-update` - Update the catalyst executable to the latest release
+//! - `install-manifest` - Print release URLs and hashes for pinned installs
 //! - `settings` - Manage settings.json files (legacy commands)
+//! - `config` - Get and set values in catalyst.toml
+//! - `env` - Print the effective configuration, paths, and platform
+//! - `flags` - List experimental feature flags
+//! - `skills` - Scaffold new skills and lint skill-rules.json
+//! - `sessions` - Report on or export sessions recorded by the SQLite tracker
+//! - `devcontainer` - Generate dev container setup that installs Catalyst
+//! - `export-state` - Bundle catalyst's project (and user) state into an archive
+//! - `import-state` - Restore state from an archive on a new machine
+//! - `explain-issue` - Explain a status issue in depth by its ID
 //!
 //! # Examples
 //!
@@ -18,38 +32,168 @@
 //! # Initialize with interactive mode
 //! catalyst init --interactive
 //!
+//! # Provision in CI, failing if any skill can't be installed
+//! catalyst init --all --strict-skills
+//!
 //! # Check status of current installation
 //! catalyst status
 //!
 //! # Auto-fix common issues
 //! catalyst status --fix
 //!
+//! # Review and fix issues one at a time
+//! catalyst doctor
+//!
+//! # Run the hook wrappers with the minimal PATH Claude Code gives them
+//! catalyst doctor --hook-env
+//!
+//! # Recreate missing hook wrappers and nothing else
+//! catalyst fix CAT004
+//!
 //! # Update to latest version
 //! catalyst update
+//!
+//! # Update and move renamed skills to their new names without asking
+//! catalyst update --migrate-skills
+//!
+//! # Build the hook binaries from a checkout and install them
+//! catalyst install-binaries --build ./catalyst --sqlite
+//!
+//! # See whether a newer catalyst release is out
+//! catalyst self-update --check
+//!
+//! # Pin this catalyst release in a Nix flake
+//! catalyst install-manifest --format nix > catalyst.nix
+//!
+//! # Show everything catalyst resolved (for bug reports)
+//! catalyst env --json
+//!
+//! # See which phases of a command are slow
+//! catalyst status --timings
+//!
+//! # Set up a Rust project: rust-developer skill, hooks, cargo permissions
+//! catalyst init --profile rust
+//!
+//! # Status as aligned rows, or without icons and color for screen readers
+//! catalyst status --display table
+//! catalyst status --display accessible
+//!
+//! # Check skill-rules.json for broken patterns and keywords
+//! catalyst skills lint
+//!
+//! # Scaffold a custom skill and register it in skill-rules.json
+//! catalyst skills new billing-rules --keyword invoice --path-pattern "src/billing/**"
+//!
+//! # What did the last three sessions touch?
+//! catalyst sessions report --last 3
+//!
+//! # Dump March's edits for a dashboard
+//! catalyst sessions export --format csv --since 2026-03-01 --until 2026-03-31 -o edits.csv
+//!
+//! # Set up Catalyst in a dev container as a local feature
+//! catalyst devcontainer generate --format feature
+//!
+//! # Move to a new machine: export everything, then restore it there
+//! catalyst export-state catalyst-state.json --include-user
+//! catalyst import-state catalyst-state.json
+//!
+//! # Learn more about an issue reported by status
+//! catalyst explain-issue CAT004
 //! ```
 
 use anyhow::{Context, Result};
+use catalyst_cli::binaries;
+use catalyst_cli::config::{self, CatalystConfig, SkillSource};
+use catalyst_cli::deprecation;
+use catalyst_cli::devcontainer::{self, DevcontainerFormat};
+use catalyst_cli::flags::{self, EnabledFlags, FlagSource};
 use catalyst_cli::init;
-use catalyst_cli::types::{InitConfig, AVAILABLE_SKILLS, AVAILABLE_SKILLS_WITH_DESC};
+use catalyst_cli::install_manifest::{self, ManifestFormat};
+use catalyst_cli::lock::{self, OperationLock};
+use catalyst_cli::lockfile::{self, Lockfile};
+use catalyst_cli::modification_store::TrackerBackend;
+use catalyst_cli::package;
+use catalyst_cli::preview::{self, FileDiff};
+use catalyst_cli::profiles;
+use catalyst_cli::progress::{self, JsonLinesReporter, QuietReporter, TerminalReporter, Verbosity};
+use catalyst_cli::project;
+use catalyst_cli::registry;
+use catalyst_cli::report_display::{DisplayMode, Displayable, StatusDisplay};
+use catalyst_cli::scripts;
+use catalyst_cli::signals;
+use catalyst_cli::stack::{self, DetectedStack};
+use catalyst_cli::state;
+use catalyst_cli::sync;
+use catalyst_cli::timings;
+use catalyst_cli::types::{
+    CatalystError, ExitCode, InitConfig, InstallMethod, SkillSelection, StrictSkills,
+    AVAILABLE_SKILLS, DEPRECATED_SKILLS, LOCK_FILE,
+};
 use catalyst_cli::update;
 use catalyst_cli::validation::check_binaries_installed;
+use catalyst_core::fs_ops::Fs;
 use catalyst_core::settings::*;
 use clap::{Parser, Subcommand};
 use colored::Colorize;
-use dialoguer::{theme::ColorfulTheme, Confirm, MultiSelect};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect, Select};
 use std::env;
 use std::io::{self, IsTerminal};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
+
+/// `println!` unless `--quiet` was given
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !progress::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
 
 #[derive(Parser)]
 #[command(name = "catalyst")]
 #[command(version, about = "Catalyst - Claude Code project setup and management", long_about = None)]
 struct Cli {
+    /// Print a phase-timing breakdown when the command finishes
+    #[arg(long, global = true)]
+    timings: bool,
+
+    /// How init, update, and status print their reports: text, json, table, or accessible
+    #[arg(long, global = true, default_value = "text")]
+    display: String,
+
+    /// When another catalyst process is changing the project, wait for it to
+    /// finish instead of failing
+    #[arg(long, global = true)]
+    wait: bool,
+
+    /// Print nothing but errors; JSON and CSV output is still printed
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Also print each file written, hash computed, lock taken, and status
+    /// check made
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+impl Cli {
+    /// How much `-q` and `-v` ask commands to print
+    fn verbosity(&self) -> Verbosity {
+        if self.quiet {
+            Verbosity::Quiet
+        } else if self.verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Initialize a Claude Code project with hooks and skills
@@ -66,9 +210,62 @@ enum Commands {
         #[arg(short, long)]
         force: bool,
 
+        /// Re-run on an existing install, creating only what's missing,
+        /// rewriting stale wrappers, and reporting what's already correct
+        #[arg(long, conflicts_with = "force")]
+        reconcile: bool,
+
+        /// Install the skills and hooks recorded in catalyst.lock, then
+        /// report anything that differs from it
+        #[arg(
+            long,
+            conflicts_with_all = ["force", "interactive", "all", "no_default_skills", "profile"]
+        )]
+        from_lock: bool,
+
         /// Install all available skills
         #[arg(long)]
         all: bool,
+
+        /// Install no skills unless chosen interactively, ignoring init.skills
+        /// and the built-in default
+        #[arg(long, conflicts_with = "all")]
+        no_default_skills: bool,
+
+        /// Use a setup profile's skills, hooks, and settings (built in:
+        /// minimal, rust, fullstack-ts, python, ci; add your own in
+        /// ~/.config/catalyst/profiles/<name>.toml)
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["all", "no_default_skills"])]
+        profile: Option<String>,
+
+        /// Print what would be created without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Give regenerated files default permissions instead of keeping
+        /// their current mode and ownership
+        #[arg(long)]
+        reset_permissions: bool,
+
+        /// Link skills from the machine-wide skill library
+        /// (~/.claude/skills-library) instead of copying them
+        #[arg(long)]
+        library: bool,
+
+        /// Never run skill post-install scripts (the default in CI and
+        /// without a terminal)
+        #[arg(long)]
+        no_scripts: bool,
+
+        /// Fail if any requested skill can't be installed: finish (the
+        /// default) completes init first, abort rolls it back
+        #[arg(long, value_name = "MODE", num_args = 0..=1, default_missing_value = "finish")]
+        strict_skills: Option<String>,
+
+        /// Where the tracker hook records edits: sqlite (file-change-tracker)
+        /// or jsonl (file-change-tracker-basic); saved as tracker.backend
+        #[arg(long, value_name = "BACKEND")]
+        tracker_backend: Option<String>,
     },
 
     /// Validate installation and report issues
@@ -80,6 +277,61 @@ enum Commands {
         /// Auto-fix common issues
         #[arg(short, long)]
         fix: bool,
+
+        /// With --fix, add missing hook entries to settings.json without asking
+        #[arg(short, long, requires = "fix")]
+        yes: bool,
+
+        /// With --fix, show a diff of every file the fixes would change
+        /// instead of changing them
+        #[arg(long, requires = "fix")]
+        diff: bool,
+
+        /// For CI: fail on warnings, a version mismatch, or modified skills,
+        /// and print sorted, uncolored output
+        #[arg(long, conflicts_with = "fix")]
+        strict: bool,
+
+        /// Also run the `[healthcheck] command` of each installed skill.
+        /// The commands come from the project's skill.toml files, so only
+        /// use this on projects you trust
+        #[arg(long)]
+        run_healthchecks: bool,
+    },
+
+    /// Diagnose the installation and walk through fixes interactively
+    Doctor {
+        /// Directory to check (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Record how Catalyst was installed (script, cargo, homebrew, tarball)
+        /// so suggested fixes use the matching commands
+        #[arg(long, value_name = "METHOD")]
+        install_method: Option<String>,
+
+        /// Only run the hook wrappers with a stripped PATH and report what
+        /// they can't find
+        #[arg(long)]
+        hook_env: bool,
+    },
+
+    /// Fix only the issues with one ID (e.g., CAT004), without prompting
+    Fix {
+        /// Issue ID as shown by `catalyst status`
+        id: String,
+
+        /// Directory to fix (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Only fix the issue for this component (as shown by status)
+        #[arg(long, value_name = "NAME")]
+        component: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Update hooks and skills to latest version
@@ -91,6 +343,89 @@ enum Commands {
         /// Force update even if files were modified locally
         #[arg(short, long)]
         force: bool,
+
+        /// Finish a previously interrupted update
+        #[arg(long, conflicts_with_all = ["rollback", "force"])]
+        resume: bool,
+
+        /// Undo a previously interrupted update
+        #[arg(long, conflicts_with = "force")]
+        rollback: bool,
+
+        /// Never run skill post-install scripts (the default in CI and
+        /// without a terminal)
+        #[arg(long)]
+        no_scripts: bool,
+
+        /// Rename installed skills that were renamed upstream without asking
+        #[arg(long, conflicts_with_all = ["resume", "rollback"])]
+        migrate_skills: bool,
+
+        /// Update only this skill, if a newer version ships (repeatable)
+        #[arg(long = "skill", value_name = "ID", conflicts_with_all = ["resume", "rollback", "migrate_skills"])]
+        skills: Vec<String>,
+
+        /// Show a diff of every file the update would change instead of
+        /// changing them
+        #[arg(long, conflicts_with_all = ["resume", "rollback"])]
+        diff: bool,
+    },
+
+    /// Initialize, update, and repair as needed, then verify (for bootstrap scripts)
+    ///
+    /// Exits 0 when the project is healthy, 1 when a step failed, and 2 or
+    /// 3 when every step ran but warnings or errors remain.
+    Sync {
+        /// Directory to sync (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Add missing hook entries to settings.json (never asked for)
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Never run skill post-install scripts (the default in CI and
+        /// without a terminal)
+        #[arg(long)]
+        no_scripts: bool,
+    },
+
+    /// Record the installed skills, hook wrappers, and binary versions in
+    /// catalyst.lock
+    Lock {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+    },
+
+    /// Install the hook binaries into ~/.claude-hooks/bin
+    InstallBinaries {
+        /// Directory containing prebuilt binaries (defaults to the directory
+        /// this catalyst binary is in)
+        #[arg(long, value_name = "DIR", conflicts_with = "build")]
+        from: Option<PathBuf>,
+
+        /// Build the binaries from a Catalyst checkout first
+        #[arg(long, value_name = "CHECKOUT")]
+        build: Option<PathBuf>,
+
+        /// With --build, include file-change-tracker (SQLite feature)
+        #[arg(long, requires = "build")]
+        sqlite: bool,
+    },
+
+    /// Update the catalyst executable to the latest release
+    SelfUpdate {
+        /// Only report whether a newer release is available
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Print the release archive URLs and SHA-256 hashes for this version
+    InstallManifest {
+        /// Output format (json or nix)
+        #[arg(long, value_name = "FORMAT", default_value = "json")]
+        format: String,
     },
 
     /// Manage settings.json files (legacy commands)
@@ -98,968 +433,5024 @@ enum Commands {
         #[command(subcommand)]
         command: SettingsCommands,
     },
-}
 
-#[derive(Subcommand)]
-enum SettingsCommands {
-    /// Read and display settings file
-    Read {
-        /// Path to settings.json
-        #[arg(default_value = ".claude/settings.json")]
-        path: String,
+    /// Get and set project configuration (catalyst.toml)
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
     },
 
-    /// Validate settings file structure
-    Validate {
-        /// Path to settings.json
-        #[arg(default_value = ".claude/settings.json")]
-        path: String,
+    /// Print the effective configuration, environment overrides, and paths
+    Env {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
-    /// Add a hook to settings
-    AddHook {
-        /// Path to settings.json
-        #[arg(short, long, default_value = ".claude/settings.json")]
-        path: String,
+    /// Inspect experimental feature flags
+    Flags {
+        #[command(subcommand)]
+        command: FlagsCommands,
+    },
 
-        /// Hook event type (UserPromptSubmit, PostToolUse, Stop)
-        #[arg(short, long)]
-        event: String,
+    /// Work with installed skills and their activation rules
+    Skills {
+        #[command(subcommand)]
+        command: SkillsCommands,
+    },
 
-        /// Hook command to execute
-        #[arg(short, long)]
-        command: String,
+    /// Manage subagent definitions in .claude/agents
+    Agents {
+        #[command(subcommand)]
+        command: AgentsCommands,
+    },
 
-        /// Optional matcher pattern (regex)
-        #[arg(short, long)]
-        matcher: Option<String>,
+    /// Manage slash commands in .claude/commands
+    #[command(name = "commands")]
+    SlashCommand {
+        #[command(subcommand)]
+        command: CommandsCommands,
+    },
 
-        /// Dry run - preview changes without writing
-        #[arg(long)]
-        dry_run: bool,
+    /// Summarize what happened in past Claude Code sessions (SQLite tracker)
+    Sessions {
+        #[command(subcommand)]
+        command: SessionsCommands,
     },
 
-    /// Remove hooks matching a command pattern
-    RemoveHook {
-        /// Path to settings.json
-        #[arg(short, long, default_value = ".claude/settings.json")]
-        path: String,
+    /// Summarize tracked work on this project: files by category, risky
+    /// patterns, check runs
+    Report {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
 
-        /// Hook event type
-        #[arg(short, long)]
-        event: String,
+        /// Format as Markdown for a pull request description
+        #[arg(long, conflicts_with = "json")]
+        pr: bool,
 
-        /// Command pattern to match for removal
-        #[arg(short = 'P', long)]
-        pattern: String,
+        /// Post the Markdown as a comment on the branch's PR with `gh pr comment`
+        #[arg(long, requires = "pr")]
+        post: bool,
 
-        /// Dry run - preview changes without writing
+        /// Report only this session ID (default: the latest session)
+        #[arg(long, value_name = "ID")]
+        session: Option<String>,
+
+        /// Report every session's edits on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE", conflicts_with = "session")]
+        since: Option<String>,
+
+        /// Output as JSON
         #[arg(long)]
-        dry_run: bool,
+        json: bool,
     },
 
-    /// Merge two settings files
-    Merge {
-        /// Base settings file
-        base: String,
+    /// Hook latency and outcome stats (SQLite tracker)
+    Stats {
+        #[command(subcommand)]
+        command: StatsCommands,
+    },
 
-        /// Settings file to merge in
-        merge: String,
+    /// Read the prompt audit log written by the prompt-logger hook
+    Logs {
+        #[command(subcommand)]
+        command: LogsCommands,
+    },
 
-        /// Output file (defaults to base file)
-        #[arg(short, long)]
-        output: Option<String>,
+    /// Set up Catalyst inside dev containers
+    Devcontainer {
+        #[command(subcommand)]
+        command: DevcontainerCommands,
+    },
 
-        /// Dry run - preview merge without writing
+    /// Bundle catalyst's state (hashes, version, config) into an archive
+    ExportState {
+        /// Archive file to write
+        #[arg(value_name = "ARCHIVE")]
+        archive: PathBuf,
+
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Also include user-level state: install method, tracker databases,
+        /// and the skill library
         #[arg(long)]
-        dry_run: bool,
+        include_user: bool,
     },
-}
 
-/// Run interactive initialization prompts
-///
-/// Guides the user through setup with prompts for:
-/// - Directory confirmation
-/// - Hook installation
-/// - File tracker installation
-/// - Skill selection (multi-select)
-///
-/// Returns Some(InitConfig) with user selections, or None if cancelled
-fn run_interactive_init(target_dir: &Path, force: bool) -> Result<Option<InitConfig>> {
-    // Use fixed width for consistent formatting across terminals
-    const SEPARATOR_WIDTH: usize = 60;
-    let theme = ColorfulTheme::default();
+    /// Restore state written by `catalyst export-state`
+    ImportState {
+        /// Archive file to read
+        #[arg(value_name = "ARCHIVE")]
+        archive: PathBuf,
 
-    println!("{}", "━".repeat(SEPARATOR_WIDTH).bright_cyan());
-    println!("{}", "  Interactive Catalyst Setup  ".bright_cyan().bold());
-    println!("{}", "━".repeat(SEPARATOR_WIDTH).bright_cyan());
-    println!();
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
 
-    // Confirm directory
-    println!("{}", "Target directory:".cyan().bold());
-    println!("  {}", target_dir.display());
-    println!();
+        /// Overwrite existing files whose content differs from the archive
+        #[arg(short, long)]
+        force: bool,
 
-    let proceed = Confirm::with_theme(&theme)
-        .with_prompt("Initialize Catalyst in this directory?")
-        .default(true)
-        .interact()
-        .context("Failed to get directory confirmation")?;
+        /// Restore only project state, even if the archive has user state
+        #[arg(long)]
+        project_only: bool,
+    },
 
-    if !proceed {
-        return Ok(None);
-    }
+    /// Explain a status issue in depth (lists all issue IDs if none given)
+    ExplainIssue {
+        /// Issue ID as shown by `catalyst status` (e.g., CAT004)
+        id: Option<String>,
+    },
+}
 
-    println!();
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Show the effective configuration and where it was loaded from
+    List {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+    },
 
-    // Ask about hooks
-    let install_hooks = Confirm::with_theme(&theme)
-        .with_prompt("Install skill auto-activation hooks?")
-        .default(true)
-        .interact()
-        .context("Failed to get hook installation preference")?;
+    /// Print the effective value of a key (e.g., init.skills)
+    Get {
+        /// Dotted configuration key
+        key: String,
 
-    println!();
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+    },
 
-    // Ask about tracker
-    let install_tracker = Confirm::with_theme(&theme)
-        .with_prompt("Install file-change-tracker hook?")
-        .default(true)
-        .interact()
-        .context("Failed to get tracker installation preference")?;
+    /// Set a key in catalyst.toml (value is parsed as TOML, e.g. false or ["a", "b"])
+    Set {
+        /// Dotted configuration key
+        key: String,
 
-    println!();
+        /// New value
+        value: String,
 
-    // Multi-select for skills
-    println!("{}", "Select skills to install:".cyan().bold());
-    println!("{}", "  (Use Space to select, Enter to confirm)".dimmed());
-    println!();
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+    },
 
-    let skill_items: Vec<String> = AVAILABLE_SKILLS_WITH_DESC
-        .iter()
-        .map(|(name, desc)| format!("{:<30} - {}", name, desc))
-        .collect();
+    /// Remove a key from catalyst.toml, restoring its default
+    Unset {
+        /// Dotted configuration key
+        key: String,
 
-    // Create default selection (skill-developer pre-selected)
-    let default_selection: Vec<bool> = AVAILABLE_SKILLS
-        .iter()
-        .map(|&skill| skill == "skill-developer")
-        .collect();
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+    },
+}
 
-    let selected_indices = MultiSelect::with_theme(&theme)
-        .items(&skill_items)
-        .defaults(&default_selection)
+#[derive(Subcommand)]
+enum FlagsCommands {
+    /// List every feature flag with its stability and whether it is enabled
+    List {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SkillsCommands {
+    /// List installed skills with their version and summary
+    List {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List installed skills with a newer version in this CLI
+    ///
+    /// Exits 2 when any skill is outdated.
+    Outdated {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Search the skill registry
+    Search {
+        /// Words to look for in skill IDs, descriptions, and keywords
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Project directory whose catalyst.toml names the registry
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Download the index even if the cached copy is recent
+        #[arg(long)]
+        refresh: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Install a skill from the registry or a .catskill package
+    Add {
+        /// Registry skill ID, or path to a .catskill file
+        id: String,
+
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Replace the skill if it is already installed
+        #[arg(short, long)]
+        force: bool,
+
+        /// Install the skill even if it isn't signed by a key in registry.trusted_keys
+        #[arg(long)]
+        allow_unverified: bool,
+
+        /// Download the index even if the cached copy is recent
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Bundle a skill directory into a .catskill package for sharing
+    Pack {
+        /// Skill directory (containing SKILL.md)
+        dir: PathBuf,
+
+        /// Package file to write (default: <name>-<version>.catskill)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Check skill-rules.json for schema errors, bad patterns, and shadowed keywords
+    Lint {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Create a custom skill and add it to skill-rules.json
+    New {
+        /// Skill name in kebab-case (e.g., billing-rules)
+        name: String,
+
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// One-line description for SKILL.md
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Prompt keyword that activates the skill (repeatable)
+        #[arg(long = "keyword", value_name = "WORD")]
+        keywords: Vec<String>,
+
+        /// Regex matched against prompts (repeatable)
+        #[arg(long = "intent", value_name = "REGEX")]
+        intent_patterns: Vec<String>,
+
+        /// Glob for files the skill applies to (repeatable)
+        #[arg(long = "path-pattern", value_name = "GLOB")]
+        path_patterns: Vec<String>,
+
+        /// Don't prompt; use the given options and defaults for the rest
+        #[arg(long)]
+        no_prompt: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AgentsCommands {
+    /// List installed agents (checking their front matter) and shipped templates
+    List {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Install an agent from a shipped template (code-reviewer, test-writer, doc-writer)
+    Add {
+        /// Template name
+        template: String,
+
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Install under this name instead of the template's
+        #[arg(long, value_name = "NAME")]
+        name: Option<String>,
+
+        /// Template variable (repeatable), e.g. --var model=opus
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+
+        /// Overwrite an existing agent with the same name
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Create a blank agent to fill in
+    New {
+        /// Agent name in kebab-case (e.g., migration-writer)
+        name: String,
+
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// When Claude should use the agent
+        #[arg(short, long)]
+        description: String,
+
+        /// Tool the agent may use (repeatable; default: all tools)
+        #[arg(long = "tool", value_name = "TOOL")]
+        tools: Vec<String>,
+
+        /// Model alias (inherit, sonnet, opus, haiku)
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Overwrite an existing agent with the same name
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Delete an agent from .claude/agents
+    Remove {
+        /// Agent name
+        name: String,
+
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CommandsCommands {
+    /// List slash commands and the templates `commands new` can use
+    List {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Create a slash command from a template (basic, review, fix-issue)
+    New {
+        /// Command name in kebab-case (e.g., fix-issue); run as /NAME
+        name: String,
+
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Template to start from (default: basic)
+        #[arg(short, long)]
+        template: Option<String>,
+
+        /// Description shown in the slash command menu
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Arguments shown after the name, e.g. "[issue-number]"
+        #[arg(long, value_name = "HINT")]
+        argument_hint: Option<String>,
+
+        /// Overwrite an existing command with the same name
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Check front matter and argument placeholders; exits 1 on problems
+    Validate {
+        /// Only these commands (default: all)
+        names: Vec<String>,
+
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum StatsCommands {
+    /// p50/p95 latency, blocks, and failures for each hook binary
+    Hooks {
+        /// Only runs on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum LogsCommands {
+    /// List logged prompts, oldest first
+    Show {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Only prompts from this session ID
+        #[arg(long, value_name = "ID")]
+        session: Option<String>,
+
+        /// Only prompts on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+
+        /// Show only the last N matching prompts
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the last prompts, optionally waiting for new ones
+    Tail {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Number of prompts to print
+        #[arg(short = 'n', long, value_name = "N", default_value_t = 10)]
+        lines: usize,
+
+        /// Keep printing prompts as they're logged (Ctrl-C to stop)
+        #[arg(short, long)]
+        follow: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum SessionsCommands {
+    /// Per-session stats: files by category, risky patterns, most-edited files, timeline
+    Report {
+        /// Report only this session ID
+        #[arg(long, value_name = "ID")]
+        session: Option<String>,
+
+        /// Report the N most recent sessions
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 1,
+            conflicts_with = "session"
+        )]
+        last: usize,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Dump the tracker tables as JSON or CSV for dashboards
+    Export {
+        /// Output format (json or csv)
+        #[arg(long, value_name = "FORMAT", default_value = "json")]
+        format: String,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Export only this session ID
+        #[arg(long, value_name = "ID")]
+        session: Option<String>,
+
+        /// Only edits on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+
+        /// Only edits up to and including this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE")]
+        until: Option<String>,
+
+        /// Table to write as CSV (file_modifications or sessions); JSON has both
+        #[arg(long, value_name = "TABLE", default_value = "file_modifications")]
+        table: String,
+    },
+
+    /// List tracked edits matching filters, as a table, JSON, or CSV
+    Query {
+        /// Only edits in this session ID
+        #[arg(long, value_name = "ID")]
+        session: Option<String>,
+
+        /// Only files in this category (backend, frontend, database, other)
+        #[arg(long, value_name = "CATEGORY")]
+        category: Option<String>,
+
+        /// Only edits since this date, timestamp, or age (e.g. 2h, 7d)
+        #[arg(long, value_name = "WHEN")]
+        since: Option<String>,
+
+        /// Only edits up to and including this date, timestamp, or age
+        #[arg(long, value_name = "WHEN")]
+        until: Option<String>,
+
+        /// Only files whose path matches this glob (e.g. 'src/**')
+        #[arg(long, value_name = "GLOB")]
+        file_glob: Option<String>,
+
+        /// Only edits that left async code without a try/catch
+        #[arg(long)]
+        risky: bool,
+
+        /// Only edits made with this git branch checked out
+        #[arg(long, value_name = "BRANCH")]
+        branch: Option<String>,
+
+        /// Output format (table, json, or csv)
+        #[arg(long, value_name = "FORMAT", default_value = "table")]
+        format: String,
+    },
+
+    /// Send session summaries to the [analytics] endpoint in catalyst.toml
+    Push {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Resend every session, not just those with new edits
+        #[arg(long)]
+        all: bool,
+
+        /// Print the request bodies instead of sending them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DevcontainerCommands {
+    /// Write a setup script or feature to .devcontainer/ and print the
+    /// devcontainer.json snippet that uses it
+    Generate {
+        /// Project directory (defaults to current directory)
+        #[arg(short, long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// What to generate (post-create or feature)
+        #[arg(long, value_name = "FORMAT", default_value = "post-create")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum SettingsCommands {
+    /// Read and display settings file
+    Read {
+        /// Path to settings.json
+        #[arg(default_value = ".claude/settings.json")]
+        path: String,
+    },
+
+    /// Validate settings file structure
+    ///
+    /// Exits 0 when valid, 1 when the file is JSON but not valid settings,
+    /// and 2 when it can't be read or parsed.
+    Validate {
+        /// Path to settings.json
+        #[arg(default_value = ".claude/settings.json")]
+        path: String,
+
+        /// Also warn about keys Claude Code doesn't recognize
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Add a hook to settings
+    AddHook {
+        /// Path to settings.json
+        #[arg(short, long, default_value = ".claude/settings.json")]
+        path: String,
+
+        /// Hook event type (UserPromptSubmit, PreToolUse, PostToolUse, Notification,
+        /// Stop, SubagentStop, SessionStart, SessionEnd)
+        #[arg(short, long)]
+        event: String,
+
+        /// Hook command to execute
+        #[arg(short, long)]
+        command: String,
+
+        /// Optional matcher pattern (regex)
+        #[arg(short, long)]
+        matcher: Option<String>,
+
+        /// Dry run - preview changes without writing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Show a diff of the settings file instead of writing it
+        #[arg(long, conflicts_with = "dry_run")]
+        diff: bool,
+    },
+
+    /// Remove hooks matching a command pattern
+    RemoveHook {
+        /// Path to settings.json
+        #[arg(short, long, default_value = ".claude/settings.json")]
+        path: String,
+
+        /// Hook event type
+        #[arg(short, long)]
+        event: String,
+
+        /// Command pattern to match for removal
+        #[arg(short = 'P', long)]
+        pattern: String,
+
+        /// Dry run - preview changes without writing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Show a diff of the settings file instead of writing it
+        #[arg(long, conflicts_with = "dry_run")]
+        diff: bool,
+    },
+
+    /// Merge two settings files
+    Merge {
+        /// Base settings file
+        base: String,
+
+        /// Settings file to merge in
+        merge: String,
+
+        /// Output file (defaults to base file)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Dry run - preview merge without writing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Show a diff of the settings file instead of writing it
+        #[arg(long, conflicts_with = "dry_run")]
+        diff: bool,
+    },
+
+    /// Show effective settings merged from user, project, and local files
+    Effective {
+        /// Project directory (defaults to the current project)
+        path: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Manage which .mcp.json servers are enabled (lists them by default)
+    Mcp {
+        /// Path to settings.json; .mcp.json is looked up in its project
+        #[arg(short, long, default_value = ".claude/settings.json", global = true)]
+        path: String,
+
+        /// Set enableAllProjectMcpServers, enabling every .mcp.json server
+        #[arg(long, conflicts_with = "disable_all")]
+        enable_all: bool,
+
+        /// Clear enableAllProjectMcpServers
+        #[arg(long)]
+        disable_all: bool,
+
+        /// Dry run - preview changes without writing
+        #[arg(long, global = true)]
+        dry_run: bool,
+
+        #[command(subcommand)]
+        command: Option<McpCommands>,
+    },
+}
+
+#[derive(Subcommand)]
+enum McpCommands {
+    /// Enable servers (they must be defined in .mcp.json, if there is one)
+    Add {
+        /// Server names from .mcp.json
+        #[arg(required = true)]
+        names: Vec<String>,
+
+        /// Enable servers that .mcp.json doesn't define
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Stop enabling servers
+    Remove {
+        /// Server names
+        #[arg(required = true)]
+        names: Vec<String>,
+    },
+
+    /// List enabled, disabled, and available servers
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// List detected stacks for display, e.g.
+/// "Rust (Cargo.toml), Python (pyproject.toml)"
+fn join_stacks(stacks: &[DetectedStack]) -> String {
+    stacks
+        .iter()
+        .map(|detected| detected.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Run interactive initialization prompts
+///
+/// Guides the user through setup with prompts for:
+/// - Directory confirmation
+/// - Hook installation
+/// - File tracker installation
+/// - Skill selection (multi-select)
+///
+/// Returns Some(InitConfig) with user selections, or None if cancelled
+fn run_interactive_init(
+    target_dir: &Path,
+    force: bool,
+    project_config: &CatalystConfig,
+    default_skills: &[String],
+    detected_stacks: &[DetectedStack],
+) -> Result<Option<InitConfig>> {
+    // Use fixed width for consistent formatting across terminals
+    const SEPARATOR_WIDTH: usize = 60;
+    let theme = ColorfulTheme::default();
+
+    println!("{}", "━".repeat(SEPARATOR_WIDTH).bright_cyan());
+    println!("{}", "  Interactive Catalyst Setup  ".bright_cyan().bold());
+    println!("{}", "━".repeat(SEPARATOR_WIDTH).bright_cyan());
+    println!();
+
+    // Confirm directory
+    println!("{}", "Target directory:".cyan().bold());
+    println!("  {}", target_dir.display());
+    println!();
+    if !detected_stacks.is_empty() {
+        println!("{}", "Detected project type:".cyan().bold());
+        println!("  {}", join_stacks(detected_stacks));
+        println!("{}", "  (matching skills are preselected below)".dimmed());
+        println!();
+    }
+
+    let proceed = Confirm::with_theme(&theme)
+        .with_prompt("Initialize Catalyst in this directory?")
+        .default(true)
+        .interact()
+        .context("Failed to get directory confirmation")?;
+
+    if !proceed {
+        return Ok(None);
+    }
+
+    println!();
+
+    // Ask about hooks
+    let install_hooks = Confirm::with_theme(&theme)
+        .with_prompt("Install skill auto-activation hooks?")
+        .default(project_config.init.install_hooks)
+        .interact()
+        .context("Failed to get hook installation preference")?;
+
+    println!();
+
+    // Ask about tracker
+    let install_tracker = Confirm::with_theme(&theme)
+        .with_prompt("Install file-change-tracker hook?")
+        .default(project_config.init.install_tracker)
+        .interact()
+        .context("Failed to get tracker installation preference")?;
+
+    println!();
+
+    // Ask about build checks
+    let install_check_hook = Confirm::with_theme(&theme)
+        .with_prompt("Install cargo-check hook (build and lint checks after edits)?")
+        .default(project_config.init.install_check_hook.unwrap_or(false))
+        .interact()
+        .context("Failed to get check hook installation preference")?;
+
+    println!();
+
+    // Multi-select for skills
+    println!("{}", "Select skills to install:".cyan().bold());
+    println!("{}", "  (Use Space to select, Enter to confirm)".dimmed());
+    println!();
+
+    let skill_items: Vec<String> = init::shipped_manifests()
+        .iter()
+        .map(|manifest| format!("{:<30} - {}", manifest.name, manifest.summary()))
+        .collect();
+
+    // Pre-select the skills non-interactive init would install
+    let default_selection: Vec<bool> = AVAILABLE_SKILLS
+        .iter()
+        .map(|&skill| default_skills.iter().any(|s| s == skill))
+        .collect();
+
+    let selected_indices = MultiSelect::with_theme(&theme)
+        .items(&skill_items)
+        .defaults(&default_selection)
+        .interact()
+        .context("Failed to get skill selection")?;
+
+    let selected_skills: Vec<String> = selected_indices
+        .iter()
+        .filter_map(|&i| AVAILABLE_SKILLS.get(i).map(|s| s.to_string()))
+        .collect();
+
+    println!();
+
+    // Show summary
+    println!("{}", "━".repeat(SEPARATOR_WIDTH).bright_cyan());
+    println!("{}", "  Configuration Summary  ".bright_cyan().bold());
+    println!("{}", "━".repeat(SEPARATOR_WIDTH).bright_cyan());
+    println!();
+    println!("{}", "Directory:".cyan().bold());
+    println!("  {}", target_dir.display());
+    println!();
+    println!("{}", "Hooks:".cyan().bold());
+    println!(
+        "  Auto-activation hooks: {}",
+        if install_hooks {
+            "✓ Yes".green()
+        } else {
+            "✗ No".red()
+        }
+    );
+    println!(
+        "  File-change tracker:   {}",
+        if install_tracker {
+            "✓ Yes".green()
+        } else {
+            "✗ No".red()
+        }
+    );
+    println!(
+        "  Build checks:          {}",
+        if install_check_hook {
+            "✓ Yes".green()
+        } else {
+            "✗ No".red()
+        }
+    );
+    println!();
+    println!("{}", "Skills:".cyan().bold());
+    if selected_skills.is_empty() {
+        println!("  {}", "None selected".yellow());
+        println!();
+        println!(
+            "{}",
+            "  ⚠️  No skills selected - you can add them later with:".yellow()
+        );
+        println!("{}", "    catalyst update".dimmed());
+    } else {
+        for skill in &selected_skills {
+            println!("  ✓ {}", skill.green());
+        }
+    }
+    println!();
+    println!("{}", "💡 Note:".yellow().bold());
+    println!("  After initialization, customize pathPatterns in:");
+    println!("    .claude/skills/skill-rules.json");
+    println!();
+    println!("{}", "━".repeat(SEPARATOR_WIDTH).bright_cyan());
+    println!();
+
+    let confirm = Confirm::with_theme(&theme)
+        .with_prompt("Proceed with initialization?")
+        .default(true)
+        .interact()
+        .context("Failed to get final confirmation")?;
+
+    if !confirm {
+        return Ok(None);
+    }
+
+    println!();
+
+    Ok(Some(InitConfig {
+        directory: target_dir.to_path_buf(),
+        install_hooks,
+        install_tracker,
+        install_check_hook,
+        skills: selected_skills,
+        force,
+        dry_run: false,
+        reset_permissions: false,
+        skills_library: None,
+        strict_skills: project_config.init.strict_skills,
+        skill_selection: SkillSelection::Explicit,
+        settings: serde_json::Map::new(),
+        reconcile: false,
+        from_lock: false,
+        tracker_backend: None,
+    }))
+}
+
+/// How to handle an update left unfinished by a previous run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InterruptedUpdateAction {
+    None,
+    Resume,
+    Rollback,
+}
+
+/// Ask the user whether to resume or roll back an interrupted update
+fn prompt_interrupted_update(target_dir: &Path) -> Result<InterruptedUpdateAction> {
+    if let Some(journal) = update::UpdateJournal::load(target_dir)? {
+        println!(
+            "{}",
+            "⚠️  A previous update did not finish.".yellow().bold()
+        );
+        println!(
+            "  v{} → v{}, started {} ({}/{} steps completed)",
+            journal.from_version,
+            journal.to_version,
+            journal.started_at,
+            journal.completed_count(),
+            journal.entries.len()
+        );
+        println!();
+    }
+
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("How do you want to proceed?")
+        .items(&["Resume the update", "Roll back the update", "Cancel"])
+        .default(0)
+        .interact()
+        .context("Failed to get interrupted update choice")?;
+
+    match choice {
+        0 => Ok(InterruptedUpdateAction::Resume),
+        1 => Ok(InterruptedUpdateAction::Rollback),
+        _ => exit(ExitCode::Failure),
+    }
+}
+
+/// Handle installed skills that Catalyst deprecated before updating
+///
+/// Renamed skills are moved to their new name when `migrate` is set or the
+/// user agrees at the prompt, so the update that follows refreshes them.
+/// Retired skills, and renamed ones left alone, are only reported.
+fn migrate_deprecated_skills(target_dir: &Path, migrate: bool, use_color: bool) -> Result<()> {
+    let installed = deprecation::installed_deprecated_skills(target_dir, DEPRECATED_SKILLS);
+    if installed.is_empty() {
+        return Ok(());
+    }
+
+    for skill in &installed {
+        let message = match skill.replaced_by {
+            Some(new_id) => format!(
+                "⚠️  Skill '{}' was renamed to '{}' in {}",
+                skill.id, new_id, skill.since
+            ),
+            None => format!(
+                "⚠️  Skill '{}' was deprecated in {}: {}",
+                skill.id, skill.since, skill.note
+            ),
+        };
+        if use_color {
+            println!("{}", message.yellow());
+        } else {
+            println!("{}", message);
+        }
+    }
+
+    let renamed: Vec<_> = installed
+        .into_iter()
+        .filter(|skill| skill.replaced_by.is_some())
+        .collect();
+    let migrate = !renamed.is_empty()
+        && (migrate
+            || (io::stdin().is_terminal()
+                && Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Rename these skills now? Local edits are kept")
+                    .default(true)
+                    .interact()
+                    .context("Failed to get skill migration choice")?));
+
+    if migrate {
+        for skill in renamed {
+            let migration = deprecation::migrate_skill(target_dir, skill)?;
+            println!("  ✓ Renamed skill: {} → {}", migration.from, migration.to);
+        }
+    } else if !renamed.is_empty() {
+        println!("  Run 'catalyst update --migrate-skills' to rename them");
+    }
+    println!();
+    Ok(())
+}
+
+/// What to do about one issue in `catalyst doctor`
+enum DoctorChoice {
+    Fix,
+    Skip,
+    Stop,
+}
+
+/// Walk through each status issue, explaining it and offering to fix it
+///
+/// Fixable issues are applied one at a time after confirmation; the rest
+/// list their manual fixes. Ends by validating again and printing a summary.
+///
+/// # Returns
+///
+/// Returns the status level after the fixes
+fn run_doctor(target_dir: &Path, use_color: bool) -> Result<catalyst_cli::types::StatusLevel> {
+    use catalyst_cli::status::{check_installation, fix_issue, Probes};
+    use catalyst_cli::types::{IssueSeverity, Platform, StatusLevel};
+
+    let platform = Platform::detect();
+    let theme = ColorfulTheme::default();
+
+    if use_color {
+        println!("{}", "🩺 Catalyst Doctor".cyan().bold());
+    } else {
+        println!("🩺 Catalyst Doctor");
+    }
+    println!("  {}", target_dir.display());
+
+    let report = check_installation(target_dir, platform, Probes::STANDARD)?;
+    if use_color {
+        println!(
+            "  Installed via: {}",
+            report.install_method.to_string().dimmed()
+        );
+    } else {
+        println!("  Installed via: {}", report.install_method);
+    }
+    println!();
+
+    if report.issues.is_empty() {
+        if use_color {
+            println!("{}", "✅ No issues found".green().bold());
+        } else {
+            println!("✅ No issues found");
+        }
+        return Ok(StatusLevel::Ok);
+    }
+
+    let mut fixed = Vec::new();
+    let mut failed = 0;
+    let total = report.issues.len();
+
+    // Held while the user decides, so nothing else changes what was checked
+    let _lock = if report.issues.iter().any(|issue| issue.auto_fixable) {
+        Some(OperationLock::acquire(target_dir)?)
+    } else {
+        None
+    };
+
+    for (index, issue) in report.issues.iter().enumerate() {
+        let explanation = catalyst_cli::explain::explain(issue.kind);
+        let heading = format!(
+            "[{}/{}] {} {}",
+            index + 1,
+            total,
+            issue.kind.id(),
+            explanation.title
+        );
+        if use_color {
+            let heading = match issue.severity {
+                IssueSeverity::Error => heading.red().bold(),
+                IssueSeverity::Warning => heading.yellow().bold(),
+                IssueSeverity::Info => heading.cyan().bold(),
+            };
+            println!("{}", heading);
+        } else {
+            println!("{}", heading);
+        }
+        println!("  {}: {}", issue.component, issue.description);
+        println!();
+        println!("  {}", explanation.why);
+        println!();
+
+        if !issue.auto_fixable {
+            println!("  Fix manually:");
+            for (i, fix) in explanation.fixes.iter().enumerate() {
+                println!("    {}. {}", i + 1, fix);
+            }
+            println!();
+            continue;
+        }
+
+        let choice = Select::with_theme(&theme)
+            .with_prompt("Apply the fix?")
+            .items(&["Fix it", "Skip", "Stop (skip remaining issues)"])
+            .default(0)
+            .interact()
+            .context("Failed to get fix choice")?;
+        let choice = match choice {
+            0 => DoctorChoice::Fix,
+            1 => DoctorChoice::Skip,
+            _ => DoctorChoice::Stop,
+        };
+
+        match choice {
+            DoctorChoice::Fix => match fix_issue(target_dir, platform, &report, issue) {
+                Ok(message) => {
+                    if use_color {
+                        println!("  {}", format!("✓ {}", message).green());
+                    } else {
+                        println!("  ✓ {}", message);
+                    }
+                    fixed.push(message);
+                }
+                Err(e) => {
+                    failed += 1;
+                    if use_color {
+                        eprintln!("  {}", format!("❌ Fix failed: {}", e).red());
+                    } else {
+                        eprintln!("  ❌ Fix failed: {}", e);
+                    }
+                }
+            },
+            DoctorChoice::Skip => {}
+            DoctorChoice::Stop => break,
+        }
+        println!();
+    }
+
+    // Validate again so the summary reflects what is actually fixed
+    let after = check_installation(target_dir, platform, Probes::STANDARD)?;
+
+    if use_color {
+        println!("{}", "Summary".cyan().bold());
+    } else {
+        println!("Summary");
+    }
+    println!("  Issues found:     {}", total);
+    println!("  Fixed:            {}", fixed.len());
+    if failed > 0 {
+        println!("  Failed to fix:    {}", failed);
+    }
+    println!("  Remaining issues: {}", after.issues.len());
+    println!();
+
+    let (icon, text) = match after.level {
+        StatusLevel::Ok => ("✅", "Installation is healthy"),
+        StatusLevel::Warning => ("⚠️", "Installation works with warnings"),
+        StatusLevel::Error => ("❌", "Installation still has errors"),
+    };
+    if use_color {
+        let text = match after.level {
+            StatusLevel::Ok => text.green().bold(),
+            StatusLevel::Warning => text.yellow().bold(),
+            StatusLevel::Error => text.red().bold(),
+        };
+        println!("{} {}", icon, text);
+    } else {
+        println!("{} {}", icon, text);
+    }
+    if !after.issues.is_empty() {
+        println!("  Run 'catalyst status' for details");
+    }
+
+    Ok(after.level)
+}
+
+/// Run the hook wrappers with Claude Code's minimal PATH and report the result
+///
+/// When commands are missing but can be found on the current PATH, offers
+/// (on a terminal) to add their directories to the wrappers.
+///
+/// # Returns
+///
+/// Returns true if every wrapper found what it needed, after any fix
+fn run_hook_env_check(target_dir: &Path, use_color: bool) -> Result<bool> {
+    use catalyst_cli::hook_env::{self, MINIMAL_PATH};
+    use catalyst_cli::types::Platform;
+
+    let platform = CatalystConfig::load(target_dir)?.wrapper_platform(Platform::detect());
+
+    if use_color {
+        println!("{}", "🩺 Hook environment check".cyan().bold());
+    } else {
+        println!("🩺 Hook environment check");
+    }
+    println!("  PATH={}", MINIMAL_PATH);
+    println!();
+
+    let diagnoses = hook_env::diagnose_wrappers(target_dir, platform)?;
+    if diagnoses.is_empty() {
+        println!("No hook wrappers installed in {}", target_dir.display());
+        return Ok(true);
+    }
+
+    for diagnosis in &diagnoses {
+        if diagnosis.reachable() {
+            if use_color {
+                println!("  {} {}", "✓".green(), diagnosis.wrapper);
+            } else {
+                println!("  ✓ {}", diagnosis.wrapper);
+            }
+            continue;
+        }
+
+        if use_color {
+            println!("  {} {}", "✗".red(), diagnosis.wrapper.bold());
+        } else {
+            println!("  ✗ {}", diagnosis.wrapper);
+        }
+        if diagnosis.timed_out {
+            println!("      Did not finish; it may be waiting on something outside PATH");
+        }
+        for missing in &diagnosis.missing {
+            match &missing.found_at {
+                Some(found) => println!(
+                    "      {} not found (it is at {})",
+                    missing.name,
+                    found.display()
+                ),
+                None => println!("      {} not found anywhere on your PATH", missing.name),
+            }
+        }
+        if diagnosis.missing.is_empty() && !diagnosis.stderr.is_empty() {
+            for line in diagnosis.stderr.lines().take(5) {
+                println!("      {}", line);
+            }
+        }
+    }
+    println!();
+
+    if diagnoses.iter().all(|diagnosis| diagnosis.reachable()) {
+        if use_color {
+            println!("{}", "✅ Every wrapper found what it needed".green().bold());
+        } else {
+            println!("✅ Every wrapper found what it needed");
+        }
+        return Ok(true);
+    }
+
+    let dirs = hook_env::suggested_path_dirs(&diagnoses);
+    if dirs.is_empty() {
+        println!(
+            "Install the missing commands, or run 'catalyst install-binaries' for hook binaries"
+        );
+        return Ok(false);
+    }
+
+    println!("Adding these directories to the wrappers' PATH would fix it:");
+    for dir in &dirs {
+        println!("  {}", dir);
+    }
+    let apply = io::stdin().is_terminal()
+        && Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(
+                "Add them to [wrappers] path in catalyst.toml and regenerate the wrappers?",
+            )
+            .default(true)
+            .interact()
+            .context("Failed to get PATH fix choice")?;
+    if !apply {
+        println!(
+            "  Or run: catalyst config set wrappers.path '{}'",
+            toml::Value::Array(dirs.into_iter().map(toml::Value::String).collect())
+        );
+        return Ok(false);
+    }
+
+    let regenerated = hook_env::add_wrapper_path(target_dir, &dirs, platform)?;
+    for wrapper in &regenerated {
+        println!("  ✓ Regenerated {}", wrapper);
+    }
+    println!("  Hand-written wrappers need the directories added to their own PATH");
+    println!();
+
+    let after = hook_env::diagnose_wrappers(target_dir, platform)?;
+    let fixed = after.iter().all(|diagnosis| diagnosis.reachable());
+    if fixed {
+        if use_color {
+            println!("{}", "✅ Every wrapper found what it needed".green().bold());
+        } else {
+            println!("✅ Every wrapper found what it needed");
+        }
+    } else if use_color {
+        println!(
+            "{}",
+            "⚠️  Some wrappers still fail; run this check again for details"
+                .yellow()
+                .bold()
+        );
+    } else {
+        println!("⚠️  Some wrappers still fail; run this check again for details");
+    }
+    Ok(fixed)
+}
+
+/// Decide whether `status --fix` may add missing hook entries to settings.json
+///
+/// settings.json is often hand-edited, so this asks first unless `--yes` was
+/// given. Without a terminal to ask on, the entries are left alone.
+fn confirm_settings_repair(
+    report: &catalyst_cli::types::StatusReport,
+    yes: bool,
+    use_color: bool,
+) -> Result<bool> {
+    let missing = report
+        .issues
+        .iter()
+        .filter(|i| i.kind == catalyst_cli::types::IssueKind::HookEntryMissing)
+        .count();
+    let commands = report
+        .powershell
+        .as_ref()
+        .map_or(0, |status| status.commands.len());
+    if missing == 0 && commands == 0 {
+        return Ok(false);
+    }
+    if yes {
+        return Ok(true);
+    }
+    if !io::stdin().is_terminal() {
+        let msg = "⚠️  Skipping settings.json repair (rerun with --yes to allow it)";
+        if use_color {
+            println!("{}", msg.yellow());
+        } else {
+            println!("{}", msg);
+        }
+        return Ok(false);
+    }
+
+    let mut changes = Vec::new();
+    if missing > 0 {
+        changes.push(format!(
+            "add {} missing hook {}",
+            missing,
+            if missing == 1 { "entry" } else { "entries" }
+        ));
+    }
+    if commands > 0 {
+        changes.push(format!(
+            "rewrite {} PowerShell hook {}",
+            commands,
+            if commands == 1 { "command" } else { "commands" }
+        ));
+    }
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Repair settings.json ({})?", changes.join(", ")))
+        .default(true)
         .interact()
-        .context("Failed to get skill selection")?;
+        .context("Failed to get settings.json repair confirmation")
+}
+
+fn main() {
+    // Check for NO_COLOR environment variable and TTY
+    let use_color = env::var("NO_COLOR").is_err() && io::stdout().is_terminal();
+
+    // clap exits 2 on bad arguments, which would read as "warnings"
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            let code = if e.use_stderr() {
+                ExitCode::Usage
+            } else {
+                ExitCode::Ok
+            };
+            std::process::exit(code.code());
+        }
+    };
+
+    if cli.timings {
+        timings::enable();
+    }
+    if cli.wait {
+        lock::wait_for_locks();
+    }
+
+    let display = match DisplayMode::from_str(&cli.display) {
+        Ok(mode) => mode,
+        Err(e) => {
+            if use_color {
+                eprintln!("{}", format!("❌ {}", e).red().bold());
+            } else {
+                eprintln!("❌ {}", e);
+            }
+            std::process::exit(ExitCode::Usage.code());
+        }
+    };
+    // Only the text mode is colored
+    let use_color = use_color && display == DisplayMode::Text;
+    progress::set_verbosity(cli.verbosity());
+    match display {
+        _ if cli.quiet => progress::set_reporter(Arc::new(QuietReporter)),
+        // Keep stdout for the JSON report itself
+        DisplayMode::Json => progress::set_reporter(Arc::new(JsonLinesReporter::stderr())),
+        // A redrawn progress bar is noise to a screen reader
+        DisplayMode::Accessible => progress::set_reporter(Arc::new(TerminalReporter::plain())),
+        DisplayMode::Text | DisplayMode::Table => {}
+    }
+
+    let result = run(cli.command, use_color, display);
+    if let Err(e) = result {
+        eprintln!("Error: {:?}", e);
+        exit(error_exit_code(&e));
+    }
+    print_timings();
+}
+
+/// Print a command's report; `--quiet` leaves out all but the JSON one
+fn print_report(report: &impl Displayable, display: DisplayMode, use_color: bool) -> Result<()> {
+    if display == DisplayMode::Json || !progress::is_quiet() {
+        print!("{}", report.render(display, use_color)?);
+    }
+    Ok(())
+}
+
+/// Print the files a `--diff` run would change: unified diffs, or a JSON
+/// array with `--display json`
+fn print_diffs(diffs: &[FileDiff], display: DisplayMode, use_color: bool) -> Result<()> {
+    if display == DisplayMode::Json {
+        println!("{}", serde_json::to_string_pretty(diffs)?);
+    } else {
+        print!("{}", preview::render(diffs, use_color));
+    }
+    Ok(())
+}
+
+/// Print how writing `settings` to `path` would change the file
+fn print_settings_diff(
+    path: &str,
+    settings: &ClaudeSettings,
+    display: DisplayMode,
+    use_color: bool,
+) -> Result<()> {
+    let old = match std::fs::read_to_string(path) {
+        Ok(text) => Some(text),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path)),
+    };
+    // Same serialization as ClaudeSettings::write
+    let new = serde_json::to_string_pretty(settings)?;
+    let diffs: Vec<FileDiff> = preview::diff_file(path, old.as_deref(), &new)
+        .into_iter()
+        .collect();
+    print_diffs(&diffs, display, use_color)
+}
+
+/// Print the `--timings` breakdown, if enabled
+fn print_timings() {
+    if let Some(breakdown) = timings::finish() {
+        eprintln!();
+        eprint!("{}", breakdown);
+    }
+}
+
+/// Exit code for a failed command, from its `CatalystError` if it has one
+fn error_exit_code(e: &anyhow::Error) -> ExitCode {
+    e.downcast_ref::<CatalystError>()
+        .map_or(ExitCode::Failure, ExitCode::for_error)
+}
+
+/// Exit the process, printing the `--timings` breakdown first
+fn exit(code: ExitCode) -> ! {
+    print_timings();
+    std::process::exit(code.code())
+}
+
+/// Run a parsed command
+fn run(command: Commands, use_color: bool, display: DisplayMode) -> Result<()> {
+    match command {
+        Commands::Init {
+            path,
+            interactive,
+            force,
+            reconcile,
+            from_lock,
+            all,
+            no_default_skills,
+            profile,
+            dry_run,
+            reset_permissions,
+            library,
+            no_scripts,
+            strict_skills,
+            tracker_backend,
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+
+            // Check if binaries are installed (a dry run only warns)
+            let platform = catalyst_cli::types::Platform::detect();
+            if let Err(e) = check_binaries_installed(platform) {
+                if dry_run {
+                    if !progress::is_quiet() {
+                        if use_color {
+                            eprintln!("{}", format!("⚠️  {}", e).yellow());
+                        } else {
+                            eprintln!("⚠️  {}", e);
+                        }
+                        eprintln!();
+                    }
+                } else {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            }
+
+            // Project defaults from catalyst.toml, skills also from the
+            // user config
+            let configs = CatalystConfig::load(&target_dir)
+                .and_then(|project| Ok((project, CatalystConfig::load_user()?)));
+            // The chosen profile, and defaults for the detected project type
+            let user_profiles = profiles::user_profile_dir();
+            let detected_stacks = stack::detect_stacks(&target_dir);
+            let lookups = configs.and_then(|(project, user)| {
+                let init_profile = match &profile {
+                    Some(name) => Some(profiles::find_profile(name, user_profiles.as_deref())?),
+                    None => None,
+                };
+                let detected = stack::stack_defaults(&detected_stacks, user_profiles.as_deref())?;
+                Ok((project, user, init_profile, detected))
+            });
+            let (mut project_config, user_config, init_profile, detected) = match lookups {
+                Ok(lookups) => lookups,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
+            // A profile replaces the hook choices from catalyst.toml
+            if let Some(profile) = &init_profile {
+                project_config.init.install_hooks = profile.install_hooks;
+                project_config.init.install_tracker = profile.install_tracker;
+            }
+            let (default_skills, default_source) = if no_default_skills {
+                (Vec::new(), SkillSelection::NoDefaults)
+            } else if let Some(profile) = &init_profile {
+                (profile.skills.clone(), SkillSelection::Profile)
+            } else {
+                config::default_init_skills(&project_config, user_config.as_ref())
+            };
+            // A detected project type replaces the built-in default skills
+            let (default_skills, default_source) =
+                if default_source == SkillSelection::BuiltIn && !detected.skills.is_empty() {
+                    (detected.skills.clone(), SkillSelection::Detected)
+                } else {
+                    (default_skills, default_source)
+                };
+            let install_check_hook = *project_config
+                .init
+                .install_check_hook
+                .get_or_insert(detected.install_check_hook);
+
+            // Build config based on mode
+            let mut config = if interactive {
+                // Interactive mode - guide user through setup
+                match run_interactive_init(
+                    &target_dir,
+                    force,
+                    &project_config,
+                    &default_skills,
+                    &detected_stacks,
+                )? {
+                    Some(cfg) => cfg,
+                    None => {
+                        // User cancelled
+                        if use_color {
+                            say!("{}", "❌ Initialization cancelled".yellow());
+                        } else {
+                            say!("❌ Initialization cancelled");
+                        }
+                        return Ok(());
+                    }
+                }
+            } else {
+                // Non-interactive mode - use catalyst.toml and flags
+                let (skills, skill_selection) = if all {
+                    (
+                        AVAILABLE_SKILLS.iter().map(|s| s.to_string()).collect(),
+                        SkillSelection::Explicit,
+                    )
+                } else {
+                    (default_skills, default_source)
+                };
+
+                InitConfig {
+                    directory: target_dir.clone(),
+                    install_hooks: project_config.init.install_hooks,
+                    install_tracker: project_config.init.install_tracker,
+                    install_check_hook,
+                    skills,
+                    force,
+                    dry_run,
+                    reset_permissions,
+                    skills_library: None,
+                    strict_skills: project_config.init.strict_skills,
+                    skill_selection,
+                    settings: serde_json::Map::new(),
+                    reconcile: false,
+                    from_lock: false,
+                    tracker_backend: None,
+                }
+            };
+            config.dry_run = dry_run;
+            config.reset_permissions = reset_permissions;
+            config.reconcile = reconcile;
+            if let Some(profile) = init_profile {
+                config.settings = profile.settings;
+            } else if default_source == SkillSelection::Detected {
+                config.settings = detected.settings;
+            }
+            if let Some(mode) = strict_skills {
+                config.strict_skills = StrictSkills::from_str(&mode)?;
+            }
+            if library || project_config.init.skill_source == SkillSource::Library {
+                config.skills_library = Some(catalyst_cli::library::library_dir()?);
+            }
+            if from_lock {
+                let applied = lockfile::read(&Fs::read_only(), &target_dir).and_then(|locked| {
+                    locked
+                        .ok_or_else(|| {
+                            CatalystError::InvalidConfig(format!(
+                                "No {} in {}; run 'catalyst lock' in an initialized project first",
+                                LOCK_FILE,
+                                target_dir.display()
+                            ))
+                        })?
+                        .apply_to(&mut config)
+                });
+                if let Err(e) = applied {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            }
+            if let Some(backend) = tracker_backend {
+                config.tracker_backend = Some(TrackerBackend::from_str(&backend)?);
+            }
+
+            // Run initialization
+            if display == DisplayMode::Text {
+                if use_color {
+                    say!("{}", "🚀 Initializing Catalyst...".cyan().bold());
+                } else {
+                    say!("🚀 Initializing Catalyst...");
+                }
+                if !interactive && !detected_stacks.is_empty() {
+                    say!("   Detected: {}", join_stacks(&detected_stacks));
+                }
+                say!();
+            }
+
+            // Clean up lock and temp files if interrupted mid-way
+            if !config.dry_run {
+                signals::install_handler();
+                signals::set_resume_hint(if config.reconcile {
+                    "Re-run 'catalyst init --reconcile' to finish initialization"
+                } else {
+                    "Re-run 'catalyst init --force' to finish initialization"
+                });
+            }
+
+            match init::initialize(&config) {
+                Ok(report) if report.dry_run => {
+                    print_report(&report, display, use_color)?;
+                }
+                Ok(mut report) => {
+                    // Skill setup scripts run last, once everything is in place
+                    report.scripts = run_skill_scripts(
+                        &target_dir,
+                        &report.installed_skills,
+                        platform,
+                        no_scripts,
+                    )?;
+                    print_report(&report, display, use_color)?;
+                }
+                Err(e) => {
+                    // Show what was done before strict skills mode failed
+                    if let CatalystError::StrictSkillsFailed { report, .. } = &e {
+                        print_report(report.as_ref(), display, use_color)?;
+                        say!();
+                    }
+                    if use_color {
+                        eprintln!(
+                            "{}",
+                            format!("❌ Initialization failed: {}", e).red().bold()
+                        );
+                    } else {
+                        eprintln!("❌ Initialization failed: {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            }
+        }
+
+        Commands::Status {
+            path,
+            fix,
+            yes,
+            diff,
+            strict,
+            run_healthchecks,
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let use_color = use_color && !strict;
+
+            // Detect platform
+            let platform = catalyst_cli::types::Platform::detect();
+
+            // Validate installation
+            let probes = catalyst_cli::status::Probes {
+                healthchecks: run_healthchecks,
+                ..catalyst_cli::status::Probes::STANDARD
+            };
+            match catalyst_cli::status::check_installation(&target_dir, platform, probes) {
+                Ok(mut report) => {
+                    if strict {
+                        catalyst_cli::status::apply_strict(&mut report);
+                    }
+
+                    // If --fix flag provided and there are auto-fixable issues, attempt fixes
+                    let mut fixed_issues = Vec::new();
+                    if diff {
+                        let repair_settings = report.issues.iter().any(|i| i.auto_fixable)
+                            && confirm_settings_repair(&report, yes, use_color)?;
+                        // The fixes run against the copy's own report, so
+                        // every path they touch is inside the copy
+                        let previewed = preview::preview_changes(&target_dir, |scratch| {
+                            let report = catalyst_cli::status::check_installation(
+                                scratch,
+                                platform,
+                                catalyst_cli::status::Probes::STANDARD,
+                            )?;
+                            catalyst_cli::status::auto_fix(
+                                scratch,
+                                platform,
+                                &report,
+                                repair_settings,
+                            )
+                        });
+                        match previewed {
+                            Ok((_, diffs)) => {
+                                print_diffs(&diffs, display, use_color)?;
+                                return Ok(());
+                            }
+                            Err(e) => {
+                                if use_color {
+                                    eprintln!(
+                                        "{}",
+                                        format!("❌ Auto-fix preview failed: {}", e).red().bold()
+                                    );
+                                } else {
+                                    eprintln!("❌ Auto-fix preview failed: {}", e);
+                                }
+                                exit(ExitCode::for_error(&e));
+                            }
+                        }
+                    }
+                    if fix && report.issues.iter().any(|i| i.auto_fixable) {
+                        let repair_settings = confirm_settings_repair(&report, yes, use_color)?;
+                        match catalyst_cli::status::auto_fix(
+                            &target_dir,
+                            platform,
+                            &report,
+                            repair_settings,
+                        ) {
+                            Ok(fixes) => {
+                                fixed_issues = fixes;
+                            }
+                            Err(e) => {
+                                if use_color {
+                                    eprintln!(
+                                        "{}",
+                                        format!("❌ Auto-fix failed: {}", e).red().bold()
+                                    );
+                                } else {
+                                    eprintln!("❌ Auto-fix failed: {}", e);
+                                }
+                                // Another command is changing the project, so this report may be stale
+                                if let CatalystError::InitInProgress { .. } = e {
+                                    exit(ExitCode::LockContention);
+                                }
+                            }
+                        }
+                    }
+
+                    // Display status report
+                    let status = StatusDisplay {
+                        report: &report,
+                        fixed_issues: &fixed_issues,
+                    };
+                    print_report(&status, display, use_color)?;
+
+                    // Exit with error code if status is not ok
+                    if report.level != catalyst_cli::types::StatusLevel::Ok {
+                        exit(ExitCode::for_status(report.level));
+                    }
+                }
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ Status check failed: {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ Status check failed: {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            }
+        }
+
+        Commands::Doctor {
+            path,
+            install_method,
+            hook_env,
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+
+            if let Some(method) = install_method {
+                let method = InstallMethod::from_str(&method)?;
+                let bin_dir = catalyst_cli::validation::get_binary_directory()?;
+                catalyst_cli::validation::write_install_method(&bin_dir, method)?;
+                if use_color {
+                    println!(
+                        "{} {}",
+                        "✅ Recorded install method:".green(),
+                        method.to_string().bold()
+                    );
+                } else {
+                    println!("✅ Recorded install method: {}", method);
+                }
+                println!();
+            }
+
+            if hook_env {
+                if !run_hook_env_check(&target_dir, use_color)? {
+                    exit(ExitCode::Errors);
+                }
+                return Ok(());
+            }
+
+            if !io::stdin().is_terminal() {
+                if use_color {
+                    eprintln!(
+                        "{}",
+                        "❌ catalyst doctor is interactive; use 'catalyst status --fix' in scripts"
+                            .red()
+                            .bold()
+                    );
+                } else {
+                    eprintln!(
+                        "❌ catalyst doctor is interactive; use 'catalyst status --fix' in scripts"
+                    );
+                }
+                exit(ExitCode::Usage);
+            }
+
+            let level = run_doctor(&target_dir, use_color)?;
+            if level != catalyst_cli::types::StatusLevel::Ok {
+                exit(ExitCode::for_status(level));
+            }
+        }
+
+        Commands::Fix {
+            id,
+            path,
+            component,
+            json,
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+
+            let Some(kind) = catalyst_cli::types::IssueKind::from_id(&id) else {
+                let mut msg = format!("Unknown issue ID '{}'", id);
+                if let Some(suggestion) = catalyst_cli::explain::suggest_id(&id) {
+                    msg.push_str(&format!(". Did you mean '{}'?", suggestion));
+                }
+                if use_color {
+                    eprintln!("{}", format!("❌ {}", msg).red().bold());
+                } else {
+                    eprintln!("❌ {}", msg);
+                }
+                exit(ExitCode::Usage);
+            };
+
+            let platform = catalyst_cli::types::Platform::detect();
+            let probes = catalyst_cli::status::Probes::STANDARD;
+            let result = catalyst_cli::status::check_installation(&target_dir, platform, probes)
+                .and_then(|report| {
+                    catalyst_cli::status::fix_issues_by_kind(
+                        &target_dir,
+                        platform,
+                        &report,
+                        kind,
+                        component.as_deref(),
+                    )
+                });
+            let fix_report = match result {
+                Ok(fix_report) => fix_report,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&fix_report)?);
+            } else {
+                display_targeted_fix_report(&fix_report, use_color);
+            }
+            if !fix_report.failed.is_empty() {
+                exit(ExitCode::Failure);
+            }
+        }
+
+        Commands::Update {
+            path,
+            force,
+            resume,
+            rollback,
+            no_scripts,
+            migrate_skills,
+            skills,
+            diff,
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+
+            if diff {
+                // Skill renames and post-install scripts are left out; they
+                // ask first and change things outside these files
+                let (_, diffs) = preview::preview_changes(&target_dir, |scratch| {
+                    if skills.is_empty() {
+                        update::update(scratch, force)
+                    } else {
+                        update::update_skills(scratch, &skills, force)
+                    }
+                })?;
+                print_diffs(&diffs, display, use_color)?;
+                return Ok(());
+            }
+
+            // An interrupted update must be resolved before starting a new one
+            let action = if resume {
+                InterruptedUpdateAction::Resume
+            } else if rollback {
+                InterruptedUpdateAction::Rollback
+            } else if update::UpdateJournal::load(&target_dir)?.is_some()
+                && io::stdin().is_terminal()
+            {
+                prompt_interrupted_update(&target_dir)?
+            } else {
+                InterruptedUpdateAction::None
+            };
+
+            if action == InterruptedUpdateAction::Rollback {
+                let restored = update::rollback_update(&target_dir)?;
+                for item in &restored {
+                    say!("  ✓ {}", item);
+                }
+                if use_color {
+                    say!("{}", "✅ Interrupted update rolled back".green().bold());
+                } else {
+                    say!("✅ Interrupted update rolled back");
+                }
+                return Ok(());
+            }
+
+            if display == DisplayMode::Text {
+                if use_color {
+                    say!("{}", "🔄 Updating Catalyst...".cyan().bold());
+                } else {
+                    say!("🔄 Updating Catalyst...");
+                }
+                say!();
+            }
+
+            // Clean up staging files if interrupted; the journal allows resuming
+            signals::install_handler();
+            signals::set_resume_hint(
+                "Run 'catalyst update --resume' to finish or 'catalyst update --rollback' to undo",
+            );
+
+            // Run update
+            let mut report = if action == InterruptedUpdateAction::Resume {
+                update::resume_update(&target_dir)?
+            } else if !skills.is_empty() {
+                update::update_skills(&target_dir, &skills, force)?
+            } else {
+                migrate_deprecated_skills(&target_dir, migrate_skills, use_color)?;
+                update::update(&target_dir, force)?
+            };
+            let changed_skills: Vec<String> = report
+                .updated_skills
+                .iter()
+                .chain(&report.library_skills)
+                .cloned()
+                .collect();
+            report.scripts = run_skill_scripts(
+                &target_dir,
+                &changed_skills,
+                catalyst_cli::types::Platform::detect(),
+                no_scripts,
+            )?;
+
+            // Display results
+            print_report(&report, display, use_color)?;
+        }
+
+        Commands::Sync {
+            path,
+            yes,
+            no_scripts,
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let platform = catalyst_cli::types::Platform::detect();
+            if let Err(e) = check_binaries_installed(platform) {
+                if use_color {
+                    eprintln!("{}", format!("❌ {}", e).red().bold());
+                } else {
+                    eprintln!("❌ {}", e);
+                }
+                exit(ExitCode::for_error(&e));
+            }
+
+            if display == DisplayMode::Text {
+                if use_color {
+                    println!("{}", "🔄 Syncing Catalyst...".cyan().bold());
+                } else {
+                    println!("🔄 Syncing Catalyst...");
+                }
+                println!();
+            }
+
+            // Init and update both clean up after themselves; re-running
+            // sync picks up an interrupted update where it stopped
+            signals::install_handler();
+            signals::set_resume_hint("Re-run 'catalyst sync' to finish");
+
+            let options = sync::SyncOptions {
+                directory: target_dir.clone(),
+                platform,
+                repair_settings: yes,
+            };
+            let mut report = match sync::sync(&options) {
+                Ok(report) => report,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ Sync failed: {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ Sync failed: {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
+
+            // Setup scripts for whatever sync installed or changed
+            if let Some(init) = report.init.as_mut() {
+                init.scripts =
+                    run_skill_scripts(&target_dir, &init.installed_skills, platform, no_scripts)?;
+            }
+            if let Some(update) = report.update.as_mut() {
+                let changed: Vec<String> = update
+                    .updated_skills
+                    .iter()
+                    .chain(&update.library_skills)
+                    .cloned()
+                    .collect();
+                update.scripts = run_skill_scripts(&target_dir, &changed, platform, no_scripts)?;
+            }
+
+            print!("{}", report.render(display, use_color)?);
+            let code = report.exit_code();
+            if code != ExitCode::Ok {
+                exit(code);
+            }
+        }
+
+        Commands::InstallBinaries {
+            from,
+            build,
+            sqlite,
+        } => {
+            let source_dir = match (build, from) {
+                (Some(checkout), _) => {
+                    if use_color {
+                        println!(
+                            "{}",
+                            format!("🔨 Building hook binaries in {}...", checkout.display())
+                                .cyan()
+                                .bold()
+                        );
+                    } else {
+                        println!("🔨 Building hook binaries in {}...", checkout.display());
+                    }
+                    binaries::build_binaries(&checkout, sqlite)?
+                }
+                (None, Some(dir)) => dir,
+                (None, None) => binaries::default_source_dir()
+                    .context("Could not locate the catalyst executable; pass --from DIR")?,
+            };
+
+            let bin_dir = catalyst_cli::validation::get_binary_directory()?;
+            let platform = catalyst_cli::types::Platform::detect();
+            let report = binaries::install_binaries(&source_dir, &bin_dir, platform)?;
+
+            if use_color {
+                println!(
+                    "{}",
+                    format!("Installed into {}:", report.bin_dir.display())
+                        .green()
+                        .bold()
+                );
+            } else {
+                println!("Installed into {}:", report.bin_dir.display());
+            }
+            for binary in &report.installed {
+                let version = binary.version.as_deref().unwrap_or("version unknown");
+                println!("  ✓ {} ({})", binary.name, version);
+            }
+            for name in &report.skipped {
+                println!("  - {} (not built; use --build <CHECKOUT> --sqlite)", name);
+            }
+            println!();
+
+            for warning in &report.warnings {
+                if use_color {
+                    println!("{}", format!("⚠️  {}", warning).yellow());
+                } else {
+                    println!("⚠️  {}", warning);
+                }
+            }
+            if use_color {
+                println!("{}", "✅ Hook binaries installed".green().bold());
+            } else {
+                println!("✅ Hook binaries installed");
+            }
+        }
+
+        Commands::SelfUpdate { check } => {
+            // Package-managed installs must be upgraded by their package manager
+            let bin_dir = catalyst_cli::validation::get_binary_directory()?;
+            let method =
+                catalyst_cli::validation::detect_install_method(&Fs::read_only(), &bin_dir);
+            if !check && matches!(method, InstallMethod::Homebrew | InstallMethod::Cargo) {
+                let platform = catalyst_cli::types::Platform::detect();
+                let command = catalyst_cli::validation::upgrade_command(method, platform);
+                if use_color {
+                    eprintln!(
+                        "{}",
+                        format!("❌ Catalyst was installed with {}; update it with:", method)
+                            .red()
+                            .bold()
+                    );
+                } else {
+                    eprintln!("❌ Catalyst was installed with {}; update it with:", method);
+                }
+                eprintln!("   {}", command);
+                exit(ExitCode::Failure);
+            }
+
+            let report = catalyst_cli::self_update::self_update(check)?;
+            if !report.update_available {
+                if use_color {
+                    println!(
+                        "{}",
+                        format!(
+                            "✅ catalyst {} is the latest version",
+                            report.current_version
+                        )
+                        .green()
+                        .bold()
+                    );
+                } else {
+                    println!(
+                        "✅ catalyst {} is the latest version",
+                        report.current_version
+                    );
+                }
+            } else if !report.updated {
+                let message = format!(
+                    "⬆️  catalyst {} is available (installed: {})",
+                    report.latest_version, report.current_version
+                );
+                if use_color {
+                    println!("{}", message.yellow().bold());
+                } else {
+                    println!("{}", message);
+                }
+                if report.asset.is_none() {
+                    println!("   No release archive is published for {}", report.target);
+                } else {
+                    println!("   Run 'catalyst self-update' to install it");
+                }
+            } else {
+                let message = format!(
+                    "✅ Updated catalyst {} → {}",
+                    report.current_version, report.latest_version
+                );
+                if use_color {
+                    println!("{}", message.green().bold());
+                } else {
+                    println!("{}", message);
+                }
+                println!("   {}", report.executable.display());
+                if report.signature_verified {
+                    println!("   ✓ Checksum and signature verified");
+                } else {
+                    println!("   ✓ Checksum verified");
+                }
+                println!("   Run 'catalyst install-binaries' to update the hook binaries");
+            }
+        }
+
+        Commands::Lock { path } => {
+            let target_dir = project::resolve_target_dir(path);
+            let locked = OperationLock::acquire(&target_dir).and_then(|_guard| {
+                let lock = Lockfile::capture(
+                    &Fs::read_only(),
+                    &target_dir,
+                    catalyst_cli::types::Platform::detect(),
+                )?;
+                lockfile::write(&target_dir, &lock)?;
+                Ok(lock)
+            });
+            match locked {
+                Ok(lock) if display == DisplayMode::Json => {
+                    println!("{}", serde_json::to_string_pretty(&lock)?);
+                }
+                Ok(lock) => {
+                    let summary = format!(
+                        "🔒 Wrote {} ({} skills, {} wrappers, {} binaries)",
+                        LOCK_FILE,
+                        lock.skills.len(),
+                        lock.wrappers.len(),
+                        lock.binaries.len()
+                    );
+                    if use_color {
+                        println!("{}", summary.green());
+                    } else {
+                        println!("{}", summary);
+                    }
+                }
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            }
+        }
+
+        Commands::InstallManifest { format } => {
+            let rendered = format
+                .parse::<ManifestFormat>()
+                .and_then(|format| install_manifest::install_manifest()?.render(format));
+            match rendered {
+                Ok(rendered) => print!("{}", rendered),
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            }
+        }
+
+        Commands::Settings { command } => {
+            match command {
+                SettingsCommands::Read { path } => {
+                    let settings = ClaudeSettings::read(&path)?;
+                    let json = serde_json::to_string_pretty(&settings)?;
+                    println!("{}", json);
+                }
+
+                SettingsCommands::Validate { path, strict } => {
+                    let code = validate_settings_file(&path, strict, use_color);
+                    if code != ExitCode::Ok {
+                        exit(code);
+                    }
+                }
+
+                SettingsCommands::AddHook {
+                    path,
+                    event,
+                    command,
+                    matcher,
+                    dry_run,
+                    diff,
+                } => {
+                    // Load existing settings or create new
+                    let (mut settings, file_existed) = read_settings_or_default(&path)?;
+
+                    // Parse event string into HookEvent enum
+                    let hook_event = HookEvent::from_str(&event)?;
+
+                    let hook_config = HookConfig {
+                        matcher: matcher.clone(),
+                        hooks: vec![Hook {
+                            r#type: "command".to_string(),
+                            command: command.clone(),
+                        }],
+                    };
+
+                    settings.add_hook(hook_event, hook_config)?;
+
+                    if diff {
+                        print_settings_diff(&path, &settings, display, use_color)?;
+                    } else if dry_run {
+                        if use_color {
+                            say!("{}", "🔍 Dry run - would write:".yellow().bold());
+                        } else {
+                            say!("🔍 Dry run - would write:");
+                        }
+                        println!("{}", serde_json::to_string_pretty(&settings)?);
+                    } else {
+                        settings.write(&path)?;
+
+                        if use_color {
+                            if file_existed {
+                                say!(
+                                    "{} {}",
+                                    "✅ Hook added to existing file:".green().bold(),
+                                    path
+                                );
+                            } else {
+                                say!(
+                                    "{} {}",
+                                    "✅ Created new settings file:".green().bold(),
+                                    path
+                                );
+                            }
+                            say!("  {} {}", "Event:".cyan(), event);
+                            say!("  {} {}", "Command:".cyan(), command);
+                            if let Some(m) = matcher {
+                                say!("  {} {}", "Matcher:".cyan(), m);
+                            }
+                        } else {
+                            if file_existed {
+                                say!("✅ Hook added to existing file: {}", path);
+                            } else {
+                                say!("✅ Created new settings file: {}", path);
+                            }
+                            say!("  Event: {}", event);
+                            say!("  Command: {}", command);
+                            if let Some(m) = matcher {
+                                say!("  Matcher: {}", m);
+                            }
+                        }
+                    }
+                }
+
+                SettingsCommands::RemoveHook {
+                    path,
+                    event,
+                    pattern,
+                    dry_run,
+                    diff,
+                } => {
+                    let mut settings = ClaudeSettings::read(&path)?;
+
+                    // Parse event string into HookEvent enum
+                    let hook_event = HookEvent::from_str(&event)?;
+
+                    settings.remove_hook(hook_event, &pattern);
+
+                    if diff {
+                        print_settings_diff(&path, &settings, display, use_color)?;
+                    } else if dry_run {
+                        if use_color {
+                            say!("{}", "🔍 Dry run - would write:".yellow().bold());
+                        } else {
+                            say!("🔍 Dry run - would write:");
+                        }
+                        println!("{}", serde_json::to_string_pretty(&settings)?);
+                    } else {
+                        settings.write(&path)?;
+                        if use_color {
+                            say!("{} {}", "✅ Hooks removed from".green().bold(), path);
+                        } else {
+                            say!("✅ Hooks removed from {}", path);
+                        }
+                    }
+                }
+
+                SettingsCommands::Merge {
+                    base,
+                    merge,
+                    output,
+                    dry_run,
+                    diff,
+                } => {
+                    let mut base_settings = ClaudeSettings::read(&base)?;
+                    let merge_settings = ClaudeSettings::read(&merge)?;
+
+                    base_settings.merge(merge_settings);
+
+                    // Validate merged result
+                    base_settings.validate()?;
+
+                    let output_path = output.as_deref().unwrap_or(&base);
+
+                    if diff {
+                        print_settings_diff(output_path, &base_settings, display, use_color)?;
+                    } else if dry_run {
+                        if use_color {
+                            say!(
+                                "{} {}:",
+                                "🔍 Dry run - would write to".yellow().bold(),
+                                output_path
+                            );
+                        } else {
+                            say!("🔍 Dry run - would write to {}:", output_path);
+                        }
+                        println!("{}", serde_json::to_string_pretty(&base_settings)?);
+                    } else {
+                        base_settings.write(output_path)?;
+                        if use_color {
+                            say!("{}", "✅ Settings merged successfully".green().bold());
+                            say!("  {} {}", "Base file:".cyan(), base);
+                            say!("  {} {}", "Merged from:".cyan(), merge);
+                            say!("  {} {}", "Output:".cyan(), output_path);
+                        } else {
+                            say!("✅ Settings merged successfully");
+                            say!("  Base file: {}", base);
+                            say!("  Merged from: {}", merge);
+                            say!("  Output: {}", output_path);
+                        }
+                    }
+                }
+
+                SettingsCommands::Effective { path, json } => {
+                    let target_dir = project::resolve_target_dir(path);
+                    match ClaudeSettings::resolve(&target_dir) {
+                        Ok(resolved) if json => {
+                            println!("{}", serde_json::to_string_pretty(&resolved)?);
+                        }
+                        Ok(resolved) => display_effective_settings(&resolved, use_color),
+                        Err(e) => {
+                            if use_color {
+                                eprintln!("{}", format!("❌ {:#}", e).red().bold());
+                            } else {
+                                eprintln!("❌ {:#}", e);
+                            }
+                            exit(error_exit_code(&e));
+                        }
+                    }
+                }
+
+                SettingsCommands::Mcp {
+                    path,
+                    enable_all,
+                    disable_all,
+                    dry_run,
+                    command,
+                } => {
+                    let enable_all = match (enable_all, disable_all) {
+                        (true, _) => Some(true),
+                        (_, true) => Some(false),
+                        _ => None,
+                    };
+                    if let Err(e) = run_mcp_command(&path, command, enable_all, dry_run, use_color)
+                    {
+                        if use_color {
+                            eprintln!("{}", format!("❌ {}", e).red().bold());
+                        } else {
+                            eprintln!("❌ {}", e);
+                        }
+                        exit(error_exit_code(&e));
+                    }
+                }
+            }
+        }
+
+        Commands::Config { command } => {
+            if let Err(e) = run_config_command(command, use_color) {
+                if use_color {
+                    eprintln!("{}", format!("❌ {}", e).red().bold());
+                } else {
+                    eprintln!("❌ {}", e);
+                }
+                exit(error_exit_code(&e));
+            }
+        }
+
+        Commands::Env { path, json } => {
+            let target_dir = project::resolve_target_dir(path);
+            let report =
+                catalyst_cli::env::collect(&target_dir, catalyst_cli::types::Platform::detect());
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                display_env_report(&report, use_color);
+            }
+        }
+
+        Commands::Flags {
+            command: FlagsCommands::List { path },
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let project_config = match CatalystConfig::load(&target_dir) {
+                Ok(c) => c,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
+            display_flags(&EnabledFlags::resolve(&project_config), use_color);
+        }
+
+        Commands::Skills {
+            command: SkillsCommands::Lint { path, json },
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let report = match catalyst_cli::lint::lint_skill_rules(&target_dir) {
+                Ok(report) => report,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                display_lint_report(&report, use_color);
+            }
+
+            if report.has_errors() {
+                exit(ExitCode::Errors);
+            }
+        }
+
+        Commands::Skills {
+            command:
+                SkillsCommands::New {
+                    name,
+                    path,
+                    description,
+                    keywords,
+                    intent_patterns,
+                    path_patterns,
+                    no_prompt,
+                },
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let interactive = !no_prompt && std::io::stdin().is_terminal();
+            let skill = match new_skill_from_args(
+                name,
+                description,
+                keywords,
+                intent_patterns,
+                path_patterns,
+                interactive,
+            ) {
+                Ok(skill) => skill,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(error_exit_code(&e));
+                }
+            };
+
+            match catalyst_cli::scaffold::create_skill(&target_dir, &skill) {
+                Ok(skill_md) => {
+                    if use_color {
+                        println!("{}", format!("✅ Created skill '{}'", skill.name).green());
+                    } else {
+                        println!("✅ Created skill '{}'", skill.name);
+                    }
+                    println!("   {}", skill_md.display());
+                    println!("   Added to skill-rules.json; run 'catalyst skills lint' after editing its triggers");
+                }
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            }
+        }
+
+        Commands::Sessions {
+            command:
+                SessionsCommands::Report {
+                    session,
+                    last,
+                    json,
+                },
+        } => {
+            #[cfg(feature = "sqlite")]
+            {
+                use catalyst_cli::sessions;
+
+                let result = sessions::state_dir().and_then(|state_dir| {
+                    sessions::collect_reports(&state_dir, session.as_deref(), last)
+                });
+                let reports = match result {
+                    Ok(reports) => reports,
+                    Err(e) => {
+                        if use_color {
+                            eprintln!("{}", format!("❌ {}", e).red().bold());
+                        } else {
+                            eprintln!("❌ {}", e);
+                        }
+                        exit(ExitCode::for_error(&e));
+                    }
+                };
+
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&reports)?);
+                } else if reports.is_empty() {
+                    say!("No tracked sessions found. Is the file-change-tracker hook installed?");
+                } else if !progress::is_quiet() {
+                    for report in &reports {
+                        display_session_report(report, use_color);
+                    }
+                }
+            }
+
+            #[cfg(not(feature = "sqlite"))]
+            {
+                let _ = (session, last, json);
+                exit_needs_sqlite(use_color);
+            }
+        }
+
+        Commands::Sessions {
+            command:
+                SessionsCommands::Export {
+                    format,
+                    output,
+                    session,
+                    since,
+                    until,
+                    table,
+                },
+        } => {
+            #[cfg(feature = "sqlite")]
+            {
+                use catalyst_cli::sessions::{self, ExportFilter, ExportFormat, ExportTable};
+                use catalyst_cli::types::CatalystError;
+
+                let result = (|| {
+                    let format: ExportFormat = format.parse()?;
+                    let table: ExportTable = table.parse()?;
+                    let filter = ExportFilter {
+                        session,
+                        since: since
+                            .as_deref()
+                            .map(|value| sessions::parse_time_bound(value, false))
+                            .transpose()?,
+                        until: until
+                            .as_deref()
+                            .map(|value| sessions::parse_time_bound(value, true))
+                            .transpose()?,
+                    };
+                    let export = sessions::export(&sessions::state_dir()?, &filter)?;
+                    let content = match format {
+                        ExportFormat::Json => {
+                            serde_json::to_string_pretty(&export).map_err(CatalystError::Json)?
+                                + "\n"
+                        }
+                        ExportFormat::Csv => sessions::to_csv(&export, table),
+                    };
+                    Ok::<_, CatalystError>((export, content))
+                })();
+                let (export, content) = match result {
+                    Ok(result) => result,
+                    Err(e) => {
+                        if use_color {
+                            eprintln!("{}", format!("❌ {}", e).red().bold());
+                        } else {
+                            eprintln!("❌ {}", e);
+                        }
+                        exit(ExitCode::for_error(&e));
+                    }
+                };
+
+                match output {
+                    Some(path) => {
+                        init::write_file_atomic(&path, &content)?;
+                        let msg = format!(
+                            "✅ Exported {} sessions and {} file modifications to {}",
+                            export.sessions.len(),
+                            export.file_modifications.len(),
+                            path.display()
+                        );
+                        if use_color {
+                            say!("{}", msg.green().bold());
+                        } else {
+                            say!("{}", msg);
+                        }
+                    }
+                    None => print!("{}", content),
+                }
+            }
+
+            #[cfg(not(feature = "sqlite"))]
+            {
+                let _ = (format, output, session, since, until, table);
+                exit_needs_sqlite(use_color);
+            }
+        }
+
+        Commands::Sessions {
+            command:
+                SessionsCommands::Query {
+                    session,
+                    category,
+                    since,
+                    until,
+                    file_glob,
+                    risky,
+                    branch,
+                    format,
+                },
+        } => {
+            #[cfg(feature = "sqlite")]
+            {
+                use catalyst_cli::sessions::{self, ExportFilter, QueryFilter, QueryFormat};
+                use catalyst_cli::types::CatalystError;
+
+                let result = (|| {
+                    let format: QueryFormat = format.parse()?;
+                    let filter = QueryFilter {
+                        edits: ExportFilter {
+                            session,
+                            since: since
+                                .as_deref()
+                                .map(|value| sessions::parse_time_bound(value, false))
+                                .transpose()?,
+                            until: until
+                                .as_deref()
+                                .map(|value| sessions::parse_time_bound(value, true))
+                                .transpose()?,
+                        },
+                        category,
+                        file_glob,
+                        risky,
+                        branch,
+                    };
+                    let rows = sessions::query(&sessions::state_dir()?, &filter)?;
+                    Ok::<_, CatalystError>((format, rows))
+                })();
+                let (format, rows) = match result {
+                    Ok(result) => result,
+                    Err(e) => {
+                        if use_color {
+                            eprintln!("{}", format!("❌ {}", e).red().bold());
+                        } else {
+                            eprintln!("❌ {}", e);
+                        }
+                        exit(ExitCode::for_error(&e));
+                    }
+                };
+
+                match format {
+                    QueryFormat::Json => println!("{}", serde_json::to_string_pretty(&rows)?),
+                    QueryFormat::Csv => print!("{}", sessions::modifications_to_csv(&rows)),
+                    QueryFormat::Table if progress::is_quiet() => {}
+                    QueryFormat::Table if rows.is_empty() => println!("No tracked edits match."),
+                    QueryFormat::Table => display_modification_table(&rows, use_color),
+                }
+            }
+
+            #[cfg(not(feature = "sqlite"))]
+            {
+                let _ = (
+                    session, category, since, until, file_glob, risky, branch, format,
+                );
+                exit_needs_sqlite(use_color);
+            }
+        }
+
+        Commands::Sessions {
+            command: SessionsCommands::Push { path, all, dry_run },
+        } => {
+            use catalyst_cli::analytics::{self, PushOptions};
+            use catalyst_cli::config::CatalystConfig;
+            use catalyst_cli::modification_store;
+
+            let target_dir = project::resolve_target_dir(path);
+            let options = PushOptions { all, dry_run };
+            let result = CatalystConfig::load(&target_dir).and_then(|config| {
+                analytics::push(
+                    &config.analytics,
+                    &target_dir,
+                    &modification_store::state_dir()?,
+                    options,
+                )
+            });
+            let report = match result {
+                Ok(report) => report,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
+
+            if dry_run {
+                println!("{}", serde_json::to_string_pretty(&report.batches)?);
+                if !progress::is_quiet() {
+                    eprintln!(
+                        "Would send {} session summaries in {} requests to {}",
+                        report.sessions(),
+                        report.batches.len(),
+                        report.endpoint
+                    );
+                }
+            } else if report.batches.is_empty() {
+                say!("Nothing new to push");
+            } else {
+                let msg = format!(
+                    "✅ Pushed {} session summaries to {}",
+                    report.sessions(),
+                    report.endpoint
+                );
+                if use_color {
+                    say!("{}", msg.green().bold());
+                } else {
+                    say!("{}", msg);
+                }
+            }
+        }
+
+        Commands::Report {
+            path,
+            pr,
+            post,
+            session,
+            since,
+            json,
+        } => {
+            use catalyst_cli::modification_store;
+            use catalyst_cli::pr_report::{self, ReportScope};
+
+            let target_dir = project::resolve_target_dir(path);
+            let result = (|| {
+                let since = since
+                    .as_deref()
+                    .map(|value| modification_store::parse_time_bound(value, false))
+                    .transpose()?;
+                let scope = ReportScope { session, since };
+                pr_report::build(&target_dir, &modification_store::state_dir()?, &scope)
+            })();
+            let report = match result {
+                Ok(report) => report,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
 
-    let selected_skills: Vec<String> = selected_indices
-        .iter()
-        .filter_map(|&i| AVAILABLE_SKILLS.get(i).map(|s| s.to_string()))
-        .collect();
+            if report.is_empty() {
+                eprintln!(
+                    "No tracked edits for {}. Is the file-change-tracker hook installed?",
+                    target_dir.display()
+                );
+                exit(ExitCode::Failure);
+            }
 
-    println!();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else if pr {
+                let markdown = report.to_markdown();
+                if post {
+                    if let Err(e) = pr_report::post_comment(&target_dir, &markdown) {
+                        if use_color {
+                            eprintln!("{}", format!("❌ {}", e).red().bold());
+                        } else {
+                            eprintln!("❌ {}", e);
+                        }
+                        exit(ExitCode::for_error(&e));
+                    }
+                    println!("✅ Posted the summary to the pull request");
+                } else {
+                    print!("{}", markdown);
+                }
+            } else {
+                display_pr_report(&report, use_color);
+            }
+        }
 
-    // Show summary
-    println!("{}", "━".repeat(SEPARATOR_WIDTH).bright_cyan());
-    println!("{}", "  Configuration Summary  ".bright_cyan().bold());
-    println!("{}", "━".repeat(SEPARATOR_WIDTH).bright_cyan());
-    println!();
-    println!("{}", "Directory:".cyan().bold());
-    println!("  {}", target_dir.display());
-    println!();
-    println!("{}", "Hooks:".cyan().bold());
-    println!(
-        "  Auto-activation hooks: {}",
-        if install_hooks {
-            "✓ Yes".green()
-        } else {
-            "✗ No".red()
+        Commands::Agents {
+            command: AgentsCommands::List { path, json },
+        } => {
+            use catalyst_cli::agents::{self, AGENT_TEMPLATES};
+
+            let target_dir = project::resolve_target_dir(path);
+            let installed = match agents::list_agents(&target_dir) {
+                Ok(installed) => installed,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
+            let invalid = installed.iter().any(|agent| !agent.problems.is_empty());
+
+            if json {
+                let templates: Vec<_> = AGENT_TEMPLATES
+                    .iter()
+                    .map(|t| serde_json::json!({"name": t.name, "summary": t.summary}))
+                    .collect();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "agents": installed,
+                        "templates": templates,
+                    }))?
+                );
+            } else {
+                display_agents(&installed, use_color);
+            }
+            if invalid {
+                exit(ExitCode::Errors);
+            }
         }
-    );
-    println!(
-        "  File-change tracker:   {}",
-        if install_tracker {
-            "✓ Yes".green()
-        } else {
-            "✗ No".red()
+
+        Commands::Skills {
+            command: SkillsCommands::List { path, json },
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let installed = match catalyst_cli::skills::list_skills(&target_dir) {
+                Ok(installed) => installed,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
+            let invalid = installed.iter().any(|skill| skill.problem.is_some());
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "skills": installed,
+                        "shipped": init::shipped_manifests(),
+                    }))?
+                );
+            } else {
+                display_skills(&installed, use_color);
+            }
+            if invalid {
+                exit(ExitCode::Errors);
+            }
         }
-    );
-    println!();
-    println!("{}", "Skills:".cyan().bold());
-    if selected_skills.is_empty() {
-        println!("  {}", "None selected".yellow());
-        println!();
-        println!(
-            "{}",
-            "  ⚠️  No skills selected - you can add them later with:".yellow()
-        );
-        println!("{}", "    catalyst update".dimmed());
-    } else {
-        for skill in &selected_skills {
-            println!("  ✓ {}", skill.green());
+
+        Commands::Skills {
+            command:
+                SkillsCommands::Search {
+                    query,
+                    path,
+                    refresh,
+                    json,
+                },
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let loaded = match load_registry_index(&target_dir, refresh, use_color) {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
+            let found = registry::search(&loaded.index, &query.join(" "));
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&found)?);
+            } else if found.is_empty() {
+                println!("No registry skills match '{}'", query.join(" "));
+            } else {
+                for skill in &found {
+                    let id = if use_color {
+                        skill.id.bold().to_string()
+                    } else {
+                        skill.id.clone()
+                    };
+                    println!("  {:<26} {:<8} {}", id, skill.version, skill.description);
+                }
+                println!();
+                println!("Run 'catalyst skills add <ID>' to install one");
+            }
         }
-    }
-    println!();
-    println!("{}", "💡 Note:".yellow().bold());
-    println!("  After initialization, customize pathPatterns in:");
-    println!("    .claude/skills/skill-rules.json");
-    println!();
-    println!("{}", "━".repeat(SEPARATOR_WIDTH).bright_cyan());
-    println!();
 
-    let confirm = Confirm::with_theme(&theme)
-        .with_prompt("Proceed with initialization?")
-        .default(true)
-        .interact()
-        .context("Failed to get final confirmation")?;
+        Commands::Skills {
+            command: SkillsCommands::Add {
+                id, path, force, ..
+            },
+        } if package::is_package_path(&id) => {
+            let target_dir = project::resolve_target_dir(path);
+            match package::install_package(&target_dir, Path::new(&id), force) {
+                Ok((manifest, dir)) => {
+                    let version = manifest.version.as_deref().unwrap_or("(no version)");
+                    let message =
+                        format!("✅ Installed '{}' {} from {}", manifest.name, version, id);
+                    if use_color {
+                        println!("{}", message.green());
+                    } else {
+                        println!("{}", message);
+                    }
+                    println!("   {}", dir.display());
+                    println!("   Added to skill-rules.json; run 'catalyst skills lint' to check its triggers");
+                }
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            }
+        }
+
+        Commands::Skills {
+            command:
+                SkillsCommands::Add {
+                    id,
+                    path,
+                    force,
+                    allow_unverified,
+                    refresh,
+                },
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let result = load_registry_index(&target_dir, refresh, use_color).and_then(|loaded| {
+                let options = registry::AddOptions {
+                    force,
+                    allow_unverified,
+                    trusted_keys: CatalystConfig::load(&target_dir)?.registry.trusted_keys,
+                };
+                let skill = loaded
+                    .index
+                    .skills
+                    .iter()
+                    .find(|skill| skill.id == id)
+                    .ok_or_else(|| {
+                        CatalystError::RegistryFailed(format!(
+                            "No skill '{}' in the registry (try 'catalyst skills search')",
+                            id
+                        ))
+                    })?;
+                registry::add_skill(&target_dir, skill, &options)
+                    .map(|added| (skill.clone(), added))
+            });
+            match result {
+                Ok((skill, added)) => {
+                    let message = format!(
+                        "✅ Installed '{}' {} from the registry",
+                        skill.id, skill.version
+                    );
+                    if use_color {
+                        println!("{}", message.green());
+                    } else {
+                        println!("{}", message);
+                    }
+                    println!("   {}", added.dir.display());
+                    match &added.signed_by {
+                        Some(key) => println!("   Signature verified with {}", key),
+                        None => {
+                            let warning = "⚠️  Installed without signature verification";
+                            if use_color {
+                                println!("   {}", warning.yellow());
+                            } else {
+                                println!("   {}", warning);
+                            }
+                        }
+                    }
+                    println!("   Added to skill-rules.json; run 'catalyst skills lint' to check its triggers");
+                }
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            }
+        }
+
+        Commands::Skills {
+            command: SkillsCommands::Pack { dir, output },
+        } => match package::pack_skill(&dir, output.as_deref()) {
+            Ok(packed) => {
+                let message = format!(
+                    "✅ Packed '{}' into {}",
+                    packed.manifest.name,
+                    packed.path.display()
+                );
+                if use_color {
+                    println!("{}", message.green());
+                } else {
+                    println!("{}", message);
+                }
+                println!("   sha256: {}", packed.sha256);
+                println!(
+                    "   Install it with 'catalyst skills add {}'",
+                    packed.path.display()
+                );
+            }
+            Err(e) => {
+                if use_color {
+                    eprintln!("{}", format!("❌ {}", e).red().bold());
+                } else {
+                    eprintln!("❌ {}", e);
+                }
+                exit(ExitCode::for_error(&e));
+            }
+        },
+
+        Commands::Skills {
+            command: SkillsCommands::Outdated { path, json },
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let outdated = match catalyst_cli::skills::outdated_skills(&target_dir) {
+                Ok(outdated) => outdated,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&outdated)?);
+            } else if outdated.is_empty() {
+                println!("All installed skills are up to date");
+            } else {
+                println!("Outdated skills:");
+                for skill in &outdated {
+                    let installed = skill.installed.as_deref().unwrap_or("unknown");
+                    let arrow = if use_color {
+                        "→".yellow().to_string()
+                    } else {
+                        "→".to_string()
+                    };
+                    println!(
+                        "  {:<26} {:<8} {} {}",
+                        skill.id, installed, arrow, skill.available
+                    );
+                }
+                println!();
+                println!("Run 'catalyst update --skill <ID>' to update one");
+            }
+            if !outdated.is_empty() {
+                exit(ExitCode::Warnings);
+            }
+        }
+
+        Commands::Agents {
+            command:
+                AgentsCommands::Add {
+                    template,
+                    path,
+                    name,
+                    vars,
+                    force,
+                },
+        } => {
+            use catalyst_cli::agents::{self, AddAgent};
+
+            let target_dir = project::resolve_target_dir(path);
+            let result = vars
+                .iter()
+                .map(|var| {
+                    var.split_once('=')
+                        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+                        .ok_or_else(|| {
+                            catalyst_cli::CatalystError::InvalidConfig(format!(
+                                "--var needs KEY=VALUE, got '{}'",
+                                var
+                            ))
+                        })
+                })
+                .collect::<catalyst_cli::Result<Vec<_>>>()
+                .and_then(|vars| {
+                    let options = AddAgent {
+                        template,
+                        name,
+                        vars,
+                        force,
+                    };
+                    agents::add_agent(&target_dir, &options)
+                });
+            report_agent_written(result, "Added", use_color);
+        }
+
+        Commands::Agents {
+            command:
+                AgentsCommands::New {
+                    name,
+                    path,
+                    description,
+                    tools,
+                    model,
+                    force,
+                },
+        } => {
+            use catalyst_cli::agents::{self, NewAgent};
+
+            let target_dir = project::resolve_target_dir(path);
+            let agent = NewAgent {
+                name,
+                description,
+                tools,
+                model,
+                force,
+            };
+            report_agent_written(
+                agents::create_agent(&target_dir, &agent),
+                "Created",
+                use_color,
+            );
+        }
+
+        Commands::Agents {
+            command: AgentsCommands::Remove { name, path },
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            match catalyst_cli::agents::remove_agent(&target_dir, &name) {
+                Ok(path) => {
+                    if use_color {
+                        println!("{}", format!("✅ Removed agent '{}'", name).green());
+                    } else {
+                        println!("✅ Removed agent '{}'", name);
+                    }
+                    println!("   {}", path.display());
+                }
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            }
+        }
+
+        Commands::SlashCommand {
+            command: CommandsCommands::List { path, json },
+        } => {
+            use catalyst_cli::commands::{self, COMMAND_TEMPLATES};
+
+            let target_dir = project::resolve_target_dir(path);
+            let installed = match commands::list_commands(&target_dir) {
+                Ok(installed) => installed,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
+
+            if json {
+                let templates: Vec<_> = COMMAND_TEMPLATES
+                    .iter()
+                    .map(|t| serde_json::json!({"name": t.name, "summary": t.summary}))
+                    .collect();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "commands": installed,
+                        "templates": templates,
+                    }))?
+                );
+            } else {
+                display_commands(&installed, use_color);
+            }
+        }
+
+        Commands::SlashCommand {
+            command:
+                CommandsCommands::New {
+                    name,
+                    path,
+                    template,
+                    description,
+                    argument_hint,
+                    force,
+                },
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let command = catalyst_cli::commands::NewCommand {
+                name,
+                template,
+                description,
+                argument_hint,
+                force,
+            };
+            match catalyst_cli::commands::create_command(&target_dir, &command) {
+                Ok(path) => {
+                    let message = format!("✅ Created command '/{}'", command.name);
+                    if use_color {
+                        println!("{}", message.green());
+                    } else {
+                        println!("{}", message);
+                    }
+                    println!("   {}", path.display());
+                }
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            }
+        }
+
+        Commands::SlashCommand {
+            command: CommandsCommands::Validate { names, path },
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let installed = catalyst_cli::commands::list_commands(&target_dir)?;
+            let unknown: Vec<&String> = names
+                .iter()
+                .filter(|name| !installed.iter().any(|c| &c.name == *name))
+                .collect();
+            if !unknown.is_empty() {
+                for name in unknown {
+                    if use_color {
+                        eprintln!("{}", format!("❌ No command named '{}'", name).red());
+                    } else {
+                        eprintln!("❌ No command named '{}'", name);
+                    }
+                }
+                exit(ExitCode::Usage);
+            }
+
+            let checked: Vec<_> = installed
+                .iter()
+                .filter(|c| names.is_empty() || names.contains(&c.name))
+                .collect();
+            let invalid = checked.iter().filter(|c| !c.problems.is_empty()).count();
+            for command in &checked {
+                if command.problems.is_empty() {
+                    if use_color {
+                        println!("  {} /{}", "✓".green(), command.name);
+                    } else {
+                        println!("  ✓ /{}", command.name);
+                    }
+                    continue;
+                }
+                if use_color {
+                    println!("  {} /{}", "❌".red(), command.name.red());
+                } else {
+                    println!("  ❌ /{}", command.name);
+                }
+                for problem in &command.problems {
+                    println!("      {}", problem);
+                }
+            }
+            println!();
+            if invalid == 0 {
+                let message = format!("✅ {} command(s) valid", checked.len());
+                if use_color {
+                    println!("{}", message.green().bold());
+                } else {
+                    println!("{}", message);
+                }
+            } else {
+                let message = format!("❌ {} of {} command(s) invalid", invalid, checked.len());
+                if use_color {
+                    eprintln!("{}", message.red().bold());
+                } else {
+                    eprintln!("{}", message);
+                }
+                exit(ExitCode::Errors);
+            }
+        }
+
+        Commands::Stats {
+            command: StatsCommands::Hooks { since, json },
+        } => {
+            #[cfg(feature = "sqlite")]
+            {
+                use catalyst_cli::{hook_stats, modification_store};
+
+                let result = since
+                    .as_deref()
+                    .map(|value| modification_store::parse_time_bound(value, false))
+                    .transpose()
+                    .and_then(|since| {
+                        hook_stats::summarize(&modification_store::state_dir()?, since)
+                    });
+                let stats = match result {
+                    Ok(stats) => stats,
+                    Err(e) => {
+                        if use_color {
+                            eprintln!("{}", format!("❌ {}", e).red().bold());
+                        } else {
+                            eprintln!("❌ {}", e);
+                        }
+                        exit(ExitCode::for_error(&e));
+                    }
+                };
 
-    if !confirm {
-        return Ok(None);
-    }
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&stats)?);
+                } else if stats.is_empty() {
+                    println!("No hook runs recorded. Hooks record their timing when built with the sqlite feature.");
+                } else {
+                    display_hook_stats(&stats, use_color);
+                }
+            }
 
-    println!();
+            #[cfg(not(feature = "sqlite"))]
+            {
+                let _ = (since, json);
+                exit_needs_sqlite(use_color);
+            }
+        }
 
-    Ok(Some(InitConfig {
-        directory: target_dir.to_path_buf(),
-        install_hooks,
-        install_tracker,
-        skills: selected_skills,
-        force,
-    }))
-}
+        Commands::Logs {
+            command:
+                LogsCommands::Show {
+                    path,
+                    session,
+                    since,
+                    limit,
+                    json,
+                },
+        } => {
+            use catalyst_cli::modification_store;
+            use catalyst_cli::prompt_log::{self, LogFilter};
 
-fn main() -> Result<()> {
-    // Check for NO_COLOR environment variable and TTY
-    let use_color = env::var("NO_COLOR").is_err() && io::stdout().is_terminal();
+            let target_dir = project::resolve_target_dir(path);
+            let result = since
+                .as_deref()
+                .map(|value| modification_store::parse_time_bound(value, false))
+                .transpose()
+                .and_then(|since| {
+                    let filter = LogFilter {
+                        session,
+                        since,
+                        limit,
+                    };
+                    prompt_log::read(&target_dir, &filter)
+                });
+            let entries = match result {
+                Ok(entries) => entries,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
 
-    let cli = Cli::parse();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            } else if entries.is_empty() {
+                println!(
+                    "No prompts logged in {}. Is the prompt-logger hook registered?",
+                    prompt_log::log_path(&target_dir).display()
+                );
+            } else {
+                for entry in &entries {
+                    display_prompt_log_entry(entry, use_color);
+                }
+            }
+        }
 
-    match cli.command {
-        Commands::Init {
-            path,
-            interactive,
-            force,
-            all,
+        Commands::Logs {
+            command:
+                LogsCommands::Tail {
+                    path,
+                    lines,
+                    follow,
+                },
         } => {
-            let target_dir =
-                path.unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+            use catalyst_cli::prompt_log;
 
-            // Check if binaries are installed
-            let platform = catalyst_cli::types::Platform::detect();
-            if let Err(e) = check_binaries_installed(platform) {
+            let target_dir = project::resolve_target_dir(path);
+            let fail = |e: catalyst_cli::CatalystError| -> ! {
                 if use_color {
                     eprintln!("{}", format!("❌ {}", e).red().bold());
                 } else {
                     eprintln!("❌ {}", e);
                 }
-                std::process::exit(1);
+                exit(ExitCode::for_error(&e))
+            };
+
+            let (entries, mut offset) =
+                prompt_log::read_from(&target_dir, 0).unwrap_or_else(|e| fail(e));
+            for entry in &entries[entries.len().saturating_sub(lines)..] {
+                display_prompt_log_entry(entry, use_color);
+            }
+            if follow {
+                loop {
+                    std::thread::sleep(std::time::Duration::from_millis(500));
+                    let (entries, next) =
+                        prompt_log::read_from(&target_dir, offset).unwrap_or_else(|e| fail(e));
+                    for entry in &entries {
+                        display_prompt_log_entry(entry, use_color);
+                    }
+                    offset = next;
+                }
             }
+        }
 
-            // Build config based on mode
-            let config = if interactive {
-                // Interactive mode - guide user through setup
-                match run_interactive_init(&target_dir, force)? {
-                    Some(cfg) => cfg,
-                    None => {
-                        // User cancelled
-                        if use_color {
-                            println!("{}", "❌ Initialization cancelled".yellow());
-                        } else {
-                            println!("❌ Initialization cancelled");
-                        }
-                        return Ok(());
+        Commands::Devcontainer {
+            command: DevcontainerCommands::Generate { path, format },
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let generated = format
+                .parse::<DevcontainerFormat>()
+                .and_then(|format| devcontainer::generate(&target_dir, format));
+            match generated {
+                Ok(generated) => {
+                    if use_color {
+                        println!("{}", "✅ Generated dev container setup".green());
+                    } else {
+                        println!("✅ Generated dev container setup");
+                    }
+                    for file in &generated.files {
+                        let shown = file.strip_prefix(&target_dir).unwrap_or(file);
+                        println!("   {}", shown.display());
                     }
+                    println!();
+                    println!("Add to .devcontainer/devcontainer.json:");
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&generated.snippet)
+                            .unwrap_or_else(|_| generated.snippet.to_string())
+                    );
                 }
-            } else {
-                // Non-interactive mode - use defaults and flags
-                let mut skills = Vec::new();
-                if all {
-                    skills.extend_from_slice(catalyst_cli::types::AVAILABLE_SKILLS);
-                } else {
-                    // Default: install skill-developer
-                    skills.push("skill-developer");
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
                 }
+            }
+        }
 
-                InitConfig {
-                    directory: target_dir.clone(),
-                    install_hooks: true,   // Always install hooks
-                    install_tracker: true, // Always install tracker
-                    skills: skills.iter().map(|s| s.to_string()).collect(),
-                    force,
+        Commands::ExportState {
+            archive,
+            path,
+            include_user,
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let result = if include_user {
+                state::StateRoots::with_user(&target_dir)
+            } else {
+                Ok(state::StateRoots::project(&target_dir))
+            }
+            .and_then(|roots| state::export_state(&roots));
+            let state_archive = match result {
+                Ok(state_archive) => state_archive,
+                Err(e) => {
+                    if use_color {
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
+                    } else {
+                        eprintln!("❌ {}", e);
+                    }
+                    exit(ExitCode::for_error(&e));
                 }
             };
 
-            // Run initialization
+            init::write_file_atomic(&archive, &serde_json::to_string_pretty(&state_archive)?)?;
+            for entry in &state_archive.entries {
+                println!("  + [{}] {}", entry.scope, entry.path);
+            }
+            let msg = format!(
+                "✅ Exported {} files to {}",
+                state_archive.entries.len(),
+                archive.display()
+            );
             if use_color {
-                println!("{}", "🚀 Initializing Catalyst...".cyan().bold());
+                println!("{}", msg.green().bold());
             } else {
-                println!("🚀 Initializing Catalyst...");
+                println!("{}", msg);
             }
-            println!();
+        }
 
-            match init::initialize(&config) {
-                Ok(report) => {
-                    // Display success report
+        Commands::ImportState {
+            archive,
+            path,
+            force,
+            project_only,
+        } => {
+            let target_dir = project::resolve_target_dir(path);
+            let result = state::read_archive(&archive).and_then(|state_archive| {
+                let roots = if project_only {
+                    state::StateRoots::project(&target_dir)
+                } else {
+                    state::StateRoots::with_user(&target_dir)?
+                };
+                state::import_state(&state_archive, &roots, force)
+            });
+            let report = match result {
+                Ok(report) => report,
+                Err(e) => {
                     if use_color {
-                        println!("{}", "━".repeat(60).bright_cyan());
-                        println!("{}", "✅ Catalyst initialized successfully!".green().bold());
-                        println!("{}", "━".repeat(60).bright_cyan());
+                        eprintln!("{}", format!("❌ {}", e).red().bold());
                     } else {
-                        println!("{}", "=".repeat(60));
-                        println!("✅ Catalyst initialized successfully!");
-                        println!("{}", "=".repeat(60));
-                    }
-                    println!();
-
-                    // Created directories
-                    if !report.created_dirs.is_empty() {
-                        if use_color {
-                            println!("{}", "Created directories:".cyan().bold());
-                        } else {
-                            println!("Created directories:");
-                        }
-                        for dir in &report.created_dirs {
-                            println!("  ✓ {}", dir);
-                        }
-                        println!();
-                    }
-
-                    // Installed hooks
-                    if !report.installed_hooks.is_empty() {
-                        if use_color {
-                            println!("{}", "Installed hooks:".cyan().bold());
-                        } else {
-                            println!("Installed hooks:");
-                        }
-                        for hook in &report.installed_hooks {
-                            println!("  ✓ {}", hook);
-                        }
-                        println!();
+                        eprintln!("❌ {}", e);
                     }
+                    exit(ExitCode::for_error(&e));
+                }
+            };
 
-                    // Installed skills
-                    if !report.installed_skills.is_empty() {
-                        if use_color {
-                            println!("{}", "Installed skills:".cyan().bold());
-                        } else {
-                            println!("Installed skills:");
-                        }
-                        for skill in &report.installed_skills {
-                            println!("  ✓ {}", skill);
-                        }
-                        println!();
-                    }
+            for file in &report.restored {
+                println!("  ✓ {}", file.display());
+            }
+            for file in &report.conflicts {
+                if use_color {
+                    println!(
+                        "{}",
+                        format!("  ⚠️  {} differs (kept)", file.display()).yellow()
+                    );
+                } else {
+                    println!("  ⚠️  {} differs (kept)", file.display());
+                }
+            }
+            if report.skipped > 0 {
+                println!(
+                    "  - {} user-level files skipped (--project-only)",
+                    report.skipped
+                );
+            }
 
-                    // Settings file
-                    if report.settings_created {
-                        if use_color {
-                            println!("{}", "Configuration:".cyan().bold());
-                        } else {
-                            println!("Configuration:");
-                        }
-                        println!("  ✓ .claude/settings.json");
-                        println!();
-                    }
+            let msg = format!(
+                "Restored {} files ({} already up to date)",
+                report.restored.len(),
+                report.unchanged.len()
+            );
+            if report.conflicts.is_empty() {
+                if use_color {
+                    println!("{}", format!("✅ {}", msg).green().bold());
+                } else {
+                    println!("✅ {}", msg);
+                }
+            } else {
+                if use_color {
+                    println!("{}", format!("⚠️  {}", msg).yellow().bold());
+                } else {
+                    println!("⚠️  {}", msg);
+                }
+                println!("Re-run with --force to overwrite the files that differ.");
+                exit(ExitCode::Failure);
+            }
+        }
 
-                    // Next steps
-                    if use_color {
-                        println!("{}", "Next steps:".yellow().bold());
-                    } else {
-                        println!("Next steps:");
+        Commands::ExplainIssue { id } => match id {
+            Some(id) => match catalyst_cli::explain::explain_id(&id) {
+                Some(explanation) => display_issue_explanation(&explanation, use_color),
+                None => {
+                    let mut msg = format!("Unknown issue ID '{}'", id);
+                    if let Some(suggestion) = catalyst_cli::explain::suggest_id(&id) {
+                        msg.push_str(&format!(". Did you mean '{}'?", suggestion));
                     }
-                    println!("  1. Review .claude/settings.json");
-                    println!("  2. Try editing a file - hooks should activate automatically");
-                    println!("  3. Run 'catalyst status' to validate setup");
-                    println!();
-
                     if use_color {
-                        println!(
-                            "{}",
-                            "📖 Documentation: https://github.com/dwalleck/catalyst".bright_blue()
-                        );
+                        eprintln!("{}", format!("❌ {}", msg).red().bold());
                     } else {
-                        println!("📖 Documentation: https://github.com/dwalleck/catalyst");
+                        eprintln!("❌ {}", msg);
                     }
+                    eprintln!("Run 'catalyst explain-issue' to list all issue IDs.");
+                    exit(ExitCode::Usage);
                 }
-                Err(e) => {
+            },
+            None => {
+                if use_color {
+                    println!("{}", "Issue IDs:".cyan().bold());
+                } else {
+                    println!("Issue IDs:");
+                }
+                for kind in catalyst_cli::types::IssueKind::ALL {
+                    let explanation = catalyst_cli::explain::explain(*kind);
                     if use_color {
-                        eprintln!(
-                            "{}",
-                            format!("❌ Initialization failed: {}", e).red().bold()
-                        );
+                        println!("  {}  {}", kind.id().bold(), explanation.title);
                     } else {
-                        eprintln!("❌ Initialization failed: {}", e);
+                        println!("  {}  {}", kind.id(), explanation.title);
                     }
-                    std::process::exit(1);
                 }
             }
+        },
+    }
+
+    Ok(())
+}
+
+/// Display the report produced by `catalyst env`
+fn display_env_report(report: &catalyst_cli::env::EnvReport, use_color: bool) {
+    let heading = |title: &str| {
+        println!();
+        if use_color {
+            println!("{}", title.bold());
+        } else {
+            println!("{}", title);
+        }
+    };
+    let show_path = |path: &Option<PathBuf>| match path {
+        Some(p) => p.display().to_string(),
+        None => "(unknown - no home directory)".to_string(),
+    };
+
+    println!("catalyst {}", report.version);
+    println!("Project: {}", report.project_dir.display());
+
+    heading("Platform");
+    println!("  detected: {:?}", report.platform);
+    println!("  wrappers: {:?}", report.wrapper_platform);
+
+    heading("Config files (first existing file wins)");
+    for source in &report.config_files {
+        let state = if source.active {
+            "active"
+        } else if source.exists {
+            "ignored"
+        } else {
+            "not found"
+        };
+        if use_color && source.active {
+            println!("  {} ({})", source.path.display(), state.green());
+        } else {
+            println!("  {} ({})", source.path.display(), state);
+        }
+    }
+    if let Some(error) = &report.config_error {
+        if use_color {
+            println!("  {} {}", "❌".red(), error);
+        } else {
+            println!("  ❌ {}", error);
+        }
+    }
+
+    heading("Effective configuration");
+    for (key, _) in catalyst_cli::config::KNOWN_KEYS {
+        let value = report
+            .config
+            .get(key)
+            .ok()
+            .flatten()
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "(unset)".to_string());
+        println!("  {} = {}", key, value);
+    }
+
+    heading("Environment overrides");
+    if report.env_overrides.is_empty() {
+        println!("  (none)");
+    }
+    for var in &report.env_overrides {
+        println!("  {}={}", var.name, var.value);
+        if use_color {
+            println!("    {}", var.effect.dimmed());
+        } else {
+            println!("    {}", var.effect);
+        }
+    }
+
+    heading("Paths");
+    let paths = &report.paths;
+    println!("  bin dir:        {}", show_path(&paths.bin_dir));
+    println!("  tracker state:  {}", show_path(&paths.tracker_state_dir));
+    println!("  skills library: {}", show_path(&paths.skills_library));
+    println!("  hooks:          {}", paths.hooks_dir.display());
+    println!("  skills:         {}", paths.skills_dir.display());
+    println!("  settings:       {}", paths.settings_file.display());
+    println!("  version file:   {}", paths.version_file.display());
+    println!("  backups:        {}", paths.backup_dir.display());
+    println!("  update journal: {}", paths.update_journal.display());
+
+    heading("Feature flags");
+    for status in &report.flags {
+        let state = if status.enabled { "on" } else { "off" };
+        println!(
+            "  {} [{}]: {}",
+            status.flag.name, status.flag.stability, state
+        );
+    }
+
+    heading("Build features");
+    for feature in &report.features {
+        let state = if feature.enabled { "on" } else { "off" };
+        println!("  {}: {}", feature.name, state);
+    }
+}
+
+/// Display `catalyst flags list`
+fn display_flags(flags: &EnabledFlags, use_color: bool) {
+    for status in flags.statuses() {
+        let state = match status.source {
+            Some(FlagSource::Env) => format!("enabled ({})", flags::UNSTABLE_ENV_VAR),
+            Some(FlagSource::Config) => "enabled (catalyst.toml)".to_string(),
+            None => "disabled".to_string(),
+        };
+        if use_color {
+            let state = if status.enabled {
+                state.green()
+            } else {
+                state.dimmed()
+            };
+            println!(
+                "{} [{}] {}",
+                status.flag.name.cyan().bold(),
+                status.flag.stability.to_string().yellow(),
+                state
+            );
+        } else {
+            println!("{} [{}] {}", status.flag.name, status.flag.stability, state);
+        }
+        println!("  {}", status.flag.description);
+    }
+
+    for name in &flags.unknown {
+        if use_color {
+            eprintln!(
+                "{} Unknown flag '{}' in {}",
+                "⚠️".yellow(),
+                name,
+                flags::UNSTABLE_ENV_VAR
+            );
+        } else {
+            eprintln!("⚠️  Unknown flag '{}' in {}", name, flags::UNSTABLE_ENV_VAR);
+        }
+    }
+
+    println!();
+    println!(
+        "Enable with [flags] enable = [...] in catalyst.toml or {}=flag1,flag2",
+        flags::UNSTABLE_ENV_VAR
+    );
+}
+
+/// Timeline entries shown before older ones are elided
+#[cfg(feature = "sqlite")]
+const SESSION_TIMELINE_LIMIT: usize = 20;
+
+/// Exit with a hint to rebuild; the `sessions` commands read SQLite databases
+#[cfg(not(feature = "sqlite"))]
+fn exit_needs_sqlite(use_color: bool) -> ! {
+    let msg = "Session reports and hook stats need a catalyst built with the sqlite feature \
+               (cargo install catalyst-cli --features sqlite)";
+    if use_color {
+        eprintln!("{}", format!("❌ {}", msg).red().bold());
+    } else {
+        eprintln!("❌ {}", msg);
+    }
+    exit(ExitCode::Failure);
+}
+
+/// Display `catalyst stats hooks` as a table, slowest hook first
+#[cfg(feature = "sqlite")]
+fn display_hook_stats(stats: &[catalyst_cli::hook_stats::HookStats], use_color: bool) {
+    let heading = format!(
+        "{:<28} {:>6} {:>8} {:>8} {:>8} {:>8} {:>7}",
+        "HOOK", "RUNS", "P50", "P95", "MAX", "BLOCKED", "ERRORS"
+    );
+    if use_color {
+        println!("{}", heading.bold());
+    } else {
+        println!("{}", heading);
+    }
+    for hook in stats {
+        let line = format!(
+            "{:<28} {:>6} {:>6}ms {:>6}ms {:>6}ms {:>8} {:>7}",
+            hook.hook, hook.runs, hook.p50_ms, hook.p95_ms, hook.max_ms, hook.blocked, hook.errors
+        );
+        if use_color && hook.errors > 0 {
+            println!("{}", line.red());
+        } else {
+            println!("{}", line);
         }
+    }
+}
 
-        Commands::Status { path, fix } => {
-            let target_dir =
-                path.unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+/// Print tracked edits as aligned columns, flagging risky ones
+#[cfg(feature = "sqlite")]
+fn display_modification_table(rows: &[catalyst_cli::sessions::ModificationRow], use_color: bool) {
+    let session_width = rows
+        .iter()
+        .map(|row| row.session_id.len())
+        .max()
+        .unwrap_or(0)
+        .max("SESSION".len());
+    let tool_width = rows
+        .iter()
+        .map(|row| row.tool.len())
+        .max()
+        .unwrap_or(0)
+        .max("TOOL".len());
+    let branch_width = rows
+        .iter()
+        .filter_map(|row| row.git_branch.as_ref().map(String::len))
+        .max()
+        .unwrap_or(0)
+        .max("BRANCH".len());
+    // Seconds are precise enough to read; JSON and CSV keep the full timestamp
+    let time = |timestamp: &str| {
+        chrono::DateTime::parse_from_rfc3339(timestamp)
+            .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|_| timestamp.to_string())
+    };
+    let heading = format!(
+        "{:<19}  {:<session_width$}  {:<tool_width$}  {:<8}  {:<5}  {:<branch_width$}  FILE",
+        "TIME", "SESSION", "TOOL", "CATEGORY", "RISKY", "BRANCH"
+    );
+    if use_color {
+        println!("{}", heading.bold());
+    } else {
+        println!("{}", heading);
+    }
+    for row in rows {
+        let risky = if row.has_async && !row.has_try_catch {
+            "yes"
+        } else {
+            ""
+        };
+        let line = format!(
+            "{:<19}  {:<session_width$}  {:<tool_width$}  {:<8}  {:<5}  {:<branch_width$}  {}",
+            time(&row.timestamp),
+            row.session_id,
+            row.tool,
+            row.category,
+            risky,
+            row.git_branch.as_deref().unwrap_or("-"),
+            row.file_path
+        );
+        if use_color && !risky.is_empty() {
+            println!("{}", line.yellow());
+        } else {
+            println!("{}", line);
+        }
+    }
+    println!();
+    let edits = if rows.len() == 1 { "edit" } else { "edits" };
+    println!("{} {}", rows.len(), edits);
+}
 
-            // Detect platform
-            let platform = catalyst_cli::types::Platform::detect();
+/// Display one session from `catalyst sessions report`
+#[cfg(feature = "sqlite")]
+fn display_session_report(report: &catalyst_cli::sessions::SessionReport, use_color: bool) {
+    let heading = format!("Session {}", report.session_id);
+    if use_color {
+        println!("{}", heading.cyan().bold());
+    } else {
+        println!("{}", heading);
+    }
+    println!(
+        "  {} → {}  ({} edits)",
+        report.start_time.as_deref().unwrap_or("?"),
+        report.last_activity.as_deref().unwrap_or("?"),
+        report.total_edits
+    );
 
-            // Validate installation
-            match catalyst_cli::status::validate_installation(&target_dir, platform) {
-                Ok(report) => {
-                    // If --fix flag provided and there are auto-fixable issues, attempt fixes
-                    let mut fixed_issues = Vec::new();
-                    if fix && report.issues.iter().any(|i| i.auto_fixable) {
-                        match catalyst_cli::status::auto_fix(&target_dir, platform, &report) {
-                            Ok(fixes) => {
-                                fixed_issues = fixes;
-                            }
-                            Err(e) => {
-                                if use_color {
-                                    eprintln!(
-                                        "{}",
-                                        format!("❌ Auto-fix failed: {}", e).red().bold()
-                                    );
-                                } else {
-                                    eprintln!("❌ Auto-fix failed: {}", e);
-                                }
-                            }
-                        }
-                    }
+    let categories: Vec<String> = report
+        .files_by_category
+        .iter()
+        .map(|(category, count)| format!("{} {}", count, category))
+        .collect();
+    if !categories.is_empty() {
+        println!("  Files: {}", categories.join(", "));
+    }
 
-                    // Display status report
-                    display_status_report(&report, use_color, &fixed_issues);
+    let branches: Vec<String> = report
+        .files_by_branch
+        .iter()
+        .map(|(branch, count)| format!("{} on {}", count, branch))
+        .collect();
+    if !branches.is_empty() {
+        println!("  Branches: {}", branches.join(", "));
+    }
+    if !report.commits.is_empty() {
+        // Short hashes, as git log --oneline shows them
+        let commits: Vec<&str> = report
+            .commits
+            .iter()
+            .map(|commit| commit.get(..7).unwrap_or(commit))
+            .collect();
+        println!("  Commits: {}", commits.join(", "));
+    }
 
-                    // Exit with error code if status is not ok
-                    if report.level != catalyst_cli::types::StatusLevel::Ok {
-                        std::process::exit(1);
-                    }
-                }
-                Err(e) => {
-                    if use_color {
-                        eprintln!("{}", format!("❌ Status check failed: {}", e).red().bold());
-                    } else {
-                        eprintln!("❌ Status check failed: {}", e);
-                    }
-                    std::process::exit(1);
-                }
-            }
+    if !report.risky_files.is_empty() {
+        if use_color {
+            println!("  {}", "Async without try/catch:".yellow());
+        } else {
+            println!("  Async without try/catch:");
         }
+        for file in &report.risky_files {
+            println!("    ⚠️  {}", file);
+        }
+    }
 
-        Commands::Update { path, force } => {
-            let target_dir =
-                path.unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !report.most_edited.is_empty() {
+        println!("  Most edited:");
+        for file in &report.most_edited {
+            println!("    {:>3}×  {}", file.edits, file.path);
+        }
+    }
 
+    if !report.timeline.is_empty() {
+        println!("  Timeline:");
+        let skipped = report.timeline.len().saturating_sub(SESSION_TIMELINE_LIMIT);
+        if skipped > 0 {
+            println!("    ... {} earlier edits", skipped);
+        }
+        for entry in &report.timeline[skipped..] {
+            let line = format!("{}  {:<9} {}", entry.timestamp, entry.tool, entry.file_path);
             if use_color {
-                println!("{}", "🔄 Updating Catalyst...".cyan().bold());
+                println!("    {}", line.dimmed());
             } else {
-                println!("🔄 Updating Catalyst...");
+                println!("    {}", line);
             }
-            println!();
+        }
+    }
+    println!();
+}
 
-            // Run update
-            let report = update::update(&target_dir, force)?;
+/// Display `catalyst report` in the terminal
+fn display_pr_report(report: &catalyst_cli::pr_report::PrReport, use_color: bool) {
+    use catalyst_cli::check_log::CheckStatus;
 
-            // Display results
-            if report.updated_skills.is_empty()
-                && report.updated_hooks.is_empty()
-                && report.skipped_skills.is_empty()
-            {
-                if use_color {
-                    println!("{}", "✅ Already up to date!".green().bold());
-                } else {
-                    println!("✅ Already up to date!");
-                }
-            } else {
-                // Show updated hooks
-                if !report.updated_hooks.is_empty() {
-                    if use_color {
-                        println!("{}", "Updated hooks:".green().bold());
-                    } else {
-                        println!("Updated hooks:");
-                    }
-                    for hook in &report.updated_hooks {
-                        println!("  ✓ {}", hook);
-                    }
-                    println!();
-                }
+    let heading = format!(
+        "{} edits to {} files in {} session(s)",
+        report.edits,
+        report.file_count(),
+        report.sessions.len()
+    );
+    if use_color {
+        println!("{}", heading.cyan().bold());
+    } else {
+        println!("{}", heading);
+    }
 
-                // Show updated skills
-                if !report.updated_skills.is_empty() {
-                    if use_color {
-                        println!("{}", "Updated skills:".green().bold());
-                    } else {
-                        println!("Updated skills:");
-                    }
-                    for skill in &report.updated_skills {
-                        println!("  ✓ {}", skill);
-                    }
-                    println!();
-                }
+    for (category, files) in &report.files_by_category {
+        println!("  {} ({}):", category, files.len());
+        for file in files {
+            println!("    {}", file);
+        }
+    }
 
-                // Show skipped skills
-                if !report.skipped_skills.is_empty() {
-                    if use_color {
-                        println!("{}", "Skipped skills (modified locally):".yellow().bold());
-                    } else {
-                        println!("Skipped skills (modified locally):");
-                    }
-                    for skipped in &report.skipped_skills {
-                        println!("  ⚠️  {} - {}", skipped.name, skipped.reason);
-                    }
-                    println!();
-                    if use_color {
-                        println!("{}", "  Use --force to overwrite modified skills".yellow());
-                    } else {
-                        println!("  Use --force to overwrite modified skills");
-                    }
-                    println!();
-                }
+    if !report.risky_files.is_empty() {
+        if use_color {
+            println!("  {}", "Async without try/catch:".yellow());
+        } else {
+            println!("  Async without try/catch:");
+        }
+        for file in &report.risky_files {
+            println!("    ⚠️  {}", file);
+        }
+    }
+    for (pattern, files) in &report.patterns {
+        println!("  {}: {}", pattern, files.join(", "));
+    }
 
-                // Show errors
-                if !report.errors.is_empty() {
-                    if use_color {
-                        println!("{}", "Errors:".red().bold());
-                    } else {
-                        println!("Errors:");
-                    }
-                    for error in &report.errors {
-                        println!("  ❌ {}", error);
-                    }
-                    println!();
-                }
+    if report.checks.is_empty() {
+        println!("  No check runs recorded");
+    } else {
+        println!("  Checks:");
+        for check in &report.checks {
+            let last = match check.last_status {
+                CheckStatus::Passed => "✅",
+                CheckStatus::Failed => "❌",
+                CheckStatus::Incomplete => "⏱️",
+            };
+            println!(
+                "    {} {}: {} ({} runs, {} failed)",
+                last, check.hook, check.command, check.runs, check.failed
+            );
+        }
+    }
+}
 
-                // Final status
-                if report.success {
-                    if use_color {
-                        println!("{}", "✅ Update completed successfully!".green().bold());
-                    } else {
-                        println!("✅ Update completed successfully!");
-                    }
-                } else if use_color {
-                    println!("{}", "⚠️  Update completed with errors".yellow().bold());
+/// Display `catalyst agents list`
+fn display_agents(installed: &[catalyst_cli::agents::AgentInfo], use_color: bool) {
+    use catalyst_cli::agents::AGENT_TEMPLATES;
+
+    if installed.is_empty() {
+        println!("No agents in .claude/agents");
+    } else {
+        println!("Installed agents:");
+        for agent in installed {
+            if agent.problems.is_empty() {
+                let model = agent
+                    .front_matter
+                    .as_ref()
+                    .and_then(|fm| fm.model.as_deref())
+                    .unwrap_or("inherit");
+                if use_color {
+                    println!("  {} {} ({})", "✓".green(), agent.name, model);
+                } else {
+                    println!("  ✓ {} ({})", agent.name, model);
+                }
+            } else {
+                if use_color {
+                    println!("  {} {}", "❌".red(), agent.name.red());
                 } else {
-                    println!("⚠️  Update completed with errors");
+                    println!("  ❌ {}", agent.name);
+                }
+                for problem in &agent.problems {
+                    println!("      {}", problem);
                 }
             }
         }
+    }
 
-        Commands::Settings { command } => {
-            match command {
-                SettingsCommands::Read { path } => {
-                    let settings = ClaudeSettings::read(&path)?;
-                    let json = serde_json::to_string_pretty(&settings)?;
-                    println!("{}", json);
-                }
+    println!();
+    println!("Templates (catalyst agents add <template>):");
+    for template in AGENT_TEMPLATES {
+        let installed = installed.iter().any(|agent| agent.name == template.name);
+        let marker = if installed { " (installed)" } else { "" };
+        println!("  {:<14} {}{}", template.name, template.summary, marker);
+    }
+}
 
-                SettingsCommands::Validate { path } => {
-                    let settings = ClaudeSettings::read(&path)?;
-                    settings.validate()?;
+/// Load the registry index named by a project's config, warning when only
+/// an out-of-date cached copy could be read
+fn load_registry_index(
+    target_dir: &Path,
+    refresh: bool,
+    use_color: bool,
+) -> catalyst_cli::types::Result<registry::LoadedIndex> {
+    let config = CatalystConfig::load(target_dir)?;
+    let url = registry::registry_url(&config)?;
+    let loaded = registry::load_index(&url, registry::cache_dir().as_deref(), refresh)?;
+    if let Some(error) = &loaded.fetch_error {
+        let warning = format!("⚠️  Using a cached registry index ({})", error);
+        if use_color {
+            eprintln!("{}", warning.yellow());
+        } else {
+            eprintln!("{}", warning);
+        }
+    }
+    Ok(loaded)
+}
 
-                    if use_color {
-                        println!("{}", "✅ Settings file is valid".green().bold());
+/// Display `catalyst skills list`
+fn display_skills(installed: &[catalyst_cli::skills::SkillInfo], use_color: bool) {
+    if installed.is_empty() {
+        println!("No skills in .claude/skills");
+    } else {
+        println!("Installed skills:");
+        for skill in installed {
+            match (&skill.manifest, &skill.problem) {
+                (Some(manifest), None) => {
+                    let version = manifest.version.as_deref().unwrap_or("-");
+                    let linked = if skill.linked { " (linked)" } else { "" };
+                    let check = if use_color {
+                        "✓".green().to_string()
                     } else {
-                        println!("✅ Settings file is valid");
-                    }
-                }
-
-                SettingsCommands::AddHook {
-                    path,
-                    event,
-                    command,
-                    matcher,
-                    dry_run,
-                } => {
-                    // Load existing settings or create new
-                    // Only create defaults for missing files, not for other errors (permissions, invalid JSON, etc.)
-                    let (mut settings, file_existed) = match ClaudeSettings::read(&path) {
-                        Ok(s) => (s, true),
-                        Err(e) => {
-                            // Check if the underlying error is io::ErrorKind::NotFound
-                            // Use downcast_ref to check the root cause
-                            let is_not_found = e.chain().any(|cause| {
-                                cause
-                                    .downcast_ref::<std::io::Error>()
-                                    .map(|io_err| io_err.kind() == std::io::ErrorKind::NotFound)
-                                    .unwrap_or(false)
-                            });
-
-                            if is_not_found {
-                                (ClaudeSettings::default(), false)
-                            } else {
-                                // Propagate other errors (permissions, invalid JSON, etc.)
-                                return Err(e);
-                            }
-                        }
-                    };
-
-                    // Parse event string into HookEvent enum
-                    let hook_event = HookEvent::from_str(&event)?;
-
-                    let hook_config = HookConfig {
-                        matcher: matcher.clone(),
-                        hooks: vec![Hook {
-                            r#type: "command".to_string(),
-                            command: command.clone(),
-                        }],
+                        "✓".to_string()
                     };
-
-                    settings.add_hook(hook_event, hook_config)?;
-
-                    if dry_run {
-                        if use_color {
-                            println!("{}", "🔍 Dry run - would write:".yellow().bold());
-                        } else {
-                            println!("🔍 Dry run - would write:");
-                        }
-                        println!("{}", serde_json::to_string_pretty(&settings)?);
-                    } else {
-                        settings.write(&path)?;
-
-                        if use_color {
-                            if file_existed {
-                                println!(
-                                    "{} {}",
-                                    "✅ Hook added to existing file:".green().bold(),
-                                    path
-                                );
-                            } else {
-                                println!(
-                                    "{} {}",
-                                    "✅ Created new settings file:".green().bold(),
-                                    path
-                                );
-                            }
-                            println!("  {} {}", "Event:".cyan(), event);
-                            println!("  {} {}", "Command:".cyan(), command);
-                            if let Some(m) = matcher {
-                                println!("  {} {}", "Matcher:".cyan(), m);
-                            }
-                        } else {
-                            if file_existed {
-                                println!("✅ Hook added to existing file: {}", path);
-                            } else {
-                                println!("✅ Created new settings file: {}", path);
-                            }
-                            println!("  Event: {}", event);
-                            println!("  Command: {}", command);
-                            if let Some(m) = matcher {
-                                println!("  Matcher: {}", m);
-                            }
-                        }
-                    }
+                    println!(
+                        "  {} {:<26} {:<8} {}{}",
+                        check,
+                        skill.id,
+                        version,
+                        manifest.summary(),
+                        linked
+                    );
                 }
-
-                SettingsCommands::RemoveHook {
-                    path,
-                    event,
-                    pattern,
-                    dry_run,
-                } => {
-                    let mut settings = ClaudeSettings::read(&path)?;
-
-                    // Parse event string into HookEvent enum
-                    let hook_event = HookEvent::from_str(&event)?;
-
-                    settings.remove_hook(hook_event, &pattern);
-
-                    if dry_run {
-                        if use_color {
-                            println!("{}", "🔍 Dry run - would write:".yellow().bold());
-                        } else {
-                            println!("🔍 Dry run - would write:");
-                        }
-                        println!("{}", serde_json::to_string_pretty(&settings)?);
+                (_, problem) => {
+                    if use_color {
+                        println!("  {} {}", "❌".red(), skill.id.red());
                     } else {
-                        settings.write(&path)?;
-                        if use_color {
-                            println!("{} {}", "✅ Hooks removed from".green().bold(), path);
-                        } else {
-                            println!("✅ Hooks removed from {}", path);
-                        }
+                        println!("  ❌ {}", skill.id);
+                    }
+                    if let Some(problem) = problem {
+                        println!("      {}", problem);
                     }
                 }
+            }
+        }
+    }
 
-                SettingsCommands::Merge {
-                    base,
-                    merge,
-                    output,
-                    dry_run,
-                } => {
-                    let mut base_settings = ClaudeSettings::read(&base)?;
-                    let merge_settings = ClaudeSettings::read(&merge)?;
+    println!();
+    println!("Shipped skills:");
+    for manifest in init::shipped_manifests() {
+        let installed = installed.iter().any(|skill| skill.id == manifest.name);
+        let marker = if installed { " (installed)" } else { "" };
+        println!("  {:<26} {}{}", manifest.name, manifest.summary(), marker);
+    }
+}
 
-                    base_settings.merge(merge_settings);
+/// Display `catalyst commands list`
+fn display_commands(installed: &[catalyst_cli::commands::CommandInfo], use_color: bool) {
+    use catalyst_cli::commands::COMMAND_TEMPLATES;
 
-                    // Validate merged result
-                    base_settings.validate()?;
+    if installed.is_empty() {
+        println!("No commands in .claude/commands");
+    } else {
+        println!("Slash commands:");
+        for command in installed {
+            let front_matter = command.front_matter.clone().unwrap_or_default();
+            let usage = match &front_matter.argument_hint {
+                Some(hint) => format!("/{} {}", command.name, hint),
+                None => format!("/{}", command.name),
+            };
+            let valid = command.problems.is_empty();
+            match (use_color, valid) {
+                (true, true) => println!("  {} {}", "✓".green(), usage),
+                (true, false) => println!("  {} {}", "❌".red(), usage.red()),
+                (false, true) => println!("  ✓ {}", usage),
+                (false, false) => println!("  ❌ {}", usage),
+            }
+            if let Some(description) = &front_matter.description {
+                println!("      {}", description);
+            }
+            if !valid {
+                println!(
+                    "      {} problem(s); run 'catalyst commands validate {}'",
+                    command.problems.len(),
+                    command.name
+                );
+            }
+        }
+    }
 
-                    let output_path = output.as_deref().unwrap_or(&base);
+    println!();
+    println!("Templates (catalyst commands new <name> --template <template>):");
+    for template in COMMAND_TEMPLATES {
+        println!("  {:<10} {}", template.name, template.summary);
+    }
+}
 
-                    if dry_run {
-                        if use_color {
-                            println!(
-                                "{} {}:",
-                                "🔍 Dry run - would write to".yellow().bold(),
-                                output_path
-                            );
-                        } else {
-                            println!("🔍 Dry run - would write to {}:", output_path);
-                        }
-                        println!("{}", serde_json::to_string_pretty(&base_settings)?);
-                    } else {
-                        base_settings.write(output_path)?;
-                        if use_color {
-                            println!("{}", "✅ Settings merged successfully".green().bold());
-                            println!("  {} {}", "Base file:".cyan(), base);
-                            println!("  {} {}", "Merged from:".cyan(), merge);
-                            println!("  {} {}", "Output:".cyan(), output_path);
-                        } else {
-                            println!("✅ Settings merged successfully");
-                            println!("  Base file: {}", base);
-                            println!("  Merged from: {}", merge);
-                            println!("  Output: {}", output_path);
-                        }
-                    }
-                }
+/// Print the outcome of `catalyst agents add/new`, exiting on failure
+fn report_agent_written(result: catalyst_cli::Result<PathBuf>, verb: &str, use_color: bool) {
+    match result {
+        Ok(path) => {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            if use_color {
+                println!("{}", format!("✅ {} agent '{}'", verb, name).green());
+            } else {
+                println!("✅ {} agent '{}'", verb, name);
+            }
+            println!("   {}", path.display());
+        }
+        Err(e) => {
+            if use_color {
+                eprintln!("{}", format!("❌ {}", e).red().bold());
+            } else {
+                eprintln!("❌ {}", e);
             }
+            exit(ExitCode::for_error(&e));
         }
     }
+}
 
-    Ok(())
+/// Display one prompt-logger entry on a line
+fn display_prompt_log_entry(entry: &catalyst_cli::prompt_log::PromptLogEntry, use_color: bool) {
+    let session = entry.session_id.as_deref().unwrap_or("-");
+    let skills = if entry.matched_skills.is_empty() {
+        "no skills".to_string()
+    } else {
+        entry.matched_skills.join(", ")
+    };
+    let details = format!(
+        "session {}  {} chars  sha256 {}  {}",
+        session,
+        entry.prompt_length,
+        &entry.prompt_sha256[..entry.prompt_sha256.len().min(12)],
+        skills
+    );
+    if use_color {
+        println!("{}  {}", entry.timestamp.dimmed(), details);
+    } else {
+        println!("{}  {}", entry.timestamp, details);
+    }
 }
 
-/// Display a formatted status report
-fn display_status_report(
-    report: &catalyst_cli::types::StatusReport,
-    use_color: bool,
-    fixed_issues: &[String],
-) {
-    use catalyst_cli::types::{IssueSeverity, StatusLevel};
+/// Display the result of `catalyst fix <issue-id>`
+fn display_targeted_fix_report(report: &catalyst_cli::types::TargetedFixReport, use_color: bool) {
+    if report.matched == 0 {
+        if use_color {
+            println!(
+                "{}",
+                format!("✅ No {} issues found", report.issue_id).green()
+            );
+        } else {
+            println!("✅ No {} issues found", report.issue_id);
+        }
+        return;
+    }
 
-    // Show fixed issues first if any
-    if !fixed_issues.is_empty() {
+    for fixed in &report.fixed {
         if use_color {
-            println!("\n{}", "🔧 Auto-Fix Results:".cyan().bold());
+            println!("{} {}", "✅".green(), fixed);
         } else {
-            println!("\n🔧 Auto-Fix Results:");
+            println!("✅ {}", fixed);
         }
-        for fix in fixed_issues {
-            if use_color {
-                println!("  {}", format!("✓ {}", fix).green());
-            } else {
-                println!("  ✓ {}", fix);
-            }
+    }
+    for failed in &report.failed {
+        if use_color {
+            eprintln!("{} {}", "❌".red(), failed.red());
+        } else {
+            eprintln!("❌ {}", failed);
         }
-        println!();
     }
+}
 
-    // Overall status header
-    let (status_icon, status_text) = match report.level {
-        StatusLevel::Ok => ("✅", "HEALTHY"),
-        StatusLevel::Warning => ("⚠️", "WARNING"),
-        StatusLevel::Error => ("❌", "ERROR"),
+/// Fill in `catalyst skills new` options, prompting for the ones not given
+///
+/// Without prompting, the description gets a placeholder and the keywords
+/// default to the words of the skill name.
+fn new_skill_from_args(
+    name: String,
+    description: Option<String>,
+    keywords: Vec<String>,
+    intent_patterns: Vec<String>,
+    path_patterns: Vec<String>,
+    interactive: bool,
+) -> Result<catalyst_cli::scaffold::NewSkill> {
+    catalyst_cli::scaffold::validate_skill_name(&name)?;
+    let theme = ColorfulTheme::default();
+    let default_description = format!("Describe what {} covers and when to use it", name);
+    let default_keywords = name.replace('-', ", ");
+
+    let description = match description {
+        Some(description) => description,
+        None if interactive => Input::with_theme(&theme)
+            .with_prompt("Description")
+            .default(default_description)
+            .interact_text()?,
+        None => default_description,
     };
 
-    if use_color {
-        match report.level {
-            StatusLevel::Ok => {
-                println!(
-                    "{} {}",
-                    status_icon,
-                    format!("Catalyst Status: {}", status_text).green().bold()
-                );
-            }
-            StatusLevel::Warning => {
-                println!(
-                    "{} {}",
-                    status_icon,
-                    format!("Catalyst Status: {}", status_text).yellow().bold()
-                );
-            }
-            StatusLevel::Error => {
-                println!(
-                    "{} {}",
-                    status_icon,
-                    format!("Catalyst Status: {}", status_text).red().bold()
-                );
+    let keywords = if !keywords.is_empty() {
+        keywords
+    } else {
+        let answer = if interactive {
+            Input::with_theme(&theme)
+                .with_prompt("Keywords (comma-separated)")
+                .default(default_keywords)
+                .interact_text()?
+        } else {
+            default_keywords
+        };
+        answer
+            .split(',')
+            .map(|keyword| keyword.trim().to_string())
+            .filter(|keyword| !keyword.is_empty())
+            .collect()
+    };
+
+    // Regexes and globs can contain commas, so these are asked one per line
+    let prompt_list = |prompt: &str, given: Vec<String>| -> Result<Vec<String>> {
+        if !given.is_empty() || !interactive {
+            return Ok(given);
+        }
+        let mut values = Vec::new();
+        loop {
+            let value: String = Input::with_theme(&theme)
+                .with_prompt(prompt)
+                .allow_empty(true)
+                .interact_text()?;
+            if value.trim().is_empty() {
+                return Ok(values);
             }
+            values.push(value.trim().to_string());
         }
+    };
+    let intent_patterns = prompt_list("Intent pattern (regex, empty to finish)", intent_patterns)?;
+    let path_patterns = prompt_list("Path pattern (glob, empty to finish)", path_patterns)?;
+
+    Ok(catalyst_cli::scaffold::NewSkill {
+        name,
+        description,
+        keywords,
+        intent_patterns,
+        path_patterns,
+    })
+}
+
+/// Display skill-rules.json lint findings
+fn display_lint_report(report: &catalyst_cli::lint::LintReport, use_color: bool) {
+    use catalyst_cli::types::IssueSeverity;
+
+    if use_color {
+        println!("{} {}", "Linting".cyan().bold(), report.path.display());
     } else {
-        println!("{} Catalyst Status: {}", status_icon, status_text);
+        println!("Linting {}", report.path.display());
     }
     println!();
 
-    // Binaries section
-    if !report.binaries.is_empty() {
+    for finding in &report.findings {
+        let icon = match finding.severity {
+            IssueSeverity::Error => "❌",
+            IssueSeverity::Warning => "⚠️ ",
+            IssueSeverity::Info => "ℹ️ ",
+        };
+        let location = finding
+            .skill
+            .as_deref()
+            .map(|skill| format!("[{}] ", skill))
+            .unwrap_or_default();
         if use_color {
-            println!("{}", "Binaries:".cyan().bold());
+            let message = match finding.severity {
+                IssueSeverity::Error => finding.message.red(),
+                IssueSeverity::Warning => finding.message.yellow(),
+                IssueSeverity::Info => finding.message.normal(),
+            };
+            println!("{} {}{}", icon, location.cyan(), message);
         } else {
-            println!("Binaries:");
+            println!("{} {}{}", icon, location, finding.message);
         }
-        for binary in &report.binaries {
-            let status_icon = if binary.exists && binary.executable {
-                "✓"
-            } else {
-                "✗"
-            };
-            let status_text = if binary.exists {
-                if binary.executable {
-                    "found"
-                } else {
-                    "not executable"
-                }
-            } else {
-                "not found"
-            };
+    }
 
-            let variant_text = if let Some(ref v) = binary.variant {
-                format!(" ({})", v)
-            } else {
-                String::new()
-            };
+    if !report.findings.is_empty() {
+        println!();
+    }
 
-            if use_color {
-                if binary.exists && binary.executable {
-                    println!(
-                        "  {} {}{}",
-                        status_icon,
-                        format!("{} ({})", binary.name, status_text).green(),
-                        variant_text
-                    );
-                } else {
-                    println!(
-                        "  {} {}{}",
-                        status_icon,
-                        format!("{} ({})", binary.name, status_text).red(),
-                        variant_text
-                    );
-                }
+    let summary = format!(
+        "{} skill(s) checked: {} error(s), {} warning(s)",
+        report.skills_checked,
+        report.count(IssueSeverity::Error),
+        report.count(IssueSeverity::Warning)
+    );
+    if !use_color {
+        println!("{}", summary);
+    } else if report.has_errors() {
+        println!("{}", summary.red().bold());
+    } else if report.findings.is_empty() {
+        println!("{}", summary.green().bold());
+    } else {
+        println!("{}", summary.yellow().bold());
+    }
+}
+
+/// Read settings.json, starting from defaults if the file doesn't exist
+///
+/// Only a missing file falls back to defaults; permission and parse errors
+/// are returned. The flag says whether the file existed.
+fn read_settings_or_default(path: &str) -> Result<(ClaudeSettings, bool)> {
+    match ClaudeSettings::read(path) {
+        Ok(settings) => Ok((settings, true)),
+        Err(e) => {
+            let is_not_found = e.chain().any(|cause| {
+                cause
+                    .downcast_ref::<std::io::Error>()
+                    .is_some_and(|io_err| io_err.kind() == std::io::ErrorKind::NotFound)
+            });
+            if is_not_found {
+                Ok((ClaudeSettings::default(), false))
             } else {
-                println!(
-                    "  {} {} ({}){}",
-                    status_icon, binary.name, status_text, variant_text
-                );
+                Err(e)
             }
         }
-        println!();
     }
+}
 
-    // Hooks section
-    if !report.hooks.is_empty() {
-        if use_color {
-            println!("{}", "Hooks:".cyan().bold());
-        } else {
-            println!("Hooks:");
-        }
-        for hook in &report.hooks {
-            let status_icon = if hook.exists && hook.executable && hook.calls_correct_binary {
-                "✓"
-            } else {
-                "✗"
-            };
-            let event = hook.event.as_deref().unwrap_or("unknown");
+/// Run `catalyst settings mcp`
+///
+/// Server names are checked against the project's .mcp.json (next to the
+/// `.claude` directory holding settings.json) when it exists.
+fn run_mcp_command(
+    path: &str,
+    command: Option<McpCommands>,
+    enable_all: Option<bool>,
+    dry_run: bool,
+    use_color: bool,
+) -> Result<()> {
+    let non_empty = |dir: &Path| !dir.as_os_str().is_empty();
+    let settings_dir = Path::new(path)
+        .parent()
+        .filter(|dir| non_empty(dir))
+        .unwrap_or(Path::new("."));
+    let project_dir = if settings_dir
+        .file_name()
+        .is_some_and(|name| name == ".claude")
+    {
+        settings_dir
+            .parent()
+            .filter(|dir| non_empty(dir))
+            .unwrap_or(Path::new("."))
+    } else {
+        settings_dir
+    };
+    let defined = mcp_json_servers(project_dir)?;
+    let (mut settings, _) = read_settings_or_default(path)?;
 
-            if use_color {
-                if hook.exists && hook.executable && hook.calls_correct_binary {
-                    println!("  {} {} → {}", status_icon, event.green(), hook.name);
+    let mut changes = Vec::new();
+    match command {
+        // Listing is the default when nothing is being changed
+        None if enable_all.is_none() => {
+            return list_mcp_servers(&settings, defined, false, use_color)
+        }
+        Some(McpCommands::List { json }) => {
+            return list_mcp_servers(&settings, defined, json, use_color)
+        }
+        None => {}
+        Some(McpCommands::Add { names, force }) => {
+            if let (Some(defined), false) = (&defined, force) {
+                let known: Vec<&str> = defined.iter().map(String::as_str).collect();
+                for name in &names {
+                    if !defined.contains(name) {
+                        let mut msg = format!("'{}' is not defined in {}", name, MCP_JSON_FILE);
+                        match find_closest_match(name, &known) {
+                            Some(closest) => {
+                                msg.push_str(&format!(". Did you mean '{}'?", closest))
+                            }
+                            None if known.is_empty() => msg.push_str(" (it defines no servers)"),
+                            None => msg.push_str(&format!(" (defined: {})", known.join(", "))),
+                        }
+                        msg.push_str("\nUse --force to enable it anyway.");
+                        anyhow::bail!(msg);
+                    }
+                }
+            }
+            for name in names {
+                if settings.add_mcp_server(&name) {
+                    changes.push(format!("Enabled MCP server: {}", name));
                 } else {
-                    println!("  {} {} → {}", status_icon, event.red(), hook.name);
+                    changes.push(format!("Already enabled: {}", name));
                 }
-            } else {
-                println!("  {} {} → {}", status_icon, event, hook.name);
             }
         }
-        println!();
+        Some(McpCommands::Remove { names }) => {
+            for name in names {
+                if settings.remove_mcp_server(&name) {
+                    changes.push(format!("Removed MCP server: {}", name));
+                } else {
+                    changes.push(format!("Not enabled: {}", name));
+                }
+            }
+        }
+    }
+    if let Some(enable_all) = enable_all {
+        settings.enable_all_project_mcp_servers = enable_all;
+        changes.push(format!("enableAllProjectMcpServers = {}", enable_all));
     }
 
-    // Skills section
-    if !report.skills.is_empty() {
+    if dry_run {
         if use_color {
-            println!("{}", "Skills:".cyan().bold());
+            println!("{}", "🔍 Dry run - would write:".yellow().bold());
         } else {
-            println!("Skills:");
+            println!("🔍 Dry run - would write:");
         }
-        for skill in &report.skills {
-            let status_icon = if skill.has_main_file { "✓" } else { "✗" };
-            let status_text = if skill.has_main_file {
-                "installed"
-            } else {
-                "incomplete"
-            };
+        println!("{}", serde_json::to_string_pretty(&settings)?);
+        return Ok(());
+    }
+    settings.write(path)?;
+    if use_color {
+        println!("{} {}", "✅ Updated".green().bold(), path);
+    } else {
+        println!("✅ Updated {}", path);
+    }
+    for change in changes {
+        println!("  {}", change);
+    }
+    Ok(())
+}
+
+/// Display `catalyst settings mcp list`
+fn list_mcp_servers(
+    settings: &ClaudeSettings,
+    defined: Option<Vec<String>>,
+    json: bool,
+    use_color: bool,
+) -> Result<()> {
+    let disabled = settings.disabled_mcp_servers();
+    let available: Vec<&String> = defined
+        .iter()
+        .flatten()
+        .filter(|name| !settings.enabled_mcpjson_servers.contains(name) && !disabled.contains(name))
+        .collect();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "enableAllProjectMcpServers": settings.enable_all_project_mcp_servers,
+                "enabled": settings.enabled_mcpjson_servers,
+                "disabled": disabled,
+                "available": available,
+                "defined": defined,
+            }))?
+        );
+        return Ok(());
+    }
+
+    let all = if settings.enable_all_project_mcp_servers {
+        "on (every .mcp.json server is enabled)"
+    } else {
+        "off"
+    };
+    println!("enableAllProjectMcpServers: {}", all);
+    if defined.is_none() {
+        println!(
+            "No {} in this project; server names can't be checked",
+            MCP_JSON_FILE
+        );
+    }
+    println!();
 
+    let undefined = |name: &String| defined.as_ref().is_some_and(|d| !d.contains(name));
+    println!("Enabled:");
+    if settings.enabled_mcpjson_servers.is_empty() {
+        println!("  (none)");
+    }
+    for name in &settings.enabled_mcpjson_servers {
+        if !undefined(name) {
             if use_color {
-                if skill.has_main_file {
-                    println!("  {} {} ({})", status_icon, skill.name.green(), status_text);
-                } else {
-                    println!("  {} {} ({})", status_icon, skill.name.red(), status_text);
-                }
+                println!("  {} {}", "✓".green(), name);
             } else {
-                println!("  {} {} ({})", status_icon, skill.name, status_text);
+                println!("  ✓ {}", name);
             }
+        } else if use_color {
+            println!(
+                "  {} {} {}",
+                "⚠️ ".yellow(),
+                name,
+                format!("(not defined in {})", MCP_JSON_FILE).yellow()
+            );
+        } else {
+            println!("  ⚠️  {} (not defined in {})", name, MCP_JSON_FILE);
+        }
+    }
+    if !disabled.is_empty() {
+        println!();
+        println!("Disabled:");
+        for name in &disabled {
+            println!("  - {}", name);
         }
+    }
+    if !available.is_empty() {
         println!();
+        println!("Available (catalyst settings mcp add <name>):");
+        for name in available {
+            println!("  {}", name);
+        }
     }
+    Ok(())
+}
 
-    // Issues section
-    if !report.issues.is_empty() {
+/// Run `catalyst settings validate`, printing every problem found
+///
+/// # Returns
+///
+/// Returns the exit code: `Ok` if valid, `Errors` for schema or semantic
+/// errors, and `Failure` if the file can't be read or isn't JSON
+fn validate_settings_file(path: &str, strict: bool, use_color: bool) -> ExitCode {
+    let report_error = |message: String| {
         if use_color {
-            println!("{}", "Issues:".cyan().bold());
+            eprintln!("{}", format!("❌ {}", message).red().bold());
         } else {
-            println!("Issues:");
+            eprintln!("❌ {}", message);
         }
-        for issue in &report.issues {
-            let severity_icon = match issue.severity {
-                IssueSeverity::Error => "❌",
-                IssueSeverity::Warning => "⚠️",
-                IssueSeverity::Info => "ℹ️",
-            };
+    };
 
-            if use_color {
-                let colored_desc = match issue.severity {
-                    IssueSeverity::Error => issue.description.red(),
-                    IssueSeverity::Warning => issue.description.yellow(),
-                    IssueSeverity::Info => issue.description.blue(),
-                };
-                println!("  {} [{}] {}", severity_icon, issue.component, colored_desc);
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            report_error(format!("Failed to read {}: {}", path, e));
+            return ExitCode::Failure;
+        }
+    };
+    let value: serde_json::Value = match catalyst_core::jsonc::from_str(&content) {
+        Ok(value) => value,
+        Err(e) => {
+            report_error(format!("{} is not valid JSON: {}", path, e));
+            return ExitCode::Failure;
+        }
+    };
+
+    let schema_errors = catalyst_core::settings_schema::validate(&value);
+    if !schema_errors.is_empty() {
+        for error in &schema_errors {
+            report_error(error.to_string());
+        }
+        report_error(format!(
+            "{} has {} schema error(s)",
+            path,
+            schema_errors.len()
+        ));
+        return ExitCode::Errors;
+    }
+
+    let checked = serde_json::from_value::<ClaudeSettings>(value)
+        .map_err(anyhow::Error::from)
+        .and_then(|settings| {
+            if strict {
+                settings.validate_strict()
             } else {
-                println!(
-                    "  {} [{}] {}",
-                    severity_icon, issue.component, issue.description
-                );
+                settings.validate().map(|()| Vec::new())
             }
-
-            if let Some(ref fix) = issue.suggested_fix {
+        });
+    match checked {
+        Ok(warnings) => {
+            for warning in warnings {
                 if use_color {
-                    println!("     {}", format!("→ {}", fix).cyan());
+                    say!("{} {}", "⚠️".yellow(), warning);
                 } else {
-                    println!("     → {}", fix);
+                    say!("⚠️  {}", warning);
                 }
             }
         }
-        println!();
+        Err(e) => {
+            report_error(e.to_string());
+            return ExitCode::Errors;
+        }
+    }
+
+    if use_color {
+        say!("{}", "✅ Settings file is valid".green().bold());
     } else {
-        if use_color {
-            println!("{}", "Issues: None".green());
+        say!("✅ Settings file is valid");
+    }
+    ExitCode::Ok
+}
+
+/// Display `catalyst settings effective`
+fn display_effective_settings(resolved: &ResolvedSettings, use_color: bool) {
+    println!("Layers (later layers take precedence):");
+    for layer in &resolved.layers {
+        let state = if layer.exists { "" } else { " (not found)" };
+        if use_color && !layer.exists {
+            println!(
+                "  {:<8} {}{}",
+                layer.scope,
+                layer.path.display(),
+                state.dimmed()
+            );
         } else {
-            println!("Issues: None");
+            println!("  {:<8} {}{}", layer.scope, layer.path.display(), state);
         }
-        println!();
     }
+    println!();
 
-    // Final message
-    if report.level == StatusLevel::Ok {
+    if resolved.sources.is_empty() {
+        println!("No settings in any layer");
+        return;
+    }
+    println!("Effective settings:");
+    let width = resolved
+        .sources
+        .iter()
+        .map(|source| source.key.len())
+        .max()
+        .unwrap_or(0);
+    for source in &resolved.sources {
+        let scope = format!("[{}]", source.scope);
         if use_color {
-            println!("{}", "All systems operational! 🚀".green().bold());
+            println!(
+                "  {:<width$}  {}  {}",
+                source.key.bold(),
+                source.value,
+                scope.cyan()
+            );
         } else {
-            println!("All systems operational! 🚀");
+            println!("  {:<width$}  {}  {}", source.key, source.value, scope);
+        }
+    }
+}
+
+/// Run a `catalyst config` subcommand
+fn run_config_command(command: ConfigCommands, use_color: bool) -> Result<()> {
+    match command {
+        ConfigCommands::List { path } => {
+            let target_dir = project::resolve_target_dir(path);
+            let project_config = CatalystConfig::load(&target_dir)?;
+
+            match CatalystConfig::find(&target_dir) {
+                Some(file) => println!("# Loaded from {}", file.display()),
+                None => println!("# No catalyst.toml found - showing defaults"),
+            }
+            println!();
+            for (key, description) in config::KNOWN_KEYS {
+                let value = project_config
+                    .get(key)?
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "(unset)".to_string());
+                if use_color {
+                    println!("{} = {}", key.cyan(), value);
+                    println!("  {}", description.dimmed());
+                } else {
+                    println!("{} = {}", key, value);
+                    println!("  {}", description);
+                }
+            }
         }
-    } else if report.issues.iter().any(|i| i.auto_fixable) && fixed_issues.is_empty() {
+        ConfigCommands::Get { key, path } => {
+            let project_config = CatalystConfig::load(&project::resolve_target_dir(path))?;
+            match project_config.get(&key)? {
+                Some(value) => println!("{}", value),
+                None => println!("(unset)"),
+            }
+        }
+        ConfigCommands::Set { key, value, path } => {
+            let file = config::set_value(&project::resolve_target_dir(path), &key, &value)?;
+            if use_color {
+                println!(
+                    "{} {} = {} in {}",
+                    "✅".green(),
+                    key.cyan(),
+                    value,
+                    file.display()
+                );
+            } else {
+                println!("✅ {} = {} in {}", key, value, file.display());
+            }
+        }
+        ConfigCommands::Unset { key, path } => {
+            let file = config::unset_value(&project::resolve_target_dir(path), &key)?;
+            println!("✅ Removed {} from {}", key, file.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Display the plan produced by `init --dry-run`
+/// Run the post-install scripts of `skill_ids`, asking before each one
+///
+/// Scripts are skipped without asking when `no_scripts` is set, in CI, or
+/// without a terminal to confirm on.
+fn run_skill_scripts(
+    target_dir: &Path,
+    skill_ids: &[String],
+    platform: catalyst_cli::types::Platform,
+    no_scripts: bool,
+) -> Result<Vec<catalyst_cli::types::ScriptResult>> {
+    if no_scripts || scripts::scripts_disabled_by_default() {
+        return Ok(scripts::run_scripts(target_dir, skill_ids, platform, None));
+    }
+
+    let mut prompt_error = None;
+    let mut confirm = |skill: &str, script: &Path| {
+        let answer = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Skill '{}' ships a setup script ({}). Run it?",
+                skill,
+                script.display()
+            ))
+            .default(false)
+            .interact();
+        answer.unwrap_or_else(|e| {
+            prompt_error.get_or_insert(e);
+            false
+        })
+    };
+    let results = scripts::run_scripts(target_dir, skill_ids, platform, Some(&mut confirm));
+
+    match prompt_error {
+        Some(e) => Err(e.into()),
+        None => Ok(results),
+    }
+}
+
+/// Display a long-form issue explanation
+fn display_issue_explanation(
+    explanation: &catalyst_cli::explain::IssueExplanation,
+    use_color: bool,
+) {
+    let sections = [
+        ("Why it matters:", explanation.why),
+        ("How Catalyst detects it:", explanation.detection),
+    ];
+
+    if use_color {
+        println!(
+            "{} {}",
+            explanation.kind.id().bold(),
+            explanation.title.cyan().bold()
+        );
+    } else {
+        println!("{} {}", explanation.kind.id(), explanation.title);
+    }
+    println!();
+
+    for (heading, body) in sections {
         if use_color {
-            println!(
-                "{}",
-                "Run 'catalyst status --fix' to auto-repair fixable issues.".yellow()
-            );
+            println!("{}", heading.cyan().bold());
         } else {
-            println!("Run 'catalyst status --fix' to auto-repair fixable issues.");
+            println!("{}", heading);
         }
+        println!("  {}", body);
+        println!();
+    }
+
+    if use_color {
+        println!("{}", "How to fix it:".cyan().bold());
+    } else {
+        println!("How to fix it:");
+    }
+    for (i, fix) in explanation.fixes.iter().enumerate() {
+        println!("  {}. {}", i + 1, fix);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_flags() {
+        let parse = |args: &[&str]| Cli::try_parse_from(args).map(|cli| cli.verbosity());
+
+        assert_eq!(parse(&["catalyst", "status"]).unwrap(), Verbosity::Normal);
+        assert_eq!(
+            parse(&["catalyst", "-q", "status"]).unwrap(),
+            Verbosity::Quiet
+        );
+        // The flags are global, so they also work after the subcommand
+        assert_eq!(
+            parse(&["catalyst", "status", "--verbose"]).unwrap(),
+            Verbosity::Verbose
+        );
+        assert!(parse(&["catalyst", "--quiet", "--verbose", "status"]).is_err());
     }
 }
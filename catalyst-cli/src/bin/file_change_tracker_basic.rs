@@ -0,0 +1,48 @@
+// File change tracker hook (PostToolUse) without SQLite
+//
+// Same tracking as file-change-tracker, but always appends to
+// ~/.claude/hooks-state-rust/modifications.jsonl, so it builds without the
+// `sqlite` feature. `catalyst init --tracker-backend jsonl` wires it in.
+use anyhow::Result;
+use catalyst_cli::file_tracking::{self, PUSH_ANALYTICS_ARG};
+use catalyst_cli::hook_stats::{HookOutcome, HookTimer};
+use catalyst_cli::modification_store::TrackerBackend;
+use std::env;
+use std::io::{self, Read};
+use std::path::Path;
+
+fn main() -> Result<()> {
+    // Initialize tracing
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
+    match &env::args().skip(1).collect::<Vec<_>>()[..] {
+        [flag] if flag == "--version" => {
+            println!("file-change-tracker-basic {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        [flag, project_dir] if flag == PUSH_ANALYTICS_ARG => {
+            return Ok(file_tracking::push_analytics(Path::new(project_dir))?);
+        }
+        _ => {}
+    }
+
+    let timer = HookTimer::start("file-change-tracker-basic");
+    let result = read_stdin()
+        .and_then(|input| Ok(file_tracking::track(&input, Some(TrackerBackend::Jsonl))?));
+    match &result {
+        Ok(()) => timer.finish(HookOutcome::Ok, None),
+        Err(e) => timer.finish(HookOutcome::Error, Some(e.to_string())),
+    }
+    result
+}
+
+fn read_stdin() -> Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
@@ -0,0 +1,388 @@
+// Path guard hook - blocks edits to protected files (PreToolUse)
+//
+// Reads the PreToolUse payload from stdin and denies Edit/Write/MultiEdit/
+// NotebookEdit calls whose target matches a pattern in `protected` but not
+// in `allowed`, both read from .claude/path-guard.json:
+//
+//     {
+//       "protected": ["*.lock", "migrations/**", ".env*"],
+//       "allowed": [".env.example"]
+//     }
+//
+// Patterns without a '/' match the file name anywhere in the project; the
+// rest match the path relative to the project root. Without a config file
+// the defaults below apply.
+use catalyst_cli::hook_stats::{HookOutcome, HookTimer};
+use catalyst_core::hooks::{HookDecision, HookInput, HookResponse, EXIT_SUCCESS};
+use catalyst_core::settings::HookEvent;
+use clap::Parser;
+use globset::{GlobBuilder, GlobMatcher};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const CONFIG_FILE: &str = ".claude/path-guard.json";
+const GUARDED_TOOLS: &[&str] = &["Edit", "Write", "MultiEdit", "NotebookEdit"];
+
+/// Lockfiles and secrets, used when the project has no path-guard.json
+const DEFAULT_PROTECTED: &[&str] = &[
+    "*.lock",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    ".env",
+    ".env.*",
+    "*.pem",
+    "*.key",
+    "**/secrets/**",
+];
+const DEFAULT_ALLOWED: &[&str] = &[".env.example"];
+
+#[derive(Error, Debug)]
+enum PathGuardError {
+    #[error("[PG001] Failed to read input from stdin")]
+    StdinRead(#[from] io::Error),
+
+    #[error("[PG002] Invalid JSON input from hook: {0}\nCheck that the hook is passing valid JSON format")]
+    InvalidHookInput(#[source] serde_json::Error),
+
+    #[error("[PG003] Invalid path guard config {}: {message}\nFix the file or remove it to use the defaults", path.display())]
+    InvalidConfig { path: PathBuf, message: String },
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GuardConfig {
+    #[serde(default)]
+    protected: Vec<String>,
+    #[serde(default)]
+    allowed: Vec<String>,
+}
+
+impl GuardConfig {
+    fn defaults() -> Self {
+        Self {
+            protected: DEFAULT_PROTECTED.iter().map(|s| s.to_string()).collect(),
+            allowed: DEFAULT_ALLOWED.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+/// PreToolUse response refusing the tool call
+fn deny(reason: String) -> HookResponse {
+    HookResponse::builder(HookEvent::PreToolUse)
+        .decision(HookDecision::Block)
+        .reason(reason)
+        .build()
+}
+
+/// A compiled guard pattern
+struct Pattern {
+    source: String,
+    /// Whether the pattern contains a '/' and so matches the whole path
+    by_path: bool,
+    matcher: GlobMatcher,
+}
+
+/// Patterns checked in order
+struct PatternSet(Vec<Pattern>);
+
+impl PatternSet {
+    fn new(patterns: &[String]) -> Result<Self, String> {
+        patterns
+            .iter()
+            .map(|pattern| {
+                let by_path = pattern.contains('/');
+                let glob = GlobBuilder::new(pattern.trim_start_matches('/'))
+                    .literal_separator(by_path)
+                    .build()
+                    .map_err(|e| format!("bad pattern '{}': {}", pattern, e))?;
+                Ok(Pattern {
+                    source: pattern.clone(),
+                    by_path,
+                    matcher: glob.compile_matcher(),
+                })
+            })
+            .collect::<Result<_, String>>()
+            .map(Self)
+    }
+
+    /// First pattern matching `relative`, for the denial message
+    fn first_match(&self, relative: &str) -> Option<&str> {
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        self.0
+            .iter()
+            .find(|pattern| {
+                pattern
+                    .matcher
+                    .is_match(if pattern.by_path { relative } else { name })
+            })
+            .map(|pattern| pattern.source.as_str())
+    }
+}
+
+/// Loaded deny and allow patterns
+struct Guard {
+    protected: PatternSet,
+    allowed: PatternSet,
+}
+
+impl Guard {
+    fn from_config(config: &GuardConfig) -> Result<Self, String> {
+        Ok(Self {
+            protected: PatternSet::new(&config.protected)?,
+            allowed: PatternSet::new(&config.allowed)?,
+        })
+    }
+
+    /// The protecting pattern if `relative` may not be modified
+    fn check(&self, relative: &str) -> Option<&str> {
+        if self.allowed.first_match(relative).is_some() {
+            return None;
+        }
+        self.protected.first_match(relative)
+    }
+}
+
+/// The project's path-guard.json; None when it has none
+fn load_config(project_dir: &Path) -> Result<Option<GuardConfig>, PathGuardError> {
+    let path = project_dir.join(CONFIG_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(PathGuardError::InvalidConfig {
+                path,
+                message: e.to_string(),
+            })
+        }
+    };
+
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| PathGuardError::InvalidConfig {
+            path,
+            message: e.to_string(),
+        })
+}
+
+/// Path of `file` relative to the project, with '/' separators
+///
+/// Files outside the project keep their full path, so only name patterns
+/// can match them.
+fn relative_path(project_dir: &Path, file: &str) -> String {
+    let path = Path::new(file);
+    let path = if path.is_absolute() {
+        path.strip_prefix(project_dir).unwrap_or(path)
+    } else {
+        path
+    };
+    path.to_string_lossy()
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .to_string()
+}
+
+/// Files the tool call would modify
+fn target_files(tool_input: &Map<String, Value>) -> Vec<String> {
+    let mut files: Vec<String> = ["file_path", "notebook_path"]
+        .iter()
+        .filter_map(|key| tool_input.get(*key).and_then(|v| v.as_str()))
+        .map(str::to_string)
+        .collect();
+
+    if let Some(edits) = tool_input.get("edits").and_then(|v| v.as_array()) {
+        files.extend(
+            edits
+                .iter()
+                .filter_map(|edit| edit.get("file_path").and_then(|v| v.as_str()))
+                .map(str::to_string),
+        );
+    }
+    files
+}
+
+fn evaluate(input: &HookInput, project_dir: &Path) -> Result<Option<HookResponse>, PathGuardError> {
+    let Some(tool_name) = input.tool_name.as_deref() else {
+        return Ok(None);
+    };
+    if !GUARDED_TOOLS.contains(&tool_name) {
+        return Ok(None);
+    }
+    let Some(tool_input) = &input.tool_input else {
+        return Ok(None);
+    };
+
+    let files = target_files(tool_input);
+    if files.is_empty() {
+        return Ok(None);
+    }
+
+    let loaded = load_config(project_dir)?;
+    let by_default = loaded.is_none();
+    let config = loaded.unwrap_or_else(GuardConfig::defaults);
+    let guard = Guard::from_config(&config).map_err(|message| PathGuardError::InvalidConfig {
+        path: project_dir.join(CONFIG_FILE),
+        message,
+    })?;
+
+    for file in &files {
+        let relative = relative_path(project_dir, file);
+        if let Some(pattern) = guard.check(&relative) {
+            // Without a config file there is no "allowed" list to point at
+            let reason = if by_default {
+                format!(
+                    "{} is protected by path-guard by default (pattern '{}'). \
+                     Ask the user to make this change, or create {} to choose \
+                     which files are protected.",
+                    relative, pattern, CONFIG_FILE
+                )
+            } else {
+                format!(
+                    "{} is protected by path-guard (pattern '{}' in {}). \
+                     Ask the user to make this change, or add the file to \"allowed\".",
+                    relative, pattern, CONFIG_FILE
+                )
+            };
+            return Ok(Some(deny(reason)));
+        }
+    }
+    Ok(None)
+}
+
+fn run() -> Result<Option<HookResponse>, PathGuardError> {
+    let mut buffer = String::new();
+    io::stdin().read_to_string(&mut buffer)?;
+    let input: HookInput =
+        serde_json::from_str(&buffer).map_err(PathGuardError::InvalidHookInput)?;
+
+    // Absolute so target paths can be made relative to it
+    let project_dir = input.project_dir();
+    let project_dir = std::path::absolute(&project_dir).unwrap_or(project_dir);
+
+    match evaluate(&input, &project_dir) {
+        // A broken config fails closed: block and say why
+        Err(e @ PathGuardError::InvalidConfig { .. }) => Ok(Some(deny(e.to_string()))),
+        other => other,
+    }
+}
+
+/// Guards protected files (PreToolUse hook)
+#[derive(Parser)]
+#[command(name = "path-guard")]
+#[command(about = "Blocks edits to protected files named in the hook payload read from stdin", long_about = None)]
+#[command(version)]
+struct Args {}
+
+fn main() {
+    Args::parse();
+    let timer = HookTimer::start("path-guard");
+    let code = match run() {
+        Ok(response) => {
+            timer.finish(HookOutcome::of(response.as_ref()), None);
+            response.map_or(EXIT_SUCCESS, |response| response.emit())
+        }
+        Err(e) => {
+            timer.finish(HookOutcome::Error, Some(e.to_string()));
+            // Unreadable input says nothing about the target file; don't block every tool call
+            eprintln!("path-guard: {}", e);
+            EXIT_SUCCESS
+        }
+    };
+    std::process::exit(code);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use catalyst_core::hooks::PermissionDecision;
+    use tempfile::TempDir;
+
+    fn input(tool: &str, file: &str) -> HookInput {
+        serde_json::from_value(serde_json::json!({
+            "session_id": "s",
+            "tool_name": tool,
+            "tool_input": {"file_path": file}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_defaults_protect_lockfiles_and_secrets() {
+        let guard = Guard::from_config(&GuardConfig::defaults()).unwrap();
+        assert_eq!(guard.check("Cargo.lock"), Some("*.lock"));
+        assert_eq!(
+            guard.check("web/package-lock.json"),
+            Some("package-lock.json")
+        );
+        assert_eq!(guard.check("config/secrets/db.txt"), Some("**/secrets/**"));
+        assert_eq!(guard.check(".env.local"), Some(".env.*"));
+        assert_eq!(guard.check(".env.example"), None);
+        assert_eq!(guard.check("src/lock.rs"), None);
+    }
+
+    #[test]
+    fn test_evaluate_uses_project_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        fs::create_dir_all(project.join(".claude")).unwrap();
+        fs::write(
+            project.join(CONFIG_FILE),
+            r#"{"protected": ["migrations/**"], "allowed": ["migrations/README.md"]}"#,
+        )
+        .unwrap();
+
+        let migration = project.join("migrations/001_init.sql");
+        let response = evaluate(&input("Write", migration.to_str().unwrap()), project)
+            .unwrap()
+            .unwrap();
+        let output = response.hook_specific_output.unwrap();
+        assert_eq!(output.permission_decision, Some(PermissionDecision::Deny));
+        assert!(output
+            .permission_decision_reason
+            .unwrap()
+            .contains("migrations/001_init.sql"));
+
+        assert!(evaluate(&input("Edit", "migrations/README.md"), project)
+            .unwrap()
+            .is_none());
+        // The config replaces the defaults, and read-only tools are never guarded
+        assert!(evaluate(&input("Edit", "Cargo.lock"), project)
+            .unwrap()
+            .is_none());
+        assert!(evaluate(&input("Read", "migrations/001_init.sql"), project)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_default_denial_points_at_creating_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+
+        let response = evaluate(&input("Edit", "Cargo.lock"), project)
+            .unwrap()
+            .unwrap();
+        let reason = response
+            .hook_specific_output
+            .unwrap()
+            .permission_decision_reason
+            .unwrap();
+        assert!(reason.contains("protected by path-guard by default (pattern '*.lock')"));
+        assert!(reason.contains("create .claude/path-guard.json"));
+        assert!(!reason.contains("\"allowed\""));
+    }
+
+    #[test]
+    fn test_invalid_config_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        fs::create_dir_all(project.join(".claude")).unwrap();
+        fs::write(project.join(CONFIG_FILE), r#"{"protect": ["*.lock"]}"#).unwrap();
+
+        let result = evaluate(&input("Write", "Cargo.lock"), project);
+        assert!(matches!(result, Err(PathGuardError::InvalidConfig { .. })));
+    }
+}
@@ -0,0 +1,105 @@
+// Prompt logger hook - appends redacted prompt metadata to .claude/logs (UserPromptSubmit)
+//
+// Opt-in: nothing is logged unless the hook is registered in settings.json.
+// The prompt text is never written, only its length, hash, and the skills it
+// matched (see catalyst_cli::prompt_log). The hook prints nothing, so it
+// can't change what Claude sees, and a failure to log never blocks a prompt.
+use catalyst_cli::hook_stats::{HookOutcome, HookTimer};
+use catalyst_cli::prompt_log::{self, PromptLogEntry};
+use catalyst_cli::types::SKILL_RULES_FILE;
+use catalyst_core::hooks::HookInput;
+use clap::Parser;
+use std::io::{self, Read};
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+enum PromptLoggerError {
+    #[error("[PL001] Failed to read input from stdin")]
+    StdinRead(#[from] io::Error),
+
+    #[error("[PL002] Invalid JSON input from hook: {0}\nCheck that the hook is passing valid JSON format")]
+    InvalidHookInput(#[source] serde_json::Error),
+
+    #[error("[PL003] Failed to write the prompt log: {0}")]
+    LogWrite(#[source] catalyst_cli::CatalystError),
+}
+
+/// Build the log entry for a prompt
+fn entry_for(input: &HookInput, project_dir: &Path) -> PromptLogEntry {
+    let prompt = input.prompt.as_deref().unwrap_or_default();
+    let matched = prompt_log::matched_skills(&project_dir.join(SKILL_RULES_FILE), prompt);
+    PromptLogEntry::new(input.session_id.as_deref(), prompt, matched)
+}
+
+fn run() -> Result<(), PromptLoggerError> {
+    let mut buffer = String::new();
+    io::stdin().read_to_string(&mut buffer)?;
+    let input: HookInput =
+        serde_json::from_str(&buffer).map_err(PromptLoggerError::InvalidHookInput)?;
+
+    let project_dir = input.project_dir();
+
+    prompt_log::append(&project_dir, &entry_for(&input, &project_dir))
+        .map_err(PromptLoggerError::LogWrite)
+}
+
+/// Logs prompt metadata (UserPromptSubmit hook)
+#[derive(Parser)]
+#[command(name = "prompt-logger")]
+#[command(about = "Logs redacted metadata for the prompt in the hook payload read from stdin", long_about = None)]
+#[command(version)]
+struct Args {}
+
+fn main() {
+    Args::parse();
+    let timer = HookTimer::start("prompt-logger");
+    match run() {
+        Ok(()) => timer.finish(HookOutcome::Ok, None),
+        Err(e) => {
+            timer.finish(HookOutcome::Error, Some(e.to_string()));
+            eprintln!("prompt-logger: {}", e);
+        }
+    }
+    std::process::exit(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_logs_metadata_without_prompt_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        fs::create_dir_all(project.join(".claude/skills")).unwrap();
+        fs::write(
+            project.join(SKILL_RULES_FILE),
+            r#"{"version": "1.0", "skills": {"rust-developer": {"type": "domain",
+                "enforcement": "suggest", "priority": "high",
+                "promptTriggers": {"keywords": ["rust"]}}}}"#,
+        )
+        .unwrap();
+        let input: HookInput = serde_json::from_value(serde_json::json!({
+            "session_id": "s1",
+            "prompt": "secret plan for the rust parser",
+            "hook_event_name": "UserPromptSubmit"
+        }))
+        .unwrap();
+
+        prompt_log::append(project, &entry_for(&input, project)).unwrap();
+
+        let log = fs::read_to_string(prompt_log::log_path(project)).unwrap();
+        assert!(!log.contains("secret"));
+        let entries = prompt_log::read(project, &Default::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].session_id.as_deref(), Some("s1"));
+        assert_eq!(
+            entries[0].matched_skills,
+            vec!["rust-developer".to_string()]
+        );
+        assert_eq!(entries[0].prompt_length, 31);
+    }
+}
@@ -0,0 +1,137 @@
+// Session digest hook - recaps the session's edits and checks when Claude stops (Stop)
+//
+// Stop hooks have no additionalContext, so the recap reaches Claude as the
+// reason of a blocked stop: Claude carries on just long enough to fold it
+// into its final summary, and the stop after that (stop_hook_active) is let
+// through. A session is only recapped again once it has new edits. The
+// digest itself is built in catalyst_cli::session_digest.
+use catalyst_cli::hook_stats::{HookOutcome, HookTimer};
+use catalyst_cli::modification_store;
+use catalyst_cli::session_digest;
+use catalyst_core::hooks::{HookDecision, HookInput, HookResponse, EXIT_SUCCESS};
+use catalyst_core::settings::HookEvent;
+use clap::Parser;
+use std::io::{self, Read};
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+enum SessionDigestError {
+    #[error("[SD001] Failed to read input from stdin")]
+    StdinRead(#[from] io::Error),
+
+    #[error("[SD002] Invalid JSON input from hook: {0}\nCheck that the hook is passing valid JSON format")]
+    InvalidHookInput(#[source] serde_json::Error),
+
+    #[error("[SD003] Failed to read the session's tracker data: {0}")]
+    TrackerRead(#[source] catalyst_cli::CatalystError),
+}
+
+/// The recap for this stop, if the session has edits not yet recapped
+fn evaluate(
+    input: &HookInput,
+    state_dir: &Path,
+    project_dir: &Path,
+) -> Result<Option<HookResponse>, SessionDigestError> {
+    if input.event() != Some(HookEvent::Stop) || input.stop_hook_active {
+        return Ok(None);
+    }
+    let Some(session_id) = input.session_id.as_deref() else {
+        return Ok(None);
+    };
+
+    let Some(digest) = session_digest::pending(state_dir, project_dir, session_id)
+        .map_err(SessionDigestError::TrackerRead)?
+    else {
+        return Ok(None);
+    };
+    // Without the marker the next stop would recap the same edits again,
+    // which is noise but not harm
+    if let Err(e) = session_digest::mark_digested(state_dir, session_id, digest.edits) {
+        eprintln!("session-digest: could not record the recap: {}", e);
+    }
+
+    Ok(Some(
+        HookResponse::builder(HookEvent::Stop)
+            .decision(HookDecision::Block)
+            .reason(digest.render())
+            .build(),
+    ))
+}
+
+fn run() -> Result<Option<HookResponse>, SessionDigestError> {
+    let mut buffer = String::new();
+    io::stdin().read_to_string(&mut buffer)?;
+    let input: HookInput =
+        serde_json::from_str(&buffer).map_err(SessionDigestError::InvalidHookInput)?;
+
+    let state_dir = modification_store::state_dir().map_err(SessionDigestError::TrackerRead)?;
+    evaluate(&input, &state_dir, &input.project_dir())
+}
+
+/// Recaps the session when Claude stops (Stop hook)
+#[derive(Parser)]
+#[command(name = "session-digest")]
+#[command(about = "Recaps the session's edits and checks for the Stop payload read from stdin", long_about = None)]
+#[command(version)]
+struct Args {}
+
+fn main() {
+    Args::parse();
+    let timer = HookTimer::start("session-digest");
+    let code = match run() {
+        Ok(response) => {
+            timer.finish(HookOutcome::of(response.as_ref()), None);
+            response.map_or(EXIT_SUCCESS, |response| response.emit())
+        }
+        Err(e) => {
+            // A recap is a nicety; never keep Claude from stopping over one
+            timer.finish(HookOutcome::Error, Some(e.to_string()));
+            eprintln!("session-digest: {}", e);
+            EXIT_SUCCESS
+        }
+    };
+    std::process::exit(code);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use catalyst_cli::types::CONFIG_FILE;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn hook_input(json: serde_json::Value) -> HookInput {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_stops_without_new_edits_are_let_through() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = temp_dir.path().join("state");
+        let project = temp_dir.path().join("project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(
+            project.join(CONFIG_FILE),
+            "[tracker]\nbackend = \"jsonl\"\n",
+        )
+        .unwrap();
+
+        // No edits tracked: nothing to recap
+        let stop = hook_input(serde_json::json!({"hook_event_name": "Stop", "session_id": "s1"}));
+        assert!(evaluate(&stop, &state, &project).unwrap().is_none());
+
+        // Other events, and the stop after a recap, are let through
+        let prompt = hook_input(serde_json::json!({
+            "hook_event_name": "UserPromptSubmit", "session_id": "s1", "prompt": "hi"
+        }));
+        assert!(evaluate(&prompt, &state, &project).unwrap().is_none());
+        let continuing = hook_input(serde_json::json!({
+            "hook_event_name": "Stop", "session_id": "s1", "stop_hook_active": true
+        }));
+        assert!(evaluate(&continuing, &state, &project).unwrap().is_none());
+
+        let no_session = hook_input(serde_json::json!({"hook_event_name": "Stop"}));
+        assert!(evaluate(&no_session, &state, &project).unwrap().is_none());
+    }
+}
@@ -1,3 +1,17 @@
+use catalyst_cli::activation::{MatchMode, PromptText};
+use catalyst_cli::debounce;
+use catalyst_cli::file_context::{self, PathTriggers, MAX_RECENT_FILES};
+use catalyst_cli::hook_stats::{HookOutcome, HookTimer};
+use catalyst_cli::input_limits::{cap_prompt, InputLimits};
+use catalyst_cli::rules_cache::{self, Prefilter, RulesCache};
+use catalyst_cli::types::{
+    ACTIVATION_INTENT_WEIGHT as INTENT_WEIGHT, ACTIVATION_KEYWORD_WEIGHT as KEYWORD_WEIGHT,
+    ACTIVATION_PATH_WEIGHT as PATH_WEIGHT, DEFAULT_ACTIVATION_MIN_SCORE,
+};
+use catalyst_core::hooks::{HookInput, HookResponse, PROJECT_DIR_ENV};
+use catalyst_core::jsonc;
+use catalyst_core::settings::HookEvent;
+use clap::Parser;
 use colored::*;
 use regex::Regex;
 use serde::Deserialize;
@@ -5,9 +19,52 @@ use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::io::{self, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use thiserror::Error;
-use tracing::{debug, error};
+use tracing::{debug, error, info};
+
+/// Environment variable selecting the output mode when `--output` isn't given
+const OUTPUT_ENV: &str = "CATALYST_ACTIVATION_OUTPUT";
+
+/// Suggests skills for a prompt (UserPromptSubmit hook)
+#[derive(Parser)]
+#[command(name = "skill-activation-prompt")]
+#[command(about = "Suggests skills for the prompt read from stdin", long_about = None)]
+#[command(version)]
+struct Args {
+    /// Output mode: text (decorated), plain (ASCII only), or json (hook
+    /// additionalContext). Defaults to $CATALYST_ACTIVATION_OUTPUT, then text
+    #[arg(long, value_name = "MODE", value_parser = ["text", "plain", "json"])]
+    output: Option<String>,
+}
+
+/// How matched skills are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputMode {
+    /// Boxes, emoji, and colour for reading in a terminal
+    Text,
+    /// The same content in plain ASCII, for terminals that garble Unicode
+    Plain,
+    /// Hook JSON with the plain text as `additionalContext`
+    Json,
+}
+
+impl OutputMode {
+    /// Mode from `--output`, then the environment, then the default
+    fn resolve(arg: Option<&str>) -> Self {
+        let env_value = env::var(OUTPUT_ENV).ok();
+        match arg.or(env_value.as_deref()).map(str::trim) {
+            None | Some("") | Some("text") => OutputMode::Text,
+            Some("plain") => OutputMode::Plain,
+            Some("json") => OutputMode::Json,
+            Some(other) => {
+                tracing::warn!(output = %other, "Unknown {}, using text", OUTPUT_ENV);
+                OutputMode::Text
+            }
+        }
+    }
+}
 
 #[derive(Error, Debug)]
 enum SkillActivationError {
@@ -35,37 +92,6 @@ enum SkillActivationError {
     },
 }
 
-/// Input data from Claude Code's UserPromptSubmit hook
-///
-/// Note: Fields prefixed with underscore are part of the hook's JSON schema
-/// but not currently used by this binary. They're kept in the struct to:
-/// 1. Maintain complete schema compatibility with Claude Code
-/// 2. Enable future features (e.g., session-aware caching, permission checks)
-/// 3. Ensure deserialization succeeds even if Claude Code adds more fields
-///
-/// If these fields are needed in the future, remove the underscore prefix.
-#[derive(Debug, Deserialize)]
-struct HookInput {
-    /// Session ID for the current Claude Code session (reserved for future use)
-    #[serde(rename = "session_id")]
-    _session_id: String,
-
-    /// Path to the conversation transcript (reserved for future use)
-    #[serde(rename = "transcript_path")]
-    _transcript_path: String,
-
-    /// Current working directory when the hook was triggered
-    #[serde(rename = "cwd")]
-    cwd: String,
-
-    /// Permission mode from Claude Code settings (reserved for future use)
-    #[serde(rename = "permission_mode")]
-    _permission_mode: String,
-
-    /// The user's prompt text to analyze for skill activation
-    prompt: String,
-}
-
 #[derive(Debug, Deserialize)]
 struct PromptTriggers {
     #[serde(default)]
@@ -74,6 +100,14 @@ struct PromptTriggers {
     intent_patterns: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct FileTriggers {
+    #[serde(default, rename = "pathPatterns")]
+    path_patterns: Vec<String>,
+    #[serde(default, rename = "pathExclusions")]
+    path_exclusions: Vec<String>,
+}
+
 // Compiled version of PromptTriggers with pre-compiled regexes and lowercased keywords
 struct CompiledTriggers {
     keywords_lower: Vec<String>, // Pre-lowercased for efficient substring matching
@@ -82,9 +116,38 @@ struct CompiledTriggers {
 
 impl CompiledTriggers {
     fn from_triggers(triggers: &PromptTriggers) -> Self {
+        Self::compile(triggers, |_| true)
+    }
+
+    /// Compile only the intent patterns that could match `prompt_lower`
+    ///
+    /// Patterns the prefilters rule out can't add to the score, so skipping
+    /// them doesn't change the result. Patterns missing from `prefilters`
+    /// are always compiled.
+    fn for_prompt(
+        triggers: &PromptTriggers,
+        prefilters: &HashMap<String, Prefilter>,
+        prompt_lower: &str,
+    ) -> Self {
+        Self::compile(triggers, |pattern| match prefilters.get(pattern) {
+            Some(Prefilter::Invalid { error }) => {
+                tracing::warn!(
+                    pattern = %pattern,
+                    error = %error,
+                    "Failed to compile intent pattern regex, skipping"
+                );
+                false
+            }
+            Some(prefilter) => prefilter.may_match(prompt_lower),
+            None => true,
+        })
+    }
+
+    fn compile(triggers: &PromptTriggers, needed: impl Fn(&str) -> bool) -> Self {
         let intent_regexes = triggers
             .intent_patterns
             .iter()
+            .filter(|pattern| needed(pattern))
             .filter_map(|pattern| match Regex::new(pattern) {
                 Ok(regex) => Some(regex),
                 Err(e) => {
@@ -118,6 +181,32 @@ impl CompiledTriggers {
     }
 }
 
+impl CompiledTriggers {
+    /// Score a prompt against these triggers
+    ///
+    /// Keywords count once each however often they appear (and however many
+    /// times they are listed), compared according to `mode`; every matching
+    /// intent pattern counts.
+    fn score(&self, prompt: &str, text: &PromptText, mode: MatchMode) -> u32 {
+        let mut keyword_hits: Vec<&str> = self
+            .keywords_lower
+            .iter()
+            .filter(|kw_lower| text.contains_keyword(kw_lower, mode))
+            .map(String::as_str)
+            .collect();
+        keyword_hits.sort_unstable();
+        keyword_hits.dedup();
+
+        let intent_hits = self
+            .intent_regexes
+            .iter()
+            .filter(|regex| regex.is_match(prompt))
+            .count();
+
+        keyword_hits.len() as u32 * KEYWORD_WEIGHT + intent_hits as u32 * INTENT_WEIGHT
+    }
+}
+
 /// Priority levels for skill activation (PR feedback - extracted magic strings)
 ///
 /// These priority levels determine the order and prominence of skill suggestions
@@ -148,8 +237,7 @@ impl Priority {
         }
     }
 
-    /// Convert to string for display (reserved for future use)
-    #[allow(dead_code)]
+    /// Convert to string for display
     fn as_str(&self) -> &'static str {
         match self {
             Priority::Critical => "critical",
@@ -170,6 +258,24 @@ struct SkillRule {
     priority: Priority,
     #[serde(rename = "promptTriggers")]
     prompt_triggers: Option<PromptTriggers>,
+    /// Files that suggest the skill when the session is working on them
+    #[serde(rename = "fileTriggers")]
+    file_triggers: Option<FileTriggers>,
+    /// Score the prompt must reach before the skill is suggested
+    #[serde(default = "default_min_score", rename = "minScore")]
+    min_score: u32,
+    /// How keywords are compared with the prompt
+    #[serde(
+        default,
+        rename = "matchMode",
+        deserialize_with = "deserialize_match_mode"
+    )]
+    match_mode: MatchMode,
+}
+
+fn default_min_score() -> u32 {
+    // Any single match activates the skill, as before scoring existed
+    DEFAULT_ACTIVATION_MIN_SCORE
 }
 
 /// Custom deserializer for Priority enum from string
@@ -181,19 +287,56 @@ where
     Ok(Priority::from_str(&s))
 }
 
+/// Match mode from string, falling back to exact matching like unknown priorities
+fn deserialize_match_mode<'de, D>(deserializer: D) -> Result<MatchMode, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Ok(s.parse().unwrap_or_else(|_| {
+        tracing::warn!(match_mode = %s, "Unknown matchMode, defaulting to exact");
+        MatchMode::Exact
+    }))
+}
+
 struct CompiledSkillRule {
     priority: Priority,
+    min_score: u32,
+    match_mode: MatchMode,
     compiled_triggers: Option<CompiledTriggers>,
+    path_triggers: Option<PathTriggers>,
 }
 
 impl CompiledSkillRule {
     fn from_rule(rule: &SkillRule) -> Self {
+        Self::compile(rule, CompiledTriggers::from_triggers)
+    }
+
+    /// Compile a rule for one prompt, using cached prefilters to skip
+    /// intent patterns that can't match it
+    fn for_prompt(
+        rule: &SkillRule,
+        prefilters: &HashMap<String, Prefilter>,
+        prompt_lower: &str,
+    ) -> Self {
+        Self::compile(rule, |triggers| {
+            CompiledTriggers::for_prompt(triggers, prefilters, prompt_lower)
+        })
+    }
+
+    fn compile(
+        rule: &SkillRule,
+        compile_triggers: impl Fn(&PromptTriggers) -> CompiledTriggers,
+    ) -> Self {
         Self {
             priority: rule.priority,
-            compiled_triggers: rule
-                .prompt_triggers
-                .as_ref()
-                .map(CompiledTriggers::from_triggers),
+            // A zero threshold would suggest the skill for every prompt
+            min_score: rule.min_score.max(1),
+            match_mode: rule.match_mode,
+            compiled_triggers: rule.prompt_triggers.as_ref().map(compile_triggers),
+            path_triggers: rule.file_triggers.as_ref().and_then(|triggers| {
+                PathTriggers::new(&triggers.path_patterns, &triggers.path_exclusions)
+            }),
         }
     }
 }
@@ -233,19 +376,147 @@ fn map_file_read_error(path: PathBuf, error: io::Error) -> SkillActivationError
 #[derive(Debug)]
 struct MatchedSkill {
     name: String,
-    _match_type: String,
+    score: u32,
     priority: Priority,
 }
 
-fn run() -> Result<(), SkillActivationError> {
-    // Initialize tracing
+/// Score every rule against the prompt and keep the skills over threshold
+///
+/// `files` are the project-relative files the session is working on; a rule
+/// whose pathPatterns match any of them gains `PATH_WEIGHT` on top of its
+/// prompt score. The result is ordered by priority, then by descending score, then by
+/// name so the output is stable.
+fn match_skills(
+    compiled_rules: &HashMap<String, CompiledSkillRule>,
+    prompt: &str,
+    files: &[String],
+) -> Vec<MatchedSkill> {
+    // Phase 2.5: Lowercase (and stem) the prompt once for all rules
+    let text = PromptText::new(prompt);
+
+    let mut matched_skills: Vec<MatchedSkill> = compiled_rules
+        .iter()
+        .filter_map(|(skill_name, compiled_rule)| {
+            if compiled_rule.compiled_triggers.is_none() && compiled_rule.path_triggers.is_none() {
+                return None;
+            }
+            let mut score = compiled_rule
+                .compiled_triggers
+                .as_ref()
+                .map_or(0, |triggers| {
+                    triggers.score(prompt, &text, compiled_rule.match_mode)
+                });
+            let file_match = compiled_rule
+                .path_triggers
+                .as_ref()
+                .and_then(|triggers| triggers.first_match(files));
+            if let Some(file) = file_match {
+                debug!(skill = %skill_name, file = %file, "Recent file matches pathPatterns");
+                score += PATH_WEIGHT;
+            }
+            if score < compiled_rule.min_score {
+                if score > 0 {
+                    debug!(skill = %skill_name, score, min_score = compiled_rule.min_score, "Skill below threshold");
+                }
+                return None;
+            }
+            debug!(skill = %skill_name, score, "Skill matched");
+            Some(MatchedSkill {
+                name: skill_name.clone(),
+                score,
+                priority: compiled_rule.priority,
+            })
+        })
+        .collect();
+
+    matched_skills.sort_by(|a, b| {
+        a.priority
+            .cmp(&b.priority)
+            .then(b.score.cmp(&a.score))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    matched_skills
+}
+
+/// Intent pattern prefilters for the rules, from the cache or computed and
+/// cached now
+///
+/// Returns None when the cache is disabled or no home directory is known,
+/// in which case every pattern is compiled.
+fn load_prefilters(rules_path: &Path, rules: &SkillRules) -> Option<HashMap<String, Prefilter>> {
+    if !rules_cache::enabled() {
+        return None;
+    }
+    let cache_dir = rules_cache::cache_dir()?;
+    if let Some(cache) = rules_cache::load(&cache_dir, rules_path) {
+        debug!("Using cached skill rule prefilters");
+        return Some(cache.prefilters);
+    }
+
+    let patterns = rules
+        .skills
+        .values()
+        .filter_map(|rule| rule.prompt_triggers.as_ref())
+        .flat_map(|triggers| triggers.intent_patterns.iter().map(String::as_str));
+    let cache = RulesCache::build(rules_path, patterns)?;
+    if let Err(e) = rules_cache::store(&cache_dir, rules_path, &cache) {
+        // Still use the prefilters; the next prompt will try to cache again
+        debug!(error = %e, "Could not write skill rules cache");
+    }
+    Some(cache.prefilters)
+}
+
+/// Project-relative files the session recently worked on
+///
+/// Paths are made relative to the project that owns skill-rules.json, since
+/// that's what its pathPatterns are written against.
+fn recent_files(data: &HookInput, rules_path: &Path) -> Vec<String> {
+    // <project>/.claude/skills/skill-rules.json
+    let project_dir = rules_path
+        .ancestors()
+        .nth(3)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(data.cwd.as_deref().unwrap_or(".")));
+
+    #[allow(unused_mut)]
+    let mut files: Vec<PathBuf> = Vec::new();
+    #[cfg(feature = "sqlite")]
+    if let (Ok(state_dir), Some(session_id)) = (
+        catalyst_cli::sessions::state_dir(),
+        data.session_id.as_deref(),
+    ) {
+        files = file_context::tracked_files(&state_dir, session_id, MAX_RECENT_FILES);
+    }
+    let transcript = data.transcript_path.as_deref().map(Path::new);
+    for file in transcript.map_or_else(Vec::new, |path| {
+        file_context::transcript_files(path, MAX_RECENT_FILES)
+    }) {
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+
+    let files = file_context::project_relative(&files, &project_dir);
+    debug!(count = files.len(), "Recent files for path matching");
+    files
+}
+
+/// Returns how many skills matched
+fn run() -> Result<usize, SkillActivationError> {
+    let args = Args::parse();
+
+    // Initialize tracing on stderr; stdout is the hook's output
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
         )
+        .with_writer(io::stderr)
         .init();
 
+    let output_mode = OutputMode::resolve(args.output.as_deref());
+
     // Read input from stdin
     let mut input = String::new();
     io::stdin().read_to_string(&mut input).map_err(|e| {
@@ -268,10 +539,6 @@ fn run() -> Result<(), SkillActivationError> {
         SkillActivationError::InvalidHookInput(e)
     })?;
 
-    // Phase 2.5: Lowercase prompt once for efficient substring matching
-    let prompt = &data.prompt;
-    let prompt_lower = prompt.to_lowercase();
-
     // Load skill rules with multi-directory support
     //
     // Path Resolution Priority (PR feedback - detailed explanation):
@@ -295,18 +562,14 @@ fn run() -> Result<(), SkillActivationError> {
     // - Not the catalyst/ directory's rules, even if CLAUDE_PROJECT_DIR=catalyst
     // - This enables polyglot workflows (Rust + TypeScript) with appropriate skills per dir
     let rules_path = {
-        let cwd_path = PathBuf::from(&data.cwd)
-            .join(".claude")
-            .join("skills")
-            .join("skill-rules.json");
+        let cwd = PathBuf::from(data.cwd.as_deref().unwrap_or("."));
+        let cwd_path = cwd.join(".claude").join("skills").join("skill-rules.json");
 
         if cwd_path.exists() {
             debug!("Using skill-rules.json from cwd: {}", cwd_path.display());
             cwd_path
         } else {
-            let project_dir = env::var("CLAUDE_PROJECT_DIR")
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| PathBuf::from(&data.cwd));
+            let project_dir = env::var(PROJECT_DIR_ENV).map(PathBuf::from).unwrap_or(cwd);
 
             let fallback_path = project_dir
                 .join(".claude")
@@ -323,7 +586,7 @@ fn run() -> Result<(), SkillActivationError> {
 
     let rules_content =
         fs::read_to_string(&rules_path).map_err(|e| map_file_read_error(rules_path.clone(), e))?;
-    let rules: SkillRules = serde_json::from_str(&rules_content).map_err(|source| {
+    let rules: SkillRules = jsonc::from_str(&rules_content).map_err(|source| {
         error!(
             error_code = "SA005",
             error_kind = "InvalidRulesJson",
@@ -339,124 +602,216 @@ fn run() -> Result<(), SkillActivationError> {
 
     debug!("Loaded {} skills from rules", rules.skills.len());
 
-    // Pre-compile all regex patterns (CRITICAL PERFORMANCE IMPROVEMENT)
+    // Compile only the intent patterns that could match this prompt, using
+    // the cached prefilters (CRITICAL PERFORMANCE IMPROVEMENT)
+    let prefilters = load_prefilters(&rules_path, &rules);
+    let prompt = cap_prompt(
+        data.prompt.as_deref().unwrap_or_default(),
+        InputLimits::from_env().max_prompt_bytes,
+    );
+    if prompt.was_capped() {
+        info!(
+            truncated_bytes = prompt.truncated_bytes,
+            elided_tokens = prompt.elided_tokens,
+            "Capped prompt before matching"
+        );
+    }
+    let prompt_lower = prompt.text.to_lowercase();
     let compiled_rules: HashMap<String, CompiledSkillRule> = rules
         .skills
         .iter()
-        .map(|(name, rule)| (name.clone(), CompiledSkillRule::from_rule(rule)))
+        .map(|(name, rule)| {
+            let compiled = match &prefilters {
+                Some(prefilters) => CompiledSkillRule::for_prompt(rule, prefilters, &prompt_lower),
+                None => CompiledSkillRule::from_rule(rule),
+            };
+            (name.clone(), compiled)
+        })
         .collect();
 
-    let mut matched_skills = Vec::new();
-
-    // Check each skill for matches using pre-compiled regexes
-    for (skill_name, compiled_rule) in &compiled_rules {
-        if let Some(triggers) = &compiled_rule.compiled_triggers {
-            // Case-insensitive keyword matching using pre-lowercased keywords
-            let keyword_match = triggers
-                .keywords_lower
-                .iter()
-                .any(|kw_lower| prompt_lower.contains(kw_lower));
-
-            if keyword_match {
-                debug!(skill = %skill_name, match_type = "keyword", "Skill matched");
-                matched_skills.push(MatchedSkill {
-                    name: skill_name.clone(),
-                    _match_type: "keyword".to_string(),
-                    priority: compiled_rule.priority,
-                });
-                continue;
-            }
+    // Only look for recent files when some rule can use them
+    let files = if compiled_rules.values().any(|r| r.path_triggers.is_some()) {
+        recent_files(&data, &rules_path)
+    } else {
+        Vec::new()
+    };
 
-            // Intent pattern matching with pre-compiled regexes
-            // Note: Regex matching is already case-insensitive if patterns use (?i)
-            let intent_match = triggers
-                .intent_regexes
-                .iter()
-                .any(|regex| regex.is_match(prompt));
-
-            if intent_match {
-                debug!(skill = %skill_name, match_type = "intent", "Skill matched");
-                matched_skills.push(MatchedSkill {
-                    name: skill_name.clone(),
-                    _match_type: "intent".to_string(),
-                    priority: compiled_rule.priority,
-                });
-            }
+    let matched_skills = match_skills(&compiled_rules, &prompt.text, &files);
+
+    let repeat = data
+        .session_id
+        .as_deref()
+        .is_some_and(|session_id| is_repeat(session_id, &matched_skills));
+    let output = if repeat {
+        render_reminder(&matched_skills, output_mode)
+    } else {
+        render(&matched_skills, output_mode)
+    };
+    if let Some(output) = output {
+        print!("{}", output);
+    }
+
+    Ok(matched_skills.len())
+}
+
+/// Whether the session was just shown these same skills
+///
+/// Any problem with the debounce record shows the full suggestion.
+fn is_repeat(session_id: &str, matched_skills: &[MatchedSkill]) -> bool {
+    if matched_skills.is_empty() {
+        return false;
+    }
+    let (Some(window), Some(dir)) = (debounce::window(), debounce::state_dir()) else {
+        return false;
+    };
+    let names: Vec<String> = matched_skills.iter().map(|s| s.name.clone()).collect();
+    match debounce::check_and_record(&dir, session_id, &names, SystemTime::now(), window) {
+        Ok(repeat) => repeat,
+        Err(e) => {
+            debug!(error = %e, "Could not record suggestion for debounce");
+            false
         }
     }
+}
 
-    // Generate output if matches found
-    if !matched_skills.is_empty() {
-        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-        println!("🎯 SKILL ACTIVATION CHECK");
-        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+/// One-line reminder shown instead of a repeated suggestion
+fn render_reminder(matched_skills: &[MatchedSkill], mode: OutputMode) -> Option<String> {
+    if matched_skills.is_empty() {
+        return None;
+    }
+    let names: Vec<&str> = matched_skills.iter().map(|s| s.name.as_str()).collect();
+    let line = format!(
+        "Skills still apply: {} (use Skill tool BEFORE responding)",
+        names.join(", ")
+    );
+    Some(match mode {
+        OutputMode::Text => format!("🎯 {}\n", line.bright_yellow()),
+        OutputMode::Plain => format!("{}\n", line),
+        OutputMode::Json => hook_json(&format!("{}\n", line)),
+    })
+}
 
-        // Group by priority (using enum for type safety - PR feedback)
-        let critical: Vec<_> = matched_skills
-            .iter()
-            .filter(|s| s.priority == Priority::Critical)
-            .collect();
-        let high: Vec<_> = matched_skills
-            .iter()
-            .filter(|s| s.priority == Priority::High)
-            .collect();
-        let medium: Vec<_> = matched_skills
-            .iter()
-            .filter(|s| s.priority == Priority::Medium)
-            .collect();
-        let low: Vec<_> = matched_skills
+/// UserPromptSubmit hook output adding `context` to the prompt
+fn hook_json(context: &str) -> String {
+    let output = HookResponse::builder(HookEvent::UserPromptSubmit)
+        .additional_context(context)
+        .build();
+    // Serialization should never fail for our simple types - if it does, it's a bug
+    let json = serde_json::to_string(&output).expect("Failed to serialize hook response");
+    format!("{}\n", json)
+}
+
+/// Headings for each priority group, decorated and plain
+fn priority_heading(priority: Priority) -> (&'static str, &'static str) {
+    match priority {
+        Priority::Critical => (
+            "⚠️ CRITICAL SKILLS (REQUIRED):",
+            "CRITICAL SKILLS (REQUIRED):",
+        ),
+        Priority::High => ("📚 RECOMMENDED SKILLS:", "RECOMMENDED SKILLS:"),
+        Priority::Medium => ("💡 SUGGESTED SKILLS:", "SUGGESTED SKILLS:"),
+        Priority::Low => ("📌 OPTIONAL SKILLS:", "OPTIONAL SKILLS:"),
+    }
+}
+
+/// Hook output for the matched skills, or None when nothing matched
+fn render(matched_skills: &[MatchedSkill], mode: OutputMode) -> Option<String> {
+    if matched_skills.is_empty() {
+        return None;
+    }
+    Some(match mode {
+        OutputMode::Text => render_text(matched_skills),
+        OutputMode::Plain => render_plain(matched_skills),
+        OutputMode::Json => hook_json(&render_plain(matched_skills)),
+    })
+}
+
+/// Skills grouped by priority, each group highest score first
+fn priority_groups(matched_skills: &[MatchedSkill]) -> Vec<(Priority, Vec<&MatchedSkill>)> {
+    [
+        Priority::Critical,
+        Priority::High,
+        Priority::Medium,
+        Priority::Low,
+    ]
+    .into_iter()
+    .map(|priority| {
+        let skills: Vec<_> = matched_skills
             .iter()
-            .filter(|s| s.priority == Priority::Low)
+            .filter(|s| s.priority == priority)
             .collect();
+        (priority, skills)
+    })
+    .filter(|(_, skills)| !skills.is_empty())
+    .collect()
+}
 
-        if !critical.is_empty() {
-            println!("{}", "⚠️ CRITICAL SKILLS (REQUIRED):".red().bold());
-            for skill in critical {
-                println!("  → {}", skill.name.yellow());
-            }
-            println!();
-        }
-
-        if !high.is_empty() {
-            println!("{}", "📚 RECOMMENDED SKILLS:".blue().bold());
-            for skill in high {
-                println!("  → {}", skill.name.cyan());
-            }
-            println!();
+/// Decorated output for a terminal
+fn render_text(matched_skills: &[MatchedSkill]) -> String {
+    const RULE: &str = "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━";
+    let mut out = format!("{}\n🎯 SKILL ACTIVATION CHECK\n{}\n\n", RULE, RULE);
+
+    for (priority, skills) in priority_groups(matched_skills) {
+        let (heading, _) = priority_heading(priority);
+        let heading = match priority {
+            Priority::Critical => heading.red().bold(),
+            Priority::High => heading.blue().bold(),
+            Priority::Medium => heading.green().bold(),
+            Priority::Low => heading.white().bold(),
+        };
+        out.push_str(&format!("{}\n", heading));
+        for skill in skills {
+            let name = match priority {
+                Priority::Critical => skill.name.yellow(),
+                Priority::High => skill.name.cyan(),
+                Priority::Medium => skill.name.bright_green(),
+                Priority::Low => skill.name.white(),
+            };
+            out.push_str(&format!("  → {}\n", name));
         }
+        out.push('\n');
+    }
 
-        if !medium.is_empty() {
-            println!("{}", "💡 SUGGESTED SKILLS:".green().bold());
-            for skill in medium {
-                println!("  → {}", skill.name.bright_green());
-            }
-            println!();
-        }
+    out.push_str(&format!(
+        "{}\n{}\n",
+        "ACTION: Use Skill tool BEFORE responding"
+            .bright_yellow()
+            .bold(),
+        RULE
+    ));
+    out
+}
 
-        if !low.is_empty() {
-            println!("{}", "📌 OPTIONAL SKILLS:".white().bold());
-            for skill in low {
-                println!("  → {}", skill.name.white());
-            }
-            println!();
+/// ASCII-only output, also used as the JSON `additionalContext`
+fn render_plain(matched_skills: &[MatchedSkill]) -> String {
+    let mut out = String::from("SKILL ACTIVATION CHECK\n\n");
+    for (priority, skills) in priority_groups(matched_skills) {
+        let (_, heading) = priority_heading(priority);
+        out.push_str(heading);
+        out.push('\n');
+        for skill in skills {
+            out.push_str(&format!(
+                "  - {} (priority: {}, score: {})\n",
+                skill.name,
+                priority.as_str(),
+                skill.score
+            ));
         }
-
-        println!(
-            "{}",
-            "ACTION: Use Skill tool BEFORE responding"
-                .bright_yellow()
-                .bold()
-        );
-        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        out.push('\n');
     }
-
-    Ok(())
+    out.push_str("ACTION: Use Skill tool BEFORE responding\n");
+    out
 }
 
 fn main() {
-    if let Err(e) = run() {
-        eprintln!("Error: {}", e);
-        std::process::exit(1);
+    let timer = HookTimer::start("skill-activation-prompt");
+    match run() {
+        Ok(matched) => timer.finish(HookOutcome::Ok, Some(format!("{} skills matched", matched))),
+        Err(e) => {
+            timer.finish(HookOutcome::Error, Some(e.to_string()));
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
     }
 }
 
@@ -647,6 +1002,139 @@ mod tests {
         assert!(compiled.compiled_triggers.is_none());
     }
 
+    #[test]
+    fn test_score_weights_keywords_and_intents() {
+        let compiled = CompiledTriggers::from_triggers(&PromptTriggers {
+            keywords: vec!["api".to_string(), "API".to_string(), "route".to_string()],
+            intent_patterns: vec![r"(?i)add.*route".to_string()],
+        });
+
+        let score =
+            |prompt: &str| compiled.score(prompt, &PromptText::new(prompt), MatchMode::Exact);
+        // Duplicate and repeated keywords count once
+        assert_eq!(score("api api API"), KEYWORD_WEIGHT);
+        assert_eq!(
+            score("add an api route"),
+            2 * KEYWORD_WEIGHT + INTENT_WEIGHT
+        );
+        assert_eq!(score("delete a component"), 0);
+    }
+
+    #[test]
+    fn test_match_skills_threshold_and_order() {
+        let json = r#"{
+            "version": "1.0",
+            "skills": {
+                "picky": {
+                    "type": "domain", "enforcement": "suggest", "priority": "high",
+                    "minScore": 3,
+                    "promptTriggers": {"keywords": ["test"]}
+                },
+                "one-hit": {
+                    "type": "domain", "enforcement": "suggest", "priority": "high",
+                    "promptTriggers": {"keywords": ["test"]}
+                },
+                "strong": {
+                    "type": "domain", "enforcement": "suggest", "priority": "high",
+                    "promptTriggers": {"keywords": ["test"], "intentPatterns": ["(?i)write.*test"]}
+                },
+                "urgent": {
+                    "type": "domain", "enforcement": "suggest", "priority": "critical",
+                    "promptTriggers": {"keywords": ["test"]}
+                }
+            }
+        }"#;
+        let rules: SkillRules = serde_json::from_str(json).unwrap();
+        let compiled: HashMap<String, CompiledSkillRule> = rules
+            .skills
+            .iter()
+            .map(|(name, rule)| (name.clone(), CompiledSkillRule::from_rule(rule)))
+            .collect();
+
+        let matched = match_skills(&compiled, "write a test", &[]);
+        let names: Vec<&str> = matched.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["urgent", "strong", "one-hit"]);
+        assert_eq!(matched[1].score, KEYWORD_WEIGHT + INTENT_WEIGHT);
+
+        assert!(match_skills(&compiled, "refactor the parser", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_match_mode_from_rules() {
+        let json = r#"{
+            "version": "1.0",
+            "skills": {
+                "stemmed": {
+                    "type": "domain", "enforcement": "suggest", "priority": "high",
+                    "matchMode": "stemmed",
+                    "promptTriggers": {"keywords": ["test route"]}
+                },
+                "exact": {
+                    "type": "domain", "enforcement": "suggest", "priority": "high",
+                    "matchMode": "no-such-mode",
+                    "promptTriggers": {"keywords": ["test route"]}
+                }
+            }
+        }"#;
+        let rules: SkillRules = serde_json::from_str(json).unwrap();
+        assert_eq!(rules.skills["exact"].match_mode, MatchMode::Exact);
+        let compiled: HashMap<String, CompiledSkillRule> = rules
+            .skills
+            .iter()
+            .map(|(name, rule)| (name.clone(), CompiledSkillRule::from_rule(rule)))
+            .collect();
+
+        let matched = match_skills(&compiled, "Testing routes for the users API", &[]);
+        let names: Vec<&str> = matched.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["stemmed"]);
+    }
+
+    #[test]
+    fn test_match_skills_from_recent_files() {
+        let json = r#"{
+            "version": "1.0",
+            "skills": {
+                "backend": {
+                    "type": "domain", "enforcement": "suggest", "priority": "high",
+                    "fileTriggers": {
+                        "pathPatterns": ["backend/**/*.ts"],
+                        "pathExclusions": ["**/*.test.ts"]
+                    }
+                },
+                "api-docs": {
+                    "type": "domain", "enforcement": "suggest", "priority": "high",
+                    "minScore": 2,
+                    "promptTriggers": {"keywords": ["api"]},
+                    "fileTriggers": {"pathPatterns": ["backend/**"]}
+                }
+            }
+        }"#;
+        let rules: SkillRules = serde_json::from_str(json).unwrap();
+        let compiled: HashMap<String, CompiledSkillRule> = rules
+            .skills
+            .iter()
+            .map(|(name, rule)| (name.clone(), CompiledSkillRule::from_rule(rule)))
+            .collect();
+        let files = vec!["backend/src/routes.ts".to_string()];
+
+        // A matching file alone triggers a rule without promptTriggers
+        let matched = match_skills(&compiled, "fix the bug", &files);
+        let names: Vec<&str> = matched.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["backend"]);
+
+        // ...and boosts a keyword match over a higher threshold
+        let matched = match_skills(&compiled, "document the api", &files);
+        let names: Vec<&str> = matched.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["api-docs", "backend"]);
+        assert_eq!(matched[0].score, KEYWORD_WEIGHT + PATH_WEIGHT);
+        assert!(match_skills(&compiled, "document the api", &[]).is_empty());
+
+        let tests = vec!["backend/src/routes.test.ts".to_string()];
+        assert!(match_skills(&compiled, "fix the bug", &tests)
+            .iter()
+            .all(|s| s.name != "backend"));
+    }
+
     #[test]
     fn test_priority_enum_parsing() {
         // Test case-insensitive priority parsing
@@ -674,7 +1162,7 @@ mod tests {
         assert!(result.is_ok());
 
         let input = result.unwrap();
-        assert_eq!(input.prompt, "create a backend service");
+        assert_eq!(input.prompt.as_deref(), Some("create a backend service"));
     }
 
     #[test]
@@ -806,4 +1294,112 @@ mod tests {
             _ => panic!("Expected RulesReadFailed error"),
         }
     }
+
+    #[test]
+    fn test_render_plain_and_json() {
+        let matched = vec![
+            MatchedSkill {
+                name: "security".to_string(),
+                score: 3,
+                priority: Priority::Critical,
+            },
+            MatchedSkill {
+                name: "docs".to_string(),
+                score: 1,
+                priority: Priority::Low,
+            },
+        ];
+
+        let plain = render(&matched, OutputMode::Plain).unwrap();
+        assert!(plain.is_ascii());
+        assert!(plain.contains(
+            "CRITICAL SKILLS (REQUIRED):\n  - security (priority: critical, score: 3)\n"
+        ));
+        assert!(plain.contains("OPTIONAL SKILLS:\n  - docs (priority: low, score: 1)\n"));
+        assert!(!plain.contains("RECOMMENDED"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&matched, OutputMode::Json).unwrap()).unwrap();
+        assert_eq!(
+            json["hookSpecificOutput"]["hookEventName"],
+            "UserPromptSubmit"
+        );
+        assert_eq!(json["hookSpecificOutput"]["additionalContext"], plain);
+
+        assert!(render(&[], OutputMode::Json).is_none());
+        assert_eq!(OutputMode::resolve(Some("json")), OutputMode::Json);
+    }
+
+    #[test]
+    fn test_for_prompt_skips_patterns_that_cannot_match() {
+        let triggers = PromptTriggers {
+            keywords: vec!["route".to_string()],
+            intent_patterns: vec![
+                r"(?i)add.*route".to_string(),
+                r"(?i)delete.*table".to_string(),
+                r"\w+ing routes".to_string(),
+                r"(unclosed".to_string(),
+            ],
+        };
+        let prefilters: HashMap<String, Prefilter> = triggers
+            .intent_patterns
+            .iter()
+            .map(|pattern| (pattern.clone(), Prefilter::for_pattern(pattern)))
+            .collect();
+
+        let prompt = "Add routes and start testing routes";
+        let text = PromptText::new(prompt);
+        let all = CompiledTriggers::from_triggers(&triggers);
+        let filtered = CompiledTriggers::for_prompt(&triggers, &prefilters, &prompt.to_lowercase());
+
+        // "delete.*table" can't match and the invalid pattern isn't compiled
+        assert_eq!(all.intent_regexes.len(), 3);
+        assert_eq!(filtered.intent_regexes.len(), 2);
+        assert_eq!(
+            filtered.score(prompt, &text, MatchMode::Exact),
+            all.score(prompt, &text, MatchMode::Exact)
+        );
+    }
+
+    #[test]
+    fn test_render_reminder_is_one_line() {
+        let matched = vec![MatchedSkill {
+            name: "backend".to_string(),
+            score: 2,
+            priority: Priority::High,
+        }];
+        assert_eq!(
+            render_reminder(&matched, OutputMode::Plain).unwrap(),
+            "Skills still apply: backend (use Skill tool BEFORE responding)\n"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render_reminder(&matched, OutputMode::Json).unwrap()).unwrap();
+        assert!(json["hookSpecificOutput"]["additionalContext"]
+            .as_str()
+            .unwrap()
+            .starts_with("Skills still apply: backend"));
+        assert!(render_reminder(&[], OutputMode::Text).is_none());
+    }
+
+    #[test]
+    fn test_rules_generated_by_init_deserialize() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".claude/skills")).unwrap();
+        let skills = vec!["rust-developer".to_string()];
+        catalyst_cli::init::generate_skill_rules(temp_dir.path(), &skills).unwrap();
+
+        let content =
+            fs::read_to_string(temp_dir.path().join(".claude/skills/skill-rules.json")).unwrap();
+        let rules: SkillRules = jsonc::from_str(&content).unwrap();
+        let rule = &rules.skills["rust-developer"];
+        assert_eq!(rule.priority, Priority::High);
+        let triggers = rule.prompt_triggers.as_ref().unwrap();
+        assert_eq!(triggers.keywords, vec!["rust-developer".to_string()]);
+        assert!(!rule
+            .file_triggers
+            .as_ref()
+            .unwrap()
+            .path_patterns
+            .is_empty());
+    }
 }
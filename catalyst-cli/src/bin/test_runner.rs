@@ -0,0 +1,244 @@
+// Test runner hook - runs the project's tests and reports failures (Stop, PostToolUse)
+//
+// On Stop (and SubagentStop) the failures go back as a "block" decision, so
+// Claude keeps working until the tests pass. With PostToolUse enabled in
+// .claude/test-runner.toml the tests also run after each edit. In "suggest"
+// mode failures are reported without blocking. The command and parsing live
+// in catalyst_cli::test_runner.
+use catalyst_cli::check_log::{self, CheckRecord, CheckStatus};
+use catalyst_cli::hook_stats::{HookOutcome, HookTimer};
+use catalyst_cli::modification_store;
+use catalyst_cli::test_runner::{run_tests, TestDecision, TestOutcome, TestRunnerConfig};
+use catalyst_core::hooks::{HookInput, HookResponse, EXIT_SUCCESS};
+use catalyst_core::settings::HookEvent;
+use clap::Parser;
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::Duration;
+use thiserror::Error;
+
+const EDIT_TOOLS: &[&str] = &["Edit", "Write", "MultiEdit", "NotebookEdit"];
+
+#[derive(Error, Debug)]
+enum TestRunnerError {
+    #[error("[TR001] Failed to read input from stdin")]
+    StdinRead(#[from] io::Error),
+
+    #[error("[TR002] Invalid JSON input from hook: {0}\nCheck that the hook is passing valid JSON format")]
+    InvalidHookInput(#[source] serde_json::Error),
+
+    #[error("[TR003] Invalid test runner config: {0}")]
+    InvalidConfig(#[source] catalyst_cli::CatalystError),
+}
+
+/// A note for the user that doesn't affect Claude
+fn notice(message: String) -> HookResponse {
+    HookResponse::builder(HookEvent::Stop)
+        .system_message(message)
+        .build()
+}
+
+/// Failing tests, blocking or not as configured
+///
+/// Stop hooks have no additionalContext, so a non-blocking report on Stop
+/// only reaches the user.
+fn failures(event: &str, decision: TestDecision, report: String) -> HookResponse {
+    let event = event.parse().unwrap_or(HookEvent::Stop);
+    let response = HookResponse::builder(event)
+        .decision(decision.into())
+        .additional_context(report.clone());
+    match decision {
+        TestDecision::Block => response.reason(report).system_message("Tests are failing"),
+        TestDecision::Suggest if event == HookEvent::PostToolUse => {
+            response.system_message("Tests are failing")
+        }
+        TestDecision::Suggest => response,
+    }
+    .build()
+}
+
+/// Whether this event should run the tests at all
+fn should_run(input: &HookInput, event: &str, config: &TestRunnerConfig) -> bool {
+    if !config.runs_on(event) {
+        return false;
+    }
+    match event {
+        // Claude is already fixing what an earlier run reported; running
+        // again here could keep it from ever stopping
+        "Stop" | "SubagentStop" => !input.stop_hook_active,
+        "PostToolUse" => input
+            .tool_name
+            .as_deref()
+            .is_some_and(|tool| EDIT_TOOLS.contains(&tool)),
+        _ => false,
+    }
+}
+
+fn evaluate(
+    input: &HookInput,
+    project_dir: &Path,
+) -> Result<Option<HookResponse>, TestRunnerError> {
+    let event = input.hook_event_name.as_deref().unwrap_or("Stop");
+    let config = TestRunnerConfig::load(project_dir).map_err(TestRunnerError::InvalidConfig)?;
+    if !should_run(input, event, &config) {
+        return Ok(None);
+    }
+    let Some(command) = config.command_for(project_dir) else {
+        return Ok(None);
+    };
+    let shown = command.join(" ");
+
+    let outcome = run_tests(
+        project_dir,
+        &command,
+        Duration::from_secs(config.timeout_secs),
+    );
+    record_run(input, project_dir, &shown, &outcome);
+
+    match outcome {
+        TestOutcome::Passed { .. } => Ok(None),
+        TestOutcome::Failed {
+            exit_code,
+            report,
+            elapsed,
+        } => {
+            let exit = exit_code.map_or_else(|| "killed".to_string(), |code| code.to_string());
+            let report = format!(
+                "`{}` failed (exit {}, {:.1}s)\n{}\nFix the failing tests, or explain why they should fail.",
+                shown,
+                exit,
+                elapsed.as_secs_f64(),
+                report.render(config.max_failures)
+            );
+            Ok(Some(failures(event, config.decision, report)))
+        }
+        TestOutcome::Skipped { reason } => Ok(Some(notice(format!(
+            "Tests not checked: `{}` {}",
+            shown, reason
+        )))),
+    }
+}
+
+/// Add the run to the check log for `catalyst report`; a failure to write
+/// it never affects the hook
+fn record_run(input: &HookInput, project_dir: &Path, command: &str, outcome: &TestOutcome) {
+    let (status, failures) = match outcome {
+        TestOutcome::Passed { .. } => (CheckStatus::Passed, None),
+        TestOutcome::Failed { report, .. } => (
+            CheckStatus::Failed,
+            (!report.failures.is_empty()).then_some(report.failures.len()),
+        ),
+        TestOutcome::Skipped { .. } => (CheckStatus::Incomplete, None),
+    };
+    let mut record = CheckRecord::now(
+        input.session_id.as_deref(),
+        project_dir,
+        "test-runner",
+        command,
+        status,
+    );
+    record.failures = failures;
+    if let Err(e) = modification_store::state_dir().and_then(|dir| check_log::append(&dir, &record))
+    {
+        eprintln!("test-runner: could not record run: {}", e);
+    }
+}
+
+fn run() -> Result<Option<HookResponse>, TestRunnerError> {
+    let mut buffer = String::new();
+    io::stdin().read_to_string(&mut buffer)?;
+    let input: HookInput =
+        serde_json::from_str(&buffer).map_err(TestRunnerError::InvalidHookInput)?;
+
+    let project_dir = input.project_dir();
+
+    match evaluate(&input, &project_dir) {
+        // Blocking on a broken config would stop Claude from ever finishing
+        Err(e @ TestRunnerError::InvalidConfig(_)) => Ok(Some(notice(e.to_string()))),
+        other => other,
+    }
+}
+
+/// Runs the tests (Stop and PostToolUse hook)
+#[derive(Parser)]
+#[command(name = "test-runner")]
+#[command(about = "Runs the project's tests for the hook payload read from stdin", long_about = None)]
+#[command(version)]
+struct Args {}
+
+fn main() {
+    Args::parse();
+    let timer = HookTimer::start("test-runner");
+    let code = match run() {
+        Ok(response) => {
+            timer.finish(HookOutcome::of(response.as_ref()), None);
+            response.map_or(EXIT_SUCCESS, |response| response.emit())
+        }
+        Err(e) => {
+            timer.finish(HookOutcome::Error, Some(e.to_string()));
+            eprintln!("test-runner: {}", e);
+            EXIT_SUCCESS
+        }
+    };
+    std::process::exit(code);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn input(json: serde_json::Value) -> HookInput {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_should_run_respects_events_and_stop_loop() {
+        let mut config = TestRunnerConfig::default();
+        let stop = input(serde_json::json!({"hook_event_name": "Stop"}));
+        let again = input(serde_json::json!({"hook_event_name": "Stop", "stop_hook_active": true}));
+        let edit =
+            input(serde_json::json!({"hook_event_name": "PostToolUse", "tool_name": "Edit"}));
+        let read =
+            input(serde_json::json!({"hook_event_name": "PostToolUse", "tool_name": "Read"}));
+
+        assert!(should_run(&stop, "Stop", &config));
+        assert!(!should_run(&again, "Stop", &config));
+        assert!(!should_run(&edit, "PostToolUse", &config));
+
+        config.events.push("PostToolUse".to_string());
+        assert!(should_run(&edit, "PostToolUse", &config));
+        assert!(!should_run(&read, "PostToolUse", &config));
+    }
+
+    #[test]
+    fn test_failure_responses_by_event_and_decision() {
+        let blocked =
+            serde_json::to_value(failures("Stop", TestDecision::Block, "report".to_string()))
+                .unwrap();
+        assert_eq!(blocked["decision"], "block");
+        assert_eq!(blocked["reason"], "report");
+        assert!(blocked.get("hookSpecificOutput").is_none());
+
+        let suggested = serde_json::to_value(failures(
+            "PostToolUse",
+            TestDecision::Suggest,
+            "report".to_string(),
+        ))
+        .unwrap();
+        assert!(suggested.get("decision").is_none());
+        assert_eq!(
+            suggested["hookSpecificOutput"]["additionalContext"],
+            "report"
+        );
+    }
+
+    #[test]
+    fn test_project_without_tests_is_left_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("README.md"), "# nothing to test\n").unwrap();
+        let stop = input(serde_json::json!({"hook_event_name": "Stop"}));
+        assert!(evaluate(&stop, temp_dir.path()).unwrap().is_none());
+    }
+}
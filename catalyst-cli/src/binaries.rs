@@ -0,0 +1,455 @@
+//! Hook binary installation (`catalyst install-binaries`)
+//!
+//! Copies the hook binaries into `~/.claude-hooks/bin/` without going
+//! through install.sh. Binaries are taken from a directory of prebuilt
+//! binaries (by default the one the running `catalyst` lives in, which is
+//! where cargo, release tarballs, and `target/release` put them) or built
+//! from a Catalyst checkout first.
+//!
+//! Each binary is written to a temporary file and renamed into place, so a
+//! hook running during the install sees either the old or the new binary.
+//! After copying, the installed file is hashed against its source and asked
+//! for its version.
+
+use crate::target;
+use crate::types::{CatalystError, Platform, Result, CATALYST_VERSION};
+use catalyst_core::fs_ops::{self, Fs, PermissionPolicy};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tempfile::NamedTempFile;
+
+/// Hook binaries installed into ~/.claude-hooks/bin, with whether each is required
+///
+/// file-change-tracker only exists in builds with the `sqlite` feature;
+/// path-guard, test-runner, prompt-logger, session-digest, and
+/// file-change-tracker-basic are optional so binary directories from older
+/// builds still install.
+pub const HOOK_BINARIES: &[(&str, bool)] = &[
+    ("skill-activation-prompt", true),
+    ("file-analyzer", true),
+    ("cargo-check", true),
+    ("path-guard", false),
+    ("test-runner", false),
+    ("prompt-logger", false),
+    ("session-digest", false),
+    ("file-change-tracker", false),
+    ("file-change-tracker-basic", false),
+];
+
+/// A binary that was installed
+#[derive(Debug, Clone, Serialize)]
+pub struct InstalledBinary {
+    pub name: String,
+
+    /// Installed location
+    pub path: PathBuf,
+
+    /// Version reported by `--version`, if the binary supports it
+    pub version: Option<String>,
+}
+
+/// Result of `catalyst install-binaries`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BinaryInstallReport {
+    /// Directory the binaries were taken from
+    pub source_dir: PathBuf,
+
+    /// Directory the binaries were installed into
+    pub bin_dir: PathBuf,
+
+    pub installed: Vec<InstalledBinary>,
+
+    /// Optional binaries that were not present in the source directory
+    pub skipped: Vec<String>,
+
+    /// Version mismatches and other non-fatal problems
+    pub warnings: Vec<String>,
+}
+
+/// Directory holding the running executable, where sibling binaries live
+pub fn default_source_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?.canonicalize().ok()?;
+    exe.parent().map(Path::to_path_buf)
+}
+
+/// Build the hook binaries in a Catalyst checkout
+///
+/// Runs `cargo build --release` with output passed through to the terminal.
+///
+/// # Arguments
+///
+/// * `checkout` - Root of a Catalyst repository
+/// * `sqlite` - Whether to build with the `sqlite` feature (file-change-tracker)
+///
+/// # Returns
+///
+/// Returns the directory containing the built binaries
+///
+/// # Errors
+///
+/// Returns `InvalidPath` if `checkout` isn't a Catalyst repository and
+/// `BuildFailed` if cargo can't be run or the build fails.
+pub fn build_binaries(checkout: &Path, sqlite: bool) -> Result<PathBuf> {
+    if !checkout.join("catalyst-cli").join("Cargo.toml").is_file() {
+        return Err(CatalystError::InvalidPath(format!(
+            "{} is not a Catalyst checkout (catalyst-cli/Cargo.toml not found)",
+            checkout.display()
+        )));
+    }
+
+    let mut command = Command::new("cargo");
+    command
+        .args(["build", "--release", "-p", "catalyst-cli", "--bins"])
+        .current_dir(checkout);
+    if sqlite {
+        command.args(["--features", "sqlite"]);
+    }
+
+    let status = command
+        .status()
+        .map_err(|e| CatalystError::BuildFailed(format!("Could not run cargo: {}", e)))?;
+    if !status.success() {
+        return Err(CatalystError::BuildFailed(format!(
+            "cargo build exited with {}",
+            status
+        )));
+    }
+
+    let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+        .map(|dir| checkout.join(dir))
+        .unwrap_or_else(|| checkout.join("target"));
+    // Cross builds (CARGO_BUILD_TARGET=aarch64-unknown-linux-musl) land in
+    // a per-triple directory
+    Ok(match std::env::var("CARGO_BUILD_TARGET") {
+        Ok(triple) if !triple.is_empty() => target_dir.join(triple).join("release"),
+        _ => target_dir.join("release"),
+    })
+}
+
+/// Install the hook binaries from `source_dir` into `bin_dir`
+///
+/// # Arguments
+///
+/// * `source_dir` - Directory containing the built binaries
+/// * `bin_dir` - Installation directory (normally ~/.claude-hooks/bin)
+/// * `platform` - Platform the binaries are for (adds `.exe` on Windows)
+///
+/// # Returns
+///
+/// Returns a report of what was installed
+///
+/// # Errors
+///
+/// Returns `BinaryNotFound` if a required binary is missing from
+/// `source_dir` (nothing is installed in that case), and `HashMismatch` if
+/// an installed binary doesn't match its source. Binaries built for another
+/// CPU architecture are installed with a warning, since emulation may still
+/// run them.
+pub fn install_binaries(
+    source_dir: &Path,
+    bin_dir: &Path,
+    platform: Platform,
+) -> Result<BinaryInstallReport> {
+    let mut report = BinaryInstallReport {
+        source_dir: source_dir.to_path_buf(),
+        bin_dir: bin_dir.to_path_buf(),
+        ..BinaryInstallReport::default()
+    };
+
+    // Check everything up front so a missing binary doesn't leave a mix of
+    // old and new binaries behind
+    let mut to_install = Vec::new();
+    for &(name, required) in HOOK_BINARIES {
+        let file_name = binary_file_name(name, platform);
+        let source = source_dir.join(&file_name);
+        if source.is_file() {
+            to_install.push((name, source, bin_dir.join(file_name)));
+        } else if required {
+            return Err(CatalystError::BinaryNotFound(format!(
+                "{} not found in {}",
+                file_name,
+                source_dir.display()
+            )));
+        } else {
+            report.skipped.push(name.to_string());
+        }
+    }
+
+    fs::create_dir_all(bin_dir).map_err(|e| CatalystError::DirectoryCreationFailed {
+        path: bin_dir.to_path_buf(),
+        source: e,
+    })?;
+
+    let host_arch = target::host_arch();
+    for (name, source, destination) in to_install {
+        let arches = target::executable_arches(&source).unwrap_or_default();
+        if let Some(host_arch) =
+            host_arch.filter(|arch| !arches.is_empty() && !arches.contains(arch))
+        {
+            report.warnings.push(format!(
+                "{} is built for {} but this machine is {}",
+                name, arches[0], host_arch
+            ));
+        }
+
+        copy_binary(&source, &destination)?;
+
+        let version = binary_version(&destination);
+        match &version {
+            Some(version) if version != CATALYST_VERSION => report.warnings.push(format!(
+                "{} reports version {} but this CLI is {}",
+                name, version, CATALYST_VERSION
+            )),
+            _ => {}
+        }
+
+        report.installed.push(InstalledBinary {
+            name: name.to_string(),
+            path: destination,
+            version,
+        });
+    }
+
+    Ok(report)
+}
+
+/// File name of a binary on a platform
+fn binary_file_name(name: &str, platform: Platform) -> String {
+    match platform {
+        Platform::Windows => format!("{}.exe", name),
+        _ => name.to_string(),
+    }
+}
+
+/// Atomically copy a binary into place as an executable and verify the copy
+fn copy_binary(source: &Path, destination: &Path) -> Result<()> {
+    let write_error = |e: io::Error| CatalystError::FileWriteFailed {
+        path: destination.to_path_buf(),
+        source: e,
+    };
+
+    let mut input = fs::File::open(source).map_err(|e| CatalystError::FileReadFailed {
+        path: source.to_path_buf(),
+        source: e,
+    })?;
+    let mut temp = NamedTempFile::new_in(destination.parent().unwrap_or(Path::new(".")))
+        .map_err(write_error)?;
+    io::copy(&mut input, &mut temp).map_err(write_error)?;
+
+    Fs::read_write()
+        .persist_with_policy(
+            temp,
+            destination,
+            fs_ops::DEFAULT_EXECUTABLE_MODE,
+            PermissionPolicy::Reset,
+        )
+        .map_err(write_error)?;
+
+    if hash_file(source)? != hash_file(destination)? {
+        return Err(CatalystError::HashMismatch(format!(
+            "{} does not match {}",
+            destination.display(),
+            source.display()
+        )));
+    }
+
+    Ok(())
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let content = fs::read(path).map_err(|e| CatalystError::FileReadFailed {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    Ok(format!("{:x}", Sha256::digest(&content)))
+}
+
+/// Ask a binary for its version
+///
+/// Hook binaries read their input from stdin, so stdin is closed to stop
+/// one that doesn't understand `--version` from waiting for input.
+pub(crate) fn binary_version(path: &Path) -> Option<String> {
+    let output = Command::new(path)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Oldest version of each hook binary whose input and output this CLI's
+/// hook wrappers and settings still match
+///
+/// Bump an entry when a release changes what a binary reads or prints.
+/// Binaries not listed only need the CLI's own major version (major and
+/// minor before 1.0).
+pub const MIN_BINARY_VERSIONS: &[(&str, &str)] = &[
+    ("skill-activation-prompt", "0.1.0"),
+    ("file-change-tracker", "0.1.0"),
+    ("file-change-tracker-basic", "0.1.0"),
+    ("file-analyzer", "0.1.0"),
+    ("cargo-check", "0.1.0"),
+    ("path-guard", "0.1.0"),
+    ("test-runner", "0.1.0"),
+    ("prompt-logger", "0.1.0"),
+    ("session-digest", "0.1.0"),
+];
+
+/// Whether a hook binary at `version` works with a CLI at `cli_version`
+///
+/// The binary must come from the same release line as the CLI (same
+/// major version, or same major and minor before 1.0) and be no older
+/// than its entry in [`MIN_BINARY_VERSIONS`]. Unparseable versions are
+/// incompatible.
+///
+/// # Arguments
+///
+/// * `name` - Binary name (e.g., "file-analyzer")
+/// * `version` - Version the binary reported
+/// * `cli_version` - Version of this CLI
+pub fn version_compatible(name: &str, version: &str, cli_version: &str) -> bool {
+    let (Some(found), Some(cli)) = (version_triple(version), version_triple(cli_version)) else {
+        return false;
+    };
+    let same_line = if cli.0 == 0 {
+        found.0 == 0 && found.1 == cli.1
+    } else {
+        found.0 == cli.0
+    };
+    let minimum = MIN_BINARY_VERSIONS
+        .iter()
+        .find(|(binary, _)| *binary == name)
+        .and_then(|(_, minimum)| version_triple(minimum));
+
+    same_line && minimum.is_none_or(|minimum| found >= minimum)
+}
+
+/// Parse "1.2.3" (ignoring any pre-release or build suffix) into numbers
+fn version_triple(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    Some((major, minor, patch))
+}
+
+/// Extract the version from `--version` output such as "file-analyzer 0.1.0"
+fn parse_version(output: &str) -> Option<String> {
+    let version = output.lines().next()?.split_whitespace().last()?;
+    let version = version.trim_start_matches('v');
+    version
+        .split('.')
+        .next()
+        .filter(|major| !major.is_empty() && major.chars().all(|c| c.is_ascii_digit()))
+        .map(|_| version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fake_binaries(dir: &Path, names: &[&str]) {
+        fs::create_dir_all(dir).unwrap();
+        for name in names {
+            fs::write(
+                dir.join(binary_file_name(name, Platform::Linux)),
+                format!("#!/bin/sh\necho '{} {}'\n", name, CATALYST_VERSION),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_install_binaries_copies_and_skips_optional() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("release");
+        let bin_dir = temp_dir.path().join("bin");
+        fake_binaries(
+            &source,
+            &["skill-activation-prompt", "file-analyzer", "cargo-check"],
+        );
+
+        let report = install_binaries(&source, &bin_dir, Platform::Linux).unwrap();
+        assert_eq!(report.installed.len(), 3);
+        assert_eq!(
+            report.skipped,
+            vec![
+                "path-guard".to_string(),
+                "test-runner".to_string(),
+                "prompt-logger".to_string(),
+                "session-digest".to_string(),
+                "file-change-tracker".to_string(),
+                "file-change-tracker-basic".to_string()
+            ]
+        );
+        assert!(report.warnings.is_empty());
+
+        #[cfg(unix)]
+        {
+            assert_eq!(
+                fs_ops::file_mode(&bin_dir.join("cargo-check")),
+                Some(fs_ops::DEFAULT_EXECUTABLE_MODE)
+            );
+            assert_eq!(
+                report.installed[0].version.as_deref(),
+                Some(CATALYST_VERSION)
+            );
+        }
+    }
+
+    #[test]
+    fn test_install_binaries_requires_core_binaries() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("release");
+        let bin_dir = temp_dir.path().join("bin");
+        fake_binaries(&source, &["skill-activation-prompt", "file-analyzer"]);
+
+        let result = install_binaries(&source, &bin_dir, Platform::Linux);
+        assert!(matches!(result, Err(CatalystError::BinaryNotFound(_))));
+        assert!(!bin_dir.exists());
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            parse_version("file-analyzer 0.1.0\n").as_deref(),
+            Some("0.1.0")
+        );
+        assert_eq!(parse_version("v1.2.3").as_deref(), Some("1.2.3"));
+        assert_eq!(parse_version("Error: no input"), None);
+        assert_eq!(parse_version(""), None);
+    }
+
+    #[test]
+    fn test_every_hook_binary_has_minimum_version() {
+        for (name, _) in HOOK_BINARIES {
+            assert!(
+                MIN_BINARY_VERSIONS.iter().any(|(binary, _)| binary == name),
+                "{} has no entry in MIN_BINARY_VERSIONS",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_version_compatible() {
+        assert!(version_compatible("file-analyzer", "0.1.4", "0.1.0"));
+        assert!(!version_compatible("file-analyzer", "0.2.0", "0.1.0"));
+        assert!(version_compatible("path-guard", "1.4.0", "1.0.2"));
+        assert!(!version_compatible("path-guard", "2.0.0", "1.9.0"));
+        // Below the minimum for the binary
+        assert!(!version_compatible("file-analyzer", "0.0.9", "0.0.9"));
+        assert!(!version_compatible("file-analyzer", "unknown", "0.1.0"));
+    }
+}
@@ -0,0 +1,446 @@
+//! Language checkers for the build-check hook
+//!
+//! The `cargo-check` hook runs cargo for edited `.rs` files. Other languages
+//! go through the checkers defined here, picked by the edited file's
+//! extension:
+//!
+//! | Checker     | Extensions                      | Runs in nearest directory with |
+//! |-------------|---------------------------------|--------------------------------|
+//! | `tsc`       | ts, tsx, mts, cts               | `tsconfig.json`                |
+//! | `eslint`    | ts, tsx, mts, cts, js, jsx, ... | an ESLint config file          |
+//! | `go-vet`    | go                              | `go.mod`                       |
+//! | `compileall`| py                              | (the file's directory)         |
+//! | `ruff`      | py                              | `pyproject.toml`, `ruff.toml`  |
+//!
+//! A checker whose program isn't installed (on `PATH` or in the project's
+//! `node_modules/.bin`) is skipped. Projects adjust the set with
+//! `.claude/check-hooks.toml`:
+//!
+//! ```toml
+//! # Built-in checkers to turn off
+//! disable = ["eslint"]
+//!
+//! # Add a checker, or replace the built-in one with the same name.
+//! # "{files}" expands to the edited files, relative to the root.
+//! [[checkers]]
+//! name = "mypy"
+//! extensions = ["py"]
+//! command = ["mypy", "{files}"]
+//! root_markers = ["mypy.ini", "pyproject.toml"]
+//! timeout_secs = 60
+//! ```
+
+use crate::process;
+use crate::types::{CatalystError, Result, CLAUDE_DIR};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+/// Checker configuration file, inside `.claude/`
+pub const CHECK_HOOKS_FILE: &str = "check-hooks.toml";
+
+/// Placeholder in a checker command replaced by the edited files
+pub const FILES_PLACEHOLDER: &str = "{files}";
+
+/// `.claude/check-hooks.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CheckHooksConfig {
+    /// Names of built-in checkers to turn off
+    pub disable: Vec<String>,
+
+    /// Checkers added to (or replacing) the built-in ones
+    pub checkers: Vec<CheckerConfig>,
+}
+
+/// One checker: a command run for files with given extensions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckerConfig {
+    /// Name shown in the hook output; a built-in checker with the same name
+    /// is replaced
+    pub name: String,
+
+    /// File extensions (without the dot) the checker runs for
+    pub extensions: Vec<String>,
+
+    /// Program and arguments, run from the root without a shell
+    pub command: Vec<String>,
+
+    /// Files marking the directory to run in; the nearest ancestor of the
+    /// edited file containing one is used, and the checker is skipped if
+    /// there is none. Empty means the edited file's own directory.
+    #[serde(default)]
+    pub root_markers: Vec<String>,
+
+    /// Seconds before the checker is killed
+    #[serde(default = "default_checker_timeout")]
+    pub timeout_secs: u64,
+}
+
+fn default_checker_timeout() -> u64 {
+    120
+}
+
+impl CheckerConfig {
+    fn new(name: &str, extensions: &[&str], command: &[&str], root_markers: &[&str]) -> Self {
+        let strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        Self {
+            name: name.to_string(),
+            extensions: strings(extensions),
+            command: strings(command),
+            root_markers: strings(root_markers),
+            timeout_secs: default_checker_timeout(),
+        }
+    }
+
+    /// Whether the checker runs for this file
+    pub fn applies_to(&self, file: &Path) -> bool {
+        file.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                self.extensions
+                    .iter()
+                    .any(|wanted| wanted.trim_start_matches('.').eq_ignore_ascii_case(ext))
+            })
+    }
+
+    /// Directory the checker runs in for this file, or None if the file
+    /// isn't inside a project the checker understands
+    pub fn find_root(&self, file: &Path) -> Option<PathBuf> {
+        let parent = file.parent()?;
+        let parent = if parent.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            parent
+        };
+        if self.root_markers.is_empty() {
+            return Some(parent.to_path_buf());
+        }
+        parent
+            .ancestors()
+            .find(|dir| {
+                self.root_markers
+                    .iter()
+                    .any(|marker| dir.join(marker).exists())
+            })
+            .map(Path::to_path_buf)
+    }
+}
+
+/// The checkers that ship with catalyst
+pub fn builtin_checkers() -> Vec<CheckerConfig> {
+    let python = if cfg!(windows) { "python" } else { "python3" };
+    vec![
+        CheckerConfig::new(
+            "tsc",
+            &["ts", "tsx", "mts", "cts"],
+            &["tsc", "--noEmit", "--pretty", "false"],
+            &["tsconfig.json"],
+        ),
+        CheckerConfig::new(
+            "eslint",
+            &["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"],
+            &["eslint", "--no-warn-ignored", FILES_PLACEHOLDER],
+            &[
+                "eslint.config.js",
+                "eslint.config.mjs",
+                "eslint.config.cjs",
+                "eslint.config.ts",
+                ".eslintrc.js",
+                ".eslintrc.cjs",
+                ".eslintrc.json",
+                ".eslintrc.yml",
+                ".eslintrc.yaml",
+                ".eslintrc",
+            ],
+        ),
+        CheckerConfig::new("go-vet", &["go"], &["go", "vet", "./..."], &["go.mod"]),
+        CheckerConfig::new(
+            "compileall",
+            &["py"],
+            &[python, "-m", "compileall", "-q", FILES_PLACEHOLDER],
+            &[],
+        ),
+        CheckerConfig::new(
+            "ruff",
+            &["py"],
+            &["ruff", "check", "--quiet", FILES_PLACEHOLDER],
+            &["ruff.toml", ".ruff.toml", "pyproject.toml"],
+        ),
+    ]
+}
+
+impl CheckHooksConfig {
+    /// Path of the config file for a project
+    pub fn path(project_dir: &Path) -> PathBuf {
+        project_dir.join(CLAUDE_DIR).join(CHECK_HOOKS_FILE)
+    }
+
+    /// Load a project's checker configuration, or defaults if it has none
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileReadFailed` if the file can't be read, or `InvalidConfig`
+    /// if it isn't valid TOML, has unknown keys, or defines a checker with
+    /// no command or extensions
+    pub fn load(project_dir: &Path) -> Result<Self> {
+        let path = Self::path(project_dir);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).map_err(|e| CatalystError::FileReadFailed {
+            path: path.clone(),
+            source: e,
+        })?;
+        let config: Self = toml::from_str(&content).map_err(|e| {
+            CatalystError::InvalidConfig(format!("{}: {}", path.display(), e.message()))
+        })?;
+        for checker in &config.checkers {
+            if checker.command.is_empty() || checker.extensions.is_empty() {
+                return Err(CatalystError::InvalidConfig(format!(
+                    "{}: checker '{}' needs a command and at least one extension",
+                    path.display(),
+                    checker.name
+                )));
+            }
+        }
+        Ok(config)
+    }
+
+    /// Built-in checkers minus disabled ones, with configured checkers
+    /// replacing built-ins of the same name
+    pub fn checkers(&self) -> Vec<CheckerConfig> {
+        let mut checkers: Vec<CheckerConfig> = builtin_checkers()
+            .into_iter()
+            .filter(|builtin| !self.disable.contains(&builtin.name))
+            .filter(|builtin| !self.checkers.iter().any(|c| c.name == builtin.name))
+            .collect();
+        checkers.extend(self.checkers.iter().cloned());
+        checkers
+    }
+}
+
+/// A checker to run once in one root, for the edited files under it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedCheck {
+    pub checker: CheckerConfig,
+    pub root: PathBuf,
+    pub files: Vec<PathBuf>,
+}
+
+/// Group edited files into one run per checker and root
+///
+/// Files no checker applies to, or outside any root a checker recognizes,
+/// are left out.
+pub fn plan_checks(checkers: &[CheckerConfig], files: &[PathBuf]) -> Vec<PlannedCheck> {
+    let mut planned: BTreeMap<(usize, PathBuf), Vec<PathBuf>> = BTreeMap::new();
+    for (index, checker) in checkers.iter().enumerate() {
+        for file in files.iter().filter(|file| checker.applies_to(file)) {
+            if let Some(root) = checker.find_root(file) {
+                let group = planned.entry((index, root)).or_default();
+                if !group.contains(file) {
+                    group.push(file.clone());
+                }
+            }
+        }
+    }
+    planned
+        .into_iter()
+        .map(|((index, root), files)| PlannedCheck {
+            checker: checkers[index].clone(),
+            root,
+            files,
+        })
+        .collect()
+}
+
+/// What happened when a checker ran
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckOutcome {
+    /// The checker exited successfully
+    Passed,
+    /// The checker reported problems; holds its combined output
+    Failed {
+        exit_code: Option<i32>,
+        output: String,
+    },
+    /// The checker couldn't be run or didn't finish; not the edit's fault
+    Skipped { reason: String },
+}
+
+impl PlannedCheck {
+    /// Run the checker, with its stdout and stderr captured together
+    pub fn run(&self) -> CheckOutcome {
+        let Some((program, args)) = self.checker.command.split_first() else {
+            return CheckOutcome::Skipped {
+                reason: "empty command".to_string(),
+            };
+        };
+        let Some(program_path) = resolve_program(program, &self.root) else {
+            return CheckOutcome::Skipped {
+                reason: format!("{} is not installed", program),
+            };
+        };
+
+        let mut command = Command::new(program_path);
+        for arg in args {
+            if arg == FILES_PLACEHOLDER {
+                command.args(self.files.iter().map(|file| relative_to(file, &self.root)));
+            } else {
+                command.arg(arg);
+            }
+        }
+
+        command.current_dir(&self.root);
+        match process::run_captured(&mut command, Duration::from_secs(self.checker.timeout_secs)) {
+            Ok(Some((status, _))) if status.success() => CheckOutcome::Passed,
+            Ok(Some((status, output))) => CheckOutcome::Failed {
+                exit_code: status.code(),
+                output,
+            },
+            Ok(None) => CheckOutcome::Skipped {
+                reason: format!(
+                    "did not finish within {} seconds",
+                    self.checker.timeout_secs
+                ),
+            },
+            Err(e) => CheckOutcome::Skipped {
+                reason: format!("could not run {}: {}", program, e),
+            },
+        }
+    }
+}
+
+/// Locate a checker's program: the project's `node_modules/.bin` first (for
+/// tsc and eslint installed as dev dependencies), then `PATH`
+pub(crate) fn resolve_program(program: &str, root: &Path) -> Option<PathBuf> {
+    if Path::new(program).components().count() > 1 {
+        let path = root.join(program);
+        return path.is_file().then_some(path);
+    }
+    let mut search: Vec<PathBuf> = root
+        .ancestors()
+        .map(|dir| dir.join("node_modules").join(".bin"))
+        .filter(|dir| dir.is_dir())
+        .collect();
+    search.extend(env::split_paths(&env::var_os("PATH").unwrap_or_default()));
+    let joined: OsString = env::join_paths(search).ok()?;
+    process::find_on_path(program, &joined)
+}
+
+fn relative_to(file: &Path, root: &Path) -> PathBuf {
+    let file = dunce::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+    let root = dunce::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    file.strip_prefix(&root)
+        .map(Path::to_path_buf)
+        .unwrap_or(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_config_overrides_builtins() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        assert_eq!(
+            CheckHooksConfig::load(project).unwrap().checkers(),
+            builtin_checkers()
+        );
+
+        fs::create_dir_all(project.join(CLAUDE_DIR)).unwrap();
+        fs::write(
+            CheckHooksConfig::path(project),
+            r#"
+disable = ["eslint", "ruff"]
+
+[[checkers]]
+name = "tsc"
+extensions = ["ts"]
+command = ["npx", "tsc", "--noEmit"]
+root_markers = ["tsconfig.json"]
+timeout_secs = 30
+"#,
+        )
+        .unwrap();
+        let checkers = CheckHooksConfig::load(project).unwrap().checkers();
+        let names: Vec<&str> = checkers.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["go-vet", "compileall", "tsc"]);
+        assert_eq!(checkers[2].command[0], "npx");
+        assert_eq!(checkers[2].timeout_secs, 30);
+
+        fs::write(
+            CheckHooksConfig::path(project),
+            "[[checkers]]\nname = \"empty\"\nextensions = []\ncommand = [\"true\"]\n",
+        )
+        .unwrap();
+        assert!(CheckHooksConfig::load(project).is_err());
+    }
+
+    #[test]
+    fn test_plan_groups_files_by_checker_and_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let web = temp_dir.path().join("web");
+        let svc = temp_dir.path().join("svc");
+        fs::create_dir_all(web.join("src")).unwrap();
+        fs::create_dir_all(svc.join("cmd")).unwrap();
+        fs::write(web.join("tsconfig.json"), "{}").unwrap();
+        fs::write(svc.join("go.mod"), "module svc\n").unwrap();
+
+        let files = vec![
+            web.join("src").join("a.ts"),
+            web.join("src").join("b.TSX"),
+            svc.join("cmd").join("main.go"),
+            temp_dir.path().join("notes.md"),
+            temp_dir.path().join("script.py"),
+        ];
+        let plan = plan_checks(&builtin_checkers(), &files);
+        let summary: Vec<(&str, &Path, usize)> = plan
+            .iter()
+            .map(|p| (p.checker.name.as_str(), p.root.as_path(), p.files.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("tsc", web.as_path(), 2),
+                ("go-vet", svc.as_path(), 1),
+                ("compileall", temp_dir.path(), 1),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_reports_failures_and_missing_programs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        let file = root.join("a.py");
+        fs::write(&file, "x = 1\n").unwrap();
+
+        let check = |command: &[&str]| PlannedCheck {
+            checker: CheckerConfig::new("custom", &["py"], command, &[]),
+            root: root.clone(),
+            files: vec![file.clone()],
+        };
+        assert_eq!(check(&["true"]).run(), CheckOutcome::Passed);
+        match check(&["sh", "-c", "echo \"bad: $0\"; exit 3", FILES_PLACEHOLDER]).run() {
+            CheckOutcome::Failed { exit_code, output } => {
+                assert_eq!(exit_code, Some(3));
+                assert_eq!(output.trim(), "bad: a.py");
+            }
+            other => panic!("expected failure, got {:?}", other),
+        }
+        assert!(matches!(
+            check(&["catalyst-no-such-checker"]).run(),
+            CheckOutcome::Skipped { .. }
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+//! Cooperative cancellation of init, update, and status
+//!
+//! Long operations call [`checkpoint`] between units of work: each skill
+//! and phase of init, each update step, each group of status checks. When
+//! the operation running on the current thread has been cancelled, the
+//! checkpoint fails with `Cancelled` and the operation unwinds the way it
+//! does for any other error: init rolls back its journal, update leaves
+//! its journal for `--resume` or `--rollback`, and the project lock is
+//! released.
+//!
+//! An operation is made cancellable by running it inside
+//! [`with_cancellation`]. The async API (the `tokio` feature) does this
+//! with a `CancellationToken` for each call. A Ctrl-C deferred by
+//! [`signals::defer_interrupts`] cancels whatever is running.
+
+use crate::signals;
+use crate::types::{CatalystError, Result};
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::Arc;
+
+/// Answers whether the running operation should stop
+#[derive(Clone)]
+pub struct CancelCheck(Arc<dyn Fn() -> bool + Send + Sync>);
+
+impl CancelCheck {
+    pub fn new(is_cancelled: impl Fn() -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(is_cancelled))
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        (self.0)()
+    }
+}
+
+impl fmt::Debug for CancelCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CancelCheck")
+            .field(&self.is_cancelled())
+            .finish()
+    }
+}
+
+thread_local! {
+    /// Check for the operation running on this thread
+    static CURRENT: RefCell<Option<CancelCheck>> = const { RefCell::new(None) };
+}
+
+/// Run `work` on this thread, cancelled when `check` says so
+pub fn with_cancellation<T>(check: CancelCheck, work: impl FnOnce() -> T) -> T {
+    /// Puts back the outer check, even if `work` panics
+    struct Restore(Option<CancelCheck>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(Some(check))));
+    work()
+}
+
+/// The check for the operation on this thread, for handing to worker
+/// threads
+pub fn current() -> Option<CancelCheck> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Fail with `Cancelled` if the operation on this thread was cancelled or
+/// Ctrl-C was pressed
+///
+/// # Errors
+///
+/// Returns `Cancelled` once the operation's check reports cancellation or
+/// [`signals::is_interrupted`]
+pub fn checkpoint() -> Result<()> {
+    check(current().as_ref())
+}
+
+/// Fail with `Cancelled` if `check` reports cancellation or Ctrl-C was
+/// pressed
+pub(crate) fn check(check: Option<&CancelCheck>) -> Result<()> {
+    if signals::is_interrupted() || check.is_some_and(CancelCheck::is_cancelled) {
+        return Err(CatalystError::Cancelled);
+    }
+    Ok(())
+}
+
+/// Run blocking `work` on tokio's blocking pool, cancelled by `token`
+#[cfg(feature = "tokio")]
+pub(crate) async fn run_blocking<T, F>(
+    token: tokio_util::sync::CancellationToken,
+    work: F,
+) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    if token.is_cancelled() {
+        return Err(CatalystError::Cancelled);
+    }
+    let cancel = CancelCheck::new(move || token.is_cancelled());
+    tokio::task::spawn_blocking(move || with_cancellation(cancel, work))
+        .await
+        .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_checkpoint_follows_the_current_check() {
+        assert!(checkpoint().is_ok());
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&cancelled);
+        let check = CancelCheck::new(move || flag.load(Ordering::SeqCst));
+        with_cancellation(check, || {
+            assert!(checkpoint().is_ok());
+            cancelled.store(true, Ordering::SeqCst);
+            assert!(matches!(checkpoint(), Err(CatalystError::Cancelled)));
+        });
+
+        // The check only applies inside with_cancellation
+        assert!(current().is_none());
+        assert!(checkpoint().is_ok());
+    }
+}
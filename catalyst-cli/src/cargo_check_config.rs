@@ -0,0 +1,198 @@
+//! Per-project settings for the cargo-check hook (`.claude/cargo-check.toml`)
+//!
+//! ```toml
+//! clippy = true
+//! fmt = true
+//! tests = false
+//! quiet = false
+//! extra_args = ["--all-features"]   # passed to check, clippy, and test
+//! exclude = ["slow-codegen"]        # workspace members to skip
+//! targeted = true                   # check only the edited packages
+//! max_diagnostics = 10              # errors and warnings listed per command
+//! timeout_secs = 300                # whole hook run; 0 for no limit
+//! ```
+//!
+//! Any key left out falls back to the matching `CARGO_CHECK_*` environment
+//! variable, so existing wrapper setups keep working.
+
+use crate::cargo_diagnostics::DEFAULT_MAX_DIAGNOSTICS;
+use crate::types::{CatalystError, Result, CLAUDE_DIR};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Settings file, inside `.claude/`
+pub const CARGO_CHECK_FILE: &str = "cargo-check.toml";
+
+/// Time allowed for all cargo commands in one hook run
+pub const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+/// `.claude/cargo-check.toml` as written; None means "use the environment"
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CargoCheckConfig {
+    /// Also run `cargo clippy -- -D warnings` (`CARGO_CHECK_CLIPPY`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clippy: Option<bool>,
+
+    /// Also run `cargo fmt -- --check` (`CARGO_CHECK_FMT`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fmt: Option<bool>,
+
+    /// Also run `cargo test --no-run` (`CARGO_CHECK_TESTS`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tests: Option<bool>,
+
+    /// Only report errors and warnings (`CARGO_CHECK_QUIET`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quiet: Option<bool>,
+
+    /// Arguments added to check, clippy, and test (before any `--`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extra_args: Vec<String>,
+
+    /// Workspace members passed to `--exclude`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+
+    /// In a workspace, check only the packages owning the edited files and
+    /// the members depending on them (default true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub targeted: Option<bool>,
+
+    /// Diagnostics listed in each command's summary; the rest are only
+    /// counted ([`DEFAULT_MAX_DIAGNOSTICS`] if unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_diagnostics: Option<usize>,
+
+    /// Seconds the hook's cargo commands may take in total before the
+    /// running one is killed ([`DEFAULT_TIMEOUT_SECS`] if unset, 0 for no
+    /// limit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Effective settings after falling back to the environment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CargoCheckSettings {
+    pub clippy: bool,
+    pub fmt: bool,
+    pub tests: bool,
+    pub quiet: bool,
+    pub extra_args: Vec<String>,
+    pub exclude: Vec<String>,
+    pub targeted: bool,
+    pub max_diagnostics: usize,
+    pub timeout: Option<Duration>,
+}
+
+impl CargoCheckConfig {
+    /// Path of the settings file for a project
+    pub fn path(project_dir: &Path) -> PathBuf {
+        project_dir.join(CLAUDE_DIR).join(CARGO_CHECK_FILE)
+    }
+
+    /// Load a project's settings, or an empty config if it has no file
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileReadFailed` if the file can't be read, or `InvalidConfig`
+    /// if it isn't valid TOML or has unknown keys
+    pub fn load(project_dir: &Path) -> Result<Self> {
+        let path = Self::path(project_dir);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).map_err(|e| CatalystError::FileReadFailed {
+            path: path.clone(),
+            source: e,
+        })?;
+        toml::from_str(&content).map_err(|e| {
+            CatalystError::InvalidConfig(format!("{}: {}", path.display(), e.message()))
+        })
+    }
+
+    /// Fill in unset switches from the environment
+    ///
+    /// # Arguments
+    ///
+    /// * `env_enabled` - Whether a `CARGO_CHECK_*` variable is set to a
+    ///   truthy value
+    pub fn resolve(&self, env_enabled: impl Fn(&str) -> bool) -> CargoCheckSettings {
+        CargoCheckSettings {
+            clippy: self
+                .clippy
+                .unwrap_or_else(|| env_enabled("CARGO_CHECK_CLIPPY")),
+            fmt: self.fmt.unwrap_or_else(|| env_enabled("CARGO_CHECK_FMT")),
+            tests: self
+                .tests
+                .unwrap_or_else(|| env_enabled("CARGO_CHECK_TESTS")),
+            quiet: self
+                .quiet
+                .unwrap_or_else(|| env_enabled("CARGO_CHECK_QUIET")),
+            extra_args: self.extra_args.clone(),
+            exclude: self.exclude.clone(),
+            targeted: self.targeted.unwrap_or(true),
+            max_diagnostics: self.max_diagnostics.unwrap_or(DEFAULT_MAX_DIAGNOSTICS),
+            timeout: match self.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS) {
+                0 => None,
+                secs => Some(Duration::from_secs(secs)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_file_values_override_environment() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        fs::create_dir_all(project.join(CLAUDE_DIR)).unwrap();
+        fs::write(
+            CargoCheckConfig::path(project),
+            "clippy = false\nexclude = [\"codegen\"]\nextra_args = [\"--all-features\"]\ntimeout_secs = 90\n",
+        )
+        .unwrap();
+
+        let settings = CargoCheckConfig::load(project)
+            .unwrap()
+            .resolve(|var| var == "CARGO_CHECK_CLIPPY" || var == "CARGO_CHECK_FMT");
+        assert!(!settings.clippy, "file value wins over the environment");
+        assert!(settings.fmt, "unset keys fall back to the environment");
+        assert!(!settings.tests);
+        assert_eq!(settings.exclude, vec!["codegen"]);
+        assert_eq!(settings.extra_args, vec!["--all-features"]);
+        assert_eq!(settings.timeout, Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn test_missing_and_invalid_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        assert_eq!(
+            CargoCheckConfig::load(project).unwrap(),
+            CargoCheckConfig::default()
+        );
+        assert_eq!(
+            CargoCheckConfig::default().resolve(|_| false).timeout,
+            Some(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+        );
+        let unlimited = CargoCheckConfig {
+            timeout_secs: Some(0),
+            ..CargoCheckConfig::default()
+        };
+        assert_eq!(unlimited.resolve(|_| false).timeout, None);
+
+        fs::create_dir_all(project.join(CLAUDE_DIR)).unwrap();
+        fs::write(CargoCheckConfig::path(project), "clipy = true\n").unwrap();
+        assert!(matches!(
+            CargoCheckConfig::load(project),
+            Err(CatalystError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,285 @@
+//! Summaries of cargo's `--message-format=json` output
+//!
+//! The cargo-check hook runs check, clippy, and test with JSON messages and
+//! hands Claude a short summary instead of raw compiler output:
+//!
+//! ```text
+//! 2 errors, 1 warning
+//! error[E0425]: cannot find value `foo` in this scope
+//!   --> src/main.rs:10:9
+//! error: unused variable: `x`
+//!   --> src/lib.rs:4:9
+//! warning: function `helper` is never used
+//!   --> src/lib.rs:12:4
+//! ```
+//!
+//! Levels come from rustc rather than from matching "error" in a line of
+//! text, so warnings can be counted and listed separately.
+
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fmt::{self, Write as _};
+
+/// Default number of diagnostics listed in a summary
+pub const DEFAULT_MAX_DIAGNOSTICS: usize = 10;
+
+/// Severity of a compiler diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Error => write!(f, "error"),
+            Level::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// One error or warning, located at its primary span
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub level: Level,
+    /// Lint or error code such as `E0425` or `clippy::needless_return`
+    pub code: Option<String>,
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+/// A line of cargo's JSON output; only compiler messages matter here
+#[derive(Debug, Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<RustcMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RustcMessage {
+    level: String,
+    message: String,
+    code: Option<RustcCode>,
+    #[serde(default)]
+    spans: Vec<RustcSpan>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RustcCode {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RustcSpan {
+    file_name: String,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+}
+
+/// Errors and warnings from a cargo run, in the order rustc reported them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    pub errors: Vec<Diagnostic>,
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    /// Parse cargo's stdout, skipping lines that aren't compiler messages
+    ///
+    /// A diagnostic reported for several targets of one package (lib and
+    /// test builds) is kept once, as are rustc's own "aborting due to" and
+    /// "warnings emitted" tallies, which are dropped.
+    pub fn parse(stdout: &str) -> Self {
+        let mut seen = HashSet::new();
+        let mut diagnostics = Self::default();
+
+        for line in stdout.lines() {
+            let Ok(CargoMessage {
+                reason,
+                message: Some(message),
+            }) = serde_json::from_str::<CargoMessage>(line)
+            else {
+                continue;
+            };
+            if reason != "compiler-message" {
+                continue;
+            }
+            let Some(diagnostic) = Diagnostic::from_rustc(message) else {
+                continue;
+            };
+            if !seen.insert(diagnostic.clone()) {
+                continue;
+            }
+            match diagnostic.level {
+                Level::Error => diagnostics.errors.push(diagnostic),
+                Level::Warning => diagnostics.warnings.push(diagnostic),
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Whether nothing was parsed
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
+
+    /// Render the counts and the first diagnostics, errors before warnings
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Diagnostics to list; the rest are only counted
+    /// * `include_warnings` - Whether to list warnings (they're always counted)
+    pub fn summary(&self, max: usize, include_warnings: bool) -> String {
+        let mut out = format!(
+            "{}, {}\n",
+            plural(self.errors.len(), "error"),
+            plural(self.warnings.len(), "warning")
+        );
+
+        let listed: Vec<&Diagnostic> = if include_warnings {
+            self.errors.iter().chain(&self.warnings).collect()
+        } else {
+            self.errors.iter().collect()
+        };
+        for diagnostic in listed.iter().take(max) {
+            let _ = writeln!(out, "{}", diagnostic);
+        }
+        if listed.len() > max {
+            let _ = writeln!(out, "... and {} more", listed.len() - max);
+        }
+        out
+    }
+}
+
+impl Diagnostic {
+    fn from_rustc(message: RustcMessage) -> Option<Self> {
+        let level = match message.level.as_str() {
+            "error" | "error: internal compiler error" => Level::Error,
+            "warning" => Level::Warning,
+            _ => return None,
+        };
+        if message.spans.is_empty() && is_tally(&message.message) {
+            return None;
+        }
+        let primary = message
+            .spans
+            .iter()
+            .find(|span| span.is_primary)
+            .or_else(|| message.spans.first());
+        Some(Self {
+            level,
+            code: message.code.map(|code| code.code),
+            message: message.message,
+            file: primary.map(|span| span.file_name.clone()),
+            line: primary.map(|span| span.line_start),
+            column: primary.map(|span| span.column_start),
+        })
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.code {
+            Some(code) => write!(f, "{}[{}]: {}", self.level, code, self.message)?,
+            None => write!(f, "{}: {}", self.level, self.message)?,
+        }
+        if let (Some(file), Some(line), Some(column)) = (&self.file, self.line, self.column) {
+            write!(f, "\n  --> {}:{}:{}", file, line, column)?;
+        }
+        Ok(())
+    }
+}
+
+/// rustc's closing counts, which repeat what the summary already says
+fn is_tally(message: &str) -> bool {
+    message.starts_with("aborting due to")
+        || (message.contains("warning") && message.ends_with("emitted"))
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(level: &str, code: Option<&str>, text: &str, file: &str, line: usize) -> String {
+        serde_json::json!({
+            "reason": "compiler-message",
+            "package_id": "demo 0.1.0",
+            "message": {
+                "level": level,
+                "message": text,
+                "code": code.map(|code| serde_json::json!({ "code": code })),
+                "spans": [
+                    { "file_name": "src/other.rs", "line_start": 1, "column_start": 1, "is_primary": false },
+                    { "file_name": file, "line_start": line, "column_start": 9, "is_primary": true }
+                ],
+                "rendered": "..."
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_parse_splits_levels_and_drops_noise() {
+        let error = message(
+            "error",
+            Some("E0425"),
+            "cannot find value `foo` in this scope",
+            "src/main.rs",
+            10,
+        );
+        let stdout = [
+            r#"{"reason":"compiler-artifact","package_id":"dep 1.0.0"}"#.to_string(),
+            message("warning", None, "unused variable: `x`", "src/lib.rs", 4),
+            error.clone(),
+            // The same error from the test build of the target
+            error,
+            message("note", None, "some note", "src/lib.rs", 1),
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"aborting due to 1 previous error","code":null,"spans":[]}}"#.to_string(),
+            "not json".to_string(),
+            r#"{"reason":"build-finished","success":false}"#.to_string(),
+        ]
+        .join("\n");
+
+        let diagnostics = Diagnostics::parse(&stdout);
+        assert_eq!(diagnostics.errors.len(), 1);
+        assert_eq!(diagnostics.warnings.len(), 1);
+        assert_eq!(
+            diagnostics.errors[0].to_string(),
+            "error[E0425]: cannot find value `foo` in this scope\n  --> src/main.rs:10:9"
+        );
+    }
+
+    #[test]
+    fn test_summary_limits_and_filters_warnings() {
+        let stdout = [
+            message("error", None, "first", "src/a.rs", 1),
+            message("error", None, "second", "src/a.rs", 2),
+            message("warning", None, "third", "src/a.rs", 3),
+        ]
+        .join("\n");
+        let diagnostics = Diagnostics::parse(&stdout);
+
+        let summary = diagnostics.summary(2, true);
+        assert!(summary.starts_with("2 errors, 1 warning\n"));
+        assert!(summary.contains("error: second"));
+        assert!(!summary.contains("third"));
+        assert!(summary.ends_with("... and 1 more\n"));
+
+        let errors_only = diagnostics.summary(10, false);
+        assert!(!errors_only.contains("warning: third"));
+        assert!(!errors_only.contains("more"));
+    }
+}
@@ -0,0 +1,182 @@
+//! Log of check hook outcomes
+//!
+//! cargo-check and test-runner append one line to `checks.jsonl` in the
+//! tracker state directory for every run, so `catalyst report` can say which
+//! checks ran during a piece of work and how often they failed. Hooks write
+//! with a single append and ignore failures; a lost line only makes the
+//! report less complete.
+
+use crate::types::{CatalystError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Name of the log inside the state directory
+pub const CHECK_LOG_FILE: &str = "checks.jsonl";
+
+/// How a check run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+    /// Timed out or couldn't run; says nothing about the code
+    Incomplete,
+}
+
+/// One run of a check hook
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckRecord {
+    /// When the run finished (RFC 3339)
+    pub timestamp: String,
+    pub session_id: Option<String>,
+    /// Project directory the hook ran for
+    pub project: String,
+    /// Hook that ran the check (`cargo-check`, `test-runner`)
+    pub hook: String,
+    /// What ran, e.g. `cargo check + clippy` or `npm test`
+    pub command: String,
+    pub status: CheckStatus,
+    /// Failing tests or checks, when the hook counts them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failures: Option<usize>,
+}
+
+impl CheckRecord {
+    /// A record stamped with the current time
+    pub fn now(
+        session_id: Option<&str>,
+        project: &Path,
+        hook: &str,
+        command: &str,
+        status: CheckStatus,
+    ) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339(),
+            session_id: session_id.map(str::to_string),
+            project: canonical(project).to_string_lossy().into_owned(),
+            hook: hook.to_string(),
+            command: command.to_string(),
+            status,
+            failures: None,
+        }
+    }
+}
+
+/// Path of the log in `state_dir`
+pub fn log_path(state_dir: &Path) -> PathBuf {
+    state_dir.join(CHECK_LOG_FILE)
+}
+
+/// Append a record to the log, creating the state directory if needed
+///
+/// # Errors
+///
+/// Returns an error if the directory or the log can't be written
+pub fn append(state_dir: &Path, record: &CheckRecord) -> Result<()> {
+    fs::create_dir_all(state_dir).map_err(|source| CatalystError::DirectoryCreationFailed {
+        path: state_dir.to_path_buf(),
+        source,
+    })?;
+    let path = log_path(state_dir);
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|source| CatalystError::FileWriteFailed { path, source })
+}
+
+/// Records for one project at or after `since`, oldest first
+///
+/// Lines that don't parse are skipped.
+///
+/// # Errors
+///
+/// Returns `FileReadFailed` if the log exists but can't be read
+pub fn read(
+    state_dir: &Path,
+    project: &Path,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<CheckRecord>> {
+    let path = log_path(state_dir);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => return Err(CatalystError::FileReadFailed { path, source }),
+    };
+    let project = canonical(project).to_string_lossy().into_owned();
+    let mut records: Vec<CheckRecord> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CheckRecord>(line).ok())
+        .filter(|record| record.project == project)
+        .filter(|record| {
+            since.is_none_or(|since| {
+                DateTime::parse_from_rfc3339(&record.timestamp).is_ok_and(|time| time >= since)
+            })
+        })
+        .collect();
+    records.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    Ok(records)
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_and_read_by_project_and_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let state_dir = temp_dir.path().join("state");
+        let project = temp_dir.path().join("project");
+        let other = temp_dir.path().join("other");
+        fs::create_dir_all(&project).unwrap();
+        fs::create_dir_all(&other).unwrap();
+
+        let mut old = CheckRecord::now(
+            Some("s1"),
+            &project,
+            "cargo-check",
+            "cargo check",
+            CheckStatus::Failed,
+        );
+        old.timestamp = "2026-01-01T00:00:00+00:00".to_string();
+        append(&state_dir, &old).unwrap();
+        let mut tests = CheckRecord::now(
+            Some("s1"),
+            &project,
+            "test-runner",
+            "cargo test",
+            CheckStatus::Failed,
+        );
+        tests.failures = Some(2);
+        append(&state_dir, &tests).unwrap();
+        append(
+            &state_dir,
+            &CheckRecord::now(None, &other, "test-runner", "npm test", CheckStatus::Passed),
+        )
+        .unwrap();
+
+        let all = read(&state_dir, &project, None).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0], old);
+
+        let since = DateTime::parse_from_rfc3339("2026-02-01T00:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        let recent = read(&state_dir, &project, Some(since)).unwrap();
+        assert_eq!(recent, vec![tests]);
+        assert!(read(&temp_dir.path().join("missing"), &project, None)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,552 @@
+//! Slash commands in `.claude/commands` (`catalyst commands`)
+//!
+//! Each `.claude/commands/<name>.md` becomes `/<name>` in Claude Code (files
+//! in subdirectories are listed as `dir/name`). Front matter is optional;
+//! when present it may set `description`, `argument-hint`, `allowed-tools`,
+//! `model`, and `disable-model-invocation`. The body is the prompt, where
+//! `$ARGUMENTS` expands to everything typed after the command and `$1`,
+//! `$2`, ... to single arguments. Lines run through the shell with `` !`cmd` ``
+//! need `Bash` in `allowed-tools` when that field is set.
+
+use crate::agents::UNREPLACED_VARIABLE;
+use crate::config::CatalystConfig;
+use crate::init::write_file_atomic;
+use crate::scaffold::validate_kebab_name;
+use crate::template;
+use crate::types::{CatalystError, Result, COMMANDS_DIR};
+use catalyst_core::front_matter::{parse_fields, split_front_matter, unquote};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// A command file shipped with catalyst
+#[derive(Debug, Clone, Copy)]
+pub struct CommandTemplate {
+    pub name: &'static str,
+    /// One line for `catalyst commands list`
+    pub summary: &'static str,
+    /// `description` used unless another is given
+    pub description: &'static str,
+    /// `argument-hint` used unless another is given
+    pub argument_hint: &'static str,
+    pub content: &'static str,
+}
+
+/// Templates `catalyst commands new --template` can start from
+pub const COMMAND_TEMPLATES: &[CommandTemplate] = &[
+    CommandTemplate {
+        name: "basic",
+        summary: "A prompt that takes free-form arguments",
+        description: "TODO: what this command does",
+        argument_hint: "[request]",
+        content: include_str!("../resources/commands/basic.md"),
+    },
+    CommandTemplate {
+        name: "review",
+        summary: "Review changes with git context, read-only",
+        description: "Review changes for bugs and style",
+        argument_hint: "[path or ref]",
+        content: include_str!("../resources/commands/review.md"),
+    },
+    CommandTemplate {
+        name: "fix-issue",
+        summary: "Fix a GitHub issue by number",
+        description: "Fix a GitHub issue and add a regression test",
+        argument_hint: "[issue-number]",
+        content: include_str!("../resources/commands/fix-issue.md"),
+    },
+];
+
+/// Template used by `catalyst commands new` without `--template`
+pub const DEFAULT_COMMAND_TEMPLATE: &str = "basic";
+
+const FRONT_MATTER_FIELDS: &[&str] = &[
+    "description",
+    "argument-hint",
+    "allowed-tools",
+    "model",
+    "disable-model-invocation",
+];
+
+/// Model aliases accepted in command front matter
+const COMMAND_MODELS: &[&str] = &["sonnet", "opus", "haiku"];
+
+static POSITIONAL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$([1-9][0-9]*)").unwrap());
+static INLINE_SHELL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"!`[^`]*`").unwrap());
+
+/// Parsed command front matter
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CommandFrontMatter {
+    pub description: Option<String>,
+    pub argument_hint: Option<String>,
+    /// Empty means the conversation's tool permissions apply
+    pub allowed_tools: Vec<String>,
+    pub model: Option<String>,
+    pub disable_model_invocation: bool,
+}
+
+/// A command file found in `.claude/commands`
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandInfo {
+    /// Path under `.claude/commands` without `.md`, `/`-separated
+    pub name: String,
+    pub path: PathBuf,
+    /// None when the front matter couldn't be read at all
+    pub front_matter: Option<CommandFrontMatter>,
+    /// Placeholders the prompt uses, e.g. `$ARGUMENTS`, `$1`
+    pub placeholders: Vec<String>,
+    /// Everything wrong with the file; empty if it's valid
+    pub problems: Vec<String>,
+}
+
+/// Options for `catalyst commands new`
+#[derive(Debug, Clone, Default)]
+pub struct NewCommand {
+    pub name: String,
+    /// Name of a template in [`COMMAND_TEMPLATES`]; the default if None
+    pub template: Option<String>,
+    pub description: Option<String>,
+    pub argument_hint: Option<String>,
+    /// Replace an existing command with the same name
+    pub force: bool,
+}
+
+/// Look up a shipped template by name
+pub fn find_template(name: &str) -> Option<&'static CommandTemplate> {
+    COMMAND_TEMPLATES
+        .iter()
+        .find(|template| template.name == name)
+}
+
+/// Check a command file's contents
+///
+/// # Returns
+///
+/// Returns the front matter (default when the file has none, None when it
+/// is unreadable), the argument placeholders the prompt uses, and every
+/// problem found
+pub fn validate_command(content: &str) -> (Option<CommandFrontMatter>, Vec<String>, Vec<String>) {
+    let (block, body) = match split_front_matter(content) {
+        Ok(Some(parts)) => parts,
+        Ok(None) => (String::new(), content.to_string()),
+        Err(problem) => return (None, Vec::new(), vec![problem]),
+    };
+
+    let mut problems = Vec::new();
+    let fields = parse_fields(&block, FRONT_MATTER_FIELDS, &mut problems);
+    let field = |key: &str| {
+        fields
+            .get(key)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    };
+
+    let mut front_matter = CommandFrontMatter {
+        description: field("description").map(str::to_string),
+        argument_hint: field("argument-hint").map(str::to_string),
+        ..CommandFrontMatter::default()
+    };
+    if let Some(tools) = field("allowed-tools") {
+        front_matter.allowed_tools = split_tools(tools);
+        if front_matter.allowed_tools.iter().any(String::is_empty) {
+            problems.push(format!("'allowed-tools' has an empty entry: {}", tools));
+        }
+    }
+    if let Some(model) = field("model") {
+        if !COMMAND_MODELS.contains(&model) && !model.starts_with("claude-") {
+            problems.push(format!(
+                "unknown model '{}' (expected {} or a full claude-* model ID)",
+                model,
+                COMMAND_MODELS.join(", ")
+            ));
+        }
+        front_matter.model = Some(model.to_string());
+    }
+    match field("disable-model-invocation") {
+        None | Some("false") => {}
+        Some("true") => front_matter.disable_model_invocation = true,
+        Some(other) => problems.push(format!(
+            "'disable-model-invocation' must be true or false, got '{}'",
+            other
+        )),
+    }
+
+    if body.trim().is_empty() {
+        problems.push("no prompt after the front matter".to_string());
+    }
+    if content.contains("{{") {
+        problems.push(UNREPLACED_VARIABLE.to_string());
+    }
+
+    let (placeholders, uses_shell) = scan_body(&body);
+    check_placeholders(&front_matter, &placeholders, &mut problems);
+    let allows_bash = front_matter
+        .allowed_tools
+        .iter()
+        .any(|tool| tool == "Bash" || tool.starts_with("Bash("));
+    if uses_shell && !front_matter.allowed_tools.is_empty() && !allows_bash {
+        problems.push(
+            "runs shell commands with !`...` but 'allowed-tools' doesn't include Bash".to_string(),
+        );
+    }
+    (Some(front_matter), placeholders, problems)
+}
+
+/// Split `allowed-tools`, keeping commas inside `Bash(...)` patterns
+fn split_tools(tools: &str) -> Vec<String> {
+    let list = tools.trim_start_matches('[').trim_end_matches(']');
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in list.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                result.push(unquote(current.trim()).to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    result.push(unquote(current.trim()).to_string());
+    result
+}
+
+/// Placeholders in the prompt text, and whether it runs shell commands
+///
+/// Shell commands are left out of the placeholder scan: `$2` in an awk
+/// script isn't a command argument.
+fn scan_body(body: &str) -> (Vec<String>, bool) {
+    let mut found = BTreeSet::new();
+    let mut uses_shell = false;
+    let mut continued = false;
+    for line in body.lines() {
+        if continued || line.starts_with('!') {
+            uses_shell = true;
+            continued = line.trim_end().ends_with('\\');
+            continue;
+        }
+        if INLINE_SHELL.is_match(line) {
+            uses_shell = true;
+        }
+        let text = INLINE_SHELL.replace_all(line, "");
+        if text.contains("$ARGUMENTS") {
+            found.insert(0);
+        }
+        for capture in POSITIONAL.captures_iter(&text) {
+            if let Ok(n) = capture[1].parse::<usize>() {
+                found.insert(n);
+            }
+        }
+    }
+    let placeholders = found
+        .into_iter()
+        .map(|n| {
+            if n == 0 {
+                "$ARGUMENTS".to_string()
+            } else {
+                format!("${}", n)
+            }
+        })
+        .collect();
+    (placeholders, uses_shell)
+}
+
+/// Check positional arguments against each other and the argument hint
+fn check_placeholders(
+    front_matter: &CommandFrontMatter,
+    placeholders: &[String],
+    problems: &mut Vec<String>,
+) {
+    let positions: Vec<usize> = placeholders
+        .iter()
+        .filter_map(|p| p.strip_prefix('$')?.parse().ok())
+        .collect();
+    if let Some(&highest) = positions.iter().max() {
+        let missing: Vec<String> = (1..highest)
+            .filter(|n| !positions.contains(n))
+            .map(|n| format!("${}", n))
+            .collect();
+        if !missing.is_empty() {
+            problems.push(format!(
+                "uses ${} but never {}",
+                highest,
+                missing.join(" or ")
+            ));
+        }
+        match &front_matter.argument_hint {
+            None => problems.push(format!(
+                "uses positional arguments (up to ${}) but has no 'argument-hint'",
+                highest
+            )),
+            Some(hint) => {
+                // Only a hint made entirely of [groups] can be counted
+                let groups = hint.matches('[').count();
+                let only_groups = hint
+                    .split(']')
+                    .all(|part| part.trim().is_empty() || part.trim_start().starts_with('['));
+                if only_groups && groups > 0 && highest > groups {
+                    problems.push(format!(
+                        "uses ${} but 'argument-hint' describes {} argument(s): {}",
+                        highest, groups, hint
+                    ));
+                }
+            }
+        }
+    }
+    if front_matter.argument_hint.is_some() && placeholders.is_empty() {
+        problems.push(
+            "has an 'argument-hint' but the prompt never uses $ARGUMENTS or $1, $2, ..."
+                .to_string(),
+        );
+    }
+}
+
+/// Command files in `.claude/commands` and its subdirectories, sorted by
+/// name, each checked
+///
+/// # Errors
+///
+/// Returns `FileReadFailed` if a directory or file can't be read
+pub fn list_commands(target_dir: &Path) -> Result<Vec<CommandInfo>> {
+    let root = target_dir.join(COMMANDS_DIR);
+    let mut commands = Vec::new();
+    let mut pending = vec![root.clone()];
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(source) => return Err(CatalystError::FileReadFailed { path: dir, source }),
+        };
+        for entry in entries {
+            let path = entry
+                .map_err(|source| CatalystError::FileReadFailed {
+                    path: dir.clone(),
+                    source,
+                })?
+                .path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let is_command = path.extension().is_some_and(|ext| ext == "md")
+                && !path
+                    .file_name()
+                    .is_some_and(|name| name.eq_ignore_ascii_case("README.md"));
+            if !is_command {
+                continue;
+            }
+            let name = path
+                .strip_prefix(&root)
+                .unwrap_or(&path)
+                .with_extension("")
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+            let content =
+                fs::read_to_string(&path).map_err(|source| CatalystError::FileReadFailed {
+                    path: path.clone(),
+                    source,
+                })?;
+            let (front_matter, placeholders, problems) = validate_command(&content);
+            commands.push(CommandInfo {
+                name,
+                path,
+                front_matter,
+                placeholders,
+                problems,
+            });
+        }
+    }
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(commands)
+}
+
+/// Write `.claude/commands/<name>.md` from a template
+///
+/// # Returns
+///
+/// Returns the path of the written command file
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` for a bad name, an unknown template, a result
+/// that wouldn't validate, or an existing command without `force`
+pub fn create_command(target_dir: &Path, command: &NewCommand) -> Result<PathBuf> {
+    validate_kebab_name("command", &command.name)?;
+    let template_name = command
+        .template
+        .as_deref()
+        .unwrap_or(DEFAULT_COMMAND_TEMPLATE);
+    let template = find_template(template_name).ok_or_else(|| {
+        CatalystError::InvalidConfig(format!(
+            "Unknown command template '{}'. Available: {}",
+            template_name,
+            COMMAND_TEMPLATES
+                .iter()
+                .map(|template| template.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    })?;
+
+    let mut vars = template::project_vars(target_dir, &CatalystConfig::load(target_dir)?);
+    vars.extend([
+        ("name".to_string(), command.name.clone()),
+        (
+            "description".to_string(),
+            command
+                .description
+                .as_deref()
+                .unwrap_or(template.description)
+                .replace('\n', " "),
+        ),
+        (
+            "argument_hint".to_string(),
+            command
+                .argument_hint
+                .as_deref()
+                .unwrap_or(template.argument_hint)
+                .replace('\n', " "),
+        ),
+    ]);
+    let content = template::render(template.content, &vars)?;
+    let (_, _, problems) = validate_command(&content);
+    if !problems.is_empty() {
+        return Err(CatalystError::InvalidConfig(format!(
+            "Command '{}' would be invalid: {}",
+            command.name,
+            problems.join("; ")
+        )));
+    }
+
+    let dir = target_dir.join(COMMANDS_DIR);
+    let path = dir.join(format!("{}.md", command.name));
+    if path.exists() && !command.force {
+        return Err(CatalystError::InvalidConfig(format!(
+            "Command '{}' already exists at {}\nUse --force to overwrite.",
+            command.name,
+            path.display()
+        )));
+    }
+    fs::create_dir_all(&dir).map_err(|source| CatalystError::DirectoryCreationFailed {
+        path: dir.clone(),
+        source,
+    })?;
+    write_file_atomic(&path, &content)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_command_placeholders() {
+        let (front_matter, placeholders, problems) = validate_command(
+            "---\ndescription: Fix an issue\nargument-hint: [issue] [priority]\nallowed-tools: Bash(gh issue view:*), Bash(git log:*, git diff:*), Read\n---\n\n!`gh issue view $1`\nFix #$1 at priority $2.\n",
+        );
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(placeholders, vec!["$1", "$2"]);
+        assert_eq!(front_matter.unwrap().allowed_tools.len(), 3);
+
+        // Shell lines don't count as arguments
+        let (_, placeholders, problems) =
+            validate_command("Summarize:\n!awk '{print $2}' \\\n  log.txt\n");
+        assert!(
+            placeholders.is_empty() && problems.is_empty(),
+            "{:?}",
+            problems
+        );
+
+        let (_, _, problems) = validate_command(
+            "---\nargument-hint: [one]\nallowed-tools: Read\nmodel: gpt\ncolour: red\n---\nUse $1 and $3 !`ls`\n",
+        );
+        let joined = problems.join("\n");
+        for expected in [
+            "unknown field 'colour'",
+            "unknown model 'gpt'",
+            "uses $3 but never $2",
+            "describes 1 argument(s)",
+            "doesn't include Bash",
+        ] {
+            assert!(
+                joined.contains(expected),
+                "missing {:?} in {}",
+                expected,
+                joined
+            );
+        }
+        assert!(validate_command("---\ndescription: x\n").0.is_none());
+        assert!(validate_command("$1 only")
+            .2
+            .iter()
+            .any(|p| p.contains("no 'argument-hint'")));
+    }
+
+    #[test]
+    fn test_new_and_list_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        for template in COMMAND_TEMPLATES {
+            let command = NewCommand {
+                name: format!("my-{}", template.name),
+                template: Some(template.name.to_string()),
+                ..NewCommand::default()
+            };
+            create_command(project, &command).unwrap();
+        }
+        let custom = NewCommand {
+            name: "deploy".to_string(),
+            description: Some("Deploy to staging".to_string()),
+            ..NewCommand::default()
+        };
+        let path = create_command(project, &custom).unwrap();
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("description: Deploy to staging\n"));
+        assert!(create_command(project, &custom).is_err());
+        assert!(create_command(
+            project,
+            &NewCommand {
+                name: "../escape".to_string(),
+                ..NewCommand::default()
+            }
+        )
+        .is_err());
+
+        let nested = project.join(COMMANDS_DIR).join("frontend");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("component.md"), "Create a component: $1").unwrap();
+
+        let commands = list_commands(project).unwrap();
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "deploy",
+                "frontend/component",
+                "my-basic",
+                "my-fix-issue",
+                "my-review"
+            ]
+        );
+        for command in &commands {
+            assert_eq!(
+                command.problems.is_empty(),
+                command.name != "frontend/component",
+                "{}: {:?}",
+                command.name,
+                command.problems
+            );
+        }
+    }
+}
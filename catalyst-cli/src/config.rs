@@ -0,0 +1,778 @@
+//! Per-project configuration (`catalyst.toml`)
+//!
+//! Teams can commit a `catalyst.toml` (at the project root, or inside
+//! `.claude/`) so everyone initializes and updates the project the same way:
+//!
+//! ```toml
+//! [init]
+//! skills = ["skill-developer", "rust-developer"]
+//! install_hooks = true
+//! install_tracker = false
+//! install_check_hook = true   # default: when Cargo.toml, package.json, pyproject.toml, or go.mod exists
+//! skill_source = "library"   # link skills from ~/.claude/skills-library
+//! strict_skills = "finish"    # fail init if a skill can't be installed ("abort" rolls back)
+//!
+//! [wrappers]
+//! shell = "sh"   # or "ps1"; defaults to the detected platform
+//! path = ["/home/me/.cargo/bin"]   # prepended to PATH inside each wrapper
+//! powershell = "pwsh"   # or "powershell"; runs .ps1 wrappers (default: detected)
+//!
+//! [flags]
+//! enable = ["merge-engine"]   # see `catalyst flags list`
+//!
+//! [tracker]
+//! backend = "jsonl"   # where file-change-tracker records edits (default "sqlite")
+//!
+//! # Opt-in session summaries for a team endpoint (see the analytics module)
+//! [analytics]
+//! enabled = true
+//! endpoint = "https://metrics.example.com/v1/catalyst"
+//!
+//! # Where `catalyst skills search` and `skills add` find community skills
+//! [registry]
+//! url = "https://skills.example.com/index.json"
+//! trusted_keys = ["RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3"]   # minisign
+//!
+//! # External checks run by `catalyst status` (see the validators module)
+//! [[validators]]
+//! name = "license-headers"
+//! command = ["./scripts/check-licenses", "--json"]
+//! timeout_secs = 30
+//!
+//! # Values for `{{NAME}}` in templates (see the template module)
+//! [vars]
+//! TEAM = "platform"
+//! ```
+//!
+//! `init`, `update`, and `status` read this file, and `catalyst config`
+//! reads and writes individual keys.
+//!
+//! A user-wide `~/.claude/catalyst.toml` uses the same format. Only its
+//! `init.skills` is read, as the default skill set for projects whose own
+//! config doesn't name one.
+
+use crate::analytics::{self, ANALYTICS_TOKEN_ENV};
+use crate::flags;
+use crate::modification_store::TrackerBackend;
+use crate::registry;
+use crate::template;
+use crate::types::{
+    CatalystError, Platform, PowerShell, Result, SkillSelection, StrictSkills, CONFIG_FILE,
+    CONFIG_FILE_CLAUDE, DEFAULT_INIT_SKILLS,
+};
+use catalyst_core::fs_ops::{Fs, Mode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Every supported configuration key with a short description
+pub const KNOWN_KEYS: &[(&str, &str)] = &[
+    (
+        "init.skills",
+        "Skills installed by 'catalyst init' (list of skill IDs)",
+    ),
+    (
+        "init.install_hooks",
+        "Install the skill-activation-prompt hook",
+    ),
+    (
+        "init.install_tracker",
+        "Install the file-change-tracker hook",
+    ),
+    (
+        "init.install_check_hook",
+        "Install the cargo-check hook (default: when init detects a Rust, Node.js, Python, or Go project)",
+    ),
+    (
+        "init.skill_source",
+        "Where skills live: \"project\" (copied) or \"library\" (linked)",
+    ),
+    (
+        "init.strict_skills",
+        "Fail init when a skill can't be installed: \"off\", \"finish\", or \"abort\"",
+    ),
+    ("wrappers.shell", "Wrapper script flavor: \"sh\" or \"ps1\""),
+    (
+        "wrappers.path",
+        "Absolute directories wrappers add to PATH (see 'catalyst doctor --hook-env')",
+    ),
+    (
+        "wrappers.powershell",
+        "PowerShell host for .ps1 wrappers: \"pwsh\" or \"powershell\"",
+    ),
+    (
+        "flags.enable",
+        "Feature flags to enable (see 'catalyst flags list')",
+    ),
+    (
+        "tracker.backend",
+        "Where file-change-tracker records edits: \"sqlite\" or \"jsonl\"",
+    ),
+    (
+        "analytics.enabled",
+        "Send session summaries to analytics.endpoint",
+    ),
+    (
+        "analytics.endpoint",
+        "HTTPS URL session summaries are POSTed to",
+    ),
+    (
+        "analytics.token_env",
+        "Environment variable holding the endpoint's bearer token",
+    ),
+    (
+        "analytics.include_paths",
+        "Include edited file paths in session summaries",
+    ),
+    ("analytics.batch_size", "Session summaries per request"),
+    (
+        "analytics.interval_minutes",
+        "Minutes between automatic pushes (0 for 'catalyst sessions push' only)",
+    ),
+    (
+        "registry.url",
+        "Skill registry index for 'catalyst skills search' and 'skills add' (https:// or file://)",
+    ),
+    (
+        "registry.trusted_keys",
+        "Minisign public keys whose signatures 'catalyst skills add' accepts",
+    ),
+    (
+        "vars.<NAME>",
+        "Template variable for wrappers, skill templates, agents, and commands",
+    ),
+];
+
+/// Project configuration loaded from `catalyst.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CatalystConfig {
+    pub init: InitSettings,
+    pub wrappers: WrapperSettings,
+    pub flags: FlagSettings,
+    pub tracker: TrackerSettings,
+    pub analytics: AnalyticsSettings,
+    pub registry: RegistrySettings,
+
+    /// External validators run by `catalyst status`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validators: Vec<ValidatorConfig>,
+
+    /// `[vars]` values available to wrapper, skill, agent, and command
+    /// templates (see [`crate::template`])
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub vars: BTreeMap<String, String>,
+}
+
+/// `[init]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InitSettings {
+    /// Skills to install; None means the CLI default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skills: Option<Vec<String>>,
+
+    /// Whether to install the skill-activation-prompt hook
+    pub install_hooks: bool,
+
+    /// Whether to install the file-change-tracker hook
+    pub install_tracker: bool,
+
+    /// Whether to install the cargo-check hook; None means when a known
+    /// project type is detected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_check_hook: Option<bool>,
+
+    /// Whether skills are copied into the project or linked from the library
+    pub skill_source: SkillSource,
+
+    /// Whether a skill that fails to install fails init
+    pub strict_skills: StrictSkills,
+}
+
+impl Default for InitSettings {
+    fn default() -> Self {
+        Self {
+            skills: None,
+            install_hooks: true,
+            install_tracker: true,
+            install_check_hook: None,
+            skill_source: SkillSource::Project,
+            strict_skills: StrictSkills::Off,
+        }
+    }
+}
+
+/// Where a project's skills live
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillSource {
+    /// Copied into .claude/skills
+    #[default]
+    Project,
+    /// Linked from the machine-wide skill library
+    Library,
+}
+
+/// `[wrappers]` section
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WrapperSettings {
+    /// Wrapper flavor override; None means detect from the platform
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<WrapperShell>,
+
+    /// Directories prepended to PATH before the wrapper runs anything
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<String>,
+
+    /// PowerShell host override for .ps1 wrappers; None means detect
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub powershell: Option<PowerShell>,
+}
+
+/// `[flags]` section
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FlagSettings {
+    /// Feature flags enabled for this project
+    pub enable: Vec<String>,
+}
+
+/// `[tracker]` section
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrackerSettings {
+    /// Store file-change-tracker writes to
+    pub backend: TrackerBackend,
+}
+
+/// `[analytics]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnalyticsSettings {
+    /// Nothing is sent unless this is set
+    pub enabled: bool,
+
+    /// Where summaries are POSTed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+
+    /// Environment variable holding the bearer token
+    pub token_env: String,
+
+    /// Whether summaries list the edited files
+    pub include_paths: bool,
+
+    /// Session summaries per request
+    pub batch_size: usize,
+
+    /// Minutes between pushes started by file-change-tracker; 0 turns them off
+    pub interval_minutes: u64,
+}
+
+impl Default for AnalyticsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: None,
+            token_env: ANALYTICS_TOKEN_ENV.to_string(),
+            include_paths: false,
+            batch_size: 50,
+            interval_minutes: 60,
+        }
+    }
+}
+
+/// `[registry]` section
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RegistrySettings {
+    /// Index URL; the built-in registry when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Minisign public keys (base64) trusted to sign registry skills
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub trusted_keys: Vec<String>,
+}
+
+/// A `[[validators]]` entry: an external command whose JSON output adds
+/// issues to `catalyst status`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValidatorConfig {
+    /// Name shown as the component of the issues it reports
+    pub name: String,
+
+    /// Program and arguments, run from the project directory without a shell
+    pub command: Vec<String>,
+
+    /// Seconds before the validator is killed
+    #[serde(default = "default_validator_timeout")]
+    pub timeout_secs: u64,
+}
+
+fn default_validator_timeout() -> u64 {
+    30
+}
+
+/// Wrapper script flavor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WrapperShell {
+    /// POSIX shell wrappers (.sh)
+    Sh,
+    /// PowerShell wrappers (.ps1)
+    Ps1,
+}
+
+impl CatalystConfig {
+    /// Find the config file for a project
+    ///
+    /// `catalyst.toml` at the project root takes precedence over
+    /// `.claude/catalyst.toml`.
+    pub fn find(target_dir: &Path) -> Option<PathBuf> {
+        Self::find_with(&Fs::read_only(), target_dir)
+    }
+
+    fn find_with<M: Mode>(fs: &Fs<M>, target_dir: &Path) -> Option<PathBuf> {
+        [CONFIG_FILE, CONFIG_FILE_CLAUDE]
+            .iter()
+            .map(|name| target_dir.join(name))
+            .find(|path| fs.is_file(path))
+    }
+
+    /// Load the project configuration, or defaults if there is no config file
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileReadFailed` if the file can't be read, or `InvalidConfig`
+    /// if it isn't valid TOML or contains unknown keys.
+    pub fn load(target_dir: &Path) -> Result<Self> {
+        Self::read(&Fs::read_only(), target_dir)
+    }
+
+    /// Load the project configuration through a filesystem handle
+    ///
+    /// # Errors
+    ///
+    /// Same as [`CatalystConfig::load`]
+    pub fn read<M: Mode>(fs: &Fs<M>, target_dir: &Path) -> Result<Self> {
+        match Self::find_with(fs, target_dir) {
+            Some(path) => {
+                let content =
+                    fs.read_to_string(&path)
+                        .map_err(|e| CatalystError::FileReadFailed {
+                            path: path.clone(),
+                            source: e,
+                        })?;
+                Self::parse_file(&path, &content)
+            }
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Load the user-wide config, or None if there isn't one
+    ///
+    /// # Errors
+    ///
+    /// Same as [`CatalystConfig::load_from`]
+    pub fn load_user() -> Result<Option<Self>> {
+        match user_config_path().filter(|path| path.is_file()) {
+            Some(path) => Self::load_from(&path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Load configuration from a specific file
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| CatalystError::FileReadFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Self::parse_file(path, &content)
+    }
+
+    /// Parse a config file's content, naming the file in errors
+    fn parse_file(path: &Path, content: &str) -> Result<Self> {
+        Self::parse(content).map_err(|e| match e {
+            CatalystError::InvalidConfig(msg) => {
+                CatalystError::InvalidConfig(format!("{}: {}", path.display(), msg))
+            }
+            other => other,
+        })
+    }
+
+    /// Parse and validate config file contents
+    fn parse(content: &str) -> Result<Self> {
+        let config: Self =
+            toml::from_str(content).map_err(|e| CatalystError::InvalidConfig(e.to_string()))?;
+        flags::validate_flag_names(&config.flags.enable)?;
+        for dir in &config.wrappers.path {
+            validate_wrapper_path(dir)?;
+        }
+        for name in config.vars.keys() {
+            template::validate_var_name(name)?;
+        }
+        for validator in &config.validators {
+            if validator.name.trim().is_empty() || validator.command.is_empty() {
+                return Err(CatalystError::InvalidConfig(
+                    "Each [[validators]] entry needs a name and a non-empty command".to_string(),
+                ));
+            }
+        }
+        if let Some(url) = &config.registry.url {
+            registry::validate_registry_url(url)?;
+        }
+        for key in &config.registry.trusted_keys {
+            minisign_verify::PublicKey::from_base64(key).map_err(|e| {
+                CatalystError::InvalidConfig(format!(
+                    "registry.trusted_keys: '{}' is not a minisign public key: {}",
+                    key, e
+                ))
+            })?;
+        }
+        if let Some(endpoint) = &config.analytics.endpoint {
+            analytics::validate_endpoint(endpoint)?;
+        } else if config.analytics.enabled {
+            return Err(CatalystError::InvalidConfig(
+                "analytics.enabled needs analytics.endpoint".to_string(),
+            ));
+        }
+        Ok(config)
+    }
+
+    /// Platform whose wrapper flavor should be generated
+    ///
+    /// Honors `wrappers.shell` if set, otherwise returns `detected`.
+    pub fn wrapper_platform(&self, detected: Platform) -> Platform {
+        match self.wrappers.shell {
+            Some(WrapperShell::Ps1) => Platform::Windows,
+            Some(WrapperShell::Sh) if detected == Platform::Windows => Platform::Linux,
+            _ => detected,
+        }
+    }
+
+    /// Get an effective value by dotted key (defaults included)
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfig` for unknown keys
+    pub fn get(&self, key: &str) -> Result<Option<toml::Value>> {
+        validate_key(key)?;
+        let root =
+            toml::Value::try_from(self).map_err(|e| CatalystError::InvalidConfig(e.to_string()))?;
+        Ok(lookup(&root, key).cloned())
+    }
+}
+
+/// Set a key in the project's config file, creating the file if needed
+///
+/// `raw_value` is parsed as a TOML value (`true`, `["a", "b"]`), falling back
+/// to a plain string. The result is validated before anything is written.
+///
+/// # Returns
+///
+/// Returns the path of the config file that was written
+pub fn set_value(target_dir: &Path, key: &str, raw_value: &str) -> Result<PathBuf> {
+    validate_key(key)?;
+    let value = parse_value(raw_value);
+    edit_config(target_dir, |table| {
+        let (section, name) = split_key(key);
+        let entry = table
+            .entry(section.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        match entry {
+            toml::Value::Table(section_table) => {
+                section_table.insert(name.to_string(), value);
+                Ok(())
+            }
+            _ => Err(CatalystError::InvalidConfig(format!(
+                "'{}' is not a table",
+                section
+            ))),
+        }
+    })
+}
+
+/// Remove a key from the project's config file
+///
+/// # Returns
+///
+/// Returns the path of the config file that was written
+pub fn unset_value(target_dir: &Path, key: &str) -> Result<PathBuf> {
+    validate_key(key)?;
+    edit_config(target_dir, |table| {
+        let (section, name) = split_key(key);
+        let now_empty = match table.get_mut(section) {
+            Some(toml::Value::Table(section_table)) => {
+                section_table.remove(name);
+                section_table.is_empty()
+            }
+            _ => false,
+        };
+        if now_empty {
+            table.remove(section);
+        }
+        Ok(())
+    })
+}
+
+/// Read, modify, validate, and write back the raw config table
+fn edit_config<F>(target_dir: &Path, edit: F) -> Result<PathBuf>
+where
+    F: FnOnce(&mut toml::Table) -> Result<()>,
+{
+    let path = CatalystConfig::find(target_dir).unwrap_or_else(|| target_dir.join(CONFIG_FILE));
+
+    let mut table: toml::Table = match fs::read_to_string(&path) {
+        Ok(content) => toml::from_str(&content)
+            .map_err(|e| CatalystError::InvalidConfig(format!("{}: {}", path.display(), e)))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => toml::Table::new(),
+        Err(e) => return Err(CatalystError::FileReadFailed { path, source: e }),
+    };
+
+    edit(&mut table)?;
+
+    // Validate the result before writing it
+    let content =
+        toml::to_string_pretty(&table).map_err(|e| CatalystError::InvalidConfig(e.to_string()))?;
+    CatalystConfig::parse(&content)?;
+
+    fs::write(&path, content).map_err(|e| CatalystError::FileWriteFailed {
+        path: path.clone(),
+        source: e,
+    })?;
+
+    Ok(path)
+}
+
+/// Check a `wrappers.path` entry can be embedded in a wrapper script
+///
+/// Entries are pasted into a single-quoted PATH assignment, so they must be
+/// absolute (nothing expands `~` there) and free of quotes, newlines, and
+/// the PATH list separator.
+fn validate_wrapper_path(dir: &str) -> Result<()> {
+    let problem = if !Path::new(dir).is_absolute() {
+        Some("must be an absolute path")
+    } else if dir.contains(['\'', '\n', '\r']) {
+        Some("must not contain quotes or newlines")
+    } else if std::env::join_paths([dir]).is_err() {
+        Some("must not contain the PATH separator")
+    } else {
+        None
+    };
+    match problem {
+        Some(problem) => Err(CatalystError::InvalidConfig(format!(
+            "wrappers.path entry '{}' {}",
+            dir, problem
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Path of the user-wide config (`~/.claude/catalyst.toml`)
+pub fn user_config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(CONFIG_FILE_CLAUDE))
+}
+
+/// Skills init installs when none are picked explicitly
+///
+/// The project's `init.skills` wins, then the user config's, then
+/// [`DEFAULT_INIT_SKILLS`].
+///
+/// # Returns
+///
+/// Returns the skills and which of those sources they came from
+pub fn default_init_skills(
+    project: &CatalystConfig,
+    user: Option<&CatalystConfig>,
+) -> (Vec<String>, SkillSelection) {
+    if let Some(skills) = &project.init.skills {
+        return (skills.clone(), SkillSelection::ProjectConfig);
+    }
+    if let Some(skills) = user.and_then(|user| user.init.skills.as_ref()) {
+        return (skills.clone(), SkillSelection::UserConfig);
+    }
+    (
+        DEFAULT_INIT_SKILLS.iter().map(|s| s.to_string()).collect(),
+        SkillSelection::BuiltIn,
+    )
+}
+
+/// Reject keys that aren't in [`KNOWN_KEYS`] or a `vars.<NAME>` entry,
+/// suggesting the closest match
+fn validate_key(key: &str) -> Result<()> {
+    if KNOWN_KEYS.iter().any(|(known, _)| *known == key) {
+        return Ok(());
+    }
+    if let Some(name) = key.strip_prefix("vars.") {
+        return template::validate_var_name(name);
+    }
+
+    let names: Vec<&str> = KNOWN_KEYS.iter().map(|(name, _)| *name).collect();
+    let mut msg = format!("Unknown configuration key '{}'", key);
+    if let Some(suggestion) = catalyst_core::settings::find_closest_match(key, &names) {
+        msg.push_str(&format!(". Did you mean '{}'?", suggestion));
+    }
+    Err(CatalystError::InvalidConfig(msg))
+}
+
+/// Split "section.name" (keys are validated, so a dot is always present)
+fn split_key(key: &str) -> (&str, &str) {
+    key.split_once('.').unwrap_or(("", key))
+}
+
+/// Look up a dotted key in a TOML value
+fn lookup<'a>(root: &'a toml::Value, key: &str) -> Option<&'a toml::Value> {
+    key.split('.').try_fold(root, |value, part| value.get(part))
+}
+
+/// Parse a command-line value as TOML, falling back to a string
+fn parse_value(raw: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_defaults_without_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = CatalystConfig::load(temp_dir.path()).unwrap();
+        assert_eq!(config, CatalystConfig::default());
+        assert!(config.init.install_tracker);
+    }
+
+    #[test]
+    fn test_root_file_takes_precedence() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(".claude")).unwrap();
+        fs::write(
+            target.join(".claude/catalyst.toml"),
+            "[init]\ninstall_tracker = true\n",
+        )
+        .unwrap();
+        fs::write(
+            target.join("catalyst.toml"),
+            "[init]\ninstall_tracker = false\n",
+        )
+        .unwrap();
+
+        let config = CatalystConfig::load(target).unwrap();
+        assert!(!config.init.install_tracker);
+    }
+
+    #[test]
+    fn test_default_init_skills_precedence() {
+        let user = CatalystConfig::parse("[init]\nskills = [\"rust-developer\"]\n").unwrap();
+        let project = CatalystConfig::parse("[init]\nskills = [\"route-tester\"]\n").unwrap();
+        let unset = CatalystConfig::default();
+
+        assert_eq!(
+            default_init_skills(&project, Some(&user)),
+            (
+                vec!["route-tester".to_string()],
+                SkillSelection::ProjectConfig
+            )
+        );
+        assert_eq!(
+            default_init_skills(&unset, Some(&user)),
+            (
+                vec!["rust-developer".to_string()],
+                SkillSelection::UserConfig
+            )
+        );
+        assert_eq!(
+            default_init_skills(&unset, Some(&unset)),
+            (vec!["skill-developer".to_string()], SkillSelection::BuiltIn)
+        );
+        // An explicitly empty list is a choice, not a fallthrough
+        let empty = CatalystConfig::parse("[init]\nskills = []\n").unwrap();
+        assert_eq!(
+            default_init_skills(&empty, Some(&user)),
+            (Vec::new(), SkillSelection::ProjectConfig)
+        );
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("catalyst.toml"),
+            "[init]\ninstall_trackr = false\n",
+        )
+        .unwrap();
+
+        let result = CatalystConfig::load(temp_dir.path());
+        assert!(matches!(result, Err(CatalystError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_load_validators() {
+        let config = CatalystConfig::parse(
+            "[[validators]]\nname = \"headers\"\ncommand = [\"./check\", \"--json\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.validators.len(), 1);
+        assert_eq!(config.validators[0].command, vec!["./check", "--json"]);
+        assert_eq!(config.validators[0].timeout_secs, 30);
+
+        let result = CatalystConfig::parse("[[validators]]\nname = \"empty\"\ncommand = []\n");
+        assert!(matches!(result, Err(CatalystError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_set_get_unset_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+
+        let path = set_value(target, "init.skills", r#"["rust-developer"]"#).unwrap();
+        assert_eq!(path, target.join(CONFIG_FILE));
+        set_value(target, "wrappers.shell", "ps1").unwrap();
+        set_value(target, "wrappers.powershell", "pwsh").unwrap();
+
+        let config = CatalystConfig::load(target).unwrap();
+        assert_eq!(config.init.skills, Some(vec!["rust-developer".to_string()]));
+        assert_eq!(config.wrappers.powershell, Some(PowerShell::Pwsh));
+        assert_eq!(config.wrapper_platform(Platform::Linux), Platform::Windows);
+        assert_eq!(
+            config.get("wrappers.shell").unwrap(),
+            Some(toml::Value::String("ps1".to_string()))
+        );
+
+        unset_value(target, "wrappers.shell").unwrap();
+        let config = CatalystConfig::load(target).unwrap();
+        assert_eq!(config.wrappers.shell, None);
+        assert_eq!(
+            config.get("init.install_hooks").unwrap(),
+            Some(toml::Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_set_rejects_invalid_values_and_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+
+        assert!(set_value(target, "wrappers.shell", "zsh").is_err());
+        assert!(set_value(target, "wrappers.powershell", "cmd").is_err());
+        assert!(set_value(target, "init.install_tracker", "maybe").is_err());
+        match set_value(target, "init.skill", "x") {
+            Err(CatalystError::InvalidConfig(msg)) => assert!(msg.contains("init.skills")),
+            other => panic!("Expected InvalidConfig, got {:?}", other),
+        }
+        assert!(set_value(target, "flags.enable", r#"["no-such-flag"]"#).is_err());
+        assert!(set_value(target, "wrappers.path", r#"["~/.cargo/bin"]"#).is_err());
+        // Nothing written after failed validation
+        assert!(!target.join(CONFIG_FILE).exists());
+    }
+}
@@ -0,0 +1,193 @@
+//! Repeat-suggestion guard for skill-activation-prompt
+//!
+//! A multi-part prompt pasted in quick succession would otherwise print the
+//! same skill banner for every part. The hook records the last suggestion
+//! set for each session in a small timestamp file; when the same skills
+//! come up again within the window, it prints a one-line reminder instead.
+//!
+//! The window defaults to [`DEFAULT_DEBOUNCE_SECS`] and is set with
+//! `CATALYST_ACTIVATION_DEBOUNCE_SECS` (`0` turns the guard off).
+
+use crate::types::{CatalystError, Result, CLAUDE_DIR, TRACKER_STATE_DIR};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Seconds within which an identical suggestion set is collapsed
+pub const DEFAULT_DEBOUNCE_SECS: u64 = 30;
+
+/// Environment variable overriding the window, in seconds
+pub const DEBOUNCE_ENV: &str = "CATALYST_ACTIVATION_DEBOUNCE_SECS";
+
+/// Directory under the hook state directory holding per-session records
+pub const DEBOUNCE_DIR: &str = "activation-debounce";
+
+/// Records older than this are removed when another is written
+const STALE_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Last suggestion set shown in a session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct LastSuggestion {
+    /// Seconds since the epoch
+    timestamp: u64,
+    /// Suggested skill names, sorted
+    skills: Vec<String>,
+}
+
+/// Window from the environment, or None if the guard is turned off
+pub fn window() -> Option<Duration> {
+    let secs = std::env::var(DEBOUNCE_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_DEBOUNCE_SECS);
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+/// Default record directory, `~/.claude/hooks-state-rust/activation-debounce`
+pub fn state_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| {
+        home.join(CLAUDE_DIR)
+            .join(TRACKER_STATE_DIR)
+            .join(DEBOUNCE_DIR)
+    })
+}
+
+/// Record a suggestion set and report whether it repeats the last one
+///
+/// # Arguments
+///
+/// * `dir` - Directory holding the per-session records
+/// * `session_id` - Session the prompt belongs to
+/// * `skills` - Skills being suggested, in any order
+/// * `now` - Time of this prompt
+/// * `window` - How recent the previous identical set must be
+///
+/// # Returns
+///
+/// Returns true if the session was shown the same skills less than
+/// `window` ago. The record is refreshed either way, so a burst of prompts
+/// keeps collapsing until there is a pause.
+///
+/// # Errors
+///
+/// Returns an error if the record can't be written; callers should then
+/// show the full suggestion
+pub fn check_and_record(
+    dir: &Path,
+    session_id: &str,
+    skills: &[String],
+    now: SystemTime,
+    window: Duration,
+) -> Result<bool> {
+    let mut sorted = skills.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    let timestamp = now
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let path = record_path(dir, session_id);
+    let repeat = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<LastSuggestion>(&content).ok())
+        .is_some_and(|last| {
+            last.skills == sorted && timestamp.saturating_sub(last.timestamp) < window.as_secs()
+        });
+
+    fs::create_dir_all(dir).map_err(|e| CatalystError::DirectoryCreationFailed {
+        path: dir.to_path_buf(),
+        source: e,
+    })?;
+    remove_stale(dir, now);
+    let record = LastSuggestion {
+        timestamp,
+        skills: sorted,
+    };
+    let content = serde_json::to_string(&record).map_err(CatalystError::Json)?;
+    crate::init::write_file_atomic(&path, &content)?;
+
+    Ok(repeat)
+}
+
+/// Record file for a session; session IDs are hashed so any ID is a safe
+/// file name
+fn record_path(dir: &Path, session_id: &str) -> PathBuf {
+    let digest = Sha256::digest(session_id.as_bytes());
+    let name: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    dir.join(format!("{}.json", name))
+}
+
+/// Remove records of sessions that ended long ago
+fn remove_stale(dir: &Path, now: SystemTime) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let stale = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age > STALE_AFTER);
+        if stale {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn skills(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_repeat_within_window_is_collapsed() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let window = Duration::from_secs(30);
+        let t0 = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let at = |secs| t0 + Duration::from_secs(secs);
+
+        let set = skills(&["backend", "api"]);
+        assert!(!check_and_record(dir, "s1", &set, t0, window).unwrap());
+        // Same skills in another order, 10s later
+        assert!(check_and_record(dir, "s1", &skills(&["api", "backend"]), at(10), window).unwrap());
+        // The burst keeps collapsing while prompts stay within the window
+        assert!(check_and_record(dir, "s1", &set, at(35), window).unwrap());
+        // Other sessions are tracked separately
+        assert!(!check_and_record(dir, "s2", &set, at(36), window).unwrap());
+    }
+
+    #[test]
+    fn test_changed_set_or_pause_shows_full_suggestion() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let window = Duration::from_secs(30);
+        let t0 = UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        assert!(!check_and_record(dir, "s1", &skills(&["backend"]), t0, window).unwrap());
+        assert!(!check_and_record(
+            dir,
+            "s1",
+            &skills(&["backend", "api"]),
+            t0 + Duration::from_secs(5),
+            window
+        )
+        .unwrap());
+        assert!(!check_and_record(
+            dir,
+            "s1",
+            &skills(&["backend", "api"]),
+            t0 + Duration::from_secs(60),
+            window
+        )
+        .unwrap());
+    }
+}
@@ -0,0 +1,295 @@
+//! Deprecated and renamed skills
+//!
+//! When a shipped skill is retired or renamed it moves from
+//! [`AVAILABLE_SKILLS`](crate::types::AVAILABLE_SKILLS) to
+//! [`DEPRECATED_SKILLS`]. `catalyst status` warns about deprecated skills
+//! that are still installed, and `catalyst update` offers to migrate
+//! renamed ones: the skill directory is renamed in place, so local edits
+//! survive, and its skill-rules.json entry and hashes follow it. The normal
+//! update then refreshes the skill under its new name if it wasn't edited.
+
+use crate::init::write_file_atomic;
+use crate::library;
+use crate::scaffold::read_rules;
+use crate::types::{
+    CatalystError, CatalystHashes, DeprecatedSkill, Result, DEPRECATED_SKILLS, HASHES_FILE,
+    SKILLS_DIR, SKILL_RULES_FILE,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A renamed skill that was moved to its new ID
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkillMigration {
+    pub from: String,
+    pub to: String,
+}
+
+/// Deprecation entry for a skill ID, if the skill was deprecated
+pub fn lookup(skill_id: &str) -> Option<&'static DeprecatedSkill> {
+    DEPRECATED_SKILLS.iter().find(|skill| skill.id == skill_id)
+}
+
+/// Deprecated skills from `table` that are installed in the project
+pub fn installed_deprecated_skills<'a>(
+    target_dir: &Path,
+    table: &'a [DeprecatedSkill],
+) -> Vec<&'a DeprecatedSkill> {
+    let skills_dir = target_dir.join(SKILLS_DIR);
+    table
+        .iter()
+        .filter(|skill| skills_dir.join(skill.id).is_dir())
+        .collect()
+}
+
+/// Move an installed renamed skill to its new ID
+///
+/// Renames `.claude/skills/<old>` to `.claude/skills/<new>`, then rewrites
+/// the skill-rules.json entry and both hash files. If rewriting fails the
+/// directory is moved back.
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the skill has no replacement, is linked from
+/// the skill library, or the new ID is already installed; `PathNotFound` if
+/// the old skill isn't installed; and read or write errors otherwise
+pub fn migrate_skill(target_dir: &Path, skill: &DeprecatedSkill) -> Result<SkillMigration> {
+    let new_id = skill.replaced_by.ok_or_else(|| {
+        CatalystError::InvalidConfig(format!(
+            "Skill '{}' was retired without a replacement",
+            skill.id
+        ))
+    })?;
+
+    let skills_dir = target_dir.join(SKILLS_DIR);
+    let old_dir = skills_dir.join(skill.id);
+    let new_dir = skills_dir.join(new_id);
+    if !old_dir.is_dir() {
+        return Err(CatalystError::PathNotFound(old_dir));
+    }
+    if library::is_linked(target_dir, skill.id) {
+        return Err(CatalystError::InvalidConfig(format!(
+            "Skill '{}' is linked from the skill library; link '{}' instead with: \
+             catalyst init --library",
+            skill.id, new_id
+        )));
+    }
+    if new_dir.exists() {
+        return Err(CatalystError::InvalidConfig(format!(
+            "Can't rename skill '{}': {} already exists",
+            skill.id,
+            new_dir.display()
+        )));
+    }
+
+    // Parse everything that will be rewritten before moving anything
+    let rules_path = target_dir.join(SKILL_RULES_FILE);
+    let rules = if rules_path.is_file() {
+        Some(read_rules(&rules_path)?)
+    } else {
+        None
+    };
+
+    fs::rename(&old_dir, &new_dir).map_err(CatalystError::Io)?;
+    let rewritten = rewrite_rules(&rules_path, rules, skill.id, new_id)
+        .and_then(|()| rename_hashes(target_dir, skill.id, new_id));
+    if let Err(e) = rewritten {
+        let _ = fs::rename(&new_dir, &old_dir);
+        return Err(e);
+    }
+
+    Ok(SkillMigration {
+        from: skill.id.to_string(),
+        to: new_id.to_string(),
+    })
+}
+
+/// Move the skill-rules.json entry for `old_id` to `new_id`
+///
+/// The entry keeps the user's settings. If the rules already have an entry
+/// for the new ID, that one wins and the old entry is dropped.
+fn rewrite_rules(
+    rules_path: &Path,
+    rules: Option<serde_json::Value>,
+    old_id: &str,
+    new_id: &str,
+) -> Result<()> {
+    let Some(mut rules) = rules else {
+        return Ok(());
+    };
+    let Some(skills) = rules
+        .get_mut("skills")
+        .and_then(|skills| skills.as_object_mut())
+    else {
+        return Ok(());
+    };
+    let Some(entry) = skills.remove(old_id) else {
+        return Ok(());
+    };
+    skills.entry(new_id).or_insert(entry);
+
+    let content = serde_json::to_string_pretty(&rules).map_err(CatalystError::Json)?;
+    write_file_atomic(rules_path, &content)?;
+    Ok(())
+}
+
+/// Re-key the skill's entries in both hash files
+///
+/// `update` keeps per-skill hashes at the project root; `init` keeps
+/// per-file hashes under .claude/skills. Missing files are left alone.
+fn rename_hashes(target_dir: &Path, old_id: &str, new_id: &str) -> Result<()> {
+    let update_hashes = target_dir.join(HASHES_FILE);
+    if let Some(content) = read_optional(&update_hashes)? {
+        let mut hashes: CatalystHashes = serde_json::from_str(&content).map_err(|e| {
+            CatalystError::InvalidConfig(format!("{}: {}", update_hashes.display(), e))
+        })?;
+        if let Some(hash) = hashes.skills.remove(old_id) {
+            hashes.skills.insert(new_id.to_string(), hash);
+            let content = serde_json::to_string_pretty(&hashes).map_err(CatalystError::Json)?;
+            write_file_atomic(&update_hashes, &content)?;
+        }
+    }
+
+    let file_hashes = target_dir.join(SKILLS_DIR).join(HASHES_FILE);
+    if let Some(content) = read_optional(&file_hashes)? {
+        let hashes: HashMap<String, String> = serde_json::from_str(&content).map_err(|e| {
+            CatalystError::InvalidConfig(format!("{}: {}", file_hashes.display(), e))
+        })?;
+        let renamed: HashMap<String, String> = hashes
+            .into_iter()
+            .map(|(path, hash)| match Path::new(&path).strip_prefix(old_id) {
+                Ok(rest) => (
+                    PathBuf::from(new_id)
+                        .join(rest)
+                        .to_string_lossy()
+                        .to_string(),
+                    hash,
+                ),
+                Err(_) => (path, hash),
+            })
+            .collect();
+        let content = serde_json::to_string_pretty(&renamed).map_err(CatalystError::Json)?;
+        write_file_atomic(&file_hashes, &content)?;
+    }
+    Ok(())
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(source) => Err(CatalystError::FileReadFailed {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AVAILABLE_SKILLS;
+    use tempfile::TempDir;
+
+    const RENAMED: DeprecatedSkill = DeprecatedSkill {
+        id: "old-skill",
+        replaced_by: Some("new-skill"),
+        since: "0.2.0",
+        note: "Renamed",
+    };
+
+    #[test]
+    fn test_deprecated_table_is_consistent() {
+        for skill in DEPRECATED_SKILLS {
+            assert!(
+                !AVAILABLE_SKILLS.contains(&skill.id),
+                "{} still shipped",
+                skill.id
+            );
+            if let Some(new_id) = skill.replaced_by {
+                assert!(AVAILABLE_SKILLS.contains(&new_id), "{} not shipped", new_id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_migrate_skill_moves_directory_rules_and_hashes() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let skills_dir = target.join(SKILLS_DIR);
+        fs::create_dir_all(skills_dir.join("old-skill/resources")).unwrap();
+        fs::write(skills_dir.join("old-skill/SKILL.md"), "local edits").unwrap();
+        fs::write(
+            target.join(SKILL_RULES_FILE),
+            r#"{"version": "1.0", "skills": {"old-skill": {"keywords": ["mine"]}}}"#,
+        )
+        .unwrap();
+        let mut update_hashes = CatalystHashes::new("0.1.0".to_string());
+        update_hashes
+            .skills
+            .insert("old-skill".to_string(), "abc".to_string());
+        fs::write(
+            target.join(HASHES_FILE),
+            serde_json::to_string(&update_hashes).unwrap(),
+        )
+        .unwrap();
+        fs::write(
+            skills_dir.join(HASHES_FILE),
+            r#"{"old-skill/SKILL.md": "abc", "other/SKILL.md": "def"}"#,
+        )
+        .unwrap();
+
+        let found = installed_deprecated_skills(target, &[RENAMED]);
+        assert_eq!(found, vec![&RENAMED]);
+
+        let migration = migrate_skill(target, &RENAMED).unwrap();
+        assert_eq!(migration.to, "new-skill");
+        assert_eq!(
+            fs::read_to_string(skills_dir.join("new-skill/SKILL.md")).unwrap(),
+            "local edits"
+        );
+        assert!(!skills_dir.join("old-skill").exists());
+
+        let rules: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(target.join(SKILL_RULES_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(rules["skills"]["new-skill"]["keywords"][0], "mine");
+        assert!(rules["skills"].get("old-skill").is_none());
+
+        let update_hashes: CatalystHashes =
+            serde_json::from_str(&fs::read_to_string(target.join(HASHES_FILE)).unwrap()).unwrap();
+        assert_eq!(update_hashes.skills["new-skill"], "abc");
+        let file_hashes: HashMap<String, String> =
+            serde_json::from_str(&fs::read_to_string(skills_dir.join(HASHES_FILE)).unwrap())
+                .unwrap();
+        let new_key = Path::new("new-skill").join("SKILL.md");
+        assert_eq!(file_hashes[&new_key.to_string_lossy().to_string()], "abc");
+        assert_eq!(file_hashes["other/SKILL.md"], "def");
+
+        // Nothing left to migrate, and a second attempt says why
+        assert!(installed_deprecated_skills(target, &[RENAMED]).is_empty());
+        assert!(matches!(
+            migrate_skill(target, &RENAMED),
+            Err(CatalystError::PathNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_migrate_skill_refuses_existing_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path().join(SKILLS_DIR);
+        fs::create_dir_all(skills_dir.join("old-skill")).unwrap();
+        fs::create_dir_all(skills_dir.join("new-skill")).unwrap();
+
+        assert!(migrate_skill(temp_dir.path(), &RENAMED).is_err());
+        assert!(skills_dir.join("old-skill").is_dir());
+
+        let retired = DeprecatedSkill {
+            replaced_by: None,
+            ..RENAMED
+        };
+        assert!(migrate_skill(temp_dir.path(), &retired).is_err());
+    }
+}
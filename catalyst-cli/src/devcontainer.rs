@@ -0,0 +1,267 @@
+//! Dev container setup (`catalyst devcontainer generate`)
+//!
+//! Writes the files a dev container needs to install this Catalyst release
+//! and set up the project inside the container, then returns the
+//! devcontainer.json snippet that wires them in. Two forms are supported:
+//!
+//! - `post-create`: `.devcontainer/catalyst-setup.sh`, run from
+//!   `postCreateCommand`
+//! - `feature`: a local dev container feature in `.devcontainer/catalyst/`,
+//!   referenced from `features`
+//!
+//! Either way the container downloads the release archive for its own
+//! architecture, installs the hook binaries with `catalyst install-binaries`,
+//! and runs `catalyst init --profile ci` (or `catalyst status --fix` when
+//! the mounted workspace is already initialized), so the container matches
+//! the local setup. Regenerating after upgrading Catalyst keeps the pinned
+//! version in step.
+
+use crate::init::write_file_atomic;
+use crate::types::{CatalystError, Result, CATALYST_VERSION};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SETUP_TEMPLATE: &str = include_str!("../resources/devcontainer-setup.sh");
+const FEATURE_INSTALL_TEMPLATE: &str = include_str!("../resources/devcontainer-feature-install.sh");
+
+/// Directory dev container configuration lives in
+pub const DEVCONTAINER_DIR: &str = ".devcontainer";
+
+/// Setup script written for the `post-create` form
+pub const SETUP_SCRIPT: &str = "catalyst-setup.sh";
+
+/// Feature directory (under `.devcontainer/`) written for the `feature` form
+pub const FEATURE_DIR: &str = "catalyst";
+
+/// How the generated setup is hooked into devcontainer.json
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DevcontainerFormat {
+    /// A script run by `postCreateCommand`
+    #[default]
+    PostCreate,
+    /// A local dev container feature
+    Feature,
+}
+
+impl DevcontainerFormat {
+    /// Name as accepted by `--format`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DevcontainerFormat::PostCreate => "post-create",
+            DevcontainerFormat::Feature => "feature",
+        }
+    }
+}
+
+impl std::str::FromStr for DevcontainerFormat {
+    type Err = CatalystError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "post-create" | "postcreate" | "post-create-command" => {
+                Ok(DevcontainerFormat::PostCreate)
+            }
+            "feature" => Ok(DevcontainerFormat::Feature),
+            _ => Err(CatalystError::InvalidConfig(format!(
+                "Unknown devcontainer format '{}'. Expected post-create or feature",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+/// Files written by [`generate`] and how to reference them
+#[derive(Debug, Clone)]
+pub struct GeneratedDevcontainer {
+    /// Files that were written
+    pub files: Vec<PathBuf>,
+
+    /// devcontainer.json properties to merge in
+    pub snippet: serde_json::Value,
+}
+
+/// Base URL release downloads are fetched from
+fn releases_url() -> String {
+    format!("{}/releases/download", env!("CARGO_PKG_REPOSITORY"))
+}
+
+/// The setup script, pinned to `version`
+pub fn setup_script(version: &str) -> String {
+    SETUP_TEMPLATE
+        .replace("{{CATALYST_VERSION}}", version)
+        .replace("{{RELEASES_URL}}", &releases_url())
+}
+
+/// Write dev container setup files for a project
+///
+/// # Arguments
+///
+/// * `target_dir` - Project root; files go under its `.devcontainer/`
+/// * `format` - Whether to generate a postCreateCommand script or a feature
+///
+/// # Returns
+///
+/// Returns the files written and the devcontainer.json snippet that uses
+/// them. Existing generated files are overwritten so regenerating picks up
+/// the current release.
+///
+/// # Errors
+///
+/// Returns an error if `.devcontainer/` can't be created or a file can't be
+/// written
+pub fn generate(target_dir: &Path, format: DevcontainerFormat) -> Result<GeneratedDevcontainer> {
+    let devcontainer_dir = target_dir.join(DEVCONTAINER_DIR);
+    let setup = setup_script(CATALYST_VERSION);
+
+    let (files, snippet) = match format {
+        DevcontainerFormat::PostCreate => {
+            let script = devcontainer_dir.join(SETUP_SCRIPT);
+            write_generated(&script, &setup, true)?;
+            let snippet = json!({
+                "postCreateCommand": format!("bash {}/{}", DEVCONTAINER_DIR, SETUP_SCRIPT)
+            });
+            (vec![script], snippet)
+        }
+        DevcontainerFormat::Feature => {
+            let feature_dir = devcontainer_dir.join(FEATURE_DIR);
+            let manifest = json!({
+                "id": "catalyst",
+                "version": CATALYST_VERSION,
+                "name": "Catalyst",
+                "description": "Installs Catalyst and its Claude Code hooks, then sets up the workspace",
+                "documentationURL": env!("CARGO_PKG_REPOSITORY"),
+                "options": {
+                    "version": {
+                        "type": "string",
+                        "default": CATALYST_VERSION,
+                        "description": "Catalyst release to install"
+                    }
+                },
+                "postCreateCommand": "/usr/local/share/catalyst/setup.sh"
+            });
+            let manifest_content =
+                serde_json::to_string_pretty(&manifest).map_err(CatalystError::Json)?;
+
+            let files = vec![
+                feature_dir.join("devcontainer-feature.json"),
+                feature_dir.join("install.sh"),
+                feature_dir.join("setup.sh"),
+            ];
+            write_generated(&files[0], &format!("{}\n", manifest_content), false)?;
+            write_generated(
+                &files[1],
+                &FEATURE_INSTALL_TEMPLATE.replace("{{CATALYST_VERSION}}", CATALYST_VERSION),
+                true,
+            )?;
+            write_generated(&files[2], &setup, true)?;
+
+            let snippet = json!({
+                "features": {
+                    format!("./{}", FEATURE_DIR): { "version": CATALYST_VERSION }
+                }
+            });
+            (files, snippet)
+        }
+    };
+
+    Ok(GeneratedDevcontainer { files, snippet })
+}
+
+/// Write one generated file, creating its directory
+fn write_generated(path: &Path, content: &str, executable: bool) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| CatalystError::DirectoryCreationFailed {
+            path: parent.to_path_buf(),
+            source: e,
+        })?;
+    }
+    write_file_atomic(path, content)?;
+
+    #[cfg(unix)]
+    if executable {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(CatalystError::Io)?;
+    }
+    #[cfg(not(unix))]
+    let _ = executable;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generate_post_create_script() {
+        let temp_dir = TempDir::new().unwrap();
+        let generated = generate(temp_dir.path(), DevcontainerFormat::PostCreate).unwrap();
+
+        let script = temp_dir.path().join(".devcontainer/catalyst-setup.sh");
+        assert_eq!(generated.files, vec![script.clone()]);
+        assert_eq!(
+            generated.snippet["postCreateCommand"],
+            "bash .devcontainer/catalyst-setup.sh"
+        );
+
+        let content = fs::read_to_string(&script).unwrap();
+        assert!(!content.contains("{{"));
+        assert!(content.contains(&format!(
+            "CATALYST_VERSION=\"${{CATALYST_VERSION:-{}}}\"",
+            CATALYST_VERSION
+        )));
+        assert!(content.contains("install-binaries --from"));
+        assert!(content.contains("init --profile ci --no-scripts"));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&script).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+
+    #[test]
+    fn test_generate_feature() {
+        let temp_dir = TempDir::new().unwrap();
+        let generated = generate(temp_dir.path(), DevcontainerFormat::Feature).unwrap();
+        assert_eq!(generated.files.len(), 3);
+        assert!(generated.files.iter().all(|file| file.exists()));
+        assert_eq!(
+            generated.snippet["features"]["./catalyst"]["version"],
+            CATALYST_VERSION
+        );
+
+        let manifest: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(
+                temp_dir
+                    .path()
+                    .join(".devcontainer/catalyst/devcontainer-feature.json"),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(manifest["id"], "catalyst");
+        assert_eq!(manifest["options"]["version"]["default"], CATALYST_VERSION);
+
+        let install =
+            fs::read_to_string(temp_dir.path().join(".devcontainer/catalyst/install.sh")).unwrap();
+        assert!(install.contains(&format!("${{VERSION:-{}}}", CATALYST_VERSION)));
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!(
+            DevcontainerFormat::from_str("Feature").unwrap(),
+            DevcontainerFormat::Feature
+        );
+        assert_eq!(
+            DevcontainerFormat::from_str("post-create").unwrap(),
+            DevcontainerFormat::PostCreate
+        );
+        assert!(DevcontainerFormat::from_str("compose").is_err());
+    }
+}
@@ -0,0 +1,266 @@
+//! Effective environment report (`catalyst env`)
+//!
+//! Collects everything that influences how catalyst behaves in a project -
+//! the config file chain, environment variables, computed paths, platform
+//! detection, feature flags, and compiled-in features - into a single report that can be
+//! printed as text or JSON and pasted into a bug report.
+
+use crate::config::CatalystConfig;
+use crate::flags::{EnabledFlags, FlagStatus};
+use crate::library;
+use crate::types::{
+    Platform, BACKUP_DIR, CATALYST_VERSION, CLAUDE_DIR, CONFIG_FILE, CONFIG_FILE_CLAUDE, HOOKS_DIR,
+    SETTINGS_FILE, SKILLS_DIR, TRACKER_STATE_DIR, UPDATE_JOURNAL_FILE, VERSION_FILE,
+};
+use crate::validation::get_binary_directory;
+use serde::Serialize;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Environment variables that change catalyst's behavior, with their effect
+pub const ENV_VARS: &[(&str, &str)] = &[
+    ("NO_COLOR", "Disables colored output"),
+    (
+        "CATALYST_UNSTABLE",
+        "Enables the listed feature flags (comma-separated)",
+    ),
+    ("WSL_DISTRO_NAME", "Platform is detected as WSL"),
+    ("HOME", "Base for the binary and tracker state directories"),
+    (
+        "CATALYST_SKILLS_LIBRARY",
+        "Location of the machine-wide skill library",
+    ),
+    (
+        "CLAUDE_PROJECT_DIR",
+        "Project directory used by hooks to find skill rules",
+    ),
+    (
+        "CATALYST_HOOK_DIAGNOSTIC",
+        "Hook runs are left out of `catalyst stats hooks` (set by `doctor --hook-env`)",
+    ),
+    (
+        "CATALYST_MAX_PROMPT_BYTES",
+        "Prompt bytes the activation hook matches against (0 for no cap)",
+    ),
+    (
+        "CATALYST_MAX_ANALYZED_BYTES",
+        "File bytes the tracker hook analyzes (0 for no cap)",
+    ),
+    (
+        "CATALYST_TRACKER_BACKEND",
+        "Store the tracker hook writes to (sqlite or jsonl)",
+    ),
+    (
+        "CATALYST_ANALYTICS_TOKEN",
+        "Bearer token for analytics.endpoint (unless analytics.token_env names another)",
+    ),
+    ("CARGO_CHECK_DEBUG", "cargo-check hook prints debug output"),
+    ("CARGO_CHECK_QUIET", "cargo-check hook suppresses progress"),
+    ("CARGO_CHECK_CLIPPY", "cargo-check hook also runs clippy"),
+    ("CARGO_CHECK_TESTS", "cargo-check hook also checks tests"),
+    ("CARGO_CHECK_FMT", "cargo-check hook also checks formatting"),
+];
+
+/// Fully resolved catalyst environment for a project
+#[derive(Debug, Serialize)]
+pub struct EnvReport {
+    /// Version of this catalyst binary
+    pub version: String,
+
+    /// Project directory the report was collected for
+    pub project_dir: PathBuf,
+
+    /// Detected platform
+    pub platform: Platform,
+
+    /// Platform whose wrapper scripts are generated (after config overrides)
+    pub wrapper_platform: Platform,
+
+    /// Config files consulted, in precedence order
+    pub config_files: Vec<ConfigSource>,
+
+    /// Effective configuration (defaults if no file or the file is invalid)
+    pub config: CatalystConfig,
+
+    /// Error loading the config file, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_error: Option<String>,
+
+    /// Environment variables currently set that affect catalyst
+    pub env_overrides: Vec<EnvOverride>,
+
+    /// Computed paths
+    pub paths: EnvPaths,
+
+    /// Feature flags and whether they are enabled
+    pub flags: Vec<FlagStatus>,
+
+    /// Cargo features compiled into this binary
+    pub features: Vec<BuildFeature>,
+}
+
+/// A config file location and whether it is in effect
+#[derive(Debug, Serialize)]
+pub struct ConfigSource {
+    pub path: PathBuf,
+    pub exists: bool,
+    /// True for the file actually loaded (the first one that exists)
+    pub active: bool,
+}
+
+/// An environment variable that is set and what it does
+#[derive(Debug, Serialize)]
+pub struct EnvOverride {
+    pub name: String,
+    pub value: String,
+    pub effect: String,
+}
+
+/// Paths catalyst reads from or writes to
+#[derive(Debug, Serialize)]
+pub struct EnvPaths {
+    /// Where hook binaries are installed (None if home can't be determined)
+    pub bin_dir: Option<PathBuf>,
+    /// Where the file-change-tracker keeps its session databases
+    pub tracker_state_dir: Option<PathBuf>,
+    /// Machine-wide skill library that skills can be linked from
+    pub skills_library: Option<PathBuf>,
+    pub claude_dir: PathBuf,
+    pub hooks_dir: PathBuf,
+    pub skills_dir: PathBuf,
+    pub settings_file: PathBuf,
+    pub version_file: PathBuf,
+    pub backup_dir: PathBuf,
+    pub update_journal: PathBuf,
+}
+
+/// A cargo feature and whether it was enabled at build time
+#[derive(Debug, Serialize)]
+pub struct BuildFeature {
+    pub name: String,
+    pub enabled: bool,
+}
+
+/// Collect the environment report for a project
+///
+/// An unreadable or invalid config file doesn't fail the report; the error
+/// is recorded in `config_error` and defaults are shown instead.
+///
+/// # Arguments
+///
+/// * `target_dir` - Project directory
+/// * `platform` - Detected platform
+pub fn collect(target_dir: &Path, platform: Platform) -> EnvReport {
+    let active = CatalystConfig::find(target_dir);
+    let config_files = [CONFIG_FILE, CONFIG_FILE_CLAUDE]
+        .iter()
+        .map(|name| {
+            let path = target_dir.join(name);
+            ConfigSource {
+                exists: path.is_file(),
+                active: active.as_deref() == Some(path.as_path()),
+                path,
+            }
+        })
+        .collect();
+
+    let (config, config_error) = match CatalystConfig::load(target_dir) {
+        Ok(config) => (config, None),
+        Err(e) => (CatalystConfig::default(), Some(e.to_string())),
+    };
+
+    let env_overrides = ENV_VARS
+        .iter()
+        .filter_map(|(name, effect)| {
+            env::var(name).ok().map(|value| EnvOverride {
+                name: name.to_string(),
+                value,
+                effect: effect.to_string(),
+            })
+        })
+        .collect();
+
+    let flags = EnabledFlags::resolve(&config).statuses();
+
+    let bin_dir = get_binary_directory().ok();
+    let tracker_state_dir =
+        dirs::home_dir().map(|home| home.join(CLAUDE_DIR).join(TRACKER_STATE_DIR));
+    let skills_library = library::library_dir().ok();
+
+    let features = [
+        ("sqlite", cfg!(feature = "sqlite")),
+        ("parallel", cfg!(feature = "parallel")),
+        ("fast-patterns", cfg!(feature = "fast-patterns")),
+    ]
+    .iter()
+    .map(|(name, enabled)| BuildFeature {
+        name: name.to_string(),
+        enabled: *enabled,
+    })
+    .collect();
+
+    EnvReport {
+        version: CATALYST_VERSION.to_string(),
+        project_dir: target_dir.to_path_buf(),
+        platform,
+        wrapper_platform: config.wrapper_platform(platform),
+        config_files,
+        config,
+        config_error,
+        env_overrides,
+        paths: EnvPaths {
+            bin_dir,
+            tracker_state_dir,
+            skills_library,
+            claude_dir: target_dir.join(CLAUDE_DIR),
+            hooks_dir: target_dir.join(HOOKS_DIR),
+            skills_dir: target_dir.join(SKILLS_DIR),
+            settings_file: target_dir.join(SETTINGS_FILE),
+            version_file: target_dir.join(VERSION_FILE),
+            backup_dir: target_dir.join(BACKUP_DIR),
+            update_journal: target_dir.join(UPDATE_JOURNAL_FILE),
+        },
+        flags,
+        features,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_collect_marks_active_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(".claude")).unwrap();
+        fs::write(
+            target.join(CONFIG_FILE_CLAUDE),
+            "[wrappers]\nshell = \"ps1\"\n",
+        )
+        .unwrap();
+
+        let report = collect(target, Platform::Linux);
+        assert!(!report.config_files[0].exists);
+        assert!(report.config_files[1].active);
+        assert_eq!(report.wrapper_platform, Platform::Windows);
+        assert!(report.config_error.is_none());
+    }
+
+    #[test]
+    fn test_collect_reports_invalid_config() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(CONFIG_FILE), "[init\n").unwrap();
+
+        let report = collect(temp_dir.path(), Platform::Linux);
+        assert!(report.config_error.is_some());
+        assert_eq!(report.config, CatalystConfig::default());
+
+        // JSON output is always producible for bug reports
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["platform"], "Linux");
+        assert!(json["paths"]["settings_file"].is_string());
+    }
+}
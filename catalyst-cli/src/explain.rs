@@ -0,0 +1,410 @@
+//! Long-form explanations for status issues
+//!
+//! Every issue reported by `catalyst status` carries a stable ID (see
+//! [`IssueKind`]). This module maps each ID to a deep-dive explanation that
+//! `catalyst explain-issue <id>` prints: why the issue matters, how Catalyst
+//! detects it, and every known way to fix it.
+
+use crate::types::IssueKind;
+
+/// Long-form explanation for a single issue kind
+#[derive(Debug, Clone, Copy)]
+pub struct IssueExplanation {
+    /// The issue kind being explained
+    pub kind: IssueKind,
+
+    /// One-line title
+    pub title: &'static str,
+
+    /// Why the issue matters
+    pub why: &'static str,
+
+    /// How Catalyst detects the issue
+    pub detection: &'static str,
+
+    /// All known fix options, most recommended first
+    pub fixes: &'static [&'static str],
+}
+
+/// Get the explanation for an issue kind
+pub fn explain(kind: IssueKind) -> IssueExplanation {
+    match kind {
+        IssueKind::SettingsParseError => IssueExplanation {
+            kind,
+            title: "settings.json could not be parsed",
+            why: "Claude Code reads hook registrations from .claude/settings.json. If the file \
+                  is not valid JSON, or does not match the expected schema, Claude Code ignores \
+                  it and none of the Catalyst hooks run.",
+            detection: "`catalyst status` reads .claude/settings.json and deserializes it into \
+                        the settings schema. Any read or parse failure is reported with the \
+                        underlying error message (line and column when available).",
+            fixes: &[
+                "Open .claude/settings.json and fix the reported syntax error",
+                "Validate the file with: catalyst settings validate",
+                "Regenerate the file with: catalyst init --force (overwrites local edits)",
+            ],
+        },
+        IssueKind::BinaryMissing => IssueExplanation {
+            kind,
+            title: "Hook binary not installed",
+            why: "Hook wrapper scripts in .claude/hooks are thin launchers around compiled \
+                  binaries. Without the binary, the wrapper fails on every hook invocation and \
+                  the feature it provides (skill activation, file tracking) is silently off.",
+            detection: "Catalyst looks for each required binary in ~/.claude-hooks/bin, using \
+                        the platform's executable extension.",
+            fixes: &[
+                "Reinstall the way Catalyst was installed; `catalyst status` shows the exact \
+                 command (catalyst install-binaries, cargo install, or brew reinstall catalyst)",
+                "Build from a checkout and install in one step with: \
+                 catalyst install-binaries --build <checkout>",
+            ],
+        },
+        IssueKind::BinaryNotExecutable => IssueExplanation {
+            kind,
+            title: "Hook binary is not executable",
+            why: "The binary exists but can't be launched, so the wrapper script fails to run \
+                  it and the hook fails.",
+            detection: "On Unix, Catalyst checks the permission bits of the binary in \
+                        ~/.claude-hooks/bin for any execute bit. On Windows, it checks that \
+                        the file has an extension listed in PATHEXT, is not empty (a \
+                        truncated copy), and can be opened for reading.",
+            fixes: &[
+                "Run: chmod +x ~/.claude-hooks/bin/<binary> (Unix)",
+                "Run: catalyst install-binaries, which rewrites the binary with the right permissions",
+            ],
+        },
+        IssueKind::HookWrapperMissing => IssueExplanation {
+            kind,
+            title: "Hook wrapper script missing",
+            why: "Claude Code invokes hooks through the wrapper scripts in .claude/hooks. When \
+                  a wrapper is missing, the configured hook command points at nothing and \
+                  Claude Code reports a hook error on every matching event.",
+            detection: "For each hook Catalyst installs, status checks that the wrapper file \
+                        exists in .claude/hooks.",
+            fixes: &[
+                "Run: catalyst status --fix (recreates the wrapper from the built-in template)",
+                "Run: catalyst init --force",
+            ],
+        },
+        IssueKind::HookWrapperNotExecutable => IssueExplanation {
+            kind,
+            title: "Hook wrapper script is not executable",
+            why: "Claude Code executes wrapper scripts directly. Without the executable bit the \
+                  shell refuses to run them and the hook fails.",
+            detection: "On Unix, Catalyst checks the permission bits of each wrapper in \
+                        .claude/hooks for any execute bit.",
+            fixes: &[
+                "Run: catalyst status --fix (restores mode 0755)",
+                "Run: chmod +x .claude/hooks/<wrapper>",
+            ],
+        },
+        IssueKind::HookBinaryUnreachable => IssueExplanation {
+            kind,
+            title: "Hook wrapper cannot reach its binary",
+            why: "The wrapper exists and is executable, but the binary it launches is not \
+                  installed, so the hook fails at runtime.",
+            detection: "Catalyst reads the binary name the wrapper launches and checks that \
+                        the binary exists in ~/.claude-hooks/bin.",
+            fixes: &[
+                "Reinstall the binaries; `catalyst status` suggests the command for your \
+                 install method",
+                "If the binary lives elsewhere, set CATALYST_PROJECT_DIR so the wrapper can \
+                 find target/release",
+            ],
+        },
+        IssueKind::SkillMissingMainFile => IssueExplanation {
+            kind,
+            title: "Skill is missing SKILL.md",
+            why: "SKILL.md is the entry point Claude loads when a skill activates. A skill \
+                  directory without it activates but provides no guidance.",
+            detection: "For each directory in .claude/skills, status checks that SKILL.md \
+                        exists.",
+            fixes: &[
+                "Reinstall the skill with: catalyst init --force",
+                "Restore SKILL.md from version control if it was deleted by mistake",
+                "Remove the skill directory if the skill is no longer wanted",
+            ],
+        },
+        IssueKind::SkillUnregistered => IssueExplanation {
+            kind,
+            title: "Skill is not registered in skill-rules.json",
+            why: "The skill-activation-prompt hook only suggests skills listed in \
+                  skill-rules.json. An unregistered skill is installed but never activated.",
+            detection: "Catalyst parses .claude/skills/skill-rules.json and compares its \
+                        skill IDs against the directories in .claude/skills.",
+            fixes: &[
+                "Add an entry for the skill to .claude/skills/skill-rules.json",
+                "Run: catalyst update",
+                "Run: catalyst init --force to regenerate skill-rules.json",
+            ],
+        },
+        IssueKind::VersionFileMissing => IssueExplanation {
+            kind,
+            title: ".catalyst-version file missing",
+            why: "Catalyst records the version that installed a project so `catalyst update` \
+                  knows whether files need refreshing. Without it, updates cannot tell an old \
+                  install from a current one.",
+            detection: "Status checks for .catalyst-version in the project root.",
+            fixes: &[
+                "Run: catalyst status --fix (writes the current version)",
+                "Run: catalyst update",
+            ],
+        },
+        IssueKind::VersionMismatch => IssueExplanation {
+            kind,
+            title: "Installed version differs from the CLI version",
+            why: "The project was set up by a different Catalyst version. Wrapper scripts and \
+                  skills may be out of date relative to the running CLI.",
+            detection: "Status compares the contents of .catalyst-version with the version of \
+                        the running catalyst binary.",
+            fixes: &[
+                "Run: catalyst update",
+                "Run: catalyst update --force to also overwrite locally modified skills",
+            ],
+        },
+        IssueKind::HookEntryMissing => IssueExplanation {
+            kind,
+            title: "Hook not registered in settings.json",
+            why: "The wrapper script is installed, but Claude Code only runs hooks listed in \
+                  .claude/settings.json. Without the entry the hook silently never fires - \
+                  typically because the entry was deleted while editing settings by hand.",
+            detection: "For each hook enabled in catalyst.toml whose wrapper exists in \
+                        .claude/hooks, status looks for a settings.json entry under the \
+                        expected event whose command references the wrapper.",
+            fixes: &[
+                "Run: catalyst status --fix (asks before editing settings.json; add --yes to skip the prompt)",
+                "Run: catalyst settings add-hook --event <event> --command <wrapper path>",
+            ],
+        },
+        IssueKind::UnusualPermissions => IssueExplanation {
+            kind,
+            title: "Managed file has unusual permissions",
+            why: "A world-writable settings.json or hook wrapper lets any local user change \
+                  which commands Claude Code runs. Files that their owner can't read and write, \
+                  or that carry setuid/setgid bits, usually point to a broken copy or umask. \
+                  Catalyst keeps existing modes when it rewrites files, so it reports these \
+                  instead of quietly changing them.",
+            detection: "On Unix, `catalyst status` checks the mode of settings.json, \
+                        skill-rules.json, .catalyst-version, and every hook wrapper. \
+                        Group-writable files are not flagged, since shared machines rely on them.",
+            fixes: &[
+                "Run: chmod 644 <file> (chmod 755 for hook wrappers)",
+                "Run: catalyst init --force --reset-permissions to rewrite files with default modes",
+            ],
+        },
+        IssueKind::HookMislabeled => IssueExplanation {
+            kind,
+            title: "Hook wrapper has the wrong SELinux context",
+            why: "With SELinux enforcing, a wrapper whose type doesn't match its directory \
+                  (often user_tmp_t after being moved from /tmp) can be denied execution. \
+                  Claude Code doesn't surface the denial, so the hook just never runs.",
+            detection: "When SELinux is enabled, `catalyst status` compares the type field of \
+                        each wrapper's context with that of .claude/hooks. Reported as an \
+                        error when enforcing and a warning when permissive.",
+            fixes: &[
+                "Run: catalyst status --fix",
+                "Run: chcon --reference=.claude/hooks .claude/hooks/<wrapper>",
+                "Run: restorecon -v .claude/hooks/<wrapper>",
+            ],
+        },
+        IssueKind::SkillUnhealthy => IssueExplanation {
+            kind,
+            title: "Skill health check failed",
+            why: "The skill declared something it needs to work, such as a tool on PATH or a \
+                  file in the project, and that requirement isn't met. The skill still \
+                  activates, but the instructions it gives Claude will fail when followed.",
+            detection: "`catalyst status` reads the [healthcheck] table of each skill's \
+                        skill.toml and checks that the listed files exist in the project. \
+                        `catalyst status --run-healthchecks` also runs the listed command from \
+                        the project directory, expecting exit code 0.",
+            fixes: &[
+                "Install the missing tool or create the missing file named in the issue",
+                "Remove the skill if the project doesn't need it",
+            ],
+        },
+        IssueKind::ValidatorIssue => IssueExplanation {
+            kind,
+            title: "Project validator reported an issue",
+            why: "The project registered its own check in catalyst.toml, and that check found \
+                  something it considers wrong. Catalyst passes the finding through without \
+                  interpreting it.",
+            detection: "`catalyst status` runs each [[validators]] command from the project \
+                         directory and merges the issues in its JSON output into the report, \
+                         with the validator's name as the component.",
+            fixes: &[
+                "Follow the suggested fix printed with the issue",
+                "Ask whoever maintains the validator; it lives in the project, not in Catalyst",
+            ],
+        },
+        IssueKind::ValidatorFailed => IssueExplanation {
+            kind,
+            title: "Project validator could not run",
+            why: "A validator registered in catalyst.toml failed before producing a report, \
+                  so the checks it performs were skipped.",
+            detection: "Reported when the command can't be started, runs past its \
+                        timeout_secs, or prints something other than a JSON issues list.",
+            fixes: &[
+                "Run the validator's command by hand from the project directory",
+                "Raise timeout_secs in its [[validators]] entry if it is just slow",
+                "Make sure it prints {\"issues\": [...]} (or a bare array) on stdout",
+            ],
+        },
+        IssueKind::DeprecatedSkill => IssueExplanation {
+            kind,
+            title: "Deprecated skill installed",
+            why: "Catalyst no longer ships this skill, so `catalyst update` won't refresh it. \
+                  If it was renamed, the current version lives under the new name and the \
+                  installed copy falls further behind with every release.",
+            detection: "`catalyst status` compares each directory in .claude/skills against the \
+                        deprecation table compiled into the CLI.",
+            fixes: &[
+                "Run: catalyst update --migrate-skills (renamed skills keep local edits)",
+                "Run: catalyst status --fix",
+                "Remove the skill directory and its skill-rules.json entry if it was retired",
+            ],
+        },
+        IssueKind::PowerShellInvocation => IssueExplanation {
+            kind,
+            title: "PowerShell can't run the hook wrappers",
+            why: "Claude Code can't execute a .ps1 file by path, so each wrapper has to be run \
+                  through pwsh or powershell. A host that isn't installed fails every prompt, \
+                  and without -ExecutionPolicy Bypass the default policy refuses the unsigned \
+                  wrapper. A policy set by Group Policy overrides Bypass entirely.",
+            detection: "When wrappers are .ps1, `catalyst status` looks for pwsh and powershell on \
+                        PATH, checks each Catalyst hook command in settings.json, and on Windows \
+                        asks PowerShell for the MachinePolicy and UserPolicy execution policies.",
+            fixes: &[
+                "Run: catalyst status --fix (rewrites hook commands to use an installed host)",
+                "Pin the host with: catalyst config set wrappers.powershell pwsh",
+                "Install PowerShell 7: winget install Microsoft.PowerShell",
+                "Ask an administrator to relax an execution policy enforced by Group Policy",
+            ],
+        },
+        IssueKind::BinaryArchMismatch => IssueExplanation {
+            kind,
+            title: "Hook binary built for another architecture",
+            why: "Hooks run these binaries on every prompt and edit. Linux refuses to start a \
+                  binary for a different CPU, so the hooks fail; macOS and Windows on ARM run \
+                  x86_64 binaries under emulation, which works but is slower.",
+            detection: "`catalyst status` reads the ELF, Mach-O, or PE header of each binary in \
+                        ~/.claude-hooks/bin and compares it with this machine's architecture.",
+            fixes: &[
+                "Reinstall the binaries built for this machine (install.sh, install.ps1, or catalyst install-binaries --build <checkout>)",
+                "Download the release archive named for this machine's target triple, e.g. catalyst-aarch64-apple-darwin.tar.gz",
+            ],
+        },
+        IssueKind::SkillModified => IssueExplanation {
+            kind,
+            title: "Skill modified since install",
+            why: "Someone edited or deleted files of a skill Catalyst installed. That's often \
+                  deliberate, so this is informational, but `catalyst update` skips modified \
+                  skills and they stop receiving upstream fixes.",
+            detection: "`catalyst status` hashes each file listed for the skill in \
+                        .claude/skills/.catalyst-hashes.json and compares it with the hash \
+                        recorded at install. Files added since install are not counted.",
+            fixes: &[
+                "Keep the edits: nothing to do (catalyst update will leave the skill alone)",
+                "Discard the edits and reinstall: catalyst update --force",
+            ],
+        },
+        IssueKind::HookCommandDangling => IssueExplanation {
+            kind,
+            title: "Hook command runs a file that doesn't exist",
+            why: "Claude Code runs the command on every matching event and it fails each \
+                  time, usually because of a typo or a wrapper that was renamed or deleted. \
+                  Blocking events like PreToolUse can stop tool calls entirely.",
+            detection: "`catalyst status` looks for $CLAUDE_PROJECT_DIR (and its %VAR% and \
+                        $env: forms) in every hook command in settings.json, resolves the path \
+                        against the project root, and checks that it exists. When a file with \
+                        a close name sits in the same directory, it is offered as the fix.",
+            fixes: &[
+                "Run: catalyst status --fix (when a close match was found)",
+                "Correct the path in .claude/settings.json",
+                "Remove the hook: catalyst settings remove-hook -e <event> -P <path>",
+            ],
+        },
+        IssueKind::BinaryVersionMismatch => IssueExplanation {
+            kind,
+            title: "Hook binary version incompatible with the CLI",
+            why: "The hook wrappers and settings Catalyst writes expect binaries from the \
+                  same release. A binary left behind by an older install (or installed ahead \
+                  of the CLI) may read hook input or print results differently, so hooks can \
+                  fail or silently do nothing.",
+            detection: "`catalyst status` runs each installed binary with --version and checks \
+                        the result against the CLI's version: same major version (major and \
+                        minor before 1.0) and no older than the minimum listed for that \
+                        binary. Binaries that don't answer --version are not flagged.",
+            fixes: &[
+                "Update the CLI and reinstall the binaries: catalyst update, then catalyst install-binaries",
+                "Reinstall just the binaries to match this CLI: catalyst install-binaries",
+            ],
+        },
+        IssueKind::LockfileDrift => IssueExplanation {
+            kind,
+            title: "Install differs from catalyst.lock",
+            why: "catalyst.lock records the skills, hook wrappers, and binary versions the \
+                  project was set up with, so everyone working on it gets the same hooks and \
+                  skill guidance. A skill edited or missing locally, or a binary from another \
+                  release, means this checkout behaves differently from the team's.",
+            detection: "`catalyst status` hashes each installed skill directory and hook \
+                        wrapper, asks each hook binary for its --version, and compares the \
+                        results with catalyst.lock. Wrappers for the other platform (.sh vs \
+                        .ps1) are not compared.",
+            fixes: &[
+                "Install what's missing from the lockfile: catalyst init --from-lock",
+                "Reinstall edited skills: catalyst update --force",
+                "Accept the current install: catalyst lock (then commit catalyst.lock)",
+            ],
+        },
+    }
+}
+
+/// Get the explanation for an issue ID (case-insensitive)
+///
+/// Returns None if the ID is not a known issue ID.
+pub fn explain_id(id: &str) -> Option<IssueExplanation> {
+    IssueKind::from_id(id).map(explain)
+}
+
+/// Suggest the closest known issue ID for a mistyped one
+pub fn suggest_id(id: &str) -> Option<&'static str> {
+    let ids: Vec<&'static str> = IssueKind::ALL.iter().map(|kind| kind.id()).collect();
+    catalyst_core::settings::find_closest_match(&id.to_uppercase(), &ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_every_kind_has_explanation() {
+        for kind in IssueKind::ALL {
+            let explanation = explain(*kind);
+            assert_eq!(explanation.kind, *kind);
+            assert!(!explanation.title.is_empty());
+            assert!(!explanation.why.is_empty());
+            assert!(!explanation.detection.is_empty());
+            assert!(!explanation.fixes.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_issue_ids_are_unique() {
+        let ids: HashSet<&str> = IssueKind::ALL.iter().map(|kind| kind.id()).collect();
+        assert_eq!(ids.len(), IssueKind::ALL.len());
+    }
+
+    #[test]
+    fn test_explain_id_case_insensitive() {
+        let explanation = explain_id("cat004").unwrap();
+        assert_eq!(explanation.kind, IssueKind::HookWrapperMissing);
+        assert!(explain_id("CAT999").is_none());
+    }
+
+    #[test]
+    fn test_suggest_id() {
+        assert_eq!(suggest_id("CAT01"), Some("CAT001"));
+        assert_eq!(suggest_id("completely-unrelated"), None);
+    }
+}
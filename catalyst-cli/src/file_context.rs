@@ -0,0 +1,279 @@
+//! Files a Claude Code session is working on
+//!
+//! The skill-activation-prompt hook matches each rule's
+//! `fileTriggers.pathPatterns` against the files the session recently
+//! edited or read, so working in `backend/` suggests backend skills even
+//! when the prompt doesn't mention them. Recent files come from the
+//! session transcript (the tool calls Claude made) and, in builds with the
+//! `sqlite` feature, from the file-change-tracker database.
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Component, Path, PathBuf};
+
+/// Most recent files considered when matching path patterns
+pub const MAX_RECENT_FILES: usize = 20;
+
+/// Only the end of the transcript is read, to keep the hook fast on long
+/// sessions
+const TRANSCRIPT_TAIL_BYTES: u64 = 512 * 1024;
+
+/// Tools whose input names the file being worked on
+const FILE_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit", "Read"];
+
+/// Files named by the most recent file tool calls in a transcript
+///
+/// # Arguments
+///
+/// * `transcript` - Session transcript (JSONL) from the hook input
+/// * `limit` - Maximum number of files to return
+///
+/// # Returns
+///
+/// Returns distinct paths, most recently used first. An unreadable
+/// transcript yields no files rather than an error, since activation should
+/// still work from the prompt alone.
+pub fn transcript_files(transcript: &Path, limit: usize) -> Vec<PathBuf> {
+    let Ok(mut file) = File::open(transcript) else {
+        return Vec::new();
+    };
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let start = len.saturating_sub(TRANSCRIPT_TAIL_BYTES);
+    if file.seek(SeekFrom::Start(start)).is_err() {
+        return Vec::new();
+    }
+
+    let mut lines = BufReader::new(file).lines();
+    if start > 0 {
+        // Starting mid-file, so the first line is likely partial
+        lines.next();
+    }
+
+    let mut used: Vec<PathBuf> = Vec::new();
+    for line in lines.map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        used.extend(tool_use_paths(&entry));
+    }
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in used.into_iter().rev() {
+        if !files.contains(&path) {
+            files.push(path);
+            if files.len() == limit {
+                break;
+            }
+        }
+    }
+    files
+}
+
+/// Paths passed to file tools in one transcript entry
+fn tool_use_paths(entry: &Value) -> Vec<PathBuf> {
+    let Some(content) = entry.pointer("/message/content").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    content
+        .iter()
+        .filter(|item| item.get("type").and_then(Value::as_str) == Some("tool_use"))
+        .filter(|item| {
+            item.get("name")
+                .and_then(Value::as_str)
+                .is_some_and(|name| FILE_TOOLS.contains(&name))
+        })
+        .filter_map(|item| {
+            let input = item.get("input")?;
+            input
+                .get("file_path")
+                .or_else(|| input.get("notebook_path"))
+                .and_then(Value::as_str)
+        })
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Files the tracker recorded for a session, most recently modified first
+///
+/// Returns no files if nothing was tracked or the database can't be read.
+#[cfg(feature = "sqlite")]
+pub fn tracked_files(state_dir: &Path, session_id: &str, limit: usize) -> Vec<PathBuf> {
+    let Ok(Some(conn)) = crate::tracker::open_tracker_db_read_only(state_dir) else {
+        return Vec::new();
+    };
+    let query = conn
+        .prepare(
+            "SELECT file_path FROM file_modifications WHERE session_id = ?1
+             GROUP BY file_path ORDER BY MAX(timestamp) DESC, file_path LIMIT ?2",
+        )
+        .and_then(|mut statement| {
+            statement
+                .query_map(rusqlite::params![session_id, limit as i64], |row| {
+                    row.get::<_, String>(0)
+                })?
+                .collect::<rusqlite::Result<Vec<String>>>()
+        });
+    query
+        .map(|paths| paths.into_iter().map(PathBuf::from).collect())
+        .unwrap_or_default()
+}
+
+/// Paths relative to the project, with `/` separators, as pathPatterns
+/// are written
+///
+/// Relative paths are taken as relative to the project already. Files
+/// outside the project are dropped.
+pub fn project_relative(files: &[PathBuf], project_dir: &Path) -> Vec<String> {
+    files
+        .iter()
+        .filter_map(|file| {
+            let relative = if file.is_absolute() {
+                file.strip_prefix(project_dir).ok()?
+            } else {
+                file.as_path()
+            };
+            let parts: Vec<String> = relative
+                .components()
+                .map(|component| match component {
+                    Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+                    Component::CurDir => None,
+                    // ".." leaves the project
+                    _ => Some(String::new()),
+                })
+                .collect::<Option<Vec<_>>>()
+                .unwrap_or_default();
+            (!parts.is_empty() && parts.iter().all(|part| !part.is_empty()))
+                .then(|| parts.join("/"))
+        })
+        .collect()
+}
+
+/// A rule's compiled `pathPatterns` and `pathExclusions`
+#[derive(Debug, Clone)]
+pub struct PathTriggers {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl PathTriggers {
+    /// Compile a rule's patterns, skipping invalid ones
+    ///
+    /// Returns None if no include pattern compiles, so the rule never
+    /// matches on files. `catalyst skills lint` reports invalid globs.
+    pub fn new(patterns: &[String], exclusions: &[String]) -> Option<Self> {
+        let include = build_set(patterns)?;
+        let exclude = build_set(exclusions).unwrap_or_else(GlobSet::empty);
+        Some(Self { include, exclude })
+    }
+
+    /// Whether a project-relative path matches the patterns and no exclusion
+    pub fn matches(&self, path: &str) -> bool {
+        self.include.is_match(path) && !self.exclude.is_match(path)
+    }
+
+    /// First of `files` the patterns match
+    pub fn first_match<'a>(&self, files: &'a [String]) -> Option<&'a str> {
+        files
+            .iter()
+            .map(String::as_str)
+            .find(|file| self.matches(file))
+    }
+}
+
+fn build_set(patterns: &[String]) -> Option<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    let mut added = false;
+    for pattern in patterns {
+        match Glob::new(pattern) {
+            Ok(glob) => {
+                builder.add(glob);
+                added = true;
+            }
+            Err(e) => {
+                tracing::warn!(pattern = %pattern, error = %e, "Invalid path pattern, skipping");
+            }
+        }
+    }
+    if !added {
+        return None;
+    }
+    builder.build().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn tool_use(name: &str, path: &str) -> String {
+        serde_json::json!({
+            "type": "assistant",
+            "message": {"content": [
+                {"type": "text", "text": "working"},
+                {"type": "tool_use", "name": name, "input": {"file_path": path}}
+            ]}
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_transcript_files_most_recent_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let transcript = temp_dir.path().join("session.jsonl");
+        let lines = [
+            tool_use("Read", "/p/src/lib.rs"),
+            tool_use("Edit", "/p/backend/routes.ts"),
+            "not json".to_string(),
+            tool_use("Bash", "/p/ignored.sh"),
+            tool_use("Edit", "/p/src/lib.rs"),
+        ];
+        fs::write(&transcript, lines.join("\n")).unwrap();
+
+        let files = transcript_files(&transcript, MAX_RECENT_FILES);
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("/p/src/lib.rs"),
+                PathBuf::from("/p/backend/routes.ts")
+            ]
+        );
+        assert_eq!(transcript_files(&transcript, 1).len(), 1);
+        assert!(transcript_files(&temp_dir.path().join("missing"), 5).is_empty());
+    }
+
+    #[test]
+    fn test_path_triggers_match_project_relative_files() {
+        let project = if cfg!(windows) { r"C:\p" } else { "/p" };
+        let files = project_relative(
+            &[
+                Path::new(project).join("backend").join("routes.ts"),
+                Path::new(project).join("backend").join("routes.test.ts"),
+                std::env::temp_dir().join("backend").join("x.ts"),
+                PathBuf::from("frontend/app.tsx"),
+                PathBuf::from("../outside.ts"),
+            ],
+            Path::new(project),
+        );
+        assert_eq!(
+            files,
+            vec![
+                "backend/routes.ts",
+                "backend/routes.test.ts",
+                "frontend/app.tsx"
+            ]
+        );
+
+        let triggers = PathTriggers::new(
+            &["backend/**/*.ts".to_string(), "[invalid".to_string()],
+            &["**/*.test.ts".to_string()],
+        )
+        .unwrap();
+        assert!(triggers.matches("backend/routes.ts"));
+        assert!(!triggers.matches("backend/routes.test.ts"));
+        assert_eq!(triggers.first_match(&files), Some("backend/routes.ts"));
+        assert!(PathTriggers::new(&["[invalid".to_string()], &[]).is_none());
+    }
+}
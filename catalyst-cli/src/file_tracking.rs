@@ -0,0 +1,789 @@
+//! Edit tracking shared by the file-change-tracker hooks
+//!
+//! Two binaries run this as a PostToolUse hook: `file-change-tracker`,
+//! which needs the `sqlite` feature and writes to whichever store
+//! [`TrackerBackend::resolve`] picks, and `file-change-tracker-basic`, which
+//! always appends to `modifications.jsonl` and builds without SQLite.
+//! `catalyst init --tracker-backend` chooses which one the tracker wrapper
+//! calls.
+//!
+//! For each Edit, Write, MultiEdit, or NotebookEdit call, the tracker
+//! classifies the file by its path, scans JavaScript and TypeScript sources
+//! for a few patterns, and records a [`ModificationRow`].
+
+use crate::analytics::{self, PushOptions, PushState};
+use crate::config::CatalystConfig;
+use crate::input_limits::{read_file_head, FileHead, InputLimits};
+use crate::modification_store::{ModificationRow, ModificationStore, TrackerBackend};
+use crate::types::{CatalystError, Result, CLAUDE_DIR, TRACKER_STATE_DIR};
+use catalyst_core::hooks::HookInput;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tracing::{debug, info};
+
+/// Argument the tracker re-runs itself with to push analytics in the background
+pub const PUSH_ANALYTICS_ARG: &str = "--push-analytics";
+
+/// Tools whose calls are tracked
+pub const FILE_TOOLS: &[&str] = &["Edit", "Write", "MultiEdit", "NotebookEdit"];
+
+// Pre-compiled regex patterns for file analysis (10-100x faster than compiling on each call)
+static TRY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"try\s*\{").unwrap());
+static ASYNC_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"async\s+").unwrap());
+static PRISMA_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"prisma\.|PrismaClient").unwrap());
+static CONTROLLER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Controller|router\.|app\.(get|post)").unwrap());
+static API_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"fetch\(|axios\.|apiClient\.").unwrap());
+
+/// Returns the home directory path in a cross-platform way
+/// On Windows: Uses USERPROFILE, falls back to HOME, then TEMP, then LOCALAPPDATA, then C:\Users\Default
+/// On Unix/Linux/macOS: Uses HOME
+#[cfg(windows)]
+fn get_home_dir() -> PathBuf {
+    env::var("USERPROFILE")
+        .or_else(|_| env::var("HOME"))
+        .or_else(|_| env::var("TEMP"))
+        .or_else(|_| env::var("LOCALAPPDATA"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("C:\\Users\\Default"))
+}
+
+#[cfg(not(windows))]
+fn get_home_dir() -> PathBuf {
+    env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("/tmp"))
+}
+
+/// File category classification for tracking purposes
+#[derive(Debug, Clone, Copy)]
+enum Category {
+    Backend,
+    Frontend,
+    Database,
+    Other,
+}
+
+impl Category {
+    /// Returns the string representation for storage
+    fn as_str(&self) -> &'static str {
+        match self {
+            Category::Backend => "backend",
+            Category::Frontend => "frontend",
+            Category::Database => "database",
+            Category::Other => "other",
+        }
+    }
+}
+
+struct Tracker {
+    store: Box<dyn ModificationStore>,
+}
+
+/// Validates session_id to prevent path traversal attacks
+/// Only allows alphanumeric characters, hyphens, and underscores
+pub(crate) fn validate_session_id(session_id: &str) -> Result<()> {
+    let invalid = |reason: &str| Err(CatalystError::InvalidPath(format!("session_id {}", reason)));
+    if session_id.is_empty() {
+        return invalid("cannot be empty");
+    }
+
+    if session_id.len() > 255 {
+        return invalid("exceeds maximum length of 255 characters");
+    }
+
+    // Only allow alphanumeric, hyphens, and underscores (prevent path traversal)
+    if !session_id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return invalid(
+            "contains invalid characters (only alphanumeric, hyphens, and underscores allowed)",
+        );
+    }
+
+    Ok(())
+}
+
+impl Tracker {
+    fn new(
+        session_id: &str,
+        project_dir: &Path,
+        state_dir: &Path,
+        backend: Option<TrackerBackend>,
+    ) -> Result<Self> {
+        // Session IDs are still validated before they are stored
+        validate_session_id(session_id)?;
+
+        // Unless the binary fixes one, catalyst.toml (or
+        // CATALYST_TRACKER_BACKEND) picks the store; with SQLite all sessions
+        // share tracker.db
+        let backend = match backend {
+            Some(backend) => backend,
+            None => TrackerBackend::resolve(project_dir)?,
+        };
+        let store = backend.open(state_dir)?;
+
+        Ok(Self { store })
+    }
+
+    fn track_modification(
+        &mut self,
+        session_id: &str,
+        project_dir: &Path,
+        file_path: &str,
+        tool: &str,
+    ) -> Result<()> {
+        let category = get_file_category(file_path);
+        let analysis = if should_analyze(file_path) {
+            analyze_file(file_path)
+        } else {
+            FileAnalysis::default()
+        };
+        let head = git_head(project_dir).unwrap_or_default();
+
+        self.store.record(&ModificationRow {
+            session_id: session_id.to_string(),
+            file_path: file_path.to_string(),
+            tool: tool.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            category: category.as_str().to_string(),
+            has_async: analysis.has_async,
+            has_try_catch: analysis.has_try_catch,
+            has_prisma: analysis.has_prisma,
+            has_controller: analysis.has_controller,
+            has_api_call: analysis.has_api_call,
+            line_count: i64::from(analysis.line_count),
+            git_branch: head.branch,
+            git_commit: head.commit,
+        })?;
+
+        Ok(())
+    }
+}
+
+/// Where a project's git checkout stands
+#[derive(Debug, Default, PartialEq)]
+struct GitHead {
+    /// None on a detached HEAD
+    branch: Option<String>,
+    commit: Option<String>,
+}
+
+/// Branch and HEAD commit of the git repository containing `project_dir`
+///
+/// Returns None when the directory isn't in a repository, the repository
+/// has no commits yet, or git isn't installed; an edit is tracked all the
+/// same.
+fn git_head(project_dir: &Path) -> Option<GitHead> {
+    // `--abbrev-ref` only applies to the revisions after it, so this prints
+    // the full hash and then the branch name
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(project_dir)
+        .args(["rev-parse", "HEAD", "--abbrev-ref", "HEAD"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let mut lines = stdout.lines().map(str::trim);
+    let commit = lines.next().filter(|line| !line.is_empty())?;
+    let branch = lines
+        .next()
+        .filter(|line| !line.is_empty() && *line != "HEAD");
+    Some(GitHead {
+        branch: branch.map(str::to_string),
+        commit: Some(commit.to_string()),
+    })
+}
+
+#[derive(Default)]
+struct FileAnalysis {
+    has_async: bool,
+    has_try_catch: bool,
+    has_prisma: bool,
+    has_controller: bool,
+    has_api_call: bool,
+    line_count: i32,
+}
+
+// Cross-platform path categorization using path components instead of string contains
+fn get_file_category(path: &str) -> Category {
+    let path_obj = Path::new(path);
+
+    // Check each path component (works on both Unix and Windows)
+    for component in path_obj.components() {
+        if let Some(comp_str) = component.as_os_str().to_str() {
+            match comp_str {
+                "frontend" | "client" | "components" | "features" => return Category::Frontend,
+                "controllers" | "services" | "routes" | "api" | "backend" | "server" => {
+                    return Category::Backend
+                }
+                "database" | "prisma" | "migrations" => return Category::Database,
+                _ => continue,
+            }
+        }
+    }
+
+    Category::Other
+}
+
+fn should_analyze(path: &str) -> bool {
+    let path_lower = path.to_lowercase();
+    !path_lower.contains(".test.")
+        && !path_lower.contains(".spec.")
+        && (path_lower.ends_with(".ts")
+            || path_lower.ends_with(".tsx")
+            || path_lower.ends_with(".js")
+            || path_lower.ends_with(".jsx"))
+}
+
+fn analyze_file(path: &str) -> FileAnalysis {
+    let limit = InputLimits::from_env().max_analyzed_bytes;
+    let content = match read_file_head(Path::new(path), limit) {
+        Ok(FileHead::Text {
+            content,
+            truncated_bytes,
+        }) => {
+            if truncated_bytes > 0 {
+                info!(
+                    file_path = %path,
+                    truncated_bytes,
+                    "Analyzed only the head of a large file"
+                );
+            }
+            content
+        }
+        Ok(FileHead::Binary) => {
+            info!(file_path = %path, "Skipped analysis of binary content");
+            return FileAnalysis::default();
+        }
+        Err(_) => return FileAnalysis::default(),
+    };
+
+    let line_count = content.lines().count() as i32;
+
+    // Use pre-compiled static regexes (10-100x faster than compiling on each call)
+    FileAnalysis {
+        has_try_catch: TRY_REGEX.is_match(&content),
+        has_async: ASYNC_REGEX.is_match(&content),
+        has_prisma: PRISMA_REGEX.is_match(&content),
+        has_controller: CONTROLLER_REGEX.is_match(&content),
+        has_api_call: API_REGEX.is_match(&content),
+        line_count,
+    }
+}
+
+fn extract_file_path(_tool: &str, args: &Map<String, Value>) -> Option<String> {
+    args.get("file_path")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Cross-platform tracker state directory
+fn state_dir() -> PathBuf {
+    get_home_dir().join(CLAUDE_DIR).join(TRACKER_STATE_DIR)
+}
+
+/// Start a background push of session summaries when the project has
+/// analytics enabled and the interval has passed
+///
+/// The push runs in a detached copy of this binary so curl never delays the
+/// edit. The attempt is recorded first, so concurrent edits don't each
+/// start one. Failures here never fail the hook.
+fn start_analytics_push_if_due(project_dir: &Path, state_dir: &Path) {
+    let Ok(config) = CatalystConfig::load(project_dir) else {
+        return;
+    };
+    let mut state = PushState::load(state_dir);
+    let now = Utc::now();
+    if !state.is_due(&config.analytics, now) {
+        return;
+    }
+    state.last_attempt = Some(now.to_rfc3339());
+    if state.save(state_dir).is_err() {
+        return;
+    }
+    let Ok(exe) = env::current_exe() else {
+        return;
+    };
+    let spawned = Command::new(exe)
+        .arg(PUSH_ANALYTICS_ARG)
+        .arg(project_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Err(e) = spawned {
+        debug!(error = %e, "Could not start analytics push");
+    }
+}
+
+/// Push session summaries for a project (the background half of
+/// [`start_analytics_push_if_due`], run with [`PUSH_ANALYTICS_ARG`])
+///
+/// # Errors
+///
+/// Returns the config file's errors or the push's
+pub fn push_analytics(project_dir: &Path) -> Result<()> {
+    let config = CatalystConfig::load(project_dir)?;
+    analytics::push(
+        &config.analytics,
+        project_dir,
+        &state_dir(),
+        PushOptions::default(),
+    )?;
+    Ok(())
+}
+
+/// Record the edit described by a PostToolUse payload
+///
+/// Calls of tools other than [`FILE_TOOLS`], and calls without a
+/// `file_path` argument, are ignored.
+///
+/// # Arguments
+///
+/// * `input` - The hook's stdin
+/// * `backend` - Store to write to, or None for the project's configured one
+///
+/// # Errors
+///
+/// Returns an error for malformed input, an invalid session ID, or a store
+/// that can't be opened or written
+pub fn track(input: &str, backend: Option<TrackerBackend>) -> Result<()> {
+    let data: HookInput = serde_json::from_str(input)?;
+
+    // Only track file modification tools
+    let Some(tool) = data
+        .tool_name
+        .as_deref()
+        .filter(|tool| FILE_TOOLS.contains(tool))
+    else {
+        return Ok(());
+    };
+    let Some(file_path) = data
+        .tool_input
+        .as_ref()
+        .and_then(|args| extract_file_path(tool, args))
+    else {
+        return Ok(());
+    };
+
+    // A missing session ID is rejected like any other invalid one
+    let session_id = data.session_id.as_deref().unwrap_or_default();
+    let project_dir = data.project_dir();
+    let state_dir = state_dir();
+    let mut tracker = Tracker::new(session_id, &project_dir, &state_dir, backend)?;
+    tracker.track_modification(session_id, &project_dir, &file_path, tool)?;
+    start_analytics_push_if_due(&project_dir, &state_dir);
+
+    // Structured logging (controlled by RUST_LOG=debug)
+    debug!(
+        file_path = %file_path,
+        category = %get_file_category(&file_path).as_str(),
+        tool = %tool,
+        "Tracked file modification"
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_session_id_valid() {
+        assert!(validate_session_id("test-123").is_ok());
+        assert!(validate_session_id("session_456").is_ok());
+        assert!(validate_session_id("abc123-xyz789").is_ok());
+        assert!(validate_session_id("UPPERCASE").is_ok());
+        assert!(validate_session_id("lower-Case_123").is_ok());
+    }
+
+    #[test]
+    fn test_validate_session_id_invalid_empty() {
+        assert!(validate_session_id("").is_err());
+    }
+
+    #[test]
+    fn test_validate_session_id_invalid_length() {
+        let long_id = "a".repeat(256);
+        assert!(validate_session_id(&long_id).is_err());
+    }
+
+    #[test]
+    fn test_validate_session_id_invalid_characters() {
+        // Path traversal attempts
+        assert!(validate_session_id("../etc/passwd").is_err());
+        assert!(validate_session_id("..\\windows\\system32").is_err());
+
+        // Special characters
+        assert!(validate_session_id("session@123").is_err());
+        assert!(validate_session_id("session#123").is_err());
+        assert!(validate_session_id("session 123").is_err());
+        assert!(validate_session_id("session/123").is_err());
+        assert!(validate_session_id("session\\123").is_err());
+    }
+
+    #[test]
+    fn test_validate_session_id_security_edge_cases() {
+        // Unicode characters (should be rejected - only ASCII allowed)
+        assert!(validate_session_id("session™123").is_err());
+        assert!(validate_session_id("session_中文").is_err());
+        assert!(validate_session_id("session🎯test").is_err());
+        assert!(validate_session_id("café").is_err());
+
+        // Null bytes (should be rejected)
+        assert!(validate_session_id("session\0id").is_err());
+        assert!(validate_session_id("\0session").is_err());
+
+        // Control characters
+        assert!(validate_session_id("session\nid").is_err());
+        assert!(validate_session_id("session\rid").is_err());
+        assert!(validate_session_id("session\tid").is_err());
+
+        // Boundary length conditions
+        let length_254 = "a".repeat(254);
+        assert!(validate_session_id(&length_254).is_ok()); // Just under limit
+
+        let length_255 = "a".repeat(255);
+        assert!(validate_session_id(&length_255).is_ok()); // Exactly at limit
+
+        let length_256 = "a".repeat(256);
+        assert!(validate_session_id(&length_256).is_err()); // Just over limit
+
+        let length_1000 = "a".repeat(1000);
+        assert!(validate_session_id(&length_1000).is_err()); // Way over limit
+    }
+
+    #[test]
+    fn test_category_as_str() {
+        assert_eq!(Category::Backend.as_str(), "backend");
+        assert_eq!(Category::Frontend.as_str(), "frontend");
+        assert_eq!(Category::Database.as_str(), "database");
+        assert_eq!(Category::Other.as_str(), "other");
+    }
+
+    /// Each category bumps its own counter in the sessions table
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_category_sql_update() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let state = temp_dir.path();
+        let mut tracker = Tracker::new("s1", state, state, Some(TrackerBackend::Sqlite)).unwrap();
+        for file in [
+            "/project/api/handlers.ts",
+            "/project/src/components/Header.tsx",
+            "/project/database/schema.sql",
+            "/project/README.md",
+            "/project/services/AuthService.ts",
+        ] {
+            tracker
+                .track_modification("s1", state, file, "Edit")
+                .unwrap();
+        }
+
+        let conn = crate::tracker::open_tracker_db(state).unwrap();
+        let counts: (i64, i64, i64, i64) = conn
+            .query_row(
+                "SELECT total_files, backend_files, frontend_files, database_files
+                 FROM sessions WHERE session_id = 's1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(counts, (5, 2, 1, 1));
+    }
+
+    #[test]
+    fn test_get_file_category_frontend() {
+        assert!(matches!(
+            get_file_category("/project/frontend/App.tsx"),
+            Category::Frontend
+        ));
+        assert!(matches!(
+            get_file_category("/project/client/Button.tsx"),
+            Category::Frontend
+        ));
+        assert!(matches!(
+            get_file_category("/project/src/components/Header.tsx"),
+            Category::Frontend
+        ));
+        assert!(matches!(
+            get_file_category("/project/features/auth/Login.tsx"),
+            Category::Frontend
+        ));
+    }
+
+    #[test]
+    fn test_get_file_category_backend() {
+        assert!(matches!(
+            get_file_category("/project/controllers/UserController.ts"),
+            Category::Backend
+        ));
+        assert!(matches!(
+            get_file_category("/project/services/AuthService.ts"),
+            Category::Backend
+        ));
+        assert!(matches!(
+            get_file_category("/project/routes/api.ts"),
+            Category::Backend
+        ));
+        assert!(matches!(
+            get_file_category("/project/api/handlers.ts"),
+            Category::Backend
+        ));
+        assert!(matches!(
+            get_file_category("/project/backend/server.ts"),
+            Category::Backend
+        ));
+        assert!(matches!(
+            get_file_category("/project/server/index.ts"),
+            Category::Backend
+        ));
+    }
+
+    #[test]
+    fn test_get_file_category_database() {
+        assert!(matches!(
+            get_file_category("/project/database/schema.sql"),
+            Category::Database
+        ));
+        assert!(matches!(
+            get_file_category("/project/prisma/schema.prisma"),
+            Category::Database
+        ));
+        assert!(matches!(
+            get_file_category("/project/migrations/001_init.sql"),
+            Category::Database
+        ));
+    }
+
+    #[test]
+    fn test_get_file_category_other() {
+        assert!(matches!(
+            get_file_category("/project/utils/helpers.ts"),
+            Category::Other
+        ));
+        assert!(matches!(
+            get_file_category("/project/lib/logger.ts"),
+            Category::Other
+        ));
+        assert!(matches!(
+            get_file_category("/project/README.md"),
+            Category::Other
+        ));
+    }
+
+    #[test]
+    fn test_should_analyze_valid_files() {
+        assert!(should_analyze("/project/app.ts"));
+        assert!(should_analyze("/project/Component.tsx"));
+        assert!(should_analyze("/project/script.js"));
+        assert!(should_analyze("/project/App.jsx"));
+    }
+
+    #[test]
+    fn test_should_analyze_skip_test_files() {
+        assert!(!should_analyze("/project/app.test.ts"));
+        assert!(!should_analyze("/project/Component.spec.tsx"));
+        assert!(!should_analyze("/project/test.spec.js"));
+    }
+
+    #[test]
+    fn test_should_analyze_skip_non_code_files() {
+        assert!(!should_analyze("/project/README.md"));
+        assert!(!should_analyze("/project/config.json"));
+        assert!(!should_analyze("/project/styles.css"));
+    }
+
+    #[test]
+    fn test_extract_file_path_with_valid_path() {
+        let mut args = Map::new();
+        args.insert(
+            "file_path".to_string(),
+            serde_json::Value::String("/project/test.ts".to_string()),
+        );
+
+        let result = extract_file_path("Edit", &args);
+        assert_eq!(result, Some("/project/test.ts".to_string()));
+    }
+
+    #[test]
+    fn test_extract_file_path_missing_key() {
+        let args = Map::new();
+        let result = extract_file_path("Edit", &args);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_extract_file_path_non_string_value() {
+        let mut args = Map::new();
+        args.insert(
+            "file_path".to_string(),
+            serde_json::Value::Number(123.into()),
+        );
+
+        let result = extract_file_path("Edit", &args);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_extract_file_path_different_tools() {
+        let mut args = Map::new();
+        args.insert(
+            "file_path".to_string(),
+            serde_json::Value::String("/project/test.ts".to_string()),
+        );
+
+        // Test all supported tool types
+        assert_eq!(
+            extract_file_path("Edit", &args),
+            Some("/project/test.ts".to_string())
+        );
+        assert_eq!(
+            extract_file_path("Write", &args),
+            Some("/project/test.ts".to_string())
+        );
+        assert_eq!(
+            extract_file_path("MultiEdit", &args),
+            Some("/project/test.ts".to_string())
+        );
+        assert_eq!(
+            extract_file_path("NotebookEdit", &args),
+            Some("/project/test.ts".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_file_category_windows_paths() {
+        // Note: On Windows, Rust PathBuf automatically handles both / and \ as separators
+        // On Unix, only / is treated as a separator, so we use forward slashes for cross-platform tests
+
+        // Windows paths with forward slashes (works on all platforms)
+        assert!(matches!(
+            get_file_category("C:/project/frontend/App.tsx"),
+            Category::Frontend
+        ));
+        assert!(matches!(
+            get_file_category("C:/project/controllers/UserController.ts"),
+            Category::Backend
+        ));
+        assert!(matches!(
+            get_file_category("C:/project/database/schema.sql"),
+            Category::Database
+        ));
+
+        // UNC paths (Windows network paths)
+        assert!(matches!(
+            get_file_category("//storage/share/frontend/App.tsx"),
+            Category::Frontend
+        ));
+    }
+
+    #[test]
+    fn test_should_analyze_windows_paths() {
+        // Note: Using forward slashes for cross-platform compatibility
+        // On Windows, Rust automatically normalizes these
+
+        // Windows paths with drive letters
+        assert!(should_analyze("C:/project/app.ts"));
+        assert!(should_analyze("C:/Users/dev/Component.tsx"));
+
+        // Skip test files on Windows paths
+        assert!(!should_analyze("C:/project/app.test.ts"));
+        assert!(!should_analyze("D:/code/Component.spec.tsx"));
+
+        // UNC paths (Windows network paths)
+        assert!(should_analyze("//storage/share/app.ts"));
+        assert!(!should_analyze("//storage/share/app.test.ts"));
+    }
+
+    #[test]
+    fn test_hook_input_deserialization() {
+        let json = r#"{
+            "session_id": "test-123",
+            "tool_name": "Edit",
+            "tool_args": {
+                "file_path": "/project/app.ts"
+            }
+        }"#;
+
+        let result = serde_json::from_str::<HookInput>(json);
+        assert!(result.is_ok());
+
+        let input = result.unwrap();
+        assert_eq!(input.session_id.as_deref(), Some("test-123"));
+        assert_eq!(input.tool_name, Some("Edit".to_string()));
+    }
+
+    #[test]
+    fn test_hook_input_optional_fields() {
+        let json = r#"{
+            "session_id": "test-123"
+        }"#;
+
+        let result = serde_json::from_str::<HookInput>(json);
+        assert!(result.is_ok());
+
+        let input = result.unwrap();
+        assert_eq!(input.session_id.as_deref(), Some("test-123"));
+        assert_eq!(input.tool_name, None);
+        assert_eq!(input.tool_input, None);
+    }
+
+    #[test]
+    fn test_file_analysis_default() {
+        let analysis = FileAnalysis::default();
+        assert!(!analysis.has_try_catch);
+        assert!(!analysis.has_async);
+        assert!(!analysis.has_prisma);
+        assert!(!analysis.has_controller);
+        assert!(!analysis.has_api_call);
+        assert_eq!(analysis.line_count, 0);
+    }
+
+    #[test]
+    fn test_git_head() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .output()
+                .map(|output| output.status.success())
+                .unwrap_or(false)
+        };
+        if !git(&["init", "-q", "-b", "feature"]) {
+            // git isn't installed
+            return;
+        }
+        assert_eq!(git_head(dir), None);
+
+        assert!(git(&["commit", "-q", "--allow-empty", "-m", "first"]));
+        let head = git_head(dir).unwrap();
+        assert_eq!(head.branch.as_deref(), Some("feature"));
+        let commit = head.commit.unwrap();
+        assert_eq!(commit.len(), 40);
+
+        assert!(git(&["checkout", "-q", "--detach"]));
+        assert_eq!(
+            git_head(dir),
+            Some(GitHead {
+                branch: None,
+                commit: Some(commit)
+            })
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Feature flags for experimental behaviors
+//!
+//! Large features ship behind a flag until they are ready to be on by
+//! default. A flag is enabled per project in `catalyst.toml`:
+//!
+//! ```toml
+//! [flags]
+//! enable = ["merge-engine"]
+//! ```
+//!
+//! or for a single invocation with `CATALYST_UNSTABLE=merge-engine,registry`.
+
+use crate::config::CatalystConfig;
+use crate::types::{CatalystError, Result};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Environment variable listing flags to enable (comma-separated)
+pub const UNSTABLE_ENV_VAR: &str = "CATALYST_UNSTABLE";
+
+/// How mature a flagged feature is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stability {
+    /// Incomplete; behavior and file formats may change without notice
+    Experimental,
+    /// Feature-complete and expected to become the default
+    Preview,
+}
+
+impl fmt::Display for Stability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stability::Experimental => write!(f, "experimental"),
+            Stability::Preview => write!(f, "preview"),
+        }
+    }
+}
+
+/// A feature that can be switched on with a flag
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FeatureFlag {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub stability: Stability,
+}
+
+/// Every flag this version of catalyst understands
+pub const FLAGS: &[FeatureFlag] = &[
+    FeatureFlag {
+        name: "merge-engine",
+        description: "Three-way merge of locally modified skills during update",
+        stability: Stability::Experimental,
+    },
+    FeatureFlag {
+        name: "async-tracker",
+        description: "Record file changes from the tracker hook in the background",
+        stability: Stability::Experimental,
+    },
+    FeatureFlag {
+        name: "registry",
+        description: "Install skills from a remote skill registry",
+        stability: Stability::Experimental,
+    },
+];
+
+/// Where a flag was enabled from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlagSource {
+    Config,
+    Env,
+}
+
+/// Resolved set of enabled flags
+#[derive(Debug, Clone, Default)]
+pub struct EnabledFlags {
+    config: BTreeSet<String>,
+    env: BTreeSet<String>,
+    /// Names from `CATALYST_UNSTABLE` that don't match any flag
+    pub unknown: Vec<String>,
+}
+
+/// A flag with its resolved state, for listing
+#[derive(Debug, Clone, Serialize)]
+pub struct FlagStatus {
+    #[serde(flatten)]
+    pub flag: FeatureFlag,
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<FlagSource>,
+}
+
+impl EnabledFlags {
+    /// Resolve flags from the project config and `CATALYST_UNSTABLE`
+    ///
+    /// Unknown names in the config are already rejected when it is loaded;
+    /// unknown names in the environment are collected in `unknown` so the
+    /// caller can warn without failing.
+    pub fn resolve(config: &CatalystConfig) -> Self {
+        Self::from_sources(config, std::env::var(UNSTABLE_ENV_VAR).ok().as_deref())
+    }
+
+    /// Resolve flags from the config and an explicit env value
+    pub fn from_sources(config: &CatalystConfig, env_value: Option<&str>) -> Self {
+        let mut flags = Self {
+            config: config.flags.enable.iter().cloned().collect(),
+            ..Self::default()
+        };
+
+        for name in env_value
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+        {
+            if find_flag(name).is_some() {
+                flags.env.insert(name.to_string());
+            } else {
+                flags.unknown.push(name.to_string());
+            }
+        }
+
+        flags
+    }
+
+    /// Whether a flag is enabled from any source
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.source(name).is_some()
+    }
+
+    /// Where a flag was enabled from (the environment wins over config)
+    pub fn source(&self, name: &str) -> Option<FlagSource> {
+        if self.env.contains(name) {
+            Some(FlagSource::Env)
+        } else if self.config.contains(name) {
+            Some(FlagSource::Config)
+        } else {
+            None
+        }
+    }
+
+    /// Every known flag with its resolved state
+    pub fn statuses(&self) -> Vec<FlagStatus> {
+        FLAGS
+            .iter()
+            .map(|flag| FlagStatus {
+                flag: *flag,
+                enabled: self.is_enabled(flag.name),
+                source: self.source(flag.name),
+            })
+            .collect()
+    }
+}
+
+/// Look up a flag by name
+pub fn find_flag(name: &str) -> Option<&'static FeatureFlag> {
+    FLAGS.iter().find(|flag| flag.name == name)
+}
+
+/// Check that every name is a known flag, suggesting the closest match
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` naming the first unknown flag
+pub fn validate_flag_names(names: &[String]) -> Result<()> {
+    let known: Vec<&str> = FLAGS.iter().map(|flag| flag.name).collect();
+
+    for name in names {
+        if find_flag(name).is_none() {
+            let mut msg = format!(
+                "Unknown feature flag '{}'. Known flags: {}",
+                name,
+                known.join(", ")
+            );
+            if let Some(suggestion) = catalyst_core::settings::find_closest_match(name, &known) {
+                msg = format!(
+                    "Unknown feature flag '{}'. Did you mean '{}'?",
+                    name, suggestion
+                );
+            }
+            return Err(CatalystError::InvalidConfig(msg));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_from_config_and_env() {
+        let mut config = CatalystConfig::default();
+        config.flags.enable = vec!["merge-engine".to_string()];
+
+        let flags = EnabledFlags::from_sources(&config, Some("registry, bogus,,merge-engine"));
+        assert_eq!(flags.source("registry"), Some(FlagSource::Env));
+        assert_eq!(flags.source("merge-engine"), Some(FlagSource::Env));
+        assert!(!flags.is_enabled("async-tracker"));
+        assert_eq!(flags.unknown, vec!["bogus".to_string()]);
+
+        let flags = EnabledFlags::from_sources(&config, None);
+        assert_eq!(flags.source("merge-engine"), Some(FlagSource::Config));
+        assert_eq!(flags.statuses().len(), FLAGS.len());
+    }
+
+    #[test]
+    fn test_validate_flag_names_suggests() {
+        assert!(validate_flag_names(&["registry".to_string()]).is_ok());
+
+        match validate_flag_names(&["regsitry".to_string()]) {
+            Err(CatalystError::InvalidConfig(msg)) => assert!(msg.contains("'registry'")),
+            other => panic!("Expected InvalidConfig, got {:?}", other),
+        }
+    }
+}
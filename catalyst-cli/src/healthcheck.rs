@@ -0,0 +1,229 @@
+//! Skill-defined health checks
+//!
+//! A skill can declare what it needs from the project or machine in a
+//! `[healthcheck]` table of its `skill.toml`:
+//!
+//! ```toml
+//! [healthcheck]
+//! # Runs in the project directory; healthy if it exits 0
+//! command = ["node", "--version"]
+//! # Project-relative paths that must exist
+//! files = ["scripts/test-auth-route.js"]
+//! ```
+//!
+//! `catalyst status` runs the checks of every installed skill and reports
+//! failures as CAT014, so skills can extend project validation without
+//! changes to Catalyst itself. The `command` comes from the project's own
+//! files, so it only runs when asked for with `--run-healthchecks`; plain
+//! status checks the `files` alone.
+
+use crate::process;
+use crate::types::{SkillHealth, SKILL_MANIFEST_FILE};
+use catalyst_core::fs_ops::{Fs, Mode};
+use serde::Deserialize;
+use std::path::{Component, Path};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// How long a health check command may run before it counts as failed
+pub const HEALTHCHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Checks a skill declares in its manifest
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Healthcheck {
+    /// Program and arguments to run (no shell is involved)
+    #[serde(default)]
+    pub command: Option<Vec<String>>,
+
+    /// Paths relative to the project root that must exist
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ManifestHealthcheck {
+    #[serde(default)]
+    healthcheck: Option<Healthcheck>,
+}
+
+/// Load the health check a skill declares, if any
+///
+/// # Errors
+///
+/// Returns a description of the problem if `skill.toml` can't be read or
+/// its `[healthcheck]` table is malformed.
+pub fn load_healthcheck<M: Mode>(
+    fs: &Fs<M>,
+    skill_dir: &Path,
+) -> std::result::Result<Option<Healthcheck>, String> {
+    let manifest_path = skill_dir.join(SKILL_MANIFEST_FILE);
+    let content = match fs.read_to_string(&manifest_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Could not read {}: {}", SKILL_MANIFEST_FILE, e)),
+    };
+
+    toml::from_str::<ManifestHealthcheck>(&content)
+        .map(|manifest| manifest.healthcheck)
+        .map_err(|e| format!("Invalid [healthcheck] in {}: {}", SKILL_MANIFEST_FILE, e))
+}
+
+/// Run a skill's health check, if it declares one
+///
+/// # Arguments
+///
+/// * `fs` - Filesystem handle for reading the manifest and checking files
+/// * `target_dir` - Project directory (commands run here, files resolve from here)
+/// * `skill_dir` - Installed skill directory
+/// * `run_command` - Whether to run the declared `command`; when false only
+///   the `files` are checked
+///
+/// # Returns
+///
+/// Returns None if the skill declares no health check
+pub fn check_skill<M: Mode>(
+    fs: &Fs<M>,
+    target_dir: &Path,
+    skill_dir: &Path,
+    run_command: bool,
+) -> Option<SkillHealth> {
+    let check = match load_healthcheck(fs, skill_dir) {
+        Ok(Some(check)) => check,
+        Ok(None) => return None,
+        Err(problem) => {
+            return Some(SkillHealth {
+                healthy: false,
+                failures: vec![problem],
+            })
+        }
+    };
+
+    let mut failures = Vec::new();
+    for file in &check.files {
+        if let Some(failure) = check_file(fs, target_dir, file) {
+            failures.push(failure);
+        }
+    }
+    if let Some(command) = check.command.as_ref().filter(|_| run_command) {
+        if let Some(failure) = check_command(target_dir, command) {
+            failures.push(failure);
+        }
+    }
+
+    Some(SkillHealth {
+        healthy: failures.is_empty(),
+        failures,
+    })
+}
+
+fn check_file<M: Mode>(fs: &Fs<M>, target_dir: &Path, file: &str) -> Option<String> {
+    let relative = Path::new(file);
+    let escapes = relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+    if escapes {
+        return Some(format!(
+            "'{}' must be a path inside the project (no absolute paths or '..')",
+            file
+        ));
+    }
+
+    (!fs.exists(&target_dir.join(relative))).then(|| format!("Required file {} not found", file))
+}
+
+fn check_command(target_dir: &Path, command: &[String]) -> Option<String> {
+    let Some((program, args)) = command.split_first() else {
+        return Some("Health check command is empty".to_string());
+    };
+    let display = command.join(" ");
+
+    let mut child = match Command::new(program)
+        .args(args)
+        .current_dir(target_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Some(format!("{} is not installed (not found on PATH)", program))
+        }
+        Err(e) => return Some(format!("Could not run `{}`: {}", display, e)),
+    };
+
+    match process::wait_timeout(&mut child, HEALTHCHECK_TIMEOUT) {
+        Ok(Some(status)) if status.success() => None,
+        Ok(Some(status)) => Some(format!("`{}` failed ({})", display, status)),
+        Ok(None) => Some(format!(
+            "`{}` did not finish within {} seconds",
+            display,
+            HEALTHCHECK_TIMEOUT.as_secs()
+        )),
+        Err(e) => Some(format!("Could not wait for `{}`: {}", display, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn skill_with_manifest(dir: &Path, manifest: &str) -> std::path::PathBuf {
+        let skill_dir = dir.join("skill");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join(SKILL_MANIFEST_FILE), manifest).unwrap();
+        skill_dir
+    }
+
+    #[test]
+    fn test_check_skill_reports_each_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::write(target.join("present.txt"), "").unwrap();
+        let skill_dir = skill_with_manifest(
+            target,
+            r#"
+[healthcheck]
+command = ["catalyst-test-no-such-program"]
+files = ["present.txt", "missing.txt", "../outside.txt"]
+"#,
+        );
+
+        let health = check_skill(&Fs::read_only(), target, &skill_dir, true).unwrap();
+        assert!(!health.healthy);
+        assert_eq!(health.failures.len(), 3, "{:?}", health.failures);
+        assert!(health.failures[0].contains("missing.txt"));
+        assert!(health.failures[1].contains("inside the project"));
+        assert!(health.failures[2].contains("not installed"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_skill_passes_and_skips_undeclared() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+
+        // No manifest, or a manifest without [healthcheck]: nothing to report
+        assert!(check_skill(&Fs::read_only(), target, target, true).is_none());
+        let skill_dir = skill_with_manifest(target, "[other]\nkey = 1\n");
+        assert!(check_skill(&Fs::read_only(), target, &skill_dir, true).is_none());
+
+        let skill_dir = skill_with_manifest(target, "[healthcheck]\ncommand = [\"true\"]\n");
+        let health = check_skill(&Fs::read_only(), target, &skill_dir, true).unwrap();
+        assert!(health.healthy && health.failures.is_empty());
+
+        let skill_dir = skill_with_manifest(target, "[healthcheck]\ncommand = [\"false\"]\n");
+        assert!(
+            !check_skill(&Fs::read_only(), target, &skill_dir, true)
+                .unwrap()
+                .healthy
+        );
+
+        // Without run_command the command is left alone
+        let health = check_skill(&Fs::read_only(), target, &skill_dir, false).unwrap();
+        assert!(health.healthy && health.failures.is_empty());
+    }
+}
@@ -0,0 +1,358 @@
+//! Hook wrappers under Claude Code's minimal environment
+//!
+//! Claude Code launches hooks without the login shell's profile, so a
+//! wrapper that works from a terminal can fail inside a session because
+//! `cargo`, `node`, or the hook binary itself isn't on PATH. `catalyst
+//! doctor --hook-env` runs each installed wrapper with a stripped
+//! environment (marked with [`DIAGNOSTIC_RUN_ENV`] so the runs stay out of
+//! hook telemetry), names the commands it couldn't find, and can add their
+//! directories to `[wrappers] path` in catalyst.toml so regenerated wrappers
+//! extend PATH themselves.
+
+use crate::binaries::HOOK_BINARIES;
+use crate::config::{self, CatalystConfig};
+use crate::hook_stats::DIAGNOSTIC_RUN_ENV;
+use crate::powershell;
+use crate::process;
+use crate::status::fix_hook_wrapper;
+use crate::types::{CatalystError, Platform, PowerShell, Result, HOOKS_DIR};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// PATH a hook sees when nothing from the user's profile is loaded
+#[cfg(unix)]
+pub const MINIMAL_PATH: &str = "/usr/bin:/bin";
+
+/// PATH a hook sees when nothing from the user's profile is loaded
+#[cfg(windows)]
+pub const MINIMAL_PATH: &str =
+    r"C:\Windows\System32;C:\Windows;C:\Windows\System32\WindowsPowerShell\v1.0";
+
+/// How long a wrapper may run before the check gives up on it
+const WRAPPER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Messages shells and wrappers print when a command can't be found
+static MISSING_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        // bash: "line 3: cargo: command not found"
+        r"([^\s:'`]+): command not found",
+        // dash and other POSIX shells: "sh: 1: cargo: not found"
+        r"(?m)([^\s:'`]+): not found$",
+        // Catalyst wrappers when the hook binary is missing
+        r"Error: (\S+?)(?:\.exe)? binary not found",
+        // PowerShell
+        r"The term '([^']+)' is not recognized",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).expect("valid missing-command pattern"))
+    .collect()
+});
+
+/// A command a wrapper couldn't find under the minimal PATH
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingCommand {
+    /// Command name as the shell reported it
+    pub name: String,
+
+    /// Where it lives on the current PATH, if it's there at all
+    pub found_at: Option<PathBuf>,
+}
+
+/// Outcome of running one wrapper with the minimal environment
+#[derive(Debug, Clone, Serialize)]
+pub struct WrapperDiagnosis {
+    /// Wrapper file name in .claude/hooks
+    pub wrapper: String,
+
+    /// Exit code, or None if the wrapper was killed or didn't start
+    pub exit_code: Option<i32>,
+
+    /// Whether the wrapper ran past the timeout
+    pub timed_out: bool,
+
+    /// Commands the wrapper reported as not found
+    pub missing: Vec<MissingCommand>,
+
+    /// What the wrapper printed on stderr
+    pub stderr: String,
+}
+
+impl WrapperDiagnosis {
+    /// Whether everything the wrapper needed was found
+    ///
+    /// Wrappers are fed empty input, so hook binaries usually exit non-zero
+    /// after rejecting it; only missing commands and hangs count here.
+    pub fn reachable(&self) -> bool {
+        self.missing.is_empty() && !self.timed_out && self.exit_code != Some(127)
+    }
+}
+
+/// Run every installed wrapper the way Claude Code would start it
+///
+/// Each wrapper for `platform` in .claude/hooks runs from the project
+/// directory with only HOME (or USERPROFILE), CLAUDE_PROJECT_DIR, and
+/// [`MINIMAL_PATH`] set, and with empty stdin.
+///
+/// # Errors
+///
+/// Returns `Io` if the hooks directory can't be read
+pub fn diagnose_wrappers(target_dir: &Path, platform: Platform) -> Result<Vec<WrapperDiagnosis>> {
+    // Hooks get an absolute CLAUDE_PROJECT_DIR and run from the project
+    let target_dir = &std::path::absolute(target_dir).map_err(CatalystError::Io)?;
+    let hooks_dir = target_dir.join(HOOKS_DIR);
+    let mut wrappers: Vec<PathBuf> = fs::read_dir(&hooks_dir)
+        .map_err(CatalystError::Io)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path.extension().and_then(|ext| ext.to_str()) == Some(platform.hook_extension())
+        })
+        .collect();
+    wrappers.sort();
+    let host = powershell::host(CatalystConfig::load(target_dir)?.wrappers.powershell);
+
+    Ok(wrappers
+        .iter()
+        .map(|wrapper| run_wrapper(target_dir, wrapper, platform, host))
+        .collect())
+}
+
+fn run_wrapper(
+    target_dir: &Path,
+    wrapper: &Path,
+    platform: Platform,
+    host: PowerShell,
+) -> WrapperDiagnosis {
+    let mut diagnosis = WrapperDiagnosis {
+        wrapper: wrapper
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        exit_code: None,
+        timed_out: false,
+        missing: Vec::new(),
+        stderr: String::new(),
+    };
+
+    let mut command = match platform {
+        Platform::Windows => {
+            let mut command = Command::new(host.program());
+            command.args(["-NoProfile", "-ExecutionPolicy", "Bypass", "-File"]);
+            command
+        }
+        _ => Command::new("bash"),
+    };
+    command
+        .arg(wrapper)
+        .current_dir(target_dir)
+        .env_clear()
+        .env("PATH", MINIMAL_PATH)
+        .env("CLAUDE_PROJECT_DIR", target_dir)
+        .env(DIAGNOSTIC_RUN_ENV, "1");
+    if let Some(home) = dirs::home_dir() {
+        command.env(if cfg!(windows) { "USERPROFILE" } else { "HOME" }, home);
+    }
+    if let Some(system_root) = env::var_os("SystemRoot") {
+        command.env("SystemRoot", system_root);
+    }
+
+    // Captured in a file so a noisy wrapper can't fill a pipe and stall
+    let stderr_file = tempfile::tempfile().and_then(|file| {
+        let handle = file.try_clone()?;
+        Ok((file, handle))
+    });
+    let (mut stderr, stderr_handle) = match stderr_file {
+        Ok(files) => files,
+        Err(e) => {
+            diagnosis.stderr = format!("Could not capture output: {}", e);
+            return diagnosis;
+        }
+    };
+
+    let spawned = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(stderr_handle)
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            diagnosis.stderr = format!("Could not start wrapper: {}", e);
+            return diagnosis;
+        }
+    };
+
+    match process::wait_timeout(&mut child, WRAPPER_TIMEOUT) {
+        Ok(Some(status)) => diagnosis.exit_code = status.code(),
+        Ok(None) => diagnosis.timed_out = true,
+        Err(e) => diagnosis.stderr = format!("Could not wait for wrapper: {}", e),
+    }
+
+    let mut output = String::new();
+    if stderr.seek(SeekFrom::Start(0)).is_ok() {
+        let _ = stderr.read_to_string(&mut output);
+    }
+    let path = env::var_os("PATH");
+    diagnosis.missing = missing_commands(&output)
+        .into_iter()
+        .map(|name| MissingCommand {
+            found_at: path
+                .as_deref()
+                .and_then(|path| process::find_on_path(&name, path)),
+            name,
+        })
+        .collect();
+    diagnosis.stderr.push_str(output.trim());
+    diagnosis
+}
+
+/// Command names from "not found" messages, without duplicates
+fn missing_commands(stderr: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for pattern in MISSING_PATTERNS.iter() {
+        for captures in pattern.captures_iter(stderr) {
+            let name = captures[1].to_string();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Directories that would let the wrappers find what they were missing
+///
+/// These are the directories the missing commands were found in on the
+/// current PATH, skipping any already in [`MINIMAL_PATH`].
+pub fn suggested_path_dirs(diagnoses: &[WrapperDiagnosis]) -> Vec<String> {
+    let minimal: Vec<PathBuf> = env::split_paths(MINIMAL_PATH).collect();
+    let mut dirs: Vec<String> = Vec::new();
+    for found in diagnoses
+        .iter()
+        .flat_map(|diagnosis| &diagnosis.missing)
+        .filter_map(|missing| missing.found_at.as_deref())
+    {
+        let Some(dir) = found.parent() else {
+            continue;
+        };
+        let dir_string = dir.to_string_lossy().to_string();
+        if !minimal.iter().any(|known| known == dir) && !dirs.contains(&dir_string) {
+            dirs.push(dir_string);
+        }
+    }
+    dirs
+}
+
+/// Add directories to `[wrappers] path` and regenerate the installed wrappers
+///
+/// Directories already listed are kept in place; new ones are appended.
+/// Only Catalyst's own wrappers that already exist for `platform` are
+/// rewritten.
+///
+/// # Returns
+///
+/// Returns the names of the regenerated wrappers
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if a directory can't be embedded in a wrapper
+/// (see `wrappers.path` in the config module), or the error from writing the
+/// config or a wrapper
+pub fn add_wrapper_path(
+    target_dir: &Path,
+    dirs: &[String],
+    platform: Platform,
+) -> Result<Vec<String>> {
+    let mut path = CatalystConfig::load(target_dir)?.wrappers.path;
+    for dir in dirs {
+        if !path.contains(dir) {
+            path.push(dir.clone());
+        }
+    }
+    let value = toml::Value::Array(path.into_iter().map(toml::Value::String).collect());
+    config::set_value(target_dir, "wrappers.path", &value.to_string())?;
+
+    let mut regenerated = Vec::new();
+    for wrapper in catalyst_wrappers(target_dir, platform) {
+        fix_hook_wrapper(target_dir, &wrapper, platform)?;
+        regenerated.push(wrapper);
+    }
+    Ok(regenerated)
+}
+
+/// Installed wrappers for `platform` that Catalyst generated
+///
+/// Hand-written wrappers in .claude/hooks are left alone.
+fn catalyst_wrappers(target_dir: &Path, platform: Platform) -> Vec<String> {
+    let hooks_dir = target_dir.join(HOOKS_DIR);
+    HOOK_BINARIES
+        .iter()
+        .map(|(name, _)| format!("{}.{}", name, platform.hook_extension()))
+        .filter(|wrapper| hooks_dir.join(wrapper).is_file())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_commands_from_shell_messages() {
+        let stderr = "/p/.claude/hooks/cargo-check.sh: line 4: cargo: command not found\n\
+                      sh: 1: node: not found\n\
+                      Error: file-analyzer binary not found\n\
+                      cargo: command not found\n";
+        assert_eq!(
+            missing_commands(stderr),
+            vec!["cargo", "node", "file-analyzer"]
+        );
+        assert!(missing_commands("error: invalid JSON input").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_diagnose_and_add_wrapper_path() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let hooks_dir = target.join(HOOKS_DIR);
+        fs::create_dir_all(&hooks_dir).unwrap();
+        // Stands in for a wrapper whose hook shells out to a tool
+        fs::write(
+            hooks_dir.join("cargo-check.sh"),
+            "#!/bin/bash\ncatalyst-test-missing-tool --version\n",
+        )
+        .unwrap();
+        fs::write(hooks_dir.join("custom.sh"), "#!/bin/bash\nexit 0\n").unwrap();
+
+        let diagnoses = diagnose_wrappers(target, Platform::Linux).unwrap();
+        assert_eq!(diagnoses.len(), 2);
+        assert!(diagnoses[1].reachable());
+        assert!(!diagnoses[0].reachable());
+        assert_eq!(diagnoses[0].missing[0].name, "catalyst-test-missing-tool");
+        assert_eq!(diagnoses[0].missing[0].found_at, None);
+        assert!(suggested_path_dirs(&diagnoses).is_empty());
+
+        let regenerated =
+            add_wrapper_path(target, &["/opt/tools/bin".to_string()], Platform::Linux).unwrap();
+        assert_eq!(regenerated, vec!["cargo-check.sh"]);
+        let content = fs::read_to_string(hooks_dir.join("cargo-check.sh")).unwrap();
+        assert!(content.contains("export PATH='/opt/tools/bin':\"$PATH\""));
+        assert_eq!(
+            fs::read_to_string(hooks_dir.join("custom.sh")).unwrap(),
+            "#!/bin/bash\nexit 0\n"
+        );
+        assert_eq!(
+            CatalystConfig::load(target).unwrap().wrappers.path,
+            vec!["/opt/tools/bin"]
+        );
+    }
+}
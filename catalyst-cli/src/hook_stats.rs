@@ -0,0 +1,307 @@
+//! Hook timing telemetry (`catalyst stats hooks`)
+//!
+//! Every hook binary times itself with [`HookTimer`] and records one row per
+//! invocation in the `hook_runs` table of the tracker database (see
+//! [`crate::tracker`]): how long it took, whether it let Claude carry on,
+//! blocked, or failed, and a short detail such as how many skills matched.
+//! [`summarize`] turns those rows into p50/p95 latencies per hook, so a hook
+//! that slows sessions down is easy to spot.
+//!
+//! Recording needs the `sqlite` feature; without it [`HookTimer::finish`]
+//! does nothing. A failure to record is logged and never affects the hook.
+//! Runs started by `catalyst doctor --hook-env` carry
+//! [`DIAGNOSTIC_RUN_ENV`] and are not recorded, so probing wrappers with an
+//! empty payload doesn't show up as hook errors.
+
+use catalyst_core::hooks::{Decision, HookResponse, PermissionDecision};
+use serde::Serialize;
+use std::fmt;
+use std::time::Instant;
+#[cfg(feature = "sqlite")]
+use {
+    crate::tracker,
+    crate::types::{CatalystError, Result},
+    chrono::{DateTime, Utc},
+    rusqlite::params,
+    std::collections::BTreeMap,
+    std::path::Path,
+};
+
+/// Set for hook runs that only check a wrapper can start; they are not
+/// recorded
+pub const DIAGNOSTIC_RUN_ENV: &str = "CATALYST_HOOK_DIAGNOSTIC";
+
+/// How a hook invocation ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HookOutcome {
+    /// Claude carried on
+    Ok,
+    /// The hook blocked or denied
+    Blocked,
+    /// The hook failed
+    Error,
+}
+
+impl HookOutcome {
+    /// Outcome of a hook that printed `response`, or nothing
+    pub fn of(response: Option<&HookResponse>) -> Self {
+        let Some(response) = response else {
+            return HookOutcome::Ok;
+        };
+        let denied = response
+            .hook_specific_output
+            .as_ref()
+            .is_some_and(|output| output.permission_decision == Some(PermissionDecision::Deny));
+        if denied || response.decision == Some(Decision::Block) {
+            HookOutcome::Blocked
+        } else {
+            HookOutcome::Ok
+        }
+    }
+
+    /// Name stored in the database
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HookOutcome::Ok => "ok",
+            HookOutcome::Blocked => "blocked",
+            HookOutcome::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for HookOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Times one hook invocation; [`HookTimer::finish`] records it
+#[derive(Debug)]
+pub struct HookTimer {
+    hook: &'static str,
+    started: Instant,
+}
+
+impl HookTimer {
+    /// Start timing `hook` (its binary name, such as `cargo-check`)
+    pub fn start(hook: &'static str) -> Self {
+        Self {
+            hook,
+            started: Instant::now(),
+        }
+    }
+
+    /// Record the invocation in the tracker database, unless
+    /// [`DIAGNOSTIC_RUN_ENV`] is set
+    ///
+    /// # Arguments
+    ///
+    /// * `outcome` - How the hook ended
+    /// * `detail` - Short note on what the hook did, such as "2 skills matched"
+    pub fn finish(self, outcome: HookOutcome, detail: Option<String>) {
+        #[cfg(feature = "sqlite")]
+        {
+            if std::env::var_os(DIAGNOSTIC_RUN_ENV).is_some() {
+                return;
+            }
+            let run = HookRun {
+                hook: self.hook.to_string(),
+                timestamp: Utc::now().to_rfc3339(),
+                duration_ms: self.started.elapsed().as_millis() as u64,
+                outcome,
+                detail,
+            };
+            let result = crate::modification_store::state_dir().and_then(|dir| record(&dir, &run));
+            if let Err(e) = result {
+                tracing::debug!(hook = self.hook, error = %e, "Could not record hook run");
+            }
+        }
+        #[cfg(not(feature = "sqlite"))]
+        let _ = (self.hook, self.started, outcome, detail);
+    }
+}
+
+/// One recorded hook invocation (a row of `hook_runs`)
+#[cfg(feature = "sqlite")]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HookRun {
+    pub hook: String,
+    /// When the hook finished (RFC 3339)
+    pub timestamp: String,
+    pub duration_ms: u64,
+    pub outcome: HookOutcome,
+    pub detail: Option<String>,
+}
+
+/// Latency and outcome counts for one hook
+#[cfg(feature = "sqlite")]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HookStats {
+    pub hook: String,
+    pub runs: usize,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub max_ms: u64,
+    pub blocked: usize,
+    pub errors: usize,
+}
+
+/// Add a run to the tracker database in `state_dir`
+///
+/// # Errors
+///
+/// Returns `SessionDatabase` if the database can't be opened or written
+#[cfg(feature = "sqlite")]
+pub fn record(state_dir: &Path, run: &HookRun) -> Result<()> {
+    let conn = tracker::open_tracker_db(state_dir)?;
+    conn.execute(
+        "INSERT INTO hook_runs (hook, timestamp, duration_ms, outcome, detail) \
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            run.hook,
+            run.timestamp,
+            run.duration_ms as i64,
+            run.outcome.as_str(),
+            run.detail
+        ],
+    )
+    .map_err(|e| CatalystError::SessionDatabase(format!("recording hook run: {}", e)))?;
+    Ok(())
+}
+
+/// Stats for every hook with runs on or after `since`, slowest p95 first
+///
+/// # Errors
+///
+/// Returns `SessionDatabase` if the database can't be read
+#[cfg(feature = "sqlite")]
+pub fn summarize(state_dir: &Path, since: Option<DateTime<Utc>>) -> Result<Vec<HookStats>> {
+    let Some(conn) = tracker::open_tracker_db_read_only(state_dir)? else {
+        return Ok(Vec::new());
+    };
+    let to_error = |e: rusqlite::Error| CatalystError::SessionDatabase(e.to_string());
+    let since = since.map(|since| since.to_rfc3339()).unwrap_or_default();
+
+    // Databases written before hook timing existed have no hook_runs table
+    let has_table: bool = conn
+        .query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'hook_runs')",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(to_error)?;
+    if !has_table {
+        return Ok(Vec::new());
+    }
+
+    let mut statement = conn
+        .prepare("SELECT hook, duration_ms, outcome FROM hook_runs WHERE timestamp >= ?1")
+        .map_err(to_error)?;
+    let rows = statement
+        .query_map(params![since], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(to_error)?;
+
+    let mut by_hook: BTreeMap<String, (Vec<u64>, usize, usize)> = BTreeMap::new();
+    for row in rows {
+        let (hook, duration_ms, outcome) = row.map_err(to_error)?;
+        let (durations, blocked, errors) = by_hook.entry(hook).or_default();
+        durations.push(duration_ms.max(0) as u64);
+        match outcome.as_str() {
+            "blocked" => *blocked += 1,
+            "error" => *errors += 1,
+            _ => {}
+        }
+    }
+
+    let mut stats: Vec<HookStats> = by_hook
+        .into_iter()
+        .map(|(hook, (mut durations, blocked, errors))| {
+            durations.sort_unstable();
+            HookStats {
+                hook,
+                runs: durations.len(),
+                p50_ms: percentile(&durations, 50),
+                p95_ms: percentile(&durations, 95),
+                max_ms: durations.last().copied().unwrap_or_default(),
+                blocked,
+                errors,
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| b.p95_ms.cmp(&a.p95_ms).then(a.hook.cmp(&b.hook)));
+    Ok(stats)
+}
+
+/// Nearest-rank percentile of sorted values
+#[cfg(feature = "sqlite")]
+fn percentile(sorted: &[u64], pct: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use catalyst_core::hooks::HookDecision;
+    use catalyst_core::settings::HookEvent;
+
+    #[test]
+    fn test_outcome_of_response() {
+        assert_eq!(HookOutcome::of(None), HookOutcome::Ok);
+        let denied = HookResponse::builder(HookEvent::PreToolUse)
+            .decision(HookDecision::Block)
+            .build();
+        assert_eq!(HookOutcome::of(Some(&denied)), HookOutcome::Blocked);
+        let suggested = HookResponse::builder(HookEvent::PostToolUse)
+            .decision(HookDecision::Suggest)
+            .additional_context("slow")
+            .build();
+        assert_eq!(HookOutcome::of(Some(&suggested)), HookOutcome::Ok);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_record_and_summarize() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let state_dir = temp_dir.path();
+        assert!(summarize(state_dir, None).unwrap().is_empty());
+
+        for (hook, duration_ms, outcome) in [
+            ("cargo-check", 900, HookOutcome::Blocked),
+            ("cargo-check", 1200, HookOutcome::Ok),
+            ("skill-activation-prompt", 4, HookOutcome::Ok),
+            ("skill-activation-prompt", 6, HookOutcome::Ok),
+            ("skill-activation-prompt", 40, HookOutcome::Error),
+        ] {
+            let run = HookRun {
+                hook: hook.to_string(),
+                timestamp: Utc::now().to_rfc3339(),
+                duration_ms,
+                outcome,
+                detail: None,
+            };
+            record(state_dir, &run).unwrap();
+        }
+
+        let stats = summarize(state_dir, None).unwrap();
+        assert_eq!(stats[0].hook, "cargo-check");
+        assert_eq!((stats[0].p50_ms, stats[0].p95_ms), (900, 1200));
+        assert_eq!(stats[0].blocked, 1);
+        assert_eq!(stats[1].runs, 3);
+        assert_eq!((stats[1].p50_ms, stats[1].max_ms), (6, 40));
+        assert_eq!(stats[1].errors, 1);
+
+        let later = Utc::now() + chrono::Duration::hours(1);
+        assert!(summarize(state_dir, Some(later)).unwrap().is_empty());
+    }
+}
@@ -3,18 +3,39 @@
 //! This module handles the `catalyst init` command, which creates the .claude/
 //! directory structure, installs hooks, and sets up skills.
 
+use crate::cancel;
+use crate::config::{self, CatalystConfig, SkillSource};
+use crate::journal::InitJournal;
+use crate::library;
+use crate::lock::OperationLock;
+use crate::lockfile::{self, Lockfile};
+use crate::modification_store::TrackerBackend;
+use crate::powershell;
+use crate::profiles;
+use crate::progress::{self, ProgressEvent};
+use crate::safe_path;
+use crate::selinux;
+use crate::signals;
+use crate::stack;
+use crate::template::{self, TemplateVars};
+use crate::timings;
 use crate::types::{
-    CatalystError, InitConfig, InitReport, Platform, Result, AGENTS_DIR, AVAILABLE_SKILLS,
-    CATALYST_VERSION, CLAUDE_DIR, COMMANDS_DIR, HOOKS_DIR, SKILLS_DIR, VERSION_FILE,
+    CatalystError, FailedSkill, InitConfig, InitReport, Platform, Result, SkillSelection,
+    StrictSkills, AGENTS_DIR, AVAILABLE_SKILLS, BACKUP_DIR, CATALYST_VERSION, CLAUDE_DIR,
+    COMMANDS_DIR, CONFIG_FILE, HASHES_FILE, HOOKS_DIR, LOCK_FILE, SETTINGS_FILE, SKILLS_DIR,
+    SKILL_MANIFEST_FILE, SKILL_RULES_FILE, VERSION_FILE,
 };
+use catalyst_core::fs_ops::{self, Fs, PermissionPolicy};
+use catalyst_core::settings::{ClaudeSettings, Hook, HookConfig, HookEvent};
+use catalyst_core::skill_manifest::{Activation, SkillManifest, SKILL_FILE, SKILL_TEMPLATE_FILE};
 use include_dir::{include_dir, Dir};
-use indicatif::{ProgressBar, ProgressStyle};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::{self, IsTerminal, Write};
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
-use std::process;
 use tempfile::NamedTempFile;
 
 #[cfg(unix)]
@@ -27,216 +48,6 @@ const WRAPPER_TEMPLATE_PS1: &str = include_str!("../resources/wrapper-template.p
 // Embed skills directory at compile time
 static SKILLS: Dir = include_dir!("$CARGO_MANIFEST_DIR/../.claude/skills");
 
-/// Lock file name for concurrent init protection
-const LOCK_FILE: &str = ".catalyst.lock";
-
-/// EXDEV error code (cross-device link) on Unix systems
-#[cfg(unix)]
-const EXDEV: i32 = 18;
-
-/// Guard that automatically releases the lock when dropped
-///
-/// # Lock Cleanup Guarantee
-///
-/// The lock is **automatically released** when this guard is dropped, even if
-/// initialization fails or panics. This RAII pattern ensures that:
-/// - Lock files are never leaked on normal program exit
-/// - Subsequent initialization attempts can proceed after errors
-/// - No manual cleanup is required in error handling paths
-///
-/// The Drop trait makes lock cleanup exception-safe and foolproof.
-pub struct InitLock {
-    lock_file: PathBuf,
-}
-
-impl Drop for InitLock {
-    fn drop(&mut self) {
-        let _ = release_init_lock(&self.lock_file);
-    }
-}
-
-/// Helper function to atomically create a lock file and write PID
-///
-/// # Arguments
-///
-/// * `lock_file` - Path to the lock file
-/// * `pid` - Process ID to write to the lock file
-///
-/// # Returns
-///
-/// Returns an `InitLock` guard or an I/O error
-fn try_create_lock_file(lock_file: &Path, pid: u32) -> Result<InitLock> {
-    use std::io::Write;
-
-    let mut file = fs::OpenOptions::new()
-        .write(true)
-        .create_new(true) // Atomic check-and-create
-        .open(lock_file)
-        .map_err(CatalystError::Io)?;
-
-    write!(file, "{}", pid).map_err(CatalystError::Io)?;
-
-    Ok(InitLock {
-        lock_file: lock_file.to_path_buf(),
-    })
-}
-
-/// Acquire a lock to prevent concurrent init operations
-///
-/// Creates a .catalyst.lock file with the current process ID using atomic file creation.
-/// Returns an error if a lock already exists and the process is still running.
-///
-/// # Arguments
-///
-/// * `target_dir` - The directory being initialized
-///
-/// # Returns
-///
-/// Returns an `InitLock` guard that will automatically release the lock when dropped
-///
-/// # Concurrency Safety
-///
-/// Uses atomic file creation (O_EXCL on Unix, CREATE_NEW on Windows) to prevent
-/// race conditions where two processes might both acquire the lock.
-pub fn acquire_init_lock(target_dir: &Path) -> Result<InitLock> {
-    let lock_file = target_dir.join(LOCK_FILE);
-    let current_pid = process::id();
-
-    // Try to atomically create the lock file
-    // This prevents TOCTOU race conditions
-    match try_create_lock_file(&lock_file, current_pid) {
-        Ok(lock) => Ok(lock),
-        Err(CatalystError::Io(e)) if e.kind() == std::io::ErrorKind::AlreadyExists => {
-            // Lock file exists - check if it's stale
-            let pid_str = fs::read_to_string(&lock_file).map_err(CatalystError::Io)?;
-
-            match pid_str.trim().parse::<u32>() {
-                Ok(pid) if is_valid_pid(pid) => {
-                    // Valid PID - check if process is still running
-                    if is_process_running(pid) {
-                        Err(CatalystError::InitInProgress {
-                            pid,
-                            lock_file: lock_file.display().to_string(),
-                        })
-                    } else {
-                        // Stale lock file - remove and retry once
-                        fs::remove_file(&lock_file).map_err(CatalystError::Io)?;
-
-                        // NOTE: There is a small TOCTOU race window between remove_file and
-                        // try_create_lock_file where another process could create the lock.
-                        // This is acceptable because:
-                        // 1. The window is extremely small (microseconds)
-                        // 2. If it happens, try_create_lock_file will fail with AlreadyExists,
-                        //    causing this init to fail cleanly
-                        // 3. The race is rare in practice (requires precise timing)
-                        // 4. The failure is safe - no corruption or data loss
-
-                        // Retry lock acquisition (non-recursive)
-                        try_create_lock_file(&lock_file, current_pid)
-                    }
-                }
-                _ => {
-                    // Invalid PID (0, 1, current, or parse error) - treat as stale
-                    fs::remove_file(&lock_file).map_err(CatalystError::Io)?;
-
-                    // NOTE: Known TOCTOU race window here (see comment above)
-                    // Retry lock acquisition
-                    try_create_lock_file(&lock_file, current_pid)
-                }
-            }
-        }
-        Err(e) => Err(e),
-    }
-}
-
-/// Validate that a PID is reasonable
-///
-/// Returns false for:
-/// - PID 0 (invalid)
-/// - PID 1 (system process, likely malicious lock file)
-///
-/// Note: We intentionally allow checking our own PID. If the lock file contains
-/// our PID, we'll check is_process_running() which will return true, causing
-/// the lock acquisition to fail with InitInProgress. This prevents the same
-/// process from acquiring the lock twice.
-fn is_valid_pid(pid: u32) -> bool {
-    pid != 0 && pid != 1
-}
-
-/// Release the init lock
-fn release_init_lock(lock_file: &Path) -> Result<()> {
-    if lock_file.exists() {
-        fs::remove_file(lock_file).map_err(CatalystError::Io)?;
-    }
-    Ok(())
-}
-
-/// Check if a process is running on the current system
-///
-/// # Platform-specific behavior
-///
-/// - **Unix/Linux/macOS**: Uses `kill -0 pid` to check if process exists
-/// - **Windows**: Uses OpenProcess to check if process exists
-#[cfg(unix)]
-fn is_process_running(pid: u32) -> bool {
-    use std::process::Command;
-
-    // On Unix, use kill -0 to check if process exists
-    Command::new("kill")
-        .arg("-0")
-        .arg(pid.to_string())
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
-}
-
-#[cfg(windows)]
-fn is_process_running(pid: u32) -> bool {
-    use windows_sys::Win32::Foundation::{
-        CloseHandle, GetLastError, ERROR_ACCESS_DENIED, ERROR_INVALID_PARAMETER,
-    };
-    use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
-
-    // Try to open the process with minimal access rights
-    //
-    // NOTE: We could use GetExitCodeProcess for more certainty, but OpenProcess
-    // with PROCESS_QUERY_LIMITED_INFORMATION is sufficient and requires minimal
-    // permissions. The conservative error handling (assume exists on unknown errors)
-    // provides adequate safety for lock file cleanup.
-    //
-    // SAFETY: This is safe because we're just checking if a process exists
-    // and we immediately close the handle if successful
-    unsafe {
-        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
-
-        if handle == 0 {
-            // Failed to open process - check why
-            let error = GetLastError();
-
-            // Explicitly handle known error cases:
-            // - ERROR_INVALID_PARAMETER (87): Process definitely doesn't exist
-            // - ERROR_ACCESS_DENIED (5): Process exists but is protected (system/elevated)
-            // - Other errors: Conservatively assume process exists to avoid stale lock cleanup race
-            //
-            // This conservative approach prevents accidentally cleaning up locks for
-            // running processes in edge cases (network errors, permission issues, etc.)
-            match error {
-                ERROR_INVALID_PARAMETER => false, // Process doesn't exist
-                ERROR_ACCESS_DENIED => true,      // Process exists but protected
-                _ => {
-                    // Unknown error - be conservative and assume process exists
-                    // This prevents false positives that could cause concurrent init
-                    true
-                }
-            }
-        } else {
-            // Successfully opened - process exists
-            CloseHandle(handle);
-            true
-        }
-    }
-}
-
 /// Create the .claude subdirectory structure
 ///
 /// First checks that .claude/ exists (created by Claude Code).
@@ -264,6 +75,47 @@ fn is_process_running(pid: u32) -> bool {
 pub fn create_directory_structure(target_dir: &Path, force: bool) -> Result<Vec<String>> {
     let mut created_dirs = Vec::new();
 
+    for dir in directories_to_create(target_dir, force)? {
+        let dir_path = target_dir.join(dir);
+        let existed = dir_path.is_dir();
+
+        // Create directory
+        fs::create_dir_all(&dir_path).map_err(CatalystError::Io)?;
+
+        // Verify directory was created successfully
+        if !dir_path.exists() || !dir_path.is_dir() {
+            return Err(CatalystError::InvalidPath(format!(
+                "Failed to create directory: {}",
+                dir_path.display()
+            )));
+        }
+
+        // Set permissions on Unix (only on directories we created, so a
+        // shared group-writable setup survives --force)
+        #[cfg(unix)]
+        if !existed {
+            let permissions = fs::Permissions::from_mode(0o755);
+            fs::set_permissions(&dir_path, permissions).map_err(CatalystError::Io)?;
+        }
+
+        created_dirs.push(dir.to_string());
+    }
+
+    Ok(created_dirs)
+}
+
+/// Determine which .claude subdirectories need to be created
+///
+/// Shared by [`create_directory_structure`] and dry-run planning so both
+/// apply the same checks.
+///
+/// # Errors
+///
+/// Returns an error if .claude is missing or a subdirectory path exists but
+/// is not a directory.
+fn directories_to_create(target_dir: &Path, force: bool) -> Result<Vec<&'static str>> {
+    let mut to_create = Vec::new();
+
     // First, verify .claude directory exists (created by Claude Code)
     let claude_dir = target_dir.join(CLAUDE_DIR);
     if !claude_dir.exists() {
@@ -306,28 +158,10 @@ pub fn create_directory_structure(target_dir: &Path, force: bool) -> Result<Vec<
             continue;
         }
 
-        // Create directory
-        fs::create_dir_all(&dir_path).map_err(CatalystError::Io)?;
-
-        // Verify directory was created successfully
-        if !dir_path.exists() || !dir_path.is_dir() {
-            return Err(CatalystError::InvalidPath(format!(
-                "Failed to create directory: {}",
-                dir_path.display()
-            )));
-        }
-
-        // Set permissions on Unix
-        #[cfg(unix)]
-        {
-            let permissions = fs::Permissions::from_mode(0o755);
-            fs::set_permissions(&dir_path, permissions).map_err(CatalystError::Io)?;
-        }
-
-        created_dirs.push(dir.to_string());
+        to_create.push(dir);
     }
 
-    Ok(created_dirs)
+    Ok(to_create)
 }
 
 /// Generate wrapper scripts for hooks
@@ -341,7 +175,9 @@ pub fn create_directory_structure(target_dir: &Path, force: bool) -> Result<Vec<
 /// * `target_dir` - Base directory where .claude/hooks/ exists
 /// * `install_hooks` - Whether to install skill-activation-prompt wrapper
 /// * `install_tracker` - Whether to install file-change-tracker wrapper
+/// * `install_check_hook` - Whether to install the cargo-check wrapper
 /// * `platform` - Target platform for wrapper generation
+/// * `policy` - Whether existing wrappers keep their mode and ownership
 ///
 /// # Returns
 ///
@@ -350,56 +186,167 @@ pub fn generate_wrapper_scripts(
     target_dir: &Path,
     install_hooks: bool,
     install_tracker: bool,
+    install_check_hook: bool,
     platform: Platform,
+    policy: PermissionPolicy,
 ) -> Result<Vec<String>> {
     let mut installed = Vec::new();
     let hooks_dir = target_dir.join(HOOKS_DIR);
+    let project_config = CatalystConfig::load(target_dir)?;
+
+    for (binary_name, wrapper_name) in planned_wrappers(
+        install_hooks,
+        install_tracker,
+        install_check_hook,
+        project_config.tracker.backend,
+        platform,
+    ) {
+        let content = render_wrapper(binary_name, platform, &project_config, target_dir)?;
+        write_wrapper(&hooks_dir, &wrapper_name, &content, platform, policy)?;
+        installed.push(wrapper_name);
+    }
+
+    Ok(installed)
+}
 
-    // Determine which template to use based on platform
-    let (template, extension) = match platform {
-        Platform::Windows => (WRAPPER_TEMPLATE_PS1, "ps1"),
-        _ => (WRAPPER_TEMPLATE_SH, "sh"),
+/// Write one rendered wrapper script into the hooks directory
+fn write_wrapper(
+    hooks_dir: &Path,
+    wrapper_name: &str,
+    content: &str,
+    platform: Platform,
+    policy: PermissionPolicy,
+) -> Result<()> {
+    let wrapper_path = hooks_dir.join(wrapper_name);
+
+    // Shell wrappers must be executable; PowerShell runs scripts by path
+    let mode = match platform {
+        Platform::Windows => fs_ops::DEFAULT_FILE_MODE,
+        _ => fs_ops::DEFAULT_EXECUTABLE_MODE,
     };
+    Fs::read_write()
+        .write_file(&wrapper_path, content.as_bytes(), mode, policy)
+        .map_err(CatalystError::Io)?;
 
-    // Generate skill-activation-prompt wrapper
-    if install_hooks {
-        let binary_name = "skill-activation-prompt";
-        let wrapper_name = format!("{}.{}", binary_name, extension);
-        let wrapper_path = hooks_dir.join(&wrapper_name);
+    // Not fatal: status reports the wrapper as mislabeled if this fails
+    if let Err(e) = selinux::copy_context(hooks_dir, &wrapper_path) {
+        progress::warn(e.to_string());
+    }
 
-        let content = template.replace("{{BINARY_NAME}}", binary_name);
-        fs::write(&wrapper_path, content).map_err(CatalystError::Io)?;
+    Ok(())
+}
 
-        // Set executable permission on Unix
-        #[cfg(unix)]
-        if matches!(platform, Platform::Linux | Platform::MacOS | Platform::WSL) {
-            let permissions = fs::Permissions::from_mode(0o755);
-            fs::set_permissions(&wrapper_path, permissions).map_err(CatalystError::Io)?;
-        }
+/// Fill in the wrapper template for a hook binary
+///
+/// `[wrappers] path` in catalyst.toml becomes a preamble that prepends
+/// those directories to PATH before looking for anything, since Claude Code
+/// runs hooks with a minimal PATH. Entries are validated when the config is
+/// loaded. Besides `BINARY_NAME` and `PATH_PREAMBLE`, the template can use
+/// the variables from [`template::project_vars`].
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the template uses an undefined variable
+pub(crate) fn render_wrapper(
+    binary_name: &str,
+    platform: Platform,
+    config: &CatalystConfig,
+    project_dir: &Path,
+) -> Result<String> {
+    let extra_path = &config.wrappers.path;
+    let (template, preamble) = match platform {
+        Platform::Windows if extra_path.is_empty() => (WRAPPER_TEMPLATE_PS1, String::new()),
+        Platform::Windows => (
+            WRAPPER_TEMPLATE_PS1,
+            format!(
+                "# Directories from [wrappers] path in catalyst.toml\n$env:PATH = '{};' + $env:PATH\n\n",
+                extra_path.join(";")
+            ),
+        ),
+        _ if extra_path.is_empty() => (WRAPPER_TEMPLATE_SH, String::new()),
+        _ => (
+            WRAPPER_TEMPLATE_SH,
+            format!(
+                "# Directories from [wrappers] path in catalyst.toml\nexport PATH='{}':\"$PATH\"\n\n",
+                extra_path.join(":")
+            ),
+        ),
+    };
 
-        installed.push(wrapper_name);
+    let mut vars = template::project_vars(project_dir, config);
+    vars.insert("BINARY_NAME".to_string(), binary_name.to_string());
+    vars.insert("PATH_PREAMBLE".to_string(), preamble);
+    template::render(template, &vars)
+}
+
+/// List the wrapper scripts to generate as (binary name, wrapper file name)
+///
+/// The tracker wrapper keeps its name whichever binary `tracker_backend`
+/// picks, so settings.json and status checks don't change with it.
+fn planned_wrappers(
+    install_hooks: bool,
+    install_tracker: bool,
+    install_check_hook: bool,
+    tracker_backend: TrackerBackend,
+    platform: Platform,
+) -> Vec<(&'static str, String)> {
+    let extension = platform.hook_extension();
+    let mut wrappers = Vec::new();
+
+    // skill-activation-prompt wrapper
+    if install_hooks {
+        wrappers.push((
+            "skill-activation-prompt",
+            format!("skill-activation-prompt.{}", extension),
+        ));
     }
 
-    // Generate file-change-tracker wrapper
+    // file-change-tracker wrapper
     if install_tracker {
-        let binary_name = "file-change-tracker";
-        let wrapper_name = format!("{}.{}", binary_name, extension);
-        let wrapper_path = hooks_dir.join(&wrapper_name);
+        wrappers.push((
+            tracker_backend.hook_binary(),
+            format!("file-change-tracker.{}", extension),
+        ));
+    }
 
-        let content = template.replace("{{BINARY_NAME}}", binary_name);
-        fs::write(&wrapper_path, content).map_err(CatalystError::Io)?;
+    if install_check_hook {
+        wrappers.push(("cargo-check", format!("cargo-check.{}", extension)));
+    }
 
-        // Set executable permission on Unix
-        #[cfg(unix)]
-        if matches!(platform, Platform::Linux | Platform::MacOS | Platform::WSL) {
-            let permissions = fs::Permissions::from_mode(0o755);
-            fs::set_permissions(&wrapper_path, permissions).map_err(CatalystError::Io)?;
-        }
+    wrappers
+}
 
-        installed.push(wrapper_name);
-    }
+/// Tracker backend to generate the tracker wrapper for
+///
+/// `--tracker-backend` wins over `tracker.backend` in catalyst.toml. A
+/// different choice is saved there (or planned, when `journal` is None for
+/// a dry run) so `catalyst update` and `status --fix` later regenerate the
+/// wrapper for the same binary.
+fn save_tracker_backend(
+    config: &InitConfig,
+    project_config: &CatalystConfig,
+    journal: Option<&mut InitJournal>,
+    report: &mut InitReport,
+) -> Result<TrackerBackend> {
+    let configured = project_config.tracker.backend;
+    let backend = match config.tracker_backend {
+        Some(backend) if config.install_tracker && backend != configured => backend,
+        _ => return Ok(configured),
+    };
 
-    Ok(installed)
+    match journal {
+        Some(journal) => {
+            let path = CatalystConfig::find(&config.directory)
+                .unwrap_or_else(|| config.directory.join(CONFIG_FILE));
+            journal.record(path.strip_prefix(&config.directory).unwrap_or(&path))?;
+            config::set_value(&config.directory, "tracker.backend", &backend.to_string())?;
+        }
+        None => report.planned_actions.push(format!(
+            "Set tracker.backend = \"{}\" in {}",
+            backend, CONFIG_FILE
+        )),
+    }
+    Ok(backend)
 }
 
 /// Write content to a file atomically with fallback to regular write
@@ -419,16 +366,33 @@ pub fn generate_wrapper_scripts(
 /// Returns `Ok(true)` if atomic write succeeded, `Ok(false)` if fallback was used,
 /// or an error if both methods failed.
 pub fn write_file_atomic(path: &Path, content: &str) -> Result<bool> {
+    write_file_atomic_with_policy(path, content, PermissionPolicy::Preserve)
+}
+
+/// Write content to a file atomically, choosing what happens to an existing
+/// file's mode and ownership
+///
+/// See [`write_file_atomic`].
+pub fn write_file_atomic_with_policy(
+    path: &Path,
+    content: &str,
+    policy: PermissionPolicy,
+) -> Result<bool> {
     // Try atomic write first
-    match try_atomic_write(path, content) {
-        Ok(()) => Ok(true), // Atomic write succeeded
+    match try_atomic_write(path, content, policy) {
+        Ok(()) => {
+            progress::detail(|| format!("Wrote {}", path.display()));
+            Ok(true) // Atomic write succeeded
+        }
         Err(e) => {
             // Check if it's a cross-device link error or temp creation failure
-            if is_cross_device_error(&e) || is_temp_creation_error(&e) {
+            if fs_ops::is_cross_device(&e) || is_temp_creation_error(&e) {
                 // Fall back to regular write
-                eprintln!("⚠️  Atomic write not supported on this filesystem");
-                eprintln!("   Reason: {}", e);
-                eprintln!("   Falling back to regular write for: {}", path.display());
+                progress::warn(format!(
+                    "Atomic write not supported on this filesystem\n   Reason: {}\n   Falling back to regular write for: {}",
+                    e,
+                    path.display()
+                ));
 
                 fs::write(path, content).map_err(CatalystError::Io)?;
 
@@ -442,7 +406,7 @@ pub fn write_file_atomic(path: &Path, content: &str) -> Result<bool> {
 }
 
 /// Attempt atomic write using temp file + persist
-fn try_atomic_write(path: &Path, content: &str) -> std::io::Result<()> {
+fn try_atomic_write(path: &Path, content: &str, policy: PermissionPolicy) -> std::io::Result<()> {
     // Get parent directory for temp file
     let parent = path.parent().ok_or_else(|| {
         std::io::Error::new(
@@ -453,6 +417,7 @@ fn try_atomic_write(path: &Path, content: &str) -> std::io::Result<()> {
 
     // Create temp file in same directory
     let mut temp_file = NamedTempFile::new_in(parent)?;
+    let _cleanup = signals::CleanupGuard::new(temp_file.path());
 
     // Write content
     temp_file.write_all(content.as_bytes())?;
@@ -460,26 +425,13 @@ fn try_atomic_write(path: &Path, content: &str) -> std::io::Result<()> {
     // Flush to disk
     temp_file.flush()?;
 
-    // Atomically persist (rename) to final location
-    temp_file.persist(path)?;
+    // Atomically persist (rename) to final location, copying within the
+    // target directory if the rename crosses a device boundary
+    Fs::read_write().persist_with_policy(temp_file, path, fs_ops::DEFAULT_FILE_MODE, policy)?;
 
     Ok(())
 }
 
-/// Check if error is a cross-device link error (EXDEV)
-fn is_cross_device_error(e: &std::io::Error) -> bool {
-    #[cfg(unix)]
-    {
-        e.raw_os_error() == Some(EXDEV)
-    }
-
-    #[cfg(not(unix))]
-    {
-        // On non-Unix systems, check error kind
-        matches!(e.kind(), std::io::ErrorKind::Other)
-    }
-}
-
 /// Check if error is related to temp file creation
 fn is_temp_creation_error(e: &std::io::Error) -> bool {
     matches!(
@@ -501,7 +453,11 @@ fn is_temp_creation_error(e: &std::io::Error) -> bool {
 /// * `target_dir` - Base directory where .claude/ exists
 /// * `install_hooks` - Whether to add skill-activation-prompt hook
 /// * `install_tracker` - Whether to add file-change-tracker hook
+/// * `install_check_hook` - Whether to add the cargo-check hook
 /// * `platform` - Target platform (determines file extension)
+/// * `policy` - Whether an existing settings.json keeps its mode and ownership
+/// * `extra` - Other top-level keys to write (e.g., an init profile's
+///   `permissions`); a `hooks` key here is ignored
 ///
 /// # Returns
 ///
@@ -510,135 +466,239 @@ pub fn create_settings_json(
     target_dir: &Path,
     install_hooks: bool,
     install_tracker: bool,
+    install_check_hook: bool,
     platform: Platform,
+    policy: PermissionPolicy,
+    extra: &serde_json::Map<String, serde_json::Value>,
 ) -> Result<bool> {
     let settings_path = target_dir.join(".claude/settings.json");
 
-    // Determine wrapper extension
-    let extension = platform.hook_extension();
-
-    // Build hooks array
-    let mut hooks = Vec::new();
-
-    // Add skill-activation-prompt hook
-    if install_hooks {
-        hooks.push(serde_json::json!({
-            "event": "UserPromptSubmit",
-            "script": format!("$CLAUDE_PROJECT_DIR/.claude/hooks/skill-activation-prompt.{}", extension),
-            "async": false
-        }));
-    }
-
-    // Add file-change-tracker hook
-    if install_tracker {
-        hooks.push(serde_json::json!({
-            "event": "PostToolUse",
-            "script": format!("$CLAUDE_PROJECT_DIR/.claude/hooks/file-change-tracker.{}", extension),
-            "async": false,
-            "matchers": [
-                {
-                    "toolName": "Write"
-                },
-                {
-                    "toolName": "Edit"
-                },
-                {
-                    "toolName": "MultiEdit"
-                }
-            ]
-        }));
+    // Start from the extra keys, so a profile's permissions land in the
+    // typed fields Claude Code reads
+    let config = CatalystConfig::load(target_dir)?;
+    let mut extra = extra.clone();
+    extra.remove("hooks");
+    let mut settings: ClaudeSettings = serde_json::from_value(serde_json::Value::Object(extra))
+        .map_err(|e| {
+            CatalystError::InvalidConfig(format!("Invalid settings for {}: {}", SETTINGS_FILE, e))
+        })?;
+    for (event, hook_config) in settings_hooks(
+        install_hooks,
+        install_tracker,
+        install_check_hook,
+        platform,
+        &config,
+    ) {
+        settings
+            .add_hook(event, hook_config)
+            .map_err(|e| CatalystError::InvalidConfig(format!("{:#}", e)))?;
     }
 
-    // Create settings JSON
-    let settings = serde_json::json!({
-        "hooks": hooks
-    });
-
     // Pretty-print JSON
     let content = serde_json::to_string_pretty(&settings).map_err(CatalystError::Json)?;
 
     // Write atomically
-    write_file_atomic(&settings_path, &content)?;
+    write_file_atomic_with_policy(&settings_path, &content, policy)?;
 
     Ok(true)
 }
 
+/// Matcher a Catalyst hook is registered with in settings.json
+///
+/// PostToolUse hooks only care about the file-editing tools.
+pub(crate) fn hook_matcher(event: HookEvent) -> Option<String> {
+    match event {
+        HookEvent::PostToolUse => Some("Write|Edit|MultiEdit".to_string()),
+        _ => None,
+    }
+}
+
+/// Hooks init registers in settings.json, in Claude Code's hook schema
+///
+/// .ps1 wrappers are invoked through PowerShell (see
+/// [`powershell::settings_command`]).
+pub(crate) fn settings_hooks(
+    install_hooks: bool,
+    install_tracker: bool,
+    install_check_hook: bool,
+    platform: Platform,
+    config: &CatalystConfig,
+) -> Vec<(HookEvent, HookConfig)> {
+    let extension = platform.hook_extension();
+    let wrappers = [
+        (
+            install_hooks,
+            HookEvent::UserPromptSubmit,
+            "skill-activation-prompt",
+        ),
+        (
+            install_tracker,
+            HookEvent::PostToolUse,
+            "file-change-tracker",
+        ),
+        // Build and lint checks for edited files
+        (install_check_hook, HookEvent::PostToolUse, "cargo-check"),
+    ];
+    wrappers
+        .into_iter()
+        .filter(|(install, _, _)| *install)
+        .map(|(_, event, binary_name)| {
+            let command =
+                powershell::settings_command(&format!("{}.{}", binary_name, extension), config);
+            (
+                event,
+                HookConfig {
+                    matcher: hook_matcher(event),
+                    hooks: vec![Hook {
+                        r#type: "command".to_string(),
+                        command,
+                    }],
+                },
+            )
+        })
+        .collect()
+}
+
+/// Convert a hook entry written by an older init (`{event, script,
+/// matchers: [{toolName}]}`) into Claude Code's hook schema
+fn legacy_hook(entry: &serde_json::Value) -> Option<(HookEvent, HookConfig)> {
+    let event: HookEvent = entry.get("event")?.as_str()?.parse().ok()?;
+    let command = entry.get("script")?.as_str()?.to_string();
+    let tools: Vec<&str> = entry
+        .get("matchers")
+        .and_then(|matchers| matchers.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|matcher| matcher.get("toolName")?.as_str())
+        .collect();
+    let matcher = if tools.is_empty() {
+        hook_matcher(event)
+    } else {
+        Some(tools.join("|"))
+    };
+    Some((
+        event,
+        HookConfig {
+            matcher,
+            hooks: vec![Hook {
+                r#type: "command".to_string(),
+                command,
+            }],
+        },
+    ))
+}
+
 /// Install skills from embedded resources
 ///
 /// Extracts skills from the embedded SKILLS directory and installs them
-/// to the target `.claude/skills/` directory.
+/// to the target `.claude/skills/` directory, or links them from the skill
+/// library (see [`crate::library`]). With the `parallel` feature, skills are
+/// extracted concurrently and progress is reported as each one finishes;
+/// the returned list still keeps the order given.
 ///
 /// # Arguments
 ///
 /// * `target_dir` - Base directory where .claude exists
 /// * `skill_ids` - List of skill IDs to install
 /// * `force` - Whether to overwrite existing skill directories
+/// * `library` - Skill library to link skills from, or None to copy them
 ///
 /// # Returns
 ///
 /// Returns a list of successfully installed skill IDs
-pub fn install_skills(target_dir: &Path, skill_ids: &[String], force: bool) -> Result<Vec<String>> {
+pub fn install_skills(
+    target_dir: &Path,
+    skill_ids: &[String],
+    force: bool,
+    library: Option<&Path>,
+) -> Result<Vec<String>> {
+    install_skills_reporting_failures(target_dir, skill_ids, force, library)
+        .map(|(installed, _)| installed)
+}
+
+/// Install skills like [`install_skills`], also returning the ones that
+/// failed and why
+///
+/// # Returns
+///
+/// Returns the installed skill IDs and the failed skills, each in the
+/// order given
+pub fn install_skills_reporting_failures(
+    target_dir: &Path,
+    skill_ids: &[String],
+    force: bool,
+    library: Option<&Path>,
+) -> Result<(Vec<String>, Vec<FailedSkill>)> {
     let mut installed = Vec::new();
+    let mut failed = Vec::new();
 
-    // Skip progress bar if no skills to install
+    // Nothing to report if no skills to install
     if skill_ids.is_empty() {
-        return Ok(installed);
+        return Ok((installed, failed));
     }
 
-    // Only show progress bar if stdout is a terminal
-    let use_progress = io::stdout().is_terminal();
+    // Installing the same skill twice would race when done in parallel
+    let mut seen = HashSet::new();
+    let skill_ids: Vec<&String> = skill_ids.iter().filter(|id| seen.insert(*id)).collect();
 
-    let pb = if use_progress {
-        let pb = ProgressBar::new(skill_ids.len() as u64);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
-                // Template is static and valid, but use fallback as defensive programming
-                .unwrap_or_else(|_| ProgressStyle::default_bar())
-                .progress_chars("━━╸"),
-        );
-        Some(pb)
-    } else {
-        None
-    };
+    let reporter = progress::reporter();
+    // Worker threads don't share this thread's cancellation check
+    let cancellation = cancel::current();
+    reporter.report(ProgressEvent::Started {
+        total: skill_ids.len(),
+    });
 
-    for skill_id in skill_ids {
-        if let Some(ref pb) = pb {
-            pb.set_message(format!("Installing {}...", skill_id));
+    // Progress is reported as each skill finishes, whichever thread it ran on
+    let install_one = |skill_id: &&String| {
+        cancel::check(cancellation.as_ref())?;
+        reporter.report(ProgressEvent::ItemStarted {
+            item: skill_id,
+            message: format!("Installing {}", skill_id),
+        });
+        let result = install_skill(target_dir, skill_id, force, library);
+        match &result {
+            Ok(()) => reporter.report(ProgressEvent::ItemDone {
+                item: skill_id,
+                message: format!("Installed {}", skill_id),
+            }),
+            Err(e) => reporter.report(ProgressEvent::ItemFailed {
+                item: skill_id,
+                message: format!("Failed to install skill '{}': {}", skill_id, e),
+            }),
         }
+        result
+    };
 
-        match install_skill(target_dir, skill_id, force) {
-            Ok(()) => {
-                installed.push(skill_id.clone());
-                if pb.is_none() {
-                    // If no progress bar, print messages directly
-                    println!("  ✓ Installed {}", skill_id);
-                }
-            }
-            Err(e) => {
-                let error_msg = format!("⚠️  Failed to install skill '{}': {}", skill_id, e);
-                if let Some(ref pb) = pb {
-                    pb.println(error_msg);
-                } else {
-                    eprintln!("{}", error_msg);
-                }
-            }
-        }
+    #[cfg(feature = "parallel")]
+    let results: Vec<Result<()>> = skill_ids.par_iter().map(install_one).collect();
+    #[cfg(not(feature = "parallel"))]
+    let results: Vec<Result<()>> = skill_ids.iter().map(install_one).collect();
 
-        if let Some(ref pb) = pb {
-            pb.inc(1);
+    if results
+        .iter()
+        .any(|result| matches!(result, Err(CatalystError::Cancelled)))
+    {
+        return Err(CatalystError::Cancelled);
+    }
+    for (skill_id, result) in skill_ids.into_iter().zip(results) {
+        match result {
+            Ok(()) => installed.push(skill_id.clone()),
+            Err(e) => failed.push(FailedSkill {
+                name: skill_id.clone(),
+                error: e.to_string(),
+            }),
         }
     }
 
-    if let Some(ref pb) = pb {
-        pb.finish_with_message(format!(
-            "✅ Installed {} skill{}",
+    reporter.report(ProgressEvent::Finished {
+        message: format!(
+            "Installed {} skill{}",
             installed.len(),
             if installed.len() == 1 { "" } else { "s" }
-        ));
-    }
+        ),
+    });
 
-    Ok(installed)
+    Ok((installed, failed))
 }
 
 /// Install a single skill from embedded resources
@@ -648,57 +708,107 @@ pub fn install_skills(target_dir: &Path, skill_ids: &[String], force: bool) -> R
 /// * `target_dir` - Base directory where .claude exists
 /// * `skill_id` - The skill ID to install
 /// * `force` - Whether to overwrite existing skill directory
-fn install_skill(target_dir: &Path, skill_id: &str, force: bool) -> Result<()> {
-    // Validate skill ID against available skills
-    if !AVAILABLE_SKILLS.contains(&skill_id) {
-        return Err(CatalystError::InvalidConfig(format!(
-            "Invalid skill ID: '{}'. Available skills: {}",
-            skill_id,
-            AVAILABLE_SKILLS.join(", ")
-        )));
-    }
+/// * `library` - Skill library to link the skill from, or None to copy it
+fn install_skill(
+    target_dir: &Path,
+    skill_id: &str,
+    force: bool,
+    library: Option<&Path>,
+) -> Result<()> {
+    let _span = timings::span("copy skill");
+    let skill_dir = resolve_installable_skill(target_dir, skill_id, force)?;
+    let skill_target = target_dir.join(SKILLS_DIR).join(skill_id);
 
-    let skills_dir = target_dir.join(SKILLS_DIR);
-    let skill_target = skills_dir.join(skill_id);
+    if let Some(library) = library {
+        return library::link_skill(library, target_dir, skill_id);
+    }
 
-    // Check if skill directory already exists
-    if skill_target.exists() && !force {
-        return Err(CatalystError::InvalidPath(format!(
-            "Skill directory already exists: {}\nUse --force to overwrite.",
-            skill_target.display()
-        )));
+    // Copying through a link would overwrite the shared library copy
+    if library::is_linked(target_dir, skill_id) {
+        fs::remove_file(&skill_target).map_err(CatalystError::Io)?;
     }
 
-    // Find the skill in embedded resources
-    let skill_dir = SKILLS
-        .get_dir(skill_id)
-        .ok_or_else(|| CatalystError::InvalidPath(format!("Skill not found: {}", skill_id)))?;
+    let vars = template::project_vars(target_dir, &CatalystConfig::load(target_dir)?);
+    copy_embedded_skill(skill_dir, &skill_target, &vars)
+}
+
+/// Look up a skill in the embedded resources
+pub(crate) fn embedded_skill(skill_id: &str) -> Option<&'static Dir<'static>> {
+    SKILLS.get_dir(skill_id)
+}
 
+/// Copy an embedded skill into a directory, creating it if needed
+///
+/// Skill templates are rendered with `vars` (see [`template::skill_file`]).
+pub(crate) fn copy_embedded_skill(
+    skill_dir: &Dir,
+    skill_target: &Path,
+    vars: &TemplateVars,
+) -> Result<()> {
     // Create skill directory
-    fs::create_dir_all(&skill_target).map_err(CatalystError::Io)?;
+    fs::create_dir_all(skill_target).map_err(CatalystError::Io)?;
 
     // Copy all files recursively
-    copy_dir_recursive(skill_dir, &skill_target)?;
+    copy_dir_recursive(skill_dir, skill_target, vars)?;
 
     // Set permissions on Unix
     #[cfg(unix)]
     {
         let permissions = fs::Permissions::from_mode(0o755);
-        fs::set_permissions(&skill_target, permissions).map_err(CatalystError::Io)?;
+        fs::set_permissions(skill_target, permissions).map_err(CatalystError::Io)?;
     }
 
     Ok(())
 }
 
+/// Check that a skill can be installed and return its embedded directory
+///
+/// Shared by [`install_skill`] and dry-run planning.
+fn resolve_installable_skill(
+    target_dir: &Path,
+    skill_id: &str,
+    force: bool,
+) -> Result<&'static Dir<'static>> {
+    // Reject path tricks before the name reaches any join or error message
+    safe_path::validate_skill_id(skill_id)?;
+
+    // Validate skill ID against available skills
+    if !AVAILABLE_SKILLS.contains(&skill_id) {
+        return Err(CatalystError::InvalidConfig(format!(
+            "Invalid skill ID: '{}'. Available skills: {}",
+            skill_id,
+            AVAILABLE_SKILLS.join(", ")
+        )));
+    }
+
+    let skills_dir = target_dir.join(SKILLS_DIR);
+    let skill_target = skills_dir.join(skill_id);
+
+    // Check if skill directory already exists
+    if skill_target.exists() && !force {
+        return Err(CatalystError::InvalidPath(format!(
+            "Skill directory already exists: {}\nUse --force to overwrite.",
+            skill_target.display()
+        )));
+    }
+
+    // Find the skill in embedded resources
+    SKILLS
+        .get_dir(skill_id)
+        .ok_or_else(|| CatalystError::InvalidPath(format!("Skill not found: {}", skill_id)))
+}
+
 /// Recursively copy directory contents from embedded resources
-fn copy_dir_recursive(source: &include_dir::Dir, target: &Path) -> Result<()> {
+fn copy_dir_recursive(source: &include_dir::Dir, target: &Path, vars: &TemplateVars) -> Result<()> {
     // Copy all files in this directory
     for file in source.files() {
         let file_name = file.path().file_name().ok_or_else(|| {
             CatalystError::InvalidPath(format!("Invalid file path: {:?}", file.path()))
         })?;
+        let (file_name, contents) = template::skill_file(file_name, file.contents(), vars)?;
         let file_path = target.join(file_name);
-        fs::write(&file_path, file.contents()).map_err(CatalystError::Io)?;
+        fs::write(&file_path, contents).map_err(CatalystError::Io)?;
+        progress::detail(|| format!("Wrote {}", file_path.display()));
 
         // Set executable permission on Unix if needed
         #[cfg(unix)]
@@ -715,7 +825,7 @@ fn copy_dir_recursive(source: &include_dir::Dir, target: &Path) -> Result<()> {
         })?;
         let subdir_path = target.join(subdir_name);
         fs::create_dir_all(&subdir_path).map_err(CatalystError::Io)?;
-        copy_dir_recursive(subdir, &subdir_path)?;
+        copy_dir_recursive(subdir, &subdir_path, vars)?;
     }
 
     Ok(())
@@ -745,80 +855,158 @@ pub fn generate_skill_rules(target_dir: &Path, installed_skills: &[String]) -> R
         })?;
 
     for skill_id in installed_skills {
-        let (keywords, intent_patterns, path_patterns) = get_skill_patterns(skill_id);
-
-        skills_obj.insert(
-            skill_id.clone(),
-            serde_json::json!({
-                "type": "skill",
-                "enforcement": "suggest",
-                "priority": 1,
-                "keywords": keywords,
-                "intentPatterns": intent_patterns,
-                "pathPatterns": path_patterns,
-                "enabled": true
-            }),
-        );
+        skills_obj.insert(skill_id.clone(), skill_rule(target_dir, skill_id));
     }
 
+    write_skill_rules(&skill_rules_path, &rules)
+}
+
+/// Add default rules for newly installed skills to an existing
+/// skill-rules.json, keeping every rule already there
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the file isn't valid JSON(C) or has no
+/// `skills` object
+pub(crate) fn add_skill_rules(target_dir: &Path, skill_ids: &[String]) -> Result<()> {
+    let skill_rules_path = target_dir.join(SKILL_RULES_FILE);
+    let content =
+        fs::read_to_string(&skill_rules_path).map_err(|e| CatalystError::FileReadFailed {
+            path: skill_rules_path.clone(),
+            source: e,
+        })?;
+    let mut rules = catalyst_core::jsonc::from_str::<serde_json::Value>(&content).map_err(|e| {
+        CatalystError::InvalidConfig(format!("{}: {}", skill_rules_path.display(), e))
+    })?;
+
+    let skills_obj = rules
+        .get_mut("skills")
+        .and_then(|v| v.as_object_mut())
+        .ok_or_else(|| {
+            CatalystError::InvalidConfig(format!(
+                "{}: missing \"skills\" object",
+                skill_rules_path.display()
+            ))
+        })?;
+    for skill_id in skill_ids {
+        if !skills_obj.contains_key(skill_id) {
+            skills_obj.insert(skill_id.clone(), skill_rule(target_dir, skill_id));
+        }
+    }
+
+    write_skill_rules(&skill_rules_path, &rules)
+}
+
+/// Default activation rule for a skill
+///
+/// The patterns come from the `[activation]` table of the installed skill's
+/// skill.toml, or are derived from its ID if it has none.
+fn skill_rule(target_dir: &Path, skill_id: &str) -> serde_json::Value {
+    let skill_dir = target_dir.join(SKILLS_DIR).join(skill_id);
+    let activation = match SkillManifest::load(&skill_dir) {
+        Ok(manifest) => manifest.activation,
+        Err(e) => {
+            tracing::debug!(skill = skill_id, error = %e, "No usable skill manifest");
+            None
+        }
+    }
+    .unwrap_or_else(|| default_activation(skill_id));
+
+    let mut rule = activation_rule(&activation);
+    if library::is_linked(target_dir, skill_id) {
+        rule["source"] = serde_json::json!("library");
+    }
+    rule
+}
+
+/// Priority given to generated skill rules
+const DEFAULT_RULE_PRIORITY: &str = "high";
+
+/// A skill-rules.json entry in the schema skill-activation-prompt reads
+///
+/// Keywords and intent patterns go under `promptTriggers` and path patterns
+/// under `fileTriggers`; the hook ignores them anywhere else.
+pub(crate) fn activation_rule(activation: &Activation) -> serde_json::Value {
+    serde_json::json!({
+        "type": "skill",
+        "enforcement": "suggest",
+        "priority": DEFAULT_RULE_PRIORITY,
+        "promptTriggers": {
+            "keywords": activation.keywords,
+            "intentPatterns": activation.intent_patterns,
+        },
+        "fileTriggers": {
+            "pathPatterns": activation.path_patterns,
+        },
+        "enabled": true
+    })
+}
+
+/// Write skill rules with the customization header comment
+fn write_skill_rules(skill_rules_path: &Path, rules: &serde_json::Value) -> Result<()> {
     // Pretty-print JSON with comment
     let mut content = String::from("// Customize pathPatterns for your project structure\n");
-    content.push_str(&serde_json::to_string_pretty(&rules).map_err(CatalystError::Json)?);
+    content.push_str(&serde_json::to_string_pretty(rules).map_err(CatalystError::Json)?);
 
     // Write atomically
-    write_file_atomic(&skill_rules_path, &content)?;
+    write_file_atomic(skill_rules_path, &content)?;
 
     Ok(())
 }
 
-/// Get skill-specific patterns (keywords, intent, and path patterns)
-fn get_skill_patterns(skill_id: &str) -> (Vec<String>, Vec<String>, Vec<String>) {
-    match skill_id {
-        "frontend-dev-guidelines" => (
-            vec!["frontend".to_string(), "react".to_string()],
-            vec![
-                "frontend development".to_string(),
-                "react component".to_string(),
-            ],
-            vec!["**/*.{ts,tsx,js,jsx,vue,svelte}".to_string()],
-        ),
-        "backend-dev-guidelines" => (
-            vec!["backend".to_string(), "api".to_string()],
-            vec![
-                "backend development".to_string(),
-                "api endpoint".to_string(),
-            ],
-            vec!["**/*.{ts,js}".to_string(), "src/routes/**/*".to_string()],
-        ),
-        "rust-developer" => (
-            vec!["rust".to_string()],
-            vec!["rust development".to_string()],
-            vec!["**/*.rs".to_string(), "Cargo.toml".to_string()],
-        ),
-        _ => (
-            vec![skill_id.to_string()],
-            vec![format!("{} skill", skill_id)],
-            vec![
-                "src/**/*".to_string(),
-                "lib/**/*".to_string(),
-                "app/**/*".to_string(),
-                "tests/**/*".to_string(),
-            ],
-        ),
+/// Activation patterns for a skill whose manifest doesn't declare any
+fn default_activation(skill_id: &str) -> Activation {
+    Activation {
+        keywords: vec![skill_id.to_string()],
+        intent_patterns: vec![format!("{} skill", skill_id)],
+        path_patterns: vec![
+            "src/**/*".to_string(),
+            "lib/**/*".to_string(),
+            "app/**/*".to_string(),
+            "tests/**/*".to_string(),
+        ],
     }
 }
 
+/// Manifest of a skill shipped with catalyst, or None if `skill_id` isn't
+/// one or its manifest is malformed
+pub fn shipped_manifest(skill_id: &str) -> Option<SkillManifest> {
+    let dir = SKILLS.get_dir(skill_id)?;
+    let read = |name: &str| {
+        dir.get_file(dir.path().join(name))
+            .and_then(|file| file.contents_utf8())
+    };
+    let skill_md = read(SKILL_FILE).or_else(|| read(SKILL_TEMPLATE_FILE))?;
+    match SkillManifest::parse(skill_md, read(SKILL_MANIFEST_FILE)) {
+        Ok(manifest) => Some(manifest),
+        Err(e) => {
+            tracing::warn!(skill = skill_id, error = %e, "Malformed shipped skill manifest");
+            None
+        }
+    }
+}
+
+/// Manifests of the skills in [`AVAILABLE_SKILLS`], in that order
+pub fn shipped_manifests() -> Vec<SkillManifest> {
+    AVAILABLE_SKILLS
+        .iter()
+        .filter_map(|skill_id| shipped_manifest(skill_id))
+        .collect()
+}
+
 /// Compute SHA256 hash of a file
-fn hash_file(file_path: &Path) -> Result<String> {
+pub(crate) fn hash_file(file_path: &Path) -> Result<String> {
     let contents = fs::read(file_path).map_err(CatalystError::Io)?;
-    let hash = Sha256::digest(&contents);
-    Ok(format!("{:x}", hash))
+    let hash = format!("{:x}", Sha256::digest(&contents));
+    progress::detail(|| format!("Hashed {}: {}", file_path.display(), hash));
+    Ok(hash)
 }
 
 /// Generate .catalyst-hashes.json for tracking file modifications
 ///
 /// Computes SHA256 hashes for all installed skill files and stores them
 /// in .catalyst-hashes.json for modification detection during updates.
+/// Files are hashed concurrently with the `parallel` feature.
 ///
 /// # Arguments
 ///
@@ -828,12 +1016,14 @@ pub fn generate_skill_hashes(target_dir: &Path, installed_skills: &[String]) ->
     let hashes_path = target_dir.join(SKILLS_DIR).join(".catalyst-hashes.json");
     let skills_dir = target_dir.join(SKILLS_DIR);
 
-    let mut hashes: HashMap<String, String> = HashMap::new();
-
+    // Linked skills are tracked by the library's own hash file
+    let mut files = Vec::new();
     for skill_id in installed_skills {
-        let skill_path = skills_dir.join(skill_id);
-        collect_file_hashes(&skills_dir, &skill_path, &mut hashes)?;
+        if !library::is_linked(target_dir, skill_id) {
+            collect_skill_files(&skills_dir.join(skill_id), &mut files)?;
+        }
     }
+    let hashes = collect_file_hashes(&skills_dir, &files)?;
 
     // Pretty-print JSON
     let content = serde_json::to_string_pretty(&hashes).map_err(CatalystError::Json)?;
@@ -844,18 +1034,49 @@ pub fn generate_skill_hashes(target_dir: &Path, installed_skills: &[String]) ->
     Ok(())
 }
 
-/// Recursively collect hashes for all files in a directory
+/// Add one skill's file hashes to an existing .catalyst-hashes.json
+///
+/// Entries for other skills are kept; the file is created if missing.
+///
+/// # Arguments
+///
+/// * `target_dir` - Base directory where .claude exists
+/// * `skill_id` - Skill whose files should be (re)hashed
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the existing hash file isn't a JSON object
+pub fn add_skill_hashes(target_dir: &Path, skill_id: &str) -> Result<()> {
+    let skills_dir = target_dir.join(SKILLS_DIR);
+    let hashes_path = skills_dir.join(".catalyst-hashes.json");
+
+    let mut hashes: HashMap<String, String> = match fs::read_to_string(&hashes_path) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| {
+            CatalystError::InvalidConfig(format!("{}: {}", hashes_path.display(), e))
+        })?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+        Err(e) => return Err(CatalystError::Io(e)),
+    };
+
+    hashes.retain(|path, _| !Path::new(path).starts_with(skill_id));
+
+    let mut files = Vec::new();
+    collect_skill_files(&skills_dir.join(skill_id), &mut files)?;
+    hashes.extend(collect_file_hashes(&skills_dir, &files)?);
+
+    let content = serde_json::to_string_pretty(&hashes).map_err(CatalystError::Json)?;
+    write_file_atomic(&hashes_path, &content)?;
+
+    Ok(())
+}
+
+/// Recursively list all files in a directory
 ///
 /// # Arguments
 ///
-/// * `base_dir` - Base directory for computing relative paths (e.g., .claude/skills)
 /// * `current_dir` - Current directory being traversed
-/// * `hashes` - HashMap to store file path -> hash mappings
-fn collect_file_hashes(
-    base_dir: &Path,
-    current_dir: &Path,
-    hashes: &mut HashMap<String, String>,
-) -> Result<()> {
+/// * `files` - Vec to append file paths to
+fn collect_skill_files(current_dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
     if !current_dir.is_dir() {
         return Ok(());
     }
@@ -865,41 +1086,50 @@ fn collect_file_hashes(
         let path = entry.path();
 
         if path.is_file() {
-            // Compute relative path from base_dir, with proper error handling
-            let relative_path = path
-                .strip_prefix(base_dir)
-                .map_err(|_| {
-                    CatalystError::PathTraversalDetected(format!(
-                        "Path {} is not within base directory {}",
-                        path.display(),
-                        base_dir.display()
-                    ))
-                })?
-                .to_string_lossy()
-                .to_string();
-
-            let hash = hash_file(&path)?;
-            hashes.insert(relative_path, hash);
+            files.push(path);
         } else if path.is_dir() {
-            collect_file_hashes(base_dir, &path, hashes)?;
+            collect_skill_files(&path, files)?;
         }
     }
 
     Ok(())
 }
 
-/// Initialize a Claude Code project
-///
-/// This is the main entry point for the `catalyst init` command.
+/// Hash files, keyed by their path relative to `base_dir`
 ///
 /// # Arguments
 ///
-/// * `config` - Configuration for initialization
-///
-/// # Returns
-///
-/// Returns an `InitReport` with details of what was created
-///
+/// * `base_dir` - Base directory for computing relative paths (e.g., .claude/skills)
+/// * `files` - Files to hash, all within `base_dir`
+fn collect_file_hashes(base_dir: &Path, files: &[PathBuf]) -> Result<HashMap<String, String>> {
+    let hash_one = |path: &PathBuf| -> Result<(String, String)> {
+        // Compute relative path from base_dir, with proper error handling
+        let relative_path = path
+            .strip_prefix(base_dir)
+            .map_err(|_| {
+                CatalystError::PathTraversalDetected(format!(
+                    "Path {} is not within base directory {}",
+                    path.display(),
+                    base_dir.display()
+                ))
+            })?
+            .to_string_lossy()
+            .to_string();
+
+        Ok((relative_path, hash_file(path)?))
+    };
+
+    #[cfg(feature = "parallel")]
+    {
+        files.par_iter().map(hash_one).collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        files.iter().map(hash_one).collect()
+    }
+}
+
 /// Write .catalyst-version file to track installation version
 ///
 /// # Arguments
@@ -947,72 +1177,1020 @@ pub fn read_version_file(target_dir: &Path) -> Result<Option<String>> {
     }
 }
 
+impl InitConfig {
+    /// Start a config for `directory` with the library defaults: hooks and
+    /// tracker installed, no skills, nothing forced
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use catalyst_cli::init;
+    /// use catalyst_cli::types::InitConfig;
+    ///
+    /// let config = InitConfig::builder("my-project")
+    ///     .skills(["rust-developer", "skill-developer"])
+    ///     .install_tracker(false)
+    ///     .build()?;
+    /// init::initialize(&config)?;
+    /// # Ok::<(), catalyst_cli::types::CatalystError>(())
+    /// ```
+    pub fn builder(directory: impl Into<PathBuf>) -> InitConfigBuilder {
+        InitConfigBuilder {
+            config: InitConfig {
+                directory: directory.into(),
+                ..InitConfig::default()
+            },
+        }
+    }
+
+    /// The config non-interactive `catalyst init` would use for
+    /// `directory` without flags
+    ///
+    /// Hook choices, the skill source, and strict skills mode come from
+    /// catalyst.toml. Skills come from `init.skills` (project, then user
+    /// config), or else from the project types detected in `directory`,
+    /// or else [`crate::types::DEFAULT_INIT_SKILLS`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfig` if catalyst.toml, the user config, or a
+    /// detected stack's profile is invalid
+    pub fn detect(directory: &Path) -> Result<Self> {
+        let project = CatalystConfig::load(directory)?;
+        let user = CatalystConfig::load_user()?;
+        let detected = stack::stack_defaults(
+            &stack::detect_stacks(directory),
+            profiles::user_profile_dir().as_deref(),
+        )?;
+        let (mut skills, mut skill_selection) =
+            config::default_init_skills(&project, user.as_ref());
+        let mut settings = serde_json::Map::new();
+        if skill_selection == SkillSelection::BuiltIn && !detected.skills.is_empty() {
+            skills = detected.skills;
+            skill_selection = SkillSelection::Detected;
+            settings = detected.settings;
+        }
+        let skills_library = match project.init.skill_source {
+            SkillSource::Library => Some(library::library_dir()?),
+            SkillSource::Project => None,
+        };
+        Ok(InitConfig {
+            directory: directory.to_path_buf(),
+            install_hooks: project.init.install_hooks,
+            install_tracker: project.init.install_tracker,
+            install_check_hook: project
+                .init
+                .install_check_hook
+                .unwrap_or(detected.install_check_hook),
+            skills,
+            skills_library,
+            strict_skills: project.init.strict_skills,
+            skill_selection,
+            settings,
+            ..InitConfig::default()
+        })
+    }
+}
+
+/// Builds an [`InitConfig`], checking it before init sees it
+///
+/// Created with [`InitConfig::builder`]; options left unset keep the
+/// defaults of [`InitConfig::default`].
+#[derive(Debug, Clone)]
+pub struct InitConfigBuilder {
+    config: InitConfig,
+}
+
+impl InitConfigBuilder {
+    /// Skills to install, replacing any set before
+    pub fn skills<I, S>(mut self, skills: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.skills = skills.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Add one skill to install
+    pub fn skill(mut self, skill_id: impl Into<String>) -> Self {
+        self.config.skills.push(skill_id.into());
+        self
+    }
+
+    pub fn install_hooks(mut self, install: bool) -> Self {
+        self.config.install_hooks = install;
+        self
+    }
+
+    pub fn install_tracker(mut self, install: bool) -> Self {
+        self.config.install_tracker = install;
+        self
+    }
+
+    pub fn install_check_hook(mut self, install: bool) -> Self {
+        self.config.install_check_hook = install;
+        self
+    }
+
+    /// Store the tracker hook writes to (saved in catalyst.toml)
+    pub fn tracker_backend(mut self, backend: TrackerBackend) -> Self {
+        self.config.tracker_backend = Some(backend);
+        self
+    }
+
+    /// Overwrite an existing installation
+    pub fn force(mut self, force: bool) -> Self {
+        self.config.force = force;
+        self
+    }
+
+    /// Bring an existing installation in line instead of overwriting it
+    pub fn reconcile(mut self, reconcile: bool) -> Self {
+        self.config.reconcile = reconcile;
+        self
+    }
+
+    /// Plan without writing anything
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.config.dry_run = dry_run;
+        self
+    }
+
+    /// Give regenerated files the default modes
+    pub fn reset_permissions(mut self, reset: bool) -> Self {
+        self.config.reset_permissions = reset;
+        self
+    }
+
+    /// Link skills from a skill library instead of copying them
+    pub fn skills_library(mut self, library: impl Into<PathBuf>) -> Self {
+        self.config.skills_library = Some(library.into());
+        self
+    }
+
+    pub fn strict_skills(mut self, strict: StrictSkills) -> Self {
+        self.config.strict_skills = strict;
+        self
+    }
+
+    /// Extra top-level settings.json keys, e.g. `permissions`
+    pub fn settings(mut self, settings: serde_json::Map<String, serde_json::Value>) -> Self {
+        self.config.settings = settings;
+        self
+    }
+
+    /// Check the options and return the config
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathNotFound` if the directory doesn't exist, `InvalidPath`
+    /// if it isn't a directory, and `InvalidConfig` for an unknown skill or
+    /// for `force` combined with `reconcile`
+    pub fn build(mut self) -> Result<InitConfig> {
+        let config = &mut self.config;
+        if !config.directory.exists() {
+            return Err(CatalystError::PathNotFound(config.directory.clone()));
+        }
+        if !config.directory.is_dir() {
+            return Err(CatalystError::InvalidPath(format!(
+                "{} is not a directory",
+                config.directory.display()
+            )));
+        }
+        if config.force && config.reconcile {
+            return Err(CatalystError::InvalidConfig(
+                "force and reconcile can't be combined: reconcile keeps the existing install"
+                    .to_string(),
+            ));
+        }
+        for skill_id in &config.skills {
+            safe_path::validate_skill_id(skill_id)?;
+            if !AVAILABLE_SKILLS.contains(&skill_id.as_str()) {
+                return Err(CatalystError::InvalidConfig(format!(
+                    "Invalid skill ID: '{}'. Available skills: {}",
+                    skill_id,
+                    AVAILABLE_SKILLS.join(", ")
+                )));
+            }
+        }
+        let mut seen = HashSet::new();
+        config
+            .skills
+            .retain(|skill_id| seen.insert(skill_id.clone()));
+        Ok(self.config)
+    }
+}
+
+/// Initialize a Claude Code project
+///
+/// This is the main entry point for the `catalyst init` command. Every path is recorded in an [`InitJournal`] before it is touched. If a
+/// fatal error stops init partway through, the journal is rolled back so the
+/// project is left exactly as it was before the run.
+/// Cancellation (see [`crate::cancel`]) and a first Ctrl-C are handled the
+/// same way, once the skill or phase in progress is done.
+///
+/// # Arguments
+///
+/// * `config` - Configuration for initialization
+///
+/// # Returns
+///
+/// Returns an `InitReport` with details of what was created (or, for a dry
+/// run, what would be)
+///
+/// # Errors
+///
+/// Returns the error that stopped init, after rolling back its changes.
 pub fn initialize(config: &InitConfig) -> Result<InitReport> {
+    if config.dry_run {
+        return if config.reconcile {
+            reconcile_installation(config, None)
+        } else {
+            plan_initialization(config)
+        };
+    }
+
+    // A first Ctrl-C stops at the next checkpoint and rolls back below
+    let _defer = signals::defer_interrupts();
+
     // Acquire lock to prevent concurrent init
-    let _lock = acquire_init_lock(&config.directory)?;
+    let _lock = {
+        let _span = timings::span("acquire lock");
+        OperationLock::acquire(&config.directory)?
+    };
+
+    let mut journal = InitJournal::new(&config.directory);
+    let result = if config.reconcile {
+        reconcile_installation(config, Some(&mut journal))
+    } else {
+        run_initialization(config, &mut journal)
+    };
+    match result {
+        Ok(report) => {
+            journal.commit();
+            // Finish mode keeps everything that was installed but still fails
+            if config.strict_skills == StrictSkills::Finish && !report.failed_skills.is_empty() {
+                return Err(strict_skills_error(report));
+            }
+            Ok(report)
+        }
+        Err(e) => {
+            let _span = timings::span("roll back");
+            let recorded = journal.paths().count();
+            let failures = journal.rollback();
+            if failures.is_empty() {
+                progress::warn(format!("Init failed; rolled back {} change(s)", recorded));
+            } else {
+                let mut message = "Init failed and could not be fully rolled back:".to_string();
+                for (path, error) in &failures {
+                    message.push_str(&format!("\n   {}: {}", path.display(), error));
+                }
+                message.push_str(&format!(
+                    "\n   Original files are kept in {}",
+                    config.directory.join(BACKUP_DIR).display()
+                ));
+                progress::warn(message);
+            }
+            Err(e)
+        }
+    }
+}
 
+/// Initialize a project without blocking an async runtime
+///
+/// Runs [`initialize`] on tokio's blocking pool. Cancelling `cancel` stops
+/// init before the next skill or phase; what it had written is rolled back.
+///
+/// # Errors
+///
+/// Returns `Cancelled` if `cancel` was cancelled before init finished, and
+/// otherwise the errors of [`initialize`]
+#[cfg(feature = "tokio")]
+pub async fn initialize_async(
+    config: InitConfig,
+    cancel: tokio_util::sync::CancellationToken,
+) -> Result<InitReport> {
+    cancel::run_blocking(cancel, move || initialize(&config)).await
+}
+
+/// Run the init phases, recording each path in the journal before touching it
+fn run_initialization(config: &InitConfig, journal: &mut InitJournal) -> Result<InitReport> {
     let mut report = InitReport::new();
-    let platform = Platform::detect();
+    report.skill_selection = config.skill_selection;
+    let project_config = CatalystConfig::load(&config.directory)?;
+    let platform = project_config.wrapper_platform(Platform::detect());
+    let policy = if config.reset_permissions {
+        PermissionPolicy::Reset
+    } else {
+        PermissionPolicy::Preserve
+    };
 
-    // Phase 2.1: Create directory structure
+    // Phase 2.1: Create directory structure (existing directories are kept,
+    // so only new ones are journaled)
+    let span = timings::span("create directories");
+    for dir in directories_to_create(&config.directory, config.force)? {
+        if !config.directory.join(dir).exists() {
+            journal.record(dir)?;
+        }
+    }
     let created_dirs = create_directory_structure(&config.directory, config.force)?;
     report.created_dirs = created_dirs;
+    drop(span);
+    cancel::checkpoint()?;
 
     // Phase 2.2: Generate wrapper scripts
+    let span = timings::span("generate wrappers");
+    let tracker_backend =
+        save_tracker_backend(config, &project_config, Some(journal), &mut report)?;
+    for (_, wrapper_name) in planned_wrappers(
+        config.install_hooks,
+        config.install_tracker,
+        config.install_check_hook,
+        tracker_backend,
+        platform,
+    ) {
+        journal.record(Path::new(HOOKS_DIR).join(wrapper_name))?;
+    }
     let installed_hooks = generate_wrapper_scripts(
         &config.directory,
         config.install_hooks,
         config.install_tracker,
+        config.install_check_hook,
         platform,
+        policy,
     )?;
     report.installed_hooks = installed_hooks;
+    drop(span);
+    cancel::checkpoint()?;
 
     // Phase 2.3: Create settings.json
+    let span = timings::span("write settings");
+    journal.record(SETTINGS_FILE)?;
     let settings_created = create_settings_json(
         &config.directory,
         config.install_hooks,
         config.install_tracker,
+        config.install_check_hook,
         platform,
+        policy,
+        &config.settings,
     )?;
     report.settings_created = settings_created;
+    drop(span);
+    cancel::checkpoint()?;
 
     // Phase 3.1-3.2: Install skills
     if !config.skills.is_empty() {
-        let installed_skills = install_skills(&config.directory, &config.skills, config.force)?;
+        let span = timings::span("install skills");
+        for skill_id in &config.skills {
+            journal.record(Path::new(SKILLS_DIR).join(skill_id))?;
+        }
+        let (installed_skills, failed_skills) = install_skills_reporting_failures(
+            &config.directory,
+            &config.skills,
+            config.force,
+            config.skills_library.as_deref(),
+        )?;
         report.installed_skills = installed_skills.clone();
+        report.failed_skills = failed_skills;
+        drop(span);
+
+        // Abort mode stops here; initialize() rolls back what was written
+        if config.strict_skills == StrictSkills::Abort && !report.failed_skills.is_empty() {
+            return Err(strict_skills_error(report));
+        }
 
         // Phase 3.3: Generate skill-rules.json (gracefully degrade on failure)
         if !installed_skills.is_empty() {
-            if let Err(e) = generate_skill_rules(&config.directory, &installed_skills) {
-                let warning = format!("⚠️  Failed to generate skill-rules.json: {}", e);
-                eprintln!("{}", warning);
-                report.warnings.push(warning);
+            let span = timings::span("generate skill rules");
+            journal.record(SKILL_RULES_FILE)?;
+            let rules_result = generate_skill_rules(&config.directory, &installed_skills);
+            drop(span);
+            if let Err(e) = rules_result {
+                add_warning(
+                    &mut report,
+                    format!("Failed to generate skill-rules.json: {}", e),
+                );
             }
 
             // Phase 3.4: Generate .catalyst-hashes.json (gracefully degrade on failure)
-            if let Err(e) = generate_skill_hashes(&config.directory, &installed_skills) {
-                let warning = format!("⚠️  Failed to generate .catalyst-hashes.json: {}", e);
-                eprintln!("{}", warning);
-                report.warnings.push(warning);
+            let span = timings::span("hash skills");
+            journal.record(Path::new(SKILLS_DIR).join(HASHES_FILE))?;
+            let hashes_result = generate_skill_hashes(&config.directory, &installed_skills);
+            drop(span);
+            if let Err(e) = hashes_result {
+                add_warning(
+                    &mut report,
+                    format!("Failed to generate .catalyst-hashes.json: {}", e),
+                );
             }
         }
     }
 
+    cancel::checkpoint()?;
+
     // Phase 6.1: Write .catalyst-version file to track installation
-    if let Err(e) = write_version_file(&config.directory) {
-        let warning = format!("⚠️  Failed to write .catalyst-version: {}", e);
-        eprintln!("{}", warning);
-        report.warnings.push(warning);
+    let span = timings::span("write version file");
+    journal.record(VERSION_FILE)?;
+    let version_result = write_version_file(&config.directory);
+    drop(span);
+    if let Err(e) = version_result {
+        add_warning(
+            &mut report,
+            format!("Failed to write .catalyst-version: {}", e),
+        );
     } else {
         report.version_file_created = true;
     }
 
+    // Phase 6.2: Record the install in catalyst.lock
+    let span = timings::span("write lockfile");
+    lock_installation(config, Some(journal), &mut report)?;
+    drop(span);
+
+    Ok(report)
+}
+
+/// Bring an existing install in line with the config
+///
+/// Compares each piece init manages with what the config asks for and only
+/// touches what differs: missing directories, wrappers, skills, and hook
+/// entries are created, wrappers whose content or mode no longer match are
+/// rewritten, and everything else is reported as unchanged. Existing
+/// settings.json keys and skill rules are kept. Without a journal nothing is
+/// written and the actions are recorded in `planned_actions` instead.
+///
+/// # Errors
+///
+/// Returns the fatal errors of a normal init, plus `InvalidConfig` if
+/// settings.json can't be parsed
+fn reconcile_installation(
+    config: &InitConfig,
+    mut journal: Option<&mut InitJournal>,
+) -> Result<InitReport> {
+    let mut report = InitReport::new();
+    report.reconciled = true;
+    report.dry_run = journal.is_none();
+    report.skill_selection = config.skill_selection;
+    let project_config = CatalystConfig::load(&config.directory)?;
+    let platform = project_config.wrapper_platform(Platform::detect());
+    let policy = if config.reset_permissions {
+        PermissionPolicy::Reset
+    } else {
+        PermissionPolicy::Preserve
+    };
+    let target = &config.directory;
+
+    // Directories
+    let missing_dirs = directories_to_create(target, false)?;
+    for dir in [HOOKS_DIR, SKILLS_DIR, AGENTS_DIR, COMMANDS_DIR] {
+        if !missing_dirs.contains(&dir) {
+            report.unchanged.push(dir.to_string());
+        }
+    }
+    match journal.as_deref_mut() {
+        Some(journal) => {
+            for dir in &missing_dirs {
+                journal.record(dir)?;
+            }
+            report.created_dirs = create_directory_structure(target, false)?;
+        }
+        None => {
+            for dir in missing_dirs {
+                report
+                    .planned_actions
+                    .push(format!("Create directory {}", dir));
+                report.created_dirs.push(dir.to_string());
+            }
+        }
+    }
+
+    // Wrapper scripts
+    let hooks_dir = target.join(HOOKS_DIR);
+    let tracker_backend =
+        save_tracker_backend(config, &project_config, journal.as_deref_mut(), &mut report)?;
+    let project_config = CatalystConfig::load(target)?;
+    for (binary_name, wrapper_name) in planned_wrappers(
+        config.install_hooks,
+        config.install_tracker,
+        config.install_check_hook,
+        tracker_backend,
+        platform,
+    ) {
+        let relative = format!("{}/{}", HOOKS_DIR, wrapper_name);
+        let wrapper_path = hooks_dir.join(&wrapper_name);
+        let content = render_wrapper(binary_name, platform, &project_config, &config.directory)?;
+        let existing = match fs::read_to_string(&wrapper_path) {
+            Ok(existing) => Some(existing),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            // Unreadable wrappers are rewritten
+            Err(_) => Some(String::new()),
+        };
+        let stale = match &existing {
+            None => false,
+            Some(existing) => *existing != content || !wrapper_mode_ok(&wrapper_path, platform),
+        };
+        if existing.is_some() && !stale {
+            report.unchanged.push(relative);
+            continue;
+        }
+
+        match journal.as_deref_mut() {
+            Some(journal) => {
+                journal.record(&relative)?;
+                write_wrapper(&hooks_dir, &wrapper_name, &content, platform, policy)?;
+            }
+            None => report.planned_actions.push(format!(
+                "{} wrapper {} (calls {})",
+                if stale { "Rewrite stale" } else { "Write" },
+                relative,
+                binary_name
+            )),
+        }
+        if stale {
+            report.updated.push(relative);
+        } else {
+            report.installed_hooks.push(wrapper_name);
+        }
+    }
+
+    // settings.json
+    let desired_hooks = settings_hooks(
+        config.install_hooks,
+        config.install_tracker,
+        config.install_check_hook,
+        platform,
+        &project_config,
+    );
+    let settings_path = target.join(SETTINGS_FILE);
+    match fs::read_to_string(&settings_path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            match journal.as_deref_mut() {
+                Some(journal) => {
+                    journal.record(SETTINGS_FILE)?;
+                    create_settings_json(
+                        target,
+                        config.install_hooks,
+                        config.install_tracker,
+                        config.install_check_hook,
+                        platform,
+                        policy,
+                        &config.settings,
+                    )?;
+                }
+                None => report
+                    .planned_actions
+                    .push(format!("Write {}", SETTINGS_FILE)),
+            }
+            report.settings_created = true;
+        }
+        Err(e) => {
+            return Err(CatalystError::FileReadFailed {
+                path: settings_path,
+                source: e,
+            })
+        }
+        Ok(content) => {
+            let unparseable = |e: &dyn std::fmt::Display| {
+                CatalystError::InvalidConfig(format!(
+                    "{}: {}\nFix the file, or re-run init with --force to replace it.",
+                    settings_path.display(),
+                    e
+                ))
+            };
+            let mut raw: serde_json::Map<String, serde_json::Value> =
+                catalyst_core::jsonc::from_str(&content).map_err(|e| unparseable(&e))?;
+            let mut changes = Vec::new();
+
+            // Older inits wrote hooks as an array Claude Code can't read
+            let legacy_hooks = match raw.get("hooks") {
+                Some(serde_json::Value::Array(entries)) => {
+                    let entries = entries.clone();
+                    raw.remove("hooks");
+                    changes.push("convert hooks from the old array format".to_string());
+                    Some(entries)
+                }
+                _ => None,
+            };
+            let mut settings: ClaudeSettings =
+                serde_json::from_value(serde_json::Value::Object(raw.clone()))
+                    .map_err(|e| unparseable(&e))?;
+            for entry in legacy_hooks.iter().flatten() {
+                match legacy_hook(entry) {
+                    Some((event, hook_config)) => settings
+                        .add_hook(event, hook_config)
+                        .map_err(|e| CatalystError::InvalidConfig(format!("{:#}", e)))?,
+                    None => report.warnings.push(format!(
+                        "⚠️  Dropped a hook entry from {} that names no known event or script: {}",
+                        SETTINGS_FILE, entry
+                    )),
+                }
+            }
+
+            // A hook counts as registered when its command is under its event
+            for (event, desired) in desired_hooks {
+                let registered = desired.hooks.iter().all(|hook| {
+                    settings.hooks.get(&event).is_some_and(|configs| {
+                        configs
+                            .iter()
+                            .flat_map(|config| &config.hooks)
+                            .any(|existing| existing.command == hook.command)
+                    })
+                });
+                if registered {
+                    continue;
+                }
+                changes.extend(
+                    desired
+                        .hooks
+                        .iter()
+                        .map(|hook| format!("register {} hook {}", event, hook.command)),
+                );
+                settings
+                    .add_hook(event, desired)
+                    .map_err(|e| CatalystError::InvalidConfig(format!("{:#}", e)))?;
+            }
+            let missing_keys: Vec<(&String, &serde_json::Value)> = config
+                .settings
+                .iter()
+                .filter(|(key, _)| *key != "hooks" && !raw.contains_key(*key))
+                .collect();
+            changes.extend(missing_keys.iter().map(|(key, _)| format!("add {}", key)));
+
+            if changes.is_empty() {
+                report.unchanged.push(SETTINGS_FILE.to_string());
+            } else {
+                match journal.as_deref_mut() {
+                    Some(journal) => {
+                        journal.record(SETTINGS_FILE)?;
+                        let mut updated =
+                            serde_json::to_value(&settings).map_err(CatalystError::Json)?;
+                        if let Some(updated) = updated.as_object_mut() {
+                            for (key, value) in missing_keys {
+                                updated.insert(key.clone(), value.clone());
+                            }
+                        }
+                        let content =
+                            serde_json::to_string_pretty(&updated).map_err(CatalystError::Json)?;
+                        write_file_atomic_with_policy(&settings_path, &content, policy)?;
+                    }
+                    None => report.planned_actions.push(format!(
+                        "Update {} ({})",
+                        SETTINGS_FILE,
+                        changes.join(", ")
+                    )),
+                }
+                report.updated.push(SETTINGS_FILE.to_string());
+            }
+        }
+    }
+
+    // Skills
+    let (present, missing): (Vec<String>, Vec<String>) =
+        config.skills.iter().cloned().partition(|skill_id| {
+            target.join(SKILLS_DIR).join(skill_id).exists() || library::is_linked(target, skill_id)
+        });
+    for skill_id in &present {
+        report
+            .unchanged
+            .push(format!("{}/{}", SKILLS_DIR, skill_id));
+    }
+    match journal.as_deref_mut() {
+        Some(journal) if !missing.is_empty() => {
+            for skill_id in &missing {
+                journal.record(Path::new(SKILLS_DIR).join(skill_id))?;
+            }
+            let (installed, failed) = install_skills_reporting_failures(
+                target,
+                &missing,
+                false,
+                config.skills_library.as_deref(),
+            )?;
+            report.installed_skills = installed;
+            report.failed_skills = failed;
+
+            // Abort mode stops here; initialize() rolls back what was written
+            if config.strict_skills == StrictSkills::Abort && !report.failed_skills.is_empty() {
+                return Err(strict_skills_error(report));
+            }
+        }
+        Some(_) => {}
+        None => {
+            for skill_id in &missing {
+                match resolve_installable_skill(target, skill_id, false) {
+                    Ok(_) => {
+                        report.planned_actions.push(format!(
+                            "Install skill {} into {}/{}",
+                            skill_id, SKILLS_DIR, skill_id
+                        ));
+                        report.installed_skills.push(skill_id.clone());
+                    }
+                    Err(e) => report.warnings.push(format!(
+                        "⚠️  Skill '{}' would not be installed: {}",
+                        skill_id, e
+                    )),
+                }
+            }
+        }
+    }
+
+    // Skill metadata for newly installed skills (gracefully degrade on failure)
+    let rules_exist = target.join(SKILL_RULES_FILE).exists();
+    let needs_rules = if rules_exist {
+        !report.installed_skills.is_empty()
+    } else {
+        !present.is_empty() || !report.installed_skills.is_empty()
+    };
+    match journal.as_deref_mut() {
+        Some(journal) => {
+            if needs_rules {
+                journal.record(SKILL_RULES_FILE)?;
+                let rules_result = if rules_exist {
+                    add_skill_rules(target, &report.installed_skills)
+                } else {
+                    let mut skills = present.clone();
+                    skills.extend(report.installed_skills.iter().cloned());
+                    generate_skill_rules(target, &skills)
+                };
+                if let Err(e) = rules_result {
+                    add_warning(
+                        &mut report,
+                        format!("Failed to update skill-rules.json: {}", e),
+                    );
+                }
+            }
+            if !report.installed_skills.is_empty() {
+                journal.record(Path::new(SKILLS_DIR).join(HASHES_FILE))?;
+            }
+            for skill_id in report.installed_skills.clone() {
+                if library::is_linked(target, &skill_id) {
+                    continue;
+                }
+                if let Err(e) = add_skill_hashes(target, &skill_id) {
+                    add_warning(
+                        &mut report,
+                        format!("Failed to update .catalyst-hashes.json: {}", e),
+                    );
+                }
+            }
+        }
+        None => {
+            if needs_rules {
+                report.planned_actions.push(format!(
+                    "{} {}",
+                    if rules_exist { "Update" } else { "Write" },
+                    SKILL_RULES_FILE
+                ));
+            }
+        }
+    }
+
+    // Version file
+    if read_version_file(target)?.as_deref() == Some(CATALYST_VERSION) {
+        report.unchanged.push(VERSION_FILE.to_string());
+    } else {
+        match journal.as_deref_mut() {
+            Some(journal) => {
+                journal.record(VERSION_FILE)?;
+                write_version_file(target)?;
+            }
+            None => report
+                .planned_actions
+                .push(format!("Write {} ({})", VERSION_FILE, CATALYST_VERSION)),
+        }
+        report.updated.push(VERSION_FILE.to_string());
+    }
+
+    lock_installation(config, journal, &mut report)?;
+
+    Ok(report)
+}
+
+/// Record the finished install in catalyst.lock, or, when reproducing the
+/// lockfile, warn about everything that came out different
+///
+/// Without a journal (a dry run) the write is only planned. Failing to
+/// capture or write the lockfile is a warning, like the other metadata files.
+fn lock_installation(
+    config: &InitConfig,
+    journal: Option<&mut InitJournal>,
+    report: &mut InitReport,
+) -> Result<()> {
+    let fs = Fs::read_only();
+    let target = &config.directory;
+
+    if config.from_lock {
+        // A dry run installs nothing to compare
+        if journal.is_none() {
+            return Ok(());
+        }
+        let Some(locked) = lockfile::read(&fs, target)? else {
+            return Ok(());
+        };
+        if locked.catalyst_version != CATALYST_VERSION {
+            report.warnings.push(format!(
+                "⚠️  {} was written by catalyst {} (this is {}); skill contents may differ",
+                LOCK_FILE, locked.catalyst_version, CATALYST_VERSION
+            ));
+        }
+        match Lockfile::capture(&fs, target, Platform::detect()) {
+            Ok(installed) => report.warnings.extend(
+                lockfile::drift(&locked, &installed)
+                    .into_iter()
+                    .map(|drift| format!("⚠️  {}", drift)),
+            ),
+            Err(e) => report
+                .warnings
+                .push(format!("⚠️  Failed to compare with {}: {}", LOCK_FILE, e)),
+        }
+        return Ok(());
+    }
+
+    let Some(journal) = journal else {
+        report.planned_actions.push(format!("Write {}", LOCK_FILE));
+        return Ok(());
+    };
+    journal.record(LOCK_FILE)?;
+    let result = Lockfile::capture(&fs, target, Platform::detect())
+        .and_then(|lock| lockfile::write(target, &lock));
+    match result {
+        Ok(()) => report.lockfile_written = true,
+        Err(e) => {
+            add_warning(report, format!("Failed to write {}: {}", LOCK_FILE, e));
+        }
+    }
+    Ok(())
+}
+
+/// Whether a wrapper has the mode its platform needs to run
+#[cfg(unix)]
+fn wrapper_mode_ok(wrapper_path: &Path, platform: Platform) -> bool {
+    platform == Platform::Windows
+        || fs::metadata(wrapper_path).is_ok_and(|meta| meta.permissions().mode() & 0o111 != 0)
+}
+
+/// Whether a wrapper has the mode its platform needs to run
+#[cfg(not(unix))]
+fn wrapper_mode_ok(_wrapper_path: &Path, _platform: Platform) -> bool {
+    true
+}
+
+/// Report a non-fatal problem as it happens and keep it in the init report
+fn add_warning(report: &mut InitReport, warning: String) {
+    report.warnings.push(format!("⚠️  {}", warning));
+    progress::warn(warning);
+}
+
+/// Error returned in strict skills mode, carrying the report
+fn strict_skills_error(report: InitReport) -> CatalystError {
+    let failed = report
+        .failed_skills
+        .iter()
+        .map(|skill| format!("{} ({})", skill.name, skill.error))
+        .collect::<Vec<_>>()
+        .join(", ");
+    CatalystError::StrictSkillsFailed {
+        failed,
+        report: Box::new(report),
+    }
+}
+
+/// Plan an initialization without writing anything
+///
+/// Runs the same checks as [`initialize`] and records what it would do in
+/// `InitReport::planned_actions`. The usual report fields are filled in with
+/// what would be created so the report can be displayed the same way.
+///
+/// # Errors
+///
+/// Returns the same fatal errors a real run would hit before writing
+/// (e.g., missing .claude directory).
+fn plan_initialization(config: &InitConfig) -> Result<InitReport> {
+    let mut report = InitReport::new();
+    report.dry_run = true;
+    report.skill_selection = config.skill_selection;
+    let project_config = CatalystConfig::load(&config.directory)?;
+    let platform = project_config.wrapper_platform(Platform::detect());
+    let target = &config.directory;
+
+    // Phase 2.1: Directory structure
+    for dir in directories_to_create(target, config.force)? {
+        report
+            .planned_actions
+            .push(format!("Create directory {}", dir));
+        report.created_dirs.push(dir.to_string());
+    }
+
+    // Phase 2.2: Wrapper scripts
+    let tracker_backend = save_tracker_backend(config, &project_config, None, &mut report)?;
+    for (binary_name, wrapper_name) in planned_wrappers(
+        config.install_hooks,
+        config.install_tracker,
+        config.install_check_hook,
+        tracker_backend,
+        platform,
+    ) {
+        report.planned_actions.push(format!(
+            "Write wrapper {}/{} (calls {})",
+            HOOKS_DIR, wrapper_name, binary_name
+        ));
+        report.installed_hooks.push(wrapper_name);
+    }
+
+    // Phase 2.3: settings.json
+    let action = if target.join(SETTINGS_FILE).exists() {
+        "Overwrite"
+    } else {
+        "Write"
+    };
+    report
+        .planned_actions
+        .push(format!("{} {}", action, SETTINGS_FILE));
+    for (event, hook_config) in settings_hooks(
+        config.install_hooks,
+        config.install_tracker,
+        config.install_check_hook,
+        platform,
+        &project_config,
+    ) {
+        for hook in &hook_config.hooks {
+            report
+                .planned_actions
+                .push(format!("Register {} hook → {}", event, hook.command));
+        }
+    }
+    if !config.settings.is_empty() {
+        let keys: Vec<&str> = config.settings.keys().map(String::as_str).collect();
+        report
+            .planned_actions
+            .push(format!("Add {} to {}", keys.join(", "), SETTINGS_FILE));
+    }
+    report.settings_created = true;
+
+    // Phase 3.1-3.2: Skills
+    for skill_id in &config.skills {
+        match resolve_installable_skill(target, skill_id, config.force) {
+            Ok(_) => {
+                match &config.skills_library {
+                    Some(library) => {
+                        let library_skill = library.join(skill_id);
+                        if !library_skill.join("SKILL.md").is_file() {
+                            report.planned_actions.push(format!(
+                                "Install skill {} into {}",
+                                skill_id,
+                                library_skill.display()
+                            ));
+                        }
+                        report.planned_actions.push(format!(
+                            "Link {}/{} → {}",
+                            SKILLS_DIR,
+                            skill_id,
+                            library_skill.display()
+                        ));
+                    }
+                    None => report.planned_actions.push(format!(
+                        "Install skill {} into {}/{}",
+                        skill_id, SKILLS_DIR, skill_id
+                    )),
+                }
+                report.installed_skills.push(skill_id.clone());
+            }
+            Err(e) => {
+                report.warnings.push(format!(
+                    "⚠️  Skill '{}' would not be installed: {}",
+                    skill_id, e
+                ));
+            }
+        }
+    }
+
+    // Phase 3.3-3.4: Skill metadata
+    if !report.installed_skills.is_empty() {
+        report
+            .planned_actions
+            .push(format!("Write {}", SKILL_RULES_FILE));
+        report
+            .planned_actions
+            .push(format!("Write {}/.catalyst-hashes.json", SKILLS_DIR));
+    }
+
+    // Phase 6.1: Version file
+    report
+        .planned_actions
+        .push(format!("Write {} ({})", VERSION_FILE, CATALYST_VERSION));
+    report.version_file_created = true;
+
+    // Phase 6.2: Lockfile
+    lock_installation(config, None, &mut report)?;
+
     Ok(report)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{PowerShell, SkillSelection};
     use tempfile::TempDir;
 
     #[test]
@@ -1074,94 +2252,6 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_acquire_init_lock() {
-        let temp_dir = TempDir::new().unwrap();
-        let target = temp_dir.path();
-
-        // First lock should succeed
-        let lock1 = acquire_init_lock(target).unwrap();
-
-        // Second lock should fail while first is held
-        let lock2 = acquire_init_lock(target);
-        assert!(lock2.is_err());
-        match lock2 {
-            Err(CatalystError::InitInProgress { pid, .. }) => {
-                assert_eq!(pid, process::id());
-            }
-            _ => panic!("Expected InitInProgress error"),
-        }
-
-        // Drop first lock
-        drop(lock1);
-
-        // Now second lock should succeed
-        let lock3 = acquire_init_lock(target);
-        assert!(lock3.is_ok());
-    }
-
-    #[test]
-    fn test_stale_lock_removal() {
-        let temp_dir = TempDir::new().unwrap();
-        let target = temp_dir.path();
-        let lock_file = target.join(LOCK_FILE);
-
-        // Create a stale lock file with a non-existent PID
-        fs::write(&lock_file, "999999").unwrap();
-
-        // Should remove stale lock and succeed
-        let lock = acquire_init_lock(target);
-        assert!(lock.is_ok());
-    }
-
-    #[test]
-    fn test_invalid_pid_lock_cleanup() {
-        let temp_dir = TempDir::new().unwrap();
-        let target = temp_dir.path();
-        let lock_file = target.join(LOCK_FILE);
-
-        // Test invalid PID 0 (reserved system PID)
-        fs::write(&lock_file, "0").unwrap();
-        let lock = acquire_init_lock(target);
-        assert!(lock.is_ok(), "Should clean up lock file with PID 0");
-        drop(lock);
-
-        // Test invalid PID 1 (init process PID)
-        fs::write(&lock_file, "1").unwrap();
-        let lock = acquire_init_lock(target);
-        assert!(lock.is_ok(), "Should clean up lock file with PID 1");
-    }
-
-    #[test]
-    fn test_malformed_lock_cleanup() {
-        let temp_dir = TempDir::new().unwrap();
-        let target = temp_dir.path();
-        let lock_file = target.join(LOCK_FILE);
-
-        // Test non-numeric content
-        fs::write(&lock_file, "not-a-number").unwrap();
-        let lock = acquire_init_lock(target);
-        assert!(
-            lock.is_ok(),
-            "Should clean up lock file with invalid content"
-        );
-        drop(lock);
-
-        // Test empty lock file
-        fs::write(&lock_file, "").unwrap();
-        let lock = acquire_init_lock(target);
-        assert!(lock.is_ok(), "Should clean up empty lock file");
-        drop(lock);
-
-        // Test lock file with whitespace
-        fs::write(&lock_file, "   \n\t  ").unwrap();
-        let lock = acquire_init_lock(target);
-        assert!(
-            lock.is_ok(),
-            "Should clean up lock file with only whitespace"
-        );
-    }
-
     #[test]
     fn test_directory_exists_as_file_error() {
         let temp_dir = TempDir::new().unwrap();
@@ -1213,7 +2303,9 @@ mod tests {
             target,
             true, // install_hooks
             true, // install_tracker
+            false,
             Platform::Linux,
+            PermissionPolicy::Preserve,
         )
         .unwrap();
 
@@ -1232,7 +2324,53 @@ mod tests {
         let content = fs::read_to_string(&skill_wrapper).unwrap();
         assert!(content.contains("skill-activation-prompt"));
         assert!(!content.contains("{{BINARY_NAME}}"));
+        assert!(!content.contains("{{PATH_PREAMBLE}}"));
         assert!(content.contains("#!/bin/bash"));
+        assert!(!content.contains("export PATH"));
+    }
+
+    #[test]
+    fn test_render_wrapper_path_preamble() {
+        let mut config = CatalystConfig::default();
+        config.wrappers.path = vec![
+            "/opt/tools/bin".to_string(),
+            "/home/me/.cargo/bin".to_string(),
+        ];
+        let project_dir = Path::new("/srv/app");
+
+        let sh = render_wrapper("cargo-check", Platform::Linux, &config, project_dir).unwrap();
+        assert!(sh.starts_with("#!/bin/bash"));
+        assert!(sh.contains("export PATH='/opt/tools/bin:/home/me/.cargo/bin':\"$PATH\"\n"));
+
+        let ps1 = render_wrapper("cargo-check", Platform::Windows, &config, project_dir).unwrap();
+        assert!(ps1.contains("$env:PATH = '/opt/tools/bin;/home/me/.cargo/bin;' + $env:PATH"));
+        assert!(!ps1.contains("{{PATH_PREAMBLE}}"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_generate_wrapper_scripts_preserves_mode() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(".claude")).unwrap();
+        create_directory_structure(target, false).unwrap();
+
+        let generate = |policy| {
+            generate_wrapper_scripts(target, true, false, false, Platform::Linux, policy).unwrap()
+        };
+        let wrapper = target.join(".claude/hooks/skill-activation-prompt.sh");
+        let mode = || fs::metadata(&wrapper).unwrap().permissions().mode() & 0o777;
+
+        generate(PermissionPolicy::Preserve);
+        assert_eq!(mode(), 0o755);
+
+        // Group-writable wrapper on a shared machine survives regeneration
+        fs::set_permissions(&wrapper, fs::Permissions::from_mode(0o775)).unwrap();
+        generate(PermissionPolicy::Preserve);
+        assert_eq!(mode(), 0o775);
+
+        generate(PermissionPolicy::Reset);
+        assert_eq!(mode(), 0o755);
     }
 
     #[test]
@@ -1249,7 +2387,9 @@ mod tests {
             target,
             true,  // install_hooks
             false, // install_tracker
+            false,
             Platform::Windows,
+            PermissionPolicy::Preserve,
         )
         .unwrap();
 
@@ -1284,7 +2424,9 @@ mod tests {
             target,
             true, // install_hooks
             true, // install_tracker
+            false,
             Platform::Linux,
+            PermissionPolicy::Preserve,
         )
         .unwrap();
 
@@ -1313,6 +2455,36 @@ mod tests {
         assert_eq!(read_content, content);
     }
 
+    #[test]
+    fn test_settings_hooks_invoke_powershell() {
+        let mut config = CatalystConfig::default();
+        config.wrappers.powershell = Some(PowerShell::Pwsh);
+
+        let command = |(_, config): &(HookEvent, HookConfig)| config.hooks[0].command.clone();
+        let hooks = settings_hooks(true, true, false, Platform::Windows, &config);
+        for (hook, wrapper) in hooks
+            .iter()
+            .zip(["skill-activation-prompt.ps1", "file-change-tracker.ps1"])
+        {
+            let command = command(hook);
+            assert!(command.starts_with("pwsh -NoProfile -ExecutionPolicy Bypass -File "));
+            assert!(command.ends_with(&format!("/.claude/hooks/{}\"", wrapper)));
+        }
+
+        let hooks = settings_hooks(true, false, true, Platform::Linux, &config);
+        assert_eq!(
+            command(&hooks[0]),
+            "$CLAUDE_PROJECT_DIR/.claude/hooks/skill-activation-prompt.sh"
+        );
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[1].0, HookEvent::PostToolUse);
+        assert_eq!(hooks[1].1.matcher.as_deref(), Some("Write|Edit|MultiEdit"));
+        assert_eq!(
+            command(&hooks[1]),
+            "$CLAUDE_PROJECT_DIR/.claude/hooks/cargo-check.sh"
+        );
+    }
+
     #[test]
     fn test_create_settings_json() {
         let temp_dir = TempDir::new().unwrap();
@@ -1321,12 +2493,16 @@ mod tests {
         // Create .claude directory
         fs::create_dir(target.join(".claude")).unwrap();
 
-        // Create settings.json with both hooks
+        // Create settings.json with both hooks and a profile's permissions
+        let extra = serde_json::json!({"permissions": {"allow": ["Bash(cargo test:*)"]}});
         let result = create_settings_json(
             target,
             true, // install_hooks
             true, // install_tracker
+            false,
             Platform::Linux,
+            PermissionPolicy::Preserve,
+            extra.as_object().unwrap(),
         );
         assert!(result.is_ok());
 
@@ -1334,31 +2510,64 @@ mod tests {
         let settings_path = target.join(".claude/settings.json");
         assert!(settings_path.exists());
 
-        // Parse and verify structure
+        // Parse and verify structure: {Event: [{matcher, hooks: [{type, command}]}]}
         let content = fs::read_to_string(&settings_path).unwrap();
         let settings: serde_json::Value = serde_json::from_str(&content).unwrap();
-
-        // Should have hooks array
-        let hooks = settings["hooks"].as_array().unwrap();
+        let hooks = settings["hooks"].as_object().unwrap();
         assert_eq!(hooks.len(), 2);
 
-        // First hook should be UserPromptSubmit
-        assert_eq!(hooks[0]["event"], "UserPromptSubmit");
-        assert!(hooks[0]["script"]
+        let prompt = &hooks["UserPromptSubmit"][0];
+        assert!(prompt.get("matcher").is_none());
+        assert_eq!(prompt["hooks"][0]["type"], "command");
+        assert!(prompt["hooks"][0]["command"]
             .as_str()
             .unwrap()
             .contains("skill-activation-prompt.sh"));
 
-        // Second hook should be PostToolUse
-        assert_eq!(hooks[1]["event"], "PostToolUse");
-        assert!(hooks[1]["script"]
+        let tracker = &hooks["PostToolUse"][0];
+        assert_eq!(tracker["matcher"], "Write|Edit|MultiEdit");
+        assert!(tracker["hooks"][0]["command"]
             .as_str()
             .unwrap()
             .contains("file-change-tracker.sh"));
 
-        // PostToolUse should have matchers
-        let matchers = hooks[1]["matchers"].as_array().unwrap();
-        assert_eq!(matchers.len(), 3);
+        assert_eq!(settings["permissions"]["allow"][0], "Bash(cargo test:*)");
+        // Claude Code's own parser accepts the file
+        let parsed = ClaudeSettings::read(&settings_path).unwrap();
+        parsed.validate().unwrap();
+    }
+
+    #[test]
+    fn test_initialize_then_status_finds_no_issues() {
+        use crate::types::IssueKind;
+
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(CLAUDE_DIR)).unwrap();
+        let config = InitConfig::builder(target)
+            .install_hooks(true)
+            .install_tracker(true)
+            .install_check_hook(true)
+            .skill(AVAILABLE_SKILLS[0])
+            .build()
+            .unwrap();
+        initialize(&config).unwrap();
+
+        // Hook binaries live in ~/.claude-hooks/bin, outside what init writes
+        let report = crate::status::validate_installation(target, Platform::detect()).unwrap();
+        let issues: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|issue| {
+                !matches!(
+                    issue.kind,
+                    IssueKind::BinaryMissing
+                        | IssueKind::BinaryNotExecutable
+                        | IssueKind::HookBinaryUnreachable
+                )
+            })
+            .collect();
+        assert!(issues.is_empty(), "{:#?}", issues);
     }
 
     #[test]
@@ -1374,7 +2583,10 @@ mod tests {
             target,
             true,  // install_hooks
             false, // no tracker
+            false,
             Platform::Windows,
+            PermissionPolicy::Preserve,
+            &serde_json::Map::new(),
         );
         assert!(result.is_ok());
 
@@ -1383,11 +2595,14 @@ mod tests {
         let content = fs::read_to_string(&settings_path).unwrap();
         let settings: serde_json::Value = serde_json::from_str(&content).unwrap();
 
-        let hooks = settings["hooks"].as_array().unwrap();
+        let hooks = settings["hooks"].as_object().unwrap();
         assert_eq!(hooks.len(), 1); // Only skill-activation-prompt
 
         // Should use .ps1 extension
-        assert!(hooks[0]["script"].as_str().unwrap().contains(".ps1"));
+        assert!(hooks["UserPromptSubmit"][0]["hooks"][0]["command"]
+            .as_str()
+            .unwrap()
+            .contains(".ps1"));
     }
 
     #[test]
@@ -1403,8 +2618,18 @@ mod tests {
             directory: target.to_path_buf(),
             install_hooks: true,
             install_tracker: true,
+            install_check_hook: false,
             skills: Vec::new(),
             force: false,
+            dry_run: false,
+            reset_permissions: false,
+            skills_library: None,
+            strict_skills: StrictSkills::Off,
+            skill_selection: SkillSelection::Explicit,
+            settings: serde_json::Map::new(),
+            reconcile: false,
+            from_lock: false,
+            tracker_backend: None,
         };
 
         // Run initialize
@@ -1447,7 +2672,7 @@ mod tests {
         fs::create_dir_all(target.join(".claude/skills")).unwrap();
 
         // Install skill-developer skill
-        let result = install_skill(target, "skill-developer", false);
+        let result = install_skill(target, "skill-developer", false, None);
         assert!(result.is_ok());
 
         // Verify skill directory exists
@@ -1468,7 +2693,7 @@ mod tests {
 
         // Install multiple skills
         let skills = vec!["skill-developer".to_string(), "rust-developer".to_string()];
-        let installed = install_skills(target, &skills, false).unwrap();
+        let installed = install_skills(target, &skills, false, None).unwrap();
 
         assert_eq!(installed.len(), 2);
         assert!(target
@@ -1479,6 +2704,33 @@ mod tests {
             .exists());
     }
 
+    #[test]
+    fn test_install_skills_keeps_order_and_skips_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir_all(target.join(".claude/skills")).unwrap();
+
+        let skills: Vec<String> = AVAILABLE_SKILLS
+            .iter()
+            .rev()
+            .map(|s| s.to_string())
+            .chain(["rust-developer".to_string(), "not-a-skill".to_string()])
+            .collect();
+        let installed = install_skills(target, &skills, false, None).unwrap();
+
+        // Every valid skill once, in the order requested
+        assert_eq!(installed, skills[..AVAILABLE_SKILLS.len()].to_vec());
+
+        generate_skill_hashes(target, &installed).unwrap();
+        let content =
+            fs::read_to_string(target.join(".claude/skills/.catalyst-hashes.json")).unwrap();
+        let hashes: HashMap<String, String> = serde_json::from_str(&content).unwrap();
+        for skill in AVAILABLE_SKILLS {
+            let key = Path::new(skill).join("SKILL.md");
+            assert!(hashes.contains_key(key.to_string_lossy().as_ref()));
+        }
+    }
+
     #[test]
     fn test_install_skill_invalid_id() {
         let temp_dir = TempDir::new().unwrap();
@@ -1488,7 +2740,7 @@ mod tests {
         fs::create_dir_all(target.join(".claude/skills")).unwrap();
 
         // Try to install invalid skill
-        let result = install_skill(target, "non-existent-skill", false);
+        let result = install_skill(target, "non-existent-skill", false, None);
         assert!(result.is_err());
 
         // Verify error message contains available skills
@@ -1498,6 +2750,23 @@ mod tests {
         assert!(err_msg.contains("skill-developer"));
     }
 
+    #[test]
+    fn test_install_skill_rejects_traversal_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("project");
+        fs::create_dir_all(target.join(".claude/skills")).unwrap();
+
+        for skill_id in ["../escaped", "../../etc", "/tmp/abs", ".hidden", "a/b"] {
+            let result = install_skill(&target, skill_id, true, None);
+            assert!(
+                matches!(result, Err(CatalystError::PathTraversalDetected(_))),
+                "{:?} was not rejected",
+                skill_id
+            );
+        }
+        assert!(!temp_dir.path().join("escaped").exists());
+    }
+
     #[test]
     fn test_generate_skill_rules() {
         let temp_dir = TempDir::new().unwrap();
@@ -1530,6 +2799,55 @@ mod tests {
         assert!(parsed["skills"]["rust-developer"].is_object());
     }
 
+    #[test]
+    fn test_rules_come_from_skill_manifests() {
+        let manifests = shipped_manifests();
+        assert_eq!(manifests.len(), AVAILABLE_SKILLS.len());
+        assert!(manifests.iter().all(|manifest| manifest.version.is_some()));
+
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let skill_dir = target.join(SKILLS_DIR).join("rust-developer");
+        fs::create_dir_all(&skill_dir).unwrap();
+        let shipped = SKILLS.get_dir("rust-developer").unwrap();
+        for name in [SKILL_FILE, SKILL_MANIFEST_FILE] {
+            let file = shipped.get_file(shipped.path().join(name)).unwrap();
+            fs::write(skill_dir.join(name), file.contents()).unwrap();
+        }
+
+        let rule = skill_rule(target, "rust-developer");
+        assert_eq!(
+            rule["promptTriggers"]["keywords"],
+            serde_json::json!(["rust"])
+        );
+        assert_eq!(rule["fileTriggers"]["pathPatterns"][1], "Cargo.toml");
+        let fallback = skill_rule(target, "skill-developer");
+        assert_eq!(
+            fallback["promptTriggers"]["keywords"],
+            serde_json::json!(["skill-developer"])
+        );
+    }
+
+    #[test]
+    fn test_generated_skill_rules_pass_lint() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let skills: Vec<String> = AVAILABLE_SKILLS.iter().map(|s| s.to_string()).collect();
+        for skill_id in &skills {
+            fs::create_dir_all(target.join(SKILLS_DIR).join(skill_id)).unwrap();
+        }
+        // Give the default path patterns something to match
+        for dir in ["src", "lib", "app", "tests"] {
+            fs::create_dir_all(target.join(dir)).unwrap();
+            fs::write(target.join(dir).join("main.rs"), "").unwrap();
+        }
+        generate_skill_rules(target, &skills).unwrap();
+
+        let report = crate::lint::lint_skill_rules(target).unwrap();
+        assert!(!report.has_errors(), "{:?}", report.findings);
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+    }
+
     #[test]
     fn test_hash_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -1669,4 +2987,421 @@ mod tests {
         // Clean up - restore permissions so tempdir can be deleted
         fs::set_permissions(target, fs::Permissions::from_mode(0o755)).unwrap();
     }
+
+    #[test]
+    fn test_initialize_dry_run_writes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(".claude")).unwrap();
+
+        let config = InitConfig {
+            directory: target.to_path_buf(),
+            install_hooks: true,
+            install_tracker: false,
+            install_check_hook: false,
+            skills: vec!["skill-developer".to_string(), "not-a-skill".to_string()],
+            force: false,
+            dry_run: true,
+            reset_permissions: false,
+            skills_library: None,
+            strict_skills: StrictSkills::Off,
+            skill_selection: SkillSelection::Explicit,
+            settings: serde_json::Map::new(),
+            reconcile: false,
+            from_lock: false,
+            tracker_backend: None,
+        };
+
+        let report = initialize(&config).unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.created_dirs.len(), 4);
+        assert_eq!(report.installed_skills, vec!["skill-developer".to_string()]);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report
+            .planned_actions
+            .iter()
+            .any(|a| a.contains("Register UserPromptSubmit hook")));
+
+        // Nothing but the pre-existing .claude directory
+        let entries: Vec<_> = fs::read_dir(target).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(fs::read_dir(target.join(".claude")).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_initialize_dry_run_reports_missing_claude_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = InitConfig {
+            directory: temp_dir.path().to_path_buf(),
+            dry_run: true,
+            ..InitConfig::default()
+        };
+
+        assert!(matches!(
+            initialize(&config),
+            Err(CatalystError::InvalidPath(_))
+        ));
+    }
+    #[cfg(unix)]
+    #[test]
+    fn test_initialize_links_skills_from_library() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("project");
+        let library = temp_dir.path().join("library");
+        fs::create_dir_all(target.join(".claude")).unwrap();
+
+        let config = InitConfig {
+            directory: target.clone(),
+            skills: vec!["skill-developer".to_string()],
+            skills_library: Some(library.clone()),
+            strict_skills: StrictSkills::Off,
+            ..InitConfig::default()
+        };
+        let report = initialize(&config).unwrap();
+        assert_eq!(report.installed_skills, vec!["skill-developer".to_string()]);
+
+        assert!(library::is_linked(&target, "skill-developer"));
+        assert!(library.join("skill-developer/SKILL.md").is_file());
+
+        let rules = fs::read_to_string(target.join(SKILL_RULES_FILE)).unwrap();
+        assert!(rules.contains("\"source\": \"library\""));
+
+        // Re-initializing without the library replaces the link with a copy
+        // and leaves the library alone
+        let config = InitConfig {
+            skills_library: None,
+            strict_skills: StrictSkills::Off,
+            force: true,
+            ..config
+        };
+        initialize(&config).unwrap();
+        assert!(!library::is_linked(&target, "skill-developer"));
+        assert!(library.join("skill-developer/SKILL.md").is_file());
+    }
+
+    #[test]
+    fn test_initialize_rolls_back_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(".claude")).unwrap();
+
+        // A directory where settings.json should be makes the settings phase
+        // fail after directories and wrappers were already created
+        let blocker = target.join(SETTINGS_FILE);
+        fs::create_dir(&blocker).unwrap();
+        fs::write(blocker.join("keep.txt"), "user data").unwrap();
+
+        let config = InitConfig {
+            directory: target.to_path_buf(),
+            ..InitConfig::default()
+        };
+        assert!(initialize(&config).is_err());
+
+        // Only the original contents remain
+        let mut entries: Vec<_> = fs::read_dir(target.join(".claude"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, vec!["settings.json"]);
+        assert_eq!(
+            fs::read_to_string(blocker.join("keep.txt")).unwrap(),
+            "user data"
+        );
+        assert!(!target.join(VERSION_FILE).exists());
+        assert!(!target.join(BACKUP_DIR).exists());
+    }
+
+    #[test]
+    fn test_initialize_cancelled_rolls_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(".claude")).unwrap();
+
+        // Cancel once settings.json is written, before the skills phase
+        let settings = target.join(SETTINGS_FILE);
+        let check = cancel::CancelCheck::new(move || settings.exists());
+        let config = InitConfig {
+            directory: target.to_path_buf(),
+            skills: vec!["skill-developer".to_string()],
+            ..InitConfig::default()
+        };
+        let result = cancel::with_cancellation(check, || initialize(&config));
+
+        assert!(matches!(result, Err(CatalystError::Cancelled)));
+        assert_eq!(fs::read_dir(target.join(".claude")).unwrap().count(), 0);
+        assert!(!target.join(VERSION_FILE).exists());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_initialize_async_cancelled_removes_everything() {
+        use crate::progress::{self, EventHook, Verbosity};
+        use std::sync::Arc;
+        use tokio_util::sync::CancellationToken;
+
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(".claude")).unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let config = InitConfig {
+            directory: target.to_path_buf(),
+            skills: vec!["skill-developer".to_string()],
+            ..InitConfig::default()
+        };
+
+        // Cancel from the skills phase's progress events, once
+        // settings.json is written
+        let cancel = CancellationToken::new();
+        let hook = EventHook({
+            let cancel = cancel.clone();
+            let settings = target.join(SETTINGS_FILE);
+            move || {
+                if settings.exists() {
+                    cancel.cancel();
+                }
+            }
+        });
+        let result = progress::with_test_reporter(Arc::new(hook), Verbosity::Normal, || {
+            runtime.block_on(initialize_async(config.clone(), cancel.clone()))
+        });
+
+        assert!(matches!(result, Err(CatalystError::Cancelled)));
+        assert!(cancel.is_cancelled());
+        let left: Vec<_> = fs::read_dir(target)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(left, vec![".claude"]);
+        assert_eq!(fs::read_dir(target.join(".claude")).unwrap().count(), 0);
+
+        // A token cancelled up front stops init before it writes anything
+        let result = runtime.block_on(initialize_async(config, cancel));
+        assert!(matches!(result, Err(CatalystError::Cancelled)));
+        assert_eq!(fs::read_dir(target.join(".claude")).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_initialize_strict_skills_modes() {
+        let skills = vec!["skill-developer".to_string(), "not-a-skill".to_string()];
+
+        // Off: success, with the failure recorded in the report
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join(".claude")).unwrap();
+        let config = InitConfig {
+            directory: temp_dir.path().to_path_buf(),
+            skills: skills.clone(),
+            strict_skills: StrictSkills::Off,
+            ..InitConfig::default()
+        };
+        let report = initialize(&config).unwrap();
+        assert_eq!(report.installed_skills, vec!["skill-developer".to_string()]);
+        assert_eq!(report.failed_skills.len(), 1);
+        assert_eq!(report.failed_skills[0].name, "not-a-skill");
+
+        // Finish: remaining phases are kept, but init errors
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join(".claude")).unwrap();
+        let config = InitConfig {
+            directory: temp_dir.path().to_path_buf(),
+            strict_skills: StrictSkills::Finish,
+            ..config
+        };
+        match initialize(&config) {
+            Err(CatalystError::StrictSkillsFailed { failed, report }) => {
+                assert!(failed.starts_with("not-a-skill ("));
+                assert_eq!(report.failed_skills.len(), 1);
+            }
+            other => panic!("expected StrictSkillsFailed, got {:?}", other),
+        }
+        assert!(temp_dir.path().join(VERSION_FILE).exists());
+        assert!(temp_dir.path().join(SETTINGS_FILE).exists());
+
+        // Abort: everything init created is rolled back
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join(".claude")).unwrap();
+        let config = InitConfig {
+            directory: temp_dir.path().to_path_buf(),
+            strict_skills: StrictSkills::Abort,
+            ..config
+        };
+        assert!(matches!(
+            initialize(&config),
+            Err(CatalystError::StrictSkillsFailed { .. })
+        ));
+        assert!(!temp_dir.path().join(VERSION_FILE).exists());
+        assert!(!temp_dir.path().join(SETTINGS_FILE).exists());
+    }
+
+    #[test]
+    fn test_initialize_tracker_backend_picks_wrapper_binary() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(".claude")).unwrap();
+        let config = InitConfig {
+            directory: target.to_path_buf(),
+            install_hooks: false,
+            tracker_backend: Some(TrackerBackend::Jsonl),
+            ..InitConfig::default()
+        };
+        initialize(&config).unwrap();
+
+        let wrapper = target.join(HOOKS_DIR).join(format!(
+            "file-change-tracker.{}",
+            Platform::detect().hook_extension()
+        ));
+        let content = fs::read_to_string(&wrapper).unwrap();
+        assert!(content.contains("file-change-tracker-basic"));
+        // Saved, so later wrapper rewrites call the same binary
+        let project_config = CatalystConfig::load(target).unwrap();
+        assert_eq!(project_config.tracker.backend, TrackerBackend::Jsonl);
+    }
+
+    #[test]
+    fn test_initialize_reconcile_only_fixes_what_differs() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(".claude")).unwrap();
+        let config = InitConfig {
+            directory: target.to_path_buf(),
+            install_tracker: false,
+            skills: vec!["skill-developer".to_string()],
+            ..InitConfig::default()
+        };
+        initialize(&config).unwrap();
+
+        // A fresh install is already correct
+        let config = InitConfig {
+            reconcile: true,
+            ..config
+        };
+        let report = initialize(&config).unwrap();
+        assert!(report.reconciled);
+        assert!(report.updated.is_empty(), "{:?}", report.updated);
+        assert!(report.installed_skills.is_empty() && report.installed_hooks.is_empty());
+        assert!(report.unchanged.contains(&SETTINGS_FILE.to_string()));
+
+        // A stale wrapper is rewritten, a new skill and hook are added
+        let wrapper = format!(
+            "skill-activation-prompt.{}",
+            Platform::detect().hook_extension()
+        );
+        fs::write(target.join(HOOKS_DIR).join(&wrapper), "echo old\n").unwrap();
+        let config = InitConfig {
+            install_tracker: true,
+            skills: vec!["skill-developer".to_string(), "rust-developer".to_string()],
+            ..config
+        };
+        let report = initialize(&config).unwrap();
+        assert!(report
+            .updated
+            .contains(&format!("{}/{}", HOOKS_DIR, wrapper)));
+        assert!(report.updated.contains(&SETTINGS_FILE.to_string()));
+        assert_eq!(report.installed_skills, ["rust-developer"]);
+        assert_eq!(report.installed_hooks.len(), 1);
+
+        let rules = fs::read_to_string(target.join(SKILL_RULES_FILE)).unwrap();
+        assert!(rules.contains("\"skill-developer\"") && rules.contains("\"rust-developer\""));
+        let settings = fs::read_to_string(target.join(SETTINGS_FILE)).unwrap();
+        assert!(settings.contains("file-change-tracker"));
+    }
+
+    #[test]
+    fn test_reconcile_converts_legacy_hook_array() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(CLAUDE_DIR)).unwrap();
+        let config = InitConfig {
+            directory: target.to_path_buf(),
+            ..InitConfig::default()
+        };
+        initialize(&config).unwrap();
+
+        // What init used to write, plus a hook of the user's own
+        let extension = Platform::detect().hook_extension();
+        let legacy = serde_json::json!({
+            "model": "opus",
+            "hooks": [
+                {
+                    "event": "UserPromptSubmit",
+                    "script": format!("$CLAUDE_PROJECT_DIR/.claude/hooks/skill-activation-prompt.{}", extension),
+                    "async": false
+                },
+                {
+                    "event": "PostToolUse",
+                    "script": "./lint.sh",
+                    "matchers": [{"toolName": "Write"}, {"toolName": "Edit"}]
+                }
+            ]
+        });
+        fs::write(target.join(SETTINGS_FILE), legacy.to_string()).unwrap();
+
+        let report = initialize(&InitConfig {
+            reconcile: true,
+            ..config
+        })
+        .unwrap();
+        assert!(report.updated.contains(&SETTINGS_FILE.to_string()));
+
+        let settings = ClaudeSettings::read(target.join(SETTINGS_FILE)).unwrap();
+        settings.validate().unwrap();
+        assert_eq!(settings.extra["model"], "opus");
+        let prompt = &settings.hooks[&HookEvent::UserPromptSubmit];
+        assert_eq!(prompt.len(), 1, "converted entry isn't registered twice");
+        let lint = &settings.hooks[&HookEvent::PostToolUse];
+        assert!(lint.iter().any(|config| {
+            config.matcher.as_deref() == Some("Write|Edit")
+                && config.hooks[0].command == "./lint.sh"
+        }));
+    }
+
+    #[test]
+    fn test_init_config_builder_validates() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = InitConfig::builder(temp_dir.path())
+            .skills(["skill-developer", "rust-developer"])
+            .skill("skill-developer")
+            .install_tracker(false)
+            .build()
+            .unwrap();
+        assert_eq!(config.skills, ["skill-developer", "rust-developer"]);
+        assert!(config.install_hooks && !config.install_tracker);
+        assert_eq!(config.directory, temp_dir.path());
+
+        let unknown = InitConfig::builder(temp_dir.path()).skill("no-such-skill");
+        assert!(matches!(
+            unknown.build(),
+            Err(CatalystError::InvalidConfig(_))
+        ));
+        let conflicting = InitConfig::builder(temp_dir.path())
+            .force(true)
+            .reconcile(true);
+        assert!(matches!(
+            conflicting.build(),
+            Err(CatalystError::InvalidConfig(_))
+        ));
+        let missing = InitConfig::builder(temp_dir.path().join("missing"));
+        assert!(matches!(
+            missing.build(),
+            Err(CatalystError::PathNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_init_config_detect_reads_catalyst_toml() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(CONFIG_FILE),
+            "[init]\nskills = [\"rust-developer\"]\ninstall_tracker = false\n",
+        )
+        .unwrap();
+
+        let config = InitConfig::detect(temp_dir.path()).unwrap();
+        assert_eq!(config.skills, ["rust-developer"]);
+        assert_eq!(config.skill_selection, SkillSelection::ProjectConfig);
+        assert!(!config.install_tracker);
+        assert!(!config.force && !config.dry_run);
+    }
 }
@@ -0,0 +1,231 @@
+//! Size caps on what hook binaries match and analyze
+//!
+//! A pasted file or a base64 blob can turn a prompt into megabytes of text,
+//! and every keyword and intent pattern then scans all of it. The hooks cap
+//! their input before matching: the prompt keeps its first
+//! `CATALYST_MAX_PROMPT_BYTES` bytes, whitespace-free runs longer than
+//! [`MAX_TOKEN_BYTES`] (encoded data, minified code) are dropped, and the
+//! tracker reads at most `CATALYST_MAX_ANALYZED_BYTES` of an edited file and
+//! skips files that look binary. Setting either variable to `0` removes
+//! that cap.
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Default cap on prompt text used for skill matching
+pub const DEFAULT_MAX_PROMPT_BYTES: usize = 64 * 1024;
+
+/// Default cap on file content read for analysis
+pub const DEFAULT_MAX_ANALYZED_BYTES: usize = 1024 * 1024;
+
+/// Environment variable overriding [`DEFAULT_MAX_PROMPT_BYTES`]
+pub const MAX_PROMPT_BYTES_ENV: &str = "CATALYST_MAX_PROMPT_BYTES";
+
+/// Environment variable overriding [`DEFAULT_MAX_ANALYZED_BYTES`]
+pub const MAX_ANALYZED_BYTES_ENV: &str = "CATALYST_MAX_ANALYZED_BYTES";
+
+/// Whitespace-free runs longer than this carry no words worth matching
+pub const MAX_TOKEN_BYTES: usize = 512;
+
+/// Bytes inspected when deciding whether content is binary
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Caps in effect for one hook run; None means unlimited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputLimits {
+    pub max_prompt_bytes: Option<usize>,
+    pub max_analyzed_bytes: Option<usize>,
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        Self {
+            max_prompt_bytes: Some(DEFAULT_MAX_PROMPT_BYTES),
+            max_analyzed_bytes: Some(DEFAULT_MAX_ANALYZED_BYTES),
+        }
+    }
+}
+
+impl InputLimits {
+    /// Limits from the environment, falling back to the defaults for unset
+    /// or unparsable values
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            max_prompt_bytes: env_limit(MAX_PROMPT_BYTES_ENV, defaults.max_prompt_bytes),
+            max_analyzed_bytes: env_limit(MAX_ANALYZED_BYTES_ENV, defaults.max_analyzed_bytes),
+        }
+    }
+}
+
+fn env_limit(var: &str, default: Option<usize>) -> Option<usize> {
+    match std::env::var(var)
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+    {
+        Some(0) => None,
+        Some(limit) => Some(limit),
+        None => default,
+    }
+}
+
+/// A prompt prepared for matching, with what was cut from it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CappedPrompt<'a> {
+    pub text: Cow<'a, str>,
+    /// Bytes dropped from the end of the prompt
+    pub truncated_bytes: usize,
+    /// Over-long whitespace-free runs removed
+    pub elided_tokens: usize,
+}
+
+impl CappedPrompt<'_> {
+    /// Whether anything was removed
+    pub fn was_capped(&self) -> bool {
+        self.truncated_bytes > 0 || self.elided_tokens > 0
+    }
+}
+
+/// Keep the head of a prompt and drop encoded blobs from it
+///
+/// # Arguments
+///
+/// * `prompt` - Prompt as submitted
+/// * `max_bytes` - Bytes to keep, or None to keep the whole prompt; the cut
+///   falls on a character boundary
+pub fn cap_prompt(prompt: &str, max_bytes: Option<usize>) -> CappedPrompt<'_> {
+    let head = match max_bytes {
+        Some(max) if prompt.len() > max => &prompt[..floor_char_boundary(prompt, max)],
+        _ => prompt,
+    };
+    let truncated_bytes = prompt.len() - head.len();
+
+    let elided_tokens = head
+        .split_whitespace()
+        .filter(|token| token.len() > MAX_TOKEN_BYTES)
+        .count();
+    let text = if elided_tokens == 0 {
+        Cow::Borrowed(head)
+    } else {
+        let mut kept = String::with_capacity(head.len());
+        for token in head
+            .split_whitespace()
+            .filter(|token| token.len() <= MAX_TOKEN_BYTES)
+        {
+            if !kept.is_empty() {
+                kept.push(' ');
+            }
+            kept.push_str(token);
+        }
+        Cow::Owned(kept)
+    };
+
+    CappedPrompt {
+        text,
+        truncated_bytes,
+        elided_tokens,
+    }
+}
+
+/// Largest index no greater than `index` that starts a character
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    (0..=index.min(text.len()))
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(0)
+}
+
+/// Whether content looks binary (has a NUL byte near the start)
+pub fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
+/// Head of a file read for analysis
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileHead {
+    /// Text content, and how many bytes past the cap were not read
+    Text {
+        content: String,
+        truncated_bytes: u64,
+    },
+    /// The file looks binary and wasn't analyzed
+    Binary,
+}
+
+/// Read at most `max_bytes` of a file as text
+///
+/// Invalid UTF-8 (including a character split by the cap) is replaced
+/// rather than rejected.
+///
+/// # Errors
+///
+/// Returns the I/O error if the file can't be opened or read
+pub fn read_file_head(path: &Path, max_bytes: Option<usize>) -> io::Result<FileHead> {
+    let file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let mut bytes = Vec::new();
+    match max_bytes {
+        Some(max) => file.take(max as u64).read_to_end(&mut bytes)?,
+        None => (&file).read_to_end(&mut bytes)?,
+    };
+    if looks_binary(&bytes) {
+        return Ok(FileHead::Binary);
+    }
+    Ok(FileHead::Text {
+        truncated_bytes: len.saturating_sub(bytes.len() as u64),
+        content: String::from_utf8_lossy(&bytes).into_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cap_prompt_truncates_head_and_drops_blobs() {
+        let short = cap_prompt("fix the login route", Some(64));
+        assert_eq!(short.text, "fix the login route");
+        assert!(!short.was_capped());
+        assert!(matches!(short.text, Cow::Borrowed(_)));
+
+        // The cut never splits a character
+        let capped = cap_prompt("add a café route", Some(10));
+        assert_eq!(capped.text, "add a caf");
+        assert_eq!(capped.truncated_bytes, "é route".len());
+
+        let blob = "QUJD".repeat(200);
+        let prompt = format!("debug this payload {} in the parser", blob);
+        let capped = cap_prompt(&prompt, None);
+        assert_eq!(capped.text, "debug this payload in the parser");
+        assert_eq!(capped.elided_tokens, 1);
+        assert_eq!(capped.truncated_bytes, 0);
+    }
+
+    #[test]
+    fn test_read_file_head() {
+        let temp_dir = TempDir::new().unwrap();
+        let text = temp_dir.path().join("big.ts");
+        std::fs::write(&text, "a".repeat(100)).unwrap();
+        assert_eq!(
+            read_file_head(&text, Some(40)).unwrap(),
+            FileHead::Text {
+                content: "a".repeat(40),
+                truncated_bytes: 60
+            }
+        );
+        assert!(matches!(
+            read_file_head(&text, None).unwrap(),
+            FileHead::Text {
+                truncated_bytes: 0,
+                ..
+            }
+        ));
+
+        let binary = temp_dir.path().join("image.js");
+        std::fs::write(&binary, [0x89, b'P', b'N', b'G', 0, 0, 1]).unwrap();
+        assert_eq!(read_file_head(&binary, Some(40)).unwrap(), FileHead::Binary);
+    }
+}
@@ -0,0 +1,284 @@
+//! Reproducible install metadata (`catalyst install-manifest`)
+//!
+//! Lists the release archive for every supported target triple with its
+//! download URL and SHA-256, pinned to the version of the running catalyst,
+//! so teams can install the same binaries through Nix, Bazel, or any other
+//! tool that wants a URL and a hash.
+//!
+//! Versions, targets, and archive names are built in. Hashes come from the
+//! release's `SHA256SUMS`: builds made with `CATALYST_RELEASE_SHA256SUMS`
+//! set to its contents carry it embedded, and other builds download it from
+//! the release.
+
+use crate::self_update::{curl, find_checksum, CHECKSUMS_ASSET};
+use crate::target::{TargetOs, TargetTriple, SUPPORTED_TARGETS};
+use crate::types::{CatalystError, Result, CATALYST_VERSION};
+use serde::Serialize;
+use std::fmt::Write as _;
+
+/// `SHA256SUMS` of this build's release, if it was embedded at build time
+pub const EMBEDDED_CHECKSUMS: Option<&str> = option_env!("CATALYST_RELEASE_SHA256SUMS");
+
+/// Output format for the manifest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ManifestFormat {
+    #[default]
+    Json,
+    Nix,
+}
+
+impl std::str::FromStr for ManifestFormat {
+    type Err = CatalystError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "json" => Ok(ManifestFormat::Json),
+            "nix" => Ok(ManifestFormat::Nix),
+            _ => Err(CatalystError::InvalidConfig(format!(
+                "Unknown manifest format '{}'. Expected json or nix",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+/// Release archive for one target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Artifact {
+    /// Rust target triple
+    pub target: String,
+
+    /// Nix system double (e.g. `x86_64-linux`), where Nix has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nix_system: Option<String>,
+
+    /// Archive file name
+    pub name: String,
+
+    pub url: String,
+
+    /// Lowercase hex SHA-256 of the archive
+    pub sha256: String,
+}
+
+/// Everything needed to install one catalyst release reproducibly
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InstallManifest {
+    pub version: String,
+    pub repository: String,
+    pub artifacts: Vec<Artifact>,
+}
+
+/// Build the manifest for this catalyst version
+///
+/// # Returns
+///
+/// Returns one artifact per supported target the release's `SHA256SUMS`
+/// lists
+///
+/// # Errors
+///
+/// Returns `SelfUpdateFailed` if the checksums aren't embedded and can't be
+/// downloaded, and `InvalidConfig` if they list none of the supported
+/// targets
+pub fn install_manifest() -> Result<InstallManifest> {
+    let checksums = match EMBEDDED_CHECKSUMS {
+        Some(checksums) => checksums.to_string(),
+        None => {
+            let url = format!("{}/{}", release_url(CATALYST_VERSION), CHECKSUMS_ASSET);
+            let content = curl(&[&url])?;
+            String::from_utf8_lossy(&content).into_owned()
+        }
+    };
+    manifest_from_checksums(CATALYST_VERSION, &checksums)
+}
+
+/// Build a manifest for `version` from the contents of its `SHA256SUMS`
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if no supported target's archive is listed
+pub fn manifest_from_checksums(version: &str, checksums: &str) -> Result<InstallManifest> {
+    let base_url = release_url(version);
+    let artifacts: Vec<Artifact> = SUPPORTED_TARGETS
+        .iter()
+        .filter_map(|triple| triple.parse::<TargetTriple>().ok())
+        .filter_map(|target| {
+            // Older releases only have the `<arch>-<os>` archive names
+            target.archive_names().into_iter().find_map(|name| {
+                let sha256 = find_checksum(checksums, &name)?.to_ascii_lowercase();
+                Some(Artifact {
+                    target: target.to_string(),
+                    nix_system: nix_system(&target),
+                    url: format!("{}/{}", base_url, name),
+                    name,
+                    sha256,
+                })
+            })
+        })
+        .collect();
+
+    if artifacts.is_empty() {
+        return Err(CatalystError::InvalidConfig(format!(
+            "{} for v{} lists no release archives for supported targets",
+            CHECKSUMS_ASSET, version
+        )));
+    }
+
+    Ok(InstallManifest {
+        version: version.to_string(),
+        repository: env!("CARGO_PKG_REPOSITORY").to_string(),
+        artifacts,
+    })
+}
+
+fn release_url(version: &str) -> String {
+    format!(
+        "{}/releases/download/v{}",
+        env!("CARGO_PKG_REPOSITORY"),
+        version
+    )
+}
+
+/// Nix system for a target; musl builds share the glibc one's system, so
+/// only the glibc archive gets it
+fn nix_system(target: &TargetTriple) -> Option<String> {
+    let os = match target.os {
+        TargetOs::Linux { musl: false } => "linux",
+        TargetOs::MacOS => "darwin",
+        TargetOs::Linux { musl: true } | TargetOs::Windows => return None,
+    };
+    Some(format!("{}-{}", target.arch, os))
+}
+
+impl InstallManifest {
+    /// Render the manifest in the given format
+    ///
+    /// # Errors
+    ///
+    /// Returns `Json` if the manifest can't be serialized
+    pub fn render(&self, format: ManifestFormat) -> Result<String> {
+        match format {
+            ManifestFormat::Json => serde_json::to_string_pretty(self)
+                .map(|json| format!("{}\n", json))
+                .map_err(CatalystError::Json),
+            ManifestFormat::Nix => Ok(self.to_nix()),
+        }
+    }
+
+    /// An attribute set usable with `builtins.fetchurl` or `pkgs.fetchurl`
+    ///
+    /// `systems` maps each Nix system to its archive, so a flake can pick
+    /// `manifest.systems.${system}`.
+    fn to_nix(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# Generated by `catalyst install-manifest --format nix`"
+        );
+        let _ = writeln!(out, "{{");
+        let _ = writeln!(out, "  version = {};", nix_string(&self.version));
+        let _ = writeln!(out, "  repository = {};", nix_string(&self.repository));
+        let _ = writeln!(out, "  artifacts = {{");
+        for artifact in &self.artifacts {
+            let _ = writeln!(out, "    {} = {{", nix_string(&artifact.target));
+            let _ = writeln!(out, "      name = {};", nix_string(&artifact.name));
+            let _ = writeln!(out, "      url = {};", nix_string(&artifact.url));
+            let _ = writeln!(out, "      sha256 = {};", nix_string(&artifact.sha256));
+            let _ = writeln!(out, "    }};");
+        }
+        let _ = writeln!(out, "  }};");
+        let _ = writeln!(out, "  systems = {{");
+        for artifact in &self.artifacts {
+            if let Some(system) = &artifact.nix_system {
+                let _ = writeln!(
+                    out,
+                    "    {} = {{ url = {}; sha256 = {}; }};",
+                    nix_string(system),
+                    nix_string(&artifact.url),
+                    nix_string(&artifact.sha256)
+                );
+            }
+        }
+        let _ = writeln!(out, "  }};");
+        let _ = writeln!(out, "}}");
+        out
+    }
+}
+
+/// A Nix string literal
+fn nix_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("${", "\\${");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const CHECKSUMS: &str = "\
+aaaa  catalyst-x86_64-unknown-linux-gnu.tar.gz
+BBBB *catalyst-aarch64-apple-darwin.tar.gz
+cccc  catalyst-x86_64-windows.zip
+dddd  catalyst-riscv64-unknown-linux-gnu.tar.gz
+";
+
+    #[test]
+    fn test_manifest_from_checksums() {
+        let manifest = manifest_from_checksums("1.2.3", CHECKSUMS).unwrap();
+        let targets: Vec<&str> = manifest
+            .artifacts
+            .iter()
+            .map(|a| a.target.as_str())
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                "x86_64-unknown-linux-gnu",
+                "aarch64-apple-darwin",
+                "x86_64-pc-windows-msvc"
+            ]
+        );
+
+        let darwin = &manifest.artifacts[1];
+        assert_eq!(darwin.sha256, "bbbb");
+        assert_eq!(darwin.nix_system.as_deref(), Some("aarch64-darwin"));
+        assert!(darwin
+            .url
+            .ends_with("/releases/download/v1.2.3/catalyst-aarch64-apple-darwin.tar.gz"));
+        // Legacy archive name, and no Nix system for Windows
+        assert_eq!(manifest.artifacts[2].name, "catalyst-x86_64-windows.zip");
+        assert_eq!(manifest.artifacts[2].nix_system, None);
+
+        assert!(manifest_from_checksums("1.2.3", "eeee  unrelated.txt\n").is_err());
+    }
+
+    #[test]
+    fn test_render_nix_and_json() {
+        let manifest = manifest_from_checksums("1.2.3", CHECKSUMS).unwrap();
+
+        let nix = manifest.render(ManifestFormat::Nix).unwrap();
+        assert!(nix.contains("  version = \"1.2.3\";"));
+        assert!(nix.contains("    \"x86_64-unknown-linux-gnu\" = {"));
+        assert!(nix.contains("    \"x86_64-linux\" = { url = \""));
+        assert!(!nix.contains("\"x86_64-windows\""));
+        assert_eq!(nix.matches('{').count(), nix.matches('}').count());
+
+        let json: serde_json::Value =
+            serde_json::from_str(&manifest.render(ManifestFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["version"], "1.2.3");
+        assert_eq!(json["artifacts"][0]["sha256"], "aaaa");
+        assert!(json["artifacts"][2].get("nix_system").is_none());
+
+        assert_eq!(
+            ManifestFormat::from_str("NIX").unwrap(),
+            ManifestFormat::Nix
+        );
+        assert!(ManifestFormat::from_str("bazel").is_err());
+        assert_eq!(nix_string("a\"${b}"), "\"a\\\"\\${b}\"");
+    }
+}
@@ -0,0 +1,374 @@
+//! Update journal for crash-safe, resumable updates
+//!
+//! Before `catalyst update` modifies anything it writes a journal listing
+//! every planned step. Each step moves through three states:
+//!
+//! 1. `Pending` - nothing has been touched yet
+//! 2. `BackedUp` - the affected path was saved to the backup set and may now
+//!    be partially overwritten
+//! 3. `Completed` - the step finished
+//!
+//! The journal is rewritten atomically after every transition, so after a
+//! crash or Ctrl-C the next run can tell exactly which steps need to be
+//! resumed, or which paths need to be restored to roll the update back.
+//!
+//! `catalyst init` uses the lighter, in-memory [`InitJournal`]: init has
+//! nothing to resume, so it only needs to undo its own changes when a fatal
+//! error stops it partway through.
+
+use crate::backup::{backup_path, create_backup_set, remove_backup_set, restore_path};
+use crate::init::write_file_atomic;
+use crate::types::{
+    CatalystError, Result, SkippedSkill, HASHES_FILE, HOOKS_DIR, SKILLS_DIR, UPDATE_JOURNAL_FILE,
+    VERSION_FILE,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A single step of an update
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UpdateStep {
+    /// Regenerate hook wrapper scripts
+    Wrappers,
+
+    /// Copy a skill from the embedded resources
+    Skill { name: String },
+
+    /// Regenerate .catalyst-hashes.json for updated skills
+    Hashes,
+
+    /// Record the new version in .catalyst-version
+    VersionFile,
+}
+
+impl UpdateStep {
+    /// Path this step modifies, relative to the project directory
+    pub fn path(&self) -> PathBuf {
+        match self {
+            UpdateStep::Wrappers => PathBuf::from(HOOKS_DIR),
+            UpdateStep::Skill { name } => Path::new(SKILLS_DIR).join(name),
+            UpdateStep::Hashes => PathBuf::from(HASHES_FILE),
+            UpdateStep::VersionFile => PathBuf::from(VERSION_FILE),
+        }
+    }
+}
+
+impl std::fmt::Display for UpdateStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UpdateStep::Wrappers => write!(f, "update hook wrappers"),
+            UpdateStep::Skill { name } => write!(f, "update skill '{}'", name),
+            UpdateStep::Hashes => write!(f, "regenerate skill hashes"),
+            UpdateStep::VersionFile => write!(f, "write version file"),
+        }
+    }
+}
+
+/// Progress of a journaled step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Pending,
+    BackedUp,
+    Completed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// What this step does
+    pub step: UpdateStep,
+
+    /// How far the step got
+    pub status: StepStatus,
+
+    /// Whether the step's path existed before it was modified
+    /// (only meaningful once the step is backed up)
+    #[serde(default)]
+    pub existed: bool,
+
+    /// Error recorded if the step completed unsuccessfully
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateJournal {
+    /// Version installed before the update
+    pub from_version: String,
+
+    /// Version being installed
+    pub to_version: String,
+
+    /// When the update started (RFC 3339)
+    pub started_at: String,
+
+    /// Backup set holding the original copies of modified paths
+    pub backup_dir: PathBuf,
+
+    /// Skills left alone because they were modified locally
+    #[serde(default)]
+    pub skipped_skills: Vec<SkippedSkill>,
+
+    /// Planned steps, in execution order
+    pub entries: Vec<JournalEntry>,
+}
+
+impl UpdateJournal {
+    /// Create a journal with every step pending
+    pub fn new(
+        from_version: String,
+        to_version: String,
+        backup_dir: PathBuf,
+        steps: Vec<UpdateStep>,
+    ) -> Self {
+        Self {
+            from_version,
+            to_version,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            backup_dir,
+            skipped_skills: Vec::new(),
+            entries: steps
+                .into_iter()
+                .map(|step| JournalEntry {
+                    step,
+                    status: StepStatus::Pending,
+                    existed: false,
+                    error: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Path of the journal file for a project
+    pub fn path(target_dir: &Path) -> PathBuf {
+        target_dir.join(UPDATE_JOURNAL_FILE)
+    }
+
+    /// Load the journal left behind by an interrupted update, if any
+    pub fn load(target_dir: &Path) -> Result<Option<Self>> {
+        let path = Self::path(target_dir);
+        match fs::read_to_string(&path) {
+            Ok(content) => Ok(Some(
+                serde_json::from_str(&content).map_err(CatalystError::Json)?,
+            )),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(CatalystError::FileReadFailed { path, source: e }),
+        }
+    }
+
+    /// Atomically persist the journal
+    pub fn save(&self, target_dir: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(CatalystError::Json)?;
+        write_file_atomic(&Self::path(target_dir), &json)?;
+        Ok(())
+    }
+
+    /// Delete the journal once the update is finished or rolled back
+    pub fn remove(target_dir: &Path) -> Result<()> {
+        match fs::remove_file(Self::path(target_dir)) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(CatalystError::Io(e)),
+        }
+    }
+
+    /// Number of completed steps
+    pub fn completed_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.status == StepStatus::Completed)
+            .count()
+    }
+
+    /// Skills whose update step completed without error
+    pub fn updated_skills(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|e| e.status == StepStatus::Completed && e.error.is_none())
+            .filter_map(|e| match &e.step {
+                UpdateStep::Skill { name } => Some(name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Record of the paths a single `catalyst init` run touched
+///
+/// Call [`InitJournal::record`] before modifying a path. Paths that already
+/// exist are copied into a backup set (created on first use), so
+/// [`InitJournal::rollback`] can put the project back exactly as it was.
+#[derive(Debug)]
+pub struct InitJournal {
+    target_dir: PathBuf,
+    backup_dir: Option<PathBuf>,
+    /// Recorded paths (relative to `target_dir`) and whether each existed
+    entries: Vec<(PathBuf, bool)>,
+}
+
+impl InitJournal {
+    /// Start an empty journal for a project
+    pub fn new(target_dir: &Path) -> Self {
+        Self {
+            target_dir: target_dir.to_path_buf(),
+            backup_dir: None,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Record a path before it is created or overwritten
+    ///
+    /// Paths already covered by an earlier entry (the path itself or one of
+    /// its parents) are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `relative` - Path about to be modified, relative to the project
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the existing path could not be backed up; the
+    /// caller should not modify it in that case.
+    pub fn record(&mut self, relative: impl AsRef<Path>) -> Result<()> {
+        let relative = relative.as_ref();
+        if self
+            .entries
+            .iter()
+            .any(|(recorded, _)| relative.starts_with(recorded))
+        {
+            return Ok(());
+        }
+
+        let existed = fs::symlink_metadata(self.target_dir.join(relative)).is_ok();
+        if existed {
+            let backup_dir = match &self.backup_dir {
+                Some(dir) => dir.clone(),
+                None => {
+                    let dir = create_backup_set(&self.target_dir, "init")?;
+                    self.backup_dir = Some(dir.clone());
+                    dir
+                }
+            };
+            backup_path(&self.target_dir, &backup_dir, relative)?;
+        }
+
+        self.entries.push((relative.to_path_buf(), existed));
+        Ok(())
+    }
+
+    /// Paths recorded so far, in the order they were recorded
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.iter().map(|(path, _)| path.as_path())
+    }
+
+    /// Undo every recorded change, newest first
+    ///
+    /// Created paths are removed and overwritten ones restored from the
+    /// backup set. Every entry is attempted even if an earlier one fails.
+    ///
+    /// # Returns
+    ///
+    /// Returns the paths that could not be restored, with the reason
+    pub fn rollback(self) -> Vec<(PathBuf, CatalystError)> {
+        let mut failures = Vec::new();
+        let backup_dir = self.backup_dir.clone().unwrap_or_default();
+
+        for (relative, existed) in self.entries.iter().rev() {
+            if let Err(e) = restore_path(&self.target_dir, &backup_dir, relative, *existed) {
+                failures.push((relative.clone(), e));
+            }
+        }
+
+        // Keep the backups around if anything could not be restored
+        if failures.is_empty() {
+            self.discard_backups();
+        }
+        failures
+    }
+
+    /// Keep all changes and delete the backup set
+    pub fn commit(self) {
+        self.discard_backups();
+    }
+
+    fn discard_backups(&self) {
+        if let Some(dir) = &self.backup_dir {
+            let _ = remove_backup_set(dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_journal_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut journal = UpdateJournal::new(
+            "0.0.9".to_string(),
+            "0.1.0".to_string(),
+            temp_dir.path().join("backup"),
+            vec![
+                UpdateStep::Wrappers,
+                UpdateStep::Skill {
+                    name: "skill-developer".to_string(),
+                },
+                UpdateStep::VersionFile,
+            ],
+        );
+        journal.entries[0].status = StepStatus::Completed;
+        journal.entries[1].status = StepStatus::BackedUp;
+        journal.entries[1].existed = true;
+        journal.save(temp_dir.path()).unwrap();
+
+        let loaded = UpdateJournal::load(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.entries.len(), 3);
+        assert_eq!(loaded.entries[1].status, StepStatus::BackedUp);
+        assert!(loaded.entries[1].existed);
+        assert_eq!(loaded.completed_count(), 1);
+
+        UpdateJournal::remove(temp_dir.path()).unwrap();
+        assert!(UpdateJournal::load(temp_dir.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_init_journal_rollback_restores_pre_init_state() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir_all(target.join(".claude")).unwrap();
+        fs::write(target.join(".claude/settings.json"), "original").unwrap();
+
+        let mut journal = InitJournal::new(target);
+        journal.record(".claude/hooks").unwrap();
+        fs::create_dir_all(target.join(".claude/hooks")).unwrap();
+        journal.record(".claude/hooks/hook.sh").unwrap();
+        fs::write(target.join(".claude/hooks/hook.sh"), "#!/bin/bash").unwrap();
+        journal.record(".claude/settings.json").unwrap();
+        fs::write(target.join(".claude/settings.json"), "rewritten").unwrap();
+
+        // The wrapper is covered by its new parent directory
+        assert_eq!(journal.paths().count(), 2);
+
+        let failures = journal.rollback();
+        assert!(failures.is_empty());
+        assert!(!target.join(".claude/hooks").exists());
+        assert_eq!(
+            fs::read_to_string(target.join(".claude/settings.json")).unwrap(),
+            "original"
+        );
+        assert!(!target.join(crate::types::BACKUP_DIR).exists());
+    }
+
+    #[test]
+    fn test_step_paths() {
+        let step = UpdateStep::Skill {
+            name: "rust-developer".to_string(),
+        };
+        assert_eq!(step.path(), Path::new(".claude/skills/rust-developer"));
+        assert_eq!(UpdateStep::VersionFile.path(), Path::new(VERSION_FILE));
+    }
+}
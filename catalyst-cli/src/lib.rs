@@ -3,11 +3,74 @@
 //! Core library providing types, validation, and helper functions
 //! for the Catalyst CLI tool.
 
+pub mod activation;
+pub mod agents;
+pub mod analytics;
+pub mod backup;
+pub mod binaries;
+pub mod build_check;
+pub mod cancel;
+pub mod cargo_check_config;
+pub mod cargo_diagnostics;
+pub mod check_log;
+pub mod commands;
+pub mod config;
+pub mod debounce;
+pub mod deprecation;
+pub mod devcontainer;
+pub mod env;
+pub mod explain;
+pub mod file_context;
+pub mod file_tracking;
+pub mod flags;
+pub mod healthcheck;
+pub mod hook_env;
+pub mod hook_stats;
 pub mod init;
+pub mod input_limits;
+pub mod install_manifest;
+pub mod journal;
+pub mod library;
+pub mod lint;
+pub mod lock;
+pub mod lockfile;
+pub mod modification_store;
+pub mod package;
+pub mod powershell;
+pub mod pr_report;
+pub mod preview;
+pub mod process;
+pub mod profiles;
+pub mod progress;
+pub mod project;
+pub mod prompt_log;
+pub mod registry;
+pub mod report_display;
+pub mod rules_cache;
+pub mod safe_path;
+pub mod scaffold;
+pub mod scripts;
+pub mod self_update;
+pub mod selinux;
+pub mod session_digest;
+#[cfg(feature = "sqlite")]
+pub mod sessions;
+pub mod signals;
+pub mod skills;
+pub mod stack;
+pub mod state;
 pub mod status;
+pub mod sync;
+pub mod target;
+pub mod template;
+pub mod test_runner;
+pub mod timings;
+#[cfg(feature = "sqlite")]
+pub mod tracker;
 pub mod types;
 pub mod update;
 pub mod validation;
+pub mod validators;
 
 // Re-export commonly used types
 pub use types::{CatalystError, Platform, Result};
@@ -0,0 +1,310 @@
+//! Machine-wide skill library
+//!
+//! Instead of copying every skill into each project, skills can be installed
+//! once under `~/.claude/skills-library/` and linked into `.claude/skills/`.
+//! Linked skills are marked with `"source": "library"` in skill-rules.json
+//! and are left out of the project's hash file; `catalyst update` updates
+//! them in the library, so every project that links them picks up the new
+//! version at once.
+//!
+//! The library keeps its own `.catalyst-hashes.json` so skills edited in
+//! the library are not overwritten without `--force`.
+
+use crate::config::CatalystConfig;
+use crate::init::{copy_embedded_skill, embedded_skill};
+use crate::safe_path;
+use crate::signals;
+use crate::template;
+use crate::types::{
+    CatalystError, CatalystHashes, Result, SkippedSkill, CATALYST_VERSION, HASHES_FILE, SKILLS_DIR,
+    SKILLS_LIBRARY_DIR,
+};
+use catalyst_core::fs_ops::Fs;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that overrides the library location
+pub const LIBRARY_ENV: &str = "CATALYST_SKILLS_LIBRARY";
+
+/// Location of the skill library
+///
+/// Uses `CATALYST_SKILLS_LIBRARY` if set, otherwise `~/.claude/skills-library`.
+///
+/// # Errors
+///
+/// Returns `InvalidPath` if the home directory can't be determined.
+pub fn library_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os(LIBRARY_ENV).filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    let home = dirs::home_dir().ok_or_else(|| {
+        CatalystError::InvalidPath("Could not determine home directory".to_string())
+    })?;
+    Ok(home.join(SKILLS_LIBRARY_DIR))
+}
+
+/// Make sure a skill is in the library, installing it if needed
+///
+/// # Returns
+///
+/// Returns true if the skill was installed now, false if it was already there
+pub fn ensure_skill(library: &Path, skill_id: &str) -> Result<bool> {
+    safe_path::validate_skill_id(skill_id)?;
+    if library.join(skill_id).join("SKILL.md").is_file() {
+        return Ok(false);
+    }
+
+    install_skill(library, skill_id)?;
+    Ok(true)
+}
+
+/// Link a library skill into a project, installing it into the library first
+///
+/// Anything already at `.claude/skills/<skill_id>` is replaced; callers are
+/// expected to have checked `--force`.
+///
+/// # Errors
+///
+/// Returns an error if the skill can't be installed into the library or the
+/// link can't be created (on Windows, symlinks need Developer Mode).
+pub fn link_skill(library: &Path, target_dir: &Path, skill_id: &str) -> Result<()> {
+    safe_path::validate_skill_id(skill_id)?;
+    ensure_skill(library, skill_id)?;
+
+    let link = target_dir.join(SKILLS_DIR).join(skill_id);
+    remove_path(&link)?;
+    Fs::read_write()
+        .symlink(&library.join(skill_id), &link)
+        .map_err(|e| {
+            CatalystError::SkillInstallationFailed(format!(
+                "Failed to link {} to the skill library: {}",
+                link.display(),
+                e
+            ))
+        })
+}
+
+/// Whether a project's skill is linked rather than copied
+pub fn is_linked(target_dir: &Path, skill_id: &str) -> bool {
+    Fs::read_only()
+        .symlink_metadata(&target_dir.join(SKILLS_DIR).join(skill_id))
+        .is_ok_and(|m| m.file_type().is_symlink())
+}
+
+/// Skills linked into a project, with the library directory each points into
+pub fn linked_skills(target_dir: &Path) -> Vec<(String, PathBuf)> {
+    let skills_dir = target_dir.join(SKILLS_DIR);
+    let Ok(entries) = fs::read_dir(&skills_dir) else {
+        return Vec::new();
+    };
+
+    let mut linked: Vec<(String, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let link = fs::read_link(entry.path()).ok()?;
+            let target = skills_dir.join(link);
+            let library = target.parent()?.to_path_buf();
+            Some((entry.file_name().to_string_lossy().into_owned(), library))
+        })
+        .collect();
+    linked.sort();
+    linked
+}
+
+/// Update library skills to the versions shipped with this CLI
+///
+/// Skills whose SKILL.md was edited in the library are skipped unless
+/// `force` is set; skills already up to date are left alone.
+///
+/// # Arguments
+///
+/// * `library` - Library directory
+/// * `skill_ids` - Skills to update
+/// * `force` - Whether to overwrite skills edited in the library
+///
+/// # Returns
+///
+/// Returns a tuple of (updated_skills, skipped_skills)
+pub fn update_skills(
+    library: &Path,
+    skill_ids: &[String],
+    force: bool,
+) -> Result<(Vec<String>, Vec<SkippedSkill>)> {
+    let hashes = load_hashes(library)?;
+    let mut updated = Vec::new();
+    let mut skipped = Vec::new();
+
+    for skill_id in skill_ids {
+        let Some(shipped) = embedded_skill(skill_id)
+            .and_then(|dir| dir.get_file(format!("{}/SKILL.md", skill_id)))
+            .map(|file| hash_bytes(file.contents()))
+        else {
+            // Not a skill we ship
+            continue;
+        };
+
+        let current = fs::read(library.join(skill_id).join("SKILL.md"))
+            .ok()
+            .map(|content| hash_bytes(&content));
+        if current.as_deref() == Some(shipped.as_str()) {
+            continue;
+        }
+
+        if let (Some(current), Some(expected)) = (&current, hashes.skills.get(skill_id)) {
+            if current != expected && !force {
+                skipped.push(SkippedSkill {
+                    name: skill_id.clone(),
+                    reason: "Modified in the skill library".to_string(),
+                    current_hash: current.clone(),
+                    expected_hash: expected.clone(),
+                });
+                continue;
+            }
+        }
+
+        install_skill(library, skill_id)?;
+        updated.push(skill_id.clone());
+    }
+
+    Ok((updated, skipped))
+}
+
+/// Copy a skill into the library and record its hash
+///
+/// The skill is staged next to its final location and swapped in, so other
+/// projects never see a half-copied skill.
+fn install_skill(library: &Path, skill_id: &str) -> Result<()> {
+    // The ID is also spliced into staging names, so check it here too
+    safe_path::validate_skill_id(skill_id)?;
+    let skill_dir = embedded_skill(skill_id)
+        .ok_or_else(|| CatalystError::InvalidPath(format!("Skill not found: {}", skill_id)))?;
+
+    fs::create_dir_all(library).map_err(|e| CatalystError::DirectoryCreationFailed {
+        path: library.to_path_buf(),
+        source: e,
+    })?;
+
+    let staging = library.join(format!(".{}.partial-{}", skill_id, std::process::id()));
+    let previous = library.join(format!(".{}.old-{}", skill_id, std::process::id()));
+    let destination = library.join(skill_id);
+
+    remove_path(&staging)?;
+    let _cleanup = signals::CleanupGuard::new(&staging);
+    // Shared by every project, so templates get the user config's [vars]
+    // and no PROJECT_DIR
+    let user_config = CatalystConfig::load_user()?.unwrap_or_default();
+    copy_embedded_skill(skill_dir, &staging, &template::shared_vars(&user_config))?;
+
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |e| CatalystError::FileWriteFailed { path, source: e }
+    };
+    if destination.exists() {
+        fs::rename(&destination, &previous).map_err(io_error(&destination))?;
+    }
+    fs::rename(&staging, &destination).map_err(io_error(&destination))?;
+    remove_path(&previous)?;
+
+    record_hash(library, skill_id)
+}
+
+fn load_hashes(library: &Path) -> Result<CatalystHashes> {
+    let path = library.join(HASHES_FILE);
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).map_err(CatalystError::Json),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Ok(CatalystHashes::new(CATALYST_VERSION.to_string()))
+        }
+        Err(e) => Err(CatalystError::FileReadFailed { path, source: e }),
+    }
+}
+
+fn record_hash(library: &Path, skill_id: &str) -> Result<()> {
+    let skill_file = library.join(skill_id).join("SKILL.md");
+    let content = fs::read(&skill_file).map_err(|e| CatalystError::FileReadFailed {
+        path: skill_file,
+        source: e,
+    })?;
+
+    let mut hashes = load_hashes(library)?;
+    hashes
+        .skills
+        .insert(skill_id.to_string(), hash_bytes(&content));
+    hashes.version = CATALYST_VERSION.to_string();
+    hashes.updated_at = chrono::Utc::now().to_rfc3339();
+
+    let json = serde_json::to_string_pretty(&hashes).map_err(CatalystError::Json)?;
+    crate::init::write_file_atomic(&library.join(HASHES_FILE), &json)?;
+    Ok(())
+}
+
+fn hash_bytes(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Remove a file, link, or directory if it exists (links are not followed)
+fn remove_path(path: &Path) -> Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(m) if m.is_dir() => fs::remove_dir_all(path).map_err(CatalystError::Io),
+        Ok(_) => fs::remove_file(path).map_err(CatalystError::Io),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(CatalystError::Io(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_link_skill_shares_library_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let library = temp_dir.path().join("library");
+        let projects = ["one", "two"].map(|name| temp_dir.path().join(name));
+
+        for project in &projects {
+            fs::create_dir_all(project.join(SKILLS_DIR)).unwrap();
+            link_skill(&library, project, "skill-developer").unwrap();
+            assert!(is_linked(project, "skill-developer"));
+            assert!(project
+                .join(SKILLS_DIR)
+                .join("skill-developer/SKILL.md")
+                .is_file());
+            assert_eq!(
+                linked_skills(project),
+                vec![("skill-developer".to_string(), library.clone())]
+            );
+        }
+
+        // Installed once, with its hash recorded
+        assert!(!ensure_skill(&library, "skill-developer").unwrap());
+        let hashes = load_hashes(&library).unwrap();
+        assert!(hashes.skills.contains_key("skill-developer"));
+    }
+
+    #[test]
+    fn test_update_skills_skips_library_edits() {
+        let temp_dir = TempDir::new().unwrap();
+        let library = temp_dir.path();
+        let skills = vec!["skill-developer".to_string()];
+        ensure_skill(library, "skill-developer").unwrap();
+
+        // Up to date: nothing to do
+        let (updated, skipped) = update_skills(library, &skills, false).unwrap();
+        assert!(updated.is_empty() && skipped.is_empty());
+
+        let skill_file = library.join("skill-developer/SKILL.md");
+        fs::write(&skill_file, "edited").unwrap();
+        let (updated, skipped) = update_skills(library, &skills, false).unwrap();
+        assert!(updated.is_empty());
+        assert_eq!(skipped[0].reason, "Modified in the skill library");
+
+        let (updated, _) = update_skills(library, &skills, true).unwrap();
+        assert_eq!(updated, skills);
+        assert_ne!(fs::read_to_string(&skill_file).unwrap(), "edited");
+    }
+}
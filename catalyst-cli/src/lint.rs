@@ -0,0 +1,721 @@
+//! Linting for skill-rules.json (`catalyst skills lint`)
+//!
+//! skill-rules.json is edited by hand, and mistakes are silent: the
+//! skill-activation-prompt hook skips regexes that fail to compile and gives
+//! up on the whole file if it doesn't match the schema it expects. The linter
+//! checks the file the way the hook reads it and reports:
+//!
+//! - Schema problems that make the hook reject the file
+//! - `minScore` thresholds the skill's triggers can never reach
+//! - Intent patterns that don't compile, can never match, or match every prompt
+//! - Keywords that duplicate or shadow other keywords
+//! - Path patterns that don't compile or match no file in the project
+//! - Skills that have rules but are not installed
+
+use crate::activation::MatchMode;
+use crate::types::{
+    CatalystError, IssueSeverity, Result, ACTIVATION_INTENT_WEIGHT, ACTIVATION_KEYWORD_WEIGHT,
+    ACTIVATION_PATH_WEIGHT, SKILLS_DIR, SKILL_RULES_FILE,
+};
+use catalyst_core::jsonc;
+use globset::Glob;
+use ignore::WalkBuilder;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Priorities understood by skill-activation-prompt
+const PRIORITIES: &[&str] = &["critical", "high", "medium", "low"];
+
+/// A single problem found in skill-rules.json
+#[derive(Debug, Clone, Serialize)]
+pub struct LintFinding {
+    pub severity: IssueSeverity,
+
+    /// Skill the finding belongs to (None for file-level findings)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+
+    pub message: String,
+}
+
+/// Result of linting skill-rules.json
+#[derive(Debug, Clone, Serialize)]
+pub struct LintReport {
+    /// Path of the linted file
+    pub path: PathBuf,
+
+    /// Number of skill entries that were checked
+    pub skills_checked: usize,
+
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    /// Number of findings with the given severity
+    pub fn count(&self, severity: IssueSeverity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+
+    /// Whether any finding would break skill activation
+    pub fn has_errors(&self) -> bool {
+        self.count(IssueSeverity::Error) > 0
+    }
+
+    fn push(&mut self, severity: IssueSeverity, skill: Option<&str>, message: String) {
+        self.findings.push(LintFinding {
+            severity,
+            skill: skill.map(str::to_string),
+            message,
+        });
+    }
+}
+
+/// Triggers collected from one skill entry
+#[derive(Debug, Default)]
+struct SkillTriggers {
+    keywords: Vec<String>,
+    intent_patterns: Vec<String>,
+    path_patterns: Vec<String>,
+}
+
+/// Lint `.claude/skills/skill-rules.json` in a project
+///
+/// # Arguments
+///
+/// * `target_dir` - Project directory containing .claude/
+///
+/// # Returns
+///
+/// Returns the findings; a file the hook can't parse is reported as an error
+/// finding rather than an `Err`.
+///
+/// # Errors
+///
+/// Returns `PathNotFound` if skill-rules.json doesn't exist, or
+/// `FileReadFailed` if it can't be read.
+pub fn lint_skill_rules(target_dir: &Path) -> Result<LintReport> {
+    let path = target_dir.join(SKILL_RULES_FILE);
+    if !path.is_file() {
+        return Err(CatalystError::PathNotFound(path));
+    }
+    let content = fs::read_to_string(&path).map_err(|source| CatalystError::FileReadFailed {
+        path: path.clone(),
+        source,
+    })?;
+
+    let mut report = LintReport {
+        path,
+        skills_checked: 0,
+        findings: Vec::new(),
+    };
+
+    // Comments and trailing commas are fine; the hook parses JSONC too
+    let root: Value = match jsonc::from_str(&content) {
+        Ok(root) => root,
+        Err(e) => {
+            report.push(IssueSeverity::Error, None, format!("Invalid JSON: {}", e));
+            return Ok(report);
+        }
+    };
+
+    let Some(root) = root.as_object() else {
+        report.push(
+            IssueSeverity::Error,
+            None,
+            "Top level must be an object with \"version\" and \"skills\"".to_string(),
+        );
+        return Ok(report);
+    };
+
+    if !root.get("version").is_some_and(Value::is_string) {
+        report.push(
+            IssueSeverity::Error,
+            None,
+            "Missing string field \"version\" (required by skill-activation-prompt)".to_string(),
+        );
+    }
+
+    let skills = match root.get("skills") {
+        Some(Value::Object(skills)) => skills,
+        Some(_) => {
+            report.push(
+                IssueSeverity::Error,
+                None,
+                "\"skills\" must be an object keyed by skill name".to_string(),
+            );
+            return Ok(report);
+        }
+        None => {
+            report.push(
+                IssueSeverity::Error,
+                None,
+                "Missing \"skills\" object".to_string(),
+            );
+            return Ok(report);
+        }
+    };
+
+    let mut all_triggers = Vec::new();
+    for (name, rule) in skills {
+        report.skills_checked += 1;
+        let Some(rule) = rule.as_object() else {
+            report.push(
+                IssueSeverity::Error,
+                Some(name),
+                "Rule must be an object".to_string(),
+            );
+            continue;
+        };
+
+        check_schema(&mut report, name, rule);
+        let triggers = collect_triggers(&mut report, name, rule);
+
+        for pattern in &triggers.intent_patterns {
+            check_intent_pattern(&mut report, name, pattern);
+        }
+        check_min_score(&mut report, name, rule, &triggers);
+
+        if !target_dir.join(SKILLS_DIR).join(name).is_dir() {
+            report.push(
+                IssueSeverity::Warning,
+                Some(name),
+                format!(
+                    "Skill is not installed ({}/{} does not exist)",
+                    SKILLS_DIR, name
+                ),
+            );
+        }
+
+        all_triggers.push((name.as_str(), triggers));
+    }
+
+    check_keywords(&mut report, &all_triggers);
+    check_path_patterns(&mut report, target_dir, &all_triggers);
+
+    Ok(report)
+}
+
+/// Check the fields skill-activation-prompt requires on every rule
+fn check_schema(report: &mut LintReport, name: &str, rule: &Map<String, Value>) {
+    for field in ["type", "enforcement"] {
+        if !rule.get(field).is_some_and(Value::is_string) {
+            report.push(
+                IssueSeverity::Error,
+                Some(name),
+                format!("Missing string field \"{}\"", field),
+            );
+        }
+    }
+
+    match rule.get("priority") {
+        Some(Value::String(priority)) => {
+            if !PRIORITIES.contains(&priority.to_lowercase().as_str()) {
+                report.push(
+                    IssueSeverity::Warning,
+                    Some(name),
+                    format!(
+                        "Unknown priority '{}' is treated as medium (expected one of: {})",
+                        priority,
+                        PRIORITIES.join(", ")
+                    ),
+                );
+            }
+        }
+        Some(other) => report.push(
+            IssueSeverity::Error,
+            Some(name),
+            format!(
+                "\"priority\" must be a string (one of: {}), found {}",
+                PRIORITIES.join(", "),
+                other
+            ),
+        ),
+        None => report.push(
+            IssueSeverity::Error,
+            Some(name),
+            "Missing string field \"priority\"".to_string(),
+        ),
+    }
+
+    match rule.get("matchMode") {
+        Some(Value::String(mode)) if MatchMode::from_str(mode).is_err() => report.push(
+            IssueSeverity::Warning,
+            Some(name),
+            format!(
+                "Unknown matchMode '{}' is treated as exact (expected one of: exact, stemmed, fuzzy)",
+                mode
+            ),
+        ),
+        Some(Value::String(_)) | None => {}
+        Some(other) => report.push(
+            IssueSeverity::Error,
+            Some(name),
+            format!("\"matchMode\" must be a string, found {}", other),
+        ),
+    }
+}
+
+/// Check `minScore` is a valid threshold the skill's triggers can reach
+///
+/// The best possible score counts each distinct keyword and each intent
+/// pattern that compiles, plus the recent-file bonus when the rule has a
+/// valid path pattern, using the hook's weights.
+fn check_min_score(
+    report: &mut LintReport,
+    name: &str,
+    rule: &Map<String, Value>,
+    triggers: &SkillTriggers,
+) {
+    let Some(value) = rule.get("minScore") else {
+        return;
+    };
+    let Some(min_score) = value.as_u64().filter(|n| *n <= u64::from(u32::MAX)) else {
+        report.push(
+            IssueSeverity::Error,
+            Some(name),
+            format!(
+                "\"minScore\" must be a non-negative integer, found {}",
+                value
+            ),
+        );
+        return;
+    };
+
+    let mut keywords: Vec<String> = triggers.keywords.iter().map(|k| k.to_lowercase()).collect();
+    keywords.sort_unstable();
+    keywords.dedup();
+    let patterns = triggers
+        .intent_patterns
+        .iter()
+        .filter(|pattern| Regex::new(pattern).is_ok())
+        .count();
+    let has_paths = triggers
+        .path_patterns
+        .iter()
+        .any(|pattern| Glob::new(pattern).is_ok());
+    let best = keywords.len() as u64 * u64::from(ACTIVATION_KEYWORD_WEIGHT)
+        + patterns as u64 * u64::from(ACTIVATION_INTENT_WEIGHT)
+        + if has_paths {
+            u64::from(ACTIVATION_PATH_WEIGHT)
+        } else {
+            0
+        };
+
+    if min_score > best {
+        report.push(
+            IssueSeverity::Warning,
+            Some(name),
+            format!(
+                "minScore {} can never be reached: the best possible score is {} \
+                 ({} per keyword, {} per intent pattern, {} for a matching recent file)",
+                min_score,
+                best,
+                ACTIVATION_KEYWORD_WEIGHT,
+                ACTIVATION_INTENT_WEIGHT,
+                ACTIVATION_PATH_WEIGHT
+            ),
+        );
+    }
+}
+
+/// Gather keywords and patterns from a rule, flagging misplaced or mistyped ones
+///
+/// The hook reads keywords and intent patterns from `promptTriggers` only.
+/// Copies at the top level of the rule are still linted so that moving them
+/// doesn't surface a second round of problems.
+fn collect_triggers(
+    report: &mut LintReport,
+    name: &str,
+    rule: &Map<String, Value>,
+) -> SkillTriggers {
+    let mut triggers = SkillTriggers::default();
+
+    let prompt_triggers = rule.get("promptTriggers").and_then(Value::as_object);
+    for (field, target) in [
+        ("keywords", &mut triggers.keywords),
+        ("intentPatterns", &mut triggers.intent_patterns),
+    ] {
+        if let Some(value) = prompt_triggers.and_then(|t| t.get(field)) {
+            target.extend(string_list(report, name, field, value));
+        }
+        if let Some(value) = rule.get(field) {
+            report.push(
+                IssueSeverity::Warning,
+                Some(name),
+                format!(
+                    "Top-level \"{}\" is ignored by skill-activation-prompt; move it under \"promptTriggers\"",
+                    field
+                ),
+            );
+            target.extend(string_list(report, name, field, value));
+        }
+    }
+
+    let file_triggers = rule.get("fileTriggers").and_then(Value::as_object);
+    for value in [
+        file_triggers.and_then(|t| t.get("pathPatterns")),
+        rule.get("pathPatterns"),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        triggers
+            .path_patterns
+            .extend(string_list(report, name, "pathPatterns", value));
+    }
+
+    triggers
+}
+
+/// Read a JSON array of strings, reporting any non-string entries
+fn string_list(report: &mut LintReport, name: &str, field: &str, value: &Value) -> Vec<String> {
+    let Some(items) = value.as_array() else {
+        report.push(
+            IssueSeverity::Error,
+            Some(name),
+            format!("\"{}\" must be an array of strings", field),
+        );
+        return Vec::new();
+    };
+
+    let mut strings = Vec::new();
+    for item in items {
+        match item.as_str() {
+            Some(s) => strings.push(s.to_string()),
+            None => report.push(
+                IssueSeverity::Error,
+                Some(name),
+                format!("\"{}\" contains a non-string entry: {}", field, item),
+            ),
+        }
+    }
+    strings
+}
+
+/// Check that an intent pattern compiles and can match some, but not every, prompt
+fn check_intent_pattern(report: &mut LintReport, name: &str, pattern: &str) {
+    if let Err(e) = Regex::new(pattern) {
+        report.push(
+            IssueSeverity::Error,
+            Some(name),
+            format!("Intent pattern '{}' does not compile: {}", pattern, e),
+        );
+        return;
+    }
+
+    // A pattern that compiles also parses, so this only fails on patterns
+    // regex accepts with options the parser doesn't see
+    let Ok(hir) = regex_syntax::parse(pattern) else {
+        return;
+    };
+
+    match hir.properties().minimum_len() {
+        None => report.push(
+            IssueSeverity::Warning,
+            Some(name),
+            format!("Intent pattern '{}' can never match", pattern),
+        ),
+        Some(0) => report.push(
+            IssueSeverity::Warning,
+            Some(name),
+            format!(
+                "Intent pattern '{}' matches the empty string, so it activates on every prompt",
+                pattern
+            ),
+        ),
+        Some(_) => {}
+    }
+}
+
+/// Report duplicate keywords and keywords made redundant by shorter ones
+///
+/// The hook does a case-insensitive substring match, so a prompt containing
+/// "react component" also contains "react": the longer keyword adds nothing
+/// to its own skill and drags in any other skill that lists the shorter one.
+fn check_keywords(report: &mut LintReport, skills: &[(&str, SkillTriggers)]) {
+    let keywords: Vec<(&str, &str, String)> = skills
+        .iter()
+        .flat_map(|(name, triggers)| {
+            triggers
+                .keywords
+                .iter()
+                .map(move |kw| (*name, kw.as_str(), kw.to_lowercase()))
+        })
+        .collect();
+
+    for (i, (skill, keyword, lower)) in keywords.iter().enumerate() {
+        if lower.trim().is_empty() {
+            report.push(
+                IssueSeverity::Warning,
+                Some(skill),
+                "Empty keyword matches every prompt".to_string(),
+            );
+            continue;
+        }
+
+        for (j, (other_skill, other_keyword, other_lower)) in keywords.iter().enumerate() {
+            if i == j || other_lower.trim().is_empty() || !lower.contains(other_lower.as_str()) {
+                continue;
+            }
+
+            if lower == other_lower {
+                // Report each duplicate pair once
+                if j < i {
+                    continue;
+                }
+                let message = if skill == other_skill {
+                    format!("Duplicate keyword '{}'", keyword)
+                } else {
+                    format!(
+                        "Keyword '{}' is also a keyword of '{}'",
+                        keyword, other_skill
+                    )
+                };
+                report.push(IssueSeverity::Warning, Some(skill), message);
+            } else if skill == other_skill {
+                report.push(
+                    IssueSeverity::Warning,
+                    Some(skill),
+                    format!(
+                        "Keyword '{}' is redundant: '{}' already matches any prompt containing it",
+                        keyword, other_keyword
+                    ),
+                );
+            } else {
+                report.push(
+                    IssueSeverity::Warning,
+                    Some(skill),
+                    format!(
+                        "Keyword '{}' also activates '{}' through its keyword '{}'",
+                        keyword, other_skill, other_keyword
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Report path patterns that don't compile or match no file in the project
+///
+/// The project is walked once, honoring .gitignore, and only if some skill
+/// has path patterns.
+fn check_path_patterns(
+    report: &mut LintReport,
+    target_dir: &Path,
+    skills: &[(&str, SkillTriggers)],
+) {
+    let mut compiled = Vec::new();
+    for (name, triggers) in skills {
+        for pattern in &triggers.path_patterns {
+            match Glob::new(pattern) {
+                Ok(glob) => compiled.push((*name, pattern.as_str(), glob.compile_matcher(), false)),
+                Err(e) => report.push(
+                    IssueSeverity::Error,
+                    Some(name),
+                    format!("Path pattern '{}' is not a valid glob: {}", pattern, e),
+                ),
+            }
+        }
+    }
+
+    if compiled.is_empty() {
+        return;
+    }
+
+    for entry in WalkBuilder::new(target_dir).build().flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(target_dir) else {
+            continue;
+        };
+
+        for (_, _, matcher, matched) in compiled.iter_mut().filter(|c| !c.3) {
+            *matched = matcher.is_match(relative);
+        }
+        if compiled.iter().all(|c| c.3) {
+            break;
+        }
+    }
+
+    for (name, pattern, _, matched) in compiled {
+        if !matched {
+            report.push(
+                IssueSeverity::Warning,
+                Some(name),
+                format!("Path pattern '{}' matches no files in the project", pattern),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_rules(target: &Path, rules: &str) {
+        let skill_dir = target.join(SKILLS_DIR).join("backend");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Backend").unwrap();
+        fs::write(target.join(SKILL_RULES_FILE), rules).unwrap();
+    }
+
+    fn messages(report: &LintReport) -> Vec<&str> {
+        report.findings.iter().map(|f| f.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_clean_rules_have_no_findings() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir_all(target.join("src")).unwrap();
+        fs::write(target.join("src/main.rs"), "fn main() {}").unwrap();
+        write_rules(
+            target,
+            r#"{
+                "version": "1.0",
+                "skills": {
+                    "backend": {
+                        "type": "domain",
+                        "enforcement": "suggest",
+                        "priority": "high",
+                        "minScore": 4,
+                        "promptTriggers": {
+                            "keywords": ["backend", "api"],
+                            "intentPatterns": ["(?i)build.*service"]
+                        },
+                        "fileTriggers": { "pathPatterns": ["src/**/*.rs"] }
+                    }
+                }
+            }"#,
+        );
+
+        let report = lint_skill_rules(target).unwrap();
+        assert_eq!(report.skills_checked, 1);
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+    }
+
+    #[test]
+    fn test_reports_bad_min_score_and_match_mode() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        write_rules(
+            target,
+            r#"{
+                "version": "1.0",
+                "skills": {
+                    "backend": {
+                        "type": "domain",
+                        "enforcement": "suggest",
+                        "priority": "high",
+                        "minScore": 4,
+                        "promptTriggers": {
+                            "keywords": ["api", "API"],
+                            "intentPatterns": ["(?i)build.*service", "(unclosed"]
+                        }
+                    },
+                    "frontend": {
+                        "type": "domain",
+                        "enforcement": "suggest",
+                        "priority": "low",
+                        "minScore": -1,
+                        "matchMode": "loose",
+                        "promptTriggers": { "keywords": ["react"] }
+                    }
+                }
+            }"#,
+        );
+
+        let report = lint_skill_rules(target).unwrap();
+        let messages = messages(&report);
+        assert!(messages.iter().any(
+            |m| m.starts_with("minScore 4 can never be reached: the best possible score is 3")
+        ));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("\"minScore\" must be a non-negative integer")));
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("Unknown matchMode 'loose'")));
+    }
+
+    #[test]
+    fn test_reports_patterns_keywords_and_missing_skills() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        write_rules(
+            target,
+            r#"{
+                "version": "1.0",
+                "skills": {
+                    "backend": {
+                        "type": "domain",
+                        "enforcement": "suggest",
+                        "priority": "high",
+                        "promptTriggers": {
+                            "keywords": ["api", "rest api"],
+                            "intentPatterns": ["(unclosed", "[^\\s\\S]", "a*"]
+                        },
+                        "fileTriggers": { "pathPatterns": ["**/*.go"] }
+                    },
+                    "frontend": {
+                        "type": "domain",
+                        "enforcement": "suggest",
+                        "priority": "low",
+                        "promptTriggers": { "keywords": ["API"] }
+                    }
+                }
+            }"#,
+        );
+
+        let report = lint_skill_rules(target).unwrap();
+        let messages = messages(&report);
+        let has = |needle: &str| messages.iter().any(|m| m.contains(needle));
+
+        assert!(has("'(unclosed' does not compile"));
+        assert!(has("can never match"));
+        assert!(has("activates on every prompt"));
+        assert!(has("'rest api' is redundant"));
+        assert!(has("'api' is also a keyword of 'frontend'"));
+        assert!(has("'**/*.go' matches no files"));
+        assert!(has("not installed"));
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn test_reports_generated_format_problems() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        write_rules(
+            target,
+            "// Customize pathPatterns for your project structure\n{\n  \"version\": \"1.0\",\n  \"skills\": {\n    \"backend\": {\n      \"type\": \"skill\",\n      \"enforcement\": \"suggest\",\n      \"priority\": 1,\n      \"keywords\": [\"backend\"]\n    }\n  }\n}\n",
+        );
+
+        let report = lint_skill_rules(target).unwrap();
+        let messages = messages(&report);
+        assert!(!messages.iter().any(|m| m.contains("comment")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("\"priority\" must be a string")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("Top-level \"keywords\" is ignored")));
+    }
+
+    #[test]
+    fn test_missing_rules_file() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(matches!(
+            lint_skill_rules(temp_dir.path()),
+            Err(CatalystError::PathNotFound(_))
+        ));
+    }
+}
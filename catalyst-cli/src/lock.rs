@@ -0,0 +1,513 @@
+//! Project lock shared by commands that change an install
+//!
+//! `catalyst init`, `update`, `sync`, and the fixes applied by `status --fix`,
+//! `fix`, and `doctor` all take an [`OperationLock`] on the project's
+//! `.catalyst-operation.lock` before writing anything, so two of them can't
+//! interleave their changes. The lock is an OS advisory lock (`flock` on Unix,
+//! `LockFileEx` on Windows); on filesystems without lock support it falls
+//! back to a PID file checked with [`is_process_running`].
+//!
+//! By default a held lock is an error (`InitInProgress`). After
+//! [`wait_for_locks`] (the global `--wait` flag), acquiring blocks until the
+//! other process is done instead.
+
+use crate::progress;
+use crate::signals;
+use crate::types::{CatalystError, Result};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Lock file name, at the project root
+///
+/// Only exists while a command runs, so it is never committed. It is
+/// unrelated to `catalyst.lock` (see [`crate::lockfile`]), the committed
+/// record of what a project installs; the name keeps the two apart in
+/// listings and `.gitignore` entries.
+pub const OPERATION_LOCK_FILE: &str = ".catalyst-operation.lock";
+
+/// Attempts at locking the file at the lock path before giving up
+///
+/// A holder removes the lock file when it finishes, so a process that opened
+/// the old file just before that can lock a file that's no longer there; it
+/// then reopens the path and tries again.
+const LOCK_ATTEMPTS: usize = 3;
+
+/// How often a waiting process rechecks a PID lock file
+const PID_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+static WAIT: AtomicBool = AtomicBool::new(false);
+
+/// Make every later [`OperationLock::acquire`] in this process wait for a
+/// held lock instead of failing
+pub fn wait_for_locks() {
+    WAIT.store(true, Ordering::Relaxed);
+}
+
+/// Whether a held lock is waited for
+pub fn is_waiting() -> bool {
+    WAIT.load(Ordering::Relaxed)
+}
+
+/// Guard that automatically releases the lock when dropped
+///
+/// # Lock Cleanup Guarantee
+///
+/// The lock is **automatically released** when this guard is dropped, even if
+/// the operation fails or panics. This RAII pattern ensures that:
+/// - Lock files are never leaked on normal program exit
+/// - Subsequent operations can proceed after errors
+/// - No manual cleanup is required in error handling paths
+///
+/// When the lock is an OS lock, the operating system also releases it if the
+/// process dies without running `Drop`.
+#[derive(Debug)]
+pub struct OperationLock {
+    lock_file: PathBuf,
+    /// Handle holding the OS lock; None under the PID-file fallback
+    _file: Option<fs::File>,
+}
+
+impl Drop for OperationLock {
+    fn drop(&mut self) {
+        // Runs before `_file` is closed, so the file is removed while still locked
+        let _ = release(&self.lock_file);
+    }
+}
+
+impl OperationLock {
+    /// Lock the project in `target_dir` for a command that changes it
+    ///
+    /// Takes an exclusive OS advisory lock on `.catalyst-operation.lock` and
+    /// writes the current process ID into it for the error message other
+    /// processes show. On filesystems without lock support it falls back to
+    /// the PID-file scheme of [`acquire_pid_lock`].
+    ///
+    /// # Arguments
+    ///
+    /// * `target_dir` - The project directory being changed
+    ///
+    /// # Returns
+    ///
+    /// Returns a guard that releases the lock when dropped
+    ///
+    /// # Errors
+    ///
+    /// Returns `InitInProgress` if another process holds the lock (unless
+    /// [`wait_for_locks`] was called), or `Io` if the lock file can't be
+    /// created
+    ///
+    /// # Concurrency Safety
+    ///
+    /// The OS decides who holds the lock, so there is no window between
+    /// checking for a holder and taking the lock, and a lock left by a
+    /// crashed process is released with it. A leftover lock file is simply
+    /// reused.
+    pub fn acquire(target_dir: &Path) -> Result<Self> {
+        let lock_file = target_dir.join(OPERATION_LOCK_FILE);
+        let result = match try_os_lock(&lock_file, false) {
+            Err(CatalystError::Io(e)) if e.kind() == io::ErrorKind::Unsupported => {
+                tracing::debug!(
+                    lock_file = %lock_file.display(),
+                    "File locking unsupported, using a PID lock file"
+                );
+                return acquire_pid_lock(&lock_file, is_waiting());
+            }
+            other => other,
+        };
+
+        let lock = match result {
+            Err(CatalystError::InitInProgress { pid, .. }) if is_waiting() => {
+                if !progress::is_quiet() {
+                    eprintln!(
+                        "⏳ Waiting for another catalyst process (PID {}) to finish...",
+                        pid
+                    );
+                }
+                try_os_lock(&lock_file, true)
+            }
+            other => other,
+        }?;
+        progress::detail(|| format!("Acquired lock {}", lock.path().display()));
+        Ok(lock)
+    }
+
+    /// Path of the lock file
+    pub fn path(&self) -> &Path {
+        &self.lock_file
+    }
+}
+
+/// Take the OS lock on `lock_file`, blocking until it is free if `wait`
+fn try_os_lock(lock_file: &Path, wait: bool) -> Result<OperationLock> {
+    let mut attempts = 0;
+    loop {
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(lock_file)
+            .map_err(CatalystError::Io)?;
+
+        if wait {
+            file.lock().map_err(CatalystError::Io)?;
+        } else {
+            match file.try_lock() {
+                Ok(()) => {}
+                Err(fs::TryLockError::WouldBlock) => return Err(lock_held(lock_file)),
+                Err(fs::TryLockError::Error(e)) => return Err(CatalystError::Io(e)),
+            }
+        }
+
+        if !is_same_file(&file, lock_file) {
+            attempts += 1;
+            // A waiting process keeps following the lock file until it wins
+            if !wait && attempts >= LOCK_ATTEMPTS {
+                return Err(lock_held(lock_file));
+            }
+            continue;
+        }
+
+        signals::register_cleanup(lock_file);
+        file.set_len(0).map_err(CatalystError::Io)?;
+        write!(&file, "{}", process::id()).map_err(CatalystError::Io)?;
+
+        return Ok(OperationLock {
+            lock_file: lock_file.to_path_buf(),
+            _file: Some(file),
+        });
+    }
+}
+
+/// Whether `lock_file` still names the file `file` has open
+#[cfg(unix)]
+fn is_same_file(file: &fs::File, lock_file: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (file.metadata(), fs::metadata(lock_file)) {
+        (Ok(open), Ok(named)) => open.dev() == named.dev() && open.ino() == named.ino(),
+        _ => false,
+    }
+}
+
+/// Windows won't open a file that is being deleted, so the open file is
+/// always the one at the path
+#[cfg(not(unix))]
+fn is_same_file(_file: &fs::File, lock_file: &Path) -> bool {
+    lock_file.exists()
+}
+
+/// `InitInProgress` for a lock another process holds
+///
+/// The holder writes its PID just after locking, so an empty or partial file
+/// reports PID 0.
+fn lock_held(lock_file: &Path) -> CatalystError {
+    let pid = fs::read_to_string(lock_file)
+        .ok()
+        .and_then(|content| content.trim().parse().ok())
+        .unwrap_or(0);
+    CatalystError::InitInProgress {
+        pid,
+        lock_file: lock_file.display().to_string(),
+    }
+}
+
+/// Helper function to atomically create a lock file and write PID
+///
+/// # Arguments
+///
+/// * `lock_file` - Path to the lock file
+/// * `pid` - Process ID to write to the lock file
+///
+/// # Returns
+///
+/// Returns an `OperationLock` guard or an I/O error
+fn try_create_lock_file(lock_file: &Path, pid: u32) -> Result<OperationLock> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true) // Atomic check-and-create
+        .open(lock_file)
+        .map_err(CatalystError::Io)?;
+
+    // Registered before writing the PID so a signal can't leave an empty lock behind
+    signals::register_cleanup(lock_file);
+
+    write!(file, "{}", pid).map_err(CatalystError::Io)?;
+
+    Ok(OperationLock {
+        lock_file: lock_file.to_path_buf(),
+        _file: None,
+    })
+}
+
+/// Fallback lock for filesystems without OS file locking
+///
+/// Creates the lock file with the current process ID using atomic file
+/// creation (O_EXCL on Unix, CREATE_NEW on Windows). An existing lock is
+/// stale if its process is no longer running, in which case it is removed
+/// and creation retried once. With `wait`, a live holder is polled until it
+/// goes away.
+fn acquire_pid_lock(lock_file: &Path, wait: bool) -> Result<OperationLock> {
+    loop {
+        match try_pid_lock(lock_file) {
+            Err(CatalystError::InitInProgress { .. }) if wait => {
+                std::thread::sleep(PID_POLL_INTERVAL)
+            }
+            other => return other,
+        }
+    }
+}
+
+/// One attempt at the PID-file lock
+fn try_pid_lock(lock_file: &Path) -> Result<OperationLock> {
+    let current_pid = process::id();
+
+    // Try to atomically create the lock file
+    // This prevents TOCTOU race conditions
+    match try_create_lock_file(lock_file, current_pid) {
+        Ok(lock) => Ok(lock),
+        Err(CatalystError::Io(e)) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            // Lock file exists - check if it's stale
+            let pid_str = fs::read_to_string(lock_file).map_err(CatalystError::Io)?;
+
+            match pid_str.trim().parse::<u32>() {
+                Ok(pid) if is_valid_pid(pid) => {
+                    // Valid PID - check if process is still running
+                    if is_process_running(pid) {
+                        Err(CatalystError::InitInProgress {
+                            pid,
+                            lock_file: lock_file.display().to_string(),
+                        })
+                    } else {
+                        // Stale lock file - remove and retry once
+                        fs::remove_file(lock_file).map_err(CatalystError::Io)?;
+
+                        // NOTE: There is a small TOCTOU race window between remove_file and
+                        // try_create_lock_file where another process could create the lock.
+                        // If it happens, try_create_lock_file fails with AlreadyExists and
+                        // this operation fails cleanly. OS locks don't have this window,
+                        // which is why this scheme is only the fallback.
+
+                        // Retry lock acquisition (non-recursive)
+                        try_create_lock_file(lock_file, current_pid)
+                    }
+                }
+                _ => {
+                    // Invalid PID (0, 1, current, or parse error) - treat as stale
+                    fs::remove_file(lock_file).map_err(CatalystError::Io)?;
+
+                    // NOTE: Known TOCTOU race window here (see comment above)
+                    // Retry lock acquisition
+                    try_create_lock_file(lock_file, current_pid)
+                }
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Validate that a PID is reasonable
+///
+/// Returns false for:
+/// - PID 0 (invalid)
+/// - PID 1 (system process, likely malicious lock file)
+///
+/// Note: We intentionally allow checking our own PID. If the lock file contains
+/// our PID, we'll check is_process_running() which will return true, causing
+/// the lock acquisition to fail with InitInProgress. This prevents the same
+/// process from acquiring the lock twice.
+fn is_valid_pid(pid: u32) -> bool {
+    pid != 0 && pid != 1
+}
+
+/// Remove the lock file
+fn release(lock_file: &Path) -> Result<()> {
+    signals::unregister_cleanup(lock_file);
+    if lock_file.exists() {
+        fs::remove_file(lock_file).map_err(CatalystError::Io)?;
+    }
+    Ok(())
+}
+
+/// Check if a process is running on the current system
+///
+/// # Platform-specific behavior
+///
+/// - **Unix/Linux/macOS**: Uses `kill -0 pid` to check if process exists
+/// - **Windows**: Uses OpenProcess to check if process exists
+#[cfg(unix)]
+fn is_process_running(pid: u32) -> bool {
+    use std::process::Command;
+
+    // On Unix, use kill -0 to check if process exists
+    Command::new("kill")
+        .arg("-0")
+        .arg(pid.to_string())
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn is_process_running(pid: u32) -> bool {
+    use windows_sys::Win32::Foundation::{
+        CloseHandle, GetLastError, ERROR_ACCESS_DENIED, ERROR_INVALID_PARAMETER,
+    };
+    use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    // Try to open the process with minimal access rights
+    //
+    // NOTE: We could use GetExitCodeProcess for more certainty, but OpenProcess
+    // with PROCESS_QUERY_LIMITED_INFORMATION is sufficient and requires minimal
+    // permissions. The conservative error handling (assume exists on unknown errors)
+    // provides adequate safety for lock file cleanup.
+    //
+    // SAFETY: This is safe because we're just checking if a process exists
+    // and we immediately close the handle if successful
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+
+        if handle == 0 {
+            // Failed to open process - check why
+            let error = GetLastError();
+
+            // Explicitly handle known error cases:
+            // - ERROR_INVALID_PARAMETER (87): Process definitely doesn't exist
+            // - ERROR_ACCESS_DENIED (5): Process exists but is protected (system/elevated)
+            // - Other errors: Conservatively assume process exists to avoid stale lock cleanup race
+            //
+            // This conservative approach prevents accidentally cleaning up locks for
+            // running processes in edge cases (network errors, permission issues, etc.)
+            match error {
+                ERROR_INVALID_PARAMETER => false, // Process doesn't exist
+                ERROR_ACCESS_DENIED => true,      // Process exists but protected
+                _ => {
+                    // Unknown error - be conservative and assume process exists
+                    // This prevents false positives that could cause concurrent init
+                    true
+                }
+            }
+        } else {
+            // Successfully opened - process exists
+            CloseHandle(handle);
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_acquire_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+
+        // First lock should succeed
+        let lock1 = OperationLock::acquire(target).unwrap();
+
+        // Second lock should fail while first is held
+        let lock2 = OperationLock::acquire(target);
+        assert!(lock2.is_err());
+        match lock2 {
+            Err(CatalystError::InitInProgress { pid, .. }) => {
+                assert_eq!(pid, process::id());
+            }
+            _ => panic!("Expected InitInProgress error"),
+        }
+
+        // Drop first lock
+        drop(lock1);
+
+        // Now second lock should succeed
+        let lock3 = OperationLock::acquire(target);
+        assert!(lock3.is_ok());
+    }
+
+    #[test]
+    fn test_leftover_lock_file_is_reused() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let lock_file = target.join(OPERATION_LOCK_FILE);
+
+        // A live PID in the file doesn't matter; only the OS lock does
+        fs::write(&lock_file, process::id().to_string()).unwrap();
+        let lock = OperationLock::acquire(target).unwrap();
+        assert!(lock._file.is_some());
+
+        // The update side of the lock is the same file
+        let result = crate::update::update(target, true);
+        assert!(matches!(result, Err(CatalystError::InitInProgress { .. })));
+
+        drop(lock);
+        assert!(!lock_file.exists());
+    }
+
+    #[test]
+    fn test_stale_lock_removal() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let lock_file = target.join(OPERATION_LOCK_FILE);
+
+        // Create a stale lock file with a non-existent PID
+        fs::write(&lock_file, "999999").unwrap();
+
+        // The PID fallback should remove the stale lock and succeed
+        let lock = acquire_pid_lock(&lock_file, false);
+        assert!(lock.is_ok());
+    }
+
+    #[test]
+    fn test_invalid_pid_lock_cleanup() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let lock_file = target.join(OPERATION_LOCK_FILE);
+
+        // Test invalid PID 0 (reserved system PID)
+        fs::write(&lock_file, "0").unwrap();
+        let lock = acquire_pid_lock(&lock_file, false);
+        assert!(lock.is_ok(), "Should clean up lock file with PID 0");
+        drop(lock);
+
+        // Test invalid PID 1 (init process PID)
+        fs::write(&lock_file, "1").unwrap();
+        let lock = acquire_pid_lock(&lock_file, false);
+        assert!(lock.is_ok(), "Should clean up lock file with PID 1");
+    }
+
+    #[test]
+    fn test_malformed_lock_cleanup() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let lock_file = target.join(OPERATION_LOCK_FILE);
+
+        // Test non-numeric content
+        fs::write(&lock_file, "not-a-number").unwrap();
+        let lock = acquire_pid_lock(&lock_file, false);
+        assert!(
+            lock.is_ok(),
+            "Should clean up lock file with invalid content"
+        );
+        drop(lock);
+
+        // Test empty lock file
+        fs::write(&lock_file, "").unwrap();
+        let lock = acquire_pid_lock(&lock_file, false);
+        assert!(lock.is_ok(), "Should clean up empty lock file");
+        drop(lock);
+
+        // Test lock file with whitespace
+        fs::write(&lock_file, "   \n\t  ").unwrap();
+        let lock = acquire_pid_lock(&lock_file, false);
+        assert!(
+            lock.is_ok(),
+            "Should clean up lock file with only whitespace"
+        );
+    }
+}
@@ -0,0 +1,556 @@
+//! Installed-components lockfile (`catalyst.lock`)
+//!
+//! Init records what it installed in `catalyst.lock` at the project root:
+//! each skill with a hash of its files, each hook wrapper with a hash of its
+//! script, and the version of each hook binary the wrappers call. The file is
+//! meant to be committed. `catalyst status` reports where the install has
+//! drifted from it, and `catalyst init --from-lock` sets up the same skills
+//! and hooks on another machine.
+//!
+//! ```toml
+//! version = 1
+//! catalyst_version = "0.1.0"
+//!
+//! [[skill]]
+//! name = "skill-developer"
+//! source = "project"
+//! version = "1.0.0"
+//! hash = "3f2a..."
+//!
+//! [[wrapper]]
+//! name = "skill-activation-prompt.sh"
+//! hash = "9c1e..."
+//!
+//! [[binary]]
+//! name = "skill-activation-prompt"
+//! version = "0.1.0"
+//! ```
+//!
+//! Wrappers differ between Unix (`.sh`) and Windows (`.ps1`), so a locked
+//! wrapper is only compared with an installed one of the same platform.
+
+use crate::binaries::{self, HOOK_BINARIES};
+use crate::config::{CatalystConfig, SkillSource};
+use crate::init;
+use crate::library;
+use crate::modification_store::TrackerBackend;
+use crate::types::{
+    CatalystError, InitConfig, LockDrift, LockedComponent, Platform, Result, SkillSelection,
+    CATALYST_VERSION, HOOKS_DIR, LOCK_FILE, SKILLS_DIR,
+};
+use crate::validation::get_binary_directory;
+use catalyst_core::fs_ops::{Fs, Mode};
+use catalyst_core::skill_manifest::{
+    SkillManifest, MANIFEST_FILE, SKILL_FILE, SKILL_TEMPLATE_FILE,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Lockfile format this CLI reads and writes
+pub const LOCK_FORMAT_VERSION: u32 = 1;
+
+const HEADER: &str = "# Written by catalyst init. Commit this file; don't edit it by hand.\n\n";
+
+/// Contents of catalyst.lock
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Format version (see [`LOCK_FORMAT_VERSION`])
+    pub version: u32,
+
+    /// Version of the CLI that wrote the lockfile
+    pub catalyst_version: String,
+
+    #[serde(default, rename = "skill")]
+    pub skills: Vec<LockedSkill>,
+
+    #[serde(default, rename = "wrapper")]
+    pub wrappers: Vec<LockedWrapper>,
+
+    #[serde(default, rename = "binary")]
+    pub binaries: Vec<LockedBinary>,
+}
+
+/// An installed skill
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedSkill {
+    pub name: String,
+
+    /// Whether the skill is copied into the project or linked from the
+    /// skill library
+    pub source: SkillSource,
+
+    /// Version from the skill's manifest, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Hash of the skill's files (see [`skill_hash`])
+    pub hash: String,
+}
+
+/// An installed hook wrapper script
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedWrapper {
+    /// File name in .claude/hooks (e.g., "skill-activation-prompt.sh")
+    pub name: String,
+
+    /// SHA-256 of the script
+    pub hash: String,
+}
+
+/// A hook binary called by an installed wrapper
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedBinary {
+    pub name: String,
+
+    /// Version reported by `--version`, if the binary supports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl Lockfile {
+    /// Describe what is currently installed in a project
+    ///
+    /// # Arguments
+    ///
+    /// * `fs` - Filesystem handle; capturing only reads
+    /// * `target_dir` - Project root
+    /// * `platform` - Current platform (for the binary file names)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a skill or wrapper file can't be read
+    pub fn capture<M: Mode>(fs: &Fs<M>, target_dir: &Path, platform: Platform) -> Result<Self> {
+        Self::capture_with(fs, target_dir, platform, |_, path| {
+            binaries::binary_version(path)
+        })
+    }
+
+    /// Describe what is installed, taking binary versions from `version`
+    /// instead of running each binary
+    ///
+    /// # Arguments
+    ///
+    /// * `fs` - Filesystem handle; capturing only reads
+    /// * `target_dir` - Project root
+    /// * `platform` - Current platform (for the binary file names)
+    /// * `version` - Version of a binary, given its name and path; None
+    ///   leaves it out of the comparison in [`drift`]
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Lockfile::capture`]
+    pub fn capture_with<M: Mode>(
+        fs: &Fs<M>,
+        target_dir: &Path,
+        platform: Platform,
+        version: impl Fn(&str, &Path) -> Option<String>,
+    ) -> Result<Self> {
+        let mut skills = Vec::new();
+        for (name, path) in dir_entries(fs, &target_dir.join(SKILLS_DIR))? {
+            if !fs.is_dir(&path) {
+                continue;
+            }
+            let linked = fs
+                .symlink_metadata(&path)
+                .is_ok_and(|m| m.file_type().is_symlink());
+            let source = if linked {
+                SkillSource::Library
+            } else {
+                SkillSource::Project
+            };
+            skills.push(LockedSkill {
+                version: skill_version(fs, &path),
+                hash: skill_hash(fs, &path)?,
+                name,
+                source,
+            });
+        }
+
+        let mut wrappers = Vec::new();
+        let mut binary_names = Vec::new();
+        let tracker_binary = CatalystConfig::read(fs, target_dir)?
+            .tracker
+            .backend
+            .hook_binary();
+        for (name, path) in dir_entries(fs, &target_dir.join(HOOKS_DIR))? {
+            let Some(binary) = wrapper_binary(&name) else {
+                continue;
+            };
+            if !fs.is_file(&path) {
+                continue;
+            }
+            let content = fs.read(&path).map_err(|e| CatalystError::FileReadFailed {
+                path: path.clone(),
+                source: e,
+            })?;
+            wrappers.push(LockedWrapper {
+                name,
+                hash: format!("{:x}", Sha256::digest(&content)),
+            });
+            // The tracker wrapper keeps its name for either tracker binary
+            let binary = match binary {
+                "file-change-tracker" => tracker_binary,
+                binary => binary,
+            };
+            if !binary_names.contains(&binary) {
+                binary_names.push(binary);
+            }
+        }
+
+        let mut binaries = Vec::new();
+        if let Ok(bin_dir) = get_binary_directory() {
+            for name in binary_names {
+                let exe = match platform {
+                    Platform::Windows => ".exe",
+                    _ => "",
+                };
+                let path = bin_dir.join(format!("{}{}", name, exe));
+                if fs.is_file(&path) {
+                    binaries.push(LockedBinary {
+                        name: name.to_string(),
+                        version: version(name, &path),
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            version: LOCK_FORMAT_VERSION,
+            catalyst_version: CATALYST_VERSION.to_string(),
+            skills,
+            wrappers,
+            binaries,
+        })
+    }
+}
+
+impl Lockfile {
+    /// Point an init config at the locked skills and hooks
+    ///
+    /// The config is switched to reconcile mode, so pieces already installed
+    /// are kept, and marked as reproducing the lockfile, so init compares
+    /// the result with it instead of rewriting it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a skill is locked as linked from the skill
+    /// library and the library location can't be determined
+    pub fn apply_to(&self, config: &mut InitConfig) -> Result<()> {
+        let locks_wrapper = |binary: &str| {
+            self.wrappers
+                .iter()
+                .any(|w| wrapper_binary(&w.name) == Some(binary))
+        };
+        config.skills = self.skills.iter().map(|skill| skill.name.clone()).collect();
+        config.skill_selection = SkillSelection::Lockfile;
+        config.install_hooks = locks_wrapper("skill-activation-prompt");
+        config.install_tracker = locks_wrapper("file-change-tracker");
+        config.install_check_hook = locks_wrapper("cargo-check");
+        if self
+            .binaries
+            .iter()
+            .any(|binary| binary.name == TrackerBackend::Jsonl.hook_binary())
+        {
+            config.tracker_backend = Some(TrackerBackend::Jsonl);
+        }
+        config.settings.clear();
+        if self
+            .skills
+            .iter()
+            .any(|skill| skill.source == SkillSource::Library)
+        {
+            config.skills_library = Some(library::library_dir()?);
+        }
+        config.reconcile = true;
+        config.from_lock = true;
+        Ok(())
+    }
+}
+
+/// Hook binary a wrapper file name calls, if it is a Catalyst wrapper
+fn wrapper_binary(file_name: &str) -> Option<&'static str> {
+    let (stem, extension) = file_name.rsplit_once('.')?;
+    if extension != "sh" && extension != "ps1" {
+        return None;
+    }
+    HOOK_BINARIES
+        .iter()
+        .map(|(name, _)| *name)
+        .find(|name| *name == stem)
+}
+
+/// Entries of a directory as (file name, path), sorted by name; empty if
+/// the directory doesn't exist
+fn dir_entries<M: Mode>(fs: &Fs<M>, dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let entries = match fs.read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(CatalystError::Io(e)),
+    };
+    let mut found = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(CatalystError::Io)?;
+        if let Some(name) = entry.file_name().to_str() {
+            found.push((name.to_string(), entry.path()));
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Hash a skill directory
+///
+/// The hash covers every file's path relative to the skill directory and
+/// its contents, so adding, removing, renaming, or editing a file changes
+/// it. Paths use `/` on every platform.
+///
+/// # Errors
+///
+/// Returns an error if a file can't be read
+pub fn skill_hash<M: Mode>(fs: &Fs<M>, skill_dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    collect_files(fs, skill_dir, "", &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for (relative, path) in files {
+        let content = fs.read(&path).map_err(|e| CatalystError::FileReadFailed {
+            path: path.clone(),
+            source: e,
+        })?;
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update(format!("{:x}\n", Sha256::digest(&content)));
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Version in a skill's manifest; None if it has none or the manifest
+/// can't be read
+fn skill_version<M: Mode>(fs: &Fs<M>, skill_dir: &Path) -> Option<String> {
+    let skill_md = fs
+        .read_to_string(&skill_dir.join(SKILL_FILE))
+        .or_else(|_| fs.read_to_string(&skill_dir.join(SKILL_TEMPLATE_FILE)))
+        .ok()?;
+    let skill_toml = fs.read_to_string(&skill_dir.join(MANIFEST_FILE)).ok();
+    SkillManifest::parse(&skill_md, skill_toml.as_deref())
+        .ok()?
+        .version
+}
+
+fn collect_files<M: Mode>(
+    fs: &Fs<M>,
+    dir: &Path,
+    prefix: &str,
+    files: &mut Vec<(String, PathBuf)>,
+) -> Result<()> {
+    for (name, path) in dir_entries(fs, dir)? {
+        let relative = format!("{}{}", prefix, name);
+        if fs.is_dir(&path) {
+            collect_files(fs, &path, &format!("{}/", relative), files)?;
+        } else {
+            files.push((relative, path));
+        }
+    }
+    Ok(())
+}
+
+/// Read a project's catalyst.lock
+///
+/// # Returns
+///
+/// Returns None if the project has no lockfile
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the file isn't a valid lockfile or was written
+/// in a newer format
+pub fn read<M: Mode>(fs: &Fs<M>, target_dir: &Path) -> Result<Option<Lockfile>> {
+    let path = target_dir.join(LOCK_FILE);
+    let content = match fs.read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(CatalystError::FileReadFailed { path, source: e }),
+    };
+    let lock: Lockfile = toml::from_str(&content)
+        .map_err(|e| CatalystError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+    if lock.version > LOCK_FORMAT_VERSION {
+        return Err(CatalystError::InvalidConfig(format!(
+            "{} uses format version {} (written by catalyst {}); this catalyst reads version {}",
+            path.display(),
+            lock.version,
+            lock.catalyst_version,
+            LOCK_FORMAT_VERSION
+        )));
+    }
+    Ok(Some(lock))
+}
+
+/// Write a project's catalyst.lock
+///
+/// # Errors
+///
+/// Returns an error if the file can't be written
+pub fn write(target_dir: &Path, lock: &Lockfile) -> Result<()> {
+    let body = toml::to_string(lock)
+        .map_err(|e| CatalystError::InvalidConfig(format!("{}: {}", LOCK_FILE, e)))?;
+    init::write_file_atomic(&target_dir.join(LOCK_FILE), &format!("{}{}", HEADER, body))?;
+    Ok(())
+}
+
+/// Compare a lockfile with what is installed
+///
+/// Binaries are only compared when both sides know their version; a
+/// missing binary is already reported by `catalyst status` on its own.
+///
+/// # Arguments
+///
+/// * `locked` - The committed lockfile
+/// * `installed` - [`Lockfile::capture`] of the project
+///
+/// # Returns
+///
+/// Every difference, skills first, then wrappers and binaries
+pub fn drift(locked: &Lockfile, installed: &Lockfile) -> Vec<LockDrift> {
+    let mut found = Vec::new();
+    let short = |hash: &str| hash.chars().take(12).collect::<String>();
+
+    for skill in &locked.skills {
+        let current = installed.skills.iter().find(|s| s.name == skill.name);
+        if current.is_none_or(|current| current.hash != skill.hash) {
+            found.push(LockDrift {
+                kind: LockedComponent::Skill,
+                name: skill.name.clone(),
+                locked: Some(short(&skill.hash)),
+                installed: current.map(|current| short(&current.hash)),
+            });
+        }
+    }
+    for skill in &installed.skills {
+        if !locked.skills.iter().any(|s| s.name == skill.name) {
+            found.push(LockDrift {
+                kind: LockedComponent::Skill,
+                name: skill.name.clone(),
+                locked: None,
+                installed: Some(short(&skill.hash)),
+            });
+        }
+    }
+
+    // A wrapper for the other platform's extension isn't comparable
+    let same_binary = |a: &str, b: &str| wrapper_binary(a) == wrapper_binary(b);
+    for wrapper in &locked.wrappers {
+        let current = installed.wrappers.iter().find(|w| w.name == wrapper.name);
+        let other_platform = current.is_none()
+            && installed
+                .wrappers
+                .iter()
+                .any(|w| same_binary(&w.name, &wrapper.name));
+        if !other_platform && current.is_none_or(|current| current.hash != wrapper.hash) {
+            found.push(LockDrift {
+                kind: LockedComponent::Wrapper,
+                name: wrapper.name.clone(),
+                locked: Some(short(&wrapper.hash)),
+                installed: current.map(|current| short(&current.hash)),
+            });
+        }
+    }
+    for wrapper in &installed.wrappers {
+        if !locked
+            .wrappers
+            .iter()
+            .any(|w| same_binary(&w.name, &wrapper.name))
+        {
+            found.push(LockDrift {
+                kind: LockedComponent::Wrapper,
+                name: wrapper.name.clone(),
+                locked: None,
+                installed: Some(short(&wrapper.hash)),
+            });
+        }
+    }
+
+    for binary in &locked.binaries {
+        let current = installed
+            .binaries
+            .iter()
+            .find(|b| b.name == binary.name)
+            .and_then(|b| b.version.as_ref());
+        if let (Some(locked_version), Some(current)) = (&binary.version, current) {
+            if locked_version != current {
+                found.push(LockDrift {
+                    kind: LockedComponent::Binary,
+                    name: binary.name.clone(),
+                    locked: Some(locked_version.clone()),
+                    installed: Some(current.clone()),
+                });
+            }
+        }
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::InitConfig;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_capture_round_trips_and_detects_drift() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir(target.join(".claude")).unwrap();
+        init::initialize(&InitConfig {
+            directory: target.to_path_buf(),
+            skills: vec!["skill-developer".to_string()],
+            ..InitConfig::default()
+        })
+        .unwrap();
+
+        let fs_ro = Fs::read_only();
+        let locked = read(&fs_ro, target)
+            .unwrap()
+            .expect("init writes the lockfile");
+        assert_eq!(locked.skills.len(), 1);
+        assert_eq!(locked.skills[0].source, SkillSource::Project);
+        assert_eq!(locked.skills[0].version.as_deref(), Some("1.0.0"));
+        assert_eq!(locked.wrappers.len(), 2);
+        let installed = Lockfile::capture(&fs_ro, target, Platform::detect()).unwrap();
+        assert!(drift(&locked, &installed).is_empty());
+
+        fs::write(
+            target.join(SKILLS_DIR).join("skill-developer/SKILL.md"),
+            "edited",
+        )
+        .unwrap();
+        let wrapper = &locked.wrappers[0].name;
+        fs::remove_file(target.join(HOOKS_DIR).join(wrapper)).unwrap();
+        let installed = Lockfile::capture(&fs_ro, target, Platform::detect()).unwrap();
+        let drifted = drift(&locked, &installed);
+        let components: Vec<String> = drifted
+            .iter()
+            .map(|d| format!("{} {}", d.kind, d.name))
+            .collect();
+        assert_eq!(
+            components,
+            [
+                "skill skill-developer".to_string(),
+                format!("wrapper {}", wrapper)
+            ]
+        );
+        assert_eq!(drifted[1].installed, None);
+
+        // A Windows checkout of a lockfile written on Unix isn't flagged
+        let mut windows = locked.clone();
+        for wrapper in &mut windows.wrappers {
+            wrapper.name = wrapper.name.replace(".sh", ".ps1");
+            wrapper.hash = "other".to_string();
+        }
+        assert!(drift(&locked, &windows).is_empty());
+    }
+}
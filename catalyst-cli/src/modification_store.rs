@@ -0,0 +1,457 @@
+//! Storage backends for file-change-tracker
+//!
+//! The tracker hook records edits through [`ModificationStore`] rather than
+//! talking to a database directly, so where edits end up is a matter of
+//! configuration:
+//!
+//! - `sqlite` - `tracker.db` (see [`crate::tracker`]); needs the `sqlite`
+//!   feature and is what `catalyst sessions` reads
+//! - `jsonl` - one JSON object per line in `modifications.jsonl`, which
+//!   needs nothing beyond the standard library and is easy to ship elsewhere
+//!
+//! The backend is chosen by `CATALYST_TRACKER_BACKEND`, then by
+//! `[tracker] backend` in the project's catalyst.toml, and defaults to
+//! `sqlite`. Both live in the same state directory
+//! (`~/.claude/hooks-state-rust`).
+
+use crate::config::CatalystConfig;
+use crate::types::{CatalystError, Result, CLAUDE_DIR, TRACKER_STATE_DIR};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the configured backend
+pub const TRACKER_BACKEND_ENV: &str = "CATALYST_TRACKER_BACKEND";
+
+/// Name of the JSONL backend's file inside the state directory
+pub const JSONL_FILE: &str = "modifications.jsonl";
+
+/// Directory file-change-tracker keeps its store in
+///
+/// # Errors
+///
+/// Returns `InvalidPath` if the home directory can't be determined
+pub fn state_dir() -> Result<PathBuf> {
+    dirs::home_dir()
+        .map(|home| home.join(CLAUDE_DIR).join(TRACKER_STATE_DIR))
+        .ok_or_else(|| CatalystError::InvalidPath("Could not determine home directory".into()))
+}
+
+/// One tracked edit (a row of the tracker's `file_modifications` table)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModificationRow {
+    pub session_id: String,
+    pub file_path: String,
+    pub tool: String,
+    /// When the edit was tracked (RFC 3339)
+    pub timestamp: String,
+    /// backend, frontend, database, or other
+    pub category: String,
+    pub has_async: bool,
+    pub has_try_catch: bool,
+    pub has_prisma: bool,
+    pub has_controller: bool,
+    pub has_api_call: bool,
+    pub line_count: i64,
+    /// Branch checked out in the project, if it's a git repository and not
+    /// on a detached HEAD
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    /// Full hash of the project's HEAD commit when the edit was tracked
+    #[serde(default)]
+    pub git_commit: Option<String>,
+}
+
+#[cfg(test)]
+impl ModificationRow {
+    /// A 10-line `Edit` of a backend file with no risk flags or git
+    /// details; tests override the fields they care about
+    pub(crate) fn test_edit(session_id: &str, file_path: &str, timestamp: &str) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            file_path: file_path.to_string(),
+            tool: "Edit".to_string(),
+            timestamp: timestamp.to_string(),
+            category: "backend".to_string(),
+            has_async: false,
+            has_try_catch: false,
+            has_prisma: false,
+            has_controller: false,
+            has_api_call: false,
+            line_count: 10,
+            git_branch: None,
+            git_commit: None,
+        }
+    }
+}
+
+/// Which edits a query returns
+#[derive(Debug, Clone, Default)]
+pub struct ModificationFilter {
+    /// Only this session
+    pub session: Option<String>,
+
+    /// Only edits at or after this time (sessions still active by then)
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only edits before this time (sessions started by then)
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl ModificationFilter {
+    /// Whether a timestamp falls in the filter's range
+    ///
+    /// Unparseable timestamps can't be placed in a range, so they only match
+    /// when no range was asked for.
+    pub fn includes_time(&self, timestamp: &str) -> bool {
+        match DateTime::parse_from_rfc3339(timestamp) {
+            Ok(time) => {
+                self.since.is_none_or(|since| time >= since)
+                    && self.until.is_none_or(|until| time < until)
+            }
+            Err(_) => self.since.is_none() && self.until.is_none(),
+        }
+    }
+
+    /// Whether an edit matches the session and time range
+    pub fn matches(&self, row: &ModificationRow) -> bool {
+        self.session
+            .as_deref()
+            .is_none_or(|session| session == row.session_id)
+            && self.includes_time(&row.timestamp)
+    }
+}
+
+/// Parse a `--since`/`--until` bound: an RFC 3339 timestamp, a date, or an
+/// age such as `30m`, `2h`, `7d`, or `2w`
+///
+/// A bare date means the start of that day (UTC), or with `end_of_day` the
+/// start of the next, so `--until 2026-03-31` includes all of March 31. An
+/// age counts back from now.
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the value is none of these forms
+pub fn parse_time_bound(value: &str, end_of_day: bool) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value.trim()) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Some(age) = parse_age(value.trim()) {
+        return Ok(Utc::now() - age);
+    }
+    let date = NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").map_err(|_| {
+        CatalystError::InvalidConfig(format!(
+            "Invalid date '{}'. Use YYYY-MM-DD, an RFC 3339 timestamp, or an age like 2h or 7d",
+            value.trim()
+        ))
+    })?;
+    let start = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+    Ok(if end_of_day {
+        start + Duration::days(1)
+    } else {
+        start
+    })
+}
+
+/// `<number><unit>` with a unit of m (minutes), h, d, or w
+fn parse_age(value: &str) -> Option<Duration> {
+    let unit = value.chars().last()?;
+    let amount: u32 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    let amount = i64::from(amount);
+    match unit {
+        'm' => Duration::try_minutes(amount),
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+/// Somewhere tracked edits are kept
+pub trait ModificationStore {
+    /// Record one edit, along with whatever per-session summary the backend
+    /// maintains
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the edit can't be written
+    fn record(&mut self, modification: &ModificationRow) -> Result<()>;
+
+    /// Edits matching `filter`, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store can't be read
+    fn query(&self, filter: &ModificationFilter) -> Result<Vec<ModificationRow>>;
+
+    /// Remove edits tracked before `cutoff`
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of edits removed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store can't be rewritten
+    fn prune(&mut self, cutoff: DateTime<Utc>) -> Result<usize>;
+}
+
+/// Which [`ModificationStore`] the tracker writes to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackerBackend {
+    /// `tracker.db`
+    #[default]
+    Sqlite,
+    /// `modifications.jsonl`
+    Jsonl,
+}
+
+impl std::str::FromStr for TrackerBackend {
+    type Err = CatalystError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "sqlite" => Ok(TrackerBackend::Sqlite),
+            "jsonl" => Ok(TrackerBackend::Jsonl),
+            _ => Err(CatalystError::InvalidConfig(format!(
+                "Unknown tracker backend '{}'. Expected sqlite or jsonl",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for TrackerBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            TrackerBackend::Sqlite => "sqlite",
+            TrackerBackend::Jsonl => "jsonl",
+        })
+    }
+}
+
+impl TrackerBackend {
+    /// Hook binary the tracker wrapper runs for this backend
+    ///
+    /// `file-change-tracker` needs a build with the `sqlite` feature (and
+    /// still honors `tracker.backend`); `file-change-tracker-basic` always
+    /// writes JSONL.
+    pub fn hook_binary(self) -> &'static str {
+        match self {
+            TrackerBackend::Sqlite => "file-change-tracker",
+            TrackerBackend::Jsonl => "file-change-tracker-basic",
+        }
+    }
+
+    /// Backend for a project: the environment override, then catalyst.toml
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfig` if the variable names an unknown backend, or
+    /// the config file's errors if it can't be loaded
+    pub fn resolve(project_dir: &Path) -> Result<Self> {
+        match std::env::var(TRACKER_BACKEND_ENV) {
+            Ok(value) if !value.trim().is_empty() => value.parse(),
+            _ => Ok(CatalystConfig::load(project_dir)?.tracker.backend),
+        }
+    }
+
+    /// Open this backend's store in `state_dir`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfig` for `sqlite` in a build without the `sqlite`
+    /// feature, or the backend's error if the store can't be opened
+    pub fn open(self, state_dir: &Path) -> Result<Box<dyn ModificationStore>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            TrackerBackend::Sqlite => Ok(Box::new(crate::tracker::SqliteStore::open(state_dir)?)),
+            #[cfg(not(feature = "sqlite"))]
+            TrackerBackend::Sqlite => Err(CatalystError::InvalidConfig(
+                "The sqlite tracker backend needs a build with the 'sqlite' feature; \
+                 set tracker.backend = \"jsonl\" instead"
+                    .to_string(),
+            )),
+            TrackerBackend::Jsonl => Ok(Box::new(JsonlStore::open(state_dir)?)),
+        }
+    }
+}
+
+/// Edits appended to `modifications.jsonl`, one JSON object per line
+#[derive(Debug, Clone)]
+pub struct JsonlStore {
+    path: PathBuf,
+}
+
+impl JsonlStore {
+    /// Use the JSONL file in `state_dir`, creating the directory if needed
+    ///
+    /// # Errors
+    ///
+    /// Returns `DirectoryCreationFailed` if `state_dir` can't be created
+    pub fn open(state_dir: &Path) -> Result<Self> {
+        fs::create_dir_all(state_dir).map_err(|source| CatalystError::DirectoryCreationFailed {
+            path: state_dir.to_path_buf(),
+            source,
+        })?;
+        Ok(Self {
+            path: state_dir.join(JSONL_FILE),
+        })
+    }
+
+    /// Path of the JSONL file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every stored edit, skipping lines that don't parse (a write cut
+    /// short by a crash leaves at most one)
+    fn read_all(&self) -> Result<Vec<ModificationRow>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => {
+                return Err(CatalystError::FileReadFailed {
+                    path: self.path.clone(),
+                    source,
+                })
+            }
+        };
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
+
+impl ModificationStore for JsonlStore {
+    fn record(&mut self, modification: &ModificationRow) -> Result<()> {
+        let mut line = serde_json::to_string(modification)?;
+        line.push('\n');
+        // One write per line: appends from concurrent hooks don't interleave
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|source| CatalystError::FileWriteFailed {
+                path: self.path.clone(),
+                source,
+            })
+    }
+
+    fn query(&self, filter: &ModificationFilter) -> Result<Vec<ModificationRow>> {
+        let mut rows: Vec<ModificationRow> = self
+            .read_all()?
+            .into_iter()
+            .filter(|row| filter.matches(row))
+            .collect();
+        rows.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        Ok(rows)
+    }
+
+    fn prune(&mut self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let rows = self.read_all()?;
+        let total = rows.len();
+        let keep = ModificationFilter {
+            since: Some(cutoff),
+            ..ModificationFilter::default()
+        };
+        let kept: Vec<String> = rows
+            .iter()
+            .filter(|row| keep.matches(row))
+            .map(serde_json::to_string)
+            .collect::<std::result::Result<_, _>>()?;
+        let removed = total - kept.len();
+        if removed > 0 {
+            let mut content = kept.join("\n");
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            crate::init::write_file_atomic(&self.path, &content)?;
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_jsonl_store_record_query_prune() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = TrackerBackend::Jsonl.open(temp_dir.path()).unwrap();
+        store
+            .record(&ModificationRow::test_edit(
+                "one",
+                "b.ts",
+                "2026-03-02T10:00:00+00:00",
+            ))
+            .unwrap();
+        store
+            .record(&ModificationRow::test_edit(
+                "one",
+                "a.ts",
+                "2026-03-01T10:00:00+00:00",
+            ))
+            .unwrap();
+        store
+            .record(&ModificationRow::test_edit(
+                "two",
+                "c.ts",
+                "2026-03-03T10:00:00+00:00",
+            ))
+            .unwrap();
+
+        let all = store.query(&ModificationFilter::default()).unwrap();
+        let files: Vec<&str> = all.iter().map(|row| row.file_path.as_str()).collect();
+        assert_eq!(files, vec!["a.ts", "b.ts", "c.ts"]);
+        assert_eq!(
+            all[0],
+            ModificationRow::test_edit("one", "a.ts", "2026-03-01T10:00:00+00:00")
+        );
+
+        let session_one = ModificationFilter {
+            session: Some("one".to_string()),
+            ..ModificationFilter::default()
+        };
+        assert_eq!(store.query(&session_one).unwrap().len(), 2);
+
+        let cutoff = DateTime::parse_from_rfc3339("2026-03-02T00:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(store.prune(cutoff).unwrap(), 1);
+        assert_eq!(
+            store.query(&ModificationFilter::default()).unwrap().len(),
+            2
+        );
+        assert_eq!(store.prune(cutoff).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_backend_from_config_and_name() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("catalyst.toml"),
+            "[tracker]\nbackend = \"jsonl\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            CatalystConfig::load(temp_dir.path())
+                .unwrap()
+                .tracker
+                .backend,
+            TrackerBackend::Jsonl
+        );
+        assert_eq!(
+            "SQLite".parse::<TrackerBackend>().unwrap(),
+            TrackerBackend::Sqlite
+        );
+        assert!("redis".parse::<TrackerBackend>().is_err());
+    }
+}
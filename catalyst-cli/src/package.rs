@@ -0,0 +1,336 @@
+//! Skill packages (`catalyst skills pack` and `skills add <FILE>.catskill`)
+//!
+//! A `.catskill` file is a gzipped tar archive holding one skill directory,
+//! named after the skill, next to a `SHA256SUMS` file with a line for every
+//! file in it:
+//!
+//! ```text
+//! SHA256SUMS
+//! terraform-patterns/SKILL.md
+//! terraform-patterns/skill.toml
+//! terraform-patterns/resources/modules.md
+//! ```
+//!
+//! The skill's manifest (SKILL.md front matter plus skill.toml, see
+//! [`SkillManifest`]) is checked when packing and again when installing.
+//! The checksums catch a damaged or edited package; they don't say who made
+//! it, so share packages the way you would share the skill's files.
+
+use crate::lock::OperationLock;
+use crate::registry;
+use crate::safe_path;
+use crate::types::{CatalystError, Result};
+use catalyst_core::skill_manifest::SkillManifest;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+/// File extension of skill packages
+pub const PACKAGE_EXTENSION: &str = "catskill";
+
+/// Checksum file at the root of every package
+pub const PACKAGE_CHECKSUMS: &str = "SHA256SUMS";
+
+/// A package written by [`pack_skill`]
+#[derive(Debug, Clone)]
+pub struct PackedSkill {
+    pub path: PathBuf,
+    pub manifest: SkillManifest,
+    /// SHA-256 of the package, as a registry index lists it
+    pub sha256: String,
+}
+
+/// Whether a `skills add` argument names a package file rather than a
+/// registry skill
+pub fn is_package_path(arg: &str) -> bool {
+    Path::new(arg)
+        .extension()
+        .is_some_and(|extension| extension == PACKAGE_EXTENSION)
+}
+
+/// Bundle a skill directory into a `.catskill` package
+///
+/// Hidden files and directories are left out.
+///
+/// # Arguments
+///
+/// * `skill_dir` - Directory with the skill's SKILL.md
+/// * `output` - Package to write; defaults to `<name>-<version>.catskill`
+///   (or `<name>.catskill` without a version) in the current directory
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the skill's manifest is invalid or the
+/// directory contains a link, and `BuildFailed` if tar fails
+pub fn pack_skill(skill_dir: &Path, output: Option<&Path>) -> Result<PackedSkill> {
+    let manifest = SkillManifest::load(skill_dir)
+        .map_err(|e| CatalystError::InvalidConfig(format!("{}: {:#}", skill_dir.display(), e)))?;
+    safe_path::validate_skill_id(&manifest.name)?;
+
+    let staging = tempfile::tempdir().map_err(CatalystError::Io)?;
+    let staged_skill = staging.path().join(&manifest.name);
+    let mut checksums = String::new();
+    let entries = WalkDir::new(skill_dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !entry.file_name().to_string_lossy().starts_with('.'));
+    for entry in entries {
+        let entry = entry.map_err(|e| CatalystError::Io(e.into()))?;
+        let relative = entry
+            .path()
+            .strip_prefix(skill_dir)
+            .expect("walkdir yields paths under its root");
+        let destination = staged_skill.join(relative);
+        if entry.path_is_symlink() {
+            return Err(CatalystError::InvalidConfig(format!(
+                "{} is a link; packages can only hold regular files",
+                entry.path().display()
+            )));
+        }
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&destination).map_err(|source| {
+                CatalystError::DirectoryCreationFailed {
+                    path: destination.clone(),
+                    source,
+                }
+            })?;
+            continue;
+        }
+        let content = fs::read(entry.path()).map_err(|source| CatalystError::FileReadFailed {
+            path: entry.path().to_path_buf(),
+            source,
+        })?;
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent).map_err(|source| {
+                CatalystError::DirectoryCreationFailed {
+                    path: parent.to_path_buf(),
+                    source,
+                }
+            })?;
+        }
+        fs::write(&destination, &content).map_err(|source| CatalystError::FileWriteFailed {
+            path: destination.clone(),
+            source,
+        })?;
+        let archive_path = Path::new(&manifest.name).join(relative);
+        checksums.push_str(&format!(
+            "{:x}  {}\n",
+            Sha256::digest(&content),
+            archive_path.to_string_lossy().replace('\\', "/")
+        ));
+    }
+    let checksums_path = staging.path().join(PACKAGE_CHECKSUMS);
+    fs::write(&checksums_path, checksums).map_err(|source| CatalystError::FileWriteFailed {
+        path: checksums_path,
+        source,
+    })?;
+
+    let path = match output {
+        Some(output) => output.to_path_buf(),
+        None => PathBuf::from(match &manifest.version {
+            Some(version) => format!("{}-{}.{}", manifest.name, version, PACKAGE_EXTENSION),
+            None => format!("{}.{}", manifest.name, PACKAGE_EXTENSION),
+        }),
+    };
+    let output = Command::new("tar")
+        .arg("-czf")
+        .arg(&path)
+        .arg("-C")
+        .arg(staging.path())
+        .arg(PACKAGE_CHECKSUMS)
+        .arg(&manifest.name)
+        .output()
+        .map_err(|e| CatalystError::BuildFailed(format!("Could not run tar: {}", e)))?;
+    if !output.status.success() {
+        return Err(CatalystError::BuildFailed(format!(
+            "tar could not write {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let content = fs::read(&path).map_err(|source| CatalystError::FileReadFailed {
+        path: path.clone(),
+        source,
+    })?;
+    Ok(PackedSkill {
+        sha256: format!("{:x}", Sha256::digest(&content)),
+        path,
+        manifest,
+    })
+}
+
+/// Install a skill from a `.catskill` package
+///
+/// # Arguments
+///
+/// * `target_dir` - Project directory containing .claude
+/// * `package` - Package file
+/// * `force` - Replace the skill if it is already installed
+///
+/// # Returns
+///
+/// Returns the skill's manifest and installed directory
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the file isn't a skill package or the skill
+/// can't be installed (see [`registry::add_skill`]), and `HashMismatch` if
+/// a file doesn't match `SHA256SUMS` or isn't listed in it
+pub fn install_package(
+    target_dir: &Path,
+    package: &Path,
+    force: bool,
+) -> Result<(SkillManifest, PathBuf)> {
+    if !package.is_file() {
+        return Err(CatalystError::PathNotFound(package.to_path_buf()));
+    }
+    let _lock = OperationLock::acquire(target_dir)?;
+
+    let work_dir = tempfile::tempdir().map_err(CatalystError::Io)?;
+    registry::extract(package, work_dir.path())?;
+    verify_checksums(work_dir.path(), package)?;
+    let not_a_package = || {
+        CatalystError::InvalidConfig(format!(
+            "{} is not a skill package (no skill directory)",
+            package.display()
+        ))
+    };
+    let source = registry::find_skill_root(work_dir.path()).ok_or_else(not_a_package)?;
+    let manifest = SkillManifest::load(&source)
+        .map_err(|e| CatalystError::InvalidConfig(format!("{}: {:#}", package.display(), e)))?;
+    if source.file_name() != Some(manifest.name.as_ref()) {
+        return Err(not_a_package());
+    }
+
+    registry::check_installable(target_dir, &manifest.name, force)?;
+    let dir = registry::install_skill_dir(target_dir, &manifest.name, &source)?;
+    Ok((manifest, dir))
+}
+
+/// Check every unpacked file against the package's SHA256SUMS
+fn verify_checksums(root: &Path, package: &Path) -> Result<()> {
+    let checksums_path = root.join(PACKAGE_CHECKSUMS);
+    let checksums = fs::read_to_string(&checksums_path).map_err(|_| {
+        CatalystError::InvalidConfig(format!(
+            "{} is not a skill package (no {})",
+            package.display(),
+            PACKAGE_CHECKSUMS
+        ))
+    })?;
+    let mut expected = BTreeMap::new();
+    for line in checksums.lines().filter(|line| !line.trim().is_empty()) {
+        let (hash, name) = line.split_once("  ").ok_or_else(|| {
+            CatalystError::InvalidConfig(format!("Malformed {} line: {}", PACKAGE_CHECKSUMS, line))
+        })?;
+        expected.insert(safe_path::relative_path(name, "checksum entry")?, hash);
+    }
+
+    for entry in WalkDir::new(root).min_depth(1) {
+        let entry = entry.map_err(|e| CatalystError::Io(e.into()))?;
+        if !entry.file_type().is_file() || entry.path() == checksums_path {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(root)
+            .expect("walkdir yields paths under its root");
+        let name = relative.display();
+        let hash = expected.remove(relative).ok_or_else(|| {
+            CatalystError::HashMismatch(format!("{} is not listed in {}", name, PACKAGE_CHECKSUMS))
+        })?;
+        let content = fs::read(entry.path()).map_err(|source| CatalystError::FileReadFailed {
+            path: entry.path().to_path_buf(),
+            source,
+        })?;
+        let actual = format!("{:x}", Sha256::digest(&content));
+        if !actual.eq_ignore_ascii_case(hash) {
+            return Err(CatalystError::HashMismatch(format!(
+                "{}: expected {}, found {}",
+                name, hash, actual
+            )));
+        }
+    }
+    if let Some(missing) = expected.keys().next() {
+        return Err(CatalystError::HashMismatch(format!(
+            "{} is listed in {} but missing from the package",
+            missing.display(),
+            PACKAGE_CHECKSUMS
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CLAUDE_DIR, SKILLS_DIR};
+    use tempfile::TempDir;
+
+    fn write_skill(dir: &Path) {
+        fs::create_dir_all(dir.join("resources")).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: demo\ndescription: Demo skill\n---\n",
+        )
+        .unwrap();
+        fs::write(dir.join("skill.toml"), "version = \"0.3.0\"\n").unwrap();
+        fs::write(dir.join("resources/notes.md"), "notes").unwrap();
+        fs::write(dir.join(".DS_Store"), "junk").unwrap();
+    }
+
+    #[test]
+    fn test_pack_and_install_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("my-skill");
+        write_skill(&source);
+        let output = temp_dir.path().join("demo.catskill");
+
+        let packed = pack_skill(&source, Some(&output)).unwrap();
+        assert_eq!(packed.manifest.version.as_deref(), Some("0.3.0"));
+        assert_eq!(packed.sha256.len(), 64);
+
+        let target = temp_dir.path().join("project");
+        fs::create_dir_all(target.join(CLAUDE_DIR)).unwrap();
+        let (manifest, dir) = install_package(&target, &output, false).unwrap();
+        assert_eq!(manifest.name, "demo");
+        assert_eq!(dir, target.join(SKILLS_DIR).join("demo"));
+        assert_eq!(
+            fs::read_to_string(dir.join("resources/notes.md")).unwrap(),
+            "notes"
+        );
+        assert!(!dir.join(".DS_Store").exists());
+    }
+
+    #[test]
+    fn test_verify_checksums_rejects_edited_and_extra_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("demo")).unwrap();
+        fs::write(root.join("demo/SKILL.md"), "original").unwrap();
+        let hash = format!("{:x}", Sha256::digest(b"original"));
+        fs::write(
+            root.join(PACKAGE_CHECKSUMS),
+            format!("{}  demo/SKILL.md\n", hash),
+        )
+        .unwrap();
+        let package = Path::new("demo.catskill");
+        verify_checksums(root, package).unwrap();
+
+        fs::write(root.join("demo/extra.md"), "extra").unwrap();
+        assert!(matches!(
+            verify_checksums(root, package),
+            Err(CatalystError::HashMismatch(_))
+        ));
+        fs::remove_file(root.join("demo/extra.md")).unwrap();
+        fs::write(root.join("demo/SKILL.md"), "edited").unwrap();
+        assert!(matches!(
+            verify_checksums(root, package),
+            Err(CatalystError::HashMismatch(_))
+        ));
+    }
+}
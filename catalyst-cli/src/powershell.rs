@@ -0,0 +1,429 @@
+//! PowerShell hosts for .ps1 hook wrappers
+//!
+//! Windows ships Windows PowerShell as `powershell`, PowerShell 7 installs
+//! as `pwsh`, and some machines (every non-Windows one, and stripped-down
+//! Windows images) only have the latter. Claude Code can't run a .ps1 file
+//! by path, and the default execution policy refuses unsigned scripts, so
+//! settings.json invokes each wrapper through an installed host:
+//!
+//! ```text
+//! pwsh -NoProfile -ExecutionPolicy Bypass -File "$CLAUDE_PROJECT_DIR/.claude/hooks/skill-activation-prompt.ps1"
+//! ```
+//!
+//! The host is `wrappers.powershell` from catalyst.toml if set, otherwise
+//! the first of `powershell` and `pwsh` found on PATH. `catalyst status`
+//! reports commands that don't match (CAT018) and `--fix` rewrites them.
+
+use crate::binaries::HOOK_BINARIES;
+use crate::config::CatalystConfig;
+use crate::process;
+use crate::types::{
+    CatalystError, PowerShell, PowerShellCommandStatus, PowerShellProblem, PowerShellStatus,
+    Result, HOOKS_DIR, SETTINGS_FILE,
+};
+use catalyst_core::settings::ClaudeSettings;
+use std::env;
+use std::ffi::OsStr;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Hosts in order of preference: Windows PowerShell is present on every
+/// Windows machine, so it is only passed over when missing
+const PREFERENCE: &[PowerShell] = &[PowerShell::WindowsPowerShell, PowerShell::Pwsh];
+
+/// Execution policies that refuse unsigned local scripts
+const BLOCKING_POLICIES: &[&str] = &["Restricted", "AllSigned"];
+
+/// How long the execution policy query may take
+const POLICY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// PowerShell hosts on the current PATH, in order of preference
+pub fn installed() -> Vec<PowerShell> {
+    env::var_os("PATH")
+        .map(|path| installed_on(&path))
+        .unwrap_or_default()
+}
+
+/// PowerShell hosts on a PATH-style list of directories
+pub fn installed_on(path: &OsStr) -> Vec<PowerShell> {
+    PREFERENCE
+        .iter()
+        .copied()
+        .filter(|host| process::find_on_path(host.program(), path).is_some())
+        .collect()
+}
+
+/// Host to run wrappers with: the configured one, else the first installed
+pub fn resolve(configured: Option<PowerShell>, installed: &[PowerShell]) -> Option<PowerShell> {
+    configured.or_else(|| installed.first().copied())
+}
+
+/// Host to run .ps1 files with, falling back to Windows PowerShell when
+/// none is detected so the error names a real program
+pub fn host(configured: Option<PowerShell>) -> PowerShell {
+    resolve(configured, &installed()).unwrap_or(PowerShell::WindowsPowerShell)
+}
+
+/// Command settings.json runs for a Catalyst hook wrapper
+///
+/// .sh wrappers are run by path; .ps1 wrappers through the project's
+/// PowerShell host with the execution policy bypassed.
+pub fn settings_command(wrapper_name: &str, config: &CatalystConfig) -> String {
+    if wrapper_name.ends_with(".ps1") {
+        hook_command(host(config.wrappers.powershell), wrapper_name)
+    } else {
+        format!("$CLAUDE_PROJECT_DIR/{}/{}", HOOKS_DIR, wrapper_name)
+    }
+}
+
+/// Invocation of a .ps1 wrapper through `host`
+pub fn hook_command(host: PowerShell, wrapper_name: &str) -> String {
+    format!(
+        "{} -NoProfile -ExecutionPolicy Bypass -File \"$CLAUDE_PROJECT_DIR/{}/{}\"",
+        host.program(),
+        HOOKS_DIR,
+        wrapper_name
+    )
+}
+
+/// PowerShell host a command starts with, if any
+///
+/// Accepts a bare program name or a (quoted) path, with or without `.exe`.
+pub fn invoked_host(command: &str) -> Option<PowerShell> {
+    let command = command.trim_start();
+    let program = match command.strip_prefix('"') {
+        Some(rest) => rest.split('"').next().unwrap_or_default(),
+        None => command.split_whitespace().next().unwrap_or_default(),
+    };
+    let file_name = program.rsplit(['/', '\\']).next().unwrap_or_default();
+    let file_name = file_name.to_ascii_lowercase();
+    let name = file_name.strip_suffix(".exe").unwrap_or(&file_name);
+    PREFERENCE
+        .iter()
+        .copied()
+        .find(|host| host.program() == name)
+}
+
+/// Whether a command passes `-ExecutionPolicy Bypass` (or an abbreviation)
+fn bypasses_policy(command: &str) -> bool {
+    let words: Vec<String> = command
+        .split_whitespace()
+        .map(str::to_ascii_lowercase)
+        .collect();
+    words.windows(2).any(|pair| {
+        let flag = pair[0].as_str();
+        let abbreviates = flag.len() >= 3 && "-executionpolicy".starts_with(flag);
+        (abbreviates || flag == "-ep") && pair[1] == "bypass"
+    })
+}
+
+/// What is wrong with the settings.json command for a .ps1 wrapper
+///
+/// # Arguments
+///
+/// * `command` - Command as written in settings.json
+/// * `configured` - `wrappers.powershell` from catalyst.toml
+/// * `installed` - Hosts on PATH
+pub fn command_problem(
+    command: &str,
+    configured: Option<PowerShell>,
+    installed: &[PowerShell],
+) -> Option<PowerShellProblem> {
+    let Some(invoked) = invoked_host(command) else {
+        return Some(PowerShellProblem::NoHost);
+    };
+    if !installed.is_empty() && !installed.contains(&invoked) {
+        return Some(PowerShellProblem::HostNotInstalled { host: invoked });
+    }
+    if let Some(configured) = configured.filter(|&configured| configured != invoked) {
+        return Some(PowerShellProblem::WrongHost {
+            host: invoked,
+            configured,
+        });
+    }
+    if !bypasses_policy(command) {
+        return Some(PowerShellProblem::NoPolicyBypass);
+    }
+    None
+}
+
+/// Execution policy set by Group Policy that blocks unsigned scripts
+///
+/// Policies set by Group Policy take precedence over `-ExecutionPolicy
+/// Bypass`. Only Windows has them; elsewhere this is always None, as it is
+/// when the host can't be run.
+pub fn enforced_policy(host: PowerShell) -> Option<String> {
+    if !cfg!(windows) {
+        return None;
+    }
+
+    let mut child = Command::new(host.program())
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Get-ExecutionPolicy -Scope MachinePolicy; Get-ExecutionPolicy -Scope UserPolicy",
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    process::wait_timeout(&mut child, POLICY_TIMEOUT).ok()??;
+
+    let mut output = String::new();
+    child.stdout.take()?.read_to_string(&mut output).ok()?;
+    output
+        .lines()
+        .map(str::trim)
+        .find_map(|policy| {
+            BLOCKING_POLICIES
+                .iter()
+                .find(|blocking| blocking.eq_ignore_ascii_case(policy))
+        })
+        .map(|policy| policy.to_string())
+}
+
+/// Check the PowerShell setup of a project that uses .ps1 wrappers
+///
+/// An unreadable settings.json is reported elsewhere (CAT001), so its
+/// commands are simply not checked.
+pub fn check(target_dir: &Path, config: &CatalystConfig) -> PowerShellStatus {
+    let installed = installed();
+    let configured = config.wrappers.powershell;
+    let host = resolve(configured, &installed);
+
+    let settings_path = target_dir.join(SETTINGS_FILE);
+    let commands = if settings_path.is_file() {
+        ClaudeSettings::read(&settings_path)
+            .map(|settings| check_commands(&settings, configured, host, &installed))
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    PowerShellStatus {
+        enforced_policy: host
+            .filter(|host| installed.contains(host))
+            .and_then(enforced_policy),
+        installed,
+        host,
+        commands,
+    }
+}
+
+/// settings.json commands for Catalyst .ps1 wrappers that need rewriting
+fn check_commands(
+    settings: &ClaudeSettings,
+    configured: Option<PowerShell>,
+    host: Option<PowerShell>,
+    installed: &[PowerShell],
+) -> Vec<PowerShellCommandStatus> {
+    let mut commands = Vec::new();
+    for (event, configs) in &settings.hooks {
+        for hook in configs.iter().flat_map(|config| &config.hooks) {
+            let Some(wrapper) = HOOK_BINARIES
+                .iter()
+                .map(|(name, _)| format!("{}.ps1", name))
+                .find(|wrapper| hook.command.contains(wrapper.as_str()))
+            else {
+                continue;
+            };
+            if let Some(problem) = command_problem(&hook.command, configured, installed) {
+                let expected =
+                    hook_command(host.unwrap_or(PowerShell::WindowsPowerShell), &wrapper);
+                commands.push(PowerShellCommandStatus {
+                    wrapper,
+                    event: event.to_string(),
+                    command: hook.command.clone(),
+                    problem,
+                    expected,
+                });
+            }
+        }
+    }
+    commands.sort_by(|a, b| (&a.event, &a.wrapper).cmp(&(&b.event, &b.wrapper)));
+    commands
+}
+
+/// Replace hook commands in settings.json with their expected form
+///
+/// Everything else in the file is preserved.
+///
+/// # Returns
+///
+/// Returns a description of each command that was rewritten
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if settings.json can't be read or written, or
+/// if one of the commands is no longer in it
+pub fn rewrite_commands(
+    target_dir: &Path,
+    commands: &[&PowerShellCommandStatus],
+) -> Result<Vec<String>> {
+    let settings_path = target_dir.join(SETTINGS_FILE);
+    let mut settings = ClaudeSettings::read(&settings_path)
+        .map_err(|e| CatalystError::InvalidConfig(format!("{:#}", e)))?;
+
+    let mut rewritten = Vec::new();
+    for status in commands {
+        let mut found = false;
+        for hook in settings
+            .hooks
+            .values_mut()
+            .flatten()
+            .flat_map(|config| &mut config.hooks)
+            .filter(|hook| hook.command == status.command)
+        {
+            hook.command = status.expected.clone();
+            found = true;
+        }
+        if !found {
+            return Err(CatalystError::InvalidConfig(format!(
+                "Hook command for {} not found in settings.json",
+                status.wrapper
+            )));
+        }
+        rewritten.push(format!(
+            "Rewrote {} hook command: {}",
+            status.wrapper, status.expected
+        ));
+    }
+
+    settings
+        .write(&settings_path)
+        .map_err(|e| CatalystError::InvalidConfig(format!("{:#}", e)))?;
+    Ok(rewritten)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use catalyst_core::settings::{Hook, HookConfig, HookEvent};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_command_problem() {
+        let both = [PowerShell::WindowsPowerShell, PowerShell::Pwsh];
+        let pwsh_only = [PowerShell::Pwsh];
+        let good = hook_command(PowerShell::Pwsh, "skill-activation-prompt.ps1");
+        assert_eq!(invoked_host(&good), Some(PowerShell::Pwsh));
+        assert_eq!(command_problem(&good, None, &pwsh_only), None);
+
+        assert_eq!(
+            command_problem(
+                "$CLAUDE_PROJECT_DIR/.claude/hooks/skill-activation-prompt.ps1",
+                None,
+                &both
+            ),
+            Some(PowerShellProblem::NoHost)
+        );
+        assert_eq!(
+            command_problem("powershell -File x.ps1", None, &pwsh_only),
+            Some(PowerShellProblem::HostNotInstalled {
+                host: PowerShell::WindowsPowerShell
+            })
+        );
+        assert_eq!(
+            command_problem(
+                "powershell -ep bypass -File x.ps1",
+                Some(PowerShell::Pwsh),
+                &both
+            ),
+            Some(PowerShellProblem::WrongHost {
+                host: PowerShell::WindowsPowerShell,
+                configured: PowerShell::Pwsh
+            })
+        );
+        assert_eq!(
+            command_problem(
+                r#""C:\Program Files\PowerShell\7\pwsh.exe" -File x.ps1"#,
+                None,
+                &both
+            ),
+            Some(PowerShellProblem::NoPolicyBypass)
+        );
+        assert_eq!(
+            command_problem("PowerShell.exe -Exec Bypass -File x.ps1", None, &both),
+            None
+        );
+    }
+
+    #[test]
+    fn test_installed_on_prefers_windows_powershell() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first");
+        let second = temp_dir.path().join("second");
+        fs::create_dir_all(&first).unwrap();
+        fs::create_dir_all(&second).unwrap();
+        let exe = if cfg!(windows) { ".exe" } else { "" };
+        fs::write(first.join(format!("pwsh{}", exe)), "").unwrap();
+
+        let path = env::join_paths([&first, &second]).unwrap();
+        assert_eq!(installed_on(&path), vec![PowerShell::Pwsh]);
+
+        fs::write(second.join(format!("powershell{}", exe)), "").unwrap();
+        let installed = installed_on(&path);
+        assert_eq!(
+            installed,
+            vec![PowerShell::WindowsPowerShell, PowerShell::Pwsh]
+        );
+        assert_eq!(
+            resolve(None, &installed),
+            Some(PowerShell::WindowsPowerShell)
+        );
+        assert_eq!(
+            resolve(Some(PowerShell::Pwsh), &installed),
+            Some(PowerShell::Pwsh)
+        );
+    }
+
+    #[test]
+    fn test_rewrite_commands_keeps_other_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir_all(target.join(".claude")).unwrap();
+
+        let mut settings = ClaudeSettings::default();
+        for command in [
+            "$CLAUDE_PROJECT_DIR/.claude/hooks/skill-activation-prompt.ps1",
+            "my-own-hook.ps1",
+        ] {
+            settings
+                .add_hook(
+                    HookEvent::UserPromptSubmit,
+                    HookConfig {
+                        matcher: None,
+                        hooks: vec![Hook {
+                            r#type: "command".to_string(),
+                            command: command.to_string(),
+                        }],
+                    },
+                )
+                .unwrap();
+        }
+        settings.write(target.join(SETTINGS_FILE)).unwrap();
+
+        let commands = check_commands(&settings, None, Some(PowerShell::Pwsh), &[]);
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].wrapper, "skill-activation-prompt.ps1");
+        assert_eq!(commands[0].problem, PowerShellProblem::NoHost);
+
+        let rewritten = rewrite_commands(target, &[&commands[0]]).unwrap();
+        assert_eq!(rewritten.len(), 1);
+
+        let settings = ClaudeSettings::read(target.join(SETTINGS_FILE)).unwrap();
+        let written: Vec<&str> = settings.hooks[&HookEvent::UserPromptSubmit]
+            .iter()
+            .flat_map(|config| &config.hooks)
+            .map(|hook| hook.command.as_str())
+            .collect();
+        assert!(written.contains(&commands[0].expected.as_str()));
+        assert!(written.contains(&"my-own-hook.ps1"));
+        assert!(check_commands(&settings, None, Some(PowerShell::Pwsh), &[]).is_empty());
+    }
+}
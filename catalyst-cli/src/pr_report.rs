@@ -0,0 +1,408 @@
+//! Summaries of tracked work for pull requests (`catalyst report`)
+//!
+//! Combines the tracker's edits with the check log ([`crate::check_log`]) for
+//! one project: the files changed by category, files with risky patterns,
+//! and how the cargo-check and test-runner hooks fared. `--pr` renders it as
+//! Markdown for a PR description, and `--post` hands that to
+//! `gh pr comment`.
+//!
+//! Without `--session` or `--since` the report covers the most recent
+//! session that edited the project.
+
+use crate::check_log::{self, CheckStatus};
+use crate::modification_store::{ModificationFilter, ModificationRow, TrackerBackend};
+use crate::types::{CatalystError, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Which tracked work a report covers
+#[derive(Debug, Clone, Default)]
+pub struct ReportScope {
+    /// Only this session
+    pub session: Option<String>,
+
+    /// Every session's edits from this time on
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Tracked work on a project
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PrReport {
+    /// Sessions the edits came from, oldest first
+    pub sessions: Vec<String>,
+
+    /// Tracked edits
+    pub edits: usize,
+
+    /// Edited files relative to the project, by category
+    pub files_by_category: BTreeMap<String, Vec<String>>,
+
+    /// Files whose latest version has async code without try/catch
+    pub risky_files: Vec<String>,
+
+    /// Files touching the database, routes, or outside APIs, by pattern;
+    /// worth a reviewer's attention but not necessarily wrong
+    pub patterns: BTreeMap<String, Vec<String>>,
+
+    /// Check hook runs over the same period
+    pub checks: Vec<CheckSummary>,
+}
+
+/// Runs of one check command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckSummary {
+    pub hook: String,
+    pub command: String,
+    pub runs: usize,
+    pub failed: usize,
+    pub last_status: CheckStatus,
+    /// Failing tests or checks in the last run, when counted
+    pub last_failures: Option<usize>,
+}
+
+impl PrReport {
+    /// Whether no edits were found
+    pub fn is_empty(&self) -> bool {
+        self.edits == 0
+    }
+
+    /// Distinct files edited
+    pub fn file_count(&self) -> usize {
+        self.files_by_category.values().map(Vec::len).sum()
+    }
+
+    /// Render as Markdown for a PR description or comment
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("## Summary of changes\n\n");
+        let _ = writeln!(
+            out,
+            "_Generated by catalyst from {} tracked Claude Code {} ({} edits)._\n",
+            self.sessions.len(),
+            if self.sessions.len() == 1 {
+                "session"
+            } else {
+                "sessions"
+            },
+            self.edits
+        );
+
+        let _ = writeln!(out, "### Files changed ({})\n", self.file_count());
+        out.push_str("| Category | Files |\n|----------|-------|\n");
+        for (category, files) in &self.files_by_category {
+            let files: Vec<String> = files.iter().map(|file| code(file)).collect();
+            let _ = writeln!(out, "| {} | {} |", category, files.join(", "));
+        }
+
+        out.push_str("\n### Risky patterns\n\n");
+        if self.risky_files.is_empty() && self.patterns.is_empty() {
+            out.push_str("None detected.\n");
+        }
+        for file in &self.risky_files {
+            let _ = writeln!(out, "- ⚠️ {}: async code without try/catch", code(file));
+        }
+        for (pattern, files) in &self.patterns {
+            let files: Vec<String> = files.iter().map(|file| code(file)).collect();
+            let _ = writeln!(out, "- {}: {}", pattern, files.join(", "));
+        }
+
+        out.push_str("\n### Checks\n\n");
+        if self.checks.is_empty() {
+            out.push_str("No check runs were recorded.\n");
+        } else {
+            out.push_str(
+                "| Check | Runs | Failed | Last result |\n|-------|------|--------|-------------|\n",
+            );
+            for check in &self.checks {
+                let last = match (check.last_status, check.last_failures) {
+                    (CheckStatus::Passed, _) => "✅ passed".to_string(),
+                    (CheckStatus::Failed, Some(count)) => format!("❌ {} failing", count),
+                    (CheckStatus::Failed, None) => "❌ failed".to_string(),
+                    (CheckStatus::Incomplete, _) => "⏱️ incomplete".to_string(),
+                };
+                let _ = writeln!(
+                    out,
+                    "| {}: {} | {} | {} | {} |",
+                    check.hook,
+                    code(&check.command),
+                    check.runs,
+                    check.failed,
+                    last
+                );
+            }
+        }
+        out
+    }
+}
+
+/// Inline code for a Markdown table cell
+fn code(text: &str) -> String {
+    format!("`{}`", text.replace('|', "\\|").replace('`', "'"))
+}
+
+/// Build the report for a project
+///
+/// # Arguments
+///
+/// * `project_dir` - Project whose edits are reported (and whose
+///   catalyst.toml picks the tracker backend)
+/// * `state_dir` - Tracker state directory
+/// * `scope` - Session or time range; the latest session when neither is set
+///
+/// # Errors
+///
+/// Returns the tracker store's or the check log's error if either can't be read
+pub fn build(project_dir: &Path, state_dir: &Path, scope: &ReportScope) -> Result<PrReport> {
+    let store = TrackerBackend::resolve(project_dir)?.open(state_dir)?;
+    let rows = store.query(&ModificationFilter {
+        session: scope.session.clone(),
+        since: scope.since,
+        until: None,
+    })?;
+
+    let project = dunce::canonicalize(project_dir).unwrap_or_else(|_| project_dir.to_path_buf());
+    let mut edits: Vec<(String, &ModificationRow)> = rows
+        .iter()
+        .filter_map(|row| relative_to(&row.file_path, &project).map(|path| (path, row)))
+        .collect();
+    if scope.session.is_none() && scope.since.is_none() {
+        if let Some((_, latest)) = edits.last() {
+            let session = latest.session_id.clone();
+            edits.retain(|(_, row)| row.session_id == session);
+        }
+    }
+    let Some((_, first)) = edits.first() else {
+        return Ok(PrReport::default());
+    };
+    let started = DateTime::parse_from_rfc3339(&first.timestamp)
+        .map(|time| time.with_timezone(&Utc))
+        .ok();
+
+    let mut report = PrReport {
+        edits: edits.len(),
+        ..PrReport::default()
+    };
+    // The latest edit of each file decides its category and patterns
+    let mut latest: BTreeMap<&str, &ModificationRow> = BTreeMap::new();
+    for (path, row) in &edits {
+        if !report.sessions.contains(&row.session_id) {
+            report.sessions.push(row.session_id.clone());
+        }
+        latest.insert(path, row);
+    }
+    for (path, row) in latest {
+        let path = path.to_string();
+        report
+            .files_by_category
+            .entry(row.category.clone())
+            .or_default()
+            .push(path.clone());
+        if row.has_async && !row.has_try_catch {
+            report.risky_files.push(path.clone());
+        }
+        for (flag, pattern) in [
+            (row.has_prisma, "Database access"),
+            (row.has_controller, "Route handlers"),
+            (row.has_api_call, "Outbound API calls"),
+        ] {
+            if flag {
+                report
+                    .patterns
+                    .entry(pattern.to_string())
+                    .or_default()
+                    .push(path.clone());
+            }
+        }
+    }
+
+    let sessions: BTreeSet<&String> = report.sessions.iter().collect();
+    let mut checks: BTreeMap<(String, String), CheckSummary> = BTreeMap::new();
+    for record in check_log::read(state_dir, &project, scope.since.or(started))? {
+        if record
+            .session_id
+            .as_ref()
+            .is_some_and(|session| !sessions.contains(session))
+        {
+            continue;
+        }
+        let summary = checks
+            .entry((record.hook.clone(), record.command.clone()))
+            .or_insert_with(|| CheckSummary {
+                hook: record.hook.clone(),
+                command: record.command.clone(),
+                runs: 0,
+                failed: 0,
+                last_status: record.status,
+                last_failures: None,
+            });
+        summary.runs += 1;
+        if record.status == CheckStatus::Failed {
+            summary.failed += 1;
+        }
+        summary.last_status = record.status;
+        summary.last_failures = record.failures;
+    }
+    report.checks = checks.into_values().collect();
+    Ok(report)
+}
+
+/// A tracked path relative to the project, or None if it's outside it
+fn relative_to(file_path: &str, project: &Path) -> Option<String> {
+    let path = PathBuf::from(file_path);
+    if path.is_relative() {
+        return Some(file_path.replace('\\', "/"));
+    }
+    let path = dunce::canonicalize(&path).unwrap_or(path);
+    path.strip_prefix(project)
+        .ok()
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+}
+
+/// Post Markdown as a comment on the current branch's pull request with
+/// `gh pr comment`
+///
+/// # Errors
+///
+/// Returns `BinaryNotFound` if gh isn't installed, or `InvalidConfig` with
+/// gh's message if the comment can't be posted (no PR for the branch, not
+/// logged in)
+pub fn post_comment(project_dir: &Path, markdown: &str) -> Result<()> {
+    let mut child = Command::new("gh")
+        .args(["pr", "comment", "--body-file", "-"])
+        .current_dir(project_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => CatalystError::BinaryNotFound(
+                "gh (GitHub CLI) is needed for --post; see https://cli.github.com".to_string(),
+            ),
+            _ => CatalystError::Io(e),
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(markdown.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(CatalystError::InvalidConfig(format!(
+            "gh pr comment failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check_log::CheckRecord;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn edit(session: &str, file: &Path, category: &str, timestamp: &str) -> ModificationRow {
+        ModificationRow {
+            category: category.to_string(),
+            has_async: category == "backend",
+            has_prisma: category == "database",
+            ..ModificationRow::test_edit(session, &file.to_string_lossy(), timestamp)
+        }
+    }
+
+    #[test]
+    fn test_build_latest_session_with_checks() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = dunce::canonicalize(temp_dir.path()).unwrap().join("app");
+        let state_dir = temp_dir.path().join("state");
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::write(
+            project.join("catalyst.toml"),
+            "[tracker]\nbackend = \"jsonl\"\n",
+        )
+        .unwrap();
+
+        let mut store = TrackerBackend::Jsonl.open(&state_dir).unwrap();
+        let api = project.join("src").join("api.ts");
+        let schema = project.join("src").join("schema.ts");
+        for row in [
+            edit("old", &api, "backend", "2026-03-01T10:00:00+00:00"),
+            edit("new", &api, "backend", "2026-03-02T10:00:00+00:00"),
+            edit("new", &schema, "database", "2026-03-02T10:05:00+00:00"),
+            edit(
+                "new",
+                Path::new("/elsewhere/x.ts"),
+                "other",
+                "2026-03-02T10:06:00+00:00",
+            ),
+        ] {
+            store.record(&row).unwrap();
+        }
+
+        for (session, status, failures) in [
+            ("old", CheckStatus::Passed, None),
+            ("new", CheckStatus::Failed, Some(2)),
+            ("new", CheckStatus::Passed, None),
+        ] {
+            let mut record =
+                CheckRecord::now(Some(session), &project, "test-runner", "npm test", status);
+            record.failures = failures;
+            check_log::append(&state_dir, &record).unwrap();
+        }
+
+        let report = build(&project, &state_dir, &ReportScope::default()).unwrap();
+        assert_eq!(report.sessions, vec!["new"]);
+        assert_eq!(report.edits, 2);
+        assert_eq!(report.files_by_category["database"], vec!["src/schema.ts"]);
+        assert_eq!(report.risky_files, vec!["src/api.ts"]);
+        assert_eq!(report.patterns["Database access"], vec!["src/schema.ts"]);
+        assert_eq!(report.checks.len(), 1);
+        assert_eq!(report.checks[0].runs, 2);
+        assert_eq!(report.checks[0].failed, 1);
+        assert_eq!(report.checks[0].last_status, CheckStatus::Passed);
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| database | `src/schema.ts` |"));
+        assert!(markdown.contains("- ⚠️ `src/api.ts`: async code without try/catch"));
+        assert!(markdown.contains("| test-runner: `npm test` | 2 | 1 | ✅ passed |"));
+
+        let all = build(
+            &project,
+            &state_dir,
+            &ReportScope {
+                since: Some(
+                    DateTime::parse_from_rfc3339("2026-01-01T00:00:00+00:00")
+                        .unwrap()
+                        .with_timezone(&Utc),
+                ),
+                ..ReportScope::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(all.sessions, vec!["old", "new"]);
+        assert_eq!(all.edits, 3);
+    }
+
+    #[test]
+    fn test_empty_report_renders() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("catalyst.toml"),
+            "[tracker]\nbackend = \"jsonl\"\n",
+        )
+        .unwrap();
+        let report = build(
+            temp_dir.path(),
+            &temp_dir.path().join("state"),
+            &ReportScope::default(),
+        )
+        .unwrap();
+        assert!(report.is_empty());
+        assert!(report
+            .to_markdown()
+            .contains("No check runs were recorded."));
+    }
+}
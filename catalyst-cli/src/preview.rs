@@ -0,0 +1,361 @@
+//! `--diff`: preview the files a command would change
+//!
+//! [`preview_changes`] runs a command against a scratch copy of the
+//! project's Catalyst files (`.claude`, catalyst.toml, catalyst.lock, the
+//! version and hash files), then compares the copy with the project and
+//! renders each difference as a unified diff. Nothing in the project is
+//! written.
+//!
+//! Skills linked from the skill library are left out of the copy: changing
+//! them would change the shared library itself.
+
+use crate::types::{
+    CatalystError, Result, CLAUDE_DIR, CONFIG_FILE, HASHES_FILE, LOCK_FILE, VERSION_FILE,
+};
+use colored::Colorize;
+use serde::Serialize;
+use similar::TextDiff;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Paths, relative to the project, that a scratch copy holds
+const PREVIEWED_PATHS: &[&str] = &[
+    CLAUDE_DIR,
+    CONFIG_FILE,
+    LOCK_FILE,
+    VERSION_FILE,
+    HASHES_FILE,
+];
+
+/// How a file would change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Removed,
+}
+
+/// One file a command would change
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDiff {
+    /// Relative to the project, with `/` separators
+    pub path: String,
+    pub kind: ChangeKind,
+    /// Unified diff, or a one-line note for binary files and mode changes
+    pub diff: String,
+}
+
+/// Run `command` on a scratch copy of `target_dir` and diff the result
+///
+/// `command` receives the scratch project directory. Absolute paths to it
+/// in rewritten files (such as the project directory in wrapper scripts)
+/// are mapped back to `target_dir` before comparing.
+///
+/// # Returns
+///
+/// Returns the command's result and the files it changed, sorted by path
+///
+/// # Errors
+///
+/// Returns `Io` if the scratch copy can't be made or read, or the
+/// command's error
+pub fn preview_changes<T>(
+    target_dir: &Path,
+    command: impl FnOnce(&Path) -> Result<T>,
+) -> Result<(T, Vec<FileDiff>)> {
+    let scratch_root = tempfile::tempdir().map_err(CatalystError::Io)?;
+    // Same directory name, so anything derived from it renders the same
+    let name = dunce::canonicalize(target_dir)
+        .ok()
+        .and_then(|dir| dir.file_name().map(|name| name.to_os_string()))
+        .unwrap_or_else(|| "project".into());
+    let scratch = scratch_root.path().join(name);
+    fs::create_dir_all(&scratch).map_err(CatalystError::Io)?;
+    for path in PREVIEWED_PATHS {
+        copy_tree(&target_dir.join(path), &scratch.join(path))?;
+    }
+
+    let output = command(&scratch)?;
+
+    let before = read_tree(target_dir)?;
+    let after = read_tree(&scratch)?;
+    let scratch_dir = canonical_string(&scratch);
+    let target = canonical_string(target_dir);
+    let mut diffs = Vec::new();
+    for (path, old) in &before {
+        match after.get(path) {
+            None => diffs.push(file_diff(path, Some(old), None)),
+            Some(new) => {
+                let new = new.with_dir_replaced(&scratch_dir, &target);
+                if *old != new {
+                    diffs.push(file_diff(path, Some(old), Some(&new)));
+                }
+            }
+        }
+    }
+    for (path, new) in &after {
+        if !before.contains_key(path) {
+            let new = new.with_dir_replaced(&scratch_dir, &target);
+            diffs.push(file_diff(path, None, Some(&new)));
+        }
+    }
+    diffs.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok((output, diffs))
+}
+
+/// Diff a file's current content with what would replace it
+///
+/// # Arguments
+///
+/// * `path` - Name shown in the diff headers
+/// * `old` - Current content, or None if the file doesn't exist yet
+/// * `new` - New content
+pub fn diff_file(path: &str, old: Option<&str>, new: &str) -> Option<FileDiff> {
+    if old == Some(new) {
+        return None;
+    }
+    let old = old.map(|text| Snapshot {
+        content: text.as_bytes().to_vec(),
+        mode: None,
+    });
+    let new = Snapshot {
+        content: new.as_bytes().to_vec(),
+        mode: None,
+    };
+    Some(file_diff(path, old.as_ref(), Some(&new)))
+}
+
+/// Render diffs for the terminal
+pub fn render(diffs: &[FileDiff], use_color: bool) -> String {
+    if diffs.is_empty() {
+        return "No files would change\n".to_string();
+    }
+    let mut out = String::new();
+    for diff in diffs {
+        for line in diff.diff.lines() {
+            let line = if !use_color {
+                line.normal()
+            } else if line.starts_with("+++") || line.starts_with("---") {
+                line.bold()
+            } else if line.starts_with('+') {
+                line.green()
+            } else if line.starts_with('-') {
+                line.red()
+            } else if line.starts_with("@@") {
+                line.cyan()
+            } else {
+                line.normal()
+            };
+            out.push_str(&format!("{}\n", line));
+        }
+    }
+    let files = if diffs.len() == 1 { "file" } else { "files" };
+    out.push_str(&format!("\n{} {} would change\n", diffs.len(), files));
+    out
+}
+
+/// Content and (on Unix) permission bits of one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Snapshot {
+    content: Vec<u8>,
+    mode: Option<u32>,
+}
+
+impl Snapshot {
+    fn with_dir_replaced(&self, from: &str, to: &str) -> Snapshot {
+        match std::str::from_utf8(&self.content) {
+            Ok(text) if !from.is_empty() && text.contains(from) => Snapshot {
+                content: text.replace(from, to).into_bytes(),
+                mode: self.mode,
+            },
+            _ => self.clone(),
+        }
+    }
+}
+
+fn file_diff(path: &str, old: Option<&Snapshot>, new: Option<&Snapshot>) -> FileDiff {
+    let kind = match (old, new) {
+        (None, _) => ChangeKind::Added,
+        (_, None) => ChangeKind::Removed,
+        _ => ChangeKind::Modified,
+    };
+    let old_name = if old.is_some() {
+        format!("a/{}", path)
+    } else {
+        "/dev/null".to_string()
+    };
+    let new_name = if new.is_some() {
+        format!("b/{}", path)
+    } else {
+        "/dev/null".to_string()
+    };
+    let old_text = old.map(|s| std::str::from_utf8(&s.content));
+    let new_text = new.map(|s| std::str::from_utf8(&s.content));
+
+    let mut diff = String::new();
+    if let (
+        Some(Snapshot {
+            mode: Some(from), ..
+        }),
+        Some(Snapshot { mode: Some(to), .. }),
+    ) = (old, new)
+    {
+        if from != to {
+            diff.push_str(&format!(
+                "mode {}: {:o} -> {:o}\n",
+                path,
+                from & 0o7777,
+                to & 0o7777
+            ));
+        }
+    }
+    if old.map(|s| &s.content) != new.map(|s| &s.content) {
+        match (old_text.unwrap_or(Ok("")), new_text.unwrap_or(Ok(""))) {
+            (Ok(old_text), Ok(new_text)) => diff.push_str(
+                &TextDiff::from_lines(old_text, new_text)
+                    .unified_diff()
+                    .context_radius(3)
+                    .header(&old_name, &new_name)
+                    .to_string(),
+            ),
+            _ => diff.push_str(&format!(
+                "Binary files {} and {} differ\n",
+                old_name, new_name
+            )),
+        }
+    }
+    FileDiff {
+        path: path.to_string(),
+        kind,
+        diff,
+    }
+}
+
+/// Copy `source` (a file or directory) to `destination`, skipping links
+fn copy_tree(source: &Path, destination: &Path) -> Result<()> {
+    if !source.exists() {
+        return Ok(());
+    }
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(|e| CatalystError::Io(e.into()))?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .expect("walkdir yields paths under its root");
+        // A lone file has an empty relative path, and joining one adds a `/`
+        let target = if relative.as_os_str().is_empty() {
+            destination.to_path_buf()
+        } else {
+            destination.join(relative)
+        };
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target).map_err(|source| {
+                CatalystError::DirectoryCreationFailed {
+                    path: target.clone(),
+                    source,
+                }
+            })?;
+        } else if entry.file_type().is_file() {
+            fs::copy(entry.path(), &target).map_err(|source| CatalystError::FileWriteFailed {
+                path: target.clone(),
+                source,
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Every regular file under the previewed paths of `root`
+fn read_tree(root: &Path) -> Result<BTreeMap<String, Snapshot>> {
+    let mut files = BTreeMap::new();
+    for path in PREVIEWED_PATHS {
+        let start = root.join(path);
+        if !start.exists() {
+            continue;
+        }
+        for entry in WalkDir::new(&start) {
+            let entry = entry.map_err(|e| CatalystError::Io(e.into()))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let content =
+                fs::read(entry.path()).map_err(|source| CatalystError::FileReadFailed {
+                    path: entry.path().to_path_buf(),
+                    source,
+                })?;
+            #[cfg(unix)]
+            let mode = {
+                use std::os::unix::fs::PermissionsExt;
+                entry.metadata().ok().map(|m| m.permissions().mode())
+            };
+            #[cfg(not(unix))]
+            let mode = None;
+            let relative = entry
+                .path()
+                .strip_prefix(root)
+                .expect("walkdir yields paths under its root");
+            files.insert(
+                relative.to_string_lossy().replace('\\', "/"),
+                Snapshot { content, mode },
+            );
+        }
+    }
+    Ok(files)
+}
+
+fn canonical_string(dir: &Path) -> String {
+    dunce::canonicalize(dir)
+        .unwrap_or_else(|_| PathBuf::from(dir))
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SETTINGS_FILE;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_preview_changes_diffs_without_writing() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir_all(target.join(CLAUDE_DIR)).unwrap();
+        fs::write(target.join(SETTINGS_FILE), "{\n  \"hooks\": {}\n}\n").unwrap();
+        fs::write(target.join(VERSION_FILE), "0.1.0").unwrap();
+
+        let (_, diffs) = preview_changes(target, |scratch| {
+            let dir = scratch.display().to_string();
+            fs::write(
+                scratch.join(SETTINGS_FILE),
+                format!("{{\n  \"dir\": \"{}\"\n}}\n", dir),
+            )
+            .map_err(CatalystError::Io)?;
+            fs::remove_file(scratch.join(VERSION_FILE)).map_err(CatalystError::Io)?;
+            fs::write(scratch.join(LOCK_FILE), "new").map_err(CatalystError::Io)?;
+            Ok(())
+        })
+        .unwrap();
+
+        let kinds: Vec<(&str, ChangeKind)> =
+            diffs.iter().map(|d| (d.path.as_str(), d.kind)).collect();
+        assert_eq!(
+            kinds,
+            [
+                (VERSION_FILE, ChangeKind::Removed),
+                (SETTINGS_FILE, ChangeKind::Modified),
+                (LOCK_FILE, ChangeKind::Added),
+            ]
+        );
+        // The scratch directory is shown as the project directory
+        let settings = &diffs[1].diff;
+        assert!(settings.contains(&format!("+  \"dir\": \"{}\"", canonical_string(target))));
+        assert!(settings.contains("-  \"hooks\": {}"));
+        // Nothing was written to the project
+        assert!(target.join(VERSION_FILE).exists());
+        assert!(!target.join(LOCK_FILE).exists());
+    }
+}
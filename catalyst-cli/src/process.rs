@@ -0,0 +1,130 @@
+//! Child process helpers shared by commands that run user-supplied programs
+//! (skill scripts, health checks, validators)
+
+use std::env;
+use std::ffi::OsStr;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How [`wait_until`] finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitOutcome {
+    /// The child exited on its own
+    Exited(ExitStatus),
+    /// The deadline passed and the child was killed
+    TimedOut,
+    /// The cancel flag was set and the child was killed
+    Cancelled,
+}
+
+/// Wait for a child to exit, killing it once `timeout` has passed
+///
+/// # Returns
+///
+/// Returns the exit status, or None if the child was killed for running too long
+///
+/// # Errors
+///
+/// Returns the underlying I/O error if the child's status can't be queried
+pub fn wait_timeout(child: &mut Child, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+    let never = AtomicBool::new(false);
+    match wait_until(child, Some(Instant::now() + timeout), &never)? {
+        WaitOutcome::Exited(status) => Ok(Some(status)),
+        WaitOutcome::TimedOut | WaitOutcome::Cancelled => Ok(None),
+    }
+}
+
+/// Wait for a child to exit, killing it at `deadline` or when `cancel` is set
+///
+/// # Arguments
+///
+/// * `child` - Running child process
+/// * `deadline` - When to give up, or None to wait indefinitely
+/// * `cancel` - Flag another thread (typically a signal handler) sets to
+///   stop the child early
+///
+/// # Errors
+///
+/// Returns the underlying I/O error if the child's status can't be queried
+pub fn wait_until(
+    child: &mut Child,
+    deadline: Option<Instant>,
+    cancel: &AtomicBool,
+) -> io::Result<WaitOutcome> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(WaitOutcome::Exited(status));
+        }
+        let outcome = if cancel.load(Ordering::SeqCst) {
+            Some(WaitOutcome::Cancelled)
+        } else if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            Some(WaitOutcome::TimedOut)
+        } else {
+            None
+        };
+        if let Some(outcome) = outcome {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(outcome);
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Run a command to completion with stdout and stderr captured together
+///
+/// Output goes to a temporary file rather than a pipe, so a chatty program
+/// can't fill a pipe buffer and stall. Stdin is closed.
+///
+/// # Returns
+///
+/// Returns the exit status and combined output, or None if the command was
+/// killed after `timeout`
+///
+/// # Errors
+///
+/// Returns the I/O error if the capture file can't be created, the command
+/// can't be started, or it can't be waited on
+pub fn run_captured(
+    command: &mut Command,
+    timeout: Duration,
+) -> io::Result<Option<(ExitStatus, String)>> {
+    let mut output_file = tempfile::tempfile()?;
+    let stdout = output_file.try_clone()?;
+    let stderr = output_file.try_clone()?;
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr)
+        .spawn()?;
+    let Some(status) = wait_timeout(&mut child, timeout)? else {
+        return Ok(None);
+    };
+
+    let mut bytes = Vec::new();
+    output_file.seek(SeekFrom::Start(0))?;
+    output_file.read_to_end(&mut bytes)?;
+    Ok(Some((status, String::from_utf8_lossy(&bytes).into_owned())))
+}
+
+/// Find an executable on a PATH-style list of directories
+///
+/// On Windows a name without an extension also matches `.exe`, `.cmd`, and
+/// `.bat` files, as the shell would.
+pub fn find_on_path(name: &str, path: &OsStr) -> Option<PathBuf> {
+    let file_names: Vec<String> = if cfg!(windows) && Path::new(name).extension().is_none() {
+        ["exe", "cmd", "bat"]
+            .iter()
+            .map(|ext| format!("{}.{}", name, ext))
+            .collect()
+    } else {
+        vec![name.to_string()]
+    };
+    env::split_paths(path)
+        .flat_map(|dir| file_names.iter().map(move |file| dir.join(file)))
+        .find(|candidate| candidate.is_file())
+}
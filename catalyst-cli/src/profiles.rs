@@ -0,0 +1,265 @@
+//! Init profiles (`catalyst init --profile <name>`)
+//!
+//! A profile bundles the skills, hooks, and settings.json defaults that
+//! suit a kind of project, so `catalyst init --profile rust` sets up a Rust
+//! project in one step. Built-in profiles are embedded from
+//! `resources/profiles.toml`; users can add their own, or replace a
+//! built-in one, with `~/.config/catalyst/profiles/<name>.toml`:
+//!
+//! ```toml
+//! description = "Our service template"
+//! skills = ["skill-developer", "backend-dev-guidelines"]
+//! install_tracker = false
+//!
+//! [settings.permissions]
+//! allow = ["Bash(make test:*)"]
+//! ```
+
+use crate::types::{CatalystError, Result, AVAILABLE_SKILLS, SETTINGS_FILE};
+use catalyst_core::settings_schema;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BUILTIN_PROFILES: &str = include_str!("../resources/profiles.toml");
+
+/// Where a profile was defined
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileSource {
+    /// Embedded in the CLI
+    BuiltIn,
+    /// A file in the user profile directory
+    User(PathBuf),
+}
+
+/// A named set of init choices
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitProfile {
+    pub name: String,
+    pub description: String,
+    /// Skills to install
+    pub skills: Vec<String>,
+    /// Whether to install the skill-activation-prompt hook
+    pub install_hooks: bool,
+    /// Whether to install the file-change-tracker hook
+    pub install_tracker: bool,
+    /// Top-level settings.json keys written next to the hooks
+    pub settings: serde_json::Map<String, serde_json::Value>,
+    pub source: ProfileSource,
+}
+
+/// A profile as written in TOML
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileFile {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    skills: Vec<String>,
+    #[serde(default = "enabled")]
+    install_hooks: bool,
+    #[serde(default = "enabled")]
+    install_tracker: bool,
+    #[serde(default)]
+    settings: toml::Table,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BuiltInProfiles {
+    profiles: BTreeMap<String, ProfileFile>,
+}
+
+impl ProfileFile {
+    /// Check the profile and convert it
+    fn into_profile(self, name: &str, source: ProfileSource) -> Result<InitProfile> {
+        let invalid =
+            |msg: String| CatalystError::InvalidConfig(format!("profile '{}': {}", name, msg));
+
+        if let Some(skill) = self
+            .skills
+            .iter()
+            .find(|skill| !AVAILABLE_SKILLS.contains(&skill.as_str()))
+        {
+            return Err(invalid(format!(
+                "unknown skill '{}' (available: {})",
+                skill,
+                AVAILABLE_SKILLS.join(", ")
+            )));
+        }
+
+        let settings = match serde_json::to_value(&self.settings) {
+            Ok(serde_json::Value::Object(settings)) => settings,
+            Ok(_) => serde_json::Map::new(),
+            Err(e) => return Err(invalid(e.to_string())),
+        };
+        // init owns the hook entries
+        if settings.contains_key("hooks") {
+            return Err(invalid(format!(
+                "settings can't set 'hooks'; {} hooks come from install_hooks and install_tracker",
+                SETTINGS_FILE
+            )));
+        }
+        if let Some(error) = settings_schema::validate(&serde_json::Value::Object(settings.clone()))
+            .into_iter()
+            .next()
+        {
+            return Err(invalid(format!("settings.{}", error)));
+        }
+
+        Ok(InitProfile {
+            name: name.to_string(),
+            description: self.description,
+            skills: self.skills,
+            install_hooks: self.install_hooks,
+            install_tracker: self.install_tracker,
+            settings,
+            source,
+        })
+    }
+}
+
+/// Directory holding user-defined profiles
+///
+/// `$XDG_CONFIG_HOME/catalyst/profiles` when XDG_CONFIG_HOME is set,
+/// otherwise `~/.config/catalyst/profiles` on every platform.
+pub fn user_profile_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+        .map(|config| config.join("catalyst").join("profiles"))
+}
+
+/// Profiles embedded in the CLI, by name
+fn builtin_profiles() -> Result<Vec<InitProfile>> {
+    let file: BuiltInProfiles = toml::from_str(BUILTIN_PROFILES)
+        .map_err(|e| CatalystError::InvalidConfig(format!("built-in profiles: {}", e)))?;
+    file.profiles
+        .into_iter()
+        .map(|(name, profile)| profile.into_profile(&name, ProfileSource::BuiltIn))
+        .collect()
+}
+
+/// Load one user profile file, named after its file stem
+fn load_user_profile(path: &Path) -> Result<InitProfile> {
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    let content = fs::read_to_string(path).map_err(|e| CatalystError::FileReadFailed {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    let profile: ProfileFile = toml::from_str(&content)
+        .map_err(|e| CatalystError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+    profile.into_profile(name, ProfileSource::User(path.to_path_buf()))
+}
+
+/// List built-in and user profiles, sorted by name
+///
+/// A user profile replaces the built-in profile with the same name.
+///
+/// # Arguments
+///
+/// * `user_dir` - Directory of user profiles (see [`user_profile_dir`]); a
+///   missing directory means no user profiles
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if a profile names an unknown skill, sets
+/// `hooks`, or has settings that don't match the settings.json schema, and
+/// `FileReadFailed` if a user profile can't be read
+pub fn list_profiles(user_dir: Option<&Path>) -> Result<Vec<InitProfile>> {
+    let mut profiles: BTreeMap<String, InitProfile> = builtin_profiles()?
+        .into_iter()
+        .map(|profile| (profile.name.clone(), profile))
+        .collect();
+
+    if let Some(entries) = user_dir.and_then(|dir| fs::read_dir(dir).ok()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "toml") && path.is_file() {
+                let profile = load_user_profile(&path)?;
+                profiles.insert(profile.name.clone(), profile);
+            }
+        }
+    }
+
+    Ok(profiles.into_values().collect())
+}
+
+/// Find a profile by name
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` listing the available profiles if none is
+/// called `name`, plus the errors of [`list_profiles`]
+pub fn find_profile(name: &str, user_dir: Option<&Path>) -> Result<InitProfile> {
+    let profiles = list_profiles(user_dir)?;
+    let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+    let available = names.join(", ");
+    profiles
+        .iter()
+        .find(|profile| profile.name == name)
+        .cloned()
+        .ok_or_else(|| {
+            CatalystError::InvalidConfig(format!(
+                "Unknown profile '{}' (available: {})",
+                name, available
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_builtin_profiles_are_valid() {
+        let profiles = list_profiles(None).unwrap();
+        let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["ci", "fullstack-ts", "minimal", "python", "rust"]);
+
+        let rust = find_profile("rust", None).unwrap();
+        assert!(rust.skills.contains(&"rust-developer".to_string()));
+        assert!(rust.settings["permissions"]["allow"].is_array());
+        assert!(find_profile("haskell", None)
+            .unwrap_err()
+            .to_string()
+            .contains("available: ci, fullstack-ts, minimal"));
+    }
+
+    #[test]
+    fn test_user_profiles_override_and_validate() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(
+            dir.join("rust.toml"),
+            "skills = [\"rust-developer\"]\ninstall_tracker = false\n",
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let rust = find_profile("rust", Some(dir)).unwrap();
+        assert_eq!(rust.skills, ["rust-developer"]);
+        assert!(rust.install_hooks);
+        assert!(!rust.install_tracker);
+        assert!(rust.settings.is_empty());
+        assert_eq!(rust.source, ProfileSource::User(dir.join("rust.toml")));
+
+        fs::write(
+            dir.join("broken.toml"),
+            "[settings.permissions]\nallow = \"Edit\"\n",
+        )
+        .unwrap();
+        let error = list_profiles(Some(dir)).unwrap_err().to_string();
+        assert!(error.contains("settings.permissions.allow: expected array"));
+    }
+}
@@ -0,0 +1,354 @@
+//! Progress and warning output from library operations
+//!
+//! init, update, and status don't print while they work; they send
+//! [`ProgressEvent`]s to the process-wide [`ProgressReporter`]. The
+//! `catalyst` binary picks the reporter from `--display`, and other
+//! frontends install their own with [`set_reporter`] before calling into
+//! the library. Three reporters are provided:
+//!
+//! - [`TerminalReporter`] (the default): a progress bar when stdout is a
+//!   terminal, otherwise one line per item, with warnings on stderr
+//! - [`QuietReporter`]: discards everything; results and warnings are still
+//!   in the returned reports
+//! - [`JsonLinesReporter`]: one JSON object per event, for example
+//!   `{"event":"item_done","item":"rust-developer","message":"Installed rust-developer"}`
+//!
+//! Like `--timings` (see [`crate::timings`]), the reporter is process state
+//! rather than a parameter, so it reaches code running on worker threads.
+//!
+//! The [`Verbosity`] (`-q` and `-v`) is process state too. Only at
+//! [`Verbosity::Verbose`] does [`detail`] send anything: per-file writes,
+//! hash computations, and lock acquisition.
+
+use indicatif::{ProgressBar, ProgressStyle};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Something a long-running operation wants its user to know
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent<'a> {
+    /// A batch of `total` items is starting
+    Started { total: usize },
+    /// Work on one item began
+    ItemStarted { item: &'a str, message: String },
+    /// One item finished successfully
+    ItemDone { item: &'a str, message: String },
+    /// One item failed; the batch carries on
+    ItemFailed { item: &'a str, message: String },
+    /// The batch is over
+    Finished { message: String },
+    /// A non-fatal problem, outside of any batch or within one
+    Warning { message: String },
+    /// A step of the work itself, only reported when verbose
+    Detail { message: String },
+}
+
+/// How much the CLI says while it works
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Errors only (`--quiet`)
+    Quiet,
+    #[default]
+    Normal,
+    /// Every file, hash, and lock too (`--verbose`)
+    Verbose,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// Receives progress events; implementations decide how (or whether) to
+/// show them
+///
+/// Events may arrive from several threads at once when skills are
+/// installed in parallel.
+pub trait ProgressReporter: Send + Sync {
+    fn report(&self, event: ProgressEvent<'_>);
+}
+
+static REPORTER: Lazy<RwLock<Arc<dyn ProgressReporter>>> =
+    Lazy::new(|| RwLock::new(Arc::new(TerminalReporter::new())));
+
+/// Send progress events to `reporter` for the rest of the process
+pub fn set_reporter(reporter: Arc<dyn ProgressReporter>) {
+    *REPORTER.write().unwrap_or_else(|e| e.into_inner()) = reporter;
+}
+
+/// The current reporter
+pub fn reporter() -> Arc<dyn ProgressReporter> {
+    REPORTER.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Set the verbosity for the rest of the process
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// The current verbosity
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Normal,
+    }
+}
+
+/// Whether `--quiet` was given
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
+
+/// Report a step of the work when verbose; the message is only built then
+pub fn detail(message: impl FnOnce() -> String) {
+    if verbosity() == Verbosity::Verbose {
+        reporter().report(ProgressEvent::Detail { message: message() });
+    }
+}
+
+/// Report a non-fatal problem to the current reporter
+pub fn warn(message: impl Into<String>) {
+    reporter().report(ProgressEvent::Warning {
+        message: message.into(),
+    });
+}
+
+/// Progress for people at a terminal
+#[derive(Debug)]
+pub struct TerminalReporter {
+    show_bar: bool,
+    bar: Mutex<Option<ProgressBar>>,
+}
+
+impl TerminalReporter {
+    /// Shows a progress bar when stdout is a terminal
+    pub fn new() -> Self {
+        Self::with_bar(io::stdout().is_terminal())
+    }
+
+    /// Never shows a progress bar, e.g. for screen readers
+    pub fn plain() -> Self {
+        Self::with_bar(false)
+    }
+
+    fn with_bar(show_bar: bool) -> Self {
+        Self {
+            show_bar,
+            bar: Mutex::new(None),
+        }
+    }
+}
+
+impl Default for TerminalReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressReporter for TerminalReporter {
+    fn report(&self, event: ProgressEvent<'_>) {
+        let mut bar = self.bar.lock().unwrap_or_else(|e| e.into_inner());
+        match event {
+            ProgressEvent::Started { total } => {
+                if self.show_bar {
+                    let pb = ProgressBar::new(total as u64);
+                    pb.set_style(
+                        ProgressStyle::default_bar()
+                            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
+                            // Template is static and valid, but use fallback as defensive programming
+                            .unwrap_or_else(|_| ProgressStyle::default_bar())
+                            .progress_chars("━━╸"),
+                    );
+                    *bar = Some(pb);
+                }
+            }
+            ProgressEvent::ItemStarted { message, .. } => {
+                if let Some(pb) = bar.as_ref() {
+                    pb.set_message(format!("{}...", message));
+                }
+            }
+            ProgressEvent::ItemDone { message, .. } => match bar.as_ref() {
+                Some(pb) => pb.inc(1),
+                None => println!("  ✓ {}", message),
+            },
+            ProgressEvent::ItemFailed { message, .. } => match bar.as_ref() {
+                Some(pb) => {
+                    pb.println(format!("⚠️  {}", message));
+                    pb.inc(1);
+                }
+                None => eprintln!("⚠️  {}", message),
+            },
+            ProgressEvent::Finished { message } => {
+                if let Some(pb) = bar.take() {
+                    pb.finish_with_message(format!("✅ {}", message));
+                }
+            }
+            ProgressEvent::Warning { message } => match bar.as_ref() {
+                Some(pb) => pb.println(format!("⚠️  {}", message)),
+                None => eprintln!("⚠️  {}", message),
+            },
+            ProgressEvent::Detail { message } => match bar.as_ref() {
+                Some(pb) => pb.println(format!("   {}", message)),
+                None => eprintln!("   {}", message),
+            },
+        }
+    }
+}
+
+/// Discards all events
+#[derive(Debug, Default, Clone, Copy)]
+pub struct QuietReporter;
+
+impl ProgressReporter for QuietReporter {
+    fn report(&self, _event: ProgressEvent<'_>) {}
+}
+
+/// Writes each event as a line of JSON
+#[derive(Debug)]
+pub struct JsonLinesReporter<W> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonLinesReporter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// Give back the writer, e.g. to read what was written
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl JsonLinesReporter<io::Stderr> {
+    /// Writes to stderr, leaving stdout to the command's own JSON report
+    pub fn stderr() -> Self {
+        Self::new(io::stderr())
+    }
+}
+
+impl<W: Write + Send> ProgressReporter for JsonLinesReporter<W> {
+    fn report(&self, event: ProgressEvent<'_>) {
+        let Ok(line) = serde_json::to_string(&event) else {
+            return;
+        };
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        // Progress is best effort; a closed pipe mustn't fail the operation
+        let _ = writeln!(writer, "{}", line);
+        let _ = writer.flush();
+    }
+}
+
+/// Keeps the messages of detail events
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct DetailRecorder(pub Mutex<Vec<String>>);
+
+#[cfg(test)]
+impl ProgressReporter for DetailRecorder {
+    fn report(&self, event: ProgressEvent<'_>) {
+        if let ProgressEvent::Detail { message } = event {
+            self.0.lock().unwrap().push(message);
+        }
+    }
+}
+
+/// Calls a closure on every event; tests use it to act partway through an
+/// operation
+#[cfg(all(test, feature = "tokio"))]
+pub(crate) struct EventHook<F>(pub F);
+
+#[cfg(all(test, feature = "tokio"))]
+impl<F: Fn() + Send + Sync> ProgressReporter for EventHook<F> {
+    fn report(&self, _event: ProgressEvent<'_>) {
+        (self.0)()
+    }
+}
+
+/// Run `work` with `reporter` and `verbosity` installed, then put back the
+/// defaults
+///
+/// Both are process state, so tests that change them take turns here
+/// instead of seeing each other's settings.
+#[cfg(test)]
+pub(crate) fn with_test_reporter<T>(
+    reporter: Arc<dyn ProgressReporter>,
+    verbosity: Verbosity,
+    work: impl FnOnce() -> T,
+) -> T {
+    static TURN: Mutex<()> = Mutex::new(());
+
+    struct Restore;
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            set_verbosity(Verbosity::Normal);
+            set_reporter(Arc::new(TerminalReporter::plain()));
+        }
+    }
+
+    let _turn = TURN.lock().unwrap_or_else(|e| e.into_inner());
+    let _restore = Restore;
+    set_reporter(reporter);
+    set_verbosity(verbosity);
+    work()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_lines_reporter_writes_one_object_per_event() {
+        let reporter = JsonLinesReporter::new(Vec::new());
+        reporter.report(ProgressEvent::Started { total: 2 });
+        reporter.report(ProgressEvent::ItemFailed {
+            item: "demo",
+            message: "Failed to install skill 'demo': boom".to_string(),
+        });
+        reporter.report(ProgressEvent::Warning {
+            message: "careful".to_string(),
+        });
+
+        let output = String::from_utf8(reporter.into_inner()).unwrap();
+        let events: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["event"], "started");
+        assert_eq!(events[0]["total"], 2);
+        assert_eq!(events[1]["event"], "item_failed");
+        assert_eq!(events[1]["item"], "demo");
+        assert_eq!(events[2]["message"], "careful");
+    }
+
+    #[test]
+    fn test_detail_only_reported_when_verbose() {
+        let recorder = Arc::new(DetailRecorder::default());
+        with_test_reporter(recorder.clone(), Verbosity::Normal, || {
+            for level in [Verbosity::Quiet, Verbosity::Normal, Verbosity::Verbose] {
+                set_verbosity(level);
+                assert_eq!(verbosity(), level);
+                assert_eq!(is_quiet(), level == Verbosity::Quiet);
+                detail(|| format!("progress-test {:?}", level));
+            }
+        });
+        assert_eq!(verbosity(), Verbosity::Normal);
+
+        // Other tests may report details while this one runs
+        let ours: Vec<String> = recorder
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| m.starts_with("progress-test"))
+            .cloned()
+            .collect();
+        assert_eq!(ours, vec!["progress-test Verbose".to_string()]);
+    }
+}
@@ -0,0 +1,90 @@
+//! Project directory resolution for `--path` arguments
+//!
+//! Commands operate on the project root, the directory that *contains*
+//! `.claude`. Users often pass `-p .claude` or run catalyst from somewhere
+//! inside `.claude/`, which used to produce errors like ".claude directory
+//! not found at .../.claude/.claude". The target is normalized here so every
+//! command sees the project root.
+
+use crate::types::CLAUDE_DIR;
+use std::env;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// Resolve a command's `--path` argument to the project root
+///
+/// Without a path the current directory is used. Either way, a directory
+/// that is (or is inside) `.claude` is replaced by the directory containing
+/// it.
+pub fn resolve_target_dir(path: Option<PathBuf>) -> PathBuf {
+    let path = path.unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    normalize_project_dir(&path)
+}
+
+/// Map a path at or below `.claude` to its project root
+///
+/// Paths outside any `.claude` directory are returned unchanged. Relative
+/// paths such as `.` are made absolute first so running from inside
+/// `.claude/hooks` is detected too.
+///
+/// # Arguments
+///
+/// * `path` - Directory given by the user
+///
+/// # Returns
+///
+/// The parent of the innermost `.claude` component, or `path` itself
+pub fn normalize_project_dir(path: &Path) -> PathBuf {
+    if let Some(root) = claude_dir_parent(path) {
+        return root;
+    }
+    match std::path::absolute(path) {
+        Ok(absolute) => claude_dir_parent(&absolute).unwrap_or_else(|| path.to_path_buf()),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+fn claude_dir_parent(path: &Path) -> Option<PathBuf> {
+    let claude_dir = path
+        .ancestors()
+        .find(|ancestor| ancestor.file_name() == Some(OsStr::new(CLAUDE_DIR)))?;
+    match claude_dir.parent() {
+        Some(parent) if parent.as_os_str().is_empty() => Some(PathBuf::from(".")),
+        Some(parent) => Some(parent.to_path_buf()),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_normalize_project_dir_strips_claude_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("app");
+
+        assert_eq!(normalize_project_dir(&project.join(".claude")), project);
+        assert_eq!(
+            normalize_project_dir(&project.join(".claude/skills/rust")),
+            project
+        );
+        assert_eq!(normalize_project_dir(&project), project);
+        assert_eq!(normalize_project_dir(Path::new(".claude")), Path::new("."));
+        assert_eq!(
+            normalize_project_dir(Path::new("app/.claude/hooks")),
+            Path::new("app")
+        );
+    }
+
+    #[test]
+    fn test_normalize_project_dir_keeps_similar_names() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join(".claude-hooks");
+        assert_eq!(normalize_project_dir(&project), project);
+
+        let nested = temp_dir.path().join("app/.claude.bak");
+        assert_eq!(normalize_project_dir(&nested), nested);
+    }
+}
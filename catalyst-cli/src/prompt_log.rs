@@ -0,0 +1,329 @@
+//! Audit log of prompts, written by the opt-in prompt-logger hook
+//!
+//! Each UserPromptSubmit appends one JSON line to `.claude/logs/prompts.jsonl`
+//! in the project. The prompt itself is never stored: an entry holds the
+//! time, session, prompt length, a SHA-256 of the prompt (to spot repeats or
+//! confirm a prompt someone quotes), and the skills whose prompt triggers
+//! matched. `catalyst logs show` and `catalyst logs tail` read it back.
+//!
+//! Matching only scores `promptTriggers`. The logger doesn't see which
+//! files the session is working on, so a skill that skill-activation-prompt
+//! suggests purely through `fileTriggers` isn't listed.
+
+use crate::activation::{MatchMode, PromptText};
+use crate::types::{
+    CatalystError, Result, ACTIVATION_INTENT_WEIGHT, ACTIVATION_KEYWORD_WEIGHT,
+    DEFAULT_ACTIVATION_MIN_SCORE,
+};
+use catalyst_core::jsonc;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Log directory, relative to the project
+pub const PROMPT_LOG_DIR: &str = ".claude/logs";
+
+/// Log file inside [`PROMPT_LOG_DIR`]
+pub const PROMPT_LOG_FILE: &str = "prompts.jsonl";
+
+/// One submitted prompt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptLogEntry {
+    /// When the prompt was submitted (RFC 3339)
+    pub timestamp: String,
+    pub session_id: Option<String>,
+    /// Length in characters
+    pub prompt_length: usize,
+    /// Hex SHA-256 of the prompt text
+    pub prompt_sha256: String,
+    /// Skills whose prompt triggers matched, by name
+    pub matched_skills: Vec<String>,
+}
+
+impl PromptLogEntry {
+    /// An entry for `prompt` stamped with the current time
+    pub fn new(session_id: Option<&str>, prompt: &str, matched_skills: Vec<String>) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339(),
+            session_id: session_id.map(str::to_string),
+            prompt_length: prompt.chars().count(),
+            prompt_sha256: format!("{:x}", Sha256::digest(prompt.as_bytes())),
+            matched_skills,
+        }
+    }
+}
+
+/// Which entries `read` returns
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    pub session: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    /// Keep only the last N matching entries
+    pub limit: Option<usize>,
+}
+
+/// Path of a project's prompt log
+pub fn log_path(project_dir: &Path) -> PathBuf {
+    project_dir.join(PROMPT_LOG_DIR).join(PROMPT_LOG_FILE)
+}
+
+/// Append an entry, creating the log directory if needed
+///
+/// The file is only ever opened for appending, and on Unix is created
+/// readable by its owner alone.
+///
+/// # Errors
+///
+/// Returns an error if the directory or the log can't be written
+pub fn append(project_dir: &Path, entry: &PromptLogEntry) -> Result<()> {
+    let path = log_path(project_dir);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|source| CatalystError::DirectoryCreationFailed {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|source| CatalystError::FileWriteFailed { path, source })
+}
+
+/// Entries matching `filter`, oldest first
+///
+/// Lines that don't parse are skipped.
+///
+/// # Errors
+///
+/// Returns `FileReadFailed` if the log exists but can't be read
+pub fn read(project_dir: &Path, filter: &LogFilter) -> Result<Vec<PromptLogEntry>> {
+    let path = log_path(project_dir);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => return Err(CatalystError::FileReadFailed { path, source }),
+    };
+    let mut entries: Vec<PromptLogEntry> = parse_lines(&content)
+        .into_iter()
+        .filter(|entry| {
+            filter
+                .session
+                .as_deref()
+                .is_none_or(|session| entry.session_id.as_deref() == Some(session))
+        })
+        .filter(|entry| {
+            filter.since.is_none_or(|since| {
+                DateTime::parse_from_rfc3339(&entry.timestamp).is_ok_and(|time| time >= since)
+            })
+        })
+        .collect();
+    if let Some(limit) = filter.limit {
+        entries.drain(..entries.len().saturating_sub(limit));
+    }
+    Ok(entries)
+}
+
+/// Entries appended after byte `offset`, and the offset to continue from
+///
+/// A trailing line without a newline is still being written, so it's left
+/// for the next call. If the log shrank (rotated or truncated), reading
+/// starts over from the beginning.
+///
+/// # Errors
+///
+/// Returns `FileReadFailed` if the log exists but can't be read
+pub fn read_from(project_dir: &Path, offset: u64) -> Result<(Vec<PromptLogEntry>, u64)> {
+    let path = log_path(project_dir);
+    let read_error = |source| CatalystError::FileReadFailed {
+        path: path.clone(),
+        source,
+    };
+    let mut file = match fs::File::open(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(source) => return Err(read_error(source)),
+    };
+    let len = file.metadata().map_err(read_error)?.len();
+    let start = if len < offset { 0 } else { offset };
+    file.seek(SeekFrom::Start(start)).map_err(read_error)?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).map_err(read_error)?;
+
+    let complete = bytes.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    let content = String::from_utf8_lossy(&bytes[..complete]);
+    Ok((parse_lines(&content), start + complete as u64))
+}
+
+fn parse_lines(content: &str) -> Vec<PromptLogEntry> {
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct Rules {
+    #[serde(default)]
+    skills: BTreeMap<String, Rule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Rule {
+    #[serde(default, rename = "promptTriggers")]
+    prompt_triggers: Option<PromptTriggers>,
+    #[serde(default, rename = "minScore")]
+    min_score: Option<u32>,
+    #[serde(default, rename = "matchMode")]
+    match_mode: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PromptTriggers {
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default, rename = "intentPatterns")]
+    intent_patterns: Vec<String>,
+}
+
+/// Skills in `skill-rules.json` whose prompt triggers reach their minScore
+///
+/// Scores the way skill-activation-prompt does: each distinct keyword hit
+/// and each matching intent pattern adds its weight. Invalid patterns and an
+/// unknown matchMode are treated as skill-activation-prompt treats them
+/// (skipped, and exact). Returns an empty list if the rules can't be read.
+pub fn matched_skills(rules_path: &Path, prompt: &str) -> Vec<String> {
+    let Some(rules) = fs::read_to_string(rules_path)
+        .ok()
+        .and_then(|content| jsonc::from_str::<Rules>(&content).ok())
+    else {
+        return Vec::new();
+    };
+    let text = PromptText::new(prompt);
+
+    rules
+        .skills
+        .into_iter()
+        .filter(|(_, rule)| {
+            let Some(triggers) = &rule.prompt_triggers else {
+                return false;
+            };
+            let mode = rule
+                .match_mode
+                .as_deref()
+                .and_then(|mode| mode.parse().ok())
+                .unwrap_or(MatchMode::Exact);
+            let mut keywords: Vec<String> = triggers
+                .keywords
+                .iter()
+                .map(|keyword| keyword.to_lowercase())
+                .filter(|keyword| text.contains_keyword(keyword, mode))
+                .collect();
+            keywords.sort_unstable();
+            keywords.dedup();
+            let intents = triggers
+                .intent_patterns
+                .iter()
+                .filter_map(|pattern| Regex::new(pattern).ok())
+                .filter(|regex| regex.is_match(prompt))
+                .count();
+            let score = keywords.len() as u32 * ACTIVATION_KEYWORD_WEIGHT
+                + intents as u32 * ACTIVATION_INTENT_WEIGHT;
+            score
+                >= rule
+                    .min_score
+                    .unwrap_or(DEFAULT_ACTIVATION_MIN_SCORE)
+                    .max(1)
+        })
+        .map(|(name, _)| name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_entry_redacts_prompt() {
+        let entry = PromptLogEntry::new(Some("s1"), "fix the ünïcode bug", vec![]);
+        assert_eq!(entry.prompt_length, 19);
+        assert_eq!(entry.prompt_sha256.len(), 64);
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(!line.contains("bug"));
+    }
+
+    #[test]
+    fn test_append_read_and_follow() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        let mut first = PromptLogEntry::new(Some("s1"), "one", vec![]);
+        first.timestamp = "2026-01-01T00:00:00+00:00".to_string();
+        append(project, &first).unwrap();
+        let second = PromptLogEntry::new(Some("s2"), "two", vec!["rust-developer".to_string()]);
+        append(project, &second).unwrap();
+
+        assert_eq!(read(project, &LogFilter::default()).unwrap().len(), 2);
+        let by_session = LogFilter {
+            session: Some("s2".to_string()),
+            ..LogFilter::default()
+        };
+        assert_eq!(read(project, &by_session).unwrap(), vec![second.clone()]);
+        let last = LogFilter {
+            limit: Some(1),
+            ..LogFilter::default()
+        };
+        assert_eq!(read(project, &last).unwrap(), vec![second.clone()]);
+
+        let (all, offset) = read_from(project, 0).unwrap();
+        assert_eq!(all, vec![first, second]);
+        // A half-written line waits for its newline
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(log_path(project))
+            .unwrap();
+        file.write_all(b"{\"timestamp\"").unwrap();
+        let (none, same) = read_from(project, offset).unwrap();
+        assert!(none.is_empty());
+        assert_eq!(same, offset);
+    }
+
+    #[test]
+    fn test_matched_skills_scores_prompt_triggers() {
+        let temp_dir = TempDir::new().unwrap();
+        let rules = temp_dir.path().join("skill-rules.json");
+        fs::write(
+            &rules,
+            r#"{"version": "1.0", "skills": {
+                "rust-developer": {"type": "domain", "enforcement": "suggest", "priority": "high",
+                    "promptTriggers": {"keywords": ["rust", "cargo"]}},
+                "route-tester": {"type": "domain", "enforcement": "suggest", "priority": "low",
+                    "minScore": 2, "promptTriggers": {"keywords": ["route"]}},
+                "testing": {"type": "domain", "enforcement": "suggest", "priority": "low",
+                    "matchMode": "stemmed",
+                    "promptTriggers": {"intentPatterns": ["(?i)(write|add).*tests?"]}}
+            }}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            matched_skills(&rules, "Add tests for this Rust route"),
+            vec!["rust-developer".to_string(), "testing".to_string()]
+        );
+        assert!(matched_skills(&temp_dir.path().join("missing.json"), "rust").is_empty());
+    }
+}
@@ -0,0 +1,679 @@
+//! Community skill registry (`catalyst skills search` and `skills add`)
+//!
+//! The registry is a single JSON index served over HTTPS. Each entry points
+//! at a tar archive of one skill directory and gives its SHA-256, and
+//! optionally a minisign signature of the archive:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "skills": [
+//!     {
+//!       "id": "terraform-patterns",
+//!       "description": "Terraform module layout and review checklist",
+//!       "version": "1.2.0",
+//!       "author": "Jane Doe",
+//!       "keywords": ["terraform", "iac"],
+//!       "url": "https://example.com/terraform-patterns-1.2.0.tar.gz",
+//!       "sha256": "9c1e...",
+//!       "signature": "untrusted comment: ...\nRUQf...\ntrusted comment: ...\ny/rU..."
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! The index URL is `registry.url` in catalyst.toml, overridden by
+//! `CATALYST_REGISTRY_URL`. Downloaded indexes are cached in
+//! `~/.claude/hooks-state-rust/registry-cache/` and reused for a day; when
+//! the registry can't be reached, an older cached copy is used instead.
+//!
+//! A skill is only installed if its signature checks out against one of the
+//! minisign public keys in `registry.trusted_keys`. The index can't vouch
+//! for itself, so keys are never read from it. Unsigned skills, and skills
+//! signed by a key that isn't trusted, need `--allow-unverified`; the
+//! checksum is verified either way, and a signature that fails to verify
+//! always stops the install.
+
+use crate::config::CatalystConfig;
+use crate::init::{self, add_skill_hashes, write_file_atomic};
+use crate::lock::OperationLock;
+use crate::safe_path;
+use crate::self_update;
+use crate::types::{
+    CatalystError, Result, AVAILABLE_SKILLS, CLAUDE_DIR, SKILLS_DIR, SKILL_RULES_FILE,
+    TRACKER_STATE_DIR,
+};
+use catalyst_core::fs_ops::Fs;
+use catalyst_core::skill_manifest::{SkillManifest, SKILL_FILE};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+/// Index used when neither catalyst.toml nor the environment names one
+pub const DEFAULT_REGISTRY_URL: &str =
+    "https://raw.githubusercontent.com/dwalleck/catalyst/main/registry/index.json";
+
+/// Environment variable that overrides `registry.url`
+pub const REGISTRY_URL_ENV: &str = "CATALYST_REGISTRY_URL";
+
+/// Index format this CLI understands
+pub const INDEX_FORMAT_VERSION: u32 = 1;
+
+/// Directory under the hook state directory that holds cached indexes
+pub const REGISTRY_CACHE_DIR: &str = "registry-cache";
+
+/// How long a cached index is used without checking the registry
+pub const INDEX_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Contents of the registry index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryIndex {
+    /// Format version (see [`INDEX_FORMAT_VERSION`])
+    pub version: u32,
+    pub skills: Vec<RegistrySkill>,
+}
+
+/// A skill published in the registry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistrySkill {
+    /// Directory name the skill is installed under
+    pub id: String,
+    pub description: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// Tar archive of the skill directory
+    pub url: String,
+    /// SHA-256 of the archive, hex-encoded
+    pub sha256: String,
+    /// Minisign signature of the archive (the contents of its .minisig file)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// How [`add_skill`] installs a skill
+#[derive(Debug, Clone, Default)]
+pub struct AddOptions {
+    /// Replace the skill if it is already installed
+    pub force: bool,
+    /// Install skills without a signature from a trusted key
+    pub allow_unverified: bool,
+    /// Minisign public keys (base64) whose signatures are trusted
+    pub trusted_keys: Vec<String>,
+}
+
+/// A skill installed from the registry
+#[derive(Debug, Clone, Serialize)]
+pub struct AddedSkill {
+    pub dir: PathBuf,
+    /// Trusted key that signed the archive; None if installed unverified
+    pub signed_by: Option<String>,
+}
+
+/// Where a loaded index came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexSource {
+    /// Downloaded just now
+    Registry,
+    /// Cached copy younger than [`INDEX_MAX_AGE`]
+    Cache,
+    /// Older cached copy, used because the registry couldn't be reached
+    StaleCache,
+}
+
+/// A registry index together with where it was read from
+#[derive(Debug, Clone)]
+pub struct LoadedIndex {
+    pub index: RegistryIndex,
+    pub source: IndexSource,
+    /// Why the registry couldn't be reached, for [`IndexSource::StaleCache`]
+    pub fetch_error: Option<String>,
+}
+
+/// Index URL for a project: `CATALYST_REGISTRY_URL`, then `registry.url`,
+/// then [`DEFAULT_REGISTRY_URL`]
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the environment variable isn't an allowed URL
+/// (see [`validate_registry_url`])
+pub fn registry_url(config: &CatalystConfig) -> Result<String> {
+    match std::env::var(REGISTRY_URL_ENV) {
+        Ok(url) if !url.is_empty() => {
+            validate_registry_url(&url)?;
+            Ok(url)
+        }
+        _ => Ok(config
+            .registry
+            .url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_REGISTRY_URL.to_string())),
+    }
+}
+
+/// Check that a registry URL uses HTTPS, or `file://` for a local mirror
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` for any other scheme
+pub fn validate_registry_url(url: &str) -> Result<()> {
+    let allowed = ["https://", "file://"].iter().any(|scheme| {
+        url.strip_prefix(scheme)
+            .is_some_and(|rest| !rest.is_empty())
+    });
+    if allowed {
+        Ok(())
+    } else {
+        Err(CatalystError::InvalidConfig(format!(
+            "registry.url '{}' must be an https:// or file:// URL",
+            url
+        )))
+    }
+}
+
+/// Default cache directory, `~/.claude/hooks-state-rust/registry-cache`
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| {
+        home.join(CLAUDE_DIR)
+            .join(TRACKER_STATE_DIR)
+            .join(REGISTRY_CACHE_DIR)
+    })
+}
+
+/// Load the index at `url`, from the cache when it is fresh enough
+///
+/// # Arguments
+///
+/// * `url` - Index URL
+/// * `cache_dir` - Where indexes are cached; None disables caching
+/// * `refresh` - Download even if the cached copy is fresh
+///
+/// # Errors
+///
+/// Returns `RegistryFailed` if the index can't be downloaded or parsed and
+/// there is no cached copy to fall back on
+pub fn load_index(url: &str, cache_dir: Option<&Path>, refresh: bool) -> Result<LoadedIndex> {
+    let cache_file = cache_dir.map(|dir| cache_path(dir, url));
+    let cached = cache_file.as_deref().and_then(read_cached);
+    if let Some((index, age)) = &cached {
+        if !refresh && *age < INDEX_MAX_AGE {
+            return Ok(LoadedIndex {
+                index: index.clone(),
+                source: IndexSource::Cache,
+                fetch_error: None,
+            });
+        }
+    }
+
+    let fetched = self_update::curl(&[url])
+        .map_err(download_error)
+        .and_then(|body| parse_index(&body).map_err(|e| e.to_string()));
+    match (fetched, cached) {
+        (Ok(index), _) => {
+            if let Some(cache_file) = &cache_file {
+                // A cache that can't be written only costs a download next time
+                if let Err(e) = store_cached(cache_file, &index) {
+                    tracing::debug!(error = %e, "Could not cache registry index");
+                }
+            }
+            Ok(LoadedIndex {
+                index,
+                source: IndexSource::Registry,
+                fetch_error: None,
+            })
+        }
+        (Err(error), Some((index, _))) => Ok(LoadedIndex {
+            index,
+            source: IndexSource::StaleCache,
+            fetch_error: Some(error),
+        }),
+        (Err(error), None) => Err(CatalystError::RegistryFailed(format!(
+            "Could not load {}: {}",
+            url, error
+        ))),
+    }
+}
+
+/// Message of a failed download, without the self-update framing curl
+/// errors carry
+fn download_error(error: CatalystError) -> String {
+    match error {
+        CatalystError::SelfUpdateFailed(message) => message,
+        other => other.to_string(),
+    }
+}
+
+/// Parse an index, rejecting formats newer than this CLI understands
+fn parse_index(content: &[u8]) -> Result<RegistryIndex> {
+    let index: RegistryIndex = serde_json::from_slice(content)
+        .map_err(|e| CatalystError::RegistryFailed(format!("Invalid registry index: {}", e)))?;
+    if index.version > INDEX_FORMAT_VERSION {
+        return Err(CatalystError::RegistryFailed(format!(
+            "Registry index format {} is newer than this catalyst supports ({}); run 'catalyst self-update'",
+            index.version, INDEX_FORMAT_VERSION
+        )));
+    }
+    Ok(index)
+}
+
+/// Cache file for an index URL, named after a hash of the URL
+fn cache_path(cache_dir: &Path, url: &str) -> PathBuf {
+    let digest = Sha256::digest(url.as_bytes());
+    let name: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    cache_dir.join(format!("{}.json", name))
+}
+
+/// A cached index and its age; None if missing or unreadable
+fn read_cached(path: &Path) -> Option<(RegistryIndex, Duration)> {
+    let content = fs::read(path).ok()?;
+    let index = parse_index(&content).ok()?;
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default();
+    Some((index, age))
+}
+
+fn store_cached(path: &Path, index: &RegistryIndex) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| CatalystError::DirectoryCreationFailed {
+            path: parent.to_path_buf(),
+            source: e,
+        })?;
+    }
+    let content = serde_json::to_string(index).map_err(CatalystError::Json)?;
+    write_file_atomic(path, &content)?;
+    Ok(())
+}
+
+/// Skills matching every word of `query`, best matches first
+///
+/// A word matches a skill if it appears (case-insensitively) in its ID,
+/// description, or keywords. Skills whose ID or keywords contain a word
+/// rank above those that only mention it in the description.
+pub fn search<'a>(index: &'a RegistryIndex, query: &str) -> Vec<&'a RegistrySkill> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let mut matches: Vec<(usize, &RegistrySkill)> = index
+        .skills
+        .iter()
+        .filter_map(|skill| {
+            let id = skill.id.to_lowercase();
+            let description = skill.description.to_lowercase();
+            let keywords: Vec<String> = skill.keywords.iter().map(|k| k.to_lowercase()).collect();
+            let mut score = 0;
+            for word in &words {
+                if id.contains(word.as_str()) || keywords.iter().any(|k| k.contains(word.as_str()))
+                {
+                    score += 2;
+                } else if description.contains(word.as_str()) {
+                    score += 1;
+                } else {
+                    return None;
+                }
+            }
+            Some((score, skill))
+        })
+        .collect();
+    matches.sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then(a.id.cmp(&b.id)));
+    matches.into_iter().map(|(_, skill)| skill).collect()
+}
+
+/// Download a registry skill and install it in `.claude/skills`
+///
+/// The archive must match the index's SHA-256, be signed by a trusted key
+/// (unless `allow_unverified` is set), and contain one skill directory (or
+/// the skill's files at its root) with a valid SKILL.md. The skill is added
+/// to skill-rules.json, using its skill.toml `[activation]` table if it has
+/// one, and to `.catalyst-hashes.json`.
+///
+/// # Arguments
+///
+/// * `target_dir` - Project directory containing .claude
+/// * `skill` - Registry entry to install
+/// * `options` - Overwrite and verification settings
+///
+/// # Returns
+///
+/// Returns the installed skill's directory and the key that signed it
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the skill is already installed (without
+/// `force`) or shares an ID with a built-in skill, `HashMismatch` if the
+/// download doesn't match the index, `VerificationFailed` if its signature
+/// is invalid, `Unverified` if it isn't signed by a trusted key, and
+/// `RegistryFailed` if the download fails or the archive holds no valid skill
+pub fn add_skill(
+    target_dir: &Path,
+    skill: &RegistrySkill,
+    options: &AddOptions,
+) -> Result<AddedSkill> {
+    let _lock = OperationLock::acquire(target_dir)?;
+    check_installable(target_dir, &skill.id, options.force)?;
+
+    let work_dir = tempfile::tempdir().map_err(CatalystError::Io)?;
+    let archive = work_dir.path().join("skill.tar.gz");
+    self_update::curl(&["-o", &archive.to_string_lossy(), &skill.url]).map_err(|e| {
+        CatalystError::RegistryFailed(format!("{}: {}", skill.id, download_error(e)))
+    })?;
+    let content = fs::read(&archive).map_err(|e| CatalystError::FileReadFailed {
+        path: archive.clone(),
+        source: e,
+    })?;
+    verify_checksum(&content, skill)?;
+    let signed_by = verify_signature(&content, skill, &options.trusted_keys)?;
+    if signed_by.is_none() && !options.allow_unverified {
+        return Err(CatalystError::Unverified(skill.id.clone()));
+    }
+
+    let extract_dir = work_dir.path().join("extracted");
+    extract(&archive, &extract_dir)?;
+    let source = find_skill_root(&extract_dir).ok_or_else(|| {
+        CatalystError::RegistryFailed(format!(
+            "The archive for '{}' has no {}",
+            skill.id, SKILL_FILE
+        ))
+    })?;
+    SkillManifest::load(&source).map_err(|e| {
+        CatalystError::RegistryFailed(format!("'{}' has an invalid manifest: {:#}", skill.id, e))
+    })?;
+
+    Ok(AddedSkill {
+        dir: install_skill_dir(target_dir, &skill.id, &source)?,
+        signed_by,
+    })
+}
+
+/// Check that a downloaded or packaged skill may be installed as `skill_id`
+///
+/// # Errors
+///
+/// Returns `PathTraversalDetected` for an unsafe ID, and `InvalidConfig` if
+/// the ID is a built-in skill's or the skill is installed (without `force`)
+pub(crate) fn check_installable(target_dir: &Path, skill_id: &str, force: bool) -> Result<()> {
+    safe_path::validate_skill_id(skill_id)?;
+    if AVAILABLE_SKILLS.contains(&skill_id) {
+        return Err(CatalystError::InvalidConfig(format!(
+            "'{}' is a built-in skill; install it with 'catalyst init --skills {}'",
+            skill_id, skill_id
+        )));
+    }
+    if target_dir.join(SKILLS_DIR).join(skill_id).exists() && !force {
+        return Err(CatalystError::InvalidConfig(format!(
+            "Skill '{}' is already installed; use --force to replace it",
+            skill_id
+        )));
+    }
+    Ok(())
+}
+
+/// Copy an unpacked skill into `.claude/skills/<skill_id>`, replacing any
+/// installed copy, and add it to skill-rules.json and the hash file
+///
+/// # Returns
+///
+/// Returns the installed skill's directory
+pub(crate) fn install_skill_dir(
+    target_dir: &Path,
+    skill_id: &str,
+    source: &Path,
+) -> Result<PathBuf> {
+    let skill_dir = target_dir.join(SKILLS_DIR).join(skill_id);
+    if skill_dir.exists() {
+        fs::remove_dir_all(&skill_dir).map_err(CatalystError::Io)?;
+    }
+    Fs::read_write()
+        .copy_dir_all(source, &skill_dir)
+        .map_err(|e| CatalystError::FileWriteFailed {
+            path: skill_dir.clone(),
+            source: e,
+        })?;
+
+    let skill_ids = [skill_id.to_string()];
+    if target_dir.join(SKILL_RULES_FILE).is_file() {
+        init::add_skill_rules(target_dir, &skill_ids)?;
+    } else {
+        init::generate_skill_rules(target_dir, &skill_ids)?;
+    }
+    add_skill_hashes(target_dir, skill_id)?;
+
+    Ok(skill_dir)
+}
+
+fn verify_checksum(content: &[u8], skill: &RegistrySkill) -> Result<()> {
+    let actual = format!("{:x}", Sha256::digest(content));
+    if !actual.eq_ignore_ascii_case(&skill.sha256) {
+        return Err(CatalystError::HashMismatch(format!(
+            "{}: expected {}, downloaded {}",
+            skill.url, skill.sha256, actual
+        )));
+    }
+    Ok(())
+}
+
+/// Check an archive's signature against the trusted keys
+///
+/// # Returns
+///
+/// Returns the trusted key that made the signature, or None if the skill
+/// is unsigned or signed by a key that isn't trusted
+///
+/// # Errors
+///
+/// Returns `VerificationFailed` if the signature can't be decoded or was
+/// made by a trusted key but doesn't match the archive
+pub fn verify_signature(
+    content: &[u8],
+    skill: &RegistrySkill,
+    trusted_keys: &[String],
+) -> Result<Option<String>> {
+    let Some(signature) = &skill.signature else {
+        return Ok(None);
+    };
+    let signature = minisign_verify::Signature::decode(signature).map_err(|e| {
+        CatalystError::VerificationFailed(format!("{}: invalid signature: {}", skill.id, e))
+    })?;
+    for key in trusted_keys {
+        let public_key = minisign_verify::PublicKey::from_base64(key).map_err(|e| {
+            CatalystError::InvalidConfig(format!("Invalid trusted key '{}': {}", key, e))
+        })?;
+        match public_key.verify(content, &signature, false) {
+            Ok(()) => return Ok(Some(key.clone())),
+            // Signed by some other key; try the next one
+            Err(minisign_verify::Error::UnexpectedKeyId) => continue,
+            Err(e) => {
+                return Err(CatalystError::VerificationFailed(format!(
+                    "{}: {}",
+                    skill.id, e
+                )))
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Unpack a skill archive after checking its entries
+pub(crate) fn extract(archive: &Path, extract_dir: &Path) -> Result<()> {
+    fs::create_dir_all(extract_dir).map_err(|e| CatalystError::DirectoryCreationFailed {
+        path: extract_dir.to_path_buf(),
+        source: e,
+    })?;
+    safe_path::check_tar_archive(archive)?;
+    let output = Command::new("tar")
+        .arg("-xf")
+        .arg(archive)
+        .arg("-C")
+        .arg(extract_dir)
+        .output()
+        .map_err(|e| CatalystError::RegistryFailed(format!("Could not run tar: {}", e)))?;
+    if !output.status.success() {
+        return Err(CatalystError::RegistryFailed(format!(
+            "tar could not unpack {}: {}",
+            archive.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// The unpacked skill: the archive root if it has a SKILL.md, otherwise its
+/// only directory that does
+pub(crate) fn find_skill_root(extract_dir: &Path) -> Option<PathBuf> {
+    if extract_dir.join(SKILL_FILE).is_file() {
+        return Some(extract_dir.to_path_buf());
+    }
+    let mut candidates = fs::read_dir(extract_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(SKILL_FILE).is_file());
+    let root = candidates.next()?;
+    candidates.next().is_none().then_some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(id: &str, description: &str, keywords: &[&str]) -> RegistrySkill {
+        RegistrySkill {
+            id: id.to_string(),
+            description: description.to_string(),
+            version: "1.0.0".to_string(),
+            author: None,
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            url: format!("https://example.com/{}.tar.gz", id),
+            sha256: String::new(),
+            signature: None,
+        }
+    }
+
+    /// Minisign test vector: a signature of b"test"
+    const TEST_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+    const TEST_SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQf6LRCGA9i559r3g7V1qNyJDApGip8MfqcadIgT9CuhV3EMhHoN1mGTkUidF/z7SrlQgXdy8ofjb7bNJJylDOocrCo8KLzZwo=
+trusted comment: timestamp:1556193335\tfile:test
+y/rUw2y8/hOUYjZU71eHp/Wo1KZ40fGy2VJEDl34XMJM+TX48Ss/17u3IvIfbVR1FkZZSNCisQbuQY+bHwhEBg==";
+
+    #[test]
+    fn test_verify_signature_needs_a_trusted_key() {
+        let mut skill = entry("demo", "Demo skill", &[]);
+        let trusted = [TEST_KEY.to_string()];
+        assert_eq!(verify_signature(b"test", &skill, &trusted).unwrap(), None);
+
+        skill.signature = Some(TEST_SIGNATURE.to_string());
+        assert_eq!(
+            verify_signature(b"test", &skill, &trusted).unwrap(),
+            Some(TEST_KEY.to_string())
+        );
+        assert_eq!(verify_signature(b"test", &skill, &[]).unwrap(), None);
+        assert!(matches!(
+            verify_signature(b"tampered", &skill, &trusted),
+            Err(CatalystError::VerificationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_search_ranks_id_and_keyword_matches_first() {
+        let index = RegistryIndex {
+            version: INDEX_FORMAT_VERSION,
+            skills: vec![
+                entry("k8s-review", "Review Terraform and Helm changes", &[]),
+                entry("terraform-patterns", "Module layout", &["iac"]),
+                entry("sql-style", "SQL style guide", &["postgres"]),
+            ],
+        };
+        let ids = |query| -> Vec<String> {
+            search(&index, query)
+                .iter()
+                .map(|skill| skill.id.clone())
+                .collect()
+        };
+        assert_eq!(ids("Terraform"), ["terraform-patterns", "k8s-review"]);
+        assert_eq!(ids("postgres style"), ["sql-style"]);
+        assert!(ids("terraform postgres").is_empty());
+    }
+
+    #[test]
+    fn test_add_skill_verifies_checksum_and_installs() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("src/demo");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(
+            source.join(SKILL_FILE),
+            "---\nname: demo\ndescription: Demo skill\n---\n",
+        )
+        .unwrap();
+        let archive = temp_dir.path().join("demo.tar.gz");
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(temp_dir.path().join("src"))
+            .arg("demo")
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let target = temp_dir.path().join("project");
+        fs::create_dir_all(target.join(CLAUDE_DIR)).unwrap();
+        let mut skill = entry("demo", "Demo skill", &[]);
+        skill.url = format!("file://{}", archive.display());
+        skill.sha256 = "0".repeat(64);
+        let mut options = AddOptions::default();
+        assert!(matches!(
+            add_skill(&target, &skill, &options),
+            Err(CatalystError::HashMismatch(_))
+        ));
+
+        skill.sha256 = format!("{:x}", Sha256::digest(fs::read(&archive).unwrap()));
+        assert!(matches!(
+            add_skill(&target, &skill, &options),
+            Err(CatalystError::Unverified(_))
+        ));
+        assert!(!target.join(SKILLS_DIR).join("demo").exists());
+
+        options.allow_unverified = true;
+        let added = add_skill(&target, &skill, &options).unwrap();
+        assert!(added.dir.join(SKILL_FILE).is_file());
+        assert_eq!(added.signed_by, None);
+        let rules = fs::read_to_string(target.join(SKILL_RULES_FILE)).unwrap();
+        assert!(rules.contains("\"demo\""));
+        assert!(matches!(
+            add_skill(&target, &skill, &options),
+            Err(CatalystError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_index_falls_back_to_stale_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let index_path = temp_dir.path().join("index.json");
+        let cache = temp_dir.path().join("cache");
+        let url = format!("file://{}", index_path.display());
+        let index = RegistryIndex {
+            version: INDEX_FORMAT_VERSION,
+            skills: vec![entry("demo", "Demo skill", &[])],
+        };
+        fs::write(&index_path, serde_json::to_string(&index).unwrap()).unwrap();
+
+        let loaded = load_index(&url, Some(&cache), false).unwrap();
+        assert_eq!(loaded.source, IndexSource::Registry);
+        assert_eq!(
+            load_index(&url, Some(&cache), false).unwrap().source,
+            IndexSource::Cache
+        );
+
+        fs::remove_file(&index_path).unwrap();
+        let loaded = load_index(&url, Some(&cache), true).unwrap();
+        assert_eq!(loaded.source, IndexSource::StaleCache);
+        assert_eq!(loaded.index, index);
+        assert!(load_index(&url, None, false).is_err());
+    }
+}
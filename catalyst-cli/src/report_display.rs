@@ -0,0 +1,807 @@
+//! Rendering of init, update, status, and sync reports
+//!
+//! Each report describes itself once as a [`ReportView`] - a header,
+//! titled sections of items, and footer lines - and every display mode is
+//! drawn from that description. JSON output serializes the report itself,
+//! so it always carries every field.
+//!
+//! | Mode         | Output                                                  |
+//! |--------------|---------------------------------------------------------|
+//! | `text`       | Headings and icons, colored on a terminal               |
+//! | `json`       | The report as pretty-printed JSON                       |
+//! | `table`      | One aligned `SECTION  STATUS  ITEM` row per item         |
+//! | `accessible` | Words instead of icons and color, for screen readers    |
+
+use crate::sync::{SyncOutcome, SyncReport};
+use crate::types::{
+    CatalystError, InitReport, IssueSeverity, Result, ScriptResult, StatusLevel, StatusReport,
+    UpdateReport, LOCK_FILE,
+};
+use colored::{ColoredString, Colorize};
+use serde::Serialize;
+use std::fmt::Write as _;
+
+/// How a report is printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DisplayMode {
+    #[default]
+    Text,
+    Json,
+    Table,
+    Accessible,
+}
+
+impl std::str::FromStr for DisplayMode {
+    type Err = CatalystError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(DisplayMode::Text),
+            "json" => Ok(DisplayMode::Json),
+            "table" => Ok(DisplayMode::Table),
+            "accessible" => Ok(DisplayMode::Accessible),
+            _ => Err(CatalystError::InvalidConfig(format!(
+                "Unknown display mode '{}'. Expected text, json, table, or accessible",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+/// Outcome attached to a header, item, or footer line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tone {
+    /// Done or healthy
+    Ok,
+    /// Missing or broken, but not an error in its own right
+    Failed,
+    Error,
+    Warning,
+    Info,
+    /// Deliberately not done
+    Skipped,
+    /// Plain text without an outcome
+    Note,
+}
+
+impl Tone {
+    fn icon(self) -> &'static str {
+        match self {
+            Tone::Ok => "✓",
+            Tone::Failed => "✗",
+            Tone::Error => "❌",
+            Tone::Warning => "⚠️ ",
+            Tone::Info => "ℹ️ ",
+            Tone::Skipped => "-",
+            Tone::Note => "",
+        }
+    }
+
+    /// Icon used for headers, which announce a whole result
+    fn banner_icon(self) -> &'static str {
+        match self {
+            Tone::Ok => "✅",
+            Tone::Failed | Tone::Error => "❌",
+            Tone::Warning => "⚠️ ",
+            Tone::Info | Tone::Skipped | Tone::Note => "",
+        }
+    }
+
+    fn word(self) -> &'static str {
+        match self {
+            Tone::Ok => "ok",
+            Tone::Failed => "failed",
+            Tone::Error => "error",
+            Tone::Warning => "warning",
+            Tone::Info => "info",
+            Tone::Skipped => "skipped",
+            Tone::Note => "",
+        }
+    }
+
+    fn paint(self, text: &str) -> ColoredString {
+        match self {
+            Tone::Ok => text.green(),
+            Tone::Failed | Tone::Error => text.red(),
+            Tone::Warning => text.yellow(),
+            Tone::Info => text.blue(),
+            Tone::Skipped | Tone::Note => text.normal(),
+        }
+    }
+}
+
+/// One line of a report, with indented detail lines under it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    pub tone: Tone,
+    pub text: String,
+    pub details: Vec<String>,
+}
+
+impl Item {
+    pub fn new(tone: Tone, text: impl Into<String>) -> Self {
+        Self {
+            tone,
+            text: text.into(),
+            details: Vec::new(),
+        }
+    }
+
+    pub fn with_details(mut self, details: impl IntoIterator<Item = String>) -> Self {
+        self.details.extend(details);
+        self
+    }
+}
+
+/// Titled group of items; empty sections are not printed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub title: String,
+    pub items: Vec<Item>,
+}
+
+impl Section {
+    pub fn new(title: impl Into<String>, items: Vec<Item>) -> Self {
+        Self {
+            title: title.into(),
+            items,
+        }
+    }
+
+    fn of(title: &str, tone: Tone, texts: &[String]) -> Self {
+        Self::new(
+            title,
+            texts.iter().map(|text| Item::new(tone, text)).collect(),
+        )
+    }
+}
+
+/// Display-independent description of a report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportView {
+    pub header: Item,
+    pub sections: Vec<Section>,
+    pub footer: Vec<Item>,
+}
+
+/// A report that can be printed in every [`DisplayMode`]
+pub trait Displayable: Serialize {
+    /// Describe the report for the text-based modes
+    fn view(&self) -> ReportView;
+
+    /// Render the report
+    ///
+    /// # Errors
+    ///
+    /// Returns `Json` if the report can't be serialized
+    fn render(&self, mode: DisplayMode, use_color: bool) -> Result<String> {
+        match mode {
+            DisplayMode::Json => serde_json::to_string_pretty(self)
+                .map(|json| format!("{}\n", json))
+                .map_err(CatalystError::Json),
+            DisplayMode::Text => Ok(render_text(&self.view(), use_color)),
+            DisplayMode::Table => Ok(render_table(&self.view())),
+            DisplayMode::Accessible => Ok(render_accessible(&self.view())),
+        }
+    }
+}
+
+fn render_text(view: &ReportView, use_color: bool) -> String {
+    let mut out = String::new();
+    let header = format!("{} {}", view.header.tone.banner_icon(), view.header.text);
+    let header = header.trim_start();
+    if use_color {
+        let _ = writeln!(out, "{}", view.header.tone.paint(header).bold());
+    } else {
+        let _ = writeln!(out, "{}", header);
+    }
+    let _ = writeln!(out);
+
+    for section in view.sections.iter().filter(|s| !s.items.is_empty()) {
+        let title = format!("{}:", section.title);
+        if use_color {
+            let _ = writeln!(out, "{}", title.cyan().bold());
+        } else {
+            let _ = writeln!(out, "{}", title);
+        }
+        for item in &section.items {
+            let line = format!("{} {}", item.tone.icon(), item.text);
+            let line = line.trim_start();
+            if use_color {
+                let _ = writeln!(out, "  {}", item.tone.paint(line));
+            } else {
+                let _ = writeln!(out, "  {}", line);
+            }
+            for detail in &item.details {
+                let _ = writeln!(out, "    {}", detail);
+            }
+        }
+        let _ = writeln!(out);
+    }
+
+    for item in &view.footer {
+        if use_color {
+            let _ = writeln!(out, "{}", item.tone.paint(&item.text));
+        } else {
+            let _ = writeln!(out, "{}", item.text);
+        }
+    }
+    out
+}
+
+fn render_table(view: &ReportView) -> String {
+    let rows: Vec<(&str, &str, &str)> = view
+        .sections
+        .iter()
+        .flat_map(|section| {
+            section
+                .items
+                .iter()
+                .map(move |item| (section.title.as_str(), item.tone.word(), item.text.as_str()))
+        })
+        .collect();
+    let section_width = rows
+        .iter()
+        .map(|row| row.0.chars().count())
+        .chain(["SECTION".len()])
+        .max()
+        .unwrap_or_default();
+    let status_width = rows
+        .iter()
+        .map(|row| row.1.len())
+        .chain(["STATUS".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", view.header.text);
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "{:<sw$}  {:<tw$}  ITEM",
+        "SECTION",
+        "STATUS",
+        sw = section_width,
+        tw = status_width
+    );
+    for (section, status, text) in rows {
+        let line = format!(
+            "{:<sw$}  {:<tw$}  {}",
+            section,
+            status,
+            text,
+            sw = section_width,
+            tw = status_width
+        );
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+    for item in &view.footer {
+        let _ = writeln!(out, "{}", item.text);
+    }
+    out
+}
+
+fn render_accessible(view: &ReportView) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", with_word(&view.header));
+    for section in view.sections.iter().filter(|s| !s.items.is_empty()) {
+        let _ = writeln!(
+            out,
+            "{}, {} {}:",
+            section.title,
+            section.items.len(),
+            if section.items.len() == 1 {
+                "item"
+            } else {
+                "items"
+            }
+        );
+        for item in &section.items {
+            let _ = writeln!(out, "  {}", with_word(item));
+            for detail in &item.details {
+                let _ = writeln!(out, "    {}", detail);
+            }
+        }
+    }
+    for item in &view.footer {
+        let _ = writeln!(out, "{}", with_word(item));
+    }
+    out
+}
+
+/// Item text prefixed with its outcome as a word, e.g. "Warning: ..."
+fn with_word(item: &Item) -> String {
+    let word = item.tone.word();
+    if word.is_empty() {
+        return item.text.clone();
+    }
+    let mut chars = word.chars();
+    let capitalized: String = chars
+        .next()
+        .map(|first| first.to_ascii_uppercase())
+        .into_iter()
+        .chain(chars)
+        .collect();
+    format!("{}: {}", capitalized, item.text)
+}
+
+/// Items for skill setup scripts that ran or were skipped
+fn script_items(scripts: &[ScriptResult]) -> Vec<Item> {
+    scripts
+        .iter()
+        .map(|result| {
+            if !result.ran {
+                return Item::new(
+                    Tone::Skipped,
+                    format!("{}: {}", result.skill, result.output),
+                )
+                .with_details([format!("Run it yourself: {}", result.script.display())]);
+            }
+            let item = if result.success {
+                Item::new(Tone::Ok, &result.skill)
+            } else {
+                let code = result
+                    .exit_code
+                    .map(|code| format!("exit code {}", code))
+                    .unwrap_or_else(|| "killed".to_string());
+                Item::new(Tone::Error, format!("{} ({})", result.skill, code))
+            };
+            item.with_details(result.output.lines().map(|line| format!("│ {}", line)))
+        })
+        .collect()
+}
+
+/// Items for the issues in a status report, with suggested fixes as details
+fn issue_items(report: &StatusReport) -> Vec<Item> {
+    report
+        .issues
+        .iter()
+        .map(|issue| {
+            let tone = match issue.severity {
+                IssueSeverity::Error => Tone::Error,
+                IssueSeverity::Warning => Tone::Warning,
+                IssueSeverity::Info => Tone::Info,
+            };
+            Item::new(
+                tone,
+                format!(
+                    "{} [{}] {}",
+                    issue.kind.id(),
+                    issue.component,
+                    issue.description
+                ),
+            )
+            .with_details(issue.suggested_fix.iter().map(|fix| format!("→ {}", fix)))
+        })
+        .collect()
+}
+
+impl Displayable for InitReport {
+    fn view(&self) -> ReportView {
+        let selection_note: Vec<Item> = self
+            .skill_selection
+            .note()
+            .map(|note| Item::new(Tone::Note, note))
+            .into_iter()
+            .collect();
+        if self.dry_run {
+            return ReportView {
+                header: Item::new(Tone::Warning, "Dry run - no files will be written"),
+                sections: vec![
+                    Section::new(
+                        "Planned actions",
+                        self.planned_actions
+                            .iter()
+                            .map(|action| Item::new(Tone::Note, format!("• {}", action)))
+                            .collect(),
+                    ),
+                    Section::of("Already correct", Tone::Ok, &self.unchanged),
+                    Section::new("Skills", selection_note),
+                    Section::of("Warnings", Tone::Warning, &self.warnings),
+                ],
+                footer: vec![Item::new(
+                    Tone::Note,
+                    "Run again without --dry-run to apply.",
+                )],
+            };
+        }
+
+        let mut configuration = Vec::new();
+        if self.settings_created {
+            configuration.push(Item::new(Tone::Ok, ".claude/settings.json"));
+        }
+        if self.lockfile_written {
+            configuration.push(Item::new(Tone::Ok, LOCK_FILE));
+        }
+        let nothing_changed = self.created_dirs.is_empty()
+            && self.installed_hooks.is_empty()
+            && self.installed_skills.is_empty()
+            && self.updated.is_empty()
+            && !self.settings_created;
+        let header = if !self.failed_skills.is_empty() {
+            Item::new(
+                Tone::Warning,
+                "Catalyst initialized, but some skills failed",
+            )
+        } else if self.reconciled && nothing_changed {
+            Item::new(Tone::Ok, "Already up to date!")
+        } else if self.reconciled {
+            Item::new(Tone::Ok, "Catalyst install reconciled")
+        } else {
+            Item::new(Tone::Ok, "Catalyst initialized successfully!")
+        };
+        ReportView {
+            header,
+            sections: vec![
+                Section::of("Created directories", Tone::Ok, &self.created_dirs),
+                Section::of("Installed hooks", Tone::Ok, &self.installed_hooks),
+                Section::new(
+                    "Installed skills",
+                    self.installed_skills
+                        .iter()
+                        .map(|skill| Item::new(Tone::Ok, skill))
+                        .chain(selection_note)
+                        .collect(),
+                ),
+                Section::new("Configuration", configuration),
+                Section::of("Updated", Tone::Ok, &self.updated),
+                Section::of("Already correct", Tone::Info, &self.unchanged),
+                Section::new(
+                    "Failed skills",
+                    self.failed_skills
+                        .iter()
+                        .map(|skill| {
+                            Item::new(Tone::Error, format!("{}: {}", skill.name, skill.error))
+                        })
+                        .collect(),
+                ),
+                Section::of("Warnings", Tone::Warning, &self.warnings),
+                Section::new("Skill setup scripts", script_items(&self.scripts)),
+                Section::new(
+                    "Next steps",
+                    [
+                        "1. Review .claude/settings.json",
+                        "2. Try editing a file - hooks should activate automatically",
+                        "3. Run 'catalyst status' to validate setup",
+                    ]
+                    .into_iter()
+                    .map(|step| Item::new(Tone::Note, step))
+                    .collect(),
+                ),
+            ],
+            footer: vec![Item::new(
+                Tone::Info,
+                "📖 Documentation: https://github.com/dwalleck/catalyst",
+            )],
+        }
+    }
+}
+
+impl Displayable for UpdateReport {
+    fn view(&self) -> ReportView {
+        let nothing_changed = self.updated_skills.is_empty()
+            && self.updated_hooks.is_empty()
+            && self.skipped_skills.is_empty()
+            && self.library_skills.is_empty();
+        let header = if nothing_changed && self.errors.is_empty() {
+            Item::new(Tone::Ok, "Already up to date!")
+        } else if self.success {
+            Item::new(Tone::Ok, "Update completed successfully!")
+        } else {
+            Item::new(Tone::Warning, "Update completed with errors")
+        };
+
+        let skipped = self
+            .skipped_skills
+            .iter()
+            .map(|skipped| {
+                Item::new(
+                    Tone::Warning,
+                    format!("{} - {}", skipped.name, skipped.reason),
+                )
+            })
+            .collect();
+        let mut footer = Vec::new();
+        if !self.skipped_skills.is_empty() {
+            footer.push(Item::new(
+                Tone::Warning,
+                "Use --force to overwrite modified skills",
+            ));
+        }
+
+        ReportView {
+            header,
+            sections: vec![
+                Section::of("Updated hooks", Tone::Ok, &self.updated_hooks),
+                Section::of("Updated skills", Tone::Ok, &self.updated_skills),
+                Section::of(
+                    "Updated in skill library (all linked projects)",
+                    Tone::Ok,
+                    &self.library_skills,
+                ),
+                Section::new("Skipped skills (modified locally)", skipped),
+                Section::of(
+                    "Binary updates available",
+                    Tone::Info,
+                    &self.binary_updates_available,
+                ),
+                Section::new("Skill setup scripts", script_items(&self.scripts)),
+                Section::of("Errors", Tone::Error, &self.errors),
+            ],
+            footer,
+        }
+    }
+}
+
+/// A status report together with what `--fix` repaired
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusDisplay<'a> {
+    #[serde(flatten)]
+    pub report: &'a StatusReport,
+
+    /// Fixes applied before the report was printed
+    pub fixed_issues: &'a [String],
+}
+
+impl Displayable for StatusDisplay<'_> {
+    fn view(&self) -> ReportView {
+        let report = self.report;
+        let header = match report.level {
+            StatusLevel::Ok => Item::new(Tone::Ok, "Catalyst Status: HEALTHY"),
+            StatusLevel::Warning => Item::new(Tone::Warning, "Catalyst Status: WARNING"),
+            StatusLevel::Error => Item::new(Tone::Error, "Catalyst Status: ERROR"),
+        };
+
+        let binaries = report
+            .binaries
+            .iter()
+            .map(|binary| {
+                let (tone, state) = match (binary.exists, binary.executable, &binary.version) {
+                    (true, true, Some(version)) if !binary.version_matches => {
+                        (Tone::Warning, format!("found, v{} incompatible", version))
+                    }
+                    (true, true, Some(version)) => (Tone::Ok, format!("found, v{}", version)),
+                    (true, true, None) => (Tone::Ok, "found".to_string()),
+                    (true, false, _) => (Tone::Failed, "not executable".to_string()),
+                    (false, _, _) => (Tone::Failed, "not found".to_string()),
+                };
+                let variant = binary
+                    .variant
+                    .as_ref()
+                    .map(|v| format!(" ({})", v))
+                    .unwrap_or_default();
+                Item::new(tone, format!("{} ({}){}", binary.name, state, variant))
+            })
+            .collect();
+
+        let hooks = report
+            .hooks
+            .iter()
+            .map(|hook| {
+                let ok = hook.exists && hook.executable && hook.calls_correct_binary;
+                let event = hook.event.as_deref().unwrap_or("unknown");
+                Item::new(
+                    if ok { Tone::Ok } else { Tone::Failed },
+                    format!("{} → {}", event, hook.name),
+                )
+            })
+            .collect();
+
+        let skills = report
+            .skills
+            .iter()
+            .map(|skill| {
+                let healthy = skill.health.as_ref().map(|h| h.healthy);
+                let ok = skill.has_main_file && healthy != Some(false);
+                let state = match (skill.has_main_file, healthy) {
+                    (false, _) => "incomplete",
+                    (true, Some(false)) => "unhealthy",
+                    (true, Some(true)) => "installed, healthy",
+                    (true, None) => "installed",
+                };
+                Item::new(
+                    if ok { Tone::Ok } else { Tone::Failed },
+                    format!("{} ({})", skill.name, state),
+                )
+            })
+            .collect();
+
+        let mut footer = Vec::new();
+        if report.issues.is_empty() {
+            footer.push(Item::new(Tone::Ok, "Issues: None"));
+        } else {
+            footer.push(Item::new(
+                Tone::Note,
+                "Run 'catalyst explain-issue <ID>' for details on any issue.",
+            ));
+        }
+        if report.level == StatusLevel::Ok {
+            footer.push(Item::new(Tone::Ok, "All systems operational! 🚀"));
+        } else if report.issues.iter().any(|i| i.auto_fixable) && self.fixed_issues.is_empty() {
+            footer.push(Item::new(
+                Tone::Warning,
+                "Run 'catalyst status --fix' to auto-repair fixable issues.",
+            ));
+        }
+
+        ReportView {
+            header,
+            sections: vec![
+                Section::of("Auto-fix results", Tone::Ok, self.fixed_issues),
+                Section::new("Binaries", binaries),
+                Section::new("Hooks", hooks),
+                Section::new("Skills", skills),
+                Section::new("Issues", issue_items(report)),
+            ],
+            footer,
+        }
+    }
+}
+
+impl Displayable for SyncReport {
+    fn view(&self) -> ReportView {
+        let header = if self.success() {
+            Item::new(
+                Tone::Ok,
+                "Sync complete: Catalyst is up to date and healthy",
+            )
+        } else if self.step_failed() {
+            Item::new(Tone::Error, "Sync failed")
+        } else {
+            Item::new(Tone::Warning, "Sync finished, but problems remain")
+        };
+
+        let steps = self
+            .steps
+            .iter()
+            .map(|step| {
+                let tone = match step.outcome {
+                    SyncOutcome::Ran => Tone::Ok,
+                    SyncOutcome::Skipped => Tone::Skipped,
+                    SyncOutcome::Failed => Tone::Failed,
+                };
+                Item::new(tone, format!("{}: {}", step.kind, step.detail))
+            })
+            .collect();
+
+        let installed = self
+            .init
+            .as_ref()
+            .map(|init| init.installed_skills.clone())
+            .unwrap_or_default();
+        let (updated, skipped): (Vec<String>, Vec<String>) = self
+            .update
+            .as_ref()
+            .map(|update| {
+                let updated = update
+                    .updated_skills
+                    .iter()
+                    .chain(&update.library_skills)
+                    .cloned()
+                    .collect();
+                let skipped = update
+                    .skipped_skills
+                    .iter()
+                    .map(|skipped| format!("{} - {}", skipped.name, skipped.reason))
+                    .collect();
+                (updated, skipped)
+            })
+            .unwrap_or_default();
+        let scripts: Vec<ScriptResult> = self
+            .init
+            .iter()
+            .flat_map(|init| &init.scripts)
+            .chain(self.update.iter().flat_map(|update| &update.scripts))
+            .cloned()
+            .collect();
+
+        let mut footer = Vec::new();
+        if self.status.level != StatusLevel::Ok {
+            footer.push(Item::new(
+                Tone::Note,
+                "Run 'catalyst status' for the full report, or 'catalyst explain-issue <ID>' for details.",
+            ));
+        }
+
+        ReportView {
+            header,
+            sections: vec![
+                Section::new("Steps", steps),
+                Section::of("Installed skills", Tone::Ok, &installed),
+                Section::of("Updated skills", Tone::Ok, &updated),
+                Section::of("Skipped skills (modified locally)", Tone::Warning, &skipped),
+                Section::of("Auto-fix results", Tone::Ok, &self.fixed_issues),
+                Section::new("Skill setup scripts", script_items(&scripts)),
+                Section::new("Remaining issues", issue_items(&self.status)),
+            ],
+            footer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{SkillSelection, SkippedSkill};
+    use std::str::FromStr;
+
+    fn update_report() -> UpdateReport {
+        let mut report = UpdateReport::new();
+        report.updated_skills = vec!["rust-developer".to_string()];
+        report.skipped_skills = vec![SkippedSkill {
+            name: "skill-developer".to_string(),
+            reason: "modified locally".to_string(),
+            current_hash: "a".to_string(),
+            expected_hash: "b".to_string(),
+        }];
+        report
+    }
+
+    #[test]
+    fn test_every_mode_shows_the_same_items() {
+        let report = update_report();
+        for mode in [
+            DisplayMode::Text,
+            DisplayMode::Table,
+            DisplayMode::Accessible,
+            DisplayMode::Json,
+        ] {
+            let out = report.render(mode, false).unwrap();
+            assert!(out.contains("rust-developer"), "{:?}: {}", mode, out);
+            assert!(out.contains("skill-developer"), "{:?}: {}", mode, out);
+        }
+
+        let text = report.render(DisplayMode::Text, false).unwrap();
+        assert!(text.starts_with("✅ Update completed successfully!\n"));
+        assert!(text.contains("Updated skills:\n  ✓ rust-developer\n"));
+        assert!(!text.contains("Errors:"), "empty sections are omitted");
+
+        let accessible = report.render(DisplayMode::Accessible, false).unwrap();
+        assert!(accessible.contains("Updated skills, 1 item:\n  Ok: rust-developer\n"));
+        assert!(!accessible.contains('✓'));
+
+        let table = report.render(DisplayMode::Table, false).unwrap();
+        let row = table
+            .lines()
+            .find(|line| line.contains("skill-developer"))
+            .unwrap();
+        assert!(row.starts_with("Skipped skills (modified locally)  warning"));
+    }
+
+    #[test]
+    fn test_init_dry_run_and_json() {
+        let mut report = InitReport::new();
+        report.dry_run = true;
+        report.planned_actions = vec!["Create .claude/hooks".to_string()];
+        let text = report.render(DisplayMode::Text, false).unwrap();
+        assert!(text.contains("Planned actions:\n  • Create .claude/hooks\n"));
+        assert!(text.ends_with("Run again without --dry-run to apply.\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.render(DisplayMode::Json, false).unwrap()).unwrap();
+        assert_eq!(json["planned_actions"][0], "Create .claude/hooks");
+
+        assert_eq!(
+            DisplayMode::from_str("Accessible").unwrap(),
+            DisplayMode::Accessible
+        );
+        assert!(DisplayMode::from_str("yaml").is_err());
+    }
+
+    #[test]
+    fn test_init_report_explains_skill_selection() {
+        let mut report = InitReport::new();
+        report.skill_selection = SkillSelection::NoDefaults;
+        let text = report.render(DisplayMode::Text, false).unwrap();
+        assert!(text.contains("Installed skills:\n  No skills installed (--no-default-skills)\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.render(DisplayMode::Json, false).unwrap()).unwrap();
+        assert_eq!(json["skill_selection"], "no_defaults");
+
+        report.skill_selection = SkillSelection::Explicit;
+        report.installed_skills = vec!["rust-developer".to_string()];
+        let text = report.render(DisplayMode::Text, false).unwrap();
+        assert!(text.contains("Installed skills:\n  ✓ rust-developer\n\n"));
+    }
+}
@@ -0,0 +1,252 @@
+//! Cache of precomputed skill-rules.json data for skill-activation-prompt
+//!
+//! Compiled regexes can't be stored, so the cache holds what lets the hook
+//! avoid compiling them: for each intent pattern, the literals one of which
+//! any match must contain (from `regex-syntax` prefix extraction). On each
+//! prompt only patterns whose literals appear in the prompt are compiled;
+//! the rest can't match and are skipped. Invalid patterns are remembered so
+//! they aren't re-parsed either.
+//!
+//! The cache lives in `~/.claude/hooks-state-rust/skill-rules-cache/`, one
+//! file per rules file, and is used only while the rules file's size and
+//! modification time match what was recorded. Set
+//! `CATALYST_RULES_CACHE=off` to bypass it.
+
+use crate::types::{CatalystError, Result, CATALYST_VERSION, CLAUDE_DIR, TRACKER_STATE_DIR};
+use regex_syntax::hir::literal::{ExtractKind, Extractor};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Directory under the hook state directory that holds cache files
+pub const RULES_CACHE_DIR: &str = "skill-rules-cache";
+
+/// Environment variable that disables the cache when set to `0`, `off`, or `false`
+pub const RULES_CACHE_ENV: &str = "CATALYST_RULES_CACHE";
+
+/// What is known about an intent pattern without compiling it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Prefilter {
+    /// Every match contains one of these (lowercase) literals
+    Literals { literals: Vec<String> },
+    /// No usable literals; the pattern must always be compiled
+    Always,
+    /// The pattern doesn't parse
+    Invalid { error: String },
+}
+
+impl Prefilter {
+    /// Analyze an intent pattern
+    pub fn for_pattern(pattern: &str) -> Self {
+        let hir = match regex_syntax::Parser::new().parse(pattern) {
+            Ok(hir) => hir,
+            Err(e) => {
+                return Prefilter::Invalid {
+                    error: e.to_string(),
+                }
+            }
+        };
+
+        let mut extractor = Extractor::new();
+        extractor.kind(ExtractKind::Prefix);
+        let seq = extractor.extract(&hir);
+        let Some(literals) = seq.literals() else {
+            return Prefilter::Always;
+        };
+
+        let mut lowered = Vec::with_capacity(literals.len());
+        for literal in literals {
+            // An empty prefix means any text may match. Partial UTF-8 and
+            // capital sigma (whose lowercase depends on context) can't be
+            // compared against a lowercased prompt reliably.
+            let Ok(text) = std::str::from_utf8(literal.as_bytes()) else {
+                return Prefilter::Always;
+            };
+            if text.is_empty() || text.contains('Σ') {
+                return Prefilter::Always;
+            }
+            lowered.push(text.to_lowercase());
+        }
+        if lowered.is_empty() {
+            return Prefilter::Always;
+        }
+        lowered.sort_unstable();
+        lowered.dedup();
+        Prefilter::Literals { literals: lowered }
+    }
+
+    /// Whether the pattern could match a prompt, given the prompt lowercased
+    pub fn may_match(&self, prompt_lower: &str) -> bool {
+        match self {
+            Prefilter::Literals { literals } => literals
+                .iter()
+                .any(|literal| prompt_lower.contains(literal)),
+            Prefilter::Always => true,
+            Prefilter::Invalid { .. } => false,
+        }
+    }
+}
+
+/// Cached data for one rules file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RulesCache {
+    /// Catalyst version that wrote the cache; other versions ignore it
+    pub version: String,
+
+    /// Size of the rules file when cached
+    pub len: u64,
+
+    /// Modification time of the rules file, in nanoseconds since the epoch
+    pub modified_ns: u128,
+
+    /// Prefilter for each intent pattern in the rules
+    pub prefilters: HashMap<String, Prefilter>,
+}
+
+impl RulesCache {
+    /// Build a cache entry for a rules file and its intent patterns
+    ///
+    /// Returns None if the rules file's metadata can't be read.
+    pub fn build<'a>(
+        rules_path: &Path,
+        patterns: impl IntoIterator<Item = &'a str>,
+    ) -> Option<Self> {
+        let (len, modified_ns) = file_stamp(rules_path)?;
+        let prefilters = patterns
+            .into_iter()
+            .map(|pattern| (pattern.to_string(), Prefilter::for_pattern(pattern)))
+            .collect();
+        Some(Self {
+            version: CATALYST_VERSION.to_string(),
+            len,
+            modified_ns,
+            prefilters,
+        })
+    }
+}
+
+/// Whether the cache is enabled in this environment
+pub fn enabled() -> bool {
+    !matches!(
+        std::env::var(RULES_CACHE_ENV)
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+            .as_str(),
+        "0" | "off" | "false"
+    )
+}
+
+/// Default cache directory, `~/.claude/hooks-state-rust/skill-rules-cache`
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| {
+        home.join(CLAUDE_DIR)
+            .join(TRACKER_STATE_DIR)
+            .join(RULES_CACHE_DIR)
+    })
+}
+
+/// Cache file for a rules file, named after a hash of its path
+pub fn cache_path(cache_dir: &Path, rules_path: &Path) -> PathBuf {
+    let rules_path = dunce::canonicalize(rules_path).unwrap_or_else(|_| rules_path.to_path_buf());
+    let digest = Sha256::digest(rules_path.to_string_lossy().as_bytes());
+    let name: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    cache_dir.join(format!("{}.json", name))
+}
+
+/// Load the cache for a rules file if it's still current
+///
+/// A missing, unreadable, or stale cache returns None, as does one written
+/// by another catalyst version.
+pub fn load(cache_dir: &Path, rules_path: &Path) -> Option<RulesCache> {
+    let content = fs::read_to_string(cache_path(cache_dir, rules_path)).ok()?;
+    let cache: RulesCache = serde_json::from_str(&content).ok()?;
+    let (len, modified_ns) = file_stamp(rules_path)?;
+    (cache.version == CATALYST_VERSION && cache.len == len && cache.modified_ns == modified_ns)
+        .then_some(cache)
+}
+
+/// Write the cache for a rules file
+///
+/// # Errors
+///
+/// Returns an error if the cache directory can't be created or the file
+/// can't be written
+pub fn store(cache_dir: &Path, rules_path: &Path, cache: &RulesCache) -> Result<()> {
+    fs::create_dir_all(cache_dir).map_err(|e| CatalystError::DirectoryCreationFailed {
+        path: cache_dir.to_path_buf(),
+        source: e,
+    })?;
+    let content = serde_json::to_string(cache).map_err(CatalystError::Json)?;
+    crate::init::write_file_atomic(&cache_path(cache_dir, rules_path), &content)?;
+    Ok(())
+}
+
+/// Size and modification time (ns since the epoch) of a file
+fn file_stamp(path: &Path) -> Option<(u64, u128)> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((metadata.len(), modified.as_nanos()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_prefilter_literals_are_necessary() {
+        let cases = [
+            ("(?i)add.*route", "please ADD a new Route", true),
+            ("(?i)add.*route", "delete the route", false),
+            ("(create|build) .*controller", "build a controller", true),
+            ("(create|build) .*controller", "write a controller", false),
+        ];
+        for (pattern, prompt, expected) in cases {
+            let prefilter = Prefilter::for_pattern(pattern);
+            assert!(
+                matches!(prefilter, Prefilter::Literals { .. }),
+                "{}",
+                pattern
+            );
+            assert_eq!(
+                prefilter.may_match(&prompt.to_lowercase()),
+                expected,
+                "{}",
+                pattern
+            );
+            // The prefilter never rules out a real match
+            if Regex::new(pattern).unwrap().is_match(prompt) {
+                assert!(prefilter.may_match(&prompt.to_lowercase()));
+            }
+        }
+
+        assert_eq!(Prefilter::for_pattern(".*test"), Prefilter::Always);
+        assert_eq!(Prefilter::for_pattern("\\w+ing"), Prefilter::Always);
+        let invalid = Prefilter::for_pattern("(unclosed");
+        assert!(matches!(invalid, Prefilter::Invalid { .. }));
+        assert!(!invalid.may_match("(unclosed"));
+    }
+
+    #[test]
+    fn test_cache_round_trip_and_staleness() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path().join("cache");
+        let rules_path = temp_dir.path().join("skill-rules.json");
+        fs::write(&rules_path, r#"{"version": "1.0", "skills": {}}"#).unwrap();
+
+        assert!(load(&cache_dir, &rules_path).is_none());
+        let cache = RulesCache::build(&rules_path, ["(?i)add.*route"]).unwrap();
+        store(&cache_dir, &rules_path, &cache).unwrap();
+        assert_eq!(load(&cache_dir, &rules_path), Some(cache));
+
+        // Editing the rules invalidates the cache
+        fs::write(&rules_path, r#"{"version": "1.0", "skills": {"new": {}}}"#).unwrap();
+        assert!(load(&cache_dir, &rules_path).is_none());
+    }
+}
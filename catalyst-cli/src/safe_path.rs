@@ -0,0 +1,271 @@
+//! Path checks for untrusted names and archive entries
+//!
+//! Skill IDs end up as directory names under `.claude/skills/` and the skill
+//! library, and archive entries (state archives, release tarballs, packed
+//! skills) end up as files under an extraction root. Both come from places
+//! catalyst doesn't control - a hand-edited hashes file, a downloaded
+//! archive - so they go through these checks before any path is joined:
+//!
+//! - no absolute paths, drive prefixes, `..`, `.`, or empty segments
+//! - no backslashes or colons, which Windows reads as separators or
+//!   stream names
+//! - after joining, the deepest existing ancestor must canonicalize to a
+//!   directory inside the root, so a symlink already on disk can't redirect
+//!   a write elsewhere
+//!
+//! Violations are reported as `PathTraversalDetected`.
+
+use crate::types::{CatalystError, Result};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+/// Check that a skill ID is a single, plain directory name
+///
+/// # Errors
+///
+/// Returns `PathTraversalDetected` if the ID is empty, hidden, contains a
+/// separator or control character, or is `.`/`..`
+pub fn validate_skill_id(skill_id: &str) -> Result<()> {
+    let plain = !skill_id.is_empty()
+        && !skill_id.starts_with('.')
+        && !skill_id
+            .chars()
+            .any(|c| matches!(c, '/' | '\\' | ':') || c.is_control())
+        && Path::new(skill_id)
+            .components()
+            .eq([Component::Normal(skill_id.as_ref())]);
+    if plain {
+        Ok(())
+    } else {
+        Err(CatalystError::PathTraversalDetected(format!(
+            "skill ID '{}' is not a plain directory name",
+            skill_id.escape_debug()
+        )))
+    }
+}
+
+/// Convert a `/`-separated archive path to a relative path that can't
+/// leave the directory it's joined to
+///
+/// A leading `./` and a trailing `/` (directory entries) are accepted.
+///
+/// # Errors
+///
+/// Returns `PathTraversalDetected` naming `what` and the path otherwise
+pub fn relative_path(path: &str, what: &str) -> Result<PathBuf> {
+    let trimmed = path.strip_prefix("./").unwrap_or(path);
+    let trimmed = trimmed.strip_suffix('/').unwrap_or(trimmed);
+    // Check the segments before joining them: splitting "/etc/passwd" would
+    // otherwise quietly yield the relative "etc/passwd"
+    let segments_ok = trimmed.split('/').all(|segment| {
+        !segment.is_empty()
+            && segment != "."
+            && segment != ".."
+            && !segment.contains(['\\', ':'])
+            && !segment.chars().any(char::is_control)
+    });
+    let relative: PathBuf = trimmed.split('/').collect();
+    let is_safe = segments_ok
+        && relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+    if is_safe {
+        Ok(relative)
+    } else {
+        Err(CatalystError::PathTraversalDetected(format!(
+            "{} '{}'",
+            what,
+            path.escape_debug()
+        )))
+    }
+}
+
+/// Join an archive path to `root`, refusing anything that would land
+/// outside it - including through a symlink already under `root`
+///
+/// # Errors
+///
+/// Returns `PathTraversalDetected` if the path is unsafe or resolves
+/// outside `root`
+pub fn join_within(root: &Path, path: &str, what: &str) -> Result<PathBuf> {
+    let destination = root.join(relative_path(path, what)?);
+    let Ok(canonical_root) = dunce::canonicalize(root) else {
+        // Nothing exists yet, so nothing on disk can redirect the path
+        return Ok(destination);
+    };
+    let existing = destination
+        .ancestors()
+        .find(|ancestor| fs::symlink_metadata(ancestor).is_ok())
+        .unwrap_or(root);
+    let resolved = dunce::canonicalize(existing).unwrap_or_else(|_| existing.to_path_buf());
+    if resolved.starts_with(&canonical_root) {
+        Ok(destination)
+    } else {
+        Err(CatalystError::PathTraversalDetected(format!(
+            "{} '{}' resolves to {}, outside {}",
+            what,
+            path.escape_debug(),
+            resolved.display(),
+            canonical_root.display()
+        )))
+    }
+}
+
+/// Check a tar (or zip, via bsdtar) archive before unpacking it
+///
+/// `tar` itself strips some dangerous names but not all, and versions
+/// differ, so entries are listed first: every name must pass
+/// [`relative_path`], and links aren't allowed at all.
+///
+/// # Errors
+///
+/// Returns `PathTraversalDetected` for an unsafe entry, or `InvalidConfig`
+/// if tar can't list the archive
+pub fn check_tar_archive(archive: &Path) -> Result<()> {
+    let list = |verbose: bool| -> Result<String> {
+        let output = Command::new("tar")
+            .arg(if verbose { "-tvf" } else { "-tf" })
+            .arg(archive)
+            .output()
+            .map_err(|e| CatalystError::InvalidConfig(format!("Could not run tar: {}", e)))?;
+        if !output.status.success() {
+            return Err(CatalystError::InvalidConfig(format!(
+                "tar could not list {}: {}",
+                archive.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    // `tar -C dir .` archives start with an entry for the root itself
+    let names = list(false)?;
+    for name in names
+        .lines()
+        .filter(|line| !matches!(*line, "" | "." | "./"))
+    {
+        relative_path(name, "archive entry")?;
+    }
+    // The first character of each long listing line is the entry type
+    if let Some(link) = list(true)?
+        .lines()
+        .find(|line| line.starts_with(['l', 'h']))
+    {
+        return Err(CatalystError::PathTraversalDetected(format!(
+            "archive contains a link: {}",
+            link.trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_skill_ids_and_relative_paths() {
+        for good in ["skill-developer", "rust_developer", "v2.skill"] {
+            assert!(validate_skill_id(good).is_ok(), "{:?} rejected", good);
+        }
+        for bad in [
+            "",
+            ".",
+            "..",
+            ".hidden",
+            "../evil",
+            "a/b",
+            "a\\b",
+            "/etc",
+            "C:evil",
+            "bad\0id",
+            "new\nline",
+        ] {
+            assert!(
+                matches!(
+                    validate_skill_id(bad),
+                    Err(CatalystError::PathTraversalDetected(_))
+                ),
+                "{:?} accepted",
+                bad
+            );
+        }
+
+        assert_eq!(
+            relative_path("./skills/a/SKILL.md", "entry").unwrap(),
+            Path::new("skills").join("a").join("SKILL.md")
+        );
+        assert_eq!(
+            relative_path("skills/", "entry").unwrap(),
+            Path::new("skills")
+        );
+        for bad in [
+            "../x",
+            "a/../../x",
+            "/abs",
+            "a//b",
+            "a/./b",
+            "C:/Windows",
+            "a\\..\\b",
+            "file:stream",
+            "",
+            "./",
+        ] {
+            assert!(relative_path(bad, "entry").is_err(), "{:?} accepted", bad);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_under_root_cannot_redirect_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("skill");
+        let outside = temp_dir.path().join("outside");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+
+        assert!(matches!(
+            join_within(&root, "link/payload", "archive entry"),
+            Err(CatalystError::PathTraversalDetected(_))
+        ));
+        assert!(join_within(&root, "plain/file", "archive entry").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_tar_archive_rejects_traversal_and_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let work = temp_dir.path().join("work");
+        fs::create_dir_all(work.join("dir")).unwrap();
+        fs::write(work.join("dir").join("catalyst"), "bin").unwrap();
+        let tar = |archive: &str, args: &[&str]| {
+            let status = Command::new("tar")
+                .current_dir(&work)
+                .arg("-cf")
+                .arg(temp_dir.path().join(archive))
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success());
+            temp_dir.path().join(archive)
+        };
+
+        assert!(check_tar_archive(&tar("good.tar", &["dir"])).is_ok());
+
+        std::os::unix::fs::symlink("/etc", work.join("etc-link")).unwrap();
+        assert!(matches!(
+            check_tar_archive(&tar("link.tar", &["etc-link"])),
+            Err(CatalystError::PathTraversalDetected(_))
+        ));
+
+        // GNU tar keeps "../" names with -P; bsdtar with -P too
+        let escaping = tar("escape.tar", &["-P", "../work/dir/catalyst"]);
+        assert!(matches!(
+            check_tar_archive(&escaping),
+            Err(CatalystError::PathTraversalDetected(_))
+        ));
+    }
+}
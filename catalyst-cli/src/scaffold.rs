@@ -0,0 +1,326 @@
+//! Scaffolding for custom skills (`catalyst skills new`)
+//!
+//! Creates `.claude/skills/<name>/SKILL.md` from a template, adds a matching
+//! entry to skill-rules.json, and records the new files in
+//! `.catalyst-hashes.json` so later updates treat them like any other
+//! installed skill.
+
+use crate::init::{activation_rule, add_skill_hashes, write_file_atomic};
+use crate::types::{CatalystError, Result, SKILLS_DIR, SKILL_RULES_FILE};
+use catalyst_core::jsonc;
+use catalyst_core::skill_manifest::Activation;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Longest skill name accepted
+pub const MAX_SKILL_NAME_LEN: usize = 64;
+
+/// Everything needed to scaffold a skill
+#[derive(Debug, Clone, Default)]
+pub struct NewSkill {
+    /// Skill ID and directory name (lowercase kebab-case)
+    pub name: String,
+
+    /// One-line description for the SKILL.md front matter
+    pub description: String,
+
+    /// Prompt keywords that activate the skill
+    pub keywords: Vec<String>,
+
+    /// Regular expressions matched against prompts
+    pub intent_patterns: Vec<String>,
+
+    /// Glob patterns for files the skill applies to
+    pub path_patterns: Vec<String>,
+}
+
+/// Check that a skill name is lowercase kebab-case
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` describing what is wrong with the name
+pub fn validate_skill_name(name: &str) -> Result<()> {
+    validate_kebab_name("skill", name)
+}
+
+/// Check that a skill or agent name is lowercase kebab-case
+pub(crate) fn validate_kebab_name(kind: &str, name: &str) -> Result<()> {
+    let invalid = |reason: &str| {
+        Err(CatalystError::InvalidConfig(format!(
+            "Invalid {} name '{}': {}",
+            kind, name, reason
+        )))
+    };
+
+    if name.is_empty() {
+        return invalid("name is empty");
+    }
+    if name.len() > MAX_SKILL_NAME_LEN {
+        return invalid(&format!("longer than {} characters", MAX_SKILL_NAME_LEN));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return invalid(&format!(
+            "use lowercase letters, digits, and hyphens (e.g. my-{})",
+            kind
+        ));
+    }
+    if name.starts_with('-') || name.ends_with('-') || name.contains("--") {
+        return invalid("hyphens must separate words");
+    }
+    Ok(())
+}
+
+/// Create a skill directory, its skill-rules.json entry, and its hashes
+///
+/// Everything is validated before anything is written, so a bad intent
+/// pattern or an unreadable skill-rules.json leaves the project untouched.
+///
+/// # Arguments
+///
+/// * `target_dir` - Project directory containing .claude
+/// * `skill` - Name, description, and triggers of the new skill
+///
+/// # Returns
+///
+/// Returns the path of the created SKILL.md
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the name or an intent pattern is invalid, the
+/// skill already exists, or skill-rules.json can't be parsed
+pub fn create_skill(target_dir: &Path, skill: &NewSkill) -> Result<PathBuf> {
+    validate_skill_name(&skill.name)?;
+    for pattern in &skill.intent_patterns {
+        regex::Regex::new(pattern).map_err(|e| {
+            CatalystError::InvalidConfig(format!("Invalid intent pattern '{}': {}", pattern, e))
+        })?;
+    }
+
+    let skill_dir = target_dir.join(SKILLS_DIR).join(&skill.name);
+    if skill_dir.exists() {
+        return Err(CatalystError::InvalidConfig(format!(
+            "Skill '{}' already exists at {}",
+            skill.name,
+            skill_dir.display()
+        )));
+    }
+
+    let rules_path = target_dir.join(SKILL_RULES_FILE);
+    let mut rules = read_rules(&rules_path)?;
+    let skills = rules
+        .as_object_mut()
+        .and_then(|root| {
+            root.entry("skills")
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+        })
+        .ok_or_else(|| {
+            CatalystError::InvalidConfig(format!(
+                "{} has no \"skills\" object",
+                rules_path.display()
+            ))
+        })?;
+    if skills.contains_key(&skill.name) {
+        return Err(CatalystError::InvalidConfig(format!(
+            "skill-rules.json already has an entry for '{}'",
+            skill.name
+        )));
+    }
+    let activation = Activation {
+        keywords: skill.keywords.clone(),
+        intent_patterns: skill.intent_patterns.clone(),
+        path_patterns: skill.path_patterns.clone(),
+    };
+    skills.insert(skill.name.clone(), activation_rule(&activation));
+
+    fs::create_dir_all(&skill_dir).map_err(|source| CatalystError::DirectoryCreationFailed {
+        path: skill_dir.clone(),
+        source,
+    })?;
+    let skill_md = skill_dir.join("SKILL.md");
+    write_file_atomic(&skill_md, &render_skill_md(skill))?;
+
+    let content = serde_json::to_string_pretty(&rules).map_err(CatalystError::Json)?;
+    write_file_atomic(&rules_path, &content)?;
+    add_skill_hashes(target_dir, &skill.name)?;
+
+    Ok(skill_md)
+}
+
+/// Read skill-rules.json, or start a fresh one if it doesn't exist
+///
+/// Comments and trailing commas are accepted and dropped, since the file is
+/// rewritten as plain JSON.
+pub(crate) fn read_rules(path: &Path) -> Result<Value> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(serde_json::json!({ "version": "1.0", "skills": {} }))
+        }
+        Err(source) => {
+            return Err(CatalystError::FileReadFailed {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    };
+
+    jsonc::from_str(&content)
+        .map_err(|e| CatalystError::InvalidConfig(format!("{}: {}", path.display(), e)))
+}
+
+/// SKILL.md template with front matter and the usual sections
+fn render_skill_md(skill: &NewSkill) -> String {
+    let title = skill
+        .name
+        .split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut triggers = String::new();
+    for keyword in &skill.keywords {
+        triggers.push_str(&format!("- Prompts mentioning \"{}\"\n", keyword));
+    }
+    for pattern in &skill.path_patterns {
+        triggers.push_str(&format!("- Working with files matching `{}`\n", pattern));
+    }
+    if triggers.is_empty() {
+        triggers.push_str("- TODO: describe when this skill applies\n");
+    }
+
+    format!(
+        "---\nname: {name}\ndescription: {description}\n---\n\n\
+         # {title}\n\n\
+         ## Purpose\n\n\
+         TODO: what this skill teaches and why it exists.\n\n\
+         ## When to Use This Skill\n\n\
+         {triggers}\n\
+         ## Guidelines\n\n\
+         TODO: the conventions to follow. Keep this file under 500 lines and\n\
+         move detailed material into resource files next to it.\n",
+        name = skill.name,
+        description = yaml_scalar(&skill.description.replace('\n', " ")),
+        title = title,
+        triggers = triggers,
+    )
+}
+
+/// Quote a front matter value when YAML would otherwise misread it
+fn yaml_scalar(value: &str) -> String {
+    let needs_quotes = value.contains(": ")
+        || value.contains(" #")
+        || value.ends_with(':')
+        || value.starts_with(|c: char| "!&*-?[]{}|>'\"%@`#,".contains(c));
+    if needs_quotes {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn new_skill(name: &str) -> NewSkill {
+        NewSkill {
+            name: name.to_string(),
+            description: "Billing conventions".to_string(),
+            keywords: vec!["invoice".to_string()],
+            intent_patterns: vec!["(create|fix).*?invoice".to_string()],
+            path_patterns: vec!["src/billing/**/*.ts".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_validate_skill_name() {
+        assert!(validate_skill_name("billing-rules2").is_ok());
+        for bad in ["", "Billing", "billing_rules", "-billing", "a--b", "../x"] {
+            assert!(validate_skill_name(bad).is_err(), "{:?} accepted", bad);
+        }
+    }
+
+    #[test]
+    fn test_create_skill_updates_rules_and_hashes() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let skills_dir = target.join(SKILLS_DIR);
+        fs::create_dir_all(&skills_dir).unwrap();
+        fs::write(
+            target.join(SKILL_RULES_FILE),
+            "// Customize pathPatterns for your project structure\n\
+             {\"version\": \"1.0\", \"skills\": {\"existing\": {\"type\": \"skill\"}}}",
+        )
+        .unwrap();
+        fs::write(
+            skills_dir.join(".catalyst-hashes.json"),
+            r#"{"existing/SKILL.md": "abc"}"#,
+        )
+        .unwrap();
+
+        let skill_md = create_skill(target, &new_skill("billing")).unwrap();
+        let content = fs::read_to_string(&skill_md).unwrap();
+        assert!(content.starts_with("---\nname: billing\ndescription: Billing conventions\n"));
+        assert!(content.contains("# Billing"));
+        assert_eq!(yaml_scalar("Use when: billing"), "\"Use when: billing\"");
+
+        let rules: Value =
+            serde_json::from_str(&fs::read_to_string(target.join(SKILL_RULES_FILE)).unwrap())
+                .unwrap();
+        assert!(rules["skills"]["existing"].is_object());
+        assert_eq!(
+            rules["skills"]["billing"]["promptTriggers"]["keywords"][0],
+            "invoice"
+        );
+        assert_eq!(rules["skills"]["billing"]["enabled"], true);
+
+        let hashes: HashMap<String, String> = serde_json::from_str(
+            &fs::read_to_string(skills_dir.join(".catalyst-hashes.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(hashes["existing/SKILL.md"], "abc");
+        assert_eq!(hashes.len(), 2);
+
+        // A second run must not clobber the first
+        assert!(create_skill(target, &new_skill("billing")).is_err());
+    }
+
+    #[test]
+    fn test_created_skill_rule_passes_lint() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir_all(target.join("src/billing")).unwrap();
+        fs::write(target.join("src/billing/invoice.ts"), "").unwrap();
+
+        create_skill(target, &new_skill("billing")).unwrap();
+
+        let report = crate::lint::lint_skill_rules(target).unwrap();
+        assert_eq!(report.skills_checked, 1);
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+    }
+
+    #[test]
+    fn test_create_skill_rejects_bad_pattern_without_writing() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+
+        let mut skill = new_skill("broken");
+        skill.intent_patterns = vec!["(unclosed".to_string()];
+        assert!(create_skill(target, &skill).is_err());
+        assert!(!target.join(SKILLS_DIR).join("broken").exists());
+        assert!(!target.join(SKILL_RULES_FILE).exists());
+    }
+}
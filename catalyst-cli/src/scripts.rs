@@ -0,0 +1,324 @@
+//! Skill post-install scripts
+//!
+//! A skill may ship an `install.sh` (Unix) or `install.ps1` (Windows) next
+//! to its SKILL.md for setup such as installing a companion CLI. Catalyst
+//! never runs one without the user confirming it; callers decide whether to
+//! ask (see [`scripts_disabled_by_default`]) and pass the answer in.
+//!
+//! Scripts that do run are contained as far as a plain child process allows:
+//!
+//! - the script must be a regular file inside the skill directory (a
+//!   symlink pointing elsewhere is refused)
+//! - it runs from the skill directory with stdin closed and an environment
+//!   reduced to [`PASSTHROUGH_ENV`] plus `CATALYST_SKILL_DIR` and
+//!   `CATALYST_PROJECT_DIR`
+//! - it is killed after [`SCRIPT_TIMEOUT`]
+//! - stdout and stderr are captured (up to [`MAX_OUTPUT_BYTES`]) into the
+//!   report instead of going to the terminal
+
+use crate::powershell;
+use crate::process;
+use crate::types::{Platform, ScriptResult, SKILLS_DIR};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// How long a post-install script may run before it is killed
+pub const SCRIPT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Captured output beyond this many bytes is dropped
+pub const MAX_OUTPUT_BYTES: usize = 64 * 1024;
+
+/// Environment variables a script inherits; everything else is cleared
+pub const PASSTHROUGH_ENV: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LANG",
+    "TMPDIR",
+    "TEMP",
+    "TMP",
+    "USERPROFILE",
+    "SystemRoot",
+    "ComSpec",
+    "PATHEXT",
+];
+
+/// Asked before each script runs, with the skill ID and script path
+pub type ConfirmScript<'a> = &'a mut dyn FnMut(&str, &Path) -> bool;
+
+/// Whether scripts should be skipped without asking
+///
+/// True in CI (the `CI` variable is set to anything but "false" or "0") and
+/// when stdin isn't a terminal, since nobody is there to confirm.
+pub fn scripts_disabled_by_default() -> bool {
+    use std::io::IsTerminal;
+
+    let ci = std::env::var("CI")
+        .map(|value| !matches!(value.as_str(), "" | "false" | "0"))
+        .unwrap_or(false);
+    ci || !std::io::stdin().is_terminal()
+}
+
+/// Post-install script a skill ships for this platform, if any
+pub fn find_script(skill_dir: &Path, platform: Platform) -> Option<PathBuf> {
+    let name = match platform {
+        Platform::Windows => "install.ps1",
+        _ => "install.sh",
+    };
+    let script = skill_dir.join(name);
+    script.is_file().then_some(script)
+}
+
+/// Skills among `skill_ids` that ship a post-install script, with the script
+pub fn pending_scripts(
+    target_dir: &Path,
+    skill_ids: &[String],
+    platform: Platform,
+) -> Vec<(String, PathBuf)> {
+    skill_ids
+        .iter()
+        .filter_map(|skill_id| {
+            let skill_dir = target_dir.join(SKILLS_DIR).join(skill_id);
+            find_script(&skill_dir, platform).map(|script| (skill_id.clone(), script))
+        })
+        .collect()
+}
+
+/// Run or skip the post-install scripts of freshly installed skills
+///
+/// # Arguments
+///
+/// * `target_dir` - Project directory
+/// * `skill_ids` - Skills that were just installed or updated
+/// * `platform` - Platform, which decides between install.sh and install.ps1
+/// * `confirm` - Asked once per script; `None` skips every script
+///
+/// # Returns
+///
+/// Returns one result per skill that ships a script
+pub fn run_scripts(
+    target_dir: &Path,
+    skill_ids: &[String],
+    platform: Platform,
+    mut confirm: Option<ConfirmScript>,
+) -> Vec<ScriptResult> {
+    pending_scripts(target_dir, skill_ids, platform)
+        .into_iter()
+        .map(|(skill, script)| {
+            let approved = match confirm.as_mut() {
+                Some(confirm) => confirm(&skill, &script),
+                None => false,
+            };
+            if approved {
+                run_script(target_dir, &skill, &script, platform)
+            } else {
+                skipped(&skill, &script, "Not run (scripts disabled or declined)")
+            }
+        })
+        .collect()
+}
+
+/// Run one post-install script under the sandboxing rules above
+pub fn run_script(
+    target_dir: &Path,
+    skill: &str,
+    script: &Path,
+    platform: Platform,
+) -> ScriptResult {
+    let Some(skill_dir) = script.parent() else {
+        return skipped(skill, script, "Script has no parent directory");
+    };
+    if let Err(reason) = check_contained(skill_dir, script) {
+        return skipped(skill, script, &reason);
+    }
+
+    let mut output_file = match tempfile::tempfile() {
+        Ok(file) => file,
+        Err(e) => return skipped(skill, script, &format!("Could not capture output: {}", e)),
+    };
+    let (stdout, stderr) = match (output_file.try_clone(), output_file.try_clone()) {
+        (Ok(stdout), Ok(stderr)) => (stdout, stderr),
+        (Err(e), _) | (_, Err(e)) => {
+            return skipped(skill, script, &format!("Could not capture output: {}", e))
+        }
+    };
+
+    let mut command = match platform {
+        Platform::Windows => {
+            let mut command = Command::new(powershell::host(None).program());
+            command.args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-ExecutionPolicy",
+                "Bypass",
+                "-File",
+            ]);
+            command
+        }
+        _ => Command::new("sh"),
+    };
+    command
+        .arg(script)
+        .current_dir(skill_dir)
+        .env_clear()
+        .envs(
+            PASSTHROUGH_ENV
+                .iter()
+                .filter_map(|name| std::env::var_os(name).map(|value| (*name, value))),
+        )
+        .env("CATALYST_SKILL_DIR", skill_dir)
+        .env("CATALYST_PROJECT_DIR", target_dir)
+        .stdin(Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr);
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => return skipped(skill, script, &format!("Could not start script: {}", e)),
+    };
+
+    let (status, timed_out) = match process::wait_timeout(&mut child, SCRIPT_TIMEOUT) {
+        Ok(Some(status)) => (Some(status), false),
+        Ok(None) => (None, true),
+        Err(_) => (None, false),
+    };
+
+    let mut output = read_output(&mut output_file);
+    if timed_out {
+        output.push_str(&format!(
+            "\n[killed after {} seconds]",
+            SCRIPT_TIMEOUT.as_secs()
+        ));
+    }
+
+    ScriptResult {
+        skill: skill.to_string(),
+        script: script.to_path_buf(),
+        ran: true,
+        success: !timed_out && status.map(|s| s.success()).unwrap_or(false),
+        exit_code: status.and_then(|s| s.code()),
+        output,
+    }
+}
+
+/// Refuse scripts that resolve outside their skill directory
+fn check_contained(skill_dir: &Path, script: &Path) -> std::result::Result<(), String> {
+    let skill_dir = skill_dir
+        .canonicalize()
+        .map_err(|e| format!("Could not resolve {}: {}", skill_dir.display(), e))?;
+    let resolved = script
+        .canonicalize()
+        .map_err(|e| format!("Could not resolve {}: {}", script.display(), e))?;
+
+    if resolved.parent() != Some(skill_dir.as_path()) {
+        return Err(format!(
+            "Refusing to run {}: it resolves outside the skill directory",
+            script.display()
+        ));
+    }
+    if !fs::metadata(&resolved)
+        .map(|m| m.is_file())
+        .unwrap_or(false)
+    {
+        return Err(format!("{} is not a regular file", script.display()));
+    }
+    Ok(())
+}
+
+fn read_output(file: &mut fs::File) -> String {
+    let mut bytes = Vec::new();
+    if file.seek(SeekFrom::Start(0)).is_ok() {
+        let _ = file
+            .take(MAX_OUTPUT_BYTES as u64 + 1)
+            .read_to_end(&mut bytes);
+    }
+
+    let truncated = bytes.len() > MAX_OUTPUT_BYTES;
+    bytes.truncate(MAX_OUTPUT_BYTES);
+    let mut output = String::from_utf8_lossy(&bytes).trim_end().to_string();
+    if truncated {
+        output.push_str("\n[output truncated]");
+    }
+    output
+}
+
+fn skipped(skill: &str, script: &Path, reason: &str) -> ScriptResult {
+    ScriptResult {
+        skill: skill.to_string(),
+        script: script.to_path_buf(),
+        ran: false,
+        success: false,
+        exit_code: None,
+        output: reason.to_string(),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn skill_with_script(target_dir: &Path, skill: &str, script: &str) -> PathBuf {
+        let skill_dir = target_dir.join(SKILLS_DIR).join(skill);
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Skill").unwrap();
+        let path = skill_dir.join("install.sh");
+        fs::write(&path, script).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_run_scripts_only_with_confirmation() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        skill_with_script(
+            target,
+            "with-script",
+            "echo \"setup in $(basename \"$PWD\")\"\necho oops >&2\necho \"secret=$CATALYST_TEST_SECRET\"\nexit 3\n",
+        );
+        fs::create_dir_all(target.join(SKILLS_DIR).join("no-script")).unwrap();
+        let skills = vec!["with-script".to_string(), "no-script".to_string()];
+
+        // Without a confirmation callback nothing runs
+        let results = run_scripts(target, &skills, Platform::Linux, None);
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].ran);
+
+        std::env::set_var("CATALYST_TEST_SECRET", "leaked");
+        let mut asked = Vec::new();
+        let mut confirm = |skill: &str, _: &Path| {
+            asked.push(skill.to_string());
+            true
+        };
+        let results = run_scripts(target, &skills, Platform::Linux, Some(&mut confirm));
+        std::env::remove_var("CATALYST_TEST_SECRET");
+
+        assert_eq!(asked, vec!["with-script".to_string()]);
+        let result = &results[0];
+        assert!(result.ran && !result.success);
+        assert_eq!(result.exit_code, Some(3));
+        assert!(result.output.contains("setup in with-script"));
+        assert!(result.output.contains("oops"));
+        assert!(result.output.contains("secret=") && !result.output.contains("leaked"));
+    }
+
+    #[test]
+    fn test_run_script_refuses_symlink_outside_skill() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let outside = target.join("elsewhere.sh");
+        fs::write(&outside, "echo hi\n").unwrap();
+
+        let skill_dir = target.join(SKILLS_DIR).join("sneaky");
+        fs::create_dir_all(&skill_dir).unwrap();
+        std::os::unix::fs::symlink(&outside, skill_dir.join("install.sh")).unwrap();
+
+        let script = find_script(&skill_dir, Platform::Linux).unwrap();
+        let result = run_script(target, "sneaky", &script, Platform::Linux);
+        assert!(!result.ran);
+        assert!(result.output.contains("outside the skill directory"));
+    }
+}
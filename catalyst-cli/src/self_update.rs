@@ -0,0 +1,393 @@
+//! `catalyst self-update`
+//!
+//! Checks the latest GitHub release, downloads the archive for this
+//! machine's target triple (see [`crate::target`]), verifies it, and swaps
+//! it in for the running executable.
+//!
+//! Downloads go through `curl` and archives are unpacked with `tar`, both of
+//! which ship with every supported platform (Windows 10+ included), so the
+//! CLI doesn't carry an HTTP or archive stack.
+//!
+//! Every release publishes a `SHA256SUMS` file, which is required. Builds
+//! compiled with `CATALYST_RELEASE_PUBLIC_KEY` (a minisign public key)
+//! additionally require a `<archive>.minisig` signature.
+
+use crate::safe_path;
+use crate::target::TargetTriple;
+use crate::types::{CatalystError, Result, CATALYST_VERSION};
+use catalyst_core::fs_ops::{self, Fs, PermissionPolicy};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::{NamedTempFile, TempDir};
+
+/// Minisign public key release archives are signed with, if this build has one
+pub const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("CATALYST_RELEASE_PUBLIC_KEY");
+
+/// Name of the checksum file attached to every release
+pub const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// A GitHub release, as returned by the releases API
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// A file attached to a release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// Version number of the release, without the leading "v"
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    fn asset(&self, name: &str) -> Option<&ReleaseAsset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+
+    /// Archive for a target, preferring the target-triple name over the
+    /// older `<arch>-<os>` one
+    pub fn archive_for(&self, target: &TargetTriple) -> Option<&ReleaseAsset> {
+        target
+            .archive_names()
+            .iter()
+            .find_map(|name| self.asset(name))
+    }
+}
+
+/// Result of `catalyst self-update`
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfUpdateReport {
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+
+    /// Target triple the archive was chosen for
+    pub target: String,
+
+    /// Release archive for this target (None if the release has none)
+    pub asset: Option<ReleaseAsset>,
+
+    /// Whether the executable was replaced
+    pub updated: bool,
+
+    /// Whether a signature was verified (false if this build has no key)
+    pub signature_verified: bool,
+
+    /// Executable that was (or would be) replaced
+    pub executable: PathBuf,
+}
+
+/// Check for a newer release and, unless `check_only`, install it
+///
+/// # Arguments
+///
+/// * `check_only` - Only report whether an update is available
+///
+/// # Returns
+///
+/// Returns a report of the versions involved and what was done
+///
+/// # Errors
+///
+/// Returns `UnsupportedPlatform` if no archives are published for this
+/// machine, and `SelfUpdateFailed` if the release can't be fetched, has no
+/// archive for this target, or fails checksum or signature verification.
+/// The running executable is untouched in all of these cases.
+pub fn self_update(check_only: bool) -> Result<SelfUpdateReport> {
+    let executable = std::env::current_exe()
+        .and_then(|exe| exe.canonicalize())
+        .map_err(|e| failed(format!("Could not locate the running executable: {}", e)))?;
+
+    let target = TargetTriple::host()?;
+    let release = fetch_latest_release()?;
+    let mut report = SelfUpdateReport {
+        current_version: CATALYST_VERSION.to_string(),
+        latest_version: release.version().to_string(),
+        update_available: is_newer(release.version(), CATALYST_VERSION),
+        target: target.to_string(),
+        asset: release.archive_for(&target).cloned(),
+        updated: false,
+        signature_verified: false,
+        executable,
+    };
+    if check_only || !report.update_available {
+        return Ok(report);
+    }
+
+    let asset = report.asset.clone().ok_or_else(|| {
+        failed(format!(
+            "Release {} has no archive for {} (expected {})",
+            release.tag_name,
+            target,
+            target.archive_name()
+        ))
+    })?;
+
+    let work_dir = TempDir::new().map_err(CatalystError::Io)?;
+    let archive = work_dir.path().join(&asset.name);
+    download(&asset.browser_download_url, &archive)?;
+
+    verify_checksum(&release, &archive, &asset.name, work_dir.path())?;
+    report.signature_verified = verify_signature(&release, &archive, &asset.name)?;
+
+    let extract_dir = work_dir.path().join("extracted");
+    let new_executable = extract_executable(&archive, &extract_dir)?;
+    replace_executable(&new_executable, &report.executable)?;
+    report.updated = true;
+
+    Ok(report)
+}
+
+/// Fetch the latest release from the GitHub API
+pub fn fetch_latest_release() -> Result<Release> {
+    let url = format!(
+        "{}/releases/latest",
+        env!("CARGO_PKG_REPOSITORY")
+            .replace("https://github.com/", "https://api.github.com/repos/")
+    );
+    let output = curl(&["-H", "Accept: application/vnd.github+json", &url])?;
+    serde_json::from_slice(&output)
+        .map_err(|e| failed(format!("Unexpected response from {}: {}", url, e)))
+}
+
+/// Whether `latest` is a higher version than `current`
+///
+/// Compares dot-separated numeric components; pre-release suffixes
+/// ("-rc.1") are ignored.
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    }
+
+    let (mut latest, mut current) = (parts(latest), parts(current));
+    let len = latest.len().max(current.len());
+    latest.resize(len, 0);
+    current.resize(len, 0);
+    latest > current
+}
+
+/// Find the checksum for `name` in a `SHA256SUMS` file
+pub fn find_checksum<'a>(checksums: &'a str, name: &str) -> Option<&'a str> {
+    checksums.lines().find_map(|line| {
+        let (hash, file) = line.split_once(char::is_whitespace)?;
+        // sha256sum marks binary-mode entries with a leading '*'
+        (file.trim_start().trim_start_matches('*') == name).then_some(hash)
+    })
+}
+
+fn verify_checksum(release: &Release, archive: &Path, name: &str, work_dir: &Path) -> Result<()> {
+    let checksums_asset = release.asset(CHECKSUMS_ASSET).ok_or_else(|| {
+        failed(format!(
+            "Release {} has no {}; refusing to install an unverified archive",
+            release.tag_name, CHECKSUMS_ASSET
+        ))
+    })?;
+    let checksums_path = work_dir.join(CHECKSUMS_ASSET);
+    download(&checksums_asset.browser_download_url, &checksums_path)?;
+    let checksums = fs::read_to_string(&checksums_path).map_err(CatalystError::Io)?;
+
+    let expected = find_checksum(&checksums, name)
+        .ok_or_else(|| failed(format!("{} has no entry for {}", CHECKSUMS_ASSET, name)))?;
+    let content = fs::read(archive).map_err(CatalystError::Io)?;
+    let actual = format!("{:x}", Sha256::digest(&content));
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(CatalystError::HashMismatch(format!(
+            "{}: expected {}, downloaded {}",
+            name, expected, actual
+        )));
+    }
+    Ok(())
+}
+
+/// Verify the archive's minisign signature if this build has a release key
+///
+/// Returns false when there is no key to verify against.
+fn verify_signature(release: &Release, archive: &Path, name: &str) -> Result<bool> {
+    let Some(public_key) = RELEASE_PUBLIC_KEY else {
+        return Ok(false);
+    };
+
+    let signature_name = format!("{}.minisig", name);
+    let signature_asset = release
+        .asset(&signature_name)
+        .ok_or_else(|| failed(format!("Release has no signature ({})", signature_name)))?;
+    let signature = String::from_utf8(curl(&[&signature_asset.browser_download_url])?)
+        .map_err(|_| failed(format!("{} is not a minisign signature", signature_name)))?;
+
+    let public_key = minisign_verify::PublicKey::from_base64(public_key)
+        .map_err(|e| failed(format!("Invalid built-in release key: {}", e)))?;
+    let signature = minisign_verify::Signature::decode(&signature)
+        .map_err(|e| failed(format!("Invalid signature {}: {}", signature_name, e)))?;
+    let content = fs::read(archive).map_err(CatalystError::Io)?;
+    public_key
+        .verify(&content, &signature, false)
+        .map_err(|e| CatalystError::VerificationFailed(format!("{}: {}", name, e)))?;
+    Ok(true)
+}
+
+/// Unpack a release archive and find the catalyst executable in it
+fn extract_executable(archive: &Path, extract_dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(extract_dir).map_err(|e| CatalystError::DirectoryCreationFailed {
+        path: extract_dir.to_path_buf(),
+        source: e,
+    })?;
+    safe_path::check_tar_archive(archive)?;
+
+    let status = Command::new("tar")
+        .arg("-xf")
+        .arg(archive)
+        .arg("-C")
+        .arg(extract_dir)
+        .status()
+        .map_err(|e| failed(format!("Could not run tar: {}", e)))?;
+    if !status.success() {
+        return Err(failed(format!(
+            "tar could not unpack {}",
+            archive.display()
+        )));
+    }
+
+    let executable = if cfg!(windows) {
+        "catalyst.exe"
+    } else {
+        "catalyst"
+    };
+    walkdir::WalkDir::new(extract_dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .find(|entry| entry.file_type().is_file() && entry.file_name() == executable)
+        .map(|entry| entry.into_path())
+        .ok_or_else(|| failed(format!("{} not found in the release archive", executable)))
+}
+
+/// Atomically replace `current` with `new_executable`
+///
+/// The new binary is staged next to `current` and renamed over it, so the
+/// path always holds a complete executable. Windows won't replace a
+/// running executable, so there the current one is first renamed aside to
+/// `<name>.old`, which the next update removes.
+pub fn replace_executable(new_executable: &Path, current: &Path) -> Result<()> {
+    let dir = current
+        .parent()
+        .ok_or_else(|| CatalystError::InvalidPath(current.display().to_string()))?;
+    let write_error = |e| CatalystError::FileWriteFailed {
+        path: current.to_path_buf(),
+        source: e,
+    };
+
+    let mut temp = NamedTempFile::new_in(dir).map_err(write_error)?;
+    let mut source = fs::File::open(new_executable).map_err(|e| CatalystError::FileReadFailed {
+        path: new_executable.to_path_buf(),
+        source: e,
+    })?;
+    std::io::copy(&mut source, &mut temp).map_err(write_error)?;
+
+    if cfg!(windows) {
+        let old = current.with_extension("old");
+        let _ = fs::remove_file(&old);
+        fs::rename(current, &old).map_err(write_error)?;
+    }
+
+    Fs::read_write()
+        .persist_with_policy(
+            temp,
+            current,
+            fs_ops::DEFAULT_EXECUTABLE_MODE,
+            PermissionPolicy::Preserve,
+        )
+        .map_err(write_error)?;
+    Ok(())
+}
+
+fn download(url: &str, destination: &Path) -> Result<()> {
+    let destination = destination.to_string_lossy();
+    curl(&["-o", &destination, url]).map(|_| ())
+}
+
+/// Run curl, failing on HTTP errors, and return its stdout
+pub(crate) fn curl(args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location"])
+        .args([
+            "--user-agent",
+            concat!("catalyst/", env!("CARGO_PKG_VERSION")),
+        ])
+        .args(args)
+        .output()
+        .map_err(|e| failed(format!("Could not run curl: {}", e)))?;
+    if !output.status.success() {
+        return Err(failed(format!(
+            "Download failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+fn failed(message: String) -> CatalystError {
+    CatalystError::SelfUpdateFailed(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer_compares_numerically() {
+        assert!(is_newer("0.10.0", "0.9.3"));
+        assert!(is_newer("v1.0.0", "0.1.0"));
+        assert!(is_newer("1.0.1", "1.0"));
+        assert!(!is_newer("1.0.0", "1.0.0"));
+        assert!(!is_newer("1.0.0-rc.1", "1.0.0"));
+        assert!(!is_newer("0.1.0", "0.2.0"));
+    }
+
+    #[test]
+    fn test_find_checksum() {
+        let sums = "abc123  catalyst-x86_64-linux.tar.gz\n\
+                    def456 *catalyst-x86_64-windows.zip\n";
+        assert_eq!(
+            find_checksum(sums, "catalyst-x86_64-linux.tar.gz"),
+            Some("abc123")
+        );
+        assert_eq!(
+            find_checksum(sums, "catalyst-x86_64-windows.zip"),
+            Some("def456")
+        );
+        assert_eq!(find_checksum(sums, "catalyst-aarch64-macos.tar.gz"), None);
+    }
+
+    #[test]
+    fn test_replace_executable_swaps_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let current = temp_dir.path().join("catalyst");
+        let new_executable = temp_dir.path().join("new-catalyst");
+        fs::write(&current, "old").unwrap();
+        fs::write(&new_executable, "new").unwrap();
+
+        replace_executable(&new_executable, &current).unwrap();
+        assert_eq!(fs::read_to_string(&current).unwrap(), "new");
+
+        #[cfg(unix)]
+        assert_eq!(
+            fs_ops::file_mode(&current).map(|mode| mode & 0o111),
+            Some(0o111)
+        );
+    }
+}
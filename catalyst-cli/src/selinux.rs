@@ -0,0 +1,122 @@
+//! SELinux labeling for generated hook scripts
+//!
+//! On SELinux systems a hook wrapper with the wrong security context (for
+//! example one created under /tmp and moved into place) can be denied
+//! execution, and Claude Code reports nothing. Catalyst copies the context of
+//! the hooks directory onto each wrapper it writes and `catalyst status`
+//! flags wrappers whose type differs from the directory's.
+//!
+//! Contexts are read and set with the standard tools (`stat`, `chcon`,
+//! `restorecon`) so no SELinux libraries are needed. Everything here is a
+//! no-op when SELinux is not enabled.
+
+use crate::types::{CatalystError, Result};
+use catalyst_core::fs_ops::{Fs, Mode};
+use std::path::Path;
+use std::process::Command;
+
+/// Present when SELinux is enabled; holds `1` when enforcing
+const ENFORCE_PATH: &str = "/sys/fs/selinux/enforce";
+
+/// Whether SELinux is enabled (enforcing or permissive)
+pub fn is_enabled() -> bool {
+    is_enabled_with(&Fs::read_only())
+}
+
+/// [`is_enabled`], reading sysfs through `fs`
+pub fn is_enabled_with<M: Mode>(fs: &Fs<M>) -> bool {
+    cfg!(target_os = "linux") && fs.exists(Path::new(ENFORCE_PATH))
+}
+
+/// Whether SELinux is enforcing, so mislabeled files are actually denied
+pub fn is_enforcing() -> bool {
+    let fs = Fs::read_only();
+    is_enabled_with(&fs)
+        && fs
+            .read_to_string(Path::new(ENFORCE_PATH))
+            .map(|value| value.trim() == "1")
+            .unwrap_or(false)
+}
+
+/// Read the security context of a file (e.g. `unconfined_u:object_r:user_home_t:s0`)
+///
+/// Returns None if the context can't be read or the file has none.
+pub fn file_context(path: &Path) -> Option<String> {
+    let output = Command::new("stat")
+        .args(["-c", "%C"])
+        .arg(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let context = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (context_type(&context).is_some()).then_some(context)
+}
+
+/// Extract the type field from a context (`user:role:type[:level]`)
+pub fn context_type(context: &str) -> Option<&str> {
+    context.split(':').nth(2).filter(|t| !t.is_empty())
+}
+
+/// Whether a file's context type differs from the one it should have
+pub fn is_mislabeled(actual: &str, expected: &str) -> bool {
+    match (context_type(actual), context_type(expected)) {
+        (Some(actual), Some(expected)) => actual != expected,
+        _ => false,
+    }
+}
+
+/// Give a file the security context of a reference path
+///
+/// Tries `chcon --reference` first and falls back to `restorecon`, which
+/// applies the policy's default label for the path. Does nothing if SELinux
+/// is not enabled.
+///
+/// # Errors
+///
+/// Returns `HookInstallationFailed` if neither tool could relabel the file.
+pub fn copy_context(reference: &Path, path: &Path) -> Result<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+
+    let mut reference_arg = std::ffi::OsString::from("--reference=");
+    reference_arg.push(reference);
+    if run(Command::new("chcon").arg(reference_arg).arg(path)) {
+        return Ok(());
+    }
+    if run(Command::new("restorecon").arg(path)) {
+        return Ok(());
+    }
+
+    Err(CatalystError::HookInstallationFailed(format!(
+        "Failed to set the SELinux context of {} (tried chcon and restorecon)",
+        path.display()
+    )))
+}
+
+fn run(command: &mut Command) -> bool {
+    command
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mislabel_compares_types() {
+        let dir = "unconfined_u:object_r:user_home_t:s0";
+        assert_eq!(context_type(dir), Some("user_home_t"));
+        assert!(!is_mislabeled("system_u:object_r:user_home_t:s0", dir));
+        assert!(is_mislabeled("unconfined_u:object_r:user_tmp_t:s0", dir));
+
+        // Unreadable contexts are never reported
+        assert!(!is_mislabeled("?", dir));
+        assert_eq!(context_type("?"), None);
+    }
+}
@@ -0,0 +1,385 @@
+//! Recap of a session's work for the session-digest hook
+//!
+//! When Claude stops, the `session-digest` hook reads what the tracker
+//! recorded for the session (edited files by category, async code left
+//! without a try/catch) and what cargo-check and test-runner logged in the
+//! check log, and hands Claude a few lines to fold into its final summary.
+//!
+//! Edits come from the project's tracker backend (see
+//! [`crate::modification_store`]), so the digest works with either store.
+//! To recap each piece of work once, the number of edits already recapped
+//! is kept per session under `digests/` in the tracker state directory;
+//! the hook stays quiet until the session has new edits.
+
+use crate::check_log::{self, CheckRecord, CheckStatus};
+use crate::file_tracking::validate_session_id;
+use crate::modification_store::{ModificationFilter, ModificationRow, TrackerBackend};
+use crate::types::{CatalystError, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory, inside the tracker state directory, of per-session markers
+pub const DIGEST_DIR: &str = "digests";
+
+/// Files listed per category before the rest are only counted
+const FILES_PER_CATEGORY: usize = 5;
+
+/// What happened in one session
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SessionDigest {
+    pub session_id: String,
+
+    /// Number of tracked edits
+    pub edits: usize,
+
+    /// Distinct files edited, by category, relative to the project where
+    /// possible
+    pub files_by_category: BTreeMap<String, Vec<String>>,
+
+    /// Files whose latest edit has async code but no try/catch
+    pub risky_files: Vec<String>,
+
+    /// Each check that ran, in the order it first ran
+    pub checks: Vec<CheckSummary>,
+}
+
+/// Runs of one check command during the session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckSummary {
+    pub hook: String,
+    pub command: String,
+    pub runs: usize,
+    pub failed: usize,
+    pub last: CheckStatus,
+}
+
+impl SessionDigest {
+    /// Summarize a session's edits and check runs
+    ///
+    /// Rows from other sessions are ignored, and edits are taken in the
+    /// order given.
+    pub fn new(
+        session_id: &str,
+        project_dir: &Path,
+        modifications: &[ModificationRow],
+        checks: &[CheckRecord],
+    ) -> Self {
+        let canonical = dunce::canonicalize(project_dir).ok();
+        let display = |path: &str| {
+            let path = Path::new(path);
+            path.strip_prefix(project_dir)
+                .ok()
+                .or_else(|| {
+                    canonical
+                        .as_deref()
+                        .and_then(|dir| path.strip_prefix(dir).ok())
+                })
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/")
+        };
+
+        // Latest edit of each file decides its category and whether it's risky
+        let mut latest: BTreeMap<String, &ModificationRow> = BTreeMap::new();
+        let mut edits = 0;
+        for row in modifications
+            .iter()
+            .filter(|row| row.session_id == session_id)
+        {
+            edits += 1;
+            latest.insert(display(&row.file_path), row);
+        }
+
+        let mut files_by_category: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut risky_files = Vec::new();
+        for (path, row) in &latest {
+            files_by_category
+                .entry(row.category.clone())
+                .or_default()
+                .push(path.clone());
+            if row.has_async && !row.has_try_catch {
+                risky_files.push(path.clone());
+            }
+        }
+
+        let mut summaries: Vec<CheckSummary> = Vec::new();
+        for record in checks
+            .iter()
+            .filter(|record| record.session_id.as_deref() == Some(session_id))
+        {
+            let index = summaries
+                .iter()
+                .position(|s| s.hook == record.hook && s.command == record.command)
+                .unwrap_or_else(|| {
+                    summaries.push(CheckSummary {
+                        hook: record.hook.clone(),
+                        command: record.command.clone(),
+                        runs: 0,
+                        failed: 0,
+                        last: record.status,
+                    });
+                    summaries.len() - 1
+                });
+            let summary = &mut summaries[index];
+            summary.runs += 1;
+            if record.status == CheckStatus::Failed {
+                summary.failed += 1;
+            }
+            summary.last = record.status;
+        }
+
+        Self {
+            session_id: session_id.to_string(),
+            edits,
+            files_by_category,
+            risky_files,
+            checks: summaries,
+        }
+    }
+
+    /// The recap handed to Claude
+    pub fn render(&self) -> String {
+        let mut out = String::from(
+            "Session recap from Catalyst. Mention these points in your final summary:\n",
+        );
+        let files: usize = self.files_by_category.values().map(Vec::len).sum();
+        let categories: Vec<String> = self
+            .files_by_category
+            .iter()
+            .map(|(category, paths)| {
+                let mut listed = paths
+                    .iter()
+                    .take(FILES_PER_CATEGORY)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if paths.len() > FILES_PER_CATEGORY {
+                    let _ = write!(listed, ", and {} more", paths.len() - FILES_PER_CATEGORY);
+                }
+                format!("{} {} ({})", paths.len(), category, listed)
+            })
+            .collect();
+        let _ = writeln!(
+            out,
+            "- Changed {} {} in {} {}: {}",
+            files,
+            plural(files, "file", "files"),
+            self.edits,
+            plural(self.edits, "edit", "edits"),
+            categories.join("; ")
+        );
+
+        if !self.risky_files.is_empty() {
+            let _ = writeln!(
+                out,
+                "- Async code without try/catch: {}",
+                self.risky_files.join(", ")
+            );
+        }
+
+        if self.checks.is_empty() {
+            out.push_str("- No checks ran during the session\n");
+        } else {
+            let checks: Vec<String> = self
+                .checks
+                .iter()
+                .map(|check| {
+                    let last = match check.last {
+                        CheckStatus::Passed => "passed",
+                        CheckStatus::Failed => "failed",
+                        CheckStatus::Incomplete => "incomplete",
+                    };
+                    let mut text = format!(
+                        "{} (`{}`) ran {} {}",
+                        check.hook,
+                        check.command,
+                        check.runs,
+                        plural(check.runs, "time", "times")
+                    );
+                    if check.failed > 0 {
+                        let _ = write!(text, ", {} failed", check.failed);
+                    }
+                    let _ = write!(text, ", last {}", last);
+                    text
+                })
+                .collect();
+            let _ = writeln!(out, "- Checks: {}", checks.join("; "));
+        }
+        out
+    }
+}
+
+fn plural<'a>(count: usize, one: &'a str, many: &'a str) -> &'a str {
+    if count == 1 {
+        one
+    } else {
+        many
+    }
+}
+
+/// Build the digest of a session from the project's tracker and the check log
+///
+/// # Errors
+///
+/// Returns `InvalidPath` for a malformed session ID, or the tracker
+/// backend's or check log's error if either can't be read
+pub fn load(state_dir: &Path, project_dir: &Path, session_id: &str) -> Result<SessionDigest> {
+    validate_session_id(session_id)?;
+    let store = TrackerBackend::resolve(project_dir)?.open(state_dir)?;
+    let modifications = store.query(&ModificationFilter {
+        session: Some(session_id.to_string()),
+        ..Default::default()
+    })?;
+    let checks = check_log::read(state_dir, project_dir, None)?;
+    Ok(SessionDigest::new(
+        session_id,
+        project_dir,
+        &modifications,
+        &checks,
+    ))
+}
+
+/// The digest of a session that has edits not yet recapped
+///
+/// Returns None while every edit of the session was already in a recap
+/// (see [`mark_digested`]).
+///
+/// # Errors
+///
+/// Returns the errors of [`load`]
+pub fn pending(
+    state_dir: &Path,
+    project_dir: &Path,
+    session_id: &str,
+) -> Result<Option<SessionDigest>> {
+    let digest = load(state_dir, project_dir, session_id)?;
+    Ok((digest.edits > digested_edits(state_dir, session_id)).then_some(digest))
+}
+
+fn marker_path(state_dir: &Path, session_id: &str) -> Result<PathBuf> {
+    validate_session_id(session_id)?;
+    Ok(state_dir.join(DIGEST_DIR).join(session_id))
+}
+
+/// Edits of the session already recapped; 0 if none were
+pub fn digested_edits(state_dir: &Path, session_id: &str) -> usize {
+    marker_path(state_dir, session_id)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|count| count.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Remember that the session's first `edits` edits were recapped
+///
+/// # Errors
+///
+/// Returns `InvalidPath` for a malformed session ID, or an error if the
+/// marker can't be written
+pub fn mark_digested(state_dir: &Path, session_id: &str, edits: usize) -> Result<()> {
+    let path = marker_path(state_dir, session_id)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|source| CatalystError::DirectoryCreationFailed {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+    }
+    fs::write(&path, edits.to_string())
+        .map_err(|source| CatalystError::FileWriteFailed { path, source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn edit(session: &str, path: &str, category: &str, risky: bool) -> ModificationRow {
+        ModificationRow {
+            category: category.to_string(),
+            has_async: risky,
+            ..ModificationRow::test_edit(session, path, "2026-01-01T10:00:00+00:00")
+        }
+    }
+
+    #[test]
+    fn test_digest_summarizes_session() {
+        let project = Path::new("/work/app");
+        let modifications = [
+            edit("s1", "/work/app/src/api/users.ts", "backend", true),
+            edit("s1", "/work/app/src/App.tsx", "frontend", false),
+            // Fixed by the later edit
+            edit("s1", "/work/app/src/api/orders.ts", "backend", true),
+            edit("s1", "/work/app/src/api/orders.ts", "backend", false),
+            edit("s2", "/work/app/src/other.ts", "backend", true),
+        ];
+        let check = |status, session: &str| {
+            CheckRecord::now(Some(session), project, "cargo-check", "cargo check", status)
+        };
+        let checks = [
+            check(CheckStatus::Failed, "s1"),
+            check(CheckStatus::Passed, "s1"),
+            check(CheckStatus::Failed, "s2"),
+        ];
+
+        let digest = SessionDigest::new("s1", project, &modifications, &checks);
+        assert_eq!(digest.edits, 4);
+        assert_eq!(
+            digest.files_by_category["backend"],
+            ["src/api/orders.ts", "src/api/users.ts"]
+        );
+        assert_eq!(digest.risky_files, ["src/api/users.ts"]);
+        assert_eq!(digest.checks.len(), 1);
+        assert_eq!((digest.checks[0].runs, digest.checks[0].failed), (2, 1));
+
+        let text = digest.render();
+        assert!(text.contains("- Changed 3 files in 4 edits: 2 backend (src/api/orders.ts, src/api/users.ts); 1 frontend (src/App.tsx)"));
+        assert!(text.contains("- Async code without try/catch: src/api/users.ts"));
+        assert!(text.contains("cargo-check (`cargo check`) ran 2 times, 1 failed, last passed"));
+    }
+
+    #[test]
+    fn test_digest_markers() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = temp_dir.path();
+        assert_eq!(digested_edits(state, "s1"), 0);
+        mark_digested(state, "s1", 4).unwrap();
+        assert_eq!(digested_edits(state, "s1"), 4);
+        assert!(mark_digested(state, "../s1", 1).is_err());
+    }
+
+    #[test]
+    fn test_pending_recaps_new_edits_once() {
+        use crate::modification_store::{JsonlStore, ModificationStore};
+
+        let temp_dir = TempDir::new().unwrap();
+        let state = temp_dir.path().join("state");
+        let project = temp_dir.path().join("project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(
+            project.join(crate::types::CONFIG_FILE),
+            "[tracker]\nbackend = \"jsonl\"\n",
+        )
+        .unwrap();
+        let mut store = JsonlStore::open(&state).unwrap();
+        let mut record = |path: &str| {
+            let file = project.join(path).display().to_string();
+            store.record(&edit("s1", &file, "backend", true)).unwrap();
+        };
+
+        assert!(pending(&state, &project, "s1").unwrap().is_none());
+        record("src/api.ts");
+        let digest = pending(&state, &project, "s1").unwrap().unwrap();
+        assert!(digest
+            .render()
+            .contains("Async code without try/catch: src/api.ts"));
+
+        // Quiet until the session has edits the last recap didn't cover
+        mark_digested(&state, "s1", digest.edits).unwrap();
+        assert!(pending(&state, &project, "s1").unwrap().is_none());
+        record("src/other.ts");
+        assert_eq!(pending(&state, &project, "s1").unwrap().unwrap().edits, 2);
+    }
+}
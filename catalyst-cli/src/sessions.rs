@@ -0,0 +1,962 @@
+//! Reports over the SQLite file-change tracker (`catalyst sessions`)
+//!
+//! file-change-tracker records every Claude Code session in
+//! `~/.claude/hooks-state-rust/tracker.db` (see [`crate::tracker`]). This
+//! module reads it back, opening the database read-only so a report never
+//! races the hook, can export the raw tables as JSON or CSV for
+//! dashboards, and answers ad hoc questions about tracked edits
+//! (`catalyst sessions query`) without opening the database in sqlite3.
+
+use crate::file_tracking::validate_session_id;
+pub use crate::modification_store::{
+    parse_time_bound, state_dir, ModificationFilter as ExportFilter, ModificationRow,
+};
+use crate::tracker;
+use crate::types::{CatalystError, Result};
+use chrono::DateTime;
+use globset::Glob;
+use rusqlite::{params, params_from_iter, Connection};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// How many files `most_edited` lists
+pub const MOST_EDITED_LIMIT: usize = 10;
+
+/// Summary of one tracked session
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionReport {
+    pub session_id: String,
+
+    /// When the first edit was tracked (RFC 3339)
+    pub start_time: Option<String>,
+
+    /// When the latest edit was tracked (RFC 3339)
+    pub last_activity: Option<String>,
+
+    /// Number of tracked edits
+    pub total_edits: usize,
+
+    /// Distinct files touched, by category (backend, frontend, database, other)
+    pub files_by_category: BTreeMap<String, usize>,
+
+    /// Files whose latest version has async code but no try/catch
+    pub risky_files: Vec<String>,
+
+    /// Distinct files touched, by the git branch checked out at the time
+    ///
+    /// Edits made outside a repository or on a detached HEAD aren't counted.
+    pub files_by_branch: BTreeMap<String, usize>,
+
+    /// HEAD commits the edits were made on, in the order first seen
+    pub commits: Vec<String>,
+
+    /// Files with the most edits, most edited first
+    pub most_edited: Vec<FileEdits>,
+
+    /// Every tracked edit, oldest first
+    pub timeline: Vec<TimelineEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileEdits {
+    pub path: String,
+    pub edits: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEntry {
+    pub timestamp: String,
+    pub tool: String,
+    pub file_path: String,
+    pub category: String,
+    pub git_branch: Option<String>,
+    pub git_commit: Option<String>,
+}
+
+/// A row of the tracker's `sessions` table
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionRow {
+    pub session_id: String,
+    pub start_time: String,
+    pub last_activity: String,
+    pub total_files: i64,
+    pub backend_files: i64,
+    pub frontend_files: i64,
+    pub database_files: i64,
+}
+
+/// Both tracker tables
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TrackerExport {
+    pub sessions: Vec<SessionRow>,
+    pub file_modifications: Vec<ModificationRow>,
+}
+
+/// Output format of `catalyst sessions export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Both tables in one document
+    Json,
+    /// One table, with a header row
+    Csv,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = CatalystError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "csv" => Ok(ExportFormat::Csv),
+            _ => Err(CatalystError::InvalidConfig(format!(
+                "Unknown export format '{}'. Expected json or csv",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+/// Table written by a CSV export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportTable {
+    FileModifications,
+    Sessions,
+}
+
+impl std::str::FromStr for ExportTable {
+    type Err = CatalystError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "file_modifications" | "modifications" => Ok(ExportTable::FileModifications),
+            "sessions" => Ok(ExportTable::Sessions),
+            _ => Err(CatalystError::InvalidConfig(format!(
+                "Unknown table '{}'. Expected file_modifications or sessions",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+/// Which edits `catalyst sessions query` lists
+#[derive(Debug, Clone, Default)]
+pub struct QueryFilter {
+    /// Session and time range
+    pub edits: ExportFilter,
+
+    /// Only files in this category (backend, frontend, database, or other)
+    pub category: Option<String>,
+
+    /// Only files whose tracked path matches this glob, e.g. `src/**`
+    ///
+    /// The tracker records absolute paths, so a relative glob may match
+    /// from any directory down: `src/**` matches `/work/app/src/main.rs`.
+    pub file_glob: Option<String>,
+
+    /// Only edits that left async code without a try/catch
+    pub risky: bool,
+
+    /// Only edits made with this git branch checked out
+    pub branch: Option<String>,
+}
+
+/// Output format of `catalyst sessions query`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueryFormat {
+    /// Aligned columns for reading in a terminal
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+impl std::str::FromStr for QueryFormat {
+    type Err = CatalystError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "table" => Ok(QueryFormat::Table),
+            "json" => Ok(QueryFormat::Json),
+            "csv" => Ok(QueryFormat::Csv),
+            _ => Err(CatalystError::InvalidConfig(format!(
+                "Unknown query format '{}'. Expected table, json, or csv",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+/// Tracked edits matching `filter`, oldest first
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` for a malformed glob, and otherwise the same
+/// errors as [`collect_reports`]
+pub fn query(state_dir: &Path, filter: &QueryFilter) -> Result<Vec<ModificationRow>> {
+    if let Some(session_id) = &filter.edits.session {
+        validate_session_id(session_id)?;
+    }
+    let glob = filter
+        .file_glob
+        .as_deref()
+        .map(|pattern| {
+            let anchored = pattern.starts_with('/') || pattern.starts_with("**");
+            let pattern = if anchored {
+                pattern.to_string()
+            } else {
+                format!("**/{}", pattern)
+            };
+            Glob::new(&pattern)
+                .map(|glob| glob.compile_matcher())
+                .map_err(|e| {
+                    CatalystError::InvalidConfig(format!("Invalid file glob '{}': {}", pattern, e))
+                })
+        })
+        .transpose()?;
+    let Some(conn) = tracker::open_tracker_db_read_only(state_dir)? else {
+        return Ok(Vec::new());
+    };
+    let db_path = tracker::tracker_db_path(state_dir);
+    let mut rows = select_modifications(&conn, filter).map_err(db_error(&db_path))?;
+
+    // Globs have no SQL equivalent
+    if let Some(glob) = &glob {
+        rows.retain(|row| glob.is_match(&row.file_path));
+    }
+    Ok(rows)
+}
+
+/// Edits matching every part of `filter` except the file glob, oldest first
+///
+/// Each filter becomes a condition with a bound parameter. Times are
+/// compared with `julianday` so any RFC 3339 offset orders correctly, and
+/// unparseable timestamps never match a range.
+fn select_modifications(
+    conn: &Connection,
+    filter: &QueryFilter,
+) -> rusqlite::Result<Vec<ModificationRow>> {
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+    if let Some(session) = &filter.edits.session {
+        conditions.push("session_id = ?");
+        values.push(session.clone());
+    }
+    if let Some(since) = filter.edits.since {
+        conditions.push("julianday(timestamp) >= julianday(?)");
+        values.push(since.to_rfc3339());
+    }
+    if let Some(until) = filter.edits.until {
+        conditions.push("julianday(timestamp) < julianday(?)");
+        values.push(until.to_rfc3339());
+    }
+    if let Some(category) = &filter.category {
+        conditions.push("category = ? COLLATE NOCASE");
+        values.push(category.clone());
+    }
+    if filter.risky {
+        conditions.push("has_async AND NOT has_try_catch");
+    }
+    if let Some(branch) = &filter.branch {
+        conditions.push("git_branch = ?");
+        values.push(branch.clone());
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    };
+    conn.prepare(&format!(
+        "SELECT {} FROM file_modifications{} ORDER BY timestamp, id",
+        tracker::MODIFICATION_COLUMNS,
+        where_clause
+    ))?
+    .query_map(params_from_iter(&values), tracker::modification_row)?
+    .collect()
+}
+
+/// Read both tracker tables, applying `filter`
+///
+/// # Errors
+///
+/// Returns the same errors as [`collect_reports`]
+pub fn export(state_dir: &Path, filter: &ExportFilter) -> Result<TrackerExport> {
+    if let Some(session_id) = &filter.session {
+        validate_session_id(session_id)?;
+    }
+    let Some(conn) = tracker::open_tracker_db_read_only(state_dir)? else {
+        return Ok(TrackerExport::default());
+    };
+    let db_path = tracker::tracker_db_path(state_dir);
+    let (sessions, modifications) =
+        read_tables(&conn, filter.session.as_deref()).map_err(db_error(&db_path))?;
+
+    let mut export = TrackerExport::default();
+    export.file_modifications.extend(
+        modifications
+            .into_iter()
+            .filter(|row| filter.includes_time(&row.timestamp)),
+    );
+    export.sessions.extend(sessions.into_iter().filter(|row| {
+        let ended_before = filter.since.is_some_and(|since| {
+            DateTime::parse_from_rfc3339(&row.last_activity).is_ok_and(|t| t < since)
+        });
+        let started_after = filter.until.is_some_and(|until| {
+            DateTime::parse_from_rfc3339(&row.start_time).is_ok_and(|t| t >= until)
+        });
+        !ended_before && !started_after
+    }));
+
+    export
+        .file_modifications
+        .sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    export
+        .sessions
+        .sort_by(|a, b| a.start_time.cmp(&b.start_time));
+    Ok(export)
+}
+
+/// Render one table of an export as CSV (RFC 4180 quoting)
+pub fn to_csv(export: &TrackerExport, table: ExportTable) -> String {
+    match table {
+        ExportTable::FileModifications => modifications_to_csv(&export.file_modifications),
+        ExportTable::Sessions => {
+            let mut csv = String::new();
+            push_csv_row(
+                &mut csv,
+                &[
+                    "session_id",
+                    "start_time",
+                    "last_activity",
+                    "total_files",
+                    "backend_files",
+                    "frontend_files",
+                    "database_files",
+                ]
+                .map(String::from),
+            );
+            for row in &export.sessions {
+                push_csv_row(
+                    &mut csv,
+                    &[
+                        row.session_id.clone(),
+                        row.start_time.clone(),
+                        row.last_activity.clone(),
+                        row.total_files.to_string(),
+                        row.backend_files.to_string(),
+                        row.frontend_files.to_string(),
+                        row.database_files.to_string(),
+                    ],
+                );
+            }
+            csv
+        }
+    }
+}
+
+/// Render tracked edits as CSV, in the columns of the `file_modifications`
+/// table
+pub fn modifications_to_csv(rows: &[ModificationRow]) -> String {
+    let mut csv = String::new();
+    push_csv_row(
+        &mut csv,
+        &[
+            "session_id",
+            "file_path",
+            "tool",
+            "timestamp",
+            "category",
+            "has_async",
+            "has_try_catch",
+            "has_prisma",
+            "has_controller",
+            "has_api_call",
+            "line_count",
+            "git_branch",
+            "git_commit",
+        ]
+        .map(String::from),
+    );
+    for row in rows {
+        push_csv_row(
+            &mut csv,
+            &[
+                row.session_id.clone(),
+                row.file_path.clone(),
+                row.tool.clone(),
+                row.timestamp.clone(),
+                row.category.clone(),
+                row.has_async.to_string(),
+                row.has_try_catch.to_string(),
+                row.has_prisma.to_string(),
+                row.has_controller.to_string(),
+                row.has_api_call.to_string(),
+                row.line_count.to_string(),
+                row.git_branch.clone().unwrap_or_default(),
+                row.git_commit.clone().unwrap_or_default(),
+            ],
+        );
+    }
+    csv
+}
+
+fn push_csv_row(csv: &mut String, fields: &[String]) {
+    let quoted: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+    csv.push_str(&quoted.join(","));
+    csv.push_str("\r\n");
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn db_error(path: &Path) -> impl Fn(rusqlite::Error) -> CatalystError + '_ {
+    move |e| CatalystError::SessionDatabase(format!("{}: {}", path.display(), e))
+}
+
+/// Rows of both tables, for one session or all of them
+fn read_tables(
+    conn: &Connection,
+    session: Option<&str>,
+) -> rusqlite::Result<(Vec<SessionRow>, Vec<ModificationRow>)> {
+    let sessions = conn
+        .prepare(
+            "SELECT session_id, start_time, last_activity, total_files,
+                    backend_files, frontend_files, database_files
+             FROM sessions WHERE ?1 IS NULL OR session_id = ?1",
+        )
+        .and_then(|mut statement| {
+            statement
+                .query_map(params![session], |row| {
+                    Ok(SessionRow {
+                        session_id: row.get(0)?,
+                        start_time: row.get(1)?,
+                        last_activity: row.get(2)?,
+                        total_files: row.get(3)?,
+                        backend_files: row.get(4)?,
+                        frontend_files: row.get(5)?,
+                        database_files: row.get(6)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()
+        })?;
+
+    let modifications = tracker::read_modifications(conn, session)?;
+
+    Ok((sessions, modifications))
+}
+
+/// Tracked session IDs, most recently active first
+///
+/// # Errors
+///
+/// Returns an error if the database can't be queried
+pub fn list_sessions(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+    conn.prepare(
+        "SELECT session_id FROM file_modifications
+         GROUP BY session_id ORDER BY MAX(timestamp) DESC, session_id",
+    )?
+    .query_map([], |row| row.get(0))?
+    .collect()
+}
+
+/// Build reports for one session or the most recent ones
+///
+/// # Arguments
+///
+/// * `state_dir` - Directory holding the tracker database
+/// * `session` - Report only this session ID
+/// * `last` - Otherwise, report this many of the most recent sessions
+///
+/// # Errors
+///
+/// Returns `InvalidPath` for a malformed session ID, or `SessionDatabase`
+/// if the session wasn't tracked or the database can't be read
+pub fn collect_reports(
+    state_dir: &Path,
+    session: Option<&str>,
+    last: usize,
+) -> Result<Vec<SessionReport>> {
+    if let Some(session_id) = session {
+        validate_session_id(session_id)?;
+    }
+    let db_path = tracker::tracker_db_path(state_dir);
+    let Some(conn) = tracker::open_tracker_db_read_only(state_dir)? else {
+        return match session {
+            Some(session_id) => Err(unknown_session(&db_path, session_id)),
+            None => Ok(Vec::new()),
+        };
+    };
+
+    if let Some(session_id) = session {
+        let report = session_report(&conn, session_id).map_err(db_error(&db_path))?;
+        if report.total_edits == 0 {
+            return Err(unknown_session(&db_path, session_id));
+        }
+        return Ok(vec![report]);
+    }
+
+    list_sessions(&conn)
+        .and_then(|sessions| {
+            sessions
+                .iter()
+                .take(last)
+                .map(|session_id| session_report(&conn, session_id))
+                .collect()
+        })
+        .map_err(db_error(&db_path))
+}
+
+fn unknown_session(db_path: &Path, session_id: &str) -> CatalystError {
+    CatalystError::SessionDatabase(format!(
+        "no session '{}' in {}",
+        session_id,
+        db_path.display()
+    ))
+}
+
+/// Summarize one session in the tracker database
+///
+/// # Errors
+///
+/// Returns an error if the database can't be queried
+pub fn session_report(conn: &Connection, session_id: &str) -> rusqlite::Result<SessionReport> {
+    let mut statement = conn.prepare(
+        "SELECT timestamp, tool, file_path, category, git_branch, git_commit
+         FROM file_modifications WHERE session_id = ?1 ORDER BY timestamp, id",
+    )?;
+    let timeline = statement
+        .query_map(params![session_id], |row| {
+            Ok(TimelineEntry {
+                timestamp: row.get(0)?,
+                tool: row.get(1)?,
+                file_path: row.get(2)?,
+                category: row.get(3)?,
+                git_branch: row.get(4)?,
+                git_commit: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut statement = conn.prepare(
+        "SELECT file_path FROM file_modifications AS m
+         WHERE session_id = ?1 AND has_async AND NOT has_try_catch
+           AND id = (SELECT MAX(id) FROM file_modifications
+                     WHERE session_id = m.session_id AND file_path = m.file_path)
+         ORDER BY file_path",
+    )?;
+    let risky_files = statement
+        .query_map(params![session_id], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+
+    let mut edits: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut categories: BTreeMap<&str, &str> = BTreeMap::new();
+    for entry in &timeline {
+        *edits.entry(&entry.file_path).or_default() += 1;
+        categories.insert(&entry.file_path, &entry.category);
+    }
+
+    let mut files_by_category = BTreeMap::new();
+    for category in categories.values() {
+        *files_by_category.entry(category.to_string()).or_default() += 1;
+    }
+
+    let mut branch_files: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    let mut commits: Vec<String> = Vec::new();
+    for entry in &timeline {
+        if let Some(branch) = &entry.git_branch {
+            branch_files
+                .entry(branch)
+                .or_default()
+                .insert(&entry.file_path);
+        }
+        if let Some(commit) = &entry.git_commit {
+            if !commits.contains(commit) {
+                commits.push(commit.clone());
+            }
+        }
+    }
+    let files_by_branch = branch_files
+        .into_iter()
+        .map(|(branch, files)| (branch.to_string(), files.len()))
+        .collect();
+
+    let mut most_edited: Vec<FileEdits> = edits
+        .iter()
+        .map(|(path, edits)| FileEdits {
+            path: path.to_string(),
+            edits: *edits,
+        })
+        .collect();
+    // Stable sort keeps ties in path order
+    most_edited.sort_by_key(|file| std::cmp::Reverse(file.edits));
+    most_edited.truncate(MOST_EDITED_LIMIT);
+
+    Ok(SessionReport {
+        session_id: session_id.to_string(),
+        start_time: timeline.first().map(|entry| entry.timestamp.clone()),
+        last_activity: timeline.last().map(|entry| entry.timestamp.clone()),
+        total_edits: timeline.len(),
+        files_by_category,
+        risky_files,
+        files_by_branch,
+        commits,
+        most_edited,
+        timeline,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Record a session in the tracker database under `dir`
+    fn tracker_db(dir: &Path, session: &str, edits: &[(&str, &str, bool, bool)]) {
+        let conn = tracker::open_tracker_db(dir).unwrap();
+        conn.execute(
+            "INSERT INTO sessions (session_id, start_time, last_activity, total_files)
+             VALUES (?1, '2026-01-01T10:00:00+00:00', ?2, ?3)",
+            params![
+                session,
+                format!(
+                    "2026-01-01T10:{:02}:00+00:00",
+                    edits.len().saturating_sub(1)
+                ),
+                edits.len()
+            ],
+        )
+        .unwrap();
+        for (minute, (file, category, has_async, has_try)) in edits.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO file_modifications
+                 (session_id, file_path, tool, timestamp, category, has_async, has_try_catch)
+                 VALUES (?1, ?2, 'Edit', ?3, ?4, ?5, ?6)",
+                params![
+                    session,
+                    file,
+                    format!("2026-01-01T10:{:02}:00+00:00", minute),
+                    category,
+                    has_async,
+                    has_try
+                ],
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_session_report_summarizes_edits() {
+        let temp_dir = TempDir::new().unwrap();
+        tracker_db(
+            temp_dir.path(),
+            "abc-123",
+            &[
+                ("src/api/users.ts", "backend", true, false),
+                ("src/components/List.tsx", "frontend", false, false),
+                ("src/api/users.ts", "backend", true, false),
+                // Fixed by the last edit, so no longer risky
+                ("src/api/orders.ts", "backend", true, false),
+                ("src/api/orders.ts", "backend", true, true),
+            ],
+        );
+
+        let conn = tracker::open_tracker_db(temp_dir.path()).unwrap();
+        let report = session_report(&conn, "abc-123").unwrap();
+        assert_eq!(report.session_id, "abc-123");
+        assert_eq!(report.total_edits, 5);
+        assert_eq!(report.files_by_category["backend"], 2);
+        assert_eq!(report.files_by_category["frontend"], 1);
+        assert_eq!(report.risky_files, vec!["src/api/users.ts".to_string()]);
+        assert_eq!(report.most_edited[0].edits, 2);
+        assert_eq!(report.most_edited[0].path, "src/api/orders.ts");
+        assert_eq!(
+            report.start_time.as_deref(),
+            Some("2026-01-01T10:00:00+00:00")
+        );
+        assert_eq!(report.timeline[4].file_path, "src/api/orders.ts");
+    }
+
+    #[test]
+    fn test_collect_reports_selects_sessions() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = temp_dir.path();
+        assert!(collect_reports(state, None, 5).unwrap().is_empty());
+
+        tracker_db(state, "one", &[("a.rs", "other", false, false)]);
+        tracker_db(state, "two", &[("b.rs", "other", false, false)]);
+        tracker_db(state, "other", &[("c.rs", "other", false, false)]);
+        tracker::open_tracker_db(state)
+            .unwrap()
+            .execute(
+                "UPDATE file_modifications SET timestamp = '2026-01-02T00:00:00+00:00'
+                 WHERE session_id = 'two'",
+                [],
+            )
+            .unwrap();
+
+        let latest = collect_reports(state, None, 1).unwrap();
+        assert_eq!(latest[0].session_id, "two");
+        assert_eq!(collect_reports(state, None, 5).unwrap().len(), 3);
+        let reports = collect_reports(state, Some("two"), 1).unwrap();
+        assert_eq!(reports[0].timeline[0].file_path, "b.rs");
+
+        assert!(matches!(
+            collect_reports(state, Some("../two"), 1),
+            Err(CatalystError::InvalidPath(_))
+        ));
+        assert!(matches!(
+            collect_reports(state, Some("three"), 1),
+            Err(CatalystError::SessionDatabase(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_filters_and_csv() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = temp_dir.path();
+        tracker_db(
+            state,
+            "s1",
+            &[
+                ("src/a.ts", "backend", true, false),
+                ("src/b, \"quoted\".ts", "other", false, false),
+                ("src/c.ts", "other", false, false),
+            ],
+        );
+        tracker_db(state, "s2", &[("src/d.ts", "other", false, false)]);
+
+        let all = export(state, &ExportFilter::default()).unwrap();
+        assert_eq!(all.file_modifications.len(), 4);
+        assert_eq!(all.sessions.len(), 2);
+
+        let filter = ExportFilter {
+            session: Some("s1".to_string()),
+            since: Some(parse_time_bound("2026-01-01T10:01:00Z", false).unwrap()),
+            until: Some(parse_time_bound("2026-01-01T10:02:00Z", false).unwrap()),
+        };
+        let ranged = export(state, &filter).unwrap();
+        assert_eq!(ranged.file_modifications.len(), 1);
+        assert_eq!(ranged.sessions.len(), 1);
+
+        let csv = to_csv(&ranged, ExportTable::FileModifications);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert!(lines[0].starts_with("session_id,file_path,tool,timestamp"));
+        assert!(lines[1].starts_with("s1,\"src/b, \"\"quoted\"\".ts\",Edit,"));
+
+        // A day bound covers the whole day; later days exclude the session
+        let next_day = ExportFilter {
+            since: Some(parse_time_bound("2026-01-02", false).unwrap()),
+            ..Default::default()
+        };
+        let none = export(state, &next_day).unwrap();
+        assert!(none.file_modifications.is_empty() && none.sessions.is_empty());
+        assert_eq!(
+            parse_time_bound("2026-01-01", true).unwrap().to_rfc3339(),
+            "2026-01-02T00:00:00+00:00"
+        );
+        assert!(parse_time_bound("last tuesday", false).is_err());
+    }
+
+    #[test]
+    fn test_report_and_query_by_branch() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = temp_dir.path();
+        tracker_db(
+            state,
+            "s1",
+            &[
+                ("src/a.ts", "backend", false, false),
+                ("src/b.ts", "backend", false, false),
+                ("src/a.ts", "backend", false, false),
+                ("notes.md", "other", false, false),
+            ],
+        );
+        let conn = tracker::open_tracker_db(state).unwrap();
+        conn.execute_batch(
+            "UPDATE file_modifications SET git_branch = 'main', git_commit = 'aaa' WHERE id <= 2;
+             UPDATE file_modifications SET git_branch = 'fix', git_commit = 'bbb' WHERE id = 3;",
+        )
+        .unwrap();
+
+        let report = session_report(&conn, "s1").unwrap();
+        assert_eq!(report.files_by_branch["main"], 2);
+        assert_eq!(report.files_by_branch["fix"], 1);
+        assert_eq!(report.commits, ["aaa", "bbb"]);
+
+        let fix = QueryFilter {
+            branch: Some("fix".to_string()),
+            ..Default::default()
+        };
+        let rows = query(state, &fix).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].git_commit.as_deref(), Some("bbb"));
+        assert!(modifications_to_csv(&rows).contains(",fix,bbb\r\n"));
+    }
+
+    /// Paths of the edits `filter` selects from a four-edit session "s1"
+    /// (minutes 0-3) and a one-edit session "s2"
+    fn query_paths(filter: QueryFilter) -> Vec<String> {
+        let temp_dir = TempDir::new().unwrap();
+        let state = temp_dir.path();
+        tracker_db(
+            state,
+            "s1",
+            &[
+                ("/work/app/src/api/users.ts", "backend", true, false),
+                ("/work/app/src/api/orders.ts", "backend", true, true),
+                ("/work/app/src/components/List.tsx", "frontend", true, false),
+                ("/work/app/scripts/seed.ts", "backend", false, false),
+            ],
+        );
+        tracker_db(
+            state,
+            "s2",
+            &[("/work/app/README.md", "other", false, false)],
+        );
+        query(state, &filter)
+            .unwrap()
+            .into_iter()
+            .map(|row| row.file_path)
+            .collect()
+    }
+
+    #[test]
+    fn test_query_by_session() {
+        let filter = QueryFilter {
+            edits: ExportFilter {
+                session: Some("s2".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(query_paths(filter), ["/work/app/README.md"]);
+    }
+
+    #[test]
+    fn test_query_by_category() {
+        let filter = QueryFilter {
+            category: Some("FRONTEND".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query_paths(filter), ["/work/app/src/components/List.tsx"]);
+    }
+
+    #[test]
+    fn test_query_by_time() {
+        // Bounds in another offset still compare by instant
+        let filter = QueryFilter {
+            edits: ExportFilter {
+                since: Some(parse_time_bound("2026-01-01T11:01:00+01:00", false).unwrap()),
+                until: Some(parse_time_bound("2026-01-01T10:03:00Z", false).unwrap()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            query_paths(filter),
+            [
+                "/work/app/src/api/orders.ts",
+                "/work/app/src/components/List.tsx"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_query_by_glob() {
+        let relative = QueryFilter {
+            file_glob: Some("api/*.ts".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            query_paths(relative),
+            ["/work/app/src/api/users.ts", "/work/app/src/api/orders.ts"]
+        );
+
+        let anchored = QueryFilter {
+            file_glob: Some("/work/app/*.md".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query_paths(anchored), ["/work/app/README.md"]);
+    }
+
+    #[test]
+    fn test_query_risky() {
+        let filter = QueryFilter {
+            risky: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            query_paths(filter),
+            [
+                "/work/app/src/api/users.ts",
+                "/work/app/src/components/List.tsx"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_query_filters_edits() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = temp_dir.path();
+        assert!(query(state, &QueryFilter::default()).unwrap().is_empty());
+        tracker_db(
+            state,
+            "s1",
+            &[
+                ("/work/app/src/api/users.ts", "backend", true, false),
+                ("/work/app/src/api/orders.ts", "backend", true, true),
+                ("/work/app/src/components/List.tsx", "frontend", true, false),
+                ("/work/app/scripts/seed.ts", "backend", false, false),
+            ],
+        );
+
+        let filter = QueryFilter {
+            category: Some("Backend".to_string()),
+            file_glob: Some("src/**".to_string()),
+            ..Default::default()
+        };
+        let paths: Vec<String> = query(state, &filter)
+            .unwrap()
+            .into_iter()
+            .map(|row| row.file_path)
+            .collect();
+        assert_eq!(
+            paths,
+            ["/work/app/src/api/users.ts", "/work/app/src/api/orders.ts"]
+        );
+
+        let risky = QueryFilter {
+            risky: true,
+            ..Default::default()
+        };
+        assert_eq!(query(state, &risky).unwrap().len(), 2);
+
+        // Tracked in 2026-01, so well over two hours ago
+        let recent = QueryFilter {
+            edits: ExportFilter {
+                since: Some(parse_time_bound("2h", false).unwrap()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(query(state, &recent).unwrap().is_empty());
+        assert!(parse_time_bound("-2h", false).is_err());
+
+        let bad_glob = QueryFilter {
+            file_glob: Some("src/[".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            query(state, &bad_glob),
+            Err(CatalystError::InvalidConfig(_))
+        ));
+        let csv = modifications_to_csv(&query(state, &risky).unwrap());
+        assert_eq!(csv.lines().count(), 3);
+    }
+}
@@ -0,0 +1,257 @@
+//! Signal handling for long-running operations
+//!
+//! `Drop` guards such as [`crate::lock::OperationLock`] never run when the process
+//! is killed by SIGINT/SIGTERM, which leaves stale lock files and half-written
+//! staging files behind. This module installs a handler that:
+//!
+//! 1. Removes every path registered with [`register_cleanup`]
+//! 2. Prints a hint describing how to resume the interrupted operation
+//! 3. Exits with status 130 (the conventional "terminated by Ctrl-C" code)
+//!
+//! Code that creates temporary files registers them for the duration of
+//! their life via [`CleanupGuard`].
+//!
+//! Init and update go further: while they hold a [`defer_interrupts`]
+//! guard, the first Ctrl-C only sets a flag. They notice it at their next
+//! cancellation checkpoint (see [`crate::cancel`]), after the skill or step
+//! in flight, roll back, and release the project lock on the way out. A
+//! second Ctrl-C exits at once as above.
+
+use crate::types::ExitCode;
+use once_cell::sync::Lazy;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Exit status used when the process is interrupted
+pub const INTERRUPTED_EXIT_CODE: i32 = ExitCode::Interrupted.code();
+
+/// Paths to remove if the process is interrupted
+static CLEANUP: Lazy<CleanupRegistry> = Lazy::new(CleanupRegistry::default);
+
+/// Set by a Ctrl-C that was deferred to the running operation
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Number of live [`DeferInterrupts`] guards
+static DEFERRING: AtomicUsize = AtomicUsize::new(0);
+
+/// Message printed after cleanup, telling the user how to continue
+static RESUME_HINT: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// Install the SIGINT/SIGTERM handler
+///
+/// Safe to call more than once; only the first call installs the handler.
+pub fn install_handler() {
+    static INSTALLED: std::sync::Once = std::sync::Once::new();
+
+    INSTALLED.call_once(|| {
+        let result = ctrlc::set_handler(|| {
+            if defer_interrupt() {
+                eprintln!();
+                eprintln!(
+                    "⚠️  Interrupted - stopping after the current step (Ctrl-C again to stop now)"
+                );
+                return;
+            }
+            let removed = run_cleanup();
+
+            eprintln!();
+            eprintln!("⚠️  Interrupted - stopping");
+            for path in &removed {
+                eprintln!("   Removed {}", path.display());
+            }
+            if let Some(hint) = take_resume_hint() {
+                eprintln!("   {}", hint);
+            }
+
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        });
+
+        if let Err(e) = result {
+            eprintln!("⚠️  Failed to install signal handler: {}", e);
+        }
+    });
+}
+
+/// Record a Ctrl-C for the running operation to act on, if one is
+/// deferring interrupts and this is the first Ctrl-C
+fn defer_interrupt() -> bool {
+    DEFERRING.load(Ordering::SeqCst) > 0 && !INTERRUPTED.swap(true, Ordering::SeqCst)
+}
+
+/// Whether a deferred Ctrl-C is waiting to stop the running operation
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Handle Ctrl-C through [`is_interrupted`] while the guard lives
+pub fn defer_interrupts() -> DeferInterrupts {
+    DEFERRING.fetch_add(1, Ordering::SeqCst);
+    DeferInterrupts { _private: () }
+}
+
+/// Guard returned by [`defer_interrupts`]
+#[derive(Debug)]
+pub struct DeferInterrupts {
+    _private: (),
+}
+
+impl Drop for DeferInterrupts {
+    fn drop(&mut self) {
+        DEFERRING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Set the hint printed if the current operation is interrupted
+pub fn set_resume_hint(hint: impl Into<String>) {
+    if let Ok(mut slot) = RESUME_HINT.lock() {
+        *slot = Some(hint.into());
+    }
+}
+
+/// Clear the resume hint once an operation is finished
+pub fn clear_resume_hint() {
+    if let Ok(mut slot) = RESUME_HINT.lock() {
+        *slot = None;
+    }
+}
+
+fn take_resume_hint() -> Option<String> {
+    RESUME_HINT.lock().ok().and_then(|mut slot| slot.take())
+}
+
+/// Register a path to be removed if the process is interrupted
+pub fn register_cleanup(path: &Path) {
+    CLEANUP.register(path);
+}
+
+/// Stop tracking a path (it was completed or removed normally)
+pub fn unregister_cleanup(path: &Path) {
+    CLEANUP.unregister(path);
+}
+
+/// Remove all paths registered for interrupt cleanup
+///
+/// # Returns
+///
+/// Returns the paths that were actually removed
+pub fn run_cleanup() -> Vec<PathBuf> {
+    CLEANUP.run()
+}
+
+/// Set of paths to remove on interruption
+#[derive(Debug, Default)]
+pub struct CleanupRegistry {
+    paths: Mutex<Vec<PathBuf>>,
+}
+
+impl CleanupRegistry {
+    /// Track a path
+    pub fn register(&self, path: &Path) {
+        if let Ok(mut paths) = self.paths.lock() {
+            paths.push(path.to_path_buf());
+        }
+    }
+
+    /// Stop tracking the most recent registration of a path
+    pub fn unregister(&self, path: &Path) {
+        if let Ok(mut paths) = self.paths.lock() {
+            if let Some(pos) = paths.iter().rposition(|p| p == path) {
+                paths.remove(pos);
+            }
+        }
+    }
+
+    /// Remove all tracked paths, most recently registered first
+    ///
+    /// # Returns
+    ///
+    /// Returns the paths that were actually removed
+    pub fn run(&self) -> Vec<PathBuf> {
+        let paths = match self.paths.lock() {
+            Ok(mut paths) => std::mem::take(&mut *paths),
+            // A panic while holding the lock shouldn't prevent cleanup
+            Err(poisoned) => std::mem::take(&mut *poisoned.into_inner()),
+        };
+
+        let mut removed = Vec::new();
+        for path in paths.into_iter().rev() {
+            let result = if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            };
+            if result.is_ok() {
+                removed.push(path);
+            }
+        }
+        removed
+    }
+}
+
+/// Registers a path for interrupt cleanup and unregisters it when dropped
+///
+/// Dropping the guard does not delete the path - it only means the path no
+/// longer needs cleaning up if a signal arrives.
+pub struct CleanupGuard {
+    path: PathBuf,
+}
+
+impl CleanupGuard {
+    pub fn new(path: &Path) -> Self {
+        register_cleanup(path);
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+}
+
+impl Drop for CleanupGuard {
+    fn drop(&mut self) {
+        unregister_cleanup(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cleanup_registry() {
+        let temp_dir = TempDir::new().unwrap();
+        let kept = temp_dir.path().join("kept.tmp");
+        let staging = temp_dir.path().join("staging.partial");
+        let staging_dir = temp_dir.path().join("staging-dir");
+        fs::write(&kept, "x").unwrap();
+        fs::write(&staging, "x").unwrap();
+        fs::create_dir(&staging_dir).unwrap();
+        fs::write(staging_dir.join("file"), "x").unwrap();
+
+        let registry = CleanupRegistry::default();
+        registry.register(&kept);
+        registry.register(&staging);
+        registry.register(&staging_dir);
+        registry.unregister(&kept);
+
+        let removed = registry.run();
+        assert_eq!(removed, vec![staging_dir.clone(), staging.clone()]);
+        assert!(kept.exists());
+        assert!(!staging.exists());
+        assert!(!staging_dir.exists());
+
+        // Registry is drained after running
+        assert!(registry.run().is_empty());
+    }
+
+    #[test]
+    fn test_resume_hint() {
+        set_resume_hint("Run 'catalyst update --resume'");
+        assert_eq!(
+            take_resume_hint().as_deref(),
+            Some("Run 'catalyst update --resume'")
+        );
+        assert!(take_resume_hint().is_none());
+    }
+}
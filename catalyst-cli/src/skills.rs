@@ -0,0 +1,185 @@
+//! Installed skills (`catalyst skills list` and `catalyst skills outdated`)
+//!
+//! Every directory in `.claude/skills` with a SKILL.md (or SKILL.md.tmpl) is
+//! a skill. Its [`SkillManifest`] supplies the name, version, and one-line
+//! summary shown in listings. A skill whose manifest can't be read is still
+//! listed, together with the problem, since Claude Code would skip it.
+//!
+//! A skill's installed version is the one in its own skill.toml, which is
+//! copied in with the rest of the skill and recorded in catalyst.lock.
+
+use crate::init;
+use crate::library;
+use crate::self_update;
+use crate::types::{CatalystError, Result, SKILLS_DIR};
+use catalyst_core::skill_manifest::{SkillManifest, SKILL_FILE, SKILL_TEMPLATE_FILE};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A skill found in `.claude/skills`
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillInfo {
+    /// Directory name
+    pub id: String,
+    pub path: PathBuf,
+    /// Linked from the skill library rather than copied
+    pub linked: bool,
+    /// None when the manifest couldn't be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<SkillManifest>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub problem: Option<String>,
+}
+
+/// Skills installed in a project, sorted by ID
+///
+/// # Errors
+///
+/// Returns `FileReadFailed` if `.claude/skills` exists but can't be read
+pub fn list_skills(target_dir: &Path) -> Result<Vec<SkillInfo>> {
+    let dir = target_dir.join(SKILLS_DIR);
+    let read_error = |source| CatalystError::FileReadFailed {
+        path: dir.clone(),
+        source,
+    };
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => return Err(read_error(source)),
+    };
+
+    let mut skills = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(read_error)?.path();
+        let is_skill = path.join(SKILL_FILE).is_file() || path.join(SKILL_TEMPLATE_FILE).is_file();
+        let Some(id) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if !is_skill {
+            continue;
+        }
+        let id = id.to_string();
+        let (manifest, problem) = match SkillManifest::load(&path) {
+            Ok(manifest) => (Some(manifest), None),
+            Err(e) => (None, Some(format!("{:#}", e))),
+        };
+        skills.push(SkillInfo {
+            linked: library::is_linked(target_dir, &id),
+            id,
+            path,
+            manifest,
+            problem,
+        });
+    }
+    skills.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(skills)
+}
+
+/// An installed skill with a newer version embedded in this CLI
+#[derive(Debug, Clone, Serialize)]
+pub struct OutdatedSkill {
+    pub id: String,
+    /// None when the installed copy has no version or an unreadable manifest
+    pub installed: Option<String>,
+    pub available: String,
+}
+
+/// Installed skills that `catalyst update --skill` would update, sorted by ID
+///
+/// Skills Catalyst doesn't ship, or ships without a version, are never
+/// outdated. Skills linked from the skill library are left out; they are
+/// updated in the library by `catalyst update`.
+///
+/// # Errors
+///
+/// Returns `FileReadFailed` if `.claude/skills` exists but can't be read
+pub fn outdated_skills(target_dir: &Path) -> Result<Vec<OutdatedSkill>> {
+    let mut outdated = Vec::new();
+    for skill in list_skills(target_dir)? {
+        if skill.linked {
+            continue;
+        }
+        let Some(available) = init::shipped_manifest(&skill.id).and_then(|m| m.version) else {
+            continue;
+        };
+        let installed = skill.manifest.and_then(|manifest| manifest.version);
+        let is_outdated = match &installed {
+            Some(installed) => self_update::is_newer(&available, installed),
+            None => true,
+        };
+        if is_outdated {
+            outdated.push(OutdatedSkill {
+                id: skill.id,
+                installed,
+                available,
+            });
+        }
+    }
+    Ok(outdated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_list_skills_reads_manifests() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path().join(SKILLS_DIR);
+        for (id, skill_md) in [
+            (
+                "good",
+                "---\nname: good\ndescription: Does good things\n---\n",
+            ),
+            ("broken", "---\nname: broken\n---\n"),
+        ] {
+            fs::create_dir_all(skills_dir.join(id)).unwrap();
+            fs::write(skills_dir.join(id).join(SKILL_FILE), skill_md).unwrap();
+        }
+        fs::write(skills_dir.join("good/skill.toml"), "version = \"2.0.0\"\n").unwrap();
+        fs::create_dir_all(skills_dir.join("not-a-skill")).unwrap();
+
+        let skills = list_skills(temp_dir.path()).unwrap();
+        let ids: Vec<&str> = skills.iter().map(|skill| skill.id.as_str()).collect();
+        assert_eq!(ids, ["broken", "good"]);
+        assert!(skills[0].problem.as_ref().unwrap().contains("description"));
+        let good = skills[1].manifest.as_ref().unwrap();
+        assert_eq!(good.version.as_deref(), Some("2.0.0"));
+        assert_eq!(good.summary(), "Does good things");
+    }
+
+    #[test]
+    fn test_outdated_skills_compares_with_shipped_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path().join(SKILLS_DIR);
+        for id in ["rust-developer", "route-tester", "homegrown"] {
+            fs::create_dir_all(skills_dir.join(id)).unwrap();
+            let skill_md = format!("---\nname: {}\ndescription: Test skill\n---\n", id);
+            fs::write(skills_dir.join(id).join(SKILL_FILE), skill_md).unwrap();
+        }
+        fs::write(
+            skills_dir.join("rust-developer/skill.toml"),
+            "version = \"0.0.1\"\n",
+        )
+        .unwrap();
+        fs::write(
+            skills_dir.join("route-tester/skill.toml"),
+            "version = \"999.0.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            skills_dir.join("homegrown/skill.toml"),
+            "version = \"0.0.1\"\n",
+        )
+        .unwrap();
+
+        let outdated = outdated_skills(temp_dir.path()).unwrap();
+        assert_eq!(outdated.len(), 1);
+        assert_eq!(outdated[0].id, "rust-developer");
+        assert_eq!(outdated[0].installed.as_deref(), Some("0.0.1"));
+        let shipped = init::shipped_manifest("rust-developer").unwrap();
+        assert_eq!(Some(outdated[0].available.clone()), shipped.version);
+    }
+}
@@ -0,0 +1,223 @@
+//! Project type detection for `catalyst init`
+//!
+//! Init looks for the manifest files of the languages Catalyst has skills
+//! and checkers for, and uses what it finds to pick defaults: the skills
+//! and settings of the matching init profile (see [`crate::profiles`]),
+//! and the cargo-check hook, whose checkers cover all four stacks (see
+//! [`crate::build_check`]).
+//!
+//! | Stack   | Marker files                                    | Profile        |
+//! |---------|-------------------------------------------------|----------------|
+//! | Rust    | `Cargo.toml`                                    | `rust`         |
+//! | Node.js | `package.json`                                  | `fullstack-ts` |
+//! | Python  | `pyproject.toml`, `setup.py`, `requirements.txt`| `python`       |
+//! | Go      | `go.mod`                                        | (none)         |
+//!
+//! Only the project root is inspected; monorepos with manifests in
+//! subdirectories are left to `--profile` or `init.skills`.
+
+use crate::profiles;
+use crate::types::Result;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::path::Path;
+
+/// A language ecosystem init knows defaults for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stack {
+    Rust,
+    Node,
+    Python,
+    Go,
+}
+
+impl Stack {
+    /// Every stack, in detection order
+    pub const ALL: &'static [Stack] = &[Stack::Rust, Stack::Node, Stack::Python, Stack::Go];
+
+    /// Files whose presence at the project root marks the stack
+    pub fn markers(self) -> &'static [&'static str] {
+        match self {
+            Stack::Rust => &["Cargo.toml"],
+            Stack::Node => &["package.json"],
+            Stack::Python => &["pyproject.toml", "setup.py", "requirements.txt"],
+            Stack::Go => &["go.mod"],
+        }
+    }
+
+    /// Init profile holding the stack's skills and settings, if there is one
+    pub fn profile(self) -> Option<&'static str> {
+        match self {
+            Stack::Rust => Some("rust"),
+            Stack::Node => Some("fullstack-ts"),
+            Stack::Python => Some("python"),
+            Stack::Go => None,
+        }
+    }
+}
+
+impl fmt::Display for Stack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Stack::Rust => "Rust",
+            Stack::Node => "Node.js",
+            Stack::Python => "Python",
+            Stack::Go => "Go",
+        })
+    }
+}
+
+/// A stack found in a project, with the file that gave it away
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedStack {
+    pub stack: Stack,
+    pub marker: &'static str,
+}
+
+impl fmt::Display for DetectedStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.stack, self.marker)
+    }
+}
+
+/// Find the stacks used at a project root
+///
+/// # Arguments
+///
+/// * `project_dir` - Project root (the directory containing `.claude`)
+///
+/// # Returns
+///
+/// Each detected stack once, in [`Stack::ALL`] order; empty if none match
+pub fn detect_stacks(project_dir: &Path) -> Vec<DetectedStack> {
+    Stack::ALL
+        .iter()
+        .filter_map(|&stack| {
+            stack
+                .markers()
+                .iter()
+                .find(|marker| project_dir.join(marker).is_file())
+                .map(|&marker| DetectedStack { stack, marker })
+        })
+        .collect()
+}
+
+/// Init defaults for a set of detected stacks
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StackDefaults {
+    /// Skills of every matching profile, without duplicates
+    pub skills: Vec<String>,
+    /// Settings of every matching profile, merged
+    pub settings: Map<String, Value>,
+    /// Whether to install the cargo-check hook
+    pub install_check_hook: bool,
+}
+
+/// Combine the profiles of the detected stacks into init defaults
+///
+/// # Arguments
+///
+/// * `stacks` - Result of [`detect_stacks`]
+/// * `user_profile_dir` - Where user profiles live, so a user's `rust`
+///   profile is used for Rust projects
+///
+/// # Errors
+///
+/// Returns the errors of [`profiles::list_profiles`]
+pub fn stack_defaults(
+    stacks: &[DetectedStack],
+    user_profile_dir: Option<&Path>,
+) -> Result<StackDefaults> {
+    let mut defaults = StackDefaults {
+        install_check_hook: !stacks.is_empty(),
+        ..Default::default()
+    };
+    if stacks.is_empty() {
+        return Ok(defaults);
+    }
+
+    let available = profiles::list_profiles(user_profile_dir)?;
+    for name in stacks
+        .iter()
+        .filter_map(|detected| detected.stack.profile())
+    {
+        let Some(profile) = available.iter().find(|p| p.name == name) else {
+            continue;
+        };
+        for skill in &profile.skills {
+            if !defaults.skills.contains(skill) {
+                defaults.skills.push(skill.clone());
+            }
+        }
+        merge_settings(&mut defaults.settings, &profile.settings);
+    }
+
+    Ok(defaults)
+}
+
+/// Merge `from` into `into`: objects key by key, arrays as a union, and
+/// otherwise the value already in `into` wins
+fn merge_settings(into: &mut Map<String, Value>, from: &Map<String, Value>) {
+    for (key, value) in from {
+        match (into.get_mut(key), value) {
+            (None, _) => {
+                into.insert(key.clone(), value.clone());
+            }
+            (Some(Value::Object(existing)), Value::Object(incoming)) => {
+                merge_settings(existing, incoming);
+            }
+            (Some(Value::Array(existing)), Value::Array(incoming)) => {
+                for item in incoming {
+                    if !existing.contains(item) {
+                        existing.push(item.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detects_stacks_and_merges_their_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        assert!(detect_stacks(dir).is_empty());
+        assert!(!stack_defaults(&[], None).unwrap().install_check_hook);
+
+        fs::write(dir.join("Cargo.toml"), "[package]\n").unwrap();
+        fs::write(dir.join("requirements.txt"), "requests\n").unwrap();
+        fs::create_dir(dir.join("go.mod")).unwrap(); // not a file
+
+        let stacks = detect_stacks(dir);
+        assert_eq!(
+            stacks,
+            [
+                DetectedStack {
+                    stack: Stack::Rust,
+                    marker: "Cargo.toml"
+                },
+                DetectedStack {
+                    stack: Stack::Python,
+                    marker: "requirements.txt"
+                },
+            ]
+        );
+        assert_eq!(stacks[1].to_string(), "Python (requirements.txt)");
+
+        let defaults = stack_defaults(&stacks, None).unwrap();
+        assert!(defaults.install_check_hook);
+        assert_eq!(defaults.skills, ["skill-developer", "rust-developer"]);
+        let allow = defaults.settings["permissions"]["allow"]
+            .as_array()
+            .unwrap();
+        assert!(allow.contains(&"Bash(cargo test:*)".into()));
+        assert!(allow.contains(&"Bash(pytest:*)".into()));
+    }
+}
@@ -0,0 +1,513 @@
+//! Catalyst state archives (`catalyst export-state` / `import-state`)
+//!
+//! Bundles the metadata catalyst keeps about a project - the installed
+//! version, skill hashes, and catalyst.toml - into a single JSON file that
+//! can be restored on another machine. User-level state (the install method
+//! marker, tracker databases, and the skill library) can be included too.
+//!
+//! Each entry records which root it belongs to and a `/`-separated path
+//! relative to that root, so an archive made on Linux restores on Windows.
+//! Text files are stored as-is; anything that isn't UTF-8 is hex-encoded.
+
+use crate::library;
+use crate::safe_path;
+use crate::types::{
+    CatalystError, Result, BINARY_DIR, CATALYST_VERSION, CLAUDE_DIR, CONFIG_FILE,
+    CONFIG_FILE_CLAUDE, HASHES_FILE, INSTALL_METHOD_FILE, SKILLS_DIR, TRACKER_STATE_DIR,
+    VERSION_FILE,
+};
+use catalyst_core::fs_ops::Fs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Value of the archive's `format` field
+pub const STATE_ARCHIVE_FORMAT: &str = "catalyst-state";
+
+/// Archive layout version written by this catalyst
+pub const STATE_ARCHIVE_VERSION: u32 = 1;
+
+/// How archive entries are named in traversal errors
+const STATE_ENTRY: &str = "state archive entry";
+
+/// Directory an archive entry is relative to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StateScope {
+    /// The project directory
+    Project,
+    /// The user's home directory
+    Home,
+    /// The machine-wide skill library
+    Library,
+}
+
+impl std::fmt::Display for StateScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateScope::Project => write!(f, "project"),
+            StateScope::Home => write!(f, "home"),
+            StateScope::Library => write!(f, "library"),
+        }
+    }
+}
+
+/// How an entry's content is stored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StateEncoding {
+    #[default]
+    Utf8,
+    Hex,
+}
+
+/// One file in a state archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateEntry {
+    pub scope: StateScope,
+
+    /// Path relative to the scope's root, with `/` separators
+    pub path: String,
+
+    #[serde(default)]
+    pub encoding: StateEncoding,
+
+    pub content: String,
+}
+
+impl StateEntry {
+    fn from_bytes(scope: StateScope, path: String, bytes: Vec<u8>) -> Self {
+        match String::from_utf8(bytes) {
+            Ok(content) => Self {
+                scope,
+                path,
+                encoding: StateEncoding::Utf8,
+                content,
+            },
+            Err(e) => Self {
+                scope,
+                path,
+                encoding: StateEncoding::Hex,
+                content: e
+                    .into_bytes()
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect(),
+            },
+        }
+    }
+
+    fn bytes(&self) -> Result<Vec<u8>> {
+        match self.encoding {
+            StateEncoding::Utf8 => Ok(self.content.clone().into_bytes()),
+            StateEncoding::Hex => {
+                let invalid = || {
+                    CatalystError::InvalidConfig(format!(
+                        "Archive entry {} is not valid hex",
+                        self.path
+                    ))
+                };
+                if !self.content.len().is_multiple_of(2) {
+                    return Err(invalid());
+                }
+                (0..self.content.len())
+                    .step_by(2)
+                    .map(|i| {
+                        self.content
+                            .get(i..i + 2)
+                            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                            .ok_or_else(invalid)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A complete state archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateArchive {
+    pub format: String,
+    pub version: u32,
+
+    /// Catalyst version that wrote the archive
+    pub catalyst_version: String,
+
+    /// RFC 3339 creation time
+    pub created: String,
+
+    pub entries: Vec<StateEntry>,
+}
+
+/// Directories archive entries are read from and restored into
+///
+/// Scopes without a root are left out of exports and skipped on import.
+#[derive(Debug, Clone, Default)]
+pub struct StateRoots {
+    pub project: PathBuf,
+    pub home: Option<PathBuf>,
+    pub library: Option<PathBuf>,
+}
+
+impl StateRoots {
+    /// Project state only
+    pub fn project(target_dir: &Path) -> Self {
+        Self {
+            project: target_dir.to_path_buf(),
+            ..Default::default()
+        }
+    }
+
+    /// Project state plus the current user's home directory and skill library
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidPath` if the home directory can't be determined
+    pub fn with_user(target_dir: &Path) -> Result<Self> {
+        let home = dirs::home_dir().ok_or_else(|| {
+            CatalystError::InvalidPath("Could not determine home directory".to_string())
+        })?;
+        Ok(Self {
+            project: target_dir.to_path_buf(),
+            home: Some(home),
+            library: Some(library::library_dir()?),
+        })
+    }
+
+    fn root(&self, scope: StateScope) -> Option<&Path> {
+        match scope {
+            StateScope::Project => Some(&self.project),
+            StateScope::Home => self.home.as_deref(),
+            StateScope::Library => self.library.as_deref(),
+        }
+    }
+}
+
+/// Outcome of restoring an archive
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    /// Files written
+    pub restored: Vec<PathBuf>,
+
+    /// Files that already had the archived content
+    pub unchanged: Vec<PathBuf>,
+
+    /// Existing files with different content, left alone (no `--force`)
+    pub conflicts: Vec<PathBuf>,
+
+    /// Entries whose scope wasn't restored
+    pub skipped: usize,
+}
+
+/// Project files that make up catalyst's metadata
+fn project_state_files() -> Vec<PathBuf> {
+    vec![
+        PathBuf::from(VERSION_FILE),
+        PathBuf::from(HASHES_FILE),
+        Path::new(SKILLS_DIR).join(HASHES_FILE),
+        PathBuf::from(CONFIG_FILE),
+        PathBuf::from(CONFIG_FILE_CLAUDE),
+    ]
+}
+
+/// Collect catalyst's state into an archive
+///
+/// # Arguments
+///
+/// * `roots` - Project directory, plus the home and library directories if
+///   user-level state should be included
+///
+/// # Errors
+///
+/// Returns `FileReadFailed` if a state file exists but can't be read
+pub fn export_state(roots: &StateRoots) -> Result<StateArchive> {
+    let mut entries = Vec::new();
+
+    for relative in project_state_files() {
+        add_file(&mut entries, StateScope::Project, &roots.project, &relative)?;
+    }
+
+    if let Some(home) = &roots.home {
+        add_file(
+            &mut entries,
+            StateScope::Home,
+            home,
+            &Path::new(BINARY_DIR).join(INSTALL_METHOD_FILE),
+        )?;
+        let tracker_dir = Path::new(CLAUDE_DIR).join(TRACKER_STATE_DIR);
+        if let Ok(read_dir) = fs::read_dir(home.join(&tracker_dir)) {
+            let mut names: Vec<_> = read_dir
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+                .map(|entry| entry.file_name())
+                .collect();
+            names.sort();
+            for name in names {
+                add_file(
+                    &mut entries,
+                    StateScope::Home,
+                    home,
+                    &tracker_dir.join(name),
+                )?;
+            }
+        }
+    }
+
+    if let Some(library) = roots.library.as_deref().filter(|dir| dir.is_dir()) {
+        for entry in WalkDir::new(library)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+        {
+            if let Ok(relative) = entry.path().strip_prefix(library) {
+                add_file(&mut entries, StateScope::Library, library, relative)?;
+            }
+        }
+    }
+
+    Ok(StateArchive {
+        format: STATE_ARCHIVE_FORMAT.to_string(),
+        version: STATE_ARCHIVE_VERSION,
+        catalyst_version: CATALYST_VERSION.to_string(),
+        created: chrono::Utc::now().to_rfc3339(),
+        entries,
+    })
+}
+
+fn add_file(
+    entries: &mut Vec<StateEntry>,
+    scope: StateScope,
+    root: &Path,
+    relative: &Path,
+) -> Result<()> {
+    let path = root.join(relative);
+    if !path.is_file() {
+        return Ok(());
+    }
+    let bytes = fs::read(&path).map_err(|source| CatalystError::FileReadFailed {
+        path: path.clone(),
+        source,
+    })?;
+    let relative = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    entries.push(StateEntry::from_bytes(scope, relative, bytes));
+    Ok(())
+}
+
+/// Read and check a state archive
+///
+/// # Errors
+///
+/// Returns `FileReadFailed` if the file can't be read, or `InvalidConfig`
+/// if it isn't a catalyst state archive or was written by a newer catalyst
+pub fn read_archive(path: &Path) -> Result<StateArchive> {
+    let content = fs::read_to_string(path).map_err(|source| CatalystError::FileReadFailed {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let archive: StateArchive = serde_json::from_str(&content).map_err(|e| {
+        CatalystError::InvalidConfig(format!("{} is not a state archive: {}", path.display(), e))
+    })?;
+    if archive.format != STATE_ARCHIVE_FORMAT {
+        return Err(CatalystError::InvalidConfig(format!(
+            "{} is not a state archive (format '{}')",
+            path.display(),
+            archive.format
+        )));
+    }
+    if archive.version > STATE_ARCHIVE_VERSION {
+        return Err(CatalystError::InvalidConfig(format!(
+            "{} uses archive version {}, but this catalyst reads up to version {}. \
+             Update catalyst and try again",
+            path.display(),
+            archive.version,
+            STATE_ARCHIVE_VERSION
+        )));
+    }
+    Ok(archive)
+}
+
+/// Restore an archive's entries under `roots`
+///
+/// All entry paths, including symlinks already under each root, are checked
+/// before anything is written. Existing files
+/// with different content are reported as conflicts unless `force` is set.
+///
+/// # Errors
+///
+/// Returns `PathTraversalDetected` if an entry would escape its root,
+/// `InvalidConfig` for undecodable content, and write errors otherwise
+pub fn import_state(
+    archive: &StateArchive,
+    roots: &StateRoots,
+    force: bool,
+) -> Result<ImportReport> {
+    let mut planned = Vec::new();
+    for entry in &archive.entries {
+        safe_path::relative_path(&entry.path, STATE_ENTRY)?;
+        let destination = roots
+            .root(entry.scope)
+            .map(|root| safe_path::join_within(root, &entry.path, STATE_ENTRY))
+            .transpose()?;
+        planned.push((destination, entry.bytes()?));
+    }
+
+    let mut report = ImportReport::default();
+    for (destination, bytes) in planned {
+        let Some(destination) = destination else {
+            report.skipped += 1;
+            continue;
+        };
+
+        match fs::read(&destination) {
+            Ok(existing) if existing == bytes => {
+                report.unchanged.push(destination);
+                continue;
+            }
+            Ok(_) if !force => {
+                report.conflicts.push(destination);
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent).map_err(|source| {
+                CatalystError::DirectoryCreationFailed {
+                    path: parent.to_path_buf(),
+                    source,
+                }
+            })?;
+        }
+        Fs::read_write()
+            .write_atomic(&destination, &bytes)
+            .map_err(|source| CatalystError::FileWriteFailed {
+                path: destination.clone(),
+                source,
+            })?;
+        report.restored.push(destination);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let old = temp_dir.path().join("old");
+        let home = temp_dir.path().join("home");
+        fs::create_dir_all(old.join(SKILLS_DIR)).unwrap();
+        fs::write(old.join(VERSION_FILE), "0.1.0").unwrap();
+        fs::write(old.join(CONFIG_FILE), "[init]\n").unwrap();
+        fs::write(old.join(SKILLS_DIR).join(HASHES_FILE), "{}").unwrap();
+        let tracker_dir = home.join(CLAUDE_DIR).join(TRACKER_STATE_DIR);
+        fs::create_dir_all(&tracker_dir).unwrap();
+        fs::write(tracker_dir.join("s1.db"), [0u8, 159, 146, 150]).unwrap();
+
+        let archive = export_state(&StateRoots {
+            project: old,
+            home: Some(home),
+            library: None,
+        })
+        .unwrap();
+        assert_eq!(archive.entries.len(), 4);
+        let db = archive.entries.iter().find(|e| e.path.ends_with("s1.db"));
+        assert_eq!(db.unwrap().encoding, StateEncoding::Hex);
+
+        // Round-trip through the JSON file
+        let archive_path = temp_dir.path().join("state.json");
+        fs::write(&archive_path, serde_json::to_string(&archive).unwrap()).unwrap();
+        let archive = read_archive(&archive_path).unwrap();
+
+        let new = temp_dir.path().join("new");
+        let new_home = temp_dir.path().join("new-home");
+        fs::create_dir_all(&new).unwrap();
+        fs::write(new.join(CONFIG_FILE), "# local\n").unwrap();
+        let roots = StateRoots {
+            project: new.clone(),
+            home: Some(new_home.clone()),
+            library: None,
+        };
+
+        let report = import_state(&archive, &roots, false).unwrap();
+        assert_eq!(report.restored.len(), 3);
+        assert_eq!(report.conflicts, vec![new.join(CONFIG_FILE)]);
+        assert_eq!(fs::read_to_string(new.join(VERSION_FILE)).unwrap(), "0.1.0");
+        assert_eq!(
+            fs::read(
+                new_home
+                    .join(CLAUDE_DIR)
+                    .join(TRACKER_STATE_DIR)
+                    .join("s1.db")
+            )
+            .unwrap(),
+            [0u8, 159, 146, 150]
+        );
+
+        let report = import_state(&archive, &StateRoots::project(&new), true).unwrap();
+        assert_eq!(report.restored, vec![new.join(CONFIG_FILE)]);
+        assert_eq!(report.unchanged.len(), 2);
+        assert_eq!(report.skipped, 1);
+    }
+
+    #[test]
+    fn test_import_rejects_escaping_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut archive = export_state(&StateRoots::project(temp_dir.path())).unwrap();
+        for path in [
+            "../evil",
+            "/etc/passwd",
+            "a/../../b",
+            "a\\..\\b",
+            "C:/x",
+            "",
+        ] {
+            archive.entries = vec![StateEntry::from_bytes(
+                StateScope::Project,
+                path.to_string(),
+                b"x".to_vec(),
+            )];
+            let result = import_state(&archive, &StateRoots::project(temp_dir.path()), true);
+            assert!(
+                matches!(result, Err(CatalystError::PathTraversalDetected(_))),
+                "{:?} accepted",
+                path
+            );
+        }
+    }
+    #[cfg(unix)]
+    #[test]
+    fn test_import_checks_every_entry_before_writing() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        let outside = temp_dir.path().join("outside");
+        fs::create_dir_all(project.join(".claude")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, project.join(".claude/link")).unwrap();
+
+        let mut archive = export_state(&StateRoots::project(&project)).unwrap();
+        archive.entries = ["CLAUDE.md", ".claude/link/payload"]
+            .into_iter()
+            .map(|path| {
+                StateEntry::from_bytes(StateScope::Project, path.to_string(), b"x".to_vec())
+            })
+            .collect();
+
+        let result = import_state(&archive, &StateRoots::project(&project), true);
+        assert!(matches!(
+            result,
+            Err(CatalystError::PathTraversalDetected(_))
+        ));
+        assert!(!project.join("CLAUDE.md").exists());
+        assert!(!outside.join("payload").exists());
+    }
+}
@@ -3,47 +3,241 @@
 //! This module provides comprehensive validation of Catalyst installations,
 //! including binary checks, hook configurations, and skill installations.
 //! It also provides auto-fix capabilities for common issues.
-
+//!
+//! Validation only reads: every check gets an [`Fs<ReadOnly>`](Fs) handle,
+//! and the checks that have to run another program (see [`Probes`]) only
+//! run when [`check_installation`] asks for them. Fixes write through an
+//! `Fs<ReadWrite>` of their own.
+
+use crate::binaries;
+use crate::cancel;
+use crate::config::CatalystConfig;
+use crate::deprecation;
+use crate::healthcheck;
+use crate::init;
+use crate::lock::OperationLock;
+use crate::lockfile::{self, Lockfile};
+use crate::modification_store::TrackerBackend;
+use crate::powershell;
+use crate::progress;
+use crate::selinux;
+use crate::target;
+use crate::timings;
 use crate::types::{
-    BinaryStatus, CatalystError, HookStatus, Issue, IssueSeverity, Platform, Result, SkillStatus,
-    StatusLevel, StatusReport, VersionStatus, BINARY_DIR, HOOKS_DIR, SETTINGS_FILE, SKILLS_DIR,
-    SKILL_RULES_FILE,
+    BinaryStatus, CatalystError, DanglingHook, HookStatus, Issue, IssueKind, IssueSeverity,
+    LabelStatus, LockedComponent, PermissionStatus, Platform, Result, SkillStatus, StatusLevel,
+    StatusReport, TargetedFixReport, VersionStatus, BINARY_DIR, CATALYST_VERSION, HASHES_FILE,
+    HOOKS_DIR, SETTINGS_FILE, SKILLS_DIR, SKILL_RULES_FILE, VERSION_FILE,
 };
-use crate::validation::{binary_exists, detect_file_change_tracker_variant, get_binary_directory};
-use catalyst_core::settings::ClaudeSettings;
-use std::fs;
-use std::path::Path;
-
+use crate::validation::{
+    binary_exists, detect_file_change_tracker_variant, detect_install_method, get_binary_directory,
+    install_command, upgrade_command,
+};
+use crate::validators;
+use catalyst_core::fs_ops::{self, Fs, PermissionPolicy, ReadOnly};
+use catalyst_core::jsonc;
+use catalyst_core::settings::{find_closest_match, ClaudeSettings, Hook, HookConfig, HookEvent};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[cfg(not(unix))]
+use crate::validation::windows_executable;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
-/// Validate the complete Catalyst installation
+/// Hook binaries `install-binaries` may skip, validated only when present
+const OPTIONAL_HOOK_BINARIES: &[&str] = &[
+    "path-guard",
+    "test-runner",
+    "prompt-logger",
+    "session-digest",
+];
+
+/// Checks that run another program, which [`validate_installation`] leaves out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Probes {
+    /// Run each hook binary with `--version`, and ask the OS for this
+    /// machine's architecture
+    pub binaries: bool,
+
+    /// Read the SELinux contexts of hook wrappers with `stat`
+    pub selinux: bool,
+
+    /// Look for PowerShell hosts and their execution policy
+    pub powershell: bool,
+
+    /// Run the `[[validators]]` configured in catalyst.toml
+    pub validators: bool,
+
+    /// Run each skill's `[healthcheck] command`
+    ///
+    /// Off in [`Probes::STANDARD`]: the commands come from skill.toml files
+    /// in the project, so they only run when the user asks for them.
+    pub healthchecks: bool,
+}
+
+impl Probes {
+    /// What `catalyst status` runs by default: everything except skill
+    /// health check commands
+    pub const STANDARD: Probes = Probes {
+        binaries: true,
+        selinux: true,
+        powershell: true,
+        validators: true,
+        healthchecks: false,
+    };
+}
+
+/// Validate the complete Catalyst installation without running anything
 ///
 /// Performs comprehensive checks on binaries, hooks, and skills,
 /// returning a detailed status report with issues and severity levels.
+/// Nothing is written and no other program is started, so it is safe to
+/// run against protected or untrusted checkouts; use
+/// [`check_installation`] for the checks that need [`Probes`].
 ///
 /// # Arguments
 ///
 /// * `target_dir` - Base directory to validate (defaults to current directory)
 /// * `platform` - Current platform (for platform-specific checks)
 pub fn validate_installation(target_dir: &Path, platform: Platform) -> Result<StatusReport> {
+    check_installation(target_dir, platform, Probes::default())
+}
+
+/// Validate the complete Catalyst installation, running the selected probes
+///
+/// # Arguments
+///
+/// * `target_dir` - Base directory to validate
+/// * `platform` - Current platform (for platform-specific checks)
+/// * `probes` - Which checks that start other programs to run
+///
+/// # Errors
+///
+/// Returns an error if catalyst.toml or catalyst.lock is invalid, or
+/// `Cancelled` if the operation was cancelled between groups of checks
+pub fn check_installation(
+    target_dir: &Path,
+    platform: Platform,
+    probes: Probes,
+) -> Result<StatusReport> {
+    let fs = Fs::read_only();
     let mut report = StatusReport::new();
+    let config = CatalystConfig::read(&fs, target_dir)?;
 
     // Task 4.2: Validate binaries
-    report.binaries = validate_binaries(platform)?;
+    let span = timings::span("validate binaries");
+    let tracker = config
+        .init
+        .install_tracker
+        .then_some(config.tracker.backend);
+    report.binaries = validate_binaries(&fs, platform, tracker, probes.binaries)?;
+    if let Ok(bin_dir) = get_binary_directory() {
+        report.install_method = detect_install_method(&fs, &bin_dir);
+    }
+    if probes.binaries {
+        report.host_arch = target::host_arch();
+    }
+    for binary in &report.binaries {
+        progress::detail(|| match (&binary.version, binary.exists) {
+            (Some(version), _) => format!("Binary {}: version {}", binary.name, version),
+            (None, true) => format!("Binary {}: installed", binary.name),
+            (None, false) => format!("Binary {}: not installed", binary.name),
+        });
+    }
+    drop(span);
+
+    cancel::checkpoint()?;
 
     // Task 4.3: Validate hooks
-    let (hooks, settings_parse_error) = validate_hooks(target_dir, platform)?;
+    let span = timings::span("validate hooks");
+    let (hooks, dangling_hooks, settings_parse_error) =
+        validate_hooks(&fs, target_dir, platform, &config)?;
     report.hooks = hooks;
+    report.dangling_hooks = dangling_hooks;
+    for hook in &report.hooks {
+        progress::detail(|| {
+            let state = match (hook.exists, hook.configured) {
+                (false, _) => "wrapper missing",
+                (true, true) => "registered",
+                (true, false) => "not registered in settings.json",
+            };
+            format!("Hook {}: {}", hook.name, state)
+        });
+    }
+    drop(span);
+
+    cancel::checkpoint()?;
 
     // Task 4.4: Validate skills
-    report.skills = validate_skills(target_dir)?;
+    let span = timings::span("validate skills");
+    report.skills = validate_skills(&fs, target_dir, probes.healthchecks)?;
+    for skill in &report.skills {
+        progress::detail(|| {
+            let state = if skill.registered {
+                "registered"
+            } else {
+                "not in skill-rules.json"
+            };
+            format!("Skill {}: {}", skill.name, state)
+        });
+    }
+    drop(span);
 
     // Check version file
-    report.version_status = check_version(target_dir)?;
+    let span = timings::span("check version");
+    report.version_status = check_version(&fs, target_dir)?;
+    drop(span);
+
+    cancel::checkpoint()?;
+
+    // Compare with a committed catalyst.lock
+    let span = timings::span("check lockfile");
+    if let Some(locked) = lockfile::read(&fs, target_dir)? {
+        // Versions were already asked for (or not) when checking binaries
+        let installed = Lockfile::capture_with(&fs, target_dir, platform, |name, _| {
+            report
+                .binaries
+                .iter()
+                .find(|binary| binary.name == name)
+                .and_then(|binary| binary.version.clone())
+        })?;
+        report.lock_drift = lockfile::drift(&locked, &installed);
+        progress::detail(|| {
+            format!(
+                "Compared with catalyst.lock: {} difference(s)",
+                report.lock_drift.len()
+            )
+        });
+    }
+    drop(span);
+
+    report.unusual_permissions = check_permissions(&fs, target_dir, &report.hooks);
+    if probes.selinux {
+        report.mislabeled_hooks = check_labels(&fs, target_dir, &report.hooks);
+    }
+
+    if probes.powershell && config.wrapper_platform(platform) == Platform::Windows {
+        let span = timings::span("check powershell");
+        report.powershell = Some(powershell::check(target_dir, &config));
+        drop(span);
+    }
 
     // Collect issues based on validation results
-    collect_issues(&mut report, settings_parse_error);
+    collect_issues(&mut report, platform, settings_parse_error);
+
+    cancel::checkpoint()?;
+
+    // Project-specific checks from catalyst.toml
+    if probes.validators {
+        let span = timings::span("run validators");
+        report
+            .issues
+            .extend(validators::run_validators(target_dir, &config.validators));
+        drop(span);
+    }
 
     // Determine overall status level
     report.level = determine_status_level(&report);
@@ -51,17 +245,43 @@ pub fn validate_installation(target_dir: &Path, platform: Platform) -> Result<St
     Ok(report)
 }
 
+/// Validate an installation without blocking an async runtime
+///
+/// Runs [`validate_installation`] on tokio's blocking pool, stopping
+/// between groups of checks once `cancel` is cancelled.
+///
+/// # Errors
+///
+/// Returns `Cancelled` if `cancel` was cancelled before validation
+/// finished, and otherwise the errors of [`validate_installation`]
+#[cfg(feature = "tokio")]
+pub async fn validate_installation_async(
+    target_dir: PathBuf,
+    platform: Platform,
+    cancel: tokio_util::sync::CancellationToken,
+) -> Result<StatusReport> {
+    cancel::run_blocking(cancel, move || validate_installation(&target_dir, platform)).await
+}
+
 /// Validate that all required binaries are installed and accessible
 ///
 /// Checks ~/.claude-hooks/bin/ (or Windows equivalent) for:
 /// - skill-activation-prompt
-/// - file-change-tracker (both variants: SQLite and basic)
+/// - file-change-tracker (SQLite) or file-change-tracker-basic (JSONL)
 /// - file-analyzer
 ///
 /// # Arguments
 ///
 /// * `platform` - Current platform (for .exe extension on Windows)
-fn validate_binaries(platform: Platform) -> Result<Vec<BinaryStatus>> {
+/// * `tracker` - Configured tracker backend, or None if the tracker is
+///   disabled in catalyst.toml
+/// * `probe_versions` - Whether to run each binary with `--version`
+fn validate_binaries(
+    fs: &Fs<ReadOnly>,
+    platform: Platform,
+    tracker: Option<TrackerBackend>,
+    probe_versions: bool,
+) -> Result<Vec<BinaryStatus>> {
     let mut binaries = Vec::new();
 
     // Get binary directory
@@ -69,35 +289,79 @@ fn validate_binaries(platform: Platform) -> Result<Vec<BinaryStatus>> {
 
     // Check skill-activation-prompt
     binaries.push(validate_binary(
+        fs,
         "skill-activation-prompt",
         &bin_dir,
         platform,
         None,
+        probe_versions,
     ));
 
-    // Check file-change-tracker (detect variant)
-    let tracker_variant = detect_file_change_tracker_variant(&bin_dir, platform)?;
-    binaries.push(validate_binary(
-        "file-change-tracker",
-        &bin_dir,
-        platform,
-        tracker_variant,
-    ));
+    // Check file-change-tracker (detect variant), unless disabled in config
+    match tracker {
+        Some(TrackerBackend::Sqlite) => {
+            let tracker_variant = detect_file_change_tracker_variant(fs, &bin_dir, platform)?;
+            binaries.push(validate_binary(
+                fs,
+                "file-change-tracker",
+                &bin_dir,
+                platform,
+                tracker_variant,
+                probe_versions,
+            ));
+        }
+        Some(backend @ TrackerBackend::Jsonl) => binaries.push(validate_binary(
+            fs,
+            backend.hook_binary(),
+            &bin_dir,
+            platform,
+            Some("basic".to_string()),
+            probe_versions,
+        )),
+        None => {}
+    }
 
-    // Check file-analyzer
-    binaries.push(validate_binary("file-analyzer", &bin_dir, platform, None));
+    // Check file-analyzer and cargo-check
+    for name in ["file-analyzer", "cargo-check"] {
+        binaries.push(validate_binary(
+            fs,
+            name,
+            &bin_dir,
+            platform,
+            None,
+            probe_versions,
+        ));
+    }
+
+    // Optional hooks are only checked once installed
+    for name in OPTIONAL_HOOK_BINARIES {
+        if binary_exists(fs, &bin_dir, name, platform) {
+            binaries.push(validate_binary(
+                fs,
+                name,
+                &bin_dir,
+                platform,
+                None,
+                probe_versions,
+            ));
+        }
+    }
 
     Ok(binaries)
 }
 
 /// Validate a single binary
+///
+/// Its version is only known if `probe_version` allows running it.
 fn validate_binary(
+    fs: &Fs<ReadOnly>,
     name: &str,
     bin_dir: &Path,
     platform: Platform,
     variant: Option<String>,
+    probe_version: bool,
 ) -> BinaryStatus {
-    let exists = binary_exists(bin_dir, name, platform);
+    let exists = binary_exists(fs, bin_dir, name, platform);
     let path = if exists {
         Some(bin_dir.join(format!(
             "{}{}",
@@ -117,7 +381,7 @@ fn validate_binary(
     let executable = {
         path.as_ref()
             .map(|p| {
-                fs::metadata(p)
+                fs.metadata(p)
                     .ok()
                     .map(|m| m.permissions().mode() & 0o111 != 0)
                     .unwrap_or(false)
@@ -126,15 +390,29 @@ fn validate_binary(
     };
 
     #[cfg(not(unix))]
-    let executable = true; // Windows executability not checked
+    let executable = path
+        .as_deref()
+        .is_some_and(|p| windows_executable(fs, p, std::env::var("PATHEXT").ok().as_deref()));
+
+    let version = path
+        .as_deref()
+        .filter(|_| executable && probe_version)
+        .and_then(binaries::binary_version);
+    let version_matches = version
+        .as_deref()
+        .is_some_and(|version| binaries::version_compatible(name, version, CATALYST_VERSION));
 
     BinaryStatus {
         name: name.to_string(),
         exists,
         executable,
-        version: None, // MVP: version detection not implemented
-        expected_version: None,
-        version_matches: false,
+        version,
+        expected_version: Some(CATALYST_VERSION.to_string()),
+        version_matches,
+        arches: path
+            .as_deref()
+            .and_then(|p| target::executable_arches(p).ok())
+            .unwrap_or_default(),
         path,
         variant,
     }
@@ -151,23 +429,40 @@ fn validate_binary(
 /// # Arguments
 ///
 /// * `target_dir` - Base directory containing .claude/
-/// * `platform` - Current platform (for wrapper extension)
+/// * `platform` - Current platform (for binary lookup)
+/// * `config` - Project configuration (wrapper flavor and which hooks are enabled)
 ///
 /// # Returns
 ///
 /// Returns (hooks, parse_error) tuple where parse_error is Some if settings.json
 /// couldn't be parsed, allowing the caller to add it to the issues list.
 fn validate_hooks(
+    fs: &Fs<ReadOnly>,
     target_dir: &Path,
     platform: Platform,
-) -> Result<(Vec<HookStatus>, Option<String>)> {
+    config: &CatalystConfig,
+) -> Result<(Vec<HookStatus>, Vec<DanglingHook>, Option<String>)> {
     let mut hooks = Vec::new();
+    let hooks_dir = target_dir.join(HOOKS_DIR);
+    let extension = config.wrapper_platform(platform).hook_extension();
 
     // Check if settings.json exists
     let settings_path = target_dir.join(SETTINGS_FILE);
-    if !settings_path.exists() {
-        // No settings.json - report empty hooks (no error, just not configured)
-        return Ok((hooks, None));
+    if !fs.exists(&settings_path) {
+        // No settings.json - only installed wrappers are worth reporting
+        let settings = ClaudeSettings::default();
+        for (event, binary_name) in catalyst_hooks(config) {
+            report_unregistered_hook(
+                fs,
+                &settings,
+                &mut hooks,
+                event,
+                binary_name,
+                &hooks_dir,
+                extension,
+            );
+        }
+        return Ok((hooks, Vec::new(), None));
     }
 
     // Parse settings.json
@@ -178,7 +473,7 @@ fn validate_hooks(
         ))
     })?;
 
-    let settings = match ClaudeSettings::read(settings_path_str) {
+    let settings = match ClaudeSettings::read_with(fs, settings_path_str) {
         Ok(s) => s,
         Err(e) => {
             // Invalid settings.json - return error details for issue reporting
@@ -186,20 +481,17 @@ fn validate_hooks(
                 "Failed to parse settings.json: {}. Check for invalid JSON, missing fields, or incorrect structure.",
                 e
             );
-            return Ok((hooks, Some(error_msg)));
+            return Ok((hooks, Vec::new(), Some(error_msg)));
         }
     };
 
-    // Check configured hooks
-    let hooks_dir = target_dir.join(HOOKS_DIR);
-    let extension = platform.hook_extension();
-
     // PR feedback: Extracted common validation logic to reduce duplication
     // Check UserPromptSubmit hook (skill-activation-prompt)
     validate_hook_for_event(
+        fs,
         &settings,
         &mut hooks,
-        catalyst_core::settings::HookEvent::UserPromptSubmit,
+        HookEvent::UserPromptSubmit,
         "UserPromptSubmit",
         "skill-activation-prompt",
         &hooks_dir,
@@ -209,9 +501,10 @@ fn validate_hooks(
 
     // Check PostToolUse hook (file-change-tracker)
     validate_hook_for_event(
+        fs,
         &settings,
         &mut hooks,
-        catalyst_core::settings::HookEvent::PostToolUse,
+        HookEvent::PostToolUse,
         "PostToolUse",
         "file-change-tracker",
         &hooks_dir,
@@ -219,7 +512,177 @@ fn validate_hooks(
         platform,
     );
 
-    Ok((hooks, None))
+    // Installed wrappers with no settings.json entry never run
+    for (event, binary_name) in catalyst_hooks(config) {
+        report_unregistered_hook(
+            fs,
+            &settings,
+            &mut hooks,
+            event,
+            binary_name,
+            &hooks_dir,
+            extension,
+        );
+    }
+
+    // Commands pointing at project files that aren't there. Catalyst's own
+    // wrappers are already covered above (and recreated by --fix).
+    let reported: Vec<&Path> = hooks.iter().filter_map(|h| h.path.as_deref()).collect();
+    let dangling = find_dangling_hooks(fs, target_dir, &settings)
+        .into_iter()
+        .filter(|dangling| !reported.contains(&dangling.path.as_path()))
+        .collect();
+
+    Ok((hooks, dangling, None))
+}
+
+/// How hook commands can refer to the project root
+const PROJECT_DIR_VARIABLES: &[&str] = &[
+    "${CLAUDE_PROJECT_DIR}",
+    "$CLAUDE_PROJECT_DIR",
+    "%CLAUDE_PROJECT_DIR%",
+    "$env:CLAUDE_PROJECT_DIR",
+];
+
+/// Paths under the project root that a hook command refers to
+///
+/// Returns each reference as written (without surrounding quotes) with the
+/// path it resolves to. A quoted reference runs to the closing quote; an
+/// unquoted one ends at whitespace.
+fn project_references(command: &str, target_dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut references = Vec::new();
+    for variable in PROJECT_DIR_VARIABLES {
+        for (start, _) in command.match_indices(variable) {
+            let quote = command[..start]
+                .chars()
+                .next_back()
+                .filter(|c| *c == '"' || *c == '\'');
+            let rest = &command[start..];
+            let end = match quote {
+                Some(quote) => rest.find(quote),
+                None => rest.find(char::is_whitespace),
+            }
+            .unwrap_or(rest.len());
+            let reference = &rest[..end];
+            let relative = reference[variable.len()..].trim_start_matches(['/', '\\']);
+            if !relative.is_empty() {
+                // Windows-style separators resolve on every platform
+                let relative = relative.replace('\\', "/");
+                references.push((reference.to_string(), target_dir.join(relative)));
+            }
+        }
+    }
+    references
+}
+
+/// Find hook commands that run a project file that doesn't exist
+///
+/// Only paths given relative to `$CLAUDE_PROJECT_DIR` are checked; bare
+/// program names are looked up on PATH by the shell and can't be resolved
+/// here. When a file with a close name exists in the same directory (a
+/// typo or a renamed wrapper), the fixed command is suggested.
+fn find_dangling_hooks(
+    fs: &Fs<ReadOnly>,
+    target_dir: &Path,
+    settings: &ClaudeSettings,
+) -> Vec<DanglingHook> {
+    let mut dangling = Vec::new();
+    for (event, configs) in &settings.hooks {
+        for hook in configs.iter().flat_map(|config| &config.hooks) {
+            for (reference, path) in project_references(&hook.command, target_dir) {
+                if fs.exists(&path) {
+                    continue;
+                }
+                let fixed_command = closest_sibling(fs, &path).and_then(|sibling| {
+                    let missing = path.file_name()?.to_str()?;
+                    let prefix = reference.strip_suffix(missing)?;
+                    Some(
+                        hook.command
+                            .replace(&reference, &format!("{}{}", prefix, sibling)),
+                    )
+                });
+                dangling.push(DanglingHook {
+                    event: event.to_string(),
+                    command: hook.command.clone(),
+                    reference,
+                    path,
+                    fixed_command,
+                });
+            }
+        }
+    }
+    dangling.sort_by(|a, b| (&a.event, &a.reference).cmp(&(&b.event, &b.reference)));
+    dangling
+}
+
+/// Name of the existing file closest to a missing one, in the same directory
+fn closest_sibling(fs: &Fs<ReadOnly>, missing: &Path) -> Option<String> {
+    let name = missing.file_name()?.to_str()?;
+    let names: Vec<String> = fs
+        .read_dir(missing.parent()?)
+        .ok()?
+        .flatten()
+        .filter(|entry| fs.is_file(&entry.path()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    find_closest_match(name, &names).map(str::to_string)
+}
+
+/// Hooks Catalyst manages for this project, as (event, binary name)
+fn catalyst_hooks(config: &CatalystConfig) -> Vec<(HookEvent, &'static str)> {
+    let mut hooks = Vec::new();
+    if config.init.install_hooks {
+        hooks.push((HookEvent::UserPromptSubmit, "skill-activation-prompt"));
+    }
+    if config.init.install_tracker {
+        hooks.push((HookEvent::PostToolUse, "file-change-tracker"));
+    }
+    // Unset means init decided from the project type; an unregistered
+    // wrapper is only reported when one was installed
+    if config.init.install_check_hook != Some(false) {
+        hooks.push((HookEvent::PostToolUse, "cargo-check"));
+    }
+    hooks
+}
+
+/// Report a hook whose wrapper is installed but which settings.json doesn't
+/// reference under its event
+///
+/// Hooks without a wrapper on disk are skipped, since that usually means
+/// Catalyst was never installed for them rather than that an entry was lost.
+fn report_unregistered_hook(
+    fs: &Fs<ReadOnly>,
+    settings: &ClaudeSettings,
+    hooks: &mut Vec<HookStatus>,
+    event: HookEvent,
+    binary_name: &str,
+    hooks_dir: &Path,
+    extension: &str,
+) {
+    let registered = settings.hooks.get(&event).is_some_and(|configs| {
+        configs
+            .iter()
+            .flat_map(|config| &config.hooks)
+            .any(|hook| hook.command.contains(binary_name))
+    });
+    let wrapper_name = format!("{}.{}", binary_name, extension);
+    let wrapper_path = hooks_dir.join(&wrapper_name);
+
+    if registered || !fs.is_file(&wrapper_path) {
+        return;
+    }
+
+    hooks.push(HookStatus {
+        name: wrapper_name,
+        exists: true,
+        // Not evaluated: the hook can't run until it is registered
+        executable: true,
+        configured: false,
+        event: Some(event.to_string()),
+        path: Some(wrapper_path),
+        calls_correct_binary: true,
+    });
 }
 
 /// Helper function to validate hooks for a specific event (PR feedback - extracted duplication)
@@ -248,9 +711,10 @@ fn validate_hooks(
 /// - No duplicate entries in the status report
 #[allow(clippy::too_many_arguments)]
 fn validate_hook_for_event(
+    fs: &Fs<ReadOnly>,
     settings: &catalyst_core::settings::ClaudeSettings,
     hooks: &mut Vec<HookStatus>,
-    event: catalyst_core::settings::HookEvent,
+    event: HookEvent,
     event_name: &str,
     binary_name: &str,
     hooks_dir: &std::path::Path,
@@ -271,6 +735,7 @@ fn validate_hook_for_event(
                     // insert() returns true if the value was newly inserted (not already present)
                     if validated_wrappers.insert(wrapper_name.clone()) {
                         hooks.push(validate_hook(
+                            fs,
                             &wrapper_name,
                             event_name,
                             hooks_dir,
@@ -286,6 +751,7 @@ fn validate_hook_for_event(
 
 /// Validate a single hook wrapper
 fn validate_hook(
+    fs: &Fs<ReadOnly>,
     wrapper_name: &str,
     event: &str,
     hooks_dir: &Path,
@@ -293,12 +759,12 @@ fn validate_hook(
     platform: Platform,
 ) -> HookStatus {
     let wrapper_path = hooks_dir.join(wrapper_name);
-    let exists = wrapper_path.exists();
+    let exists = fs.exists(&wrapper_path);
 
     // Check if executable (Unix only)
     #[cfg(unix)]
     let executable = if exists {
-        fs::metadata(&wrapper_path)
+        fs.metadata(&wrapper_path)
             .ok()
             .map(|m| m.permissions().mode() & 0o111 != 0)
             .unwrap_or(false)
@@ -307,7 +773,8 @@ fn validate_hook(
     };
 
     #[cfg(not(unix))]
-    let executable = true; // Windows doesn't need executable check
+    let executable =
+        exists && windows_executable(fs, &wrapper_path, std::env::var("PATHEXT").ok().as_deref());
 
     // Check if binary is accessible
     let bin_dir = match get_binary_directory() {
@@ -324,7 +791,7 @@ fn validate_hook(
             }
         }
     };
-    let calls_correct_binary = exists && binary_exists(&bin_dir, binary_name, platform);
+    let calls_correct_binary = exists && binary_exists(fs, &bin_dir, binary_name, platform);
 
     HookStatus {
         name: wrapper_name.to_string(),
@@ -344,6 +811,7 @@ fn validate_hook(
 /// 2. skill-rules.json exists and is valid
 /// 3. Each skill has required files (SKILL.md)
 /// 4. Skills are registered in skill-rules.json
+/// 5. Health checks declared in each skill's skill.toml pass
 ///
 /// # Arguments
 ///
@@ -355,19 +823,23 @@ fn validate_hook(
 /// Now properly parses the file and verifies each skill directory is listed
 /// in the skills object. This catches configuration drift where skills are
 /// installed but not registered.
-fn validate_skills(target_dir: &Path) -> Result<Vec<SkillStatus>> {
+fn validate_skills(
+    fs: &Fs<ReadOnly>,
+    target_dir: &Path,
+    run_healthchecks: bool,
+) -> Result<Vec<SkillStatus>> {
     let mut skills = Vec::new();
 
     let skills_dir = target_dir.join(SKILLS_DIR);
-    if !skills_dir.exists() {
+    if !fs.is_dir(&skills_dir) {
         return Ok(skills);
     }
 
     // Parse skill-rules.json to get registered skills
     let skill_rules_path = target_dir.join(SKILL_RULES_FILE);
-    let registered_skills = if skill_rules_path.exists() {
-        match fs::read_to_string(&skill_rules_path) {
-            Ok(content) => match serde_json::from_str::<serde_json::Value>(&content) {
+    let registered_skills = if fs.exists(&skill_rules_path) {
+        match fs.read_to_string(&skill_rules_path) {
+            Ok(content) => match jsonc::from_str::<serde_json::Value>(&content) {
                 Ok(json) => {
                     // Extract skill names from the "skills" object
                     json.get("skills")
@@ -390,15 +862,23 @@ fn validate_skills(target_dir: &Path) -> Result<Vec<SkillStatus>> {
         std::collections::HashSet::new()
     };
 
+    // Hashes recorded at install, keyed by path relative to the skills dir.
+    // Without them (or if they're unreadable) drift can't be detected.
+    let installed_hashes: HashMap<String, String> = fs
+        .read_to_string(&skills_dir.join(HASHES_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
     // Read installed skills from directory
-    let entries = match fs::read_dir(&skills_dir) {
+    let entries = match fs.read_dir(&skills_dir) {
         Ok(entries) => entries,
         Err(_) => return Ok(skills),
     };
 
     for entry in entries.flatten() {
         let path = entry.path();
-        if path.is_dir() {
+        if fs.is_dir(&path) {
             let skill_name = path
                 .file_name()
                 .and_then(|n| n.to_str())
@@ -410,18 +890,22 @@ fn validate_skills(target_dir: &Path) -> Result<Vec<SkillStatus>> {
                 continue;
             }
 
-            let has_main_file = path.join("SKILL.md").exists();
+            let has_main_file = fs.exists(&path.join("SKILL.md"));
             let is_registered = registered_skills.contains(&skill_name);
+            let health = healthcheck::check_skill(fs, target_dir, &path, run_healthchecks);
+            let drift = skill_drift(fs, &skills_dir, &skill_name, &installed_hashes);
 
             skills.push(SkillStatus {
                 name: skill_name,
                 exists: true,
                 has_main_file,
                 registered: is_registered,
-                current_hash: None, // Not computed during validation
-                expected_hash: None,
-                modified: false,
+                current_hash: drift.current_hash,
+                expected_hash: drift.expected_hash,
+                modified: !drift.modified_files.is_empty(),
+                modified_files: drift.modified_files,
                 path: Some(path),
+                health,
             });
         }
     }
@@ -429,16 +913,115 @@ fn validate_skills(target_dir: &Path) -> Result<Vec<SkillStatus>> {
     Ok(skills)
 }
 
+/// How an installed skill differs from the hashes recorded at install
+#[derive(Default)]
+struct SkillDrift {
+    current_hash: Option<String>,
+    expected_hash: Option<String>,
+    modified_files: Vec<String>,
+}
+
+/// Compare a skill's files with the hashes recorded when it was installed
+///
+/// Files the skill gained since install aren't drift; only recorded files
+/// that changed or disappeared are. Skills with no recorded hashes (custom
+/// skills, or skills linked from the library) never drift.
+fn skill_drift(
+    fs: &Fs<ReadOnly>,
+    skills_dir: &Path,
+    skill_name: &str,
+    installed_hashes: &HashMap<String, String>,
+) -> SkillDrift {
+    let mut drift = SkillDrift::default();
+    let main_file = format!("{}/SKILL.md", skill_name);
+
+    for (relative, expected) in installed_hashes {
+        if !Path::new(relative).starts_with(skill_name) {
+            continue;
+        }
+        let current = fs
+            .read(&skills_dir.join(relative))
+            .ok()
+            .map(|content| format!("{:x}", Sha256::digest(&content)));
+        if Path::new(relative) == Path::new(&main_file) {
+            drift.current_hash = current.clone();
+            drift.expected_hash = Some(expected.clone());
+        }
+        if current.as_ref() != Some(expected) {
+            drift.modified_files.push(relative.replace('\\', "/"));
+        }
+    }
+
+    drift.modified_files.sort();
+    drift
+}
+
+/// Find managed files whose permissions look wrong
+///
+/// Only reports; Catalyst never changes an existing file's mode unless told
+/// to with `init --reset-permissions`. Always empty on non-Unix platforms.
+fn check_permissions(
+    fs: &Fs<ReadOnly>,
+    target_dir: &Path,
+    hooks: &[HookStatus],
+) -> Vec<PermissionStatus> {
+    let files = [SETTINGS_FILE, SKILL_RULES_FILE, VERSION_FILE]
+        .iter()
+        .map(|file| target_dir.join(file))
+        .chain(hooks.iter().filter_map(|hook| hook.path.clone()));
+
+    files
+        .filter_map(|path| {
+            let mode = fs.file_mode(&path)?;
+            let reason = fs_ops::unusual_mode(mode)?;
+            Some(PermissionStatus {
+                path,
+                mode,
+                reason: reason.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Find hook wrappers whose SELinux type differs from the hooks directory
+///
+/// Always empty when SELinux is not enabled or contexts can't be read.
+/// Contexts are read with `stat`, so this only runs under [`Probes::selinux`].
+fn check_labels(fs: &Fs<ReadOnly>, target_dir: &Path, hooks: &[HookStatus]) -> Vec<LabelStatus> {
+    let hooks_dir = target_dir.join(HOOKS_DIR);
+    if !selinux::is_enabled_with(fs) || !fs.is_dir(&hooks_dir) {
+        return Vec::new();
+    }
+    let Some(expected) = selinux::file_context(&hooks_dir) else {
+        return Vec::new();
+    };
+
+    hooks
+        .iter()
+        .filter_map(|hook| hook.path.clone())
+        .filter(|path| fs.is_file(path))
+        .filter_map(|path| {
+            let context = selinux::file_context(&path)?;
+            selinux::is_mislabeled(&context, &expected).then(|| LabelStatus {
+                path,
+                context,
+                expected: expected.clone(),
+            })
+        })
+        .collect()
+}
+
 /// Check version file status
-fn check_version(target_dir: &Path) -> Result<VersionStatus> {
+fn check_version(fs: &Fs<ReadOnly>, target_dir: &Path) -> Result<VersionStatus> {
     let version_path = target_dir.join(".catalyst-version");
 
-    if !version_path.exists() {
+    if !fs.exists(&version_path) {
         return Ok(VersionStatus::Missing);
     }
 
     // Read version file
-    let version = fs::read_to_string(&version_path)
+    let version = fs
+        .read_to_string(&version_path)
         .map_err(CatalystError::Io)?
         .trim()
         .to_string();
@@ -460,11 +1043,17 @@ fn check_version(target_dir: &Path) -> Result<VersionStatus> {
 /// # Arguments
 ///
 /// * `report` - Status report to add issues to
+/// * `platform` - Current platform (for install commands)
 /// * `settings_parse_error` - Optional error from parsing settings.json
-fn collect_issues(report: &mut StatusReport, settings_parse_error: Option<String>) {
+fn collect_issues(
+    report: &mut StatusReport,
+    platform: Platform,
+    settings_parse_error: Option<String>,
+) {
     // Check for settings.json parse errors (PR #21 feedback - comment #2)
     if let Some(error_msg) = settings_parse_error {
         report.issues.push(Issue {
+            kind: IssueKind::SettingsParseError,
             severity: IssueSeverity::Error,
             component: "settings.json".to_string(),
             description: error_msg,
@@ -479,27 +1068,113 @@ fn collect_issues(report: &mut StatusReport, settings_parse_error: Option<String
     for binary in &report.binaries {
         if !binary.exists {
             report.issues.push(Issue {
+                kind: IssueKind::BinaryMissing,
                 severity: IssueSeverity::Error,
                 component: format!("{} binary", binary.name),
                 description: format!("Binary '{}' not found in {}", binary.name, BINARY_DIR),
                 auto_fixable: false,
-                suggested_fix: Some("Run: cd catalyst && ./install.sh".to_string()),
+                suggested_fix: Some(format!(
+                    "Run: {}",
+                    install_command(
+                        report.install_method,
+                        platform,
+                        binary.name == "file-change-tracker"
+                    )
+                )),
             });
         } else if !binary.executable {
             report.issues.push(Issue {
+                kind: IssueKind::BinaryNotExecutable,
                 severity: IssueSeverity::Warning,
                 component: format!("{} binary", binary.name),
                 description: format!("Binary '{}' is not executable", binary.name),
                 auto_fixable: false,
-                suggested_fix: Some(format!("Run: chmod +x ~/.claude-hooks/bin/{}", binary.name)),
+                suggested_fix: Some(match platform {
+                    Platform::Windows => format!(
+                        "Reinstall it: {}",
+                        install_command(
+                            report.install_method,
+                            platform,
+                            binary.name == "file-change-tracker"
+                        )
+                    ),
+                    _ => format!("Run: chmod +x ~/.claude-hooks/bin/{}", binary.name),
+                }),
+            });
+        }
+
+        // A binary that doesn't answer --version predates version reporting;
+        // there's nothing to compare
+        if let (Some(version), false) = (&binary.version, binary.version_matches) {
+            report.issues.push(Issue {
+                kind: IssueKind::BinaryVersionMismatch,
+                severity: IssueSeverity::Warning,
+                component: format!("{} binary", binary.name),
+                description: format!(
+                    "Binary '{}' is version {}, which is not compatible with catalyst {}",
+                    binary.name, version, CATALYST_VERSION
+                ),
+                auto_fixable: false,
+                suggested_fix: Some(format!(
+                    "Run: catalyst update, then {}",
+                    install_command(
+                        report.install_method,
+                        platform,
+                        binary.name == "file-change-tracker"
+                    )
+                )),
+            });
+        }
+
+        // A foreign binary won't start on Linux; macOS and Windows emulate it
+        if let Some(host_arch) = report
+            .host_arch
+            .filter(|arch| !binary.arches.is_empty() && !binary.arches.contains(arch))
+        {
+            report.issues.push(Issue {
+                kind: IssueKind::BinaryArchMismatch,
+                severity: if matches!(platform, Platform::Linux | Platform::WSL) {
+                    IssueSeverity::Error
+                } else {
+                    IssueSeverity::Warning
+                },
+                component: format!("{} binary", binary.name),
+                description: format!(
+                    "Binary '{}' is built for {} but this machine is {}",
+                    binary.name, binary.arches[0], host_arch
+                ),
+                auto_fixable: false,
+                suggested_fix: Some(format!(
+                    "Reinstall the {} build: {}",
+                    host_arch,
+                    install_command(
+                        report.install_method,
+                        platform,
+                        binary.name == "file-change-tracker"
+                    )
+                )),
             });
         }
     }
 
-    // Check for missing or non-executable hooks
+    // Check for unregistered, missing, or non-executable hooks
     for hook in &report.hooks {
-        if !hook.exists {
+        if !hook.configured {
+            report.issues.push(Issue {
+                kind: IssueKind::HookEntryMissing,
+                severity: IssueSeverity::Error,
+                component: format!("{} hook", hook.name),
+                description: format!(
+                    "Hook wrapper '{}' is installed but not registered for {} in settings.json",
+                    hook.name,
+                    hook.event.as_deref().unwrap_or("its event")
+                ),
+                auto_fixable: true,
+                suggested_fix: Some("Run: catalyst status --fix".to_string()),
+            });
+        } else if !hook.exists {
             report.issues.push(Issue {
+                kind: IssueKind::HookWrapperMissing,
                 severity: IssueSeverity::Error,
                 component: format!("{} hook wrapper", hook.name),
                 description: format!("Hook wrapper '{}' not found", hook.name),
@@ -508,6 +1183,7 @@ fn collect_issues(report: &mut StatusReport, settings_parse_error: Option<String
             });
         } else if !hook.executable {
             report.issues.push(Issue {
+                kind: IssueKind::HookWrapperNotExecutable,
                 severity: IssueSeverity::Warning,
                 component: format!("{} hook wrapper", hook.name),
                 description: format!("Hook wrapper '{}' is not executable", hook.name),
@@ -516,19 +1192,103 @@ fn collect_issues(report: &mut StatusReport, settings_parse_error: Option<String
             });
         } else if !hook.calls_correct_binary {
             report.issues.push(Issue {
+                kind: IssueKind::HookBinaryUnreachable,
                 severity: IssueSeverity::Warning,
                 component: format!("{} hook wrapper", hook.name),
                 description: format!("Hook wrapper '{}' cannot access required binary", hook.name),
                 auto_fixable: false,
-                suggested_fix: Some("Run: cd catalyst && ./install.sh".to_string()),
+                suggested_fix: Some(format!(
+                    "Run: {}",
+                    install_command(report.install_method, platform, false)
+                )),
             });
         }
     }
 
+    for dangling in &report.dangling_hooks {
+        let suggested_fix = match dangling.fixed_command {
+            Some(ref fixed) => format!("Run: catalyst status --fix (rewrites it to: {})", fixed),
+            None => format!(
+                "Fix the path in settings.json, or remove the hook: catalyst settings remove-hook -e {} -P \"{}\"",
+                dangling.event, dangling.reference
+            ),
+        };
+        report.issues.push(Issue {
+            kind: IssueKind::HookCommandDangling,
+            severity: IssueSeverity::Error,
+            component: format!("{} hook command", dangling.reference),
+            description: format!(
+                "{} hook runs {}, which doesn't exist",
+                dangling.event,
+                dangling.path.display()
+            ),
+            auto_fixable: dangling.fixed_command.is_some(),
+            suggested_fix: Some(suggested_fix),
+        });
+    }
+
+    for drift in &report.lock_drift {
+        let restore = match (drift.kind, &drift.installed) {
+            (_, None) => "catalyst init --from-lock".to_string(),
+            (LockedComponent::Skill, Some(_)) => "catalyst update --force".to_string(),
+            (LockedComponent::Wrapper, Some(_)) => "catalyst init --reconcile".to_string(),
+            (LockedComponent::Binary, Some(_)) => {
+                install_command(report.install_method, platform, false)
+            }
+        };
+        let suggested_fix = if drift.locked.is_none() {
+            "Run: catalyst lock to add it to the lockfile".to_string()
+        } else {
+            format!(
+                "Run: {} to restore it, or catalyst lock to accept the current install",
+                restore
+            )
+        };
+        report.issues.push(Issue {
+            kind: IssueKind::LockfileDrift,
+            severity: IssueSeverity::Warning,
+            component: format!("{} {}", drift.kind, drift.name),
+            description: drift.to_string(),
+            auto_fixable: false,
+            suggested_fix: Some(suggested_fix),
+        });
+    }
+
     // Check for incomplete skills
     for skill in &report.skills {
+        if let Some(deprecated) = deprecation::lookup(&skill.name) {
+            let (description, suggested_fix) = match deprecated.replaced_by {
+                Some(new_id) => (
+                    format!(
+                        "Skill '{}' was renamed to '{}' in {}",
+                        skill.name, new_id, deprecated.since
+                    ),
+                    "Run: catalyst update --migrate-skills".to_string(),
+                ),
+                None => (
+                    format!(
+                        "Skill '{}' was deprecated in {}: {}",
+                        skill.name, deprecated.since, deprecated.note
+                    ),
+                    format!(
+                        "Remove {}/{} and its skill-rules.json entry",
+                        SKILLS_DIR, skill.name
+                    ),
+                ),
+            };
+            report.issues.push(Issue {
+                kind: IssueKind::DeprecatedSkill,
+                severity: IssueSeverity::Warning,
+                component: format!("{} skill", skill.name),
+                description,
+                auto_fixable: deprecated.replaced_by.is_some(),
+                suggested_fix: Some(suggested_fix),
+            });
+        }
+
         if !skill.has_main_file {
             report.issues.push(Issue {
+                kind: IssueKind::SkillMissingMainFile,
                 severity: IssueSeverity::Warning,
                 component: format!("{} skill", skill.name),
                 description: format!("Skill '{}' is missing SKILL.md", skill.name),
@@ -540,6 +1300,7 @@ fn collect_issues(report: &mut StatusReport, settings_parse_error: Option<String
         // PR #21 Feedback - Comment #3: Report unregistered skills
         if !skill.registered {
             report.issues.push(Issue {
+                kind: IssueKind::SkillUnregistered,
                 severity: IssueSeverity::Warning,
                 component: format!("{} skill", skill.name),
                 description: format!(
@@ -552,12 +1313,144 @@ fn collect_issues(report: &mut StatusReport, settings_parse_error: Option<String
                 ),
             });
         }
+
+        if skill.modified {
+            report.issues.push(Issue {
+                kind: IssueKind::SkillModified,
+                severity: IssueSeverity::Info,
+                component: format!("{} skill", skill.name),
+                description: format!(
+                    "Skill '{}' was changed since install: {}",
+                    skill.name,
+                    skill.modified_files.join(", ")
+                ),
+                auto_fixable: false,
+                suggested_fix: Some(
+                    "catalyst update keeps these edits; run catalyst update --force to discard them and reinstall"
+                        .to_string(),
+                ),
+            });
+        }
+
+        // Checks the skill declares for itself
+        report.issues.extend(skill_health_issue(skill));
+    }
+
+    // Check permissions of managed files
+    for file in &report.unusual_permissions {
+        report.issues.push(Issue {
+            kind: IssueKind::UnusualPermissions,
+            severity: IssueSeverity::Warning,
+            component: file.path.display().to_string(),
+            description: format!(
+                "{} is {} (mode {:o})",
+                file.path.display(),
+                file.reason,
+                file.mode
+            ),
+            auto_fixable: false,
+            suggested_fix: Some(
+                "Fix the mode with chmod, or run: catalyst init --force --reset-permissions"
+                    .to_string(),
+            ),
+        });
+    }
+
+    // Check SELinux labels of hook wrappers; only enforcing mode blocks them
+    let label_severity = if selinux::is_enforcing() {
+        IssueSeverity::Error
+    } else {
+        IssueSeverity::Warning
+    };
+    for file in &report.mislabeled_hooks {
+        report.issues.push(Issue {
+            kind: IssueKind::HookMislabeled,
+            severity: label_severity,
+            component: file.path.display().to_string(),
+            description: format!(
+                "{} has SELinux type {} but .claude/hooks has {}",
+                file.path.display(),
+                selinux::context_type(&file.context).unwrap_or("?"),
+                selinux::context_type(&file.expected).unwrap_or("?")
+            ),
+            auto_fixable: true,
+            suggested_fix: Some("Run: catalyst status --fix".to_string()),
+        });
+    }
+
+    // Check that .ps1 wrappers have a PowerShell host that may run them
+    if let Some(status) = &report.powershell {
+        if status.installed.is_empty() {
+            report.issues.push(Issue {
+                kind: IssueKind::PowerShellInvocation,
+                severity: IssueSeverity::Error,
+                component: "PowerShell".to_string(),
+                description: "Neither pwsh nor powershell is on PATH, so .ps1 hook wrappers can't run"
+                    .to_string(),
+                auto_fixable: false,
+                suggested_fix: Some(
+                    "Install PowerShell 7 (winget install Microsoft.PowerShell), or switch to sh wrappers: catalyst config set wrappers.shell sh"
+                        .to_string(),
+                ),
+            });
+        } else if let Some(host) = status.host.filter(|host| !status.installed.contains(host)) {
+            report.issues.push(Issue {
+                kind: IssueKind::PowerShellInvocation,
+                severity: IssueSeverity::Error,
+                component: "PowerShell".to_string(),
+                description: format!(
+                    "catalyst.toml sets wrappers.powershell = \"{}\", but {} isn't on PATH",
+                    host, host
+                ),
+                auto_fixable: false,
+                suggested_fix: Some(format!(
+                    "Run: catalyst config set wrappers.powershell {}",
+                    status.installed[0]
+                )),
+            });
+        }
+
+        if let Some(policy) = &status.enforced_policy {
+            report.issues.push(Issue {
+                kind: IssueKind::PowerShellInvocation,
+                severity: IssueSeverity::Error,
+                component: "PowerShell execution policy".to_string(),
+                description: format!(
+                    "Group Policy sets the execution policy to {}, which -ExecutionPolicy Bypass can't override",
+                    policy
+                ),
+                auto_fixable: false,
+                suggested_fix: Some(
+                    "Ask an administrator to allow RemoteSigned, or sign the wrappers in .claude/hooks"
+                        .to_string(),
+                ),
+            });
+        }
+
+        for command in &status.commands {
+            report.issues.push(Issue {
+                kind: IssueKind::PowerShellInvocation,
+                severity: if command.problem.is_blocking() {
+                    IssueSeverity::Error
+                } else {
+                    IssueSeverity::Warning
+                },
+                component: format!("{} hook command", command.wrapper),
+                description: format!("{} hook command {}", command.event, command.problem),
+                auto_fixable: true,
+                suggested_fix: Some(format!(
+                    "Run: catalyst status --fix (sets the command to: {})",
+                    command.expected
+                )),
+            });
+        }
     }
 
     // Check version status
     match &report.version_status {
         VersionStatus::Missing => {
             report.issues.push(Issue {
+                kind: IssueKind::VersionFileMissing,
                 severity: IssueSeverity::Info,
                 component: "version tracking".to_string(),
                 description: ".catalyst-version file not found".to_string(),
@@ -567,6 +1460,7 @@ fn collect_issues(report: &mut StatusReport, settings_parse_error: Option<String
         }
         VersionStatus::Mismatch { expected, found } => {
             report.issues.push(Issue {
+                kind: IssueKind::VersionMismatch,
                 severity: IssueSeverity::Info,
                 component: "version tracking".to_string(),
                 description: format!(
@@ -574,23 +1468,77 @@ fn collect_issues(report: &mut StatusReport, settings_parse_error: Option<String
                     found, expected
                 ),
                 auto_fixable: false,
-                suggested_fix: Some("Run: catalyst update".to_string()),
+                suggested_fix: Some(format!(
+                    "Run: catalyst update (if the project is newer than this CLI, upgrade first: {})",
+                    upgrade_command(report.install_method, platform)
+                )),
             });
         }
         VersionStatus::Ok { .. } => {}
     }
 }
 
-/// Determine overall status level from issues
-fn determine_status_level(report: &StatusReport) -> StatusLevel {
-    let has_errors = report
-        .issues
-        .iter()
-        .any(|i| i.severity == IssueSeverity::Error);
-    let has_warnings = report
-        .issues
-        .iter()
-        .any(|i| i.severity == IssueSeverity::Warning);
+/// CAT014 issue for a skill whose own health check failed
+fn skill_health_issue(skill: &SkillStatus) -> Option<Issue> {
+    let health = skill.health.as_ref().filter(|h| !h.healthy)?;
+    Some(Issue {
+        kind: IssueKind::SkillUnhealthy,
+        severity: IssueSeverity::Warning,
+        component: format!("{} skill", skill.name),
+        description: format!(
+            "Skill '{}' health check failed: {}",
+            skill.name,
+            health.failures.join("; ")
+        ),
+        auto_fixable: false,
+        suggested_fix: Some(format!(
+            "See the [healthcheck] in .claude/skills/{}/skill.toml",
+            skill.name
+        )),
+    })
+}
+
+/// Tighten a report for `catalyst status --strict`
+///
+/// Warnings, a `.catalyst-version` that doesn't match this CLI, and skills
+/// changed since install all become errors, and issues are sorted by ID so
+/// two machines in the same state print the same report.
+///
+/// # Arguments
+///
+/// * `report` - Report from [`validate_installation`]; its level is
+///   recomputed
+pub fn apply_strict(report: &mut StatusReport) {
+    for issue in &mut report.issues {
+        if issue.severity == IssueSeverity::Warning
+            || matches!(
+                issue.kind,
+                IssueKind::VersionMismatch | IssueKind::SkillModified
+            )
+        {
+            issue.severity = IssueSeverity::Error;
+        }
+    }
+    report.issues.sort_by(|a, b| {
+        a.kind
+            .id()
+            .cmp(b.kind.id())
+            .then_with(|| a.component.cmp(&b.component))
+            .then_with(|| a.description.cmp(&b.description))
+    });
+    report.level = determine_status_level(report);
+}
+
+/// Determine overall status level from issues
+fn determine_status_level(report: &StatusReport) -> StatusLevel {
+    let has_errors = report
+        .issues
+        .iter()
+        .any(|i| i.severity == IssueSeverity::Error);
+    let has_warnings = report
+        .issues
+        .iter()
+        .any(|i| i.severity == IssueSeverity::Warning);
 
     if has_errors {
         StatusLevel::Error
@@ -606,6 +1554,11 @@ fn determine_status_level(report: &StatusReport) -> StatusLevel {
 /// Attempts to automatically repair:
 /// - Missing wrapper scripts (recreates from templates)
 /// - Non-executable wrapper scripts (sets permissions)
+/// - Catalyst hooks missing from settings.json (if `repair_settings`)
+/// - .ps1 hook commands that don't run through PowerShell with the
+///   execution policy bypassed (if `repair_settings`)
+/// - Hook commands naming a missing file that has a close match (if
+///   `repair_settings`)
 /// - Missing .catalyst-version file
 ///
 /// # Arguments
@@ -613,27 +1566,88 @@ fn determine_status_level(report: &StatusReport) -> StatusLevel {
 /// * `target_dir` - Base directory containing .claude/
 /// * `platform` - Current platform
 /// * `report` - Status report with identified issues
+/// * `repair_settings` - Whether settings.json may be modified (callers
+///   should confirm with the user first, since it is often hand-edited)
+///
+/// # Errors
+///
+/// Returns `InitInProgress` if another catalyst process is changing the
+/// project
 pub fn auto_fix(
     target_dir: &Path,
     platform: Platform,
     report: &StatusReport,
+    repair_settings: bool,
 ) -> Result<Vec<String>> {
+    let _lock = OperationLock::acquire(target_dir)?;
     let mut fixed = Vec::new();
+    let wrapper_platform = CatalystConfig::load(target_dir)?.wrapper_platform(platform);
+
+    // Re-register hooks missing from settings.json
+    let unregistered: Vec<&HookStatus> = report.hooks.iter().filter(|h| !h.configured).collect();
+    if repair_settings && !unregistered.is_empty() {
+        match fix_settings_hooks(target_dir, &unregistered) {
+            Ok(registered) => fixed.extend(registered),
+            Err(e) => {
+                progress::warn(format!("Failed to repair settings.json: {}", e));
+            }
+        }
+    }
+
+    // Run .ps1 wrappers through an installed PowerShell host
+    let commands: Vec<_> = report
+        .powershell
+        .iter()
+        .flat_map(|status| &status.commands)
+        .collect();
+    if repair_settings && !commands.is_empty() {
+        match powershell::rewrite_commands(target_dir, &commands) {
+            Ok(rewritten) => fixed.extend(rewritten),
+            Err(e) => {
+                progress::warn(format!("Failed to rewrite PowerShell hook commands: {}", e));
+            }
+        }
+    }
+
+    // Point dangling hook commands at the file they most likely meant
+    let fixable: Vec<&DanglingHook> = report
+        .dangling_hooks
+        .iter()
+        .filter(|dangling| dangling.fixed_command.is_some())
+        .collect();
+    if repair_settings && !fixable.is_empty() {
+        match rewrite_dangling_hooks(target_dir, &fixable) {
+            Ok(rewritten) => fixed.extend(rewritten),
+            Err(e) => {
+                progress::warn(format!("Failed to rewrite dangling hook commands: {}", e));
+            }
+        }
+    }
 
     // Fix missing or non-executable wrapper scripts
-    for hook in &report.hooks {
+    for hook in report.hooks.iter().filter(|h| h.configured) {
         if !hook.exists || !hook.executable {
-            match fix_hook_wrapper(target_dir, &hook.name, platform) {
+            match fix_hook_wrapper(target_dir, &hook.name, wrapper_platform) {
                 Ok(()) => {
                     fixed.push(format!("Fixed hook wrapper: {}", hook.name));
                 }
                 Err(e) => {
-                    eprintln!("⚠️  Failed to fix {}: {}", hook.name, e);
+                    progress::warn(format!("Failed to fix {}: {}", hook.name, e));
                 }
             }
         }
     }
 
+    // Relabel hook wrappers with the hooks directory's SELinux context
+    for file in &report.mislabeled_hooks {
+        match relabel_hook(target_dir, &file.path) {
+            Ok(message) => fixed.push(message),
+            Err(e) => {
+                progress::warn(e.to_string());
+            }
+        }
+    }
+
     // Fix missing version file
     if matches!(report.version_status, VersionStatus::Missing) {
         match fix_version_file(target_dir) {
@@ -641,7 +1655,7 @@ pub fn auto_fix(
                 fixed.push("Created .catalyst-version file".to_string());
             }
             Err(e) => {
-                eprintln!("⚠️  Failed to create version file: {}", e);
+                progress::warn(format!("Failed to create version file: {}", e));
             }
         }
     }
@@ -649,12 +1663,265 @@ pub fn auto_fix(
     Ok(fixed)
 }
 
+/// Fix a single auto-fixable issue
+///
+/// Used by `catalyst doctor` to apply fixes one at a time. Unlike
+/// [`auto_fix`], settings.json is always repaired (missing hooks registered,
+/// PowerShell hook commands rewritten), so callers must confirm with the
+/// user first.
+///
+/// # Arguments
+///
+/// * `target_dir` - Base directory containing .claude/
+/// * `platform` - Current platform
+/// * `report` - Status report the issue came from
+/// * `issue` - Issue to fix
+///
+/// # Returns
+///
+/// Returns a description of what was fixed
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the issue is not auto-fixable or its component
+/// is no longer in the report, or the error from the fix itself.
+pub fn fix_issue(
+    target_dir: &Path,
+    platform: Platform,
+    report: &StatusReport,
+    issue: &Issue,
+) -> Result<String> {
+    // Hook issues name their hook as "<wrapper> hook" or "<wrapper> hook wrapper"
+    let hook = || {
+        report
+            .hooks
+            .iter()
+            .find(|h| issue.component.starts_with(&format!("{} hook", h.name)))
+            .ok_or_else(|| {
+                CatalystError::InvalidConfig(format!("No hook found for '{}'", issue.component))
+            })
+    };
+
+    match issue.kind {
+        IssueKind::HookEntryMissing => {
+            let registered = fix_settings_hooks(target_dir, &[hook()?])?;
+            Ok(registered.into_iter().next().unwrap_or_default())
+        }
+        IssueKind::HookWrapperMissing | IssueKind::HookWrapperNotExecutable => {
+            let hook = hook()?;
+            let wrapper_platform = CatalystConfig::load(target_dir)?.wrapper_platform(platform);
+            fix_hook_wrapper(target_dir, &hook.name, wrapper_platform)?;
+            Ok(format!("Fixed hook wrapper: {}", hook.name))
+        }
+        IssueKind::HookMislabeled => {
+            let file = report
+                .mislabeled_hooks
+                .iter()
+                .find(|f| f.path.display().to_string() == issue.component)
+                .ok_or_else(|| {
+                    CatalystError::InvalidConfig(format!(
+                        "No mislabeled hook found for '{}'",
+                        issue.component
+                    ))
+                })?;
+            relabel_hook(target_dir, &file.path)
+        }
+        IssueKind::PowerShellInvocation => {
+            let command = report
+                .powershell
+                .iter()
+                .flat_map(|status| &status.commands)
+                .find(|command| issue.component == format!("{} hook command", command.wrapper))
+                .ok_or_else(|| {
+                    CatalystError::InvalidConfig(format!(
+                        "No PowerShell hook command found for '{}'",
+                        issue.component
+                    ))
+                })?;
+            let rewritten = powershell::rewrite_commands(target_dir, &[command])?;
+            Ok(rewritten.into_iter().next().unwrap_or_default())
+        }
+        IssueKind::HookCommandDangling => {
+            let dangling = report
+                .dangling_hooks
+                .iter()
+                .find(|d| issue.component == format!("{} hook command", d.reference))
+                .ok_or_else(|| {
+                    CatalystError::InvalidConfig(format!(
+                        "No dangling hook command found for '{}'",
+                        issue.component
+                    ))
+                })?;
+            let rewritten = rewrite_dangling_hooks(target_dir, &[dangling])?;
+            Ok(rewritten.into_iter().next().unwrap_or_default())
+        }
+        IssueKind::VersionFileMissing => {
+            fix_version_file(target_dir)?;
+            Ok("Created .catalyst-version file".to_string())
+        }
+        IssueKind::DeprecatedSkill => {
+            let deprecated = issue
+                .component
+                .strip_suffix(" skill")
+                .and_then(deprecation::lookup)
+                .ok_or_else(|| {
+                    CatalystError::InvalidConfig(format!(
+                        "No deprecated skill found for '{}'",
+                        issue.component
+                    ))
+                })?;
+            let migration = deprecation::migrate_skill(target_dir, deprecated)?;
+            Ok(format!(
+                "Renamed skill: {} -> {}",
+                migration.from, migration.to
+            ))
+        }
+        kind => Err(CatalystError::InvalidConfig(format!(
+            "Issue {} can't be fixed automatically",
+            kind.id()
+        ))),
+    }
+}
+
+/// Fix every issue of one kind, as `catalyst fix <issue-id>` does
+///
+/// Reaching for a specific ID counts as the user's consent, so hooks
+/// missing from settings.json are registered without asking.
+///
+/// # Arguments
+///
+/// * `target_dir` - Base directory containing .claude/
+/// * `platform` - Current platform
+/// * `report` - Status report to take the issues from
+/// * `kind` - Kind of issue to fix
+/// * `component` - Only fix the issue for this component (as shown by status)
+///
+/// # Returns
+///
+/// Returns what was fixed and what failed; `matched` is 0 when there is
+/// nothing to do, which callers should treat as success
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if the matching issues can't be fixed
+/// automatically, or `InitInProgress` if another catalyst process is changing
+/// the project
+pub fn fix_issues_by_kind(
+    target_dir: &Path,
+    platform: Platform,
+    report: &StatusReport,
+    kind: IssueKind,
+    component: Option<&str>,
+) -> Result<TargetedFixReport> {
+    let matching: Vec<&Issue> = report
+        .issues
+        .iter()
+        .filter(|issue| issue.kind == kind)
+        .filter(|issue| component.is_none_or(|c| issue.component == c))
+        .collect();
+
+    if matching.iter().any(|issue| !issue.auto_fixable) {
+        return Err(CatalystError::InvalidConfig(format!(
+            "Issue {} can't be fixed automatically; run 'catalyst explain-issue {}' for manual steps",
+            kind.id(),
+            kind.id()
+        )));
+    }
+
+    let _lock = if matching.is_empty() {
+        None
+    } else {
+        Some(OperationLock::acquire(target_dir)?)
+    };
+    let mut fix_report = TargetedFixReport {
+        issue_id: kind.id().to_string(),
+        matched: matching.len(),
+        fixed: Vec::new(),
+        failed: Vec::new(),
+    };
+    for issue in matching {
+        match fix_issue(target_dir, platform, report, issue) {
+            Ok(fixed) => fix_report.fixed.push(fixed),
+            Err(e) => fix_report
+                .failed
+                .push(format!("{}: {}", issue.component, e)),
+        }
+    }
+    Ok(fix_report)
+}
+
+/// Rewrite dangling hook commands in settings.json to their suggested fix
+///
+/// # Returns
+///
+/// Returns a description of each command that was rewritten
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if settings.json can't be read or written, or a
+/// command has no suggested fix or is no longer in settings.json
+fn rewrite_dangling_hooks(target_dir: &Path, dangling: &[&DanglingHook]) -> Result<Vec<String>> {
+    let settings_path = target_dir.join(SETTINGS_FILE);
+    let mut settings = ClaudeSettings::read(&settings_path)
+        .map_err(|e| CatalystError::InvalidConfig(format!("{:#}", e)))?;
+
+    let mut rewritten = Vec::new();
+    for hook_command in dangling {
+        let Some(ref fixed_command) = hook_command.fixed_command else {
+            return Err(CatalystError::InvalidConfig(format!(
+                "No existing file resembles {}",
+                hook_command.reference
+            )));
+        };
+        let event = HookEvent::from_str(&hook_command.event)
+            .map_err(|e| CatalystError::InvalidConfig(e.to_string()))?;
+        let mut found = false;
+        for hook in settings
+            .hooks
+            .get_mut(&event)
+            .into_iter()
+            .flatten()
+            .flat_map(|config| &mut config.hooks)
+            .filter(|hook| hook.command == hook_command.command)
+        {
+            hook.command = fixed_command.clone();
+            found = true;
+        }
+        if !found {
+            return Err(CatalystError::InvalidConfig(format!(
+                "{} hook command '{}' not found in settings.json",
+                hook_command.event, hook_command.command
+            )));
+        }
+        rewritten.push(format!(
+            "Rewrote {} hook command: {}",
+            hook_command.event, fixed_command
+        ));
+    }
+
+    settings
+        .write(&settings_path)
+        .map_err(|e| CatalystError::InvalidConfig(format!("{:#}", e)))?;
+    Ok(rewritten)
+}
+
 /// Fix a hook wrapper by recreating it
-fn fix_hook_wrapper(target_dir: &Path, wrapper_name: &str, platform: Platform) -> Result<()> {
-    // Extract binary name from wrapper name
-    let binary_name = wrapper_name
+pub(crate) fn fix_hook_wrapper(
+    target_dir: &Path,
+    wrapper_name: &str,
+    platform: Platform,
+) -> Result<()> {
+    let project_config = CatalystConfig::load(target_dir)?;
+
+    // Extract binary name from wrapper name; the tracker wrapper calls the
+    // configured backend's binary
+    let binary_name = match wrapper_name
         .trim_end_matches(".sh")
-        .trim_end_matches(".ps1");
+        .trim_end_matches(".ps1")
+    {
+        "file-change-tracker" => project_config.tracker.backend.hook_binary(),
+        stem => stem,
+    };
 
     // Validate binary name to prevent potential injection
     // Only allow alphanumeric characters, hyphens, and underscores
@@ -668,46 +1935,93 @@ fn fix_hook_wrapper(target_dir: &Path, wrapper_name: &str, platform: Platform) -
         )));
     }
 
-    // Use the init module's wrapper generation
-    // For now, we'll just recreate the wrapper using the same logic
     let hooks_dir = target_dir.join(HOOKS_DIR);
     let wrapper_path = hooks_dir.join(wrapper_name);
 
-    // Load template based on platform
-    let template = match platform {
-        Platform::Linux | Platform::MacOS | Platform::WSL => {
-            include_str!("../resources/wrapper-template.sh")
-        }
-        Platform::Windows => include_str!("../resources/wrapper-template.ps1"),
-    };
+    // Safe after the binary name validation above
+    let content = init::render_wrapper(binary_name, platform, &project_config, target_dir)?;
+
+    // Write wrapper file, keeping its mode and ownership but making sure
+    // it is executable
+    Fs::read_write()
+        .write_file(
+            &wrapper_path,
+            content.as_bytes(),
+            fs_ops::DEFAULT_EXECUTABLE_MODE,
+            PermissionPolicy::Preserve,
+        )
+        .map_err(CatalystError::Io)?;
+
+    selinux::copy_context(&hooks_dir, &wrapper_path)
+}
 
-    // Replace template variable (safe after validation above)
-    let content = template.replace("{{BINARY_NAME}}", binary_name);
+/// Copy the hooks directory's SELinux context onto a wrapper
+fn relabel_hook(target_dir: &Path, path: &Path) -> Result<String> {
+    selinux::copy_context(&target_dir.join(HOOKS_DIR), path)?;
+    Ok(format!("Relabeled hook wrapper: {}", path.display()))
+}
 
-    // Write wrapper file
-    fs::write(&wrapper_path, content).map_err(CatalystError::Io)?;
+/// Register hooks in settings.json, preserving everything else in the file
+///
+/// # Returns
+///
+/// Returns a description of each hook that was registered
+fn fix_settings_hooks(target_dir: &Path, hooks: &[&HookStatus]) -> Result<Vec<String>> {
+    let settings_path = target_dir.join(SETTINGS_FILE);
+    let mut settings = if settings_path.exists() {
+        ClaudeSettings::read(&settings_path)
+            .map_err(|e| CatalystError::InvalidConfig(format!("{:#}", e)))?
+    } else {
+        ClaudeSettings::default()
+    };
 
-    // Set executable permission on Unix
-    #[cfg(unix)]
-    {
-        let permissions = fs::Permissions::from_mode(0o755);
-        fs::set_permissions(&wrapper_path, permissions).map_err(CatalystError::Io)?;
+    let config = CatalystConfig::load(target_dir)?;
+    let mut registered = Vec::new();
+    for hook in hooks {
+        let event_name = hook.event.as_deref().unwrap_or_default();
+        let event = HookEvent::from_str(event_name)
+            .map_err(|e| CatalystError::InvalidConfig(e.to_string()))?;
+
+        settings
+            .add_hook(
+                event,
+                HookConfig {
+                    matcher: init::hook_matcher(event),
+                    hooks: vec![Hook {
+                        r#type: "command".to_string(),
+                        command: powershell::settings_command(&hook.name, &config),
+                    }],
+                },
+            )
+            .map_err(|e| CatalystError::InvalidConfig(e.to_string()))?;
+        registered.push(format!(
+            "Registered {} hook in settings.json: {}",
+            event, hook.name
+        ));
     }
 
-    Ok(())
+    settings
+        .write(&settings_path)
+        .map_err(|e| CatalystError::InvalidConfig(format!("{:#}", e)))?;
+
+    Ok(registered)
 }
 
 /// Fix missing version file
 fn fix_version_file(target_dir: &Path) -> Result<()> {
     let version_path = target_dir.join(".catalyst-version");
     let version = env!("CARGO_PKG_VERSION");
-    fs::write(version_path, version).map_err(CatalystError::Io)?;
+    Fs::read_write()
+        .write_atomic(&version_path, version.as_bytes())
+        .map_err(CatalystError::Io)?;
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::PowerShell;
+    use std::fs;
     use tempfile::TempDir;
 
     #[test]
@@ -719,6 +2033,7 @@ mod tests {
 
         // Warning issue = Warning
         report.issues.push(Issue {
+            kind: IssueKind::BinaryMissing,
             severity: IssueSeverity::Warning,
             component: "test".to_string(),
             description: "test warning".to_string(),
@@ -729,6 +2044,7 @@ mod tests {
 
         // Error issue = Error
         report.issues.push(Issue {
+            kind: IssueKind::SettingsParseError,
             severity: IssueSeverity::Error,
             component: "test".to_string(),
             description: "test error".to_string(),
@@ -741,7 +2057,7 @@ mod tests {
     #[test]
     fn test_check_version_missing() {
         let temp_dir = TempDir::new().unwrap();
-        let result = check_version(temp_dir.path()).unwrap();
+        let result = check_version(&Fs::read_only(), temp_dir.path()).unwrap();
         assert!(matches!(result, VersionStatus::Missing));
     }
 
@@ -752,7 +2068,7 @@ mod tests {
         let current_version = env!("CARGO_PKG_VERSION");
         fs::write(&version_path, current_version).unwrap();
 
-        let result = check_version(temp_dir.path()).unwrap();
+        let result = check_version(&Fs::read_only(), temp_dir.path()).unwrap();
         assert!(matches!(result, VersionStatus::Ok { .. }));
     }
 
@@ -762,7 +2078,7 @@ mod tests {
         let version_path = temp_dir.path().join(".catalyst-version");
         fs::write(&version_path, "0.0.1").unwrap();
 
-        let result = check_version(temp_dir.path()).unwrap();
+        let result = check_version(&Fs::read_only(), temp_dir.path()).unwrap();
         assert!(matches!(result, VersionStatus::Mismatch { .. }));
     }
 
@@ -841,6 +2157,257 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_status_reports_modified_skill_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let skill_dir = target.join(".claude/skills/demo");
+        fs::create_dir_all(skill_dir.join("resources")).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Demo\n").unwrap();
+        fs::write(skill_dir.join("resources/notes.md"), "notes\n").unwrap();
+        init::generate_skill_hashes(target, &["demo".to_string()]).unwrap();
+
+        let clean = validate_installation(target, Platform::Linux).unwrap();
+        assert!(!clean.skills[0].modified);
+        assert_eq!(clean.skills[0].current_hash, clean.skills[0].expected_hash);
+
+        fs::write(skill_dir.join("SKILL.md"), "# Demo, edited\n").unwrap();
+        fs::remove_file(skill_dir.join("resources/notes.md")).unwrap();
+        fs::write(skill_dir.join("extra.md"), "added\n").unwrap();
+
+        let report = validate_installation(target, Platform::Linux).unwrap();
+        let skill = &report.skills[0];
+        assert!(skill.modified);
+        assert_ne!(skill.current_hash, skill.expected_hash);
+        assert_eq!(
+            skill.modified_files,
+            vec!["demo/SKILL.md", "demo/resources/notes.md"]
+        );
+        let issue = report
+            .issues
+            .iter()
+            .find(|issue| issue.kind == IssueKind::SkillModified)
+            .unwrap();
+        assert_eq!(issue.severity, IssueSeverity::Info);
+        assert!(issue.description.contains("demo/resources/notes.md"));
+
+        // Strict mode fails on the edit
+        let mut strict = report.clone();
+        apply_strict(&mut strict);
+        assert_eq!(strict.level, StatusLevel::Error);
+        assert!(strict
+            .issues
+            .windows(2)
+            .all(|pair| pair[0].kind.id() <= pair[1].kind.id()));
+        assert!(strict
+            .issues
+            .iter()
+            .filter(|issue| issue.kind == IssueKind::SkillModified)
+            .all(|issue| issue.severity == IssueSeverity::Error));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_healthcheck_commands_only_run_when_asked() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let skill_dir = target.join(".claude/skills/demo");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Demo\n").unwrap();
+        fs::write(
+            skill_dir.join("skill.toml"),
+            "[healthcheck]\ncommand = [\"sh\", \"-c\", \"touch ran; exit 1\"]\n",
+        )
+        .unwrap();
+        let unhealthy = |report: &StatusReport| {
+            report
+                .issues
+                .iter()
+                .filter(|issue| issue.kind == IssueKind::SkillUnhealthy)
+                .count()
+        };
+
+        let report = check_installation(target, Platform::Linux, Probes::STANDARD).unwrap();
+        assert!(!target.join("ran").exists());
+        assert_eq!(unhealthy(&report), 0);
+
+        let probes = Probes {
+            healthchecks: true,
+            ..Probes::STANDARD
+        };
+        let report = check_installation(target, Platform::Linux, probes).unwrap();
+        assert!(target.join("ran").exists());
+        assert_eq!(unhealthy(&report), 1);
+        assert!(!report.skills[0].health.as_ref().unwrap().healthy);
+    }
+
+    /// Path, contents and mtime of every entry under `dir`
+    fn snapshot(dir: &Path) -> Vec<(PathBuf, Vec<u8>, std::time::SystemTime)> {
+        let mut entries: Vec<_> = walkdir::WalkDir::new(dir)
+            .into_iter()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                let metadata = entry.metadata().unwrap();
+                let contents = if metadata.is_file() {
+                    fs::read(entry.path()).unwrap()
+                } else {
+                    Vec::new()
+                };
+                (
+                    entry.path().to_path_buf(),
+                    contents,
+                    metadata.modified().unwrap(),
+                )
+            })
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    #[test]
+    fn test_validate_installation_performs_no_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let skill_dir = target.join(".claude/skills/demo");
+        fs::create_dir_all(target.join(".claude/hooks")).unwrap();
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Demo\n").unwrap();
+        init::generate_skill_hashes(target, &["demo".to_string()]).unwrap();
+
+        // Drift that `status --fix` would repair: edited skill, missing
+        // version file, hook wrapper without a settings.json entry
+        fs::write(skill_dir.join("SKILL.md"), "# Demo, edited\n").unwrap();
+        fs::write(
+            target.join(".claude/hooks/skill-activation-prompt.sh"),
+            "#!/bin/bash\n",
+        )
+        .unwrap();
+        fs::write(target.join(SETTINGS_FILE), r#"{"hooks": {}}"#).unwrap();
+
+        let before = snapshot(target);
+        let report = validate_installation(target, Platform::Linux).unwrap();
+        assert!(report.issues.iter().any(|issue| issue.auto_fixable));
+        assert_eq!(snapshot(target), before);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_installation_spawns_no_processes() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let marker = |name: &str| target.join(name);
+        let touch =
+            |name: &str| format!("[\"sh\", \"-c\", \"touch '{}'\"]", marker(name).display());
+
+        let skill_dir = target.join(".claude/skills/demo");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Demo\n").unwrap();
+        fs::write(
+            skill_dir.join("skill.toml"),
+            format!("[healthcheck]\ncommand = {}\n", touch("healthcheck-ran")),
+        )
+        .unwrap();
+        fs::write(
+            target.join(crate::types::CONFIG_FILE),
+            format!(
+                "[[validators]]\nname = \"spy\"\ncommand = {}\n",
+                touch("validator-ran")
+            ),
+        )
+        .unwrap();
+
+        validate_installation(target, Platform::Linux).unwrap();
+        assert!(!marker("healthcheck-ran").exists());
+        assert!(!marker("validator-ran").exists());
+
+        // Binaries are found and checked for permissions, never executed
+        let bin_dir = temp_dir.path().join("bin");
+        fs::create_dir_all(&bin_dir).unwrap();
+        let binary = bin_dir.join("file-analyzer");
+        fs::write(
+            &binary,
+            format!(
+                "#!/bin/sh\ntouch '{}'\necho 'file-analyzer {}'\n",
+                marker("binary-ran").display(),
+                CATALYST_VERSION
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+        let status = validate_binary(
+            &Fs::read_only(),
+            "file-analyzer",
+            &bin_dir,
+            Platform::Linux,
+            None,
+            false,
+        );
+        assert!(status.exists && status.executable);
+        assert!(status.version.is_none());
+        assert!(!marker("binary-ran").exists());
+
+        // The same checks with every probe enabled do run them
+        let probes = Probes {
+            healthchecks: true,
+            ..Probes::STANDARD
+        };
+        check_installation(target, Platform::Linux, probes).unwrap();
+        assert!(marker("healthcheck-ran").exists());
+        assert!(marker("validator-ran").exists());
+    }
+
+    #[test]
+    fn test_status_flags_and_fixes_dangling_hook_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir_all(target.join(".claude/hooks")).unwrap();
+        fs::write(target.join(".claude/hooks/formatter.sh"), "#!/bin/sh\n").unwrap();
+        fs::write(
+            target.join(SETTINGS_FILE),
+            r#"{"hooks": {"PostToolUse": [{"matcher": "Edit", "hooks": [
+                {"type": "command", "command": "$CLAUDE_PROJECT_DIR/.claude/hooks/formater.sh"},
+                {"type": "command", "command": "bash \"$CLAUDE_PROJECT_DIR/.claude/hooks/gone.sh\" --quiet"},
+                {"type": "command", "command": "$CLAUDE_PROJECT_DIR/.claude/hooks/formatter.sh"}
+            ]}]}}"#,
+        )
+        .unwrap();
+
+        let report = validate_installation(target, Platform::Linux).unwrap();
+        let references: Vec<&str> = report
+            .dangling_hooks
+            .iter()
+            .map(|d| d.reference.as_str())
+            .collect();
+        assert_eq!(
+            references,
+            vec![
+                "$CLAUDE_PROJECT_DIR/.claude/hooks/formater.sh",
+                "$CLAUDE_PROJECT_DIR/.claude/hooks/gone.sh"
+            ]
+        );
+        let issues: Vec<&Issue> = report
+            .issues
+            .iter()
+            .filter(|i| i.kind == IssueKind::HookCommandDangling)
+            .collect();
+        assert!(issues[0].auto_fixable);
+        assert!(!issues[1].auto_fixable);
+
+        fix_issue(target, Platform::Linux, &report, issues[0]).unwrap();
+        let fixed = validate_installation(target, Platform::Linux).unwrap();
+        assert_eq!(fixed.dangling_hooks.len(), 1);
+        let settings = ClaudeSettings::read(target.join(SETTINGS_FILE)).unwrap();
+        let commands: Vec<&str> = settings.hooks[&HookEvent::PostToolUse][0]
+            .hooks
+            .iter()
+            .map(|h| h.command.as_str())
+            .collect();
+        assert_eq!(
+            commands[0],
+            "$CLAUDE_PROJECT_DIR/.claude/hooks/formatter.sh"
+        );
+    }
+
     #[test]
     fn test_validate_installation_with_invalid_settings() {
         // PR feedback: Test settings.json parse error reporting
@@ -898,7 +2465,7 @@ mod tests {
         });
 
         // Run auto_fix
-        let result = auto_fix(target, Platform::Linux, &report);
+        let result = auto_fix(target, Platform::Linux, &report, false);
         assert!(result.is_ok());
 
         let fixed = result.unwrap();
@@ -940,7 +2507,7 @@ mod tests {
         report.version_status = VersionStatus::Missing;
 
         // Run auto_fix
-        let result = auto_fix(target, Platform::Linux, &report);
+        let result = auto_fix(target, Platform::Linux, &report, false);
         assert!(result.is_ok());
 
         let fixed = result.unwrap();
@@ -954,4 +2521,478 @@ mod tests {
         let content = fs::read_to_string(&version_path).unwrap();
         assert_eq!(content.trim(), env!("CARGO_PKG_VERSION"));
     }
+
+    #[test]
+    fn test_fix_issues_by_kind_only_touches_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let hooks_dir = target.join(".claude/hooks");
+        fs::create_dir_all(&hooks_dir).unwrap();
+
+        let mut report = StatusReport::new();
+        for name in ["skill-activation-prompt.sh", "file-analyzer.sh"] {
+            report.hooks.push(HookStatus {
+                name: name.to_string(),
+                exists: false,
+                executable: false,
+                configured: true,
+                event: Some("UserPromptSubmit".to_string()),
+                path: Some(hooks_dir.join(name)),
+                calls_correct_binary: false,
+            });
+            report.issues.push(Issue {
+                kind: IssueKind::HookWrapperMissing,
+                severity: IssueSeverity::Error,
+                component: format!("{} hook wrapper", name),
+                description: "missing".to_string(),
+                auto_fixable: true,
+                suggested_fix: None,
+            });
+        }
+        report.issues.push(Issue {
+            kind: IssueKind::VersionFileMissing,
+            severity: IssueSeverity::Warning,
+            component: ".catalyst-version".to_string(),
+            description: "missing".to_string(),
+            auto_fixable: true,
+            suggested_fix: None,
+        });
+
+        let fixed = fix_issues_by_kind(
+            target,
+            Platform::Linux,
+            &report,
+            IssueKind::HookWrapperMissing,
+            Some("file-analyzer.sh hook wrapper"),
+        )
+        .unwrap();
+        assert_eq!((fixed.matched, fixed.fixed.len()), (1, 1));
+        assert!(hooks_dir.join("file-analyzer.sh").exists());
+        assert!(!hooks_dir.join("skill-activation-prompt.sh").exists());
+        assert!(!target.join(VERSION_FILE).exists());
+
+        // Nothing of that kind left to fix is not an error
+        let none = fix_issues_by_kind(
+            target,
+            Platform::Linux,
+            &report,
+            IssueKind::HookMislabeled,
+            None,
+        )
+        .unwrap();
+        assert_eq!(none.matched, 0);
+
+        report.issues.push(Issue {
+            kind: IssueKind::VersionMismatch,
+            severity: IssueSeverity::Warning,
+            component: ".catalyst-version".to_string(),
+            description: "old".to_string(),
+            auto_fixable: false,
+            suggested_fix: None,
+        });
+        assert!(fix_issues_by_kind(
+            target,
+            Platform::Linux,
+            &report,
+            IssueKind::VersionMismatch,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_status_fix_restores_deleted_hook_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let hooks_dir = target.join(".claude/hooks");
+        fs::create_dir_all(&hooks_dir).unwrap();
+        fs::write(
+            hooks_dir.join("skill-activation-prompt.sh"),
+            "#!/bin/bash\n",
+        )
+        .unwrap();
+
+        // settings.json with unrelated content but no Catalyst hook entry
+        let settings_path = target.join(SETTINGS_FILE);
+        fs::write(&settings_path, r#"{"model": "opus", "hooks": {}}"#).unwrap();
+
+        let report = validate_installation(target, Platform::Linux).unwrap();
+        let missing: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|i| i.kind == IssueKind::HookEntryMissing)
+            .collect();
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].auto_fixable);
+
+        // Without permission to touch settings.json nothing is registered
+        let fixed = auto_fix(target, Platform::Linux, &report, false).unwrap();
+        assert!(!fixed.iter().any(|f| f.contains("settings.json")));
+
+        let fixed = auto_fix(target, Platform::Linux, &report, true).unwrap();
+        assert!(fixed
+            .iter()
+            .any(|f| f.contains("Registered UserPromptSubmit hook")));
+
+        let settings = ClaudeSettings::read(&settings_path).unwrap();
+        let configs = &settings.hooks[&HookEvent::UserPromptSubmit];
+        assert_eq!(
+            configs[0].hooks[0].command,
+            "$CLAUDE_PROJECT_DIR/.claude/hooks/skill-activation-prompt.sh"
+        );
+        assert_eq!(settings.extra["model"], "opus");
+
+        let report = validate_installation(target, Platform::Linux).unwrap();
+        assert!(!report
+            .issues
+            .iter()
+            .any(|i| i.kind == IssueKind::HookEntryMissing));
+    }
+
+    #[test]
+    fn test_status_fix_registers_cargo_check_hook() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir_all(target.join(HOOKS_DIR)).unwrap();
+        fs::write(
+            target.join(HOOKS_DIR).join("cargo-check.sh"),
+            "#!/bin/bash\n",
+        )
+        .unwrap();
+        fs::write(target.join(SETTINGS_FILE), r#"{"hooks": {}}"#).unwrap();
+
+        let report = validate_installation(target, Platform::Linux).unwrap();
+        assert!(report.issues.iter().any(|i| {
+            i.kind == IssueKind::HookEntryMissing && i.component.contains("cargo-check")
+        }));
+
+        auto_fix(target, Platform::Linux, &report, true).unwrap();
+        let settings = ClaudeSettings::read(target.join(SETTINGS_FILE)).unwrap();
+        let config = &settings.hooks[&HookEvent::PostToolUse][0];
+        assert_eq!(config.matcher.as_deref(), Some("Write|Edit|MultiEdit"));
+        assert_eq!(
+            config.hooks[0].command,
+            "$CLAUDE_PROJECT_DIR/.claude/hooks/cargo-check.sh"
+        );
+    }
+
+    #[test]
+    fn test_status_fixes_powershell_hook_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir_all(target.join(HOOKS_DIR)).unwrap();
+        fs::write(
+            target.join("catalyst.toml"),
+            "[wrappers]\npowershell = \"pwsh\"\n",
+        )
+        .unwrap();
+        let settings_path = target.join(SETTINGS_FILE);
+        fs::write(
+            &settings_path,
+            r#"{"hooks": {"UserPromptSubmit": [{"hooks": [{"type": "command",
+                "command": "$CLAUDE_PROJECT_DIR/.claude/hooks/skill-activation-prompt.ps1"}]}]}}"#,
+        )
+        .unwrap();
+
+        let report = check_installation(target, Platform::Windows, Probes::STANDARD).unwrap();
+        let status = report.powershell.as_ref().unwrap();
+        assert_eq!(status.host, Some(PowerShell::Pwsh));
+        let issue = report
+            .issues
+            .iter()
+            .find(|i| i.component == "skill-activation-prompt.ps1 hook command")
+            .unwrap();
+        assert_eq!(issue.kind, IssueKind::PowerShellInvocation);
+        assert_eq!(issue.severity, IssueSeverity::Error);
+        assert!(issue.auto_fixable);
+
+        fix_issue(target, Platform::Windows, &report, issue).unwrap();
+        let settings = ClaudeSettings::read(&settings_path).unwrap();
+        assert_eq!(
+            settings.hooks[&HookEvent::UserPromptSubmit][0].hooks[0].command,
+            "pwsh -NoProfile -ExecutionPolicy Bypass -File \
+             \"$CLAUDE_PROJECT_DIR/.claude/hooks/skill-activation-prompt.ps1\""
+        );
+
+        // sh projects skip the check entirely
+        let report = check_installation(target, Platform::Linux, Probes::STANDARD).unwrap();
+        assert!(report.powershell.is_none());
+    }
+
+    #[test]
+    fn test_fix_issue_one_at_a_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let hooks_dir = target.join(".claude/hooks");
+        fs::create_dir_all(&hooks_dir).unwrap();
+        fs::write(
+            hooks_dir.join("skill-activation-prompt.sh"),
+            "#!/bin/bash\n",
+        )
+        .unwrap();
+
+        let report = validate_installation(target, Platform::Linux).unwrap();
+        let issue = |kind| {
+            report
+                .issues
+                .iter()
+                .find(|i| i.kind == kind)
+                .unwrap()
+                .clone()
+        };
+
+        let fixed = fix_issue(
+            target,
+            Platform::Linux,
+            &report,
+            &issue(IssueKind::VersionFileMissing),
+        )
+        .unwrap();
+        assert!(fixed.contains(".catalyst-version"));
+        // The other issue is left alone
+        assert!(!target.join(SETTINGS_FILE).exists());
+
+        let fixed = fix_issue(
+            target,
+            Platform::Linux,
+            &report,
+            &issue(IssueKind::HookEntryMissing),
+        )
+        .unwrap();
+        assert!(fixed.contains("skill-activation-prompt.sh"));
+        assert!(target.join(SETTINGS_FILE).exists());
+    }
+
+    #[test]
+    fn test_fix_issue_rejects_manual_issues() {
+        let temp_dir = TempDir::new().unwrap();
+        let report = StatusReport::new();
+        let issue = Issue {
+            kind: IssueKind::BinaryMissing,
+            severity: IssueSeverity::Error,
+            component: "file-analyzer binary".to_string(),
+            description: "missing".to_string(),
+            auto_fixable: false,
+            suggested_fix: None,
+        };
+
+        assert!(fix_issue(temp_dir.path(), Platform::Linux, &report, &issue).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_status_reports_unusual_permissions() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir_all(target.join(".claude")).unwrap();
+        let settings_path = target.join(SETTINGS_FILE);
+        fs::write(&settings_path, "{}").unwrap();
+
+        // Group-writable is fine
+        fs::set_permissions(&settings_path, fs::Permissions::from_mode(0o664)).unwrap();
+        let report = validate_installation(target, Platform::Linux).unwrap();
+        assert!(report.unusual_permissions.is_empty());
+
+        fs::set_permissions(&settings_path, fs::Permissions::from_mode(0o666)).unwrap();
+        let report = validate_installation(target, Platform::Linux).unwrap();
+        let issue = report
+            .issues
+            .iter()
+            .find(|i| i.kind == IssueKind::UnusualPermissions)
+            .unwrap();
+        assert!(issue.description.contains("world-writable (mode 666)"));
+        assert!(!issue.auto_fixable);
+
+        // Reported, not changed
+        let _ = auto_fix(target, Platform::Linux, &report, false).unwrap();
+        assert_eq!(fs_ops::file_mode(&settings_path), Some(0o666));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_binary_reports_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let bin_dir = temp_dir.path();
+        let write_binary = |name: &str, version: &str| {
+            let path = bin_dir.join(name);
+            fs::write(&path, format!("#!/bin/sh\necho '{} {}'\n", name, version)).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        write_binary("file-analyzer", CATALYST_VERSION);
+        write_binary("skill-activation-prompt", "99.0.0");
+
+        let fs = Fs::read_only();
+        let current = validate_binary(&fs, "file-analyzer", bin_dir, Platform::Linux, None, true);
+        assert_eq!(current.version.as_deref(), Some(CATALYST_VERSION));
+        assert_eq!(current.expected_version.as_deref(), Some(CATALYST_VERSION));
+        assert!(current.version_matches);
+
+        let mut report = StatusReport::new();
+        report.binaries.push(validate_binary(
+            &fs,
+            "skill-activation-prompt",
+            bin_dir,
+            Platform::Linux,
+            None,
+            true,
+        ));
+        report.binaries.push(current);
+        assert!(!report.binaries[0].version_matches);
+
+        collect_issues(&mut report, Platform::Linux, None);
+        let mismatches: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.kind == IssueKind::BinaryVersionMismatch)
+            .collect();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].severity, IssueSeverity::Warning);
+        assert!(mismatches[0].description.contains("99.0.0"));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_validate_installation_async_honors_cancellation() {
+        use tokio_util::sync::CancellationToken;
+
+        let temp_dir = TempDir::new().unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let cancel = CancellationToken::new();
+        let target = temp_dir.path().to_path_buf();
+        let report = runtime
+            .block_on(validate_installation_async(
+                target,
+                Platform::Linux,
+                cancel.clone(),
+            ))
+            .unwrap();
+        assert!(!report.issues.is_empty());
+
+        cancel.cancel();
+        let target = temp_dir.path().to_path_buf();
+        let result = runtime.block_on(validate_installation_async(target, Platform::Linux, cancel));
+        assert!(matches!(result, Err(CatalystError::Cancelled)));
+    }
+
+    #[test]
+    fn test_apply_strict_promotes_and_sorts_issues() {
+        let issue = |kind, severity, component: &str| Issue {
+            kind,
+            severity,
+            component: component.to_string(),
+            description: "found".to_string(),
+            auto_fixable: false,
+            suggested_fix: None,
+        };
+
+        let mut report = StatusReport::new();
+        report.issues = vec![
+            issue(IssueKind::DeprecatedSkill, IssueSeverity::Info, "old-skill"),
+            issue(
+                IssueKind::SkillModified,
+                IssueSeverity::Info,
+                "rust-developer",
+            ),
+            issue(
+                IssueKind::VersionFileMissing,
+                IssueSeverity::Warning,
+                ".catalyst-version",
+            ),
+            issue(
+                IssueKind::VersionMismatch,
+                IssueSeverity::Info,
+                ".catalyst-version",
+            ),
+        ];
+        report.level = StatusLevel::Warning;
+
+        apply_strict(&mut report);
+
+        let ids: Vec<&str> = report.issues.iter().map(|i| i.kind.id()).collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+
+        for issue in &report.issues {
+            let expected = if issue.kind == IssueKind::DeprecatedSkill {
+                IssueSeverity::Info
+            } else {
+                IssueSeverity::Error
+            };
+            assert_eq!(issue.severity, expected, "{:?}", issue.kind);
+        }
+        assert_eq!(report.level, StatusLevel::Error);
+        assert_eq!(
+            crate::types::ExitCode::for_status(report.level),
+            crate::types::ExitCode::Errors
+        );
+    }
+
+    #[test]
+    fn test_status_json_kind_is_accepted_by_fix() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let hooks_dir = target.join(".claude/hooks");
+        fs::create_dir_all(&hooks_dir).unwrap();
+
+        let mut report = StatusReport::new();
+        report.hooks.push(HookStatus {
+            name: "file-analyzer.sh".to_string(),
+            exists: false,
+            executable: false,
+            configured: true,
+            event: Some("PostToolUse".to_string()),
+            path: Some(hooks_dir.join("file-analyzer.sh")),
+            calls_correct_binary: false,
+        });
+        report.issues.push(Issue {
+            kind: IssueKind::HookWrapperMissing,
+            severity: IssueSeverity::Error,
+            component: "file-analyzer.sh hook wrapper".to_string(),
+            description: "missing".to_string(),
+            auto_fixable: true,
+            suggested_fix: None,
+        });
+
+        // What a script reads from `catalyst status --display json`
+        let json = serde_json::to_value(&report).unwrap();
+        let id = json["issues"][0]["kind"].as_str().unwrap();
+        assert_eq!(id, "CAT004");
+
+        // ...and hands to `catalyst fix <id>`
+        let kind = IssueKind::from_id(id).unwrap();
+        let fixed = fix_issues_by_kind(target, Platform::Linux, &report, kind, None).unwrap();
+        assert_eq!((fixed.matched, fixed.fixed.len()), (1, 1));
+        assert!(hooks_dir.join("file-analyzer.sh").exists());
+
+        let round_trip: StatusReport = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip.issues[0].kind, IssueKind::HookWrapperMissing);
+    }
+
+    #[test]
+    fn test_verbose_status_reports_each_check() {
+        use crate::progress::{self, DetailRecorder, Verbosity};
+        use std::sync::Arc;
+
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        fs::create_dir_all(target.join(".claude/skills/rust-developer")).unwrap();
+
+        let recorder = Arc::new(DetailRecorder::default());
+        progress::with_test_reporter(recorder.clone(), Verbosity::Verbose, || {
+            validate_installation(target, Platform::Linux).unwrap();
+        });
+
+        let details = recorder.0.lock().unwrap();
+        // Whether the binary is installed depends on the machine
+        assert!(details
+            .iter()
+            .any(|d| d.starts_with("Binary file-analyzer: ")));
+        assert!(details
+            .iter()
+            .any(|d| d == "Skill rust-developer: not in skill-rules.json"));
+    }
 }
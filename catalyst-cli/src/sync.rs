@@ -0,0 +1,342 @@
+//! `catalyst sync`: bring a project to a healthy installation in one step
+//!
+//! Sync runs whichever of the other commands the project needs, in order:
+//!
+//! 1. **init** when there is no `.catalyst-version`, using catalyst.toml
+//!    (and the user config for skills) exactly as a non-interactive
+//!    `catalyst init` would (see [`InitConfig::detect`])
+//! 2. **update** when the installed version differs from this binary, or
+//!    resume when an earlier update was interrupted
+//! 3. **fix** for drift that `catalyst status --fix` can repair
+//! 4. **verify**, a final status check that decides the outcome
+//!
+//! Every step is idempotent, so running sync on an up-to-date project only
+//! verifies it. Nothing prompts: settings.json is only repaired when the
+//! caller allows it, which keeps sync safe to put in bootstrap scripts.
+
+use crate::init::{self, read_version_file};
+use crate::status::{auto_fix, check_installation, Probes};
+use crate::types::{
+    ExitCode, InitConfig, InitReport, Platform, Result, StatusLevel, StatusReport, UpdateReport,
+    CATALYST_VERSION,
+};
+use crate::update::{self, UpdateJournal};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Options for [`sync`]
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    pub directory: PathBuf,
+    pub platform: Platform,
+    /// Whether the fix step may add hook entries to settings.json
+    pub repair_settings: bool,
+}
+
+/// The phases of a sync, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStepKind {
+    Init,
+    Update,
+    Fix,
+    Verify,
+}
+
+impl std::fmt::Display for SyncStepKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SyncStepKind::Init => "init",
+            SyncStepKind::Update => "update",
+            SyncStepKind::Fix => "fix",
+            SyncStepKind::Verify => "verify",
+        })
+    }
+}
+
+/// What happened in one phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncOutcome {
+    Ran,
+    /// Nothing to do
+    Skipped,
+    Failed,
+}
+
+/// One phase of a sync
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncStep {
+    pub kind: SyncStepKind,
+    pub outcome: SyncOutcome,
+    pub detail: String,
+}
+
+/// Everything a sync did, and how the project looked at the end
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncReport {
+    pub steps: Vec<SyncStep>,
+
+    /// Report of the init step, if it ran
+    pub init: Option<InitReport>,
+
+    /// Report of the update step, if it ran
+    pub update: Option<UpdateReport>,
+
+    /// Fixes applied by the fix step
+    pub fixed_issues: Vec<String>,
+
+    /// The final status check
+    pub status: StatusReport,
+}
+
+impl SyncReport {
+    /// Whether a step failed outright
+    pub fn step_failed(&self) -> bool {
+        self.steps
+            .iter()
+            .any(|step| step.outcome == SyncOutcome::Failed && step.kind != SyncStepKind::Verify)
+    }
+
+    /// Whether every step succeeded and the project verified healthy
+    pub fn success(&self) -> bool {
+        !self.step_failed() && self.status.level == StatusLevel::Ok
+    }
+
+    /// Process exit code for the report
+    ///
+    /// # Returns
+    ///
+    /// Returns `Failure` when a step failed, and otherwise the code for the
+    /// level verification found (`Ok`, `Warnings`, or `Errors`)
+    pub fn exit_code(&self) -> ExitCode {
+        if self.step_failed() {
+            ExitCode::Failure
+        } else {
+            ExitCode::for_status(self.status.level)
+        }
+    }
+}
+
+fn step(kind: SyncStepKind, outcome: SyncOutcome, detail: impl Into<String>) -> SyncStep {
+    SyncStep {
+        kind,
+        outcome,
+        detail: detail.into(),
+    }
+}
+
+/// Initialize, update, repair, and verify a project as needed
+///
+/// A failed step is recorded in the report rather than returned. When init
+/// fails, update and fix are skipped (there is nothing to update, and
+/// repairs would half-initialize the project), but verification still runs
+/// so the report shows what is missing. Skill setup scripts aren't run; callers decide whether to run
+/// them for the skills in the init and update reports.
+///
+/// # Errors
+///
+/// Returns an error only if the project can't be inspected at all: the
+/// version file, update journal, or final status check can't be read
+pub fn sync(options: &SyncOptions) -> Result<SyncReport> {
+    let target_dir = &options.directory;
+    let mut steps = Vec::new();
+
+    // Init
+    let installed_version = read_version_file(target_dir)?;
+    let interrupted = UpdateJournal::load(target_dir)?.is_some();
+    let mut init_report = None;
+    let mut initialized = installed_version.is_some() || interrupted;
+    if initialized {
+        steps.push(step(
+            SyncStepKind::Init,
+            SyncOutcome::Skipped,
+            "Already initialized",
+        ));
+    } else {
+        match InitConfig::detect(target_dir).and_then(|config| init::initialize(&config)) {
+            Ok(init) => {
+                let outcome = if init.failed_skills.is_empty() {
+                    SyncOutcome::Ran
+                } else {
+                    SyncOutcome::Failed
+                };
+                let detail = format!(
+                    "Installed {} skill(s) and {} hook(s)",
+                    init.installed_skills.len(),
+                    init.installed_hooks.len()
+                );
+                steps.push(step(SyncStepKind::Init, outcome, detail));
+                init_report = Some(init);
+                initialized = true;
+            }
+            Err(e) => steps.push(step(SyncStepKind::Init, SyncOutcome::Failed, e.to_string())),
+        }
+    }
+
+    // Update
+    let update_result = match installed_version.as_deref() {
+        _ if interrupted => Some(update::resume_update(target_dir)),
+        Some(version) if version != CATALYST_VERSION => Some(update::update(target_dir, false)),
+        _ => None,
+    };
+    let mut update_report = None;
+    match update_result {
+        None => {
+            let detail = if installed_version.is_some() {
+                format!("Already at {}", CATALYST_VERSION)
+            } else if initialized {
+                "Nothing to update after init".to_string()
+            } else {
+                "Not initialized".to_string()
+            };
+            steps.push(step(SyncStepKind::Update, SyncOutcome::Skipped, detail));
+        }
+        Some(Ok(update)) => {
+            let (outcome, detail) = if update.success {
+                let from = installed_version.as_deref().unwrap_or("interrupted update");
+                (SyncOutcome::Ran, format!("{} → {}", from, CATALYST_VERSION))
+            } else {
+                (SyncOutcome::Failed, update.errors.join("; "))
+            };
+            steps.push(step(SyncStepKind::Update, outcome, detail));
+            update_report = Some(update);
+        }
+        Some(Err(e)) => steps.push(step(
+            SyncStepKind::Update,
+            SyncOutcome::Failed,
+            e.to_string(),
+        )),
+    }
+
+    // Fix
+    let mut status = check_installation(target_dir, options.platform, Probes::STANDARD)?;
+    let mut fixed_issues = Vec::new();
+    if !initialized {
+        steps.push(step(
+            SyncStepKind::Fix,
+            SyncOutcome::Skipped,
+            "Not initialized",
+        ));
+    } else if status.issues.iter().any(|issue| issue.auto_fixable) {
+        match auto_fix(
+            target_dir,
+            options.platform,
+            &status,
+            options.repair_settings,
+        ) {
+            Ok(fixed) => {
+                let detail = format!("Applied {} fix(es)", fixed.len());
+                steps.push(step(SyncStepKind::Fix, SyncOutcome::Ran, detail));
+                fixed_issues = fixed;
+            }
+            Err(e) => steps.push(step(SyncStepKind::Fix, SyncOutcome::Failed, e.to_string())),
+        }
+        status = check_installation(target_dir, options.platform, Probes::STANDARD)?;
+    } else {
+        steps.push(step(
+            SyncStepKind::Fix,
+            SyncOutcome::Skipped,
+            "No fixable drift",
+        ));
+    }
+
+    // Verify
+    let verified = if status.level == StatusLevel::Ok {
+        step(
+            SyncStepKind::Verify,
+            SyncOutcome::Ran,
+            "Installation is healthy",
+        )
+    } else {
+        step(
+            SyncStepKind::Verify,
+            SyncOutcome::Failed,
+            format!("{} issue(s) remain", status.issues.len()),
+        )
+    };
+    steps.push(verified);
+
+    Ok(SyncReport {
+        steps,
+        init: init_report,
+        update: update_report,
+        fixed_issues,
+        status,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn options(dir: &Path) -> SyncOptions {
+        SyncOptions {
+            directory: dir.to_path_buf(),
+            platform: Platform::detect(),
+            repair_settings: false,
+        }
+    }
+
+    fn outcomes(report: &SyncReport) -> Vec<SyncOutcome> {
+        report.steps.iter().map(|step| step.outcome).collect()
+    }
+
+    #[test]
+    fn test_sync_initializes_then_is_idempotent() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".claude")).unwrap();
+
+        let first = sync(&options(temp_dir.path())).unwrap();
+        assert_eq!(first.steps[0].kind, SyncStepKind::Init);
+        assert_eq!(first.steps[0].outcome, SyncOutcome::Ran);
+        assert_eq!(first.steps[1].outcome, SyncOutcome::Skipped);
+        assert!(first.init.is_some());
+        assert_eq!(
+            read_version_file(temp_dir.path()).unwrap().as_deref(),
+            Some(CATALYST_VERSION)
+        );
+
+        let second = sync(&options(temp_dir.path())).unwrap();
+        assert_eq!(
+            outcomes(&second)[..2],
+            [SyncOutcome::Skipped, SyncOutcome::Skipped]
+        );
+        assert!(second.init.is_none() && second.update.is_none());
+        assert_eq!(second.steps[3].kind, SyncStepKind::Verify);
+    }
+
+    #[test]
+    fn test_sync_updates_old_version_and_reports_failed_init() {
+        let temp_dir = TempDir::new().unwrap();
+        // No .claude directory: init fails, nothing else touches the
+        // project, but sync still verifies
+        let failed = sync(&options(temp_dir.path())).unwrap();
+        assert_eq!(
+            outcomes(&failed),
+            [
+                SyncOutcome::Failed,
+                SyncOutcome::Skipped,
+                SyncOutcome::Skipped,
+                SyncOutcome::Failed
+            ]
+        );
+        assert_eq!(failed.exit_code(), ExitCode::Failure);
+        assert!(read_version_file(temp_dir.path()).unwrap().is_none());
+
+        fs::create_dir_all(temp_dir.path().join(".claude")).unwrap();
+        sync(&options(temp_dir.path())).unwrap();
+        fs::write(temp_dir.path().join(".catalyst-version"), "0.0.1").unwrap();
+        let updated = sync(&options(temp_dir.path())).unwrap();
+        assert_eq!(updated.steps[1].kind, SyncStepKind::Update);
+        assert_eq!(updated.steps[1].outcome, SyncOutcome::Ran);
+        assert!(updated.steps[1].detail.starts_with("0.0.1"));
+        assert_eq!(
+            read_version_file(temp_dir.path()).unwrap().as_deref(),
+            Some(CATALYST_VERSION)
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! Target triples for release archives and installed binaries
+//!
+//! Releases publish one archive per target triple, named
+//! `catalyst-<triple>.tar.gz` (`.zip` for Windows), e.g.
+//! `catalyst-aarch64-unknown-linux-musl.tar.gz`. Older releases named
+//! archives `catalyst-<arch>-<os>.tar.gz`; those names are still accepted
+//! for every target except musl, which they never covered.
+//!
+//! Hook binaries have to match the machine as well: an x86_64 binary on an
+//! ARM machine won't start on Linux, and on macOS and Windows it only runs
+//! under emulation. `catalyst status` reads each binary's executable header
+//! to catch that (CAT019).
+
+use crate::types::{Arch, CatalystError, Result};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+
+/// Target triples release archives are published for
+pub const SUPPORTED_TARGETS: &[&str] = &[
+    "x86_64-unknown-linux-gnu",
+    "x86_64-unknown-linux-musl",
+    "aarch64-unknown-linux-gnu",
+    "aarch64-unknown-linux-musl",
+    "x86_64-apple-darwin",
+    "aarch64-apple-darwin",
+    "x86_64-pc-windows-msvc",
+    "aarch64-pc-windows-msvc",
+];
+
+/// Bytes of an executable read to find its architecture
+const HEADER_LEN: usize = 4096;
+
+// Mach-O CPU types
+const CPU_TYPE_X86_64: u32 = 0x0100_0007;
+const CPU_TYPE_ARM64: u32 = 0x0100_000c;
+
+// ELF machine types
+const EM_X86_64: u16 = 62;
+const EM_AARCH64: u16 = 183;
+
+// PE machine types
+const IMAGE_FILE_MACHINE_AMD64: u16 = 0x8664;
+const IMAGE_FILE_MACHINE_ARM64: u16 = 0xaa64;
+
+/// Operating system (and, for Linux, C library) part of a target triple
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetOs {
+    Linux { musl: bool },
+    MacOS,
+    Windows,
+}
+
+/// A supported target triple, e.g. `aarch64-apple-darwin`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TargetTriple {
+    pub arch: Arch,
+    pub os: TargetOs,
+}
+
+impl TargetTriple {
+    /// Target the running executable was built for
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsupportedPlatform` when built for a target no release
+    /// archives exist for
+    pub fn current() -> Result<Self> {
+        let unsupported = || {
+            CatalystError::UnsupportedPlatform(format!(
+                "{}-{}",
+                std::env::consts::ARCH,
+                std::env::consts::OS
+            ))
+        };
+        let arch = parse_arch(std::env::consts::ARCH).ok_or_else(unsupported)?;
+        let os = match std::env::consts::OS {
+            "linux" => TargetOs::Linux {
+                musl: cfg!(target_env = "musl"),
+            },
+            "macos" => TargetOs::MacOS,
+            "windows" => TargetOs::Windows,
+            _ => return Err(unsupported()),
+        };
+        Ok(Self { arch, os })
+    }
+
+    /// Target whose binaries suit this machine
+    ///
+    /// Same as [`current`](Self::current), except that an executable
+    /// running under emulation (x86_64 on Apple silicon or Windows on ARM)
+    /// gets the machine's own architecture.
+    pub fn host() -> Result<Self> {
+        let mut target = Self::current()?;
+        if let Some(arch) = host_arch() {
+            target.arch = arch;
+        }
+        Ok(target)
+    }
+
+    /// Release archive name for this target
+    pub fn archive_name(&self) -> String {
+        format!("catalyst-{}.{}", self, self.archive_extension())
+    }
+
+    /// Archive names to look for in a release, preferred first
+    pub fn archive_names(&self) -> Vec<String> {
+        let mut names = vec![self.archive_name()];
+        let legacy_os = match self.os {
+            TargetOs::Linux { musl: false } => Some("linux"),
+            TargetOs::Linux { musl: true } => None,
+            TargetOs::MacOS => Some("macos"),
+            TargetOs::Windows => Some("windows"),
+        };
+        if let Some(os) = legacy_os {
+            names.push(format!(
+                "catalyst-{}-{}.{}",
+                self.arch,
+                os,
+                self.archive_extension()
+            ));
+        }
+        names
+    }
+
+    fn archive_extension(&self) -> &'static str {
+        match self.os {
+            TargetOs::Windows => "zip",
+            _ => "tar.gz",
+        }
+    }
+}
+
+impl fmt::Display for TargetTriple {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rest = match self.os {
+            TargetOs::Linux { musl: false } => "unknown-linux-gnu",
+            TargetOs::Linux { musl: true } => "unknown-linux-musl",
+            TargetOs::MacOS => "apple-darwin",
+            TargetOs::Windows => "pc-windows-msvc",
+        };
+        write!(f, "{}-{}", self.arch, rest)
+    }
+}
+
+impl FromStr for TargetTriple {
+    type Err = CatalystError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let unsupported = || {
+            CatalystError::UnsupportedPlatform(format!(
+                "{} (supported targets: {})",
+                s,
+                SUPPORTED_TARGETS.join(", ")
+            ))
+        };
+        let (arch, rest) = s.split_once('-').ok_or_else(unsupported)?;
+        let arch = parse_arch(arch).ok_or_else(unsupported)?;
+        let os = match rest {
+            "unknown-linux-gnu" => TargetOs::Linux { musl: false },
+            "unknown-linux-musl" => TargetOs::Linux { musl: true },
+            "apple-darwin" => TargetOs::MacOS,
+            "pc-windows-msvc" => TargetOs::Windows,
+            _ => return Err(unsupported()),
+        };
+        Ok(Self { arch, os })
+    }
+}
+
+/// Parse an architecture name as used by triples, `uname`, or Windows
+fn parse_arch(name: &str) -> Option<Arch> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "x86_64" | "amd64" | "x64" => Some(Arch::X86_64),
+        "aarch64" | "arm64" => Some(Arch::Aarch64),
+        _ => None,
+    }
+}
+
+/// Architecture of this machine, as opposed to the running executable
+///
+/// Best effort: asks `sysctl` on macOS (which sees through Rosetta) and
+/// `uname` on other Unixes, and reads the processor environment variables
+/// on Windows. Returns None if the answer isn't a supported architecture.
+pub fn host_arch() -> Option<Arch> {
+    if cfg!(windows) {
+        return std::env::var("PROCESSOR_ARCHITEW6432")
+            .or_else(|_| std::env::var("PROCESSOR_ARCHITECTURE"))
+            .ok()
+            .and_then(|name| parse_arch(&name));
+    }
+
+    if cfg!(target_os = "macos") {
+        let arm64 = command_output("sysctl", &["-n", "hw.optional.arm64"]);
+        if let Some(arm64) = arm64 {
+            return Some(if arm64.trim() == "1" {
+                Arch::Aarch64
+            } else {
+                Arch::X86_64
+            });
+        }
+    }
+    command_output("uname", &["-m"]).and_then(|name| parse_arch(&name))
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Architectures an executable was built for
+///
+/// Understands ELF, Mach-O (including universal binaries), and PE headers.
+/// Returns an empty list for anything else, including architectures
+/// Catalyst doesn't ship.
+///
+/// # Errors
+///
+/// Returns the I/O error if the file can't be read
+pub fn executable_arches(path: &Path) -> io::Result<Vec<Arch>> {
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    File::open(path)?
+        .take(HEADER_LEN as u64)
+        .read_to_end(&mut header)?;
+    Ok(header_arches(&header))
+}
+
+/// Architectures named by an executable header
+fn header_arches(header: &[u8]) -> Vec<Arch> {
+    let u16_le = |at: usize| {
+        header
+            .get(at..at + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+    };
+    let u16_be = |at: usize| {
+        header
+            .get(at..at + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+    };
+    let u32_le = |at: usize| {
+        header
+            .get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    let u32_be = |at: usize| {
+        header
+            .get(at..at + 4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    let mach_arch = |cpu_type: u32| match cpu_type {
+        CPU_TYPE_X86_64 => Some(Arch::X86_64),
+        CPU_TYPE_ARM64 => Some(Arch::Aarch64),
+        _ => None,
+    };
+
+    match header.get(..4) {
+        // ELF: e_machine in the byte order given by EI_DATA
+        Some([0x7f, b'E', b'L', b'F']) => {
+            let machine = match header.get(5) {
+                Some(2) => u16_be(18),
+                _ => u16_le(18),
+            };
+            match machine {
+                Some(EM_X86_64) => vec![Arch::X86_64],
+                Some(EM_AARCH64) => vec![Arch::Aarch64],
+                _ => Vec::new(),
+            }
+        }
+        // 64-bit Mach-O, little-endian
+        Some([0xcf, 0xfa, 0xed, 0xfe]) => u32_le(4).and_then(mach_arch).into_iter().collect(),
+        // Universal binary: big-endian list of fat_arch (or fat_arch_64) entries
+        Some([0xca, 0xfe, 0xba, magic @ (0xbe | 0xbf)]) => {
+            let entry_len = if *magic == 0xbe { 20 } else { 32 };
+            let count = u32_be(4).unwrap_or(0) as usize;
+            (0..count)
+                .filter_map(|i| u32_be(8 + i * entry_len))
+                .filter_map(mach_arch)
+                .collect()
+        }
+        // PE: the "PE\0\0" signature sits at the offset stored at 0x3c
+        Some([b'M', b'Z', ..]) => {
+            let Some(pe) = u32_le(0x3c).map(|offset| offset as usize) else {
+                return Vec::new();
+            };
+            if header.get(pe..pe + 4) != Some(b"PE\0\0") {
+                return Vec::new();
+            }
+            match u16_le(pe + 4) {
+                Some(IMAGE_FILE_MACHINE_AMD64) => vec![Arch::X86_64],
+                Some(IMAGE_FILE_MACHINE_ARM64) => vec![Arch::Aarch64],
+                _ => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_triple_names() {
+        for triple in SUPPORTED_TARGETS {
+            let target = TargetTriple::from_str(triple).unwrap();
+            assert_eq!(target.to_string(), *triple);
+        }
+        assert!(TargetTriple::from_str("riscv64gc-unknown-linux-gnu").is_err());
+        assert!(TargetTriple::current().is_ok());
+
+        let musl = TargetTriple::from_str("aarch64-unknown-linux-musl").unwrap();
+        assert_eq!(
+            musl.archive_names(),
+            vec!["catalyst-aarch64-unknown-linux-musl.tar.gz"]
+        );
+        let windows = TargetTriple::from_str("x86_64-pc-windows-msvc").unwrap();
+        assert_eq!(
+            windows.archive_names(),
+            vec![
+                "catalyst-x86_64-pc-windows-msvc.zip",
+                "catalyst-x86_64-windows.zip"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_header_arches() {
+        let mut elf = vec![0u8; 64];
+        elf[..4].copy_from_slice(b"\x7fELF");
+        elf[5] = 1;
+        elf[18..20].copy_from_slice(&EM_AARCH64.to_le_bytes());
+        assert_eq!(header_arches(&elf), vec![Arch::Aarch64]);
+
+        let mut macho = vec![0xcf, 0xfa, 0xed, 0xfe];
+        macho.extend_from_slice(&CPU_TYPE_X86_64.to_le_bytes());
+        assert_eq!(header_arches(&macho), vec![Arch::X86_64]);
+
+        let mut fat = vec![0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 2];
+        for cpu_type in [CPU_TYPE_X86_64, CPU_TYPE_ARM64] {
+            fat.extend_from_slice(&cpu_type.to_be_bytes());
+            fat.extend_from_slice(&[0; 16]);
+        }
+        assert_eq!(header_arches(&fat), vec![Arch::X86_64, Arch::Aarch64]);
+
+        let mut pe = vec![0u8; 0x90];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3c..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+        pe[0x84..0x86].copy_from_slice(&IMAGE_FILE_MACHINE_AMD64.to_le_bytes());
+        assert_eq!(header_arches(&pe), vec![Arch::X86_64]);
+
+        assert!(header_arches(b"#!/bin/sh\n").is_empty());
+    }
+
+    #[test]
+    fn test_running_executable_matches_current_target() {
+        let exe = std::env::current_exe().unwrap();
+        let target = TargetTriple::current().unwrap();
+        assert_eq!(executable_arches(&exe).unwrap(), vec![target.arch]);
+    }
+}
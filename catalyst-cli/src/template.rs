@@ -0,0 +1,204 @@
+//! `{{VARIABLE}}` substitution for generated files
+//!
+//! Hook wrappers, skill files ending in [`TEMPLATE_SUFFIX`], and the agent
+//! and slash command templates are all rendered with [`render`]. Besides
+//! the variables each kind of template defines for itself (`BINARY_NAME`
+//! for wrappers, `name` for agents), every template can use:
+//!
+//! | Variable           | Value                                         |
+//! |--------------------|-----------------------------------------------|
+//! | `BIN_DIR`          | Where hook binaries are installed             |
+//! | `PROJECT_DIR`      | Absolute path of the project root             |
+//! | `CATALYST_VERSION` | Version of this CLI                           |
+//!
+//! and the project's own values from catalyst.toml:
+//!
+//! ```toml
+//! [vars]
+//! TEAM = "platform"
+//! ```
+//!
+//! A template that uses a variable nothing defines fails to render, so a
+//! typo never ends up in a generated file. Values are inserted as they are;
+//! they aren't rendered again.
+
+use crate::config::CatalystConfig;
+use crate::types::{CatalystError, Result, BINARY_DIR, CATALYST_VERSION};
+use crate::validation::get_binary_directory;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+
+/// Variables and their values
+pub type TemplateVars = BTreeMap<String, String>;
+
+/// Variables every template gets; `[vars]` can't redefine them
+pub const BUILTIN_VARS: &[&str] = &["BIN_DIR", "PROJECT_DIR", "CATALYST_VERSION"];
+
+/// Suffix marking a skill file as a template; it's rendered and installed
+/// without the suffix (`SKILL.md.tmpl` becomes `SKILL.md`)
+pub const TEMPLATE_SUFFIX: &str = ".tmpl";
+
+/// Replace every `{{variable}}` in a template
+///
+/// Whitespace inside the braces is ignored (`{{ name }}`).
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` for a variable missing from `vars` or an
+/// unterminated `{{`
+pub fn render(template: &str, vars: &TemplateVars) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or_else(|| {
+            CatalystError::InvalidConfig("Unterminated '{{' in template".to_string())
+        })?;
+        let key = after[..end].trim();
+        let value = vars.get(key).ok_or_else(|| {
+            CatalystError::InvalidConfig(format!(
+                "Unknown template variable '{}' (known: {})",
+                key,
+                vars.keys().cloned().collect::<Vec<_>>().join(", ")
+            ))
+        })?;
+        rendered.push_str(value);
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+/// The built-in variables and the project's `[vars]` for a project
+///
+/// # Arguments
+///
+/// * `project_dir` - Project root
+/// * `config` - The project's catalyst.toml
+pub fn project_vars(project_dir: &Path, config: &CatalystConfig) -> TemplateVars {
+    // Canonical so `init .` and `status /abs/path` render the same wrapper
+    let project_dir = dunce::canonicalize(project_dir)
+        .or_else(|_| std::path::absolute(project_dir))
+        .unwrap_or_else(|_| project_dir.into());
+
+    let mut vars = shared_vars(config);
+    vars.insert("PROJECT_DIR".to_string(), project_dir.display().to_string());
+    vars
+}
+
+/// Variables for files shared between projects, such as skills in the
+/// skill library: everything but `PROJECT_DIR`
+///
+/// # Arguments
+///
+/// * `config` - Config whose `[vars]` to include (the user-wide one for
+///   the skill library)
+pub fn shared_vars(config: &CatalystConfig) -> TemplateVars {
+    let bin_dir = get_binary_directory()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_else(|_| format!("~/{}", BINARY_DIR));
+
+    let mut vars = TemplateVars::new();
+    vars.insert("BIN_DIR".to_string(), bin_dir);
+    vars.insert("CATALYST_VERSION".to_string(), CATALYST_VERSION.to_string());
+    vars.extend(config.vars.clone());
+    vars
+}
+
+/// Name and contents a skill file is installed with
+///
+/// Files ending in [`TEMPLATE_SUFFIX`] are rendered and lose the suffix;
+/// everything else is copied as is, since skills are full of `{{` in code
+/// samples.
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` naming the file if a template isn't UTF-8 or
+/// doesn't render
+pub fn skill_file<'a>(
+    file_name: &OsStr,
+    contents: &'a [u8],
+    vars: &TemplateVars,
+) -> Result<(OsString, Cow<'a, [u8]>)> {
+    let name = file_name.to_string_lossy();
+    let Some(stem) = name.strip_suffix(TEMPLATE_SUFFIX) else {
+        return Ok((file_name.to_os_string(), Cow::Borrowed(contents)));
+    };
+
+    let rendered = std::str::from_utf8(contents)
+        .map_err(|_| CatalystError::InvalidConfig("template is not UTF-8".to_string()))
+        .and_then(|template| render(template, vars))
+        .map_err(|e| CatalystError::InvalidConfig(format!("{}: {}", name, e)))?;
+    Ok((OsString::from(stem), Cow::Owned(rendered.into_bytes())))
+}
+
+/// Check a `[vars]` name: letters, digits, and underscores, not starting
+/// with a digit, and not one of [`BUILTIN_VARS`]
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` describing the problem
+pub fn validate_var_name(name: &str) -> Result<()> {
+    let well_formed = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !well_formed {
+        return Err(CatalystError::InvalidConfig(format!(
+            "vars.{}: names may only use letters, digits, and underscores, and can't start with a digit",
+            name
+        )));
+    }
+    if BUILTIN_VARS.contains(&name) {
+        return Err(CatalystError::InvalidConfig(format!(
+            "vars.{}: '{}' is a built-in template variable",
+            name, name
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_and_project_vars() {
+        let mut vars = TemplateVars::new();
+        vars.insert("name".to_string(), "reviewer".to_string());
+        assert_eq!(
+            render("name: {{name}} / {{ name }}", &vars).unwrap(),
+            "name: reviewer / reviewer"
+        );
+        assert!(render("{{project}}", &vars).is_err());
+        assert!(render("{{name", &vars).is_err());
+
+        let mut config = CatalystConfig::default();
+        config
+            .vars
+            .insert("TEAM".to_string(), "{{BIN_DIR}}".to_string());
+        let vars = project_vars(Path::new("/srv/app"), &config);
+        assert_eq!(
+            render("{{TEAM}} {{CATALYST_VERSION}} {{PROJECT_DIR}}", &vars).unwrap(),
+            format!("{{{{BIN_DIR}}}} {} /srv/app", CATALYST_VERSION)
+        );
+
+        let (name, contents) =
+            skill_file(OsStr::new("SKILL.md.tmpl"), b"in {{PROJECT_DIR}}", &vars).unwrap();
+        assert_eq!(name, "SKILL.md");
+        assert_eq!(&*contents, b"in /srv/app");
+        let (name, contents) = skill_file(OsStr::new("App.svelte"), b"{{x}}", &vars).unwrap();
+        assert_eq!(
+            (name.to_str(), &*contents),
+            (Some("App.svelte"), &b"{{x}}"[..])
+        );
+
+        assert!(validate_var_name("TEAM_2").is_ok());
+        assert!(validate_var_name("2TEAM").is_err());
+        assert!(validate_var_name("BIN_DIR").is_err());
+    }
+}
@@ -0,0 +1,541 @@
+//! Test runs for the test-runner hook
+//!
+//! The `test-runner` hook runs a project's tests when Claude stops (or after
+//! edits) and hands back a short report of what failed. The command comes
+//! from `.claude/test-runner.toml`, or is detected from the project root:
+//!
+//! | Marker                                              | Command              |
+//! |-----------------------------------------------------|----------------------|
+//! | `Cargo.toml`                                        | `cargo test`         |
+//! | `package.json` with a `test` script                 | `npm test`           |
+//! | `pyproject.toml`, `pytest.ini`, `setup.cfg`, `tox.ini`, `conftest.py` | `pytest -q -rfE` |
+//!
+//! ```toml
+//! # Run instead of the detected command (no shell)
+//! command = ["cargo", "test", "--workspace"]
+//!
+//! # Hook events that run the tests: Stop, SubagentStop, PostToolUse
+//! events = ["Stop"]
+//!
+//! # "block" makes Claude fix failures; "suggest" only reports them
+//! decision = "block"
+//!
+//! timeout_secs = 120
+//! max_failures = 10
+//! ```
+//!
+//! Failures are read from cargo's libtest output, pytest's short summary,
+//! and Jest's `●` headings; output in any other shape is reported by its
+//! last lines.
+
+use crate::build_check::resolve_program;
+use crate::process;
+use crate::types::{CatalystError, Result, CLAUDE_DIR};
+use catalyst_core::hooks::HookDecision;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// Test runner configuration file, inside `.claude/`
+pub const TEST_RUNNER_FILE: &str = "test-runner.toml";
+
+/// Hook events the test runner can be configured for
+pub const SUPPORTED_EVENTS: &[&str] = &["Stop", "SubagentStop", "PostToolUse"];
+
+/// Lines of output kept when no individual failures could be found
+const TAIL_LINES: usize = 20;
+
+/// Lines of detail kept per failure
+const DETAIL_LINES: usize = 4;
+
+/// What the hook does when tests fail
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestDecision {
+    /// Block, so Claude keeps going and fixes the tests
+    #[default]
+    Block,
+    /// Report the failures without blocking
+    Suggest,
+}
+
+impl From<TestDecision> for HookDecision {
+    fn from(decision: TestDecision) -> Self {
+        match decision {
+            TestDecision::Block => HookDecision::Block,
+            TestDecision::Suggest => HookDecision::Suggest,
+        }
+    }
+}
+
+/// `.claude/test-runner.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TestRunnerConfig {
+    /// Program and arguments; detected from the project when unset
+    pub command: Option<Vec<String>>,
+
+    /// Hook events that run the tests
+    pub events: Vec<String>,
+
+    pub decision: TestDecision,
+
+    /// Seconds before the test command is killed
+    pub timeout_secs: u64,
+
+    /// Failing tests listed in the report; the rest are only counted
+    pub max_failures: usize,
+}
+
+impl Default for TestRunnerConfig {
+    fn default() -> Self {
+        Self {
+            command: None,
+            events: vec!["Stop".to_string()],
+            decision: TestDecision::Block,
+            timeout_secs: 120,
+            max_failures: 10,
+        }
+    }
+}
+
+impl TestRunnerConfig {
+    /// Path of the config file for a project
+    pub fn path(project_dir: &Path) -> PathBuf {
+        project_dir.join(CLAUDE_DIR).join(TEST_RUNNER_FILE)
+    }
+
+    /// Load a project's test runner configuration, or defaults if it has none
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileReadFailed` if the file can't be read, or `InvalidConfig`
+    /// if it isn't valid TOML, has unknown keys, sets an empty command, or
+    /// names an event the hook doesn't handle
+    pub fn load(project_dir: &Path) -> Result<Self> {
+        let path = Self::path(project_dir);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).map_err(|e| CatalystError::FileReadFailed {
+            path: path.clone(),
+            source: e,
+        })?;
+        let config: Self = toml::from_str(&content).map_err(|e| {
+            CatalystError::InvalidConfig(format!("{}: {}", path.display(), e.message()))
+        })?;
+        if config.command.as_ref().is_some_and(Vec::is_empty) {
+            return Err(CatalystError::InvalidConfig(format!(
+                "{}: command must name a program",
+                path.display()
+            )));
+        }
+        if let Some(event) = config
+            .events
+            .iter()
+            .find(|event| !SUPPORTED_EVENTS.contains(&event.as_str()))
+        {
+            return Err(CatalystError::InvalidConfig(format!(
+                "{}: unsupported event '{}'. Expected one of: {}",
+                path.display(),
+                event,
+                SUPPORTED_EVENTS.join(", ")
+            )));
+        }
+        Ok(config)
+    }
+
+    /// Whether the tests run for a hook event
+    pub fn runs_on(&self, event: &str) -> bool {
+        self.events.iter().any(|configured| configured == event)
+    }
+
+    /// The configured command, or the one detected for the project
+    pub fn command_for(&self, project_dir: &Path) -> Option<Vec<String>> {
+        self.command
+            .clone()
+            .or_else(|| detect_test_command(project_dir))
+    }
+}
+
+/// Test command for a project, from the files at its root
+pub fn detect_test_command(project_dir: &Path) -> Option<Vec<String>> {
+    let command: &[&str] = if project_dir.join("Cargo.toml").is_file() {
+        &["cargo", "test"]
+    } else if has_npm_test_script(project_dir) {
+        &["npm", "test"]
+    } else if [
+        "pyproject.toml",
+        "pytest.ini",
+        "setup.cfg",
+        "tox.ini",
+        "conftest.py",
+    ]
+    .iter()
+    .any(|marker| project_dir.join(marker).is_file())
+    {
+        &["pytest", "-q", "-rfE"]
+    } else {
+        return None;
+    };
+    Some(command.iter().map(|s| s.to_string()).collect())
+}
+
+/// Whether package.json has a test script other than npm's placeholder
+fn has_npm_test_script(project_dir: &Path) -> bool {
+    let Ok(content) = fs::read_to_string(project_dir.join("package.json")) else {
+        return false;
+    };
+    let Ok(package) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return false;
+    };
+    package["scripts"]["test"]
+        .as_str()
+        .is_some_and(|script| !script.contains("no test specified"))
+}
+
+/// What happened when the tests ran
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed {
+        elapsed: Duration,
+    },
+    Failed {
+        exit_code: Option<i32>,
+        report: FailureReport,
+        elapsed: Duration,
+    },
+    /// The tests couldn't be run or didn't finish; says nothing about the code
+    Skipped {
+        reason: String,
+    },
+}
+
+/// Run a test command in `project_dir`
+///
+/// The program is looked up in the project's `node_modules/.bin`, then on
+/// `PATH`.
+pub fn run_tests(project_dir: &Path, command: &[String], timeout: Duration) -> TestOutcome {
+    let Some((program, args)) = command.split_first() else {
+        return TestOutcome::Skipped {
+            reason: "empty command".to_string(),
+        };
+    };
+    let Some(program_path) = resolve_program(program, project_dir) else {
+        return TestOutcome::Skipped {
+            reason: format!("{} is not installed", program),
+        };
+    };
+
+    let started = Instant::now();
+    let mut command = Command::new(program_path);
+    command.args(args).current_dir(project_dir);
+    match process::run_captured(&mut command, timeout) {
+        Ok(Some((status, _))) if status.success() => TestOutcome::Passed {
+            elapsed: started.elapsed(),
+        },
+        Ok(Some((status, output))) => TestOutcome::Failed {
+            exit_code: status.code(),
+            report: FailureReport::parse(&output),
+            elapsed: started.elapsed(),
+        },
+        Ok(None) => TestOutcome::Skipped {
+            reason: format!("did not finish within {} seconds", timeout.as_secs()),
+        },
+        Err(e) => TestOutcome::Skipped {
+            reason: format!("could not run {}: {}", program, e),
+        },
+    }
+}
+
+/// One failing test
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestFailure {
+    pub name: String,
+    /// The assertion or panic message, when the output has one
+    pub detail: Vec<String>,
+}
+
+/// Failing tests picked out of a test run's output
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FailureReport {
+    pub failures: Vec<TestFailure>,
+
+    /// The runner's own result lines ("test result: FAILED. ...",
+    /// "Tests: 2 failed, 8 passed")
+    pub summary: Vec<String>,
+
+    /// Last lines of output, kept when no failures were recognized (a
+    /// compile error, a crash in setup)
+    pub tail: Vec<String>,
+}
+
+impl FailureReport {
+    /// Parse libtest, pytest, or Jest output
+    pub fn parse(output: &str) -> Self {
+        let lines: Vec<&str> = output.lines().collect();
+        let mut report = Self::default();
+
+        for (index, line) in lines.iter().enumerate() {
+            let trimmed = line.trim();
+            if let Some(name) = trimmed
+                .strip_prefix("test ")
+                .and_then(|rest| rest.strip_suffix(" ... FAILED"))
+            {
+                let detail = libtest_detail(&lines, name);
+                report.push(name, detail);
+            } else if let Some(rest) = trimmed
+                .strip_prefix("FAILED ")
+                .or_else(|| trimmed.strip_prefix("ERROR "))
+            {
+                match rest.split_once(" - ") {
+                    Some((name, message)) => report.push(name, vec![message.to_string()]),
+                    None => report.push(rest, Vec::new()),
+                }
+            } else if let Some(name) = trimmed.strip_prefix("● ") {
+                if !name.starts_with("Console") {
+                    report.push(name, following_lines(&lines[index + 1..]));
+                }
+            } else if trimmed.starts_with("test result: FAILED")
+                || trimmed.starts_with("Tests:")
+                || is_pytest_summary(trimmed)
+            {
+                report
+                    .summary
+                    .push(trimmed.trim_matches('=').trim().to_string());
+            }
+        }
+
+        if report.failures.is_empty() {
+            let nonblank: Vec<&str> = lines
+                .iter()
+                .map(|line| line.trim_end())
+                .filter(|line| !line.trim().is_empty())
+                .collect();
+            let start = nonblank.len().saturating_sub(TAIL_LINES);
+            report.tail = nonblank[start..].iter().map(|s| s.to_string()).collect();
+        }
+        report
+    }
+
+    /// Add a failure unless it's already listed (libtest prints each failing
+    /// name twice; Jest repeats headings in its summary)
+    fn push(&mut self, name: &str, detail: Vec<String>) {
+        if !self.failures.iter().any(|failure| failure.name == name) {
+            self.failures.push(TestFailure {
+                name: name.to_string(),
+                detail,
+            });
+        }
+    }
+
+    /// Render the failures, at most `max` of them, then the result lines
+    pub fn render(&self, max: usize) -> String {
+        let mut out = String::new();
+        if self.failures.is_empty() {
+            out.push_str("No failing tests were recognized; end of output:\n");
+            for line in &self.tail {
+                let _ = writeln!(out, "  {}", line);
+            }
+        } else {
+            let _ = writeln!(out, "{} failing:", plural(self.failures.len()));
+            for failure in self.failures.iter().take(max) {
+                let _ = writeln!(out, "- {}", failure.name);
+                for line in &failure.detail {
+                    let _ = writeln!(out, "    {}", line);
+                }
+            }
+            if self.failures.len() > max {
+                let _ = writeln!(out, "... and {} more", self.failures.len() - max);
+            }
+        }
+        for line in &self.summary {
+            let _ = writeln!(out, "{}", line);
+        }
+        out
+    }
+}
+
+/// The panic message from a libtest `---- name stdout ----` section
+fn libtest_detail(lines: &[&str], name: &str) -> Vec<String> {
+    let header = format!("---- {} stdout ----", name);
+    lines
+        .iter()
+        .position(|line| line.trim() == header)
+        .map(|start| following_lines(&lines[start + 1..]))
+        .unwrap_or_default()
+}
+
+/// Up to `DETAIL_LINES` non-blank lines, stopping at the next section
+fn following_lines(lines: &[&str]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| line.trim())
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| {
+            !line.is_empty() && !line.starts_with("---- ") && !line.starts_with("● ")
+        })
+        .filter(|line| !line.starts_with("note: run with `RUST_BACKTRACE"))
+        .take(DETAIL_LINES)
+        .map(str::to_string)
+        .collect()
+}
+
+/// pytest's closing line, e.g. "==== 2 failed, 5 passed in 0.12s ===="
+fn is_pytest_summary(line: &str) -> bool {
+    line.starts_with("===")
+        && (line.contains(" failed") || line.contains(" error"))
+        && line.contains(" in ")
+}
+
+fn plural(count: usize) -> String {
+    if count == 1 {
+        "1 test".to_string()
+    } else {
+        format!("{} tests", count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_and_configure_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        assert_eq!(detect_test_command(project), None);
+
+        fs::write(
+            project.join("package.json"),
+            r#"{"scripts":{"test":"echo \"Error: no test specified\" && exit 1"}}"#,
+        )
+        .unwrap();
+        fs::write(project.join("pytest.ini"), "[pytest]\n").unwrap();
+        assert_eq!(
+            detect_test_command(project).unwrap(),
+            vec!["pytest", "-q", "-rfE"]
+        );
+
+        fs::write(
+            project.join("package.json"),
+            r#"{"scripts":{"test":"jest"}}"#,
+        )
+        .unwrap();
+        assert_eq!(detect_test_command(project).unwrap(), vec!["npm", "test"]);
+
+        fs::create_dir_all(project.join(CLAUDE_DIR)).unwrap();
+        fs::write(
+            TestRunnerConfig::path(project),
+            "command = [\"make\", \"check\"]\nevents = [\"Stop\", \"PostToolUse\"]\ndecision = \"suggest\"\n",
+        )
+        .unwrap();
+        let config = TestRunnerConfig::load(project).unwrap();
+        assert_eq!(config.command_for(project).unwrap(), vec!["make", "check"]);
+        assert!(config.runs_on("PostToolUse"));
+        assert_eq!(config.decision, TestDecision::Suggest);
+        assert_eq!(config.timeout_secs, 120);
+
+        fs::write(
+            TestRunnerConfig::path(project),
+            "events = [\"PreToolUse\"]\n",
+        )
+        .unwrap();
+        assert!(TestRunnerConfig::load(project).is_err());
+    }
+
+    #[test]
+    fn test_parse_libtest_and_pytest_failures() {
+        let cargo = "\
+running 3 tests
+test tests::adds ... ok
+test tests::subtracts ... FAILED
+test tests::divides ... FAILED
+
+failures:
+
+---- tests::subtracts stdout ----
+thread 'tests::subtracts' panicked at src/lib.rs:20:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- tests::divides stdout ----
+thread 'tests::divides' panicked at src/lib.rs:25:9:
+attempt to divide by zero
+
+failures:
+    tests::divides
+    tests::subtracts
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out
+";
+        let report = FailureReport::parse(cargo);
+        let names: Vec<&str> = report.failures.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["tests::subtracts", "tests::divides"]);
+        assert_eq!(report.failures[0].detail.len(), 4);
+        assert_eq!(report.failures[1].detail[1], "attempt to divide by zero");
+        assert_eq!(report.summary.len(), 1);
+
+        let rendered = report.render(1);
+        assert!(rendered.starts_with("2 tests failing:\n- tests::subtracts\n"));
+        assert!(rendered.contains("... and 1 more"));
+        assert!(rendered.ends_with("0 filtered out\n"));
+
+        let pytest = "\
+F.
+=========================== short test summary info ============================
+FAILED tests/test_math.py::test_add - assert 3 == 4
+ERROR tests/test_db.py::test_connect
+========================= 1 failed, 1 passed, 1 error in 0.05s =========================
+";
+        let report = FailureReport::parse(pytest);
+        assert_eq!(report.failures.len(), 2);
+        assert_eq!(report.failures[0].name, "tests/test_math.py::test_add");
+        assert_eq!(report.failures[0].detail, vec!["assert 3 == 4"]);
+        assert_eq!(report.summary, vec!["1 failed, 1 passed, 1 error in 0.05s"]);
+    }
+
+    #[test]
+    fn test_parse_jest_and_unrecognized_output() {
+        let jest = "\
+FAIL src/sum.test.js
+  ● sum › adds numbers
+
+    expect(received).toBe(expected) // Object.is equality
+
+    Expected: 4
+    Received: 5
+
+  ● Console
+
+    console.log
+      hi
+
+Tests:       1 failed, 3 passed, 4 total
+";
+        let report = FailureReport::parse(jest);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].name, "sum › adds numbers");
+        assert_eq!(
+            report.failures[0].detail,
+            vec!["expect(received).toBe(expected) // Object.is equality"]
+        );
+        assert_eq!(
+            report.summary,
+            vec!["Tests:       1 failed, 3 passed, 4 total"]
+        );
+
+        let output: String = (1..=30).map(|i| format!("line {}\n\n", i)).collect();
+        let report = FailureReport::parse(&output);
+        assert!(report.failures.is_empty());
+        assert_eq!(report.tail.len(), TAIL_LINES);
+        assert_eq!(report.tail[0], "line 11");
+        assert!(report.render(10).contains("end of output"));
+    }
+}
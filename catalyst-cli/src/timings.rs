@@ -0,0 +1,219 @@
+//! Lightweight phase timing for `--timings`
+//!
+//! Major phases of each command are wrapped in a [`span`]. Spans cost a
+//! single atomic load unless timing was turned on with [`enable`], in
+//! which case each span's wall-clock time is accumulated by name and
+//! [`report`] renders a breakdown. This is mainly useful for spotting slow
+//! filesystems (network mounts, Docker volumes) where one phase dominates.
+
+use once_cell::sync::{Lazy, OnceCell};
+use std::cell::Cell;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// When timing was enabled, used as the start of the whole command
+static STARTED: OnceCell<Instant> = OnceCell::new();
+
+/// Accumulated timings, in the order phases were first entered
+static TIMINGS: Lazy<Mutex<Vec<PhaseTiming>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+thread_local! {
+    /// Nesting depth of open spans on this thread
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Total time spent in one named phase
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseTiming {
+    pub name: &'static str,
+    /// Nesting depth when the phase was first entered (0 = top level)
+    pub depth: usize,
+    /// Number of times the phase ran
+    pub calls: u32,
+    pub total: Duration,
+}
+
+/// Turn on timing collection for the rest of the process
+pub fn enable() {
+    let _ = STARTED.set(Instant::now());
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether timing collection is on
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Start timing a phase; the time is recorded when the guard is dropped
+pub fn span(name: &'static str) -> Span {
+    if !is_enabled() {
+        return Span { inner: None };
+    }
+
+    let depth = DEPTH.with(|d| {
+        let depth = d.get();
+        d.set(depth + 1);
+        depth
+    });
+
+    let index =
+        TIMINGS.lock().ok().map(
+            |mut timings| match timings.iter().position(|t| t.name == name) {
+                Some(index) => index,
+                None => {
+                    timings.push(PhaseTiming {
+                        name,
+                        depth,
+                        calls: 0,
+                        total: Duration::ZERO,
+                    });
+                    timings.len() - 1
+                }
+            },
+        );
+
+    Span {
+        inner: index.map(|index| (index, Instant::now())),
+    }
+}
+
+/// Guard returned by [`span`]
+#[must_use = "the phase is timed until the guard is dropped"]
+pub struct Span {
+    inner: Option<(usize, Instant)>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some((index, start)) = self.inner else {
+            return;
+        };
+        let elapsed = start.elapsed();
+
+        DEPTH.with(|d| d.set(d.get().saturating_sub(1)));
+        if let Ok(mut timings) = TIMINGS.lock() {
+            if let Some(timing) = timings.get_mut(index) {
+                timing.calls += 1;
+                timing.total += elapsed;
+            }
+        }
+    }
+}
+
+/// Timings collected so far
+pub fn timings() -> Vec<PhaseTiming> {
+    TIMINGS.lock().map(|t| t.clone()).unwrap_or_default()
+}
+
+/// Render the breakdown for the whole command, if timing is enabled
+///
+/// The total is measured from the call to [`enable`].
+pub fn finish() -> Option<String> {
+    let started = STARTED.get()?;
+    Some(report(&timings(), started.elapsed()))
+}
+
+/// Render a phase breakdown
+///
+/// # Arguments
+///
+/// * `timings` - Collected phase timings
+/// * `total` - Wall-clock time of the whole command
+pub fn report(timings: &[PhaseTiming], total: Duration) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Phase timings:");
+
+    let width = timings
+        .iter()
+        .map(|t| t.name.len() + t.depth * 2)
+        .max()
+        .unwrap_or(0)
+        .max("total".len());
+
+    for timing in timings {
+        let label = format!("{}{}", "  ".repeat(timing.depth), timing.name);
+        let percent = if total.is_zero() {
+            0.0
+        } else {
+            timing.total.as_secs_f64() / total.as_secs_f64() * 100.0
+        };
+        let calls = if timing.calls > 1 {
+            format!(" ({} calls)", timing.calls)
+        } else {
+            String::new()
+        };
+        let _ = writeln!(
+            out,
+            "  {:<width$}  {:>9.2} ms  {:>5.1}%{}",
+            label,
+            timing.total.as_secs_f64() * 1000.0,
+            percent,
+            calls,
+            width = width
+        );
+    }
+
+    let _ = writeln!(
+        out,
+        "  {:<width$}  {:>9.2} ms",
+        "total",
+        total.as_secs_f64() * 1000.0,
+        width = width
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_formats_nested_phases() {
+        let timings = vec![
+            PhaseTiming {
+                name: "install skills",
+                depth: 0,
+                calls: 1,
+                total: Duration::from_millis(30),
+            },
+            PhaseTiming {
+                name: "copy skill",
+                depth: 1,
+                calls: 3,
+                total: Duration::from_millis(15),
+            },
+        ];
+
+        let output = report(&timings, Duration::from_millis(60));
+        assert!(output.contains("install skills"));
+        assert!(output.contains("    copy skill"));
+        assert!(output.contains("50.0%"));
+        assert!(output.contains("(3 calls)"));
+        assert!(output.contains("total"));
+    }
+
+    #[test]
+    fn test_spans_accumulate_when_enabled() {
+        enable();
+        for _ in 0..2 {
+            let _outer = span("test outer phase");
+            let _inner = span("test inner phase");
+        }
+
+        let timings = timings();
+        let outer = timings
+            .iter()
+            .find(|t| t.name == "test outer phase")
+            .unwrap();
+        let inner = timings
+            .iter()
+            .find(|t| t.name == "test inner phase")
+            .unwrap();
+        assert_eq!(outer.calls, 2);
+        assert_eq!(inner.depth, outer.depth + 1);
+    }
+}
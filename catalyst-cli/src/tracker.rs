@@ -0,0 +1,630 @@
+//! Storage for the SQLite file-change tracker
+//!
+//! file-change-tracker records every session in one database,
+//! `~/.claude/hooks-state-rust/tracker.db`, keyed by `session_id`. Older
+//! versions wrote a separate `<session>.db` per session; [`open_tracker_db`]
+//! imports those once and moves them into `migrated/`, so cross-session
+//! queries and pruning only ever deal with a single file.
+//!
+//! [`SqliteStore`] is the `sqlite` backend of
+//! [`ModificationStore`](crate::modification_store::ModificationStore).
+
+use crate::modification_store::{ModificationFilter, ModificationRow, ModificationStore};
+use crate::types::{CatalystError, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OpenFlags, TransactionBehavior};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Name of the tracker database inside the state directory
+pub const TRACKER_DB_FILE: &str = "tracker.db";
+
+/// Subdirectory legacy per-session databases are moved to once imported
+pub const MIGRATED_DIR: &str = "migrated";
+
+/// How long a connection waits for another hook holding the write lock
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tables and indexes, safe to apply to an existing database
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS file_modifications (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        session_id TEXT NOT NULL,
+        file_path TEXT NOT NULL,
+        tool TEXT NOT NULL,
+        timestamp TEXT NOT NULL,
+        category TEXT NOT NULL,
+        has_async BOOLEAN DEFAULT 0,
+        has_try_catch BOOLEAN DEFAULT 0,
+        has_prisma BOOLEAN DEFAULT 0,
+        has_controller BOOLEAN DEFAULT 0,
+        has_api_call BOOLEAN DEFAULT 0,
+        line_count INTEGER DEFAULT 0,
+        git_branch TEXT,
+        git_commit TEXT
+    );
+    CREATE INDEX IF NOT EXISTS idx_session ON file_modifications(session_id);
+    CREATE INDEX IF NOT EXISTS idx_category ON file_modifications(session_id, category);
+    CREATE INDEX IF NOT EXISTS idx_timestamp ON file_modifications(timestamp DESC);
+    CREATE INDEX IF NOT EXISTS idx_session_file ON file_modifications(session_id, file_path);
+
+    CREATE TABLE IF NOT EXISTS sessions (
+        session_id TEXT PRIMARY KEY,
+        start_time TEXT NOT NULL,
+        last_activity TEXT NOT NULL,
+        total_files INTEGER DEFAULT 0,
+        backend_files INTEGER DEFAULT 0,
+        frontend_files INTEGER DEFAULT 0,
+        database_files INTEGER DEFAULT 0
+    );
+    CREATE INDEX IF NOT EXISTS idx_sessions_activity ON sessions(last_activity DESC);
+
+    CREATE TABLE IF NOT EXISTS hook_runs (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        hook TEXT NOT NULL,
+        timestamp TEXT NOT NULL,
+        duration_ms INTEGER NOT NULL,
+        outcome TEXT NOT NULL,
+        detail TEXT
+    );
+    CREATE INDEX IF NOT EXISTS idx_hook_runs_timestamp ON hook_runs(timestamp DESC);
+";
+
+pub(crate) const MODIFICATION_COLUMNS: &str =
+    "session_id, file_path, tool, timestamp, category, has_async, \
+     has_try_catch, has_prisma, has_controller, has_api_call, line_count, git_branch, git_commit";
+
+/// Columns of `file_modifications` before the git columns were added, which
+/// is all a legacy per-session database has
+const LEGACY_MODIFICATION_COLUMNS: &str = "session_id, file_path, tool, timestamp, category, \
+     has_async, has_try_catch, has_prisma, has_controller, has_api_call, line_count";
+
+/// Columns added to `file_modifications` after its first release, with
+/// their types; [`add_missing_columns`] adds them to older databases
+const ADDED_COLUMNS: &[(&str, &str)] = &[("git_branch", "TEXT"), ("git_commit", "TEXT")];
+
+fn db_error(path: &Path) -> impl Fn(rusqlite::Error) -> CatalystError + '_ {
+    move |e| CatalystError::SessionDatabase(format!("{}: {}", path.display(), e))
+}
+
+/// Path of the tracker database in `state_dir`
+pub fn tracker_db_path(state_dir: &Path) -> PathBuf {
+    state_dir.join(TRACKER_DB_FILE)
+}
+
+/// Open (creating if needed) the tracker database for writing
+///
+/// Applies the schema and imports any legacy per-session databases left in
+/// `state_dir`.
+///
+/// # Errors
+///
+/// Returns `DirectoryCreationFailed` if `state_dir` can't be created, or
+/// `SessionDatabase` if the database can't be opened or migrated
+pub fn open_tracker_db(state_dir: &Path) -> Result<Connection> {
+    fs::create_dir_all(state_dir).map_err(|source| CatalystError::DirectoryCreationFailed {
+        path: state_dir.to_path_buf(),
+        source,
+    })?;
+
+    let db_path = tracker_db_path(state_dir);
+    let to_error = db_error(&db_path);
+    let mut conn = Connection::open(&db_path).map_err(&to_error)?;
+    conn.busy_timeout(BUSY_TIMEOUT).map_err(&to_error)?;
+    conn.execute_batch(SCHEMA).map_err(&to_error)?;
+    add_missing_columns(&conn).map_err(&to_error)?;
+    migrate_legacy_databases(&mut conn, state_dir)?;
+    Ok(conn)
+}
+
+/// `file_modifications` columns of [`ADDED_COLUMNS`] the database lacks
+fn missing_columns(
+    conn: &Connection,
+) -> rusqlite::Result<Vec<&'static (&'static str, &'static str)>> {
+    let existing = conn
+        .prepare("SELECT name FROM pragma_table_info('file_modifications')")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(ADDED_COLUMNS
+        .iter()
+        .filter(|(name, _)| !existing.iter().any(|column| column == name))
+        .collect())
+}
+
+/// Bring a database created by an older tracker up to the current columns
+fn add_missing_columns(conn: &Connection) -> rusqlite::Result<()> {
+    for (name, kind) in missing_columns(conn)? {
+        conn.execute_batch(&format!(
+            "ALTER TABLE file_modifications ADD COLUMN {} {}",
+            name, kind
+        ))?;
+    }
+    Ok(())
+}
+
+/// Open the tracker database read-only, or `None` if nothing was tracked
+///
+/// Legacy databases are migrated first, so reports see every session.
+///
+/// # Errors
+///
+/// Returns `SessionDatabase` if the database can't be opened or migrated
+pub fn open_tracker_db_read_only(state_dir: &Path) -> Result<Option<Connection>> {
+    if !legacy_databases(state_dir)?.is_empty() {
+        open_tracker_db(state_dir)?;
+    }
+
+    let db_path = tracker_db_path(state_dir);
+    if !db_path.is_file() {
+        return Ok(None);
+    }
+    let to_error = db_error(&db_path);
+    let open = || -> rusqlite::Result<Connection> {
+        let conn = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(conn)
+    };
+    let conn = open().map_err(&to_error)?;
+    // A database no tracker has written to since an upgrade lacks the newer
+    // columns; reopening it for writing adds them
+    if !missing_columns(&conn).map_err(&to_error)?.is_empty() {
+        drop(conn);
+        open_tracker_db(state_dir)?;
+        return open().map(Some).map_err(&to_error);
+    }
+    Ok(Some(conn))
+}
+
+/// Tracked edits, for one session or all of them, in the order recorded
+///
+/// # Errors
+///
+/// Returns an error if the table can't be queried
+pub fn read_modifications(
+    conn: &Connection,
+    session: Option<&str>,
+) -> rusqlite::Result<Vec<ModificationRow>> {
+    match session {
+        Some(session) => conn
+            .prepare(&format!(
+                "SELECT {} FROM file_modifications WHERE session_id = ?1 ORDER BY id",
+                MODIFICATION_COLUMNS
+            ))?
+            .query_map(params![session], modification_row)?
+            .collect(),
+        None => conn
+            .prepare(&format!(
+                "SELECT {} FROM file_modifications ORDER BY id",
+                MODIFICATION_COLUMNS
+            ))?
+            .query_map([], modification_row)?
+            .collect(),
+    }
+}
+
+/// A row selected as [`MODIFICATION_COLUMNS`]
+pub(crate) fn modification_row(row: &rusqlite::Row) -> rusqlite::Result<ModificationRow> {
+    Ok(ModificationRow {
+        session_id: row.get(0)?,
+        file_path: row.get(1)?,
+        tool: row.get(2)?,
+        timestamp: row.get(3)?,
+        category: row.get(4)?,
+        has_async: row.get(5)?,
+        has_try_catch: row.get(6)?,
+        has_prisma: row.get(7)?,
+        has_controller: row.get(8)?,
+        has_api_call: row.get(9)?,
+        line_count: row.get(10)?,
+        git_branch: row.get(11)?,
+        git_commit: row.get(12)?,
+    })
+}
+
+/// The tracker database as a [`ModificationStore`]
+///
+/// Besides each edit, `record` keeps the per-session counts in the
+/// `sessions` table up to date. Timestamps are compared as RFC 3339 text,
+/// which orders correctly because the tracker writes them in UTC.
+pub struct SqliteStore {
+    conn: Connection,
+    path: PathBuf,
+}
+
+impl SqliteStore {
+    /// Open the tracker database in `state_dir` for writing
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`open_tracker_db`]
+    pub fn open(state_dir: &Path) -> Result<Self> {
+        Ok(Self {
+            conn: open_tracker_db(state_dir)?,
+            path: tracker_db_path(state_dir),
+        })
+    }
+}
+
+impl ModificationStore for SqliteStore {
+    fn record(&mut self, modification: &ModificationRow) -> Result<()> {
+        let to_error = db_error(&self.path);
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(&to_error)?;
+        tx.execute(
+            &format!(
+                "INSERT INTO file_modifications ({})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                MODIFICATION_COLUMNS
+            ),
+            params![
+                modification.session_id,
+                modification.file_path,
+                modification.tool,
+                modification.timestamp,
+                modification.category,
+                modification.has_async,
+                modification.has_try_catch,
+                modification.has_prisma,
+                modification.has_controller,
+                modification.has_api_call,
+                modification.line_count,
+                modification.git_branch,
+                modification.git_commit,
+            ],
+        )
+        .map_err(&to_error)?;
+
+        let in_category = |category: &str| i64::from(modification.category == category);
+        tx.execute(
+            "INSERT INTO sessions (session_id, start_time, last_activity, total_files,
+                                   backend_files, frontend_files, database_files)
+             VALUES (?1, ?2, ?2, 1, ?3, ?4, ?5)
+             ON CONFLICT(session_id) DO UPDATE SET
+                last_activity = excluded.last_activity,
+                total_files = total_files + 1,
+                backend_files = backend_files + excluded.backend_files,
+                frontend_files = frontend_files + excluded.frontend_files,
+                database_files = database_files + excluded.database_files",
+            params![
+                modification.session_id,
+                modification.timestamp,
+                in_category("backend"),
+                in_category("frontend"),
+                in_category("database"),
+            ],
+        )
+        .map_err(&to_error)?;
+        tx.commit().map_err(&to_error)
+    }
+
+    fn query(&self, filter: &ModificationFilter) -> Result<Vec<ModificationRow>> {
+        let mut rows = read_modifications(&self.conn, filter.session.as_deref())
+            .map_err(db_error(&self.path))?;
+        rows.retain(|row| filter.includes_time(&row.timestamp));
+        rows.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        Ok(rows)
+    }
+
+    fn prune(&mut self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let to_error = db_error(&self.path);
+        let cutoff = cutoff.to_rfc3339();
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(&to_error)?;
+        let removed = tx
+            .execute(
+                "DELETE FROM file_modifications WHERE timestamp < ?1",
+                params![cutoff],
+            )
+            .map_err(&to_error)?;
+        tx.execute(
+            "DELETE FROM sessions WHERE last_activity < ?1",
+            params![cutoff],
+        )
+        .map_err(&to_error)?;
+        tx.execute(
+            "DELETE FROM hook_runs WHERE timestamp < ?1",
+            params![cutoff],
+        )
+        .map_err(&to_error)?;
+        tx.commit().map_err(&to_error)?;
+        Ok(removed)
+    }
+}
+
+/// Per-session `<session>.db` files written by older trackers
+fn legacy_databases(state_dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(state_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(CatalystError::Io(e)),
+    };
+
+    let mut databases: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path.extension().is_some_and(|ext| ext == "db")
+                && path.file_name().is_some_and(|name| name != TRACKER_DB_FILE)
+        })
+        .collect();
+    databases.sort();
+    Ok(databases)
+}
+
+/// Import legacy per-session databases into the tracker database
+///
+/// Each legacy file is imported in its own transaction and moved into
+/// `migrated/` before that transaction commits, while the write lock is
+/// held, so concurrent hooks never import the same file twice.
+///
+/// # Returns
+///
+/// Returns the number of legacy databases imported
+///
+/// # Errors
+///
+/// Returns `SessionDatabase` if a legacy database can't be read, or an I/O
+/// error if it can't be moved afterwards
+pub fn migrate_legacy_databases(conn: &mut Connection, state_dir: &Path) -> Result<usize> {
+    let mut imported = 0;
+    for legacy in legacy_databases(state_dir)? {
+        let to_error = db_error(&legacy);
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(&to_error)?;
+        // Another hook may have migrated this file while we waited for the lock
+        if !legacy.is_file() {
+            continue;
+        }
+
+        let source = Connection::open_with_flags(&legacy, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(&to_error)?;
+        copy_legacy_rows(&source, &tx).map_err(&to_error)?;
+        drop(source);
+
+        let migrated_dir = state_dir.join(MIGRATED_DIR);
+        fs::create_dir_all(&migrated_dir).map_err(|source| {
+            CatalystError::DirectoryCreationFailed {
+                path: migrated_dir.clone(),
+                source,
+            }
+        })?;
+        let moved = migrated_dir.join(legacy.file_name().unwrap_or_default());
+        fs::rename(&legacy, &moved).map_err(CatalystError::Io)?;
+        if let Err(e) = tx.commit() {
+            // Put it back so the next run retries the import
+            let _ = fs::rename(&moved, &legacy);
+            return Err(to_error(e));
+        }
+        imported += 1;
+    }
+    Ok(imported)
+}
+
+fn copy_legacy_rows(source: &Connection, tx: &Connection) -> rusqlite::Result<()> {
+    let has_table = |table: &str| -> rusqlite::Result<bool> {
+        source
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                params![table],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|count| count > 0)
+    };
+
+    if has_table("file_modifications")? {
+        let mut select = source.prepare(&format!(
+            "SELECT {} FROM file_modifications ORDER BY id",
+            LEGACY_MODIFICATION_COLUMNS
+        ))?;
+        let mut insert = tx.prepare(&format!(
+            "INSERT INTO file_modifications ({})
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            LEGACY_MODIFICATION_COLUMNS
+        ))?;
+        let mut rows = select.query([])?;
+        while let Some(row) = rows.next()? {
+            let values: Vec<rusqlite::types::Value> = (0..11)
+                .map(|i| row.get(i))
+                .collect::<rusqlite::Result<_>>()?;
+            insert.execute(rusqlite::params_from_iter(values))?;
+        }
+    }
+
+    if has_table("sessions")? {
+        let mut select = source.prepare(
+            "SELECT session_id, start_time, last_activity, total_files,
+                    backend_files, frontend_files, database_files
+             FROM sessions",
+        )?;
+        // A session already in tracker.db gets the legacy counts added
+        let mut upsert = tx.prepare(
+            "INSERT INTO sessions (session_id, start_time, last_activity, total_files,
+                                   backend_files, frontend_files, database_files)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(session_id) DO UPDATE SET
+                start_time = min(start_time, excluded.start_time),
+                last_activity = max(last_activity, excluded.last_activity),
+                total_files = total_files + excluded.total_files,
+                backend_files = backend_files + excluded.backend_files,
+                frontend_files = frontend_files + excluded.frontend_files,
+                database_files = database_files + excluded.database_files",
+        )?;
+        let mut rows = select.query([])?;
+        while let Some(row) = rows.next()? {
+            let values: Vec<rusqlite::types::Value> = (0..7)
+                .map(|i| row.get(i))
+                .collect::<rusqlite::Result<_>>()?;
+            upsert.execute(rusqlite::params_from_iter(values))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn legacy_db(state_dir: &Path, session: &str, files: &[&str]) {
+        let conn = Connection::open(state_dir.join(format!("{}.db", session))).unwrap();
+        conn.execute_batch(SCHEMA).unwrap();
+        conn.execute(
+            "INSERT INTO sessions (session_id, start_time, last_activity, total_files)
+             VALUES (?1, '2026-01-01T10:00:00+00:00', '2026-01-01T11:00:00+00:00', ?2)",
+            params![session, files.len()],
+        )
+        .unwrap();
+        for file in files {
+            conn.execute(
+                "INSERT INTO file_modifications (session_id, file_path, tool, timestamp, category)
+                 VALUES (?1, ?2, 'Edit', '2026-01-01T10:30:00+00:00', 'other')",
+                params![session, file],
+            )
+            .unwrap();
+        }
+    }
+
+    fn count(conn: &Connection, sql: &str) -> i64 {
+        conn.query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_open_tracker_db_imports_legacy_databases_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = temp_dir.path();
+        legacy_db(state, "one", &["a.ts", "b.ts"]);
+        legacy_db(state, "two", &["c.ts"]);
+
+        let conn = open_tracker_db(state).unwrap();
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM file_modifications"), 3);
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM sessions"), 2);
+        assert!(!state.join("one.db").exists());
+        assert!(state.join(MIGRATED_DIR).join("one.db").is_file());
+        drop(conn);
+
+        // Nothing left to import the second time
+        let conn = open_tracker_db(state).unwrap();
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM file_modifications"), 3);
+        assert!(legacy_databases(state).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migration_merges_existing_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = temp_dir.path();
+        let conn = open_tracker_db(state).unwrap();
+        conn.execute(
+            "INSERT INTO sessions (session_id, start_time, last_activity, total_files)
+             VALUES ('one', '2026-01-01T12:00:00+00:00', '2026-01-01T12:00:00+00:00', 1)",
+            [],
+        )
+        .unwrap();
+        drop(conn);
+
+        legacy_db(state, "one", &["a.ts", "b.ts"]);
+        let conn = open_tracker_db_read_only(state).unwrap().unwrap();
+        let (start, total): (String, i64) = conn
+            .query_row(
+                "SELECT start_time, total_files FROM sessions WHERE session_id = 'one'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(start, "2026-01-01T10:00:00+00:00");
+        assert_eq!(total, 3);
+    }
+
+    #[test]
+    fn test_read_only_open_adds_git_columns() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = temp_dir.path();
+        // A tracker.db from before the git columns
+        let conn = Connection::open(tracker_db_path(state)).unwrap();
+        conn.execute_batch(SCHEMA).unwrap();
+        conn.execute_batch(
+            "ALTER TABLE file_modifications DROP COLUMN git_branch;
+             ALTER TABLE file_modifications DROP COLUMN git_commit;
+             INSERT INTO file_modifications (session_id, file_path, tool, timestamp, category)
+             VALUES ('one', 'a.ts', 'Edit', '2026-01-01T10:30:00+00:00', 'other');",
+        )
+        .unwrap();
+        drop(conn);
+
+        let conn = open_tracker_db_read_only(state).unwrap().unwrap();
+        let rows = read_modifications(&conn, None).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].git_branch, None);
+        assert!(missing_columns(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_modifications_by_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let conn = open_tracker_db(temp_dir.path()).unwrap();
+        conn.execute_batch(
+            "INSERT INTO file_modifications (session_id, file_path, tool, timestamp, category)
+             VALUES ('one', 'a.ts', 'Edit', '2026-01-01T10:00:00+00:00', 'other'),
+                    ('two', 'b.ts', 'Edit', '2026-01-01T10:01:00+00:00', 'other'),
+                    ('one', 'c.ts', 'Edit', '2026-01-01T10:02:00+00:00', 'other');",
+        )
+        .unwrap();
+
+        let paths = |session| -> Vec<String> {
+            read_modifications(&conn, session)
+                .unwrap()
+                .into_iter()
+                .map(|row| row.file_path)
+                .collect()
+        };
+        assert_eq!(paths(None), ["a.ts", "b.ts", "c.ts"]);
+        assert_eq!(paths(Some("one")), ["a.ts", "c.ts"]);
+        assert!(paths(Some("three")).is_empty());
+    }
+
+    #[test]
+    fn test_sqlite_store_counts_sessions_and_prunes() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SqliteStore::open(temp_dir.path()).unwrap();
+        let edit = |file: &str, category: &str, timestamp: &str| ModificationRow {
+            category: category.to_string(),
+            ..ModificationRow::test_edit("one", file, timestamp)
+        };
+        store
+            .record(&edit("api/a.ts", "backend", "2026-03-01T10:00:00+00:00"))
+            .unwrap();
+        store
+            .record(&edit("ui/b.tsx", "frontend", "2026-03-02T10:00:00+00:00"))
+            .unwrap();
+
+        let (total, backend, frontend): (i64, i64, i64) = store
+            .conn
+            .query_row(
+                "SELECT total_files, backend_files, frontend_files FROM sessions",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!((total, backend, frontend), (2, 1, 1));
+
+        let recent = ModificationFilter {
+            since: Some("2026-03-02T00:00:00Z".parse().unwrap()),
+            ..ModificationFilter::default()
+        };
+        let rows = store.query(&recent).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].file_path, "ui/b.tsx");
+
+        assert_eq!(store.prune(recent.since.unwrap()).unwrap(), 1);
+        assert_eq!(
+            store.query(&ModificationFilter::default()).unwrap().len(),
+            1
+        );
+    }
+}
@@ -1,6 +1,7 @@
 // Core data structures for the Catalyst CLI
 // Phase 0.1: Complete type definitions for all commands
 
+use crate::modification_store::TrackerBackend;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use thiserror::Error;
@@ -50,6 +51,21 @@ pub enum CatalystError {
     #[error("Binary not found: {0}")]
     BinaryNotFound(String),
 
+    #[error("Build failed: {0}")]
+    BuildFailed(String),
+
+    #[error("Self-update failed: {0}")]
+    SelfUpdateFailed(String),
+
+    #[error("Session database error: {0}")]
+    SessionDatabase(String),
+
+    #[error("Analytics push failed: {0}")]
+    AnalyticsPush(String),
+
+    #[error("Skill registry error: {0}")]
+    RegistryFailed(String),
+
     #[error("Required binaries not installed. Please run: {install_command}\n\nMissing: {missing_binaries}")]
     BinariesNotInstalled {
         install_command: String,
@@ -62,7 +78,16 @@ pub enum CatalystError {
     #[error("Skill installation failed: {0}")]
     SkillInstallationFailed(String),
 
-    #[error("Initialization already in progress (PID {pid}). If this is stale, remove the lock file at: {lock_file}")]
+    #[error("Requested skills failed to install (strict skills mode): {failed}")]
+    StrictSkillsFailed {
+        failed: String,
+        /// What init did before failing; rolled back in abort mode
+        report: Box<InitReport>,
+    },
+
+    #[error(
+        "Another catalyst command is changing this project (PID {pid}, lock file: {lock_file})"
+    )]
     InitInProgress { pid: u32, lock_file: String },
 
     #[error("Unsupported platform: {0}")]
@@ -71,15 +96,90 @@ pub enum CatalystError {
     #[error("Hash mismatch: {0}")]
     HashMismatch(String),
 
+    #[error("Signature verification failed: {0}")]
+    VerificationFailed(String),
+
+    #[error("'{0}' is not signed by a trusted key (registry.trusted_keys); pass --allow-unverified to install it anyway")]
+    Unverified(String),
+
     #[error("Version mismatch: expected {expected}, found {found}")]
     VersionMismatch { expected: String, found: String },
 
     #[error("Path traversal detected: {0}")]
     PathTraversalDetected(String),
+
+    #[error("A previous update was interrupted (journal: {journal}). Run 'catalyst update --resume' to finish it or 'catalyst update --rollback' to undo it")]
+    UpdateInterrupted { journal: PathBuf },
+
+    #[error("Operation cancelled")]
+    Cancelled,
 }
 
 pub type Result<T> = std::result::Result<T, CatalystError>;
 
+// ============================================================================
+// Exit Codes
+// ============================================================================
+
+/// Process exit code of a `catalyst` command
+///
+/// Codes are part of the CLI's interface: scripts branch on them, so they
+/// must never be renumbered. Commands that check something (`status`,
+/// `sync`, `skills lint`, ...) exit with [`ExitCode::Warnings`] or
+/// [`ExitCode::Errors`] for what they found, and [`ExitCode::Failure`] only
+/// when the check itself couldn't run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// Success
+    Ok = 0,
+
+    /// The command failed for any reason not listed below
+    Failure = 1,
+
+    /// The command ran, and found warnings
+    Warnings = 2,
+
+    /// The command ran, and found errors
+    Errors = 3,
+
+    /// Invalid arguments, options, or configuration
+    Usage = 4,
+
+    /// Another catalyst process holds the project's lock
+    LockContention = 5,
+
+    /// Interrupted by Ctrl-C or SIGTERM (128 + SIGINT, as shells report it)
+    Interrupted = 130,
+}
+
+impl ExitCode {
+    /// The numeric code passed to `std::process::exit`
+    pub const fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Exit code for a check whose overall result is `level`
+    pub fn for_status(level: StatusLevel) -> Self {
+        match level {
+            StatusLevel::Ok => ExitCode::Ok,
+            StatusLevel::Warning => ExitCode::Warnings,
+            StatusLevel::Error => ExitCode::Errors,
+        }
+    }
+
+    /// Exit code for a command that failed with `error`
+    pub fn for_error(error: &CatalystError) -> Self {
+        match error {
+            CatalystError::InvalidConfig(_)
+            | CatalystError::InvalidPath(_)
+            | CatalystError::PathNotFound(_) => ExitCode::Usage,
+            CatalystError::InitInProgress { .. } => ExitCode::LockContention,
+            CatalystError::Cancelled => ExitCode::Interrupted,
+            _ => ExitCode::Failure,
+        }
+    }
+}
+
 // ============================================================================
 // Platform Detection
 // ============================================================================
@@ -127,6 +227,124 @@ impl Platform {
     }
 }
 
+/// PowerShell host that runs .ps1 hook wrappers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PowerShell {
+    /// PowerShell 7+ (`pwsh`), installed separately and cross-platform
+    Pwsh,
+
+    /// Windows PowerShell 5.1 (`powershell`), bundled with Windows
+    #[serde(rename = "powershell")]
+    WindowsPowerShell,
+}
+
+impl PowerShell {
+    /// Program name used to invoke the host
+    pub fn program(&self) -> &'static str {
+        match self {
+            PowerShell::Pwsh => "pwsh",
+            PowerShell::WindowsPowerShell => "powershell",
+        }
+    }
+}
+
+impl std::fmt::Display for PowerShell {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.program())
+    }
+}
+
+/// CPU architecture of a binary or machine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Arch {
+    X86_64,
+    Aarch64,
+}
+
+impl Arch {
+    /// Architecture as written in target triples
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Arch::X86_64 => "x86_64",
+            Arch::Aarch64 => "aarch64",
+        }
+    }
+}
+
+impl std::fmt::Display for Arch {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// How the Catalyst binaries were installed
+///
+/// Recorded in a marker file next to the binaries by each installer, and used
+/// to suggest the right reinstall or upgrade command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallMethod {
+    /// install.sh / install.ps1 or `catalyst install-binaries` from a Catalyst checkout
+    #[default]
+    Script,
+
+    /// `cargo install`
+    Cargo,
+
+    /// Homebrew formula
+    Homebrew,
+
+    /// Prebuilt release tarball
+    Tarball,
+}
+
+impl InstallMethod {
+    /// All install methods, in display order
+    pub const ALL: &'static [InstallMethod] = &[
+        InstallMethod::Script,
+        InstallMethod::Cargo,
+        InstallMethod::Homebrew,
+        InstallMethod::Tarball,
+    ];
+
+    /// Name used in the marker file and on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InstallMethod::Script => "script",
+            InstallMethod::Cargo => "cargo",
+            InstallMethod::Homebrew => "homebrew",
+            InstallMethod::Tarball => "tarball",
+        }
+    }
+}
+
+impl std::fmt::Display for InstallMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for InstallMethod {
+    type Err = CatalystError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.trim().to_ascii_lowercase();
+        let name = if name == "brew" { "homebrew" } else { &name };
+        InstallMethod::ALL
+            .iter()
+            .copied()
+            .find(|method| method.as_str() == name)
+            .ok_or_else(|| {
+                CatalystError::InvalidConfig(format!(
+                    "Unknown install method '{}'. Expected one of: script, cargo, homebrew, tarball",
+                    s.trim()
+                ))
+            })
+    }
+}
+
 // ============================================================================
 // Init Command Types
 // ============================================================================
@@ -139,6 +357,11 @@ pub struct InitConfig {
     /// Whether to install the file-change-tracker hook
     pub install_tracker: bool,
 
+    /// Whether to install the cargo-check hook (build and lint checks after
+    /// edits)
+    #[serde(default)]
+    pub install_check_hook: bool,
+
     /// List of skills to install (e.g., ["skill-developer", "backend-dev-guidelines"])
     pub skills: Vec<String>,
 
@@ -147,6 +370,116 @@ pub struct InitConfig {
 
     /// Directory to initialize (defaults to current directory)
     pub directory: PathBuf,
+
+    /// Plan the initialization without writing anything to disk
+    pub dry_run: bool,
+
+    /// Give regenerated files the default modes instead of keeping the
+    /// existing mode and ownership
+    pub reset_permissions: bool,
+
+    /// Skill library to link skills from instead of copying them into the
+    /// project (None copies them)
+    #[serde(default)]
+    pub skills_library: Option<PathBuf>,
+
+    /// Whether a skill that fails to install fails init
+    #[serde(default)]
+    pub strict_skills: StrictSkills,
+
+    /// Where `skills` came from, echoed in the report
+    #[serde(default)]
+    pub skill_selection: SkillSelection,
+
+    /// Extra top-level settings.json keys (e.g., `permissions` from an init
+    /// profile), written next to the hooks
+    #[serde(default)]
+    pub settings: serde_json::Map<String, serde_json::Value>,
+
+    /// Bring an existing install in line with this config instead of
+    /// skipping or overwriting what's there
+    #[serde(default)]
+    pub reconcile: bool,
+
+    /// Reproducing catalyst.lock: compare the result with it instead of
+    /// rewriting it
+    #[serde(default)]
+    pub from_lock: bool,
+
+    /// Store the tracker hook writes to, saved as `tracker.backend` in
+    /// catalyst.toml; None keeps the configured one
+    #[serde(default)]
+    pub tracker_backend: Option<TrackerBackend>,
+}
+
+/// Where init's skill list came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillSelection {
+    /// Picked explicitly (interactive prompt, `--all`, or the library API)
+    #[default]
+    Explicit,
+    /// `init.skills` in the project's catalyst.toml
+    ProjectConfig,
+    /// `init.skills` in the user config (`~/.claude/catalyst.toml`)
+    UserConfig,
+    /// [`DEFAULT_INIT_SKILLS`]
+    BuiltIn,
+    /// `--no-default-skills`: a bare install
+    NoDefaults,
+    /// The profile chosen with `--profile`
+    Profile,
+    /// The profiles of the project types init detected
+    Detected,
+    /// The skills recorded in catalyst.lock (`--from-lock`)
+    Lockfile,
+}
+
+impl SkillSelection {
+    /// Short note for reports, or None when the user chose the skills
+    pub fn note(self) -> Option<&'static str> {
+        match self {
+            Self::Explicit => None,
+            Self::ProjectConfig => Some("Skills from init.skills in catalyst.toml"),
+            Self::UserConfig => Some("Skills from init.skills in ~/.claude/catalyst.toml"),
+            Self::BuiltIn => Some("Default skills (set init.skills to change them)"),
+            Self::NoDefaults => Some("No skills installed (--no-default-skills)"),
+            Self::Profile => Some("Skills from the init profile (--profile)"),
+            Self::Detected => {
+                Some("Skills for the detected project type (set init.skills to change them)")
+            }
+            Self::Lockfile => Some("Skills from catalyst.lock (--from-lock)"),
+        }
+    }
+}
+
+/// What init does when a requested skill fails to install
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StrictSkills {
+    /// Warn and report success
+    #[default]
+    Off,
+    /// Finish the remaining phases, keep what was installed, then fail
+    Finish,
+    /// Stop at the skills phase and roll back everything init wrote
+    Abort,
+}
+
+impl std::str::FromStr for StrictSkills {
+    type Err = CatalystError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" | "false" => Ok(StrictSkills::Off),
+            "finish" | "true" => Ok(StrictSkills::Finish),
+            "abort" => Ok(StrictSkills::Abort),
+            _ => Err(CatalystError::InvalidConfig(format!(
+                "Unknown strict skills mode '{}'. Expected off, finish, or abort",
+                s.trim()
+            ))),
+        }
+    }
 }
 
 impl Default for InitConfig {
@@ -154,9 +487,19 @@ impl Default for InitConfig {
         Self {
             install_hooks: true,
             install_tracker: true,
+            install_check_hook: false,
             skills: Vec::new(),
             force: false,
             directory: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            dry_run: false,
+            reset_permissions: false,
+            skills_library: None,
+            strict_skills: StrictSkills::Off,
+            skill_selection: SkillSelection::Explicit,
+            settings: serde_json::Map::new(),
+            reconcile: false,
+            from_lock: false,
+            tracker_backend: None,
         }
     }
 }
@@ -183,6 +526,47 @@ pub struct InitReport {
 
     /// Any warnings or notes for the user
     pub warnings: Vec<String>,
+
+    /// Whether this report describes a dry run (nothing was written)
+    pub dry_run: bool,
+
+    /// Actions a dry run would perform, in order
+    pub planned_actions: Vec<String>,
+
+    /// Skill post-install scripts that were run or skipped
+    #[serde(default)]
+    pub scripts: Vec<ScriptResult>,
+
+    /// Requested skills that could not be installed
+    #[serde(default)]
+    pub failed_skills: Vec<FailedSkill>,
+
+    /// Where the requested skill list came from
+    #[serde(default)]
+    pub skill_selection: SkillSelection,
+
+    /// Files a reconcile run rewrote because they were out of date
+    #[serde(default)]
+    pub updated: Vec<String>,
+
+    /// Items a reconcile run found already correct
+    #[serde(default)]
+    pub unchanged: Vec<String>,
+
+    /// Whether this report describes a reconcile run
+    #[serde(default)]
+    pub reconciled: bool,
+
+    /// Whether catalyst.lock was written
+    #[serde(default)]
+    pub lockfile_written: bool,
+}
+
+/// A requested skill that init could not install
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailedSkill {
+    pub name: String,
+    pub error: String,
 }
 
 impl Default for InitReport {
@@ -201,6 +585,15 @@ impl InitReport {
             version_file_created: false,
             hashes_file_created: false,
             warnings: Vec::new(),
+            dry_run: false,
+            planned_actions: Vec::new(),
+            scripts: Vec::new(),
+            failed_skills: Vec::new(),
+            skill_selection: SkillSelection::Explicit,
+            updated: Vec::new(),
+            unchanged: Vec::new(),
+            reconciled: false,
+            lockfile_written: false,
         }
     }
 }
@@ -217,6 +610,10 @@ pub struct UpdateReport {
     /// Skills that were skipped because user modified them
     pub skipped_skills: Vec<SkippedSkill>,
 
+    /// Linked skills that were updated in the skill library
+    #[serde(default)]
+    pub library_skills: Vec<String>,
+
     /// Hooks that were updated
     pub updated_hooks: Vec<String>,
 
@@ -228,6 +625,10 @@ pub struct UpdateReport {
 
     /// Any errors that occurred
     pub errors: Vec<String>,
+
+    /// Skill post-install scripts that were run or skipped
+    #[serde(default)]
+    pub scripts: Vec<ScriptResult>,
 }
 
 impl Default for UpdateReport {
@@ -241,10 +642,12 @@ impl UpdateReport {
         Self {
             updated_skills: Vec::new(),
             skipped_skills: Vec::new(),
+            library_skills: Vec::new(),
             updated_hooks: Vec::new(),
             binary_updates_available: Vec::new(),
             success: true,
             errors: Vec::new(),
+            scripts: Vec::new(),
         }
     }
 }
@@ -264,6 +667,28 @@ pub struct SkippedSkill {
     pub expected_hash: String,
 }
 
+/// Outcome of a skill's post-install script (`install.sh` / `install.ps1`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptResult {
+    /// Skill the script belongs to
+    pub skill: String,
+
+    /// Script path
+    pub script: PathBuf,
+
+    /// Whether the script was run (false if declined or disabled)
+    pub ran: bool,
+
+    /// Whether the script ran and exited successfully
+    pub success: bool,
+
+    /// Exit code, if the script exited normally
+    pub exit_code: Option<i32>,
+
+    /// Combined stdout and stderr (truncated), or why the script was skipped
+    pub output: String,
+}
+
 // ============================================================================
 // Fix Command Types
 // ============================================================================
@@ -311,6 +736,14 @@ pub struct StatusReport {
     /// Hook statuses
     pub hooks: Vec<HookStatus>,
 
+    /// Hook commands that run project files that don't exist
+    #[serde(default)]
+    pub dangling_hooks: Vec<DanglingHook>,
+
+    /// Differences from catalyst.lock; empty without a lockfile
+    #[serde(default)]
+    pub lock_drift: Vec<LockDrift>,
+
     /// Skill statuses
     pub skills: Vec<SkillStatus>,
 
@@ -319,6 +752,26 @@ pub struct StatusReport {
 
     /// Whether .catalyst-version exists and matches
     pub version_status: VersionStatus,
+
+    /// Managed files with unusual permissions
+    #[serde(default)]
+    pub unusual_permissions: Vec<PermissionStatus>,
+
+    /// Hook wrappers whose SELinux type differs from the hooks directory
+    #[serde(default)]
+    pub mislabeled_hooks: Vec<LabelStatus>,
+
+    /// PowerShell checks; None unless the project uses .ps1 wrappers
+    #[serde(default)]
+    pub powershell: Option<PowerShellStatus>,
+
+    /// How the hook binaries were installed (drives suggested fixes)
+    #[serde(default)]
+    pub install_method: InstallMethod,
+
+    /// Architecture of this machine, if it could be detected
+    #[serde(default)]
+    pub host_arch: Option<Arch>,
 }
 
 impl Default for StatusReport {
@@ -333,13 +786,36 @@ impl StatusReport {
             level: StatusLevel::Ok,
             binaries: Vec::new(),
             hooks: Vec::new(),
+            dangling_hooks: Vec::new(),
+            lock_drift: Vec::new(),
             skills: Vec::new(),
             issues: Vec::new(),
             version_status: VersionStatus::Missing,
+            unusual_permissions: Vec::new(),
+            mislabeled_hooks: Vec::new(),
+            powershell: None,
+            install_method: InstallMethod::default(),
+            host_arch: None,
         }
     }
 }
 
+/// Result of `catalyst fix <issue-id>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetedFixReport {
+    /// Issue ID that was targeted (e.g., "CAT004")
+    pub issue_id: String,
+
+    /// Number of issues with that ID found by status
+    pub matched: usize,
+
+    /// Description of each fix applied
+    pub fixed: Vec<String>,
+
+    /// Issues whose fix failed, with the error
+    pub failed: Vec<String>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StatusLevel {
     /// Everything is working perfectly
@@ -366,10 +842,11 @@ pub struct BinaryStatus {
     /// Binary version (if detectable)
     pub version: Option<String>,
 
-    /// Expected version (from embedded resources or latest release)
+    /// Version of the CLI the binary is checked against
     pub expected_version: Option<String>,
 
-    /// Whether version matches expected
+    /// Whether the version is compatible with the expected version (see
+    /// [`crate::binaries::version_compatible`]); false when unknown
     pub version_matches: bool,
 
     /// Full path to binary
@@ -378,6 +855,11 @@ pub struct BinaryStatus {
     /// Variant of the binary (for file-change-tracker: "sqlite" or "basic")
     /// None for binaries that don't have variants
     pub variant: Option<String>,
+
+    /// Architectures the binary was built for (more than one for macOS
+    /// universal binaries; empty if unknown)
+    #[serde(default)]
+    pub arches: Vec<Arch>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -404,6 +886,200 @@ pub struct HookStatus {
     pub calls_correct_binary: bool,
 }
 
+/// A settings.json hook command whose project file doesn't exist
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DanglingHook {
+    /// Event the hook is registered for (e.g., "PostToolUse")
+    pub event: String,
+
+    /// Command as written in settings.json
+    pub command: String,
+
+    /// The missing path as written (e.g., "$CLAUDE_PROJECT_DIR/.claude/hooks/x.sh")
+    pub reference: String,
+
+    /// Where the reference resolves in this project
+    pub path: PathBuf,
+
+    /// Command `catalyst status --fix` would write instead, when a file
+    /// with a close name exists next to the missing one
+    pub fixed_command: Option<String>,
+}
+
+/// Kind of component recorded in catalyst.lock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockedComponent {
+    Skill,
+    Wrapper,
+    Binary,
+}
+
+impl std::fmt::Display for LockedComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            LockedComponent::Skill => "skill",
+            LockedComponent::Wrapper => "wrapper",
+            LockedComponent::Binary => "binary",
+        })
+    }
+}
+
+/// A difference between catalyst.lock and the installed components
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockDrift {
+    pub kind: LockedComponent,
+
+    /// Skill name, wrapper file name, or binary name
+    pub name: String,
+
+    /// Hash or version in catalyst.lock; None if it isn't locked
+    pub locked: Option<String>,
+
+    /// Hash or version installed; None if it's missing
+    pub installed: Option<String>,
+}
+
+impl std::fmt::Display for LockDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.locked, &self.installed) {
+            (Some(_), None) => write!(
+                f,
+                "{} {} is in {} but not installed",
+                self.kind, self.name, LOCK_FILE
+            ),
+            (None, _) => write!(
+                f,
+                "{} {} is installed but not in {}",
+                self.kind, self.name, LOCK_FILE
+            ),
+            (Some(locked), Some(installed)) => write!(
+                f,
+                "{} {} differs from {} (locked {}, installed {})",
+                self.kind, self.name, LOCK_FILE, locked, installed
+            ),
+        }
+    }
+}
+
+/// A managed file whose mode looks wrong (reported, never changed)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionStatus {
+    /// Path of the file
+    pub path: PathBuf,
+
+    /// Permission bits (e.g., 0o666)
+    pub mode: u32,
+
+    /// What is unusual about the mode (e.g., "world-writable")
+    pub reason: String,
+}
+
+/// A hook wrapper whose SELinux context doesn't match the hooks directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelStatus {
+    /// Path of the wrapper
+    pub path: PathBuf,
+
+    /// Current context (e.g., "unconfined_u:object_r:user_tmp_t:s0")
+    pub context: String,
+
+    /// Context of the hooks directory
+    pub expected: String,
+}
+
+/// Why a .ps1 wrapper's settings.json command won't run reliably
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PowerShellProblem {
+    /// The command runs the .ps1 file by path instead of through a host
+    NoHost,
+
+    /// The command invokes a host that isn't on PATH
+    HostNotInstalled { host: PowerShell },
+
+    /// The command invokes a different host than `wrappers.powershell`
+    WrongHost {
+        host: PowerShell,
+        configured: PowerShell,
+    },
+
+    /// The command doesn't pass `-ExecutionPolicy Bypass`
+    NoPolicyBypass,
+}
+
+impl PowerShellProblem {
+    /// Whether the hook fails outright, rather than only under some setups
+    pub fn is_blocking(&self) -> bool {
+        matches!(
+            self,
+            PowerShellProblem::NoHost | PowerShellProblem::HostNotInstalled { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for PowerShellProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PowerShellProblem::NoHost => {
+                write!(
+                    f,
+                    "runs the script by path, which Claude Code can't execute"
+                )
+            }
+            PowerShellProblem::HostNotInstalled { host } => {
+                write!(f, "invokes {}, which isn't on PATH", host)
+            }
+            PowerShellProblem::WrongHost { host, configured } => write!(
+                f,
+                "invokes {} but catalyst.toml sets wrappers.powershell = \"{}\"",
+                host, configured
+            ),
+            PowerShellProblem::NoPolicyBypass => write!(
+                f,
+                "doesn't pass -ExecutionPolicy Bypass, so the execution policy can block it"
+            ),
+        }
+    }
+}
+
+/// PowerShell hosts and hook invocations, checked when wrappers are .ps1
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerShellStatus {
+    /// Hosts found on PATH, in order of preference
+    pub installed: Vec<PowerShell>,
+
+    /// Host hook commands should invoke (`wrappers.powershell`, or the
+    /// preferred installed host)
+    pub host: Option<PowerShell>,
+
+    /// Execution policy forced by Group Policy that blocks the wrappers
+    /// even with `-ExecutionPolicy Bypass` (e.g., "AllSigned")
+    pub enforced_policy: Option<String>,
+
+    /// settings.json hook commands that won't run the wrapper reliably
+    pub commands: Vec<PowerShellCommandStatus>,
+}
+
+/// A settings.json command for a .ps1 wrapper that needs rewriting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerShellCommandStatus {
+    /// Wrapper file name (e.g., "skill-activation-prompt.ps1")
+    pub wrapper: String,
+
+    /// Hook event the command is registered under
+    pub event: String,
+
+    /// Command as written in settings.json
+    pub command: String,
+
+    /// What is wrong with it
+    pub problem: PowerShellProblem,
+
+    /// Command `catalyst status --fix` would write instead
+    pub expected: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillStatus {
     /// Skill name (e.g., "skill-developer")
@@ -427,12 +1103,34 @@ pub struct SkillStatus {
     /// Whether the skill has been modified by user
     pub modified: bool,
 
+    /// Files (relative to .claude/skills) that were changed or removed
+    /// since install
+    #[serde(default)]
+    pub modified_files: Vec<String>,
+
     /// Full path to skill directory
     pub path: Option<PathBuf>,
+
+    /// Result of the skill's own health check (None if it declares none)
+    #[serde(default)]
+    pub health: Option<SkillHealth>,
+}
+
+/// Outcome of a skill-defined health check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillHealth {
+    /// Whether every check passed
+    pub healthy: bool,
+
+    /// What failed, one entry per failed check
+    pub failures: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Issue {
+    /// Stable issue kind (see `catalyst explain-issue <id>`)
+    pub kind: IssueKind,
+
     /// Issue severity
     pub severity: IssueSeverity,
 
@@ -449,6 +1147,177 @@ pub struct Issue {
     pub suggested_fix: Option<String>,
 }
 
+/// Stable identifier for each kind of issue reported by `catalyst status`
+///
+/// IDs are part of the public interface: they are printed next to issues,
+/// accepted by `catalyst explain-issue` and `catalyst fix`, and must never
+/// be renumbered. JSON output uses the ID too, so `status --display json`
+/// can be fed straight back to `fix`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IssueKind {
+    /// settings.json exists but could not be parsed
+    SettingsParseError,
+
+    /// A hook binary is missing from ~/.claude-hooks/bin
+    BinaryMissing,
+
+    /// A hook binary exists but is not executable
+    BinaryNotExecutable,
+
+    /// A hook wrapper script is missing from .claude/hooks
+    HookWrapperMissing,
+
+    /// A hook wrapper script is not executable
+    HookWrapperNotExecutable,
+
+    /// A hook wrapper cannot reach the binary it calls
+    HookBinaryUnreachable,
+
+    /// A skill directory has no SKILL.md
+    SkillMissingMainFile,
+
+    /// A skill directory is not registered in skill-rules.json
+    SkillUnregistered,
+
+    /// .catalyst-version is missing
+    VersionFileMissing,
+
+    /// .catalyst-version does not match the running CLI
+    VersionMismatch,
+
+    /// A Catalyst hook wrapper is installed but not registered in settings.json
+    HookEntryMissing,
+
+    /// A managed file has unusual permissions (e.g., world-writable)
+    UnusualPermissions,
+
+    /// A hook wrapper has a different SELinux type than the hooks directory
+    HookMislabeled,
+
+    /// A skill's own health check failed
+    SkillUnhealthy,
+
+    /// An external validator from catalyst.toml reported an issue
+    ValidatorIssue,
+
+    /// An external validator could not be run or printed an invalid report
+    ValidatorFailed,
+
+    /// An installed skill was deprecated or renamed upstream
+    DeprecatedSkill,
+
+    /// .ps1 hooks can't run: no PowerShell host, a blocking execution
+    /// policy, or a settings.json command that doesn't invoke PowerShell
+    /// correctly
+    PowerShellInvocation,
+
+    /// An installed hook binary was built for a different CPU architecture
+    BinaryArchMismatch,
+
+    /// An installed skill's files differ from the hashes recorded at install
+    SkillModified,
+
+    /// A settings.json hook command runs a project file that doesn't exist
+    HookCommandDangling,
+
+    /// An installed hook binary reports a version this CLI doesn't support
+    BinaryVersionMismatch,
+
+    /// Installed skills, wrappers, or binaries differ from catalyst.lock
+    LockfileDrift,
+}
+
+impl IssueKind {
+    /// All issue kinds, in ID order
+    pub const ALL: &'static [IssueKind] = &[
+        IssueKind::SettingsParseError,
+        IssueKind::BinaryMissing,
+        IssueKind::BinaryNotExecutable,
+        IssueKind::HookWrapperMissing,
+        IssueKind::HookWrapperNotExecutable,
+        IssueKind::HookBinaryUnreachable,
+        IssueKind::SkillMissingMainFile,
+        IssueKind::SkillUnregistered,
+        IssueKind::VersionFileMissing,
+        IssueKind::VersionMismatch,
+        IssueKind::HookEntryMissing,
+        IssueKind::UnusualPermissions,
+        IssueKind::HookMislabeled,
+        IssueKind::SkillUnhealthy,
+        IssueKind::ValidatorIssue,
+        IssueKind::ValidatorFailed,
+        IssueKind::DeprecatedSkill,
+        IssueKind::PowerShellInvocation,
+        IssueKind::BinaryArchMismatch,
+        IssueKind::SkillModified,
+        IssueKind::HookCommandDangling,
+        IssueKind::BinaryVersionMismatch,
+        IssueKind::LockfileDrift,
+    ];
+
+    /// Stable issue ID (e.g., "CAT001")
+    pub fn id(&self) -> &'static str {
+        match self {
+            IssueKind::SettingsParseError => "CAT001",
+            IssueKind::BinaryMissing => "CAT002",
+            IssueKind::BinaryNotExecutable => "CAT003",
+            IssueKind::HookWrapperMissing => "CAT004",
+            IssueKind::HookWrapperNotExecutable => "CAT005",
+            IssueKind::HookBinaryUnreachable => "CAT006",
+            IssueKind::SkillMissingMainFile => "CAT007",
+            IssueKind::SkillUnregistered => "CAT008",
+            IssueKind::VersionFileMissing => "CAT009",
+            IssueKind::VersionMismatch => "CAT010",
+            IssueKind::HookEntryMissing => "CAT011",
+            IssueKind::UnusualPermissions => "CAT012",
+            IssueKind::HookMislabeled => "CAT013",
+            IssueKind::SkillUnhealthy => "CAT014",
+            IssueKind::ValidatorIssue => "CAT015",
+            IssueKind::ValidatorFailed => "CAT016",
+            IssueKind::DeprecatedSkill => "CAT017",
+            IssueKind::PowerShellInvocation => "CAT018",
+            IssueKind::BinaryArchMismatch => "CAT019",
+            IssueKind::SkillModified => "CAT020",
+            IssueKind::HookCommandDangling => "CAT021",
+            IssueKind::BinaryVersionMismatch => "CAT022",
+            IssueKind::LockfileDrift => "CAT023",
+        }
+    }
+
+    /// Look up an issue kind by its ID (case-insensitive)
+    pub fn from_id(id: &str) -> Option<IssueKind> {
+        IssueKind::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.id().eq_ignore_ascii_case(id.trim()))
+    }
+}
+
+impl Serialize for IssueKind {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.id())
+    }
+}
+
+impl<'de> Deserialize<'de> for IssueKind {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        IssueKind::from_id(&id)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown issue ID '{}'", id)))
+    }
+}
+
+impl std::fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IssueSeverity {
     /// Critical issue, feature is broken
@@ -497,6 +1366,24 @@ pub struct Hook {
 // Skill Rules Types
 // ============================================================================
 
+/// Activation score added by each distinct keyword found in a prompt
+pub const ACTIVATION_KEYWORD_WEIGHT: u32 = 1;
+
+/// Activation score added by each matching intent pattern
+///
+/// Intent patterns describe what the user is trying to do, so one match is
+/// stronger evidence than a keyword that may appear in passing.
+pub const ACTIVATION_INTENT_WEIGHT: u32 = 2;
+
+/// Activation score added when a file the session recently worked on
+/// matches a rule's `fileTriggers.pathPatterns`
+///
+/// Added once however many files match.
+pub const ACTIVATION_PATH_WEIGHT: u32 = 1;
+
+/// Activation score a skill needs when its rule doesn't set `minScore`
+pub const DEFAULT_ACTIVATION_MIN_SCORE: u32 = 1;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillRules {
     pub skills: Vec<SkillRule>,
@@ -567,40 +1454,31 @@ pub const AVAILABLE_SKILLS: &[&str] = &[
     "rust-developer",
 ];
 
-/// Skills with descriptions for interactive mode
-/// Each tuple contains (skill_id, description)
-pub const AVAILABLE_SKILLS_WITH_DESC: &[(&str, &str)] = &[
-    (
-        "skill-developer",
-        "Meta-skill for creating custom skills (framework-agnostic)",
-    ),
-    (
-        "backend-dev-guidelines",
-        "Node.js/Express/Prisma backend development patterns",
-    ),
-    (
-        "frontend-dev-guidelines",
-        "React/MUI v7/TanStack frontend development patterns",
-    ),
-    (
-        "route-tester",
-        "JWT cookie-based authentication route testing",
-    ),
-    (
-        "error-tracking",
-        "Sentry v8 error tracking and performance monitoring",
-    ),
-    (
-        "rust-developer",
-        "Rust development best practices and patterns",
-    ),
-];
+/// Skills non-interactive init installs when no config names any
+pub const DEFAULT_INIT_SKILLS: &[&str] = &["skill-developer"];
+
+/// A shipped skill that was retired or renamed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DeprecatedSkill {
+    /// ID the skill was installed under
+    pub id: &'static str,
+
+    /// Skill that replaces it, if it was renamed
+    pub replaced_by: Option<&'static str>,
 
-// Compile-time assertion to ensure skill arrays stay synchronized
-const _: () = assert!(
-    AVAILABLE_SKILLS.len() == AVAILABLE_SKILLS_WITH_DESC.len(),
-    "AVAILABLE_SKILLS and AVAILABLE_SKILLS_WITH_DESC must have same length"
-);
+    /// Catalyst version that deprecated it
+    pub since: &'static str,
+
+    /// Release-note text shown with the warning
+    pub note: &'static str,
+}
+
+/// Skills removed from AVAILABLE_SKILLS that projects may still have installed
+///
+/// Entries are never removed, so projects that skip several releases can
+/// still be migrated. A renamed skill's `replaced_by` must be in
+/// AVAILABLE_SKILLS.
+pub const DEPRECATED_SKILLS: &[DeprecatedSkill] = &[];
 
 /// Default directory structure
 pub const CLAUDE_DIR: &str = ".claude";
@@ -614,6 +1492,81 @@ pub const SETTINGS_FILE: &str = ".claude/settings.json";
 pub const SKILL_RULES_FILE: &str = ".claude/skills/skill-rules.json";
 pub const VERSION_FILE: &str = ".catalyst-version";
 pub const HASHES_FILE: &str = ".catalyst-hashes.json";
+pub const UPDATE_JOURNAL_FILE: &str = ".catalyst-update-journal.json";
+
+/// Record of the installed components, committed with the project
+pub const LOCK_FILE: &str = "catalyst.lock";
+
+/// Optional per-skill manifest, relative to the skill directory
+pub const SKILL_MANIFEST_FILE: &str = catalyst_core::skill_manifest::MANIFEST_FILE;
+
+/// Per-project configuration (root file takes precedence)
+pub const CONFIG_FILE: &str = "catalyst.toml";
+pub const CONFIG_FILE_CLAUDE: &str = ".claude/catalyst.toml";
+
+/// Machine-wide skill library, relative to the home directory
+pub const SKILLS_LIBRARY_DIR: &str = ".claude/skills-library";
+
+/// Backup sets created before overwriting installed files
+pub const BACKUP_DIR: &str = ".catalyst-backups";
+
+/// Where file-change-tracker keeps its session databases, relative to ~/.claude
+pub const TRACKER_STATE_DIR: &str = "hooks-state-rust";
 
 /// Binary installation directory
 pub const BINARY_DIR: &str = ".claude-hooks/bin";
+
+/// Marker written next to the binaries recording how they were installed
+pub const INSTALL_METHOD_FILE: &str = ".install-method";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code_numbers_are_stable() {
+        // Scripts match on these; changing one is a breaking change
+        assert_eq!(ExitCode::Ok.code(), 0);
+        assert_eq!(ExitCode::Failure.code(), 1);
+        assert_eq!(ExitCode::Warnings.code(), 2);
+        assert_eq!(ExitCode::Errors.code(), 3);
+        assert_eq!(ExitCode::Usage.code(), 4);
+        assert_eq!(ExitCode::LockContention.code(), 5);
+        assert_eq!(ExitCode::Interrupted.code(), 130);
+    }
+
+    #[test]
+    fn test_exit_code_for_status() {
+        assert_eq!(ExitCode::for_status(StatusLevel::Ok), ExitCode::Ok);
+        assert_eq!(
+            ExitCode::for_status(StatusLevel::Warning),
+            ExitCode::Warnings
+        );
+        assert_eq!(ExitCode::for_status(StatusLevel::Error), ExitCode::Errors);
+    }
+
+    #[test]
+    fn test_exit_code_for_error() {
+        let usage = [
+            CatalystError::InvalidConfig("bad".to_string()),
+            CatalystError::InvalidPath("../x".to_string()),
+            CatalystError::PathNotFound(PathBuf::from("/missing")),
+        ];
+        for error in &usage {
+            assert_eq!(ExitCode::for_error(error), ExitCode::Usage, "{error}");
+        }
+
+        let locked = CatalystError::InitInProgress {
+            pid: 42,
+            lock_file: ".catalyst-operation.lock".to_string(),
+        };
+        assert_eq!(ExitCode::for_error(&locked), ExitCode::LockContention);
+        assert_eq!(
+            ExitCode::for_error(&CatalystError::Cancelled),
+            ExitCode::Interrupted
+        );
+
+        let io = CatalystError::Io(std::io::Error::other("disk full"));
+        assert_eq!(ExitCode::for_error(&io), ExitCode::Failure);
+    }
+}
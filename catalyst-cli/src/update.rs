@@ -3,15 +3,32 @@
 //! This module handles the `catalyst update` command, which updates an existing
 //! Catalyst installation while preserving user customizations.
 
-use crate::init::{generate_wrapper_scripts, read_version_file, write_version_file};
+use crate::backup::{backup_path, create_backup_set, remove_backup_set, restore_path};
+use crate::cancel;
+use crate::config::CatalystConfig;
+use crate::init::{self, generate_wrapper_scripts, read_version_file, write_version_file};
+pub use crate::journal::UpdateJournal;
+use crate::journal::{StepStatus, UpdateStep};
+use crate::library;
+use crate::lock::OperationLock;
+use crate::lockfile::{self, Lockfile};
+use crate::progress;
+use crate::safe_path;
+use crate::self_update;
+use crate::signals;
+use crate::template::{self, TemplateVars};
+use crate::timings;
 use crate::types::{
     CatalystError, CatalystHashes, Platform, Result, SkippedSkill, UpdateReport, CATALYST_VERSION,
-    HASHES_FILE, SKILLS_DIR,
+    HASHES_FILE, HOOKS_DIR, LOCK_FILE, SKILLS_DIR,
 };
+use catalyst_core::fs_ops::{Fs, PermissionPolicy};
+use catalyst_core::skill_manifest::SkillManifest;
 use include_dir::{include_dir, Dir};
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // Embed skills directory at compile time (same as in init.rs)
 static SKILLS: Dir = include_dir!("$CARGO_MANIFEST_DIR/../.claude/skills");
@@ -20,9 +37,11 @@ static SKILLS: Dir = include_dir!("$CARGO_MANIFEST_DIR/../.claude/skills");
 ///
 /// This function:
 /// 1. Checks the installed version
-/// 2. Updates wrapper scripts (graceful - continues on error)
-/// 3. Updates skills with hash-based modification detection (graceful)
-/// 4. Writes new version file (FATAL - fails entire update if unsuccessful)
+/// 2. Plans the update and records it in a journal (see [`crate::journal`])
+/// 3. Updates wrapper scripts (graceful - continues on error)
+/// 4. Updates skills with hash-based modification detection (graceful)
+/// 5. Writes new version file (FATAL - fails entire update if unsuccessful)
+/// 6. Updates skills linked from the skill library, in the library (graceful)
 ///
 /// # Arguments
 ///
@@ -33,6 +52,11 @@ static SKILLS: Dir = include_dir!("$CARGO_MANIFEST_DIR/../.claude/skills");
 ///
 /// Returns an `UpdateReport` with details of what was updated
 ///
+/// # Errors
+///
+/// Returns `UpdateInterrupted` if a previous update left an unfinished
+/// journal behind. Use [`resume_update`] or [`rollback_update`] first.
+///
 /// # Error Recovery Strategy
 ///
 /// Wrapper script and skill updates use graceful degradation - they continue
@@ -41,7 +65,20 @@ static SKILLS: Dir = include_dir!("$CARGO_MANIFEST_DIR/../.claude/skills");
 /// - If it fails to update, subsequent `update` commands will be confused
 /// - Users would experience confusing repeated update attempts
 /// - Better to fail loudly than enter an inconsistent state
+///
+/// Every modified path is backed up before it is touched, and the journal
+/// is only removed once all steps complete, so an interrupted update can
+/// always be resumed or rolled back. Like init, it holds the project's
+/// `.catalyst-operation.lock` throughout, so it fails with `InitInProgress` while
+/// another init or update is running.
 pub fn update(target_dir: &Path, force: bool) -> Result<UpdateReport> {
+    let _lock = OperationLock::acquire(target_dir)?;
+    if UpdateJournal::load(target_dir)?.is_some() {
+        return Err(CatalystError::UpdateInterrupted {
+            journal: UpdateJournal::path(target_dir),
+        });
+    }
+
     let mut report = UpdateReport::new();
 
     // Read installed version
@@ -61,43 +98,440 @@ pub fn update(target_dir: &Path, force: bool) -> Result<UpdateReport> {
         return Ok(report);
     }
 
-    // Phase 6.2: Update wrapper scripts (graceful degradation)
-    let platform = Platform::detect();
-    match generate_wrapper_scripts(target_dir, true, true, platform) {
-        Ok(hooks) => {
-            report.updated_hooks = hooks;
-        }
+    // Plan the update before touching anything
+    let (skills_to_update, skipped) = match plan_skill_updates(target_dir, force) {
+        Ok(plan) => plan,
         Err(e) => {
-            let error = format!("Failed to update wrapper scripts: {}", e);
+            let error = format!("Failed to update skills: {}", e);
             report.errors.push(error.clone());
             report.success = false;
-            eprintln!("⚠️  {}", error);
+            progress::warn(error.as_str());
+            (Vec::new(), Vec::new())
         }
+    };
+
+    let mut steps = vec![UpdateStep::Wrappers];
+    steps.extend(
+        skills_to_update
+            .into_iter()
+            .map(|name| UpdateStep::Skill { name }),
+    );
+    steps.push(UpdateStep::Hashes);
+    steps.push(UpdateStep::VersionFile);
+
+    let backup_dir = create_backup_set(target_dir, "update")?;
+    let mut journal = UpdateJournal::new(
+        installed_version,
+        CATALYST_VERSION.to_string(),
+        backup_dir,
+        steps,
+    );
+    journal.skipped_skills = skipped;
+    journal.save(target_dir)?;
+
+    let mut report = run_journal(target_dir, &mut journal, report)?;
+    update_library_skills(target_dir, force, &mut report);
+    refresh_lockfile(target_dir, &mut report);
+    Ok(report)
+}
+
+/// Update a project without blocking an async runtime
+///
+/// Runs [`update`] on tokio's blocking pool. Cancelling `cancel` stops the
+/// update at the next step or skill file: the step in progress is restored
+/// from its backup, finished steps are kept, and the journal is left for
+/// `catalyst update --resume` or `--rollback`.
+///
+/// # Errors
+///
+/// Returns `Cancelled` if `cancel` was cancelled before the update
+/// finished, and otherwise the errors of [`update`]
+#[cfg(feature = "tokio")]
+pub async fn update_async(
+    target_dir: PathBuf,
+    force: bool,
+    cancel: tokio_util::sync::CancellationToken,
+) -> Result<UpdateReport> {
+    cancel::run_blocking(cancel, move || update(&target_dir, force)).await
+}
+
+/// Update only the named skills (`catalyst update --skill`)
+///
+/// Each skill is replaced with the copy embedded in this CLI when that copy
+/// has a newer version, or always with `force`. Locally modified skills are
+/// skipped unless `force` is set, as in [`update`]. Hook wrappers and
+/// `.catalyst-version` are left alone. The update is journaled like a full
+/// one, so it can be resumed or rolled back the same way.
+///
+/// # Arguments
+///
+/// * `target_dir` - Directory where Catalyst is installed
+/// * `skill_ids` - Skills to update
+/// * `force` - Whether to overwrite modified skills and reinstall skills
+///   that are already up to date
+///
+/// # Errors
+///
+/// Returns `SkillInstallationFailed` if a skill isn't one Catalyst ships,
+/// `InvalidConfig` if it isn't installed or is linked from the skill
+/// library, and `UpdateInterrupted` if an earlier update is unfinished
+pub fn update_skills(target_dir: &Path, skill_ids: &[String], force: bool) -> Result<UpdateReport> {
+    let _lock = OperationLock::acquire(target_dir)?;
+    if UpdateJournal::load(target_dir)?.is_some() {
+        return Err(CatalystError::UpdateInterrupted {
+            journal: UpdateJournal::path(target_dir),
+        });
     }
 
-    // Phase 6.3: Update skills with hash-based detection (graceful degradation)
-    match update_skills(target_dir, force) {
-        Ok((updated, skipped)) => {
-            report.updated_skills = updated;
-            report.skipped_skills = skipped;
+    let stored_hashes = load_stored_hashes(target_dir)?;
+    let skills_dir = target_dir.join(SKILLS_DIR);
+    let mut to_update = Vec::new();
+    let mut skipped = Vec::new();
+    for skill_id in skill_ids {
+        safe_path::validate_skill_id(skill_id)?;
+        if SKILLS.get_dir(skill_id).is_none() {
+            return Err(CatalystError::SkillInstallationFailed(format!(
+                "Unknown skill: {}",
+                skill_id
+            )));
+        }
+        let skill_dir = skills_dir.join(skill_id);
+        if !skill_dir.is_dir() {
+            return Err(CatalystError::InvalidConfig(format!(
+                "Skill '{}' is not installed",
+                skill_id
+            )));
+        }
+        if library::is_linked(target_dir, skill_id) {
+            return Err(CatalystError::InvalidConfig(format!(
+                "Skill '{}' is linked from the skill library; 'catalyst update' updates it there",
+                skill_id
+            )));
+        }
+
+        let expected_hash = stored_hashes
+            .as_ref()
+            .and_then(|hashes| hashes.skills.get(skill_id))
+            .filter(|_| !force);
+        if let Some(expected_hash) = expected_hash {
+            let current_hash = compute_file_hash(&skill_dir.join("SKILL.md"))?;
+            if current_hash != *expected_hash {
+                skipped.push(SkippedSkill {
+                    name: skill_id.clone(),
+                    reason: "Modified locally".to_string(),
+                    current_hash,
+                    expected_hash: expected_hash.clone(),
+                });
+                continue;
+            }
         }
-        Err(e) => {
-            let error = format!("Failed to update skills: {}", e);
+
+        if (force || shipped_is_newer(&skill_dir, skill_id)) && !to_update.contains(skill_id) {
+            to_update.push(skill_id.clone());
+        }
+    }
+
+    let mut report = UpdateReport::new();
+    if to_update.is_empty() {
+        report.skipped_skills = skipped;
+        return Ok(report);
+    }
+
+    let installed_version =
+        read_version_file(target_dir)?.unwrap_or_else(|| CATALYST_VERSION.to_string());
+    let mut steps: Vec<UpdateStep> = to_update
+        .into_iter()
+        .map(|name| UpdateStep::Skill { name })
+        .collect();
+    steps.push(UpdateStep::Hashes);
+
+    let backup_dir = create_backup_set(target_dir, "update")?;
+    let mut journal = UpdateJournal::new(
+        installed_version.clone(),
+        installed_version,
+        backup_dir,
+        steps,
+    );
+    journal.skipped_skills = skipped;
+    journal.save(target_dir)?;
+
+    report = run_journal(target_dir, &mut journal, report)?;
+    refresh_lockfile(target_dir, &mut report);
+    Ok(report)
+}
+
+/// Rewrite catalyst.lock, if the project has one, to match the updated
+/// install (graceful degradation)
+fn refresh_lockfile(target_dir: &Path, report: &mut UpdateReport) {
+    if !target_dir.join(LOCK_FILE).is_file() {
+        return;
+    }
+    let result = Lockfile::capture(&Fs::read_only(), target_dir, Platform::detect())
+        .and_then(|lock| lockfile::write(target_dir, &lock));
+    if let Err(e) = result {
+        let error = format!("Failed to update {}: {}", LOCK_FILE, e);
+        progress::warn(error.as_str());
+        report.errors.push(error);
+        report.success = false;
+    }
+}
+
+/// Update skills linked from the skill library (graceful degradation)
+///
+/// Linked skills are updated once in the library rather than per project,
+/// and changes there are not journaled: other projects share them.
+fn update_library_skills(target_dir: &Path, force: bool, report: &mut UpdateReport) {
+    let _span = timings::span("update library skills");
+
+    // Links normally all point into the same library
+    let mut by_library: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    for (skill, library) in library::linked_skills(target_dir) {
+        by_library.entry(library).or_default().push(skill);
+    }
+
+    for (library, skills) in by_library {
+        match library::update_skills(&library, &skills, force) {
+            Ok((updated, skipped)) => {
+                report.library_skills.extend(updated);
+                report.skipped_skills.extend(skipped);
+            }
+            Err(e) => {
+                let error = format!(
+                    "Failed to update skill library {}: {}",
+                    library.display(),
+                    e
+                );
+                report.errors.push(error.clone());
+                report.success = false;
+                progress::warn(error.as_str());
+            }
+        }
+    }
+}
+
+/// Resume an interrupted update from its journal
+///
+/// Completed steps are skipped; any step that was in progress is re-run
+/// from the start.
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if there is no interrupted update to resume
+pub fn resume_update(target_dir: &Path) -> Result<UpdateReport> {
+    let _lock = OperationLock::acquire(target_dir)?;
+    let mut journal = UpdateJournal::load(target_dir)?.ok_or_else(|| {
+        CatalystError::InvalidConfig("No interrupted update to resume".to_string())
+    })?;
+
+    let mut report = run_journal(target_dir, &mut journal, UpdateReport::new())?;
+    refresh_lockfile(target_dir, &mut report);
+    Ok(report)
+}
+
+/// Roll back an interrupted update using its journal and backup set
+///
+/// Restores every path the update had started modifying, then removes the
+/// journal and the backup set.
+///
+/// # Returns
+///
+/// Returns a description of each restored step
+///
+/// # Errors
+///
+/// Returns `InvalidConfig` if there is no interrupted update to roll back
+pub fn rollback_update(target_dir: &Path) -> Result<Vec<String>> {
+    let _lock = OperationLock::acquire(target_dir)?;
+    let journal = UpdateJournal::load(target_dir)?.ok_or_else(|| {
+        CatalystError::InvalidConfig("No interrupted update to roll back".to_string())
+    })?;
+    undo_journal(target_dir, &journal)
+}
+
+/// Restore every started step of `journal`, newest first, then remove the
+/// journal and its backups
+fn undo_journal(target_dir: &Path, journal: &UpdateJournal) -> Result<Vec<String>> {
+    let mut restored = Vec::new();
+    for entry in journal.entries.iter().rev() {
+        if entry.status == StepStatus::Pending {
+            continue;
+        }
+        restore_path(
+            target_dir,
+            &journal.backup_dir,
+            &entry.step.path(),
+            entry.existed,
+        )?;
+        restored.push(format!("Rolled back: {}", entry.step));
+    }
+
+    UpdateJournal::remove(target_dir)?;
+    remove_backup_set(&journal.backup_dir)?;
+
+    Ok(restored)
+}
+
+/// Stop a cancelled update, rolling back step `index` if it had started
+///
+/// Finished steps are kept and the journal is saved for `--resume`. A
+/// failed rollback is reported but still ends in `Cancelled`, since the
+/// journal and backup set are left for `--rollback` either way.
+///
+/// # Returns
+///
+/// Returns `Cancelled`, for the caller to propagate
+fn cancel_journal(target_dir: &Path, journal: &UpdateJournal, index: usize) -> CatalystError {
+    let entry = &journal.entries[index];
+    if entry.status == StepStatus::BackedUp {
+        match restore_path(
+            target_dir,
+            &journal.backup_dir,
+            &entry.step.path(),
+            entry.existed,
+        ) {
+            Ok(()) => progress::detail(|| format!("Rolled back: {}", entry.step)),
+            Err(e) => progress::warn(format!("Failed to roll back {}: {}", entry.step, e)),
+        }
+    }
+    if let Err(e) = journal.save(target_dir) {
+        progress::warn(format!("Failed to save the update journal: {}", e));
+    }
+    progress::warn(
+        "Update cancelled. Run 'catalyst update --resume' to finish or 'catalyst update --rollback' to undo",
+    );
+
+    CatalystError::Cancelled
+}
+
+/// Execute all unfinished journal steps
+///
+/// The journal is saved after every state change so that an interruption at
+/// any point leaves an accurate record behind. Cancellation (see
+/// [`crate::cancel`]), including a first Ctrl-C, is honored between steps
+/// and between the files of a skill: only the step in flight is rolled
+/// back, and the journal is kept so `catalyst update --resume` can finish
+/// the rest.
+fn run_journal(
+    target_dir: &Path,
+    journal: &mut UpdateJournal,
+    mut report: UpdateReport,
+) -> Result<UpdateReport> {
+    let _defer = signals::defer_interrupts();
+    report.skipped_skills = journal.skipped_skills.clone();
+
+    for i in 0..journal.entries.len() {
+        if journal.entries[i].status == StepStatus::Completed {
+            continue;
+        }
+        if cancel::checkpoint().is_err() {
+            return Err(cancel_journal(target_dir, journal, i));
+        }
+
+        let step = journal.entries[i].step.clone();
+
+        // Back up before modifying (only once - a resumed step keeps its
+        // original backup rather than backing up its own partial output)
+        if journal.entries[i].status == StepStatus::Pending {
+            let _span = timings::span("back up");
+            journal.entries[i].existed =
+                backup_path(target_dir, &journal.backup_dir, &step.path())?;
+            journal.entries[i].status = StepStatus::BackedUp;
+            journal.save(target_dir)?;
+        }
+
+        let span = timings::span(match &step {
+            UpdateStep::Wrappers => "update wrappers",
+            UpdateStep::Skill { .. } => "update skill",
+            UpdateStep::Hashes => "hash skills",
+            UpdateStep::VersionFile => "write version file",
+        });
+        let error = match &step {
+            // Phase 6.2: Update wrapper scripts (graceful degradation)
+            UpdateStep::Wrappers => {
+                let result = CatalystConfig::load(target_dir).and_then(|config| {
+                    let platform = config.wrapper_platform(Platform::detect());
+                    // Without a setting, keep the cargo-check wrapper init
+                    // installed for a detected project type up to date
+                    let install_check_hook = config.init.install_check_hook.unwrap_or_else(|| {
+                        target_dir
+                            .join(HOOKS_DIR)
+                            .join(format!("cargo-check.{}", platform.hook_extension()))
+                            .is_file()
+                    });
+                    generate_wrapper_scripts(
+                        target_dir,
+                        config.init.install_hooks,
+                        config.init.install_tracker,
+                        install_check_hook,
+                        platform,
+                        PermissionPolicy::Preserve,
+                    )
+                });
+                match result {
+                    Ok(hooks) => {
+                        report.updated_hooks = hooks;
+                        None
+                    }
+                    Err(e) => Some(format!("Failed to update wrapper scripts: {}", e)),
+                }
+            }
+            // Phase 6.3: Update skills (graceful degradation)
+            UpdateStep::Skill { name } => match update_skill(target_dir, name) {
+                Ok(()) => None,
+                Err(CatalystError::Cancelled) => {
+                    return Err(cancel_journal(target_dir, journal, i));
+                }
+                Err(e) => {
+                    // Don't leave a half-copied skill behind
+                    let _ = restore_path(
+                        target_dir,
+                        &journal.backup_dir,
+                        &step.path(),
+                        journal.entries[i].existed,
+                    );
+                    Some(format!("Failed to update skill '{}': {}", name, e))
+                }
+            },
+            UpdateStep::Hashes => {
+                let updated = journal.updated_skills();
+                if updated.is_empty() {
+                    None
+                } else {
+                    regenerate_hashes(target_dir, &updated)
+                        .err()
+                        .map(|e| format!("Failed to update skills: {}", e))
+                }
+            }
+            // Write new version file - FATAL error because version file is critical state
+            // If this fails, the journal is left in place so the update can be resumed
+            UpdateStep::VersionFile => {
+                write_version_file(target_dir)?;
+                None
+            }
+        };
+
+        drop(span);
+
+        if let Some(error) = &error {
             report.errors.push(error.clone());
             report.success = false;
-            eprintln!("⚠️  {}", error);
+            progress::warn(error.as_str());
         }
+
+        journal.entries[i].status = StepStatus::Completed;
+        journal.entries[i].error = error;
+        journal.save(target_dir)?;
     }
 
-    // Write new version file - FATAL error because version file is critical state
-    // If this fails, the entire update should be considered failed to avoid
-    // inconsistent state where updates were applied but version wasn't recorded
-    write_version_file(target_dir)?;
+    report.updated_skills = journal.updated_skills();
+
+    UpdateJournal::remove(target_dir)?;
+    remove_backup_set(&journal.backup_dir)?;
 
     Ok(report)
 }
 
-/// Update skills using hash-based modification detection
+/// Decide which skills to update using hash-based modification detection
 ///
 /// # Arguments
 ///
@@ -106,36 +540,38 @@ pub fn update(target_dir: &Path, force: bool) -> Result<UpdateReport> {
 ///
 /// # Returns
 ///
-/// Returns a tuple of (updated_skills, skipped_skills)
-///
-/// # Implementation Note
-///
-/// Avoids TOCTOU race by directly reading the hashes file without checking
-/// existence first. Missing files are handled as NotFound errors.
-fn update_skills(target_dir: &Path, force: bool) -> Result<(Vec<String>, Vec<SkippedSkill>)> {
-    let mut updated = Vec::new();
+/// Returns a tuple of (skills_to_update, skipped_skills)
+fn plan_skill_updates(target_dir: &Path, force: bool) -> Result<(Vec<String>, Vec<SkippedSkill>)> {
+    let _span = timings::span("detect modified skills");
+    let mut to_update = Vec::new();
     let mut skipped = Vec::new();
 
-    // Read existing hashes - avoid TOCTOU race by attempting read directly
-    let hashes_path = target_dir.join(HASHES_FILE);
-    let stored_hashes: CatalystHashes = match fs::read_to_string(&hashes_path) {
-        Ok(content) => serde_json::from_str(&content).map_err(CatalystError::Json)?,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            // No hashes file, can't determine modifications
-            return Ok((updated, skipped));
-        }
-        Err(e) => {
-            return Err(CatalystError::FileReadFailed {
-                path: hashes_path,
-                source: e,
-            })
-        }
+    let Some(stored_hashes) = load_stored_hashes(target_dir)? else {
+        // No hashes file, can't determine modifications
+        return Ok((to_update, skipped));
     };
 
     let skills_dir = target_dir.join(SKILLS_DIR);
 
     // Iterate through installed skills
     for (skill_name, expected_hash) in &stored_hashes.skills {
+        // The hashes file can be hand-edited; never follow a name out of
+        // the skills directory
+        if safe_path::validate_skill_id(skill_name).is_err() {
+            skipped.push(SkippedSkill {
+                name: skill_name.clone(),
+                reason: "Invalid skill name in hashes file".to_string(),
+                current_hash: String::new(),
+                expected_hash: expected_hash.clone(),
+            });
+            continue;
+        }
+
+        // Linked skills are updated in the library, not through the link
+        if library::is_linked(target_dir, skill_name) {
+            continue;
+        }
+
         let skill_path = skills_dir.join(skill_name).join("SKILL.md");
 
         // Compute current hash - handle missing files gracefully
@@ -162,20 +598,66 @@ fn update_skills(target_dir: &Path, force: bool) -> Result<(Vec<String>, Vec<Ski
             continue;
         }
 
-        // Update skill (copy from embedded resources)
-        if let Some(skill_dir) = SKILLS.get_dir(skill_name) {
-            // Copy skill files
-            copy_skill_files(skill_dir, &skills_dir.join(skill_name))?;
-            updated.push(skill_name.clone());
+        // Only skills we ship can be updated, and only when ours is newer
+        if SKILLS.get_dir(skill_name).is_some()
+            && (force || shipped_is_newer(&skills_dir.join(skill_name), skill_name))
+        {
+            to_update.push(skill_name.clone());
         }
     }
 
-    // Regenerate hashes for updated skills
-    if !updated.is_empty() {
-        regenerate_hashes(target_dir, &updated)?;
+    // Deterministic journal order
+    to_update.sort();
+
+    Ok((to_update, skipped))
+}
+
+/// Read the hashes recorded by the last update; None if there are none
+///
+/// Avoids TOCTOU race by directly reading the hashes file without checking
+/// existence first.
+fn load_stored_hashes(target_dir: &Path) -> Result<Option<CatalystHashes>> {
+    let hashes_path = target_dir.join(HASHES_FILE);
+    match fs::read_to_string(&hashes_path) {
+        Ok(content) => Ok(Some(
+            serde_json::from_str(&content).map_err(CatalystError::Json)?,
+        )),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(CatalystError::FileReadFailed {
+            path: hashes_path,
+            source: e,
+        }),
     }
+}
 
-    Ok((updated, skipped))
+/// Whether the shipped copy of a skill has a newer manifest version than
+/// the installed one
+///
+/// A skill without a version on either side is always updated, as every
+/// skill was before manifests existed.
+fn shipped_is_newer(installed_dir: &Path, skill_name: &str) -> bool {
+    let installed = SkillManifest::load(installed_dir)
+        .ok()
+        .and_then(|manifest| manifest.version);
+    let shipped = init::shipped_manifest(skill_name).and_then(|manifest| manifest.version);
+    match (shipped, installed) {
+        (Some(shipped), Some(installed)) => self_update::is_newer(&shipped, &installed),
+        _ => true,
+    }
+}
+
+/// Copy a single skill from embedded resources
+fn update_skill(target_dir: &Path, skill_name: &str) -> Result<()> {
+    safe_path::validate_skill_id(skill_name)?;
+    let skill_dir = SKILLS.get_dir(skill_name).ok_or_else(|| {
+        CatalystError::SkillInstallationFailed(format!("Unknown skill: {}", skill_name))
+    })?;
+    let vars = template::project_vars(target_dir, &CatalystConfig::load(target_dir)?);
+    copy_skill_files(
+        skill_dir,
+        &target_dir.join(SKILLS_DIR).join(skill_name),
+        &vars,
+    )
 }
 
 /// Compute SHA256 hash of a file
@@ -190,10 +672,13 @@ fn compute_file_hash(file_path: &Path) -> Result<String> {
     })?;
     let mut hasher = Sha256::new();
     hasher.update(&content);
-    Ok(format!("{:x}", hasher.finalize()))
+    let hash = format!("{:x}", hasher.finalize());
+    progress::detail(|| format!("Hashed {}: {}", file_path.display(), hash));
+    Ok(hash)
 }
 
-/// Copy skill files from embedded resources to target directory
+/// Copy skill files from embedded resources to target directory,
+/// rendering skill templates with `vars`
 ///
 /// # Errors
 ///
@@ -201,7 +686,11 @@ fn compute_file_hash(file_path: &Path) -> Result<String> {
 /// - Directory creation failures
 /// - File write failures
 /// - Invalid subdirectory paths
-fn copy_skill_files(source_dir: &include_dir::Dir, target_dir: &Path) -> Result<()> {
+fn copy_skill_files(
+    source_dir: &include_dir::Dir,
+    target_dir: &Path,
+    vars: &TemplateVars,
+) -> Result<()> {
     // Create target directory
     fs::create_dir_all(target_dir).map_err(|e| CatalystError::DirectoryCreationFailed {
         path: target_dir.to_path_buf(),
@@ -210,7 +699,18 @@ fn copy_skill_files(source_dir: &include_dir::Dir, target_dir: &Path) -> Result<
 
     // Copy all files
     for file in source_dir.files() {
-        let target_path = target_dir.join(file.path());
+        cancel::checkpoint()?;
+
+        // Embedded paths are relative to the skills root, so only the
+        // file name is joined here
+        let file_name = file.path().file_name().ok_or_else(|| {
+            CatalystError::InvalidPath(format!(
+                "Invalid file path (missing file name): {}",
+                file.path().display()
+            ))
+        })?;
+        let (file_name, contents) = template::skill_file(file_name, file.contents(), vars)?;
+        let target_path = target_dir.join(file_name);
 
         // Create parent directories if needed
         if let Some(parent) = target_path.parent() {
@@ -221,10 +721,11 @@ fn copy_skill_files(source_dir: &include_dir::Dir, target_dir: &Path) -> Result<
         }
 
         // Write file with error context
-        fs::write(&target_path, file.contents()).map_err(|e| CatalystError::FileWriteFailed {
+        fs::write(&target_path, contents).map_err(|e| CatalystError::FileWriteFailed {
             path: target_path.clone(),
             source: e,
         })?;
+        progress::detail(|| format!("Wrote {}", target_path.display()));
     }
 
     // Recursively copy subdirectories
@@ -236,7 +737,7 @@ fn copy_skill_files(source_dir: &include_dir::Dir, target_dir: &Path) -> Result<
             ))
         })?;
         let target_subdir = target_dir.join(file_name);
-        copy_skill_files(subdir, &target_subdir)?;
+        copy_skill_files(subdir, &target_subdir, vars)?;
     }
 
     Ok(())
@@ -309,6 +810,7 @@ fn regenerate_hashes(target_dir: &Path, updated_skills: &[String]) -> Result<()>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{ExitCode, VERSION_FILE};
     use tempfile::TempDir;
 
     #[test]
@@ -389,6 +891,27 @@ mod tests {
         assert!(hash_file.exists());
     }
 
+    #[test]
+    fn test_plan_skill_updates_skips_traversal_names() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("project");
+        fs::create_dir_all(target.join(".claude/skills")).unwrap();
+        // A SKILL.md outside the skills directory that a bad name points at
+        fs::create_dir_all(temp_dir.path().join("outside")).unwrap();
+        fs::write(temp_dir.path().join("outside").join("SKILL.md"), "x").unwrap();
+        fs::write(
+            target.join(".catalyst-hashes.json"),
+            r#"{"version":"0","updated_at":"","skills":{"../../outside":"abc"},"hooks":{}}"#,
+        )
+        .unwrap();
+
+        let (to_update, skipped) = plan_skill_updates(&target, true).unwrap();
+        assert!(to_update.is_empty());
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].name, "../../outside");
+        assert!(update_skill(&target, "../../outside").is_err());
+    }
+
     #[test]
     fn test_regenerate_hashes_missing_skill_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -417,7 +940,8 @@ mod tests {
         // Use empty embedded dir for test
         static EMPTY_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/../.claude/skills");
         if let Some(skill_dir) = EMPTY_DIR.get_dir("skill-developer") {
-            let result = copy_skill_files(skill_dir, &target.join("test-skill"));
+            let result =
+                copy_skill_files(skill_dir, &target.join("test-skill"), &TemplateVars::new());
             assert!(result.is_err());
             match result {
                 Err(CatalystError::DirectoryCreationFailed { path, source }) => {
@@ -431,4 +955,278 @@ mod tests {
         // Clean up
         fs::set_permissions(target, fs::Permissions::from_mode(0o755)).unwrap();
     }
+
+    /// Build a journal for a project with one installed skill, interrupted
+    /// while the skill was being copied
+    fn interrupted_update(target: &Path) -> UpdateJournal {
+        let skill_dir = target.join(".claude/skills/skill-developer");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "original").unwrap();
+        fs::write(target.join(VERSION_FILE), "0.0.1").unwrap();
+
+        let backup_dir = create_backup_set(target, "update").unwrap();
+        let mut journal = UpdateJournal::new(
+            "0.0.1".to_string(),
+            CATALYST_VERSION.to_string(),
+            backup_dir.clone(),
+            vec![
+                UpdateStep::Wrappers,
+                UpdateStep::Skill {
+                    name: "skill-developer".to_string(),
+                },
+                UpdateStep::Hashes,
+                UpdateStep::VersionFile,
+            ],
+        );
+        journal.entries[0].status = StepStatus::Completed;
+        journal.entries[1].existed =
+            backup_path(target, &backup_dir, &journal.entries[1].step.path()).unwrap();
+        journal.entries[1].status = StepStatus::BackedUp;
+        journal.save(target).unwrap();
+
+        // Simulate the crash: skill half-copied
+        fs::write(skill_dir.join("SKILL.md"), "half-written").unwrap();
+
+        journal
+    }
+
+    #[test]
+    fn test_update_refuses_when_journal_pending() {
+        let temp_dir = TempDir::new().unwrap();
+        interrupted_update(temp_dir.path());
+
+        let result = update(temp_dir.path(), false);
+        assert!(matches!(
+            result,
+            Err(CatalystError::UpdateInterrupted { .. })
+        ));
+    }
+
+    #[test]
+    fn test_resume_update_finishes_remaining_steps() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let journal = interrupted_update(target);
+
+        let report = resume_update(target).unwrap();
+        assert!(report.success, "errors: {:?}", report.errors);
+        assert_eq!(report.updated_skills, vec!["skill-developer".to_string()]);
+        assert_eq!(
+            read_version_file(target).unwrap().unwrap(),
+            CATALYST_VERSION
+        );
+
+        let installed =
+            fs::read_to_string(target.join(".claude/skills/skill-developer/SKILL.md")).unwrap();
+        assert_ne!(installed, "half-written");
+
+        assert!(UpdateJournal::load(target).unwrap().is_none());
+        assert!(!journal.backup_dir.exists());
+    }
+
+    #[test]
+    fn test_rollback_update_restores_original_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let journal = interrupted_update(target);
+
+        let restored = rollback_update(target).unwrap();
+        assert_eq!(restored.len(), 2); // skill + wrappers
+
+        let skill =
+            fs::read_to_string(target.join(".claude/skills/skill-developer/SKILL.md")).unwrap();
+        assert_eq!(skill, "original");
+        assert_eq!(read_version_file(target).unwrap().unwrap(), "0.0.1");
+        assert!(UpdateJournal::load(target).unwrap().is_none());
+        assert!(!journal.backup_dir.exists());
+    }
+
+    #[test]
+    fn test_cancel_mid_skill_rolls_back_only_that_skill() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let journal = interrupted_update(target);
+
+        // Cancel once the skill step has written its first new file
+        let skill_dir = target.join(".claude/skills/skill-developer");
+        let check = cancel::CancelCheck::new({
+            let skill_dir = skill_dir.clone();
+            move || fs::read_dir(&skill_dir).is_ok_and(|entries| entries.count() > 1)
+        });
+        let result = cancel::with_cancellation(check, || resume_update(target));
+
+        assert!(matches!(result, Err(CatalystError::Cancelled)));
+        let error = result.unwrap_err();
+        assert_eq!(ExitCode::for_error(&error).code(), 130);
+        // The skill is back to its backup; nothing else was touched
+        assert_eq!(
+            fs::read_dir(&skill_dir).unwrap().count(),
+            1,
+            "partial skill files left behind"
+        );
+        assert_eq!(
+            fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(),
+            "original"
+        );
+        assert_eq!(read_version_file(target).unwrap().unwrap(), "0.0.1");
+
+        // The journal is kept for --resume
+        let kept = UpdateJournal::load(target).unwrap().unwrap();
+        assert_eq!(kept.entries[0].status, StepStatus::Completed);
+        assert_eq!(kept.entries[1].status, StepStatus::BackedUp);
+        assert!(journal.backup_dir.exists());
+
+        resume_update(target).unwrap();
+        assert_ne!(
+            fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(),
+            "original"
+        );
+        assert!(UpdateJournal::load(target).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cancel_between_steps_keeps_finished_steps() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let journal = interrupted_update(target);
+
+        // Cancel once the skill step is recorded as done
+        let check = cancel::CancelCheck::new({
+            let target = target.to_path_buf();
+            move || {
+                UpdateJournal::load(&target).is_ok_and(|journal| {
+                    journal.is_some_and(|j| j.entries[1].status == StepStatus::Completed)
+                })
+            }
+        });
+        let result = cancel::with_cancellation(check, || resume_update(target));
+
+        assert!(matches!(result, Err(CatalystError::Cancelled)));
+        let skill_md = target.join(".claude/skills/skill-developer/SKILL.md");
+        let updated = fs::read_to_string(&skill_md).unwrap();
+        assert_ne!(updated, "original");
+        assert_ne!(updated, "half-written");
+        assert_eq!(read_version_file(target).unwrap().unwrap(), "0.0.1");
+
+        let kept = UpdateJournal::load(target).unwrap().unwrap();
+        assert_eq!(kept.entries[1].status, StepStatus::Completed);
+        assert_eq!(kept.entries[2].status, StepStatus::Pending);
+        assert!(journal.backup_dir.exists());
+
+        // Resuming only runs what is left
+        resume_update(target).unwrap();
+        assert_eq!(fs::read_to_string(&skill_md).unwrap(), updated);
+        assert_eq!(
+            read_version_file(target).unwrap().unwrap(),
+            CATALYST_VERSION
+        );
+        assert!(UpdateJournal::load(target).unwrap().is_none());
+        assert!(!journal.backup_dir.exists());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_update_async_cancelled_restores_step_and_keeps_journal() {
+        use crate::progress::{self, EventHook, Verbosity};
+        use std::sync::Arc;
+        use tokio_util::sync::CancellationToken;
+
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        let skill_dir = target.join(".claude/skills/skill-developer");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "original").unwrap();
+        fs::write(target.join(VERSION_FILE), "0.0.1").unwrap();
+        fs::write(
+            target.join(HASHES_FILE),
+            r#"{"version":"0.0.1","updated_at":"","skills":{"skill-developer":"old"},"hooks":{}}"#,
+        )
+        .unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        // Verbose, so each file the skill step writes reports a detail;
+        // cancel once the step has written its first new file
+        let cancel = CancellationToken::new();
+        let hook = EventHook({
+            let cancel = cancel.clone();
+            let skill_dir = skill_dir.clone();
+            move || {
+                if fs::read_dir(&skill_dir).is_ok_and(|entries| entries.count() > 1) {
+                    cancel.cancel();
+                }
+            }
+        });
+        let result = progress::with_test_reporter(Arc::new(hook), Verbosity::Verbose, || {
+            runtime.block_on(update_async(target.to_path_buf(), true, cancel.clone()))
+        });
+
+        assert!(matches!(result, Err(CatalystError::Cancelled)));
+        // The skill step in progress is back to its backup
+        assert_eq!(fs::read_dir(&skill_dir).unwrap().count(), 1);
+        assert_eq!(
+            fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(),
+            "original"
+        );
+        assert_eq!(read_version_file(target).unwrap().unwrap(), "0.0.1");
+
+        // ...and the journal is left for --resume, which finishes the update
+        let journal = UpdateJournal::load(target).unwrap().unwrap();
+        assert_eq!(journal.entries[0].status, StepStatus::Completed);
+        assert_eq!(journal.entries[1].status, StepStatus::BackedUp);
+        resume_update(target).unwrap();
+        assert_ne!(
+            fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(),
+            "original"
+        );
+        assert_eq!(
+            read_version_file(target).unwrap().unwrap(),
+            CATALYST_VERSION
+        );
+        assert!(UpdateJournal::load(target).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_resume_without_journal_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(matches!(
+            resume_update(temp_dir.path()),
+            Err(CatalystError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_update_skills_updates_only_outdated_skills() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path();
+        for skill in ["skill-developer", "rust-developer"] {
+            let skill_dir = target.join(SKILLS_DIR).join(skill);
+            fs::create_dir_all(&skill_dir).unwrap();
+            let skill_md = format!("---\nname: {}\ndescription: Old copy\n---\n", skill);
+            fs::write(skill_dir.join("SKILL.md"), skill_md).unwrap();
+            fs::write(skill_dir.join("skill.toml"), "version = \"999.0.0\"\n").unwrap();
+        }
+        fs::write(
+            target.join(SKILLS_DIR).join("rust-developer/skill.toml"),
+            "version = \"0.0.1\"\n",
+        )
+        .unwrap();
+
+        let skills = ["skill-developer".to_string(), "rust-developer".to_string()];
+        let report = update_skills(target, &skills, false).unwrap();
+        assert!(report.success, "errors: {:?}", report.errors);
+        assert_eq!(report.updated_skills, vec!["rust-developer".to_string()]);
+        let updated = SkillManifest::load(&target.join(SKILLS_DIR).join("rust-developer")).unwrap();
+        assert_eq!(
+            updated.version,
+            init::shipped_manifest("rust-developer").unwrap().version
+        );
+        assert!(!target.join(VERSION_FILE).exists());
+
+        assert!(matches!(
+            update_skills(target, &["no-such-skill".to_string()], false),
+            Err(CatalystError::SkillInstallationFailed(_))
+        ));
+    }
 }
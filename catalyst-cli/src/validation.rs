@@ -3,7 +3,8 @@
 //! This module provides functionality to validate that required binaries
 //! are installed and accessible in the expected locations.
 
-use crate::types::{CatalystError, Platform, Result};
+use crate::types::{CatalystError, InstallMethod, Platform, Result, INSTALL_METHOD_FILE};
+use catalyst_core::fs_ops::{Fs, Mode};
 use dirs::home_dir;
 use std::path::{Path, PathBuf};
 
@@ -17,6 +18,7 @@ use std::path::{Path, PathBuf};
 /// Returns Ok(()) if all binaries are found, or an error with details about
 /// what's missing and how to install them.
 pub fn check_binaries_installed(platform: Platform) -> Result<Vec<String>> {
+    let fs = Fs::read_only();
     let bin_dir = get_binary_directory()?;
     let mut missing = Vec::new();
     let mut found = Vec::new();
@@ -26,7 +28,7 @@ pub fn check_binaries_installed(platform: Platform) -> Result<Vec<String>> {
 
     // Check required binaries
     for binary_name in &required {
-        if binary_exists(&bin_dir, binary_name, platform) {
+        if binary_exists(&fs, &bin_dir, binary_name, platform) {
             found.push(binary_name.to_string());
         } else {
             missing.push(binary_name.to_string());
@@ -34,7 +36,7 @@ pub fn check_binaries_installed(platform: Platform) -> Result<Vec<String>> {
     }
 
     // Check for file-change-tracker variants
-    let tracker_variant = detect_file_change_tracker_variant(&bin_dir, platform)?;
+    let tracker_variant = detect_file_change_tracker_variant(&fs, &bin_dir, platform)?;
     if let Some(variant) = tracker_variant {
         found.push(format!("file-change-tracker ({})", variant));
     } else {
@@ -43,7 +45,11 @@ pub fn check_binaries_installed(platform: Platform) -> Result<Vec<String>> {
 
     if !missing.is_empty() {
         return Err(CatalystError::BinariesNotInstalled {
-            install_command: get_install_command(&missing, platform),
+            install_command: get_install_command(
+                &missing,
+                platform,
+                detect_install_method(&fs, &bin_dir),
+            ),
             missing_binaries: missing.join(", "),
         });
     }
@@ -54,39 +60,29 @@ pub fn check_binaries_installed(platform: Platform) -> Result<Vec<String>> {
 /// Detect which variant of file-change-tracker is installed
 ///
 /// Returns:
-/// - Some("sqlite") if the SQLite version is found
-/// - Some("basic") if the basic version is found
+/// - Some("sqlite") if `file-change-tracker` is found (it only builds with
+///   the `sqlite` feature)
+/// - Some("sqlite-legacy") for the old `post-tool-use-tracker-sqlite` name
+/// - Some("basic") if only `file-change-tracker-basic` (JSONL) is found
 /// - None if neither is found
-///
-/// # Current Limitations (Phase 1)
-///
-/// Currently assumes any file-change-tracker binary is the SQLite variant
-/// since that's the only variant we build with the new name. This is acceptable
-/// for Phase 1 because:
-/// - The basic variant hasn't been implemented yet
-/// - install.sh only builds the SQLite variant with --sqlite flag
-/// - Users who have the binary are guaranteed to have the SQLite version
-///
-/// # Future Enhancement
-///
-/// TODO: Implement --version flag detection to distinguish variants accurately
-/// when basic variant is added in future phases.
-pub fn detect_file_change_tracker_variant(
+pub fn detect_file_change_tracker_variant<M: Mode>(
+    fs: &Fs<M>,
     bin_dir: &Path,
     platform: Platform,
 ) -> Result<Option<String>> {
-    // Check for new binary name (Phase 1+)
-    if binary_exists(bin_dir, "file-change-tracker", platform) {
-        // Phase 1: Assume SQLite variant (only variant available)
-        // This is safe because install.sh --sqlite is the only way to get this binary
+    if binary_exists(fs, bin_dir, "file-change-tracker", platform) {
         return Ok(Some("sqlite".to_string()));
     }
 
     // Check for legacy name (pre-Phase 1 installations)
-    if binary_exists(bin_dir, "post-tool-use-tracker-sqlite", platform) {
+    if binary_exists(fs, bin_dir, "post-tool-use-tracker-sqlite", platform) {
         return Ok(Some("sqlite-legacy".to_string()));
     }
 
+    if binary_exists(fs, bin_dir, "file-change-tracker-basic", platform) {
+        return Ok(Some("basic".to_string()));
+    }
+
     Ok(None)
 }
 
@@ -102,42 +98,201 @@ pub fn get_binary_directory() -> Result<PathBuf> {
 /// Check if a binary exists in the given directory
 ///
 /// On Windows, this checks for both the name with and without .exe extension
-pub fn binary_exists(bin_dir: &Path, name: &str, platform: Platform) -> bool {
+pub fn binary_exists<M: Mode>(fs: &Fs<M>, bin_dir: &Path, name: &str, platform: Platform) -> bool {
     let binary_path = if platform == Platform::Windows {
         bin_dir.join(format!("{}.exe", name))
     } else {
         bin_dir.join(name)
     };
 
-    binary_path.exists() && binary_path.is_file()
+    fs.is_file(&binary_path)
+}
+
+/// Extensions Windows runs directly when PATHEXT is not set
+const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD";
+
+/// Whether a file can be run on Windows
+///
+/// Windows has no executable bit, so this checks what decides whether a
+/// launch succeeds: the extension is one Windows runs (from PATHEXT) or a
+/// `.ps1` script (which Catalyst's hook commands run through PowerShell),
+/// the path is a regular file, it isn't empty (an interrupted copy), and it
+/// can be opened for reading (not locked or denied by an ACL).
+///
+/// # Arguments
+///
+/// * `path` - File to check
+/// * `pathext` - Value of the PATHEXT environment variable, if set
+pub fn windows_executable<M: Mode>(fs: &Fs<M>, path: &Path, pathext: Option<&str>) -> bool {
+    let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    let runnable = extension.eq_ignore_ascii_case("ps1")
+        || pathext
+            .unwrap_or(DEFAULT_PATHEXT)
+            .split(';')
+            .filter_map(|ext| ext.trim().strip_prefix('.'))
+            .any(|ext| ext.eq_ignore_ascii_case(extension));
+
+    runnable && fs.metadata(path).is_ok_and(|m| m.is_file() && m.len() > 0) && fs.open(path).is_ok()
+}
+
+/// Determine how the Catalyst binaries were installed
+///
+/// Checks for an install-method marker in `bin_dir`, then next to the
+/// running executable. Without a marker, the executable's location is used
+/// as a hint (a Homebrew Cellar or ~/.cargo/bin). Defaults to the install
+/// scripts, which is how most existing installations were made.
+pub fn detect_install_method<M: Mode>(fs: &Fs<M>, bin_dir: &Path) -> InstallMethod {
+    let exe_dir = std::env::current_exe()
+        .and_then(|exe| exe.canonicalize())
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+
+    detect_install_method_in(fs, bin_dir, exe_dir.as_deref())
+}
+
+fn detect_install_method_in<M: Mode>(
+    fs: &Fs<M>,
+    bin_dir: &Path,
+    exe_dir: Option<&Path>,
+) -> InstallMethod {
+    if let Some(method) = std::iter::once(bin_dir)
+        .chain(exe_dir)
+        .find_map(|dir| read_install_method(fs, dir))
+    {
+        return method;
+    }
+
+    match exe_dir {
+        Some(dir) if dir.components().any(|c| c.as_os_str() == "Cellar") => InstallMethod::Homebrew,
+        Some(dir) if dir.ends_with(".cargo/bin") => InstallMethod::Cargo,
+        _ => InstallMethod::Script,
+    }
+}
+
+/// Read the install-method marker from a directory
+///
+/// Returns None if there is no marker or it names an unknown method.
+pub fn read_install_method<M: Mode>(fs: &Fs<M>, dir: &Path) -> Option<InstallMethod> {
+    fs.read_to_string(&dir.join(INSTALL_METHOD_FILE))
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Record how the binaries in `bin_dir` were installed
+///
+/// # Errors
+///
+/// Returns `FileWriteFailed` if the marker can't be written.
+pub fn write_install_method(bin_dir: &Path, method: InstallMethod) -> Result<()> {
+    let path = bin_dir.join(INSTALL_METHOD_FILE);
+    let fs = Fs::read_write();
+    fs.create_dir_all(bin_dir)
+        .and_then(|()| fs.write_atomic(&path, format!("{}\n", method).as_bytes()))
+        .map(|_| ())
+        .map_err(|e| CatalystError::FileWriteFailed { path, source: e })
 }
 
 /// Generate the appropriate install command based on what's missing and the platform
-fn get_install_command(missing: &[String], platform: Platform) -> String {
+fn get_install_command(missing: &[String], platform: Platform, method: InstallMethod) -> String {
     let has_tracker = missing.iter().any(|m| m.contains("file-change-tracker"));
+    install_command(method, platform, has_tracker)
+}
 
-    match platform {
-        Platform::Windows => {
-            if has_tracker {
-                ".\\install.ps1 -Sqlite".to_string()
-            } else {
-                ".\\install.ps1".to_string()
-            }
+/// Command that (re)installs the binaries for an install method
+///
+/// # Arguments
+///
+/// * `method` - How Catalyst was installed
+/// * `platform` - Platform the command will run on
+/// * `with_tracker` - Whether file-change-tracker (SQLite) is needed
+pub fn install_command(method: InstallMethod, platform: Platform, with_tracker: bool) -> String {
+    match method {
+        // install-binaries copies from its own directory by default; a
+        // rebuild needs the checkout, whose location isn't recorded
+        InstallMethod::Script => {
+            let sqlite = if with_tracker { " --sqlite" } else { "" };
+            format!(
+                "catalyst install-binaries (or to rebuild: catalyst install-binaries --build <path-to-checkout>{})",
+                sqlite
+            )
         }
-        _ => {
-            // Linux, MacOS, WSL all use bash script
-            if has_tracker {
-                "cd catalyst && ./install.sh --sqlite".to_string()
-            } else {
-                "cd catalyst && ./install.sh".to_string()
-            }
+        InstallMethod::Cargo => {
+            let root = match platform {
+                Platform::Windows => "\"$env:USERPROFILE\\.claude-hooks\"",
+                _ => "~/.claude-hooks",
+            };
+            let features = if with_tracker { " --features sqlite" } else { "" };
+            format!(
+                "cargo install --force --root {} --git {} catalyst-cli{}",
+                root, REPOSITORY_URL, features
+            )
         }
+        InstallMethod::Homebrew => "brew reinstall catalyst".to_string(),
+        InstallMethod::Tarball => format!(
+            "reinstall from the latest release tarball ({}/releases/latest) into ~/.claude-hooks/bin",
+            REPOSITORY_URL
+        ),
     }
 }
 
+/// Command that upgrades Catalyst to the latest release for an install method
+pub fn upgrade_command(method: InstallMethod, platform: Platform) -> String {
+    match method {
+        InstallMethod::Script => match platform {
+            Platform::Windows => {
+                "cd catalyst; git pull; catalyst install-binaries --build .".to_string()
+            }
+            _ => "cd catalyst && git pull && catalyst install-binaries --build .".to_string(),
+        },
+        InstallMethod::Homebrew => "brew upgrade catalyst".to_string(),
+        InstallMethod::Cargo | InstallMethod::Tarball => install_command(method, platform, false),
+    }
+}
+
+const REPOSITORY_URL: &str = env!("CARGO_PKG_REPOSITORY");
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_script_install_command() {
+        let command = install_command(InstallMethod::Script, Platform::Linux, false);
+        assert!(command.starts_with("catalyst install-binaries "));
+        assert!(command.contains("--build <path-to-checkout>)"));
+
+        let command = install_command(InstallMethod::Script, Platform::Linux, true);
+        assert!(command.contains("--build <path-to-checkout> --sqlite"));
+    }
+
+    #[test]
+    fn test_windows_executable() {
+        let fs = Fs::read_only();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = |name: &str, content: &str| {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            path
+        };
+
+        assert!(windows_executable(&fs, &file("tool.exe", "MZ"), None));
+        assert!(windows_executable(&fs, &file("hook.PS1", "exit 0"), None));
+        assert!(!windows_executable(&fs, &file("empty.exe", ""), None));
+        assert!(!windows_executable(&fs, &file("notes.txt", "x"), None));
+        assert!(!windows_executable(
+            &fs,
+            &temp_dir.path().join("missing.exe"),
+            None
+        ));
+
+        let custom = file("script.py", "print()");
+        assert!(!windows_executable(&fs, &custom, None));
+        assert!(windows_executable(&fs, &custom, Some(".EXE; .PY")));
+    }
 
     #[test]
     fn test_binary_exists_handles_windows_exe() {
@@ -146,37 +301,41 @@ mod tests {
 
         // This test validates logic only - actual file doesn't exist in test
         // In real usage, the file system check will be performed
-        assert!(!binary_exists(bin_dir, "nonexistent", platform));
+        assert!(!binary_exists(
+            &Fs::read_only(),
+            bin_dir,
+            "nonexistent",
+            platform
+        ));
     }
 
     #[test]
     fn test_get_install_command_with_tracker() {
         let missing = vec!["file-change-tracker (sqlite or basic)".to_string()];
-        let cmd = get_install_command(&missing, Platform::Linux);
+        let cmd = get_install_command(&missing, Platform::Linux, InstallMethod::Script);
         assert!(cmd.contains("--sqlite"));
     }
 
     #[test]
     fn test_get_install_command_without_tracker() {
         let missing = vec!["skill-activation-prompt".to_string()];
-        let cmd = get_install_command(&missing, Platform::Linux);
+        let cmd = get_install_command(&missing, Platform::Linux, InstallMethod::Script);
         assert!(!cmd.contains("--sqlite"));
     }
 
     #[test]
     fn test_get_install_command_windows() {
         let missing = vec!["skill-activation-prompt".to_string()];
-        let cmd = get_install_command(&missing, Platform::Windows);
-        assert!(cmd.contains(".ps1"));
-        assert!(!cmd.contains(".sh"));
+        let cmd = get_install_command(&missing, Platform::Windows, InstallMethod::Script);
+        assert!(cmd.starts_with("catalyst install-binaries"));
+        assert!(!cmd.contains(".ps1"));
     }
 
     #[test]
     fn test_get_install_command_windows_with_sqlite() {
         let missing = vec!["file-change-tracker (sqlite or basic)".to_string()];
-        let cmd = get_install_command(&missing, Platform::Windows);
-        assert!(cmd.contains(".ps1"));
-        assert!(cmd.contains("-Sqlite"));
+        let cmd = get_install_command(&missing, Platform::Windows, InstallMethod::Script);
+        assert!(cmd.contains("--sqlite"));
     }
 
     #[test]
@@ -184,7 +343,8 @@ mod tests {
         use std::path::Path;
         // Use a path that definitely doesn't exist
         let nonexistent_dir = Path::new("/nonexistent/path/to/binaries");
-        let result = detect_file_change_tracker_variant(nonexistent_dir, Platform::Linux);
+        let result =
+            detect_file_change_tracker_variant(&Fs::read_only(), nonexistent_dir, Platform::Linux);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), None);
     }
@@ -220,19 +380,73 @@ mod tests {
 
     #[test]
     fn test_platform_specific_commands() {
-        // Test that different platforms get appropriate commands
+        // Installs go through the CLI itself, so no platform needs a shell script
         let missing = vec!["skill-activation-prompt".to_string()];
+        for platform in [
+            Platform::Linux,
+            Platform::MacOS,
+            Platform::WSL,
+            Platform::Windows,
+        ] {
+            let cmd = get_install_command(&missing, platform, InstallMethod::Script);
+            assert!(cmd.contains("install-binaries"));
+            assert!(!cmd.contains(".sh") && !cmd.contains(".ps1"));
+        }
 
-        let linux_cmd = get_install_command(&missing, Platform::Linux);
-        assert!(linux_cmd.contains(".sh"));
+        // Upgrades chain commands with the platform's shell separator
+        assert!(upgrade_command(InstallMethod::Script, Platform::Linux).contains(" && "));
+        assert!(upgrade_command(InstallMethod::Script, Platform::Windows).contains("; "));
+    }
+
+    #[test]
+    fn test_install_method_marker_wins_over_location() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let bin_dir = temp_dir.path().join("bin");
+        let cellar = Path::new("/opt/homebrew/Cellar/catalyst/0.1.0/bin");
+
+        // Without a marker the executable's location is the hint
+        assert_eq!(
+            detect_install_method_in(&Fs::read_only(), &bin_dir, Some(cellar)),
+            InstallMethod::Homebrew
+        );
+        assert_eq!(
+            detect_install_method_in(
+                &Fs::read_only(),
+                &bin_dir,
+                Some(Path::new("/home/me/.cargo/bin"))
+            ),
+            InstallMethod::Cargo
+        );
+        assert_eq!(
+            detect_install_method_in(&Fs::read_only(), &bin_dir, None),
+            InstallMethod::Script
+        );
 
-        let macos_cmd = get_install_command(&missing, Platform::MacOS);
-        assert!(macos_cmd.contains(".sh"));
+        write_install_method(&bin_dir, InstallMethod::Tarball).unwrap();
+        assert_eq!(
+            detect_install_method_in(&Fs::read_only(), &bin_dir, Some(cellar)),
+            InstallMethod::Tarball
+        );
+    }
+
+    #[test]
+    fn test_commands_follow_install_method() {
+        let brew = InstallMethod::Homebrew;
+        assert_eq!(
+            upgrade_command(brew, Platform::MacOS),
+            "brew upgrade catalyst"
+        );
+        assert_eq!(
+            install_command(brew, Platform::MacOS, true),
+            "brew reinstall catalyst"
+        );
 
-        let wsl_cmd = get_install_command(&missing, Platform::WSL);
-        assert!(wsl_cmd.contains(".sh"));
+        let cargo = install_command(InstallMethod::Cargo, Platform::Linux, true);
+        assert!(cargo.starts_with("cargo install --force"));
+        assert!(cargo.ends_with("--features sqlite"));
+        assert!(!cargo.contains("install.sh"));
 
-        let windows_cmd = get_install_command(&missing, Platform::Windows);
-        assert!(windows_cmd.contains(".ps1"));
+        assert_eq!("brew".parse::<InstallMethod>().unwrap(), brew);
+        assert!("apt".parse::<InstallMethod>().is_err());
     }
 }
@@ -0,0 +1,239 @@
+//! External validators (`[[validators]]` in catalyst.toml)
+//!
+//! Teams can plug their own checks into `catalyst status` without forking
+//! Catalyst. Each validator is a command run from the project directory
+//! (with `CATALYST_PROJECT_DIR` set) that prints JSON on stdout, either an
+//! object with an `issues` array or the array itself:
+//!
+//! ```json
+//! {"issues": [
+//!   {"severity": "error", "description": "src/main.rs has no license header",
+//!    "component": "src/main.rs", "suggested_fix": "Run: make headers"}
+//! ]}
+//! ```
+//!
+//! `severity` is "error", "warning" (the default), or "info"; `component`
+//! and `suggested_fix` are optional. The exit code is ignored as long as the
+//! output parses, since many linters exit non-zero when they find problems.
+//! A validator that can't be run, times out, or prints something else is
+//! itself reported as an issue.
+
+use crate::config::ValidatorConfig;
+use crate::process;
+use crate::progress;
+use crate::types::{Issue, IssueKind, IssueSeverity};
+use serde::Deserialize;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// An issue as printed by a validator
+#[derive(Debug, Deserialize)]
+struct ReportedIssue {
+    #[serde(default)]
+    severity: ReportedSeverity,
+
+    #[serde(alias = "message")]
+    description: String,
+
+    #[serde(default)]
+    component: Option<String>,
+
+    #[serde(default)]
+    suggested_fix: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ReportedSeverity {
+    Error,
+    #[default]
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ValidatorOutput {
+    Report { issues: Vec<ReportedIssue> },
+    Issues(Vec<ReportedIssue>),
+}
+
+/// Run every configured validator and collect the issues they report
+pub fn run_validators(target_dir: &Path, validators: &[ValidatorConfig]) -> Vec<Issue> {
+    validators
+        .iter()
+        .flat_map(|validator| run_validator(target_dir, validator))
+        .collect()
+}
+
+/// Run one validator
+///
+/// # Returns
+///
+/// Returns the issues the validator reported, or a single `ValidatorFailed`
+/// issue if it couldn't be run or its output couldn't be parsed
+pub fn run_validator(target_dir: &Path, validator: &ValidatorConfig) -> Vec<Issue> {
+    progress::detail(|| format!("Running validator {}", validator.name));
+    match execute(target_dir, validator) {
+        Ok(stdout) => match parse_output(&stdout) {
+            Ok(reported) => reported
+                .into_iter()
+                .map(|issue| to_issue(&validator.name, issue))
+                .collect(),
+            Err(e) => vec![failed(
+                validator,
+                format!("printed output that isn't a validator report: {}", e),
+            )],
+        },
+        Err(problem) => vec![failed(validator, problem)],
+    }
+}
+
+/// Run the validator's command and return its stdout
+fn execute(target_dir: &Path, validator: &ValidatorConfig) -> std::result::Result<String, String> {
+    let (program, args) = validator
+        .command
+        .split_first()
+        .ok_or_else(|| "has an empty command".to_string())?;
+
+    // Output goes to a file so a chatty validator can't fill a pipe and stall
+    let mut stdout =
+        tempfile::tempfile().map_err(|e| format!("could not capture output: {}", e))?;
+    let stdout_handle = stdout
+        .try_clone()
+        .map_err(|e| format!("could not capture output: {}", e))?;
+
+    let mut child = Command::new(program)
+        .args(args)
+        .current_dir(target_dir)
+        .env("CATALYST_PROJECT_DIR", target_dir)
+        .stdin(Stdio::null())
+        .stdout(stdout_handle)
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("could not be run ({}): {}", program, e))?;
+
+    let timeout = Duration::from_secs(validator.timeout_secs);
+    match process::wait_timeout(&mut child, timeout) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(format!(
+                "did not finish within {} seconds",
+                validator.timeout_secs
+            ))
+        }
+        Err(e) => return Err(format!("could not be waited on: {}", e)),
+    }
+
+    let mut output = String::new();
+    stdout
+        .seek(SeekFrom::Start(0))
+        .and_then(|_| stdout.read_to_string(&mut output))
+        .map_err(|e| format!("output could not be read: {}", e))?;
+    Ok(output)
+}
+
+fn parse_output(stdout: &str) -> serde_json::Result<Vec<ReportedIssue>> {
+    Ok(match serde_json::from_str(stdout.trim())? {
+        ValidatorOutput::Report { issues } => issues,
+        ValidatorOutput::Issues(issues) => issues,
+    })
+}
+
+fn to_issue(validator: &str, issue: ReportedIssue) -> Issue {
+    Issue {
+        kind: IssueKind::ValidatorIssue,
+        severity: match issue.severity {
+            ReportedSeverity::Error => IssueSeverity::Error,
+            ReportedSeverity::Warning => IssueSeverity::Warning,
+            ReportedSeverity::Info => IssueSeverity::Info,
+        },
+        component: match issue.component {
+            Some(component) => format!("{}: {}", validator, component),
+            None => format!("{} validator", validator),
+        },
+        description: issue.description,
+        auto_fixable: false,
+        suggested_fix: issue.suggested_fix,
+    }
+}
+
+fn failed(validator: &ValidatorConfig, problem: String) -> Issue {
+    Issue {
+        kind: IssueKind::ValidatorFailed,
+        severity: IssueSeverity::Warning,
+        component: format!("{} validator", validator.name),
+        description: format!("Validator '{}' {}", validator.name, problem),
+        auto_fixable: false,
+        suggested_fix: Some(format!(
+            "Check the [[validators]] entry for '{}' in catalyst.toml",
+            validator.name
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(name: &str, command: &[&str]) -> ValidatorConfig {
+        ValidatorConfig {
+            name: name.to_string(),
+            command: command.iter().map(|s| s.to_string()).collect(),
+            timeout_secs: 5,
+        }
+    }
+
+    #[test]
+    fn test_parse_output_accepts_both_shapes() {
+        let object = r#"{"issues": [{"severity": "error", "description": "bad",
+                         "component": "src/lib.rs"}]}"#;
+        let issues: Vec<Issue> = parse_output(object)
+            .unwrap()
+            .into_iter()
+            .map(|issue| to_issue("lint", issue))
+            .collect();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+        assert_eq!(issues[0].component, "lint: src/lib.rs");
+
+        let array = r#"[{"message": "meh"}]"#;
+        let issues = parse_output(array).unwrap();
+        assert!(matches!(issues[0].severity, ReportedSeverity::Warning));
+        assert_eq!(issues[0].description, "meh");
+
+        assert!(parse_output("not json").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_validator_reports_issues_and_failures() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let target = temp_dir.path();
+
+        // Non-zero exit is fine when the output is a report
+        let ok = validator(
+            "headers",
+            &[
+                "sh",
+                "-c",
+                r#"echo '{"issues":[{"description":"x"}]}'; exit 1"#,
+            ],
+        );
+        let issues = run_validator(target, &ok);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, IssueKind::ValidatorIssue);
+        assert_eq!(issues[0].component, "headers validator");
+
+        let garbage = validator("noisy", &["sh", "-c", "echo hello"]);
+        let issues = run_validator(target, &garbage);
+        assert_eq!(issues[0].kind, IssueKind::ValidatorFailed);
+
+        let missing = validator("gone", &["catalyst-test-no-such-validator"]);
+        let issues = run_validator(target, &missing);
+        assert_eq!(issues[0].kind, IssueKind::ValidatorFailed);
+        assert!(issues[0].description.contains("could not be run"));
+    }
+}
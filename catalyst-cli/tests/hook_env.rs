@@ -0,0 +1,90 @@
+//! End to end: `catalyst doctor --hook-env` runs the installed wrappers,
+//! which run the real hook binaries, without leaving those probe runs in
+//! the hook telemetry that `catalyst stats hooks` reads
+#![cfg(all(unix, feature = "sqlite"))]
+
+use catalyst_cli::binaries::HOOK_BINARIES;
+use catalyst_cli::hook_stats;
+use catalyst_cli::types::{CATALYST_VERSION, CLAUDE_DIR, TRACKER_STATE_DIR};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use tempfile::TempDir;
+
+/// The real binaries for the hooks init wires up by default, stand-ins that
+/// only answer `--version` for the rest
+fn install_binaries(home: &Path) {
+    let bin_dir = home.join(".claude-hooks").join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    for (name, _) in HOOK_BINARIES {
+        let path = bin_dir.join(name);
+        fs::write(
+            &path,
+            format!("#!/bin/sh\necho \"{} {}\"\n", name, CATALYST_VERSION),
+        )
+        .unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    fs::copy(
+        env!("CARGO_BIN_EXE_skill-activation-prompt"),
+        bin_dir.join("skill-activation-prompt"),
+    )
+    .unwrap();
+    fs::copy(
+        env!("CARGO_BIN_EXE_file-change-tracker"),
+        bin_dir.join("file-change-tracker"),
+    )
+    .unwrap();
+}
+
+fn catalyst(home: &Path, project: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_catalyst"))
+        .args(args)
+        .current_dir(project)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env_remove("CATALYST_TRACKER_BACKEND")
+        .output()
+        .unwrap()
+}
+
+fn recorded_runs(home: &Path) -> usize {
+    let state_dir = home.join(CLAUDE_DIR).join(TRACKER_STATE_DIR);
+    hook_stats::summarize(&state_dir, None)
+        .unwrap()
+        .iter()
+        .map(|stats| stats.runs)
+        .sum()
+}
+
+#[test]
+fn test_hook_env_check_is_not_recorded_as_hook_runs() {
+    let temp_dir = TempDir::new().unwrap();
+    let home = temp_dir.path().join("home");
+    let project = temp_dir.path().join("project");
+    fs::create_dir_all(project.join(".claude")).unwrap();
+    install_binaries(&home);
+
+    let init = catalyst(&home, &project, &["init"]);
+    assert!(
+        init.status.success(),
+        "init failed: {}",
+        String::from_utf8_lossy(&init.stderr)
+    );
+
+    let doctor = catalyst(&home, &project, &["doctor", "--hook-env"]);
+    let stdout = String::from_utf8_lossy(&doctor.stdout);
+    assert!(stdout.contains("skill-activation-prompt"), "{}", stdout);
+    assert_eq!(recorded_runs(&home), 0);
+
+    // The same empty-payload run outside the check is recorded as an error
+    Command::new(home.join(".claude-hooks/bin/skill-activation-prompt"))
+        .current_dir(&project)
+        .env("HOME", &home)
+        .env_remove(hook_stats::DIAGNOSTIC_RUN_ENV)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(recorded_runs(&home), 1);
+}
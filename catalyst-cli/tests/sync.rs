@@ -0,0 +1,74 @@
+//! End to end: a project set up by `catalyst init` must verify cleanly
+//! under `catalyst sync`
+//!
+//! Hook binaries are looked up in `~/.claude-hooks/bin`, so each run gets
+//! its own home directory holding stand-in binaries that only answer
+//! `--version`.
+#![cfg(unix)]
+
+use catalyst_cli::binaries::HOOK_BINARIES;
+use catalyst_cli::types::CATALYST_VERSION;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn install_stub_binaries(home: &Path) {
+    let bin_dir = home.join(".claude-hooks").join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    for (name, _) in HOOK_BINARIES {
+        let path = bin_dir.join(name);
+        fs::write(
+            &path,
+            format!("#!/bin/sh\necho \"{} {}\"\n", name, CATALYST_VERSION),
+        )
+        .unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+}
+
+fn catalyst(home: &Path, project: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_catalyst"))
+        .args(args)
+        .current_dir(project)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env_remove("CATALYST_TRACKER_BACKEND")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_sync_after_init_verifies_clean() {
+    let temp_dir = TempDir::new().unwrap();
+    let home = temp_dir.path().join("home");
+    let project = temp_dir.path().join("project");
+    fs::create_dir_all(project.join(".claude")).unwrap();
+    install_stub_binaries(&home);
+
+    let init = catalyst(&home, &project, &["init"]);
+    assert!(
+        init.status.success(),
+        "init failed: {}",
+        String::from_utf8_lossy(&init.stderr)
+    );
+
+    let sync = catalyst(&home, &project, &["sync"]);
+    let stdout = String::from_utf8_lossy(&sync.stdout);
+    assert_eq!(sync.status.code(), Some(0), "{}", stdout);
+    assert!(
+        stdout.contains("verify: Installation is healthy"),
+        "{}",
+        stdout
+    );
+
+    // Nothing for status to complain about either
+    let status = catalyst(&home, &project, &["status"]);
+    assert_eq!(
+        status.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&status.stdout)
+    );
+}
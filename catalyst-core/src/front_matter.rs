@@ -0,0 +1,120 @@
+//! Markdown front matter
+//!
+//! Skills, subagents, and slash commands all start with a `---` delimited
+//! block of `key: value` lines. Claude Code reads only flat fields, so this
+//! is not a YAML parser: it handles quoted values, folded continuation
+//! lines, and block scalars (`|`, `>`), which covers what those files use.
+
+use std::collections::BTreeMap;
+
+/// Split a markdown file into its front matter block and the body after it
+///
+/// # Returns
+///
+/// Returns `Ok(None)` if the file doesn't start with a `---` line, or
+/// `Err` describing the problem if the front matter is never closed
+pub fn split_front_matter(content: &str) -> std::result::Result<Option<(String, String)>, String> {
+    let content = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let Some(after_open) = content.strip_prefix("---\n") else {
+        return Ok(None);
+    };
+    let Some(close) = after_open.find("\n---\n").map(|i| (i, i + 5)).or_else(|| {
+        after_open
+            .strip_suffix("\n---")
+            .map(|block| (block.len(), after_open.len()))
+    }) else {
+        return Err("front matter is not closed with a '---' line".to_string());
+    };
+    Ok(Some((
+        after_open[..close.0].to_string(),
+        after_open[close.1..].to_string(),
+    )))
+}
+
+/// Read `key: value` lines, folding indented and block-scalar continuations
+///
+/// Keys not in `known` and repeated keys are reported in `problems`.
+pub fn parse_fields(
+    block: &str,
+    known: &[&str],
+    problems: &mut Vec<String>,
+) -> BTreeMap<String, String> {
+    let mut fields: BTreeMap<String, String> = BTreeMap::new();
+    let mut current: Option<String> = None;
+    for line in block.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            match current.as_ref().and_then(|key| fields.get_mut(key)) {
+                Some(value) => {
+                    if !value.is_empty() {
+                        value.push(' ');
+                    }
+                    value.push_str(line.trim());
+                }
+                None => problems.push(format!("unexpected indented line: {}", line.trim())),
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            problems.push(format!("front matter line isn't 'key: value': {}", line));
+            current = None;
+            continue;
+        };
+        let key = key.trim().to_string();
+        let value = value.trim();
+        // `|` and `>` start a block scalar whose text is on the next lines
+        let value = if value.starts_with(['|', '>']) {
+            ""
+        } else {
+            unquote(value)
+        };
+        if !known.contains(&key.as_str()) {
+            problems.push(format!(
+                "unknown field '{}' (expected {})",
+                key,
+                known.join(", ")
+            ));
+        }
+        if fields.insert(key.clone(), value.to_string()).is_some() {
+            problems.push(format!("field '{}' appears more than once", key));
+        }
+        current = Some(key);
+    }
+    fields
+}
+
+/// Strip one pair of matching single or double quotes
+pub fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|&quote| {
+            value
+                .strip_prefix(quote)
+                .and_then(|inner| inner.strip_suffix(quote))
+        })
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_with_folded_and_quoted_values() {
+        let content =
+            "---\nname: 'demo'\ndescription: >\n  Two\n  lines\nmodel: x\nmodel: y\n---\n# Body\n";
+        let (block, body) = split_front_matter(content).unwrap().unwrap();
+        assert_eq!(body, "# Body\n");
+
+        let mut problems = Vec::new();
+        let fields = parse_fields(&block, &["name", "description"], &mut problems);
+        assert_eq!(fields["name"], "demo");
+        assert_eq!(fields["description"], "Two lines");
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+
+        assert!(split_front_matter("# No front matter").unwrap().is_none());
+        assert!(split_front_matter("---\nname: x\n").is_err());
+    }
+}
@@ -0,0 +1,719 @@
+//! Filesystem operations that survive cross-device renames
+//!
+//! Atomic writes rely on `rename(2)`, which fails with `EXDEV` when source
+//! and destination are on different filesystems. Inside containers this
+//! happens even within a single directory: Docker bind-mounted files, and
+//! directories on overlayfs that were created in a lower layer, both refuse
+//! to be renamed over.
+//!
+//! Every persist or rename in Catalyst goes through this module so that an
+//! `EXDEV` failure falls back to copying instead of aborting:
+//!
+//! - Files are copied to a temporary sibling of the destination, fsynced,
+//!   and renamed within the destination directory.
+//! - Directories are copied into place file by file.
+//!
+//! Replacing a file keeps the mode and ownership of the file it replaces
+//! (see [`PermissionPolicy`]), so group-writable setups on shared machines
+//! survive regeneration. New files get a default mode instead of the `0600`
+//! that temporary files are created with.
+//!
+//! The operations that write are only reachable through an
+//! [`Fs<ReadWrite>`](Fs) handle. Commands that promise not to write (such
+//! as `catalyst status`) reach the filesystem through an
+//! [`Fs<ReadOnly>`](Fs) handle, which has no mutating methods at all.
+
+use std::fs;
+use std::io::{self, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+use tempfile::NamedTempFile;
+
+/// `EXDEV` ("Invalid cross-device link")
+#[cfg(unix)]
+const CROSS_DEVICE_ERROR: i32 = 18;
+
+/// `ERROR_NOT_SAME_DEVICE`
+#[cfg(windows)]
+const CROSS_DEVICE_ERROR: i32 = 17;
+
+/// Mode given to new files (and to replaced files under [`PermissionPolicy::Reset`])
+pub const DEFAULT_FILE_MODE: u32 = 0o644;
+
+/// Mode given to new scripts (and to replaced scripts under [`PermissionPolicy::Reset`])
+pub const DEFAULT_EXECUTABLE_MODE: u32 = 0o755;
+
+/// What to do with the permissions of a file that is being replaced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PermissionPolicy {
+    /// Keep the existing mode and ownership
+    ///
+    /// If the default mode is executable, execute bits are still added
+    /// wherever the existing mode grants read, so scripts keep working.
+    #[default]
+    Preserve,
+    /// Apply the default mode, discarding the existing one
+    Reset,
+}
+
+/// Mode and ownership to give a file being written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TargetMetadata {
+    mode: u32,
+    /// (uid, gid) of the file being replaced, if any
+    owner: Option<(u32, u32)>,
+}
+
+/// How a file reached its destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PersistMethod {
+    /// Renamed into place atomically
+    Renamed,
+    /// Copied because rename crossed a device boundary
+    Copied,
+}
+
+/// Check whether an error is a cross-device rename failure
+pub fn is_cross_device(e: &io::Error) -> bool {
+    #[cfg(any(unix, windows))]
+    {
+        e.raw_os_error() == Some(CROSS_DEVICE_ERROR)
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = e;
+        false
+    }
+}
+
+/// See [`Fs::write_atomic`]
+fn write_atomic(path: &Path, content: &[u8]) -> io::Result<PersistMethod> {
+    write_atomic_with(path, content, DEFAULT_FILE_MODE, PermissionPolicy::Preserve)
+}
+
+/// Write a file atomically with an explicit default mode and policy
+///
+/// # Arguments
+///
+/// * `path` - Destination file
+/// * `content` - Bytes to write
+/// * `default_mode` - Mode for a new file, or for any file under `Reset`
+/// * `policy` - What to do with an existing file's mode and ownership
+///
+/// # Errors
+///
+/// Same as [`Fs::write_atomic`].
+fn write_atomic_with(
+    path: &Path,
+    content: &[u8],
+    default_mode: u32,
+    policy: PermissionPolicy,
+) -> io::Result<PersistMethod> {
+    let dir = parent_dir(path);
+    let mut temp_file = NamedTempFile::new_in(dir)?;
+    temp_file.write_all(content)?;
+    temp_file.as_file().sync_all()?;
+    persist_with_policy(temp_file, path, default_mode, policy)
+}
+
+/// See [`Fs::write_file`]
+fn write_file(
+    path: &Path,
+    content: &[u8],
+    default_mode: u32,
+    policy: PermissionPolicy,
+) -> io::Result<()> {
+    let target = target_metadata(path, default_mode, policy);
+    fs::write(path, content)?;
+    apply_metadata(path, target)
+}
+
+/// Persist a temp file to its final location
+///
+/// Falls back to copy + fsync + rename-within-directory if the rename
+/// crosses a device boundary. The temp file is removed either way. An
+/// existing destination's mode and ownership are kept.
+fn persist(temp_file: NamedTempFile, destination: &Path) -> io::Result<PersistMethod> {
+    persist_with_policy(
+        temp_file,
+        destination,
+        DEFAULT_FILE_MODE,
+        PermissionPolicy::Preserve,
+    )
+}
+
+/// Persist a temp file with an explicit default mode and policy
+fn persist_with_policy(
+    temp_file: NamedTempFile,
+    destination: &Path,
+    default_mode: u32,
+    policy: PermissionPolicy,
+) -> io::Result<PersistMethod> {
+    let target = target_metadata(destination, default_mode, policy);
+    persist_with(temp_file, destination, target, |from, to| {
+        fs::rename(from, to)
+    })
+}
+
+fn persist_with<R>(
+    temp_file: NamedTempFile,
+    destination: &Path,
+    target: TargetMetadata,
+    rename: R,
+) -> io::Result<PersistMethod>
+where
+    R: Fn(&Path, &Path) -> io::Result<()>,
+{
+    // Set the final mode before the rename so the file never appears with
+    // the temp file's permissions
+    apply_metadata(temp_file.path(), target)?;
+
+    match rename(temp_file.path(), destination) {
+        Ok(()) => {
+            // The file is gone from its temp path; don't let Drop complain
+            let _ = temp_file.into_temp_path().keep();
+            Ok(PersistMethod::Renamed)
+        }
+        Err(e) if is_cross_device(&e) => {
+            copy_file_into_place(temp_file.path(), destination, Some(target), &rename)?;
+            // Dropping temp_file removes the original
+            Ok(PersistMethod::Copied)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// See [`Fs::rename`]
+fn rename(source: &Path, destination: &Path) -> io::Result<PersistMethod> {
+    rename_with(source, destination, |from, to| fs::rename(from, to))
+}
+
+fn rename_with<R>(source: &Path, destination: &Path, rename: R) -> io::Result<PersistMethod>
+where
+    R: Fn(&Path, &Path) -> io::Result<()>,
+{
+    match rename(source, destination) {
+        Ok(()) => Ok(PersistMethod::Renamed),
+        Err(e) if is_cross_device(&e) => {
+            if source.is_dir() {
+                // Renaming a staged copy of the tree would hit the same
+                // error, so copy straight into place
+                copy_dir_all(source, destination)?;
+                fs::remove_dir_all(source)?;
+            } else {
+                copy_file_into_place(source, destination, None, &rename)?;
+                fs::remove_file(source)?;
+            }
+            Ok(PersistMethod::Copied)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Copy a file to a temp sibling of `destination`, fsync, and rename it in
+///
+/// `fs::copy` carries the source's mode over; ownership is only restored
+/// when `target` is given.
+fn copy_file_into_place<R>(
+    source: &Path,
+    destination: &Path,
+    target: Option<TargetMetadata>,
+    rename: &R,
+) -> io::Result<()>
+where
+    R: Fn(&Path, &Path) -> io::Result<()>,
+{
+    let staged = NamedTempFile::new_in(parent_dir(destination))?;
+    fs::copy(source, staged.path())?;
+    if let Some(target) = target {
+        apply_metadata(staged.path(), target)?;
+    }
+    staged.as_file().sync_all()?;
+
+    rename(staged.path(), destination)?;
+    let _ = staged.into_temp_path().keep();
+
+    // Make the rename itself durable where directories can be fsynced
+    #[cfg(unix)]
+    if let Ok(dir) = fs::File::open(parent_dir(destination)) {
+        let _ = dir.sync_all();
+    }
+
+    Ok(())
+}
+
+/// Recursively copy a directory tree, preserving file permissions
+fn copy_dir_all(source: &Path, destination: &Path) -> io::Result<()> {
+    fs::create_dir_all(destination)?;
+
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = destination.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// See [`Fs::symlink`]
+fn symlink(original: &Path, link: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(original, link)
+    }
+
+    #[cfg(windows)]
+    {
+        // Relative targets resolve against the link's directory
+        if parent_dir(link).join(original).is_dir() {
+            std::os::windows::fs::symlink_dir(original, link)
+        } else {
+            std::os::windows::fs::symlink_file(original, link)
+        }
+    }
+}
+
+/// Permission bits of a file (Unix only)
+pub fn file_mode(path: &Path) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path)
+            .ok()
+            .map(|m| m.permissions().mode() & 0o7777)
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Describe what is unusual about a file mode, if anything
+///
+/// Group write access is deliberately not flagged: shared development
+/// servers rely on it.
+pub fn unusual_mode(mode: u32) -> Option<&'static str> {
+    if mode & 0o002 != 0 {
+        Some("world-writable")
+    } else if mode & 0o6000 != 0 {
+        Some("setuid or setgid")
+    } else if mode & 0o600 != 0o600 {
+        Some("not readable and writable by its owner")
+    } else {
+        None
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::ReadOnly {}
+    impl Sealed for super::ReadWrite {}
+}
+
+/// Access mode of an [`Fs`] handle: [`ReadOnly`] or [`ReadWrite`]
+pub trait Mode: sealed::Sealed {}
+
+/// Access mode that can only inspect the filesystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadOnly;
+
+/// Access mode that may also modify the filesystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadWrite;
+
+impl Mode for ReadOnly {}
+impl Mode for ReadWrite {}
+
+/// Filesystem handle whose type decides whether it can write
+///
+/// Reads work in every mode; writes exist only on `Fs<ReadWrite>`, so a
+/// code path handed an `Fs<ReadOnly>` can't modify anything through it:
+///
+/// ```compile_fail
+/// use catalyst_core::fs_ops::{Fs, ReadOnly};
+///
+/// let fs: Fs<ReadOnly> = Fs::read_only();
+/// fs.write_atomic(std::path::Path::new("settings.json"), b"{}");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Fs<M: Mode> {
+    _mode: PhantomData<M>,
+}
+
+impl Fs<ReadOnly> {
+    /// Handle for code that must never write
+    pub const fn read_only() -> Self {
+        Self { _mode: PhantomData }
+    }
+}
+
+impl Fs<ReadWrite> {
+    /// Handle for code that is allowed to write
+    pub const fn read_write() -> Self {
+        Self { _mode: PhantomData }
+    }
+
+    /// Read-only view of this handle, for passing to audit code paths
+    pub const fn as_read_only(&self) -> Fs<ReadOnly> {
+        Fs::read_only()
+    }
+
+    /// Write a file atomically (temp file in the same directory + rename)
+    ///
+    /// Keeps the mode and ownership of an existing file; new files get
+    /// [`DEFAULT_FILE_MODE`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the temp file can't be created or
+    /// written, or if both the rename and the copy fallback fail.
+    pub fn write_atomic(&self, path: &Path, content: &[u8]) -> io::Result<PersistMethod> {
+        write_atomic(path, content)
+    }
+
+    /// Write a file atomically with an explicit default mode and policy
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Fs::write_atomic`].
+    pub fn write_atomic_with(
+        &self,
+        path: &Path,
+        content: &[u8],
+        default_mode: u32,
+        policy: PermissionPolicy,
+    ) -> io::Result<PersistMethod> {
+        write_atomic_with(path, content, default_mode, policy)
+    }
+
+    /// Write a file in place (not atomically), applying a permission policy
+    ///
+    /// For files that are regenerated wholesale and read only by Catalyst's
+    /// own tools, such as hook wrapper scripts.
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the write or the mode change fails.
+    pub fn write_file(
+        &self,
+        path: &Path,
+        content: &[u8],
+        default_mode: u32,
+        policy: PermissionPolicy,
+    ) -> io::Result<()> {
+        write_file(path, content, default_mode, policy)
+    }
+
+    /// Persist a temp file to its final location
+    ///
+    /// Falls back to copy + fsync + rename-within-directory if the rename
+    /// crosses a device boundary. An existing destination's mode and
+    /// ownership are kept.
+    pub fn persist(
+        &self,
+        temp_file: NamedTempFile,
+        destination: &Path,
+    ) -> io::Result<PersistMethod> {
+        persist(temp_file, destination)
+    }
+
+    /// Persist a temp file with an explicit default mode and policy
+    pub fn persist_with_policy(
+        &self,
+        temp_file: NamedTempFile,
+        destination: &Path,
+        default_mode: u32,
+        policy: PermissionPolicy,
+    ) -> io::Result<PersistMethod> {
+        persist_with_policy(temp_file, destination, default_mode, policy)
+    }
+
+    /// Move a file or directory, copying if the rename crosses a device
+    /// boundary
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the rename fails for another
+    /// reason, or if the copy fallback fails.
+    pub fn rename(&self, source: &Path, destination: &Path) -> io::Result<PersistMethod> {
+        rename(source, destination)
+    }
+
+    /// Recursively copy a directory tree, preserving file permissions
+    pub fn copy_dir_all(&self, source: &Path, destination: &Path) -> io::Result<()> {
+        copy_dir_all(source, destination)
+    }
+
+    /// Create a symbolic link at `link` pointing to `original`
+    ///
+    /// On Windows a directory or file link is created depending on what
+    /// `original` is; this needs Developer Mode or administrator rights.
+    pub fn symlink(&self, original: &Path, link: &Path) -> io::Result<()> {
+        symlink(original, link)
+    }
+
+    /// Create a directory and any missing parents
+    pub fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(path)
+    }
+}
+
+impl<M: Mode> Fs<M> {
+    pub fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    pub fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    pub fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    pub fn metadata(&self, path: &Path) -> io::Result<fs::Metadata> {
+        fs::metadata(path)
+    }
+
+    /// Metadata of a path without following a final symlink
+    pub fn symlink_metadata(&self, path: &Path) -> io::Result<fs::Metadata> {
+        fs::symlink_metadata(path)
+    }
+
+    /// Open a file for reading
+    pub fn open(&self, path: &Path) -> io::Result<fs::File> {
+        fs::File::open(path)
+    }
+
+    pub fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    pub fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    pub fn read_dir(&self, path: &Path) -> io::Result<fs::ReadDir> {
+        fs::read_dir(path)
+    }
+
+    /// See [`file_mode`]
+    pub fn file_mode(&self, path: &Path) -> Option<u32> {
+        file_mode(path)
+    }
+}
+
+/// Decide the mode and ownership for a file about to be written
+fn target_metadata(
+    destination: &Path,
+    default_mode: u32,
+    policy: PermissionPolicy,
+) -> TargetMetadata {
+    #[cfg(unix)]
+    if policy == PermissionPolicy::Preserve {
+        use std::os::unix::fs::MetadataExt;
+
+        if let Ok(metadata) = fs::metadata(destination) {
+            if metadata.is_file() {
+                let mut mode = metadata.mode() & 0o7777;
+                if default_mode & 0o111 != 0 {
+                    // Add execute wherever read is granted (like chmod +x)
+                    mode |= (mode & 0o444) >> 2;
+                }
+                return TargetMetadata {
+                    mode,
+                    owner: Some((metadata.uid(), metadata.gid())),
+                };
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    let _ = (destination, policy);
+
+    TargetMetadata {
+        mode: default_mode,
+        owner: None,
+    }
+}
+
+/// Apply a target mode and ownership to a file
+///
+/// Ownership is restored on a best-effort basis: only root can give a file
+/// to another user, but the group can still be kept if we belong to it.
+fn apply_metadata(path: &Path, target: TargetMetadata) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{chown, MetadataExt, PermissionsExt};
+
+        // chown clears setuid/setgid, so it has to come before chmod
+        if let Some((uid, gid)) = target.owner {
+            let current = fs::metadata(path)?;
+            if (current.uid(), current.gid()) != (uid, gid)
+                && chown(path, Some(uid), Some(gid)).is_err()
+            {
+                let _ = chown(path, None, Some(gid));
+            }
+        }
+        fs::set_permissions(path, fs::Permissions::from_mode(target.mode))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (path, target.mode, target.owner);
+        Ok(())
+    }
+}
+
+fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Rename that fails with EXDEV unless both paths share a directory,
+    /// mimicking a bind mount
+    fn cross_device_rename(from: &Path, to: &Path) -> io::Result<()> {
+        if from.parent() == to.parent() {
+            fs::rename(from, to)
+        } else {
+            Err(io::Error::from_raw_os_error(CROSS_DEVICE_ERROR))
+        }
+    }
+
+    #[test]
+    fn test_persist_falls_back_on_exdev() {
+        let temp_dir = TempDir::new().unwrap();
+        let staging = temp_dir.path().join("staging");
+        let target = temp_dir.path().join("target");
+        fs::create_dir_all(&staging).unwrap();
+        fs::create_dir_all(&target).unwrap();
+
+        let mut temp_file = NamedTempFile::new_in(&staging).unwrap();
+        temp_file.write_all(b"{\"hooks\": {}}").unwrap();
+        let temp_path = temp_file.path().to_path_buf();
+
+        let destination = target.join("settings.json");
+        let metadata = target_metadata(&destination, DEFAULT_FILE_MODE, PermissionPolicy::Preserve);
+        let method = persist_with(temp_file, &destination, metadata, cross_device_rename).unwrap();
+
+        assert_eq!(method, PersistMethod::Copied);
+        assert_eq!(fs::read_to_string(&destination).unwrap(), "{\"hooks\": {}}");
+        assert!(!temp_path.exists());
+        // No leftover staging files next to the destination
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_rename_directory_falls_back_on_exdev() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("a").join("skill");
+        let destination = temp_dir.path().join("b").join("skill");
+        fs::create_dir_all(source.join("resources")).unwrap();
+        fs::create_dir_all(destination.parent().unwrap()).unwrap();
+        fs::write(source.join("SKILL.md"), "# Skill").unwrap();
+        fs::write(source.join("resources/guide.md"), "guide").unwrap();
+
+        let method = rename_with(&source, &destination, cross_device_rename).unwrap();
+
+        assert_eq!(method, PersistMethod::Copied);
+        assert!(!source.exists());
+        assert_eq!(
+            fs::read_to_string(destination.join("resources/guide.md")).unwrap(),
+            "guide"
+        );
+    }
+
+    #[test]
+    fn test_other_rename_errors_propagate() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = rename(
+            &temp_dir.path().join("missing"),
+            &temp_dir.path().join("dest"),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("file.json");
+
+        assert_eq!(write_atomic(&path, b"one").unwrap(), PersistMethod::Renamed);
+        write_atomic(&path, b"two").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_preserves_mode() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("settings.json");
+
+        write_atomic(&path, b"{}").unwrap();
+        assert_eq!(file_mode(&path), Some(DEFAULT_FILE_MODE));
+
+        // A shared, group-writable file stays group-writable
+        set_mode(&path, 0o664);
+        write_atomic(&path, b"{\"hooks\": {}}").unwrap();
+        assert_eq!(file_mode(&path), Some(0o664));
+
+        write_atomic_with(&path, b"{}", DEFAULT_FILE_MODE, PermissionPolicy::Reset).unwrap();
+        assert_eq!(file_mode(&path), Some(DEFAULT_FILE_MODE));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_file_keeps_scripts_executable() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("hook.sh");
+        fs::write(&path, "old").unwrap();
+        set_mode(&path, 0o660);
+
+        write_file(
+            &path,
+            b"new",
+            DEFAULT_EXECUTABLE_MODE,
+            PermissionPolicy::Preserve,
+        )
+        .unwrap();
+        assert_eq!(file_mode(&path), Some(0o770));
+
+        write_file(
+            &path,
+            b"new",
+            DEFAULT_EXECUTABLE_MODE,
+            PermissionPolicy::Reset,
+        )
+        .unwrap();
+        assert_eq!(file_mode(&path), Some(DEFAULT_EXECUTABLE_MODE));
+    }
+
+    #[test]
+    fn test_unusual_mode() {
+        assert_eq!(unusual_mode(0o644), None);
+        assert_eq!(unusual_mode(0o775), None);
+        assert_eq!(unusual_mode(0o666), Some("world-writable"));
+        assert_eq!(unusual_mode(0o4755), Some("setuid or setgid"));
+        assert!(unusual_mode(0o444).is_some());
+    }
+
+    #[cfg(unix)]
+    fn set_mode(path: &Path, mode: u32) {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+    }
+}
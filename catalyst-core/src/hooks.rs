@@ -0,0 +1,475 @@
+//! Hook payloads and responses
+//!
+//! Claude Code runs each hook with a JSON payload on stdin and reads an
+//! optional JSON response from stdout. Every Catalyst hook binary parses
+//! [`HookInput`] and prints [`HookResponse`], so they all agree on field
+//! names. The names follow Claude Code's hooks reference: snake_case in the
+//! payload, camelCase in the response.
+//!
+//! One input type covers every event. The fields every event sends come
+//! first; the rest are only set for the events noted on them. Every field
+//! is optional, so a hook given a trimmed-down payload (by hand, or by an
+//! older Claude Code) still parses it and decides what it can do without.
+//!
+//! Responses are easiest to build with [`HookResponse::builder`], which
+//! turns a [`HookDecision`] into the fields the event understands:
+//!
+//! ```
+//! use catalyst_core::hooks::{HookDecision, HookResponse};
+//! use catalyst_core::settings::HookEvent;
+//!
+//! let response = HookResponse::builder(HookEvent::PostToolUse)
+//!     .decision(HookDecision::Block)
+//!     .reason("cargo check failed")
+//!     .additional_context("error[E0308]: mismatched types")
+//!     .build();
+//! assert_eq!(response.exit_code(), 0);
+//! ```
+
+use crate::settings::HookEvent;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Environment variable Claude Code sets to the project root
+pub const PROJECT_DIR_ENV: &str = "CLAUDE_PROJECT_DIR";
+
+/// Exit code after printing a response; Claude Code ignores stdout JSON
+/// on any other code
+pub const EXIT_SUCCESS: i32 = 0;
+
+/// Exit code that blocks without a JSON response; stderr goes to Claude
+pub const EXIT_BLOCKING_ERROR: i32 = 2;
+
+/// Payload Claude Code sends a hook on stdin
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HookInput {
+    /// Current session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Path of the session's JSONL transcript
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript_path: Option<String>,
+
+    /// Directory the hook was triggered from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+
+    /// Event name, such as `PostToolUse` (see [`HookInput::event`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hook_event_name: Option<String>,
+
+    /// Permission mode of the session (`default`, `plan`, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission_mode: Option<String>,
+
+    /// UserPromptSubmit: the prompt text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+
+    /// PreToolUse and PostToolUse: the tool being called
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+
+    /// PreToolUse and PostToolUse: the tool's arguments
+    ///
+    /// Also read from `tool_args`, which early tracker payloads used.
+    #[serde(default, alias = "tool_args", skip_serializing_if = "Option::is_none")]
+    pub tool_input: Option<Map<String, Value>>,
+
+    /// PostToolUse: what the tool returned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_response: Option<Value>,
+
+    /// Notification: the message shown to the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// Stop and SubagentStop: set when Claude is already continuing because
+    /// a Stop hook blocked
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stop_hook_active: bool,
+
+    /// SessionStart: `startup`, `resume`, `clear`, or `compact`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// SessionEnd: why the session ended
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl HookInput {
+    /// The event, if `hook_event_name` is one Catalyst knows
+    pub fn event(&self) -> Option<HookEvent> {
+        self.hook_event_name
+            .as_deref()
+            .and_then(|name| HookEvent::from_str(name).ok())
+    }
+
+    /// A string argument of the tool call, such as `file_path`
+    pub fn tool_input_str(&self, key: &str) -> Option<&str> {
+        self.tool_input.as_ref()?.get(key)?.as_str()
+    }
+
+    /// Project root: `$CLAUDE_PROJECT_DIR`, then `cwd`, then `.`
+    pub fn project_dir(&self) -> PathBuf {
+        std::env::var_os(PROJECT_DIR_ENV)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| self.cwd.as_ref().map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+}
+
+/// JSON a hook prints on stdout
+///
+/// Fields left as None are omitted, and an all-None response is `{}`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookResponse {
+    /// `false` stops Claude entirely, whatever the event
+    #[serde(rename = "continue", skip_serializing_if = "Option::is_none")]
+    pub continue_: Option<bool>,
+
+    /// Shown to the user when `continue` is false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_reason: Option<String>,
+
+    /// Hide the hook's stdout from the transcript
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suppress_output: Option<bool>,
+
+    /// Warning shown to the user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_message: Option<String>,
+
+    /// `block` for Stop, SubagentStop, PostToolUse, and UserPromptSubmit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision: Option<Decision>,
+
+    /// Why, shown to Claude when blocking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Output only some events understand
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hook_specific_output: Option<HookSpecificOutput>,
+}
+
+impl HookResponse {
+    /// Start a response to `event`
+    pub fn builder(event: HookEvent) -> HookResponseBuilder {
+        HookResponseBuilder {
+            event,
+            decision: HookDecision::Allow,
+            reason: None,
+            additional_context: None,
+            system_message: None,
+            suppress_output: false,
+        }
+    }
+
+    /// Exit code to use after printing this response
+    ///
+    /// Always [`EXIT_SUCCESS`]: the JSON carries the decision, and Claude
+    /// Code only reads it when the hook exits 0.
+    pub fn exit_code(&self) -> i32 {
+        EXIT_SUCCESS
+    }
+
+    /// Print the response on stdout and return the exit code to use
+    pub fn emit(&self) -> i32 {
+        // Serialization should never fail for these types - if it does, it's a bug
+        let json = serde_json::to_string_pretty(self)
+            .expect("Failed to serialize hook response - this is a bug");
+        println!("{}", json);
+        self.exit_code()
+    }
+}
+
+/// What a hook wants done, independent of the event it answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookDecision {
+    /// Let Claude carry on; PreToolUse approves the call outright
+    Allow,
+    /// Stop the tool call, the prompt, or Claude stopping, and tell Claude why
+    Block,
+    /// Carry on, but with feedback; PreToolUse asks the user
+    Suggest,
+}
+
+impl HookDecision {
+    /// Exit code for a hook that reports this decision on stderr instead of
+    /// printing a response
+    pub fn exit_code(self) -> i32 {
+        match self {
+            HookDecision::Block => EXIT_BLOCKING_ERROR,
+            HookDecision::Allow | HookDecision::Suggest => EXIT_SUCCESS,
+        }
+    }
+}
+
+/// Builder for a [`HookResponse`], from [`HookResponse::builder`]
+#[derive(Debug, Clone)]
+pub struct HookResponseBuilder {
+    event: HookEvent,
+    decision: HookDecision,
+    reason: Option<String>,
+    additional_context: Option<String>,
+    system_message: Option<String>,
+    suppress_output: bool,
+}
+
+impl HookResponseBuilder {
+    /// Decision to report (default [`HookDecision::Allow`])
+    pub fn decision(mut self, decision: HookDecision) -> Self {
+        self.decision = decision;
+        self
+    }
+
+    /// Why: sent with Block, and with every PreToolUse decision
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Text for Claude's context
+    ///
+    /// Only UserPromptSubmit, PostToolUse, and SessionStart accept it; for
+    /// other events it's shown to the user as the system message instead,
+    /// unless one was set.
+    pub fn additional_context(mut self, context: impl Into<String>) -> Self {
+        self.additional_context = Some(context.into());
+        self
+    }
+
+    /// Warning shown to the user
+    pub fn system_message(mut self, message: impl Into<String>) -> Self {
+        self.system_message = Some(message.into());
+        self
+    }
+
+    /// Hide the hook's stdout from the transcript
+    pub fn suppress_output(mut self, suppress: bool) -> Self {
+        self.suppress_output = suppress;
+        self
+    }
+
+    /// The response, with the decision mapped onto the event's fields
+    pub fn build(self) -> HookResponse {
+        let mut specific = HookSpecificOutput::new(self.event);
+        let mut response = HookResponse {
+            suppress_output: self.suppress_output.then_some(true),
+            system_message: self.system_message,
+            ..HookResponse::default()
+        };
+
+        if self.event == HookEvent::PreToolUse {
+            specific.permission_decision = Some(match self.decision {
+                HookDecision::Allow => PermissionDecision::Allow,
+                HookDecision::Block => PermissionDecision::Deny,
+                HookDecision::Suggest => PermissionDecision::Ask,
+            });
+            specific.permission_decision_reason = self.reason;
+        } else if self.decision == HookDecision::Block {
+            response.decision = Some(Decision::Block);
+            response.reason = self.reason;
+        }
+
+        if let Some(context) = self.additional_context {
+            if accepts_additional_context(self.event) {
+                specific.additional_context = Some(context);
+            } else if response.system_message.is_none() {
+                response.system_message = Some(context);
+            }
+        }
+
+        if specific != HookSpecificOutput::new(self.event) {
+            response.hook_specific_output = Some(specific);
+        }
+        response
+    }
+}
+
+/// Events whose `hookSpecificOutput` takes `additionalContext`
+fn accepts_additional_context(event: HookEvent) -> bool {
+    matches!(
+        event,
+        HookEvent::UserPromptSubmit | HookEvent::PostToolUse | HookEvent::SessionStart
+    )
+}
+
+/// Top-level `decision` of a [`HookResponse`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Decision {
+    /// Stop Claude from stopping, or feed `reason` back after a tool call
+    Block,
+    /// Older PreToolUse spelling of `permissionDecision: allow`
+    Approve,
+}
+
+/// `hookSpecificOutput` of a [`HookResponse`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookSpecificOutput {
+    /// Event this output answers; Claude Code ignores a mismatch
+    pub hook_event_name: HookEvent,
+
+    /// UserPromptSubmit, PostToolUse, and SessionStart: text added to
+    /// Claude's context
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_context: Option<String>,
+
+    /// PreToolUse: whether the tool call may run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission_decision: Option<PermissionDecision>,
+
+    /// PreToolUse: why, shown to Claude for `deny` and to the user otherwise
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission_decision_reason: Option<String>,
+}
+
+impl HookSpecificOutput {
+    /// Output for `event` with nothing set yet
+    pub fn new(event: HookEvent) -> Self {
+        Self {
+            hook_event_name: event,
+            additional_context: None,
+            permission_decision: None,
+            permission_decision_reason: None,
+        }
+    }
+}
+
+/// PreToolUse `permissionDecision`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionDecision {
+    /// Run the tool without asking
+    Allow,
+    /// Refuse the call
+    Deny,
+    /// Ask the user
+    Ask,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_hook_input_parses_each_event_shape() {
+        let input: HookInput = serde_json::from_value(json!({
+            "session_id": "abc",
+            "transcript_path": "/t.jsonl",
+            "cwd": "/project",
+            "hook_event_name": "PostToolUse",
+            "tool_name": "Edit",
+            "tool_input": {"file_path": "/project/src/main.rs"},
+            "tool_response": {"success": true}
+        }))
+        .unwrap();
+        assert_eq!(input.event(), Some(HookEvent::PostToolUse));
+        assert_eq!(
+            input.tool_input_str("file_path"),
+            Some("/project/src/main.rs")
+        );
+
+        // The tracker's old field name still works
+        let legacy: HookInput =
+            serde_json::from_value(json!({"tool_args": {"file_path": "/a.ts"}})).unwrap();
+        assert_eq!(legacy.tool_input_str("file_path"), Some("/a.ts"));
+
+        let stop: HookInput = serde_json::from_value(json!({
+            "hook_event_name": "Stop",
+            "stop_hook_active": true,
+            "future_field": 1
+        }))
+        .unwrap();
+        assert!(stop.stop_hook_active);
+        assert_eq!(stop.prompt, None);
+    }
+
+    #[test]
+    fn test_hook_response_uses_claude_code_names() {
+        let response = HookResponse {
+            decision: Some(Decision::Block),
+            reason: Some("tests fail".to_string()),
+            hook_specific_output: Some(HookSpecificOutput {
+                permission_decision: Some(PermissionDecision::Deny),
+                ..HookSpecificOutput::new(HookEvent::PreToolUse)
+            }),
+            continue_: Some(true),
+            ..HookResponse::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({
+                "continue": true,
+                "decision": "block",
+                "reason": "tests fail",
+                "hookSpecificOutput": {
+                    "hookEventName": "PreToolUse",
+                    "permissionDecision": "deny"
+                }
+            })
+        );
+        assert_eq!(
+            serde_json::to_string(&HookResponse::default()).unwrap(),
+            "{}"
+        );
+    }
+
+    #[test]
+    fn test_builder_maps_decision_per_event() {
+        let denied = HookResponse::builder(HookEvent::PreToolUse)
+            .decision(HookDecision::Block)
+            .reason("protected")
+            .build();
+        assert_eq!(
+            serde_json::to_value(&denied).unwrap(),
+            json!({"hookSpecificOutput": {
+                "hookEventName": "PreToolUse",
+                "permissionDecision": "deny",
+                "permissionDecisionReason": "protected"
+            }})
+        );
+
+        let blocked = HookResponse::builder(HookEvent::PostToolUse)
+            .decision(HookDecision::Block)
+            .reason("errors")
+            .additional_context("details")
+            .suppress_output(true)
+            .build();
+        assert_eq!(
+            serde_json::to_value(&blocked).unwrap(),
+            json!({
+                "decision": "block",
+                "reason": "errors",
+                "suppressOutput": true,
+                "hookSpecificOutput": {
+                    "hookEventName": "PostToolUse",
+                    "additionalContext": "details"
+                }
+            })
+        );
+
+        // Stop has no additionalContext, so a suggestion reaches the user
+        let suggested = HookResponse::builder(HookEvent::Stop)
+            .decision(HookDecision::Suggest)
+            .reason("unused")
+            .additional_context("tests fail")
+            .build();
+        assert_eq!(
+            serde_json::to_value(&suggested).unwrap(),
+            json!({"systemMessage": "tests fail"})
+        );
+        assert_eq!(suggested.exit_code(), EXIT_SUCCESS);
+        assert_eq!(HookDecision::Block.exit_code(), EXIT_BLOCKING_ERROR);
+    }
+}
@@ -0,0 +1,144 @@
+//! Lenient parsing for hand-edited JSON files
+//!
+//! settings.json and skill-rules.json are edited by hand, and people add
+//! `//` and `/* */` comments and leave trailing commas behind. Both are
+//! accepted here (the "JSONC" dialect VS Code uses for its own settings).
+//! Catalyst only ever writes strict JSON.
+//!
+//! Comments and trailing commas are blanked out rather than removed, so
+//! line numbers in parse errors still point into the original file.
+
+use serde::de::DeserializeOwned;
+
+/// Blank out comments and trailing commas, leaving strict JSON
+///
+/// Newlines inside block comments are kept. Text inside strings is never
+/// touched, so `"https://example.com"` survives.
+///
+/// # Arguments
+///
+/// * `input` - JSON text that may contain comments and trailing commas
+pub fn strip(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    // Byte offset in `out` of a comma after a value that only whitespace
+    // or comments have followed so far
+    let mut pending_comma: Option<usize> = None;
+    // Last character outside whitespace and comments
+    let mut last = '\0';
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                pending_comma = None;
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                out.push(escaped);
+                            }
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+                last = '"';
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                out.push(' ');
+                while let Some(&next) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+                    chars.next();
+                    out.push(' ');
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                out.push_str("  ");
+                let mut previous = '\0';
+                for c in chars.by_ref() {
+                    out.push(if c == '\n' { '\n' } else { ' ' });
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            ',' => {
+                // `[,]` and `[1,,]` stay invalid
+                pending_comma = (!matches!(last, '[' | '{' | ',')).then_some(out.len());
+                last = c;
+                out.push(c);
+            }
+            ']' | '}' => {
+                if let Some(offset) = pending_comma.take() {
+                    out.replace_range(offset..offset + 1, " ");
+                }
+                last = c;
+                out.push(c);
+            }
+            c if c.is_whitespace() => out.push(c),
+            _ => {
+                pending_comma = None;
+                last = c;
+                out.push(c);
+            }
+        }
+    }
+
+    out
+}
+
+/// Deserialize JSON that may contain comments and trailing commas
+///
+/// # Errors
+///
+/// Returns the `serde_json` error if the text is invalid even after
+/// comments and trailing commas are removed
+pub fn from_str<T: DeserializeOwned>(input: &str) -> serde_json::Result<T> {
+    serde_json::from_str(&strip(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_accepts_comments_and_trailing_commas() {
+        let input = r#"// Customize pathPatterns for your project structure
+{
+  /* block
+     comment */
+  "url": "https://example.com/*not a comment*/", // trailing
+  "list": [1, 2,],
+  "nested": {"a": "\"//\"",},
+}
+"#;
+        let value: Value = from_str(input).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "url": "https://example.com/*not a comment*/",
+                "list": [1, 2],
+                "nested": {"a": "\"//\""}
+            })
+        );
+        assert_eq!(strip(input).lines().count(), input.lines().count());
+    }
+
+    #[test]
+    fn test_errors_keep_original_line_numbers() {
+        let input = "{\n  /* one\n     two */\n  \"a\": 1,\n  \"b\": oops\n}";
+        let error = from_str::<Value>(input).unwrap_err();
+        assert_eq!(error.line(), 5);
+
+        // Only a comma after a value can be dropped
+        assert!(from_str::<Value>("[,]").is_err());
+        assert!(from_str::<Value>("[1,,]").is_err());
+    }
+}
@@ -4,8 +4,30 @@
 //!
 //! This crate provides:
 //! - Settings management (Phase 2.6)
+//! - Cross-device safe filesystem operations
+//! - Lenient (JSONC) parsing for hand-edited JSON files
+//! - Hook payload and response types
+//! - Skill manifests
 //! - Shared utilities
 //! - Common data structures
 
+// Markdown front matter in skills, agents, and commands
+pub mod front_matter;
+
+// Cross-device safe atomic writes and renames
+pub mod fs_ops;
+
+// Hook stdin payloads and stdout responses
+pub mod hooks;
+
+// Comment- and trailing-comma-tolerant JSON parsing
+pub mod jsonc;
+
 // Phase 2.6: Settings management
 pub mod settings;
+
+// Schema validation with value locations
+pub mod settings_schema;
+
+// Skill metadata from SKILL.md front matter and skill.toml
+pub mod skill_manifest;
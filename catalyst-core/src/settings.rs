@@ -27,20 +27,23 @@
 //! # }
 //! ```
 
+use crate::fs_ops::{Fs, Mode};
+use crate::jsonc;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fs;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 /// Find the closest match from a list of valid options using Levenshtein distance
 ///
 /// Returns Some(closest_match) if a suggestion is found within reasonable distance,
 /// None otherwise. Uses a threshold of 3 edits to avoid suggesting unrelated strings.
-fn find_closest_match<'a>(input: &str, valid_options: &[&'a str]) -> Option<&'a str> {
+pub fn find_closest_match<'a>(input: &str, valid_options: &[&'a str]) -> Option<&'a str> {
     let threshold = 3; // Maximum edit distance for suggestions
 
     valid_options
@@ -56,54 +59,172 @@ fn find_closest_match<'a>(input: &str, valid_options: &[&'a str]) -> Option<&'a
 pub enum HookEvent {
     /// Triggered when user submits a prompt
     UserPromptSubmit,
+    /// Triggered before a tool is used (can block the tool call)
+    PreToolUse,
     /// Triggered after a tool is used
     PostToolUse,
+    /// Triggered when Claude Code sends a notification (e.g., waiting for input)
+    Notification,
     /// Triggered when the conversation stops
     Stop,
+    /// Triggered when a subagent (Task tool) finishes
+    SubagentStop,
+    /// Triggered when a session starts or resumes
+    SessionStart,
+    /// Triggered when a session ends
+    SessionEnd,
 }
 
-impl fmt::Display for HookEvent {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl HookEvent {
+    /// Every hook event, in the order Claude Code documents them
+    pub const ALL: &'static [HookEvent] = &[
+        HookEvent::UserPromptSubmit,
+        HookEvent::PreToolUse,
+        HookEvent::PostToolUse,
+        HookEvent::Notification,
+        HookEvent::Stop,
+        HookEvent::SubagentStop,
+        HookEvent::SessionStart,
+        HookEvent::SessionEnd,
+    ];
+
+    /// Event name as it appears in settings.json
+    pub fn as_str(&self) -> &'static str {
         match self {
-            HookEvent::UserPromptSubmit => write!(f, "UserPromptSubmit"),
-            HookEvent::PostToolUse => write!(f, "PostToolUse"),
-            HookEvent::Stop => write!(f, "Stop"),
+            HookEvent::UserPromptSubmit => "UserPromptSubmit",
+            HookEvent::PreToolUse => "PreToolUse",
+            HookEvent::PostToolUse => "PostToolUse",
+            HookEvent::Notification => "Notification",
+            HookEvent::Stop => "Stop",
+            HookEvent::SubagentStop => "SubagentStop",
+            HookEvent::SessionStart => "SessionStart",
+            HookEvent::SessionEnd => "SessionEnd",
         }
     }
+
+    /// Whether Claude Code applies `matcher` for this event
+    ///
+    /// Tool events match against the tool name and SessionStart matches
+    /// against the start source (`startup`, `resume`, `clear`, `compact`).
+    /// Other events ignore the matcher.
+    pub fn uses_matcher(&self) -> bool {
+        matches!(
+            self,
+            HookEvent::PreToolUse | HookEvent::PostToolUse | HookEvent::SessionStart
+        )
+    }
+}
+
+impl fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
 impl FromStr for HookEvent {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        match s {
-            "UserPromptSubmit" => Ok(HookEvent::UserPromptSubmit),
-            "PostToolUse" => Ok(HookEvent::PostToolUse),
-            "Stop" => Ok(HookEvent::Stop),
-            _ => {
-                // Find closest match for suggestion
-                let valid_events = ["UserPromptSubmit", "PostToolUse", "Stop"];
-                let suggestion = find_closest_match(s, &valid_events);
+        if let Some(event) = HookEvent::ALL.iter().find(|e| e.as_str() == s) {
+            return Ok(*event);
+        }
 
-                if let Some(closest) = suggestion {
-                    anyhow::bail!(
-                        "Unknown event '{}'. Did you mean '{}'? Valid events: {}",
-                        s,
-                        closest,
-                        valid_events.join(", ")
-                    );
-                } else {
-                    anyhow::bail!(
-                        "Unknown event '{}'. Valid events: {}",
-                        s,
-                        valid_events.join(", ")
-                    );
-                }
+        // Find closest match for suggestion
+        let valid_events: Vec<&str> = HookEvent::ALL.iter().map(|e| e.as_str()).collect();
+        let suggestion = find_closest_match(s, &valid_events);
+
+        if let Some(closest) = suggestion {
+            anyhow::bail!(
+                "Unknown event '{}'. Did you mean '{}'? Valid events: {}",
+                s,
+                closest,
+                valid_events.join(", ")
+            );
+        } else {
+            anyhow::bail!(
+                "Unknown event '{}'. Valid events: {}",
+                s,
+                valid_events.join(", ")
+            );
+        }
+    }
+}
+
+/// Validate a hook matcher for an event
+///
+/// The matcher must be a valid regex. For SessionStart it must also match at
+/// least one start source, since anything else would never fire.
+fn validate_matcher(event: HookEvent, matcher: &str) -> Result<()> {
+    use constants::SESSION_START_SOURCES;
+
+    let regex = regex::Regex::new(matcher).context(format!(
+        "Invalid matcher regex in {} hook: {}",
+        event, matcher
+    ))?;
+
+    if event == HookEvent::SessionStart
+        && !SESSION_START_SOURCES.iter().any(|src| regex.is_match(src))
+    {
+        anyhow::bail!(
+            "SessionStart matcher '{}' never matches. Valid sources: {}",
+            matcher,
+            SESSION_START_SOURCES.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Merge unmodeled keys, with `other` taking precedence
+///
+/// Objects present on both sides (e.g., `env`) are merged key by key so that
+/// layering settings adds variables rather than replacing the whole object.
+fn merge_extra(base: &mut Map<String, Value>, other: Map<String, Value>) {
+    for (key, value) in other {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Object(existing)), Value::Object(incoming)) => {
+                existing.extend(incoming);
+            }
+            (_, value) => {
+                base.insert(key, value);
             }
         }
     }
 }
 
+/// Append rules from `other` that `base` doesn't already have, in order
+fn merge_rules(base: &mut Vec<String>, other: Vec<String>) {
+    let mut seen: HashSet<String> = base.iter().cloned().collect();
+    for rule in other {
+        if seen.insert(rule.clone()) {
+            base.push(rule);
+        }
+    }
+}
+
+/// Check the shape of one permission rule: `Tool` or `Tool(specifier)`
+fn validate_permission_rule(list: &str, rule: &str) -> Result<()> {
+    if rule.trim().is_empty() {
+        anyhow::bail!("Empty rule in permissions.{}", list);
+    }
+    if let Some(open) = rule.find('(') {
+        if open == 0 || !rule.ends_with(')') {
+            anyhow::bail!(
+                "Invalid permissions.{} rule '{}': expected 'Tool' or 'Tool(specifier)'",
+                list,
+                rule
+            );
+        }
+    } else if rule.contains(')') {
+        anyhow::bail!(
+            "Invalid permissions.{} rule '{}': unmatched ')'",
+            list,
+            rule
+        );
+    }
+    Ok(())
+}
+
 /// Constants for Claude Code settings validation
 pub mod constants {
     /// Hook type: command
@@ -121,12 +242,242 @@ pub mod constants {
     /// Permission mode: deny (deny all tool uses not in allow list)
     pub const PERMISSION_MODE_DENY: &str = "deny";
 
+    /// Sources a SessionStart matcher is matched against
+    pub const SESSION_START_SOURCES: &[&str] = &["startup", "resume", "clear", "compact"];
+
     /// All valid permission modes
     pub const VALID_PERMISSION_MODES: &[&str] = &[
         PERMISSION_MODE_ASK,
         PERMISSION_MODE_ACCEPT_EDITS,
         PERMISSION_MODE_DENY,
     ];
+
+    /// Top-level settings.json keys documented by Claude Code
+    ///
+    /// Keys not modeled by [`super::ClaudeSettings`] are preserved in
+    /// `extra`; this list only decides which of them strict validation
+    /// reports as unrecognized.
+    pub const KNOWN_SETTINGS_KEYS: &[&str] = &[
+        "apiKeyHelper",
+        "awsAuthRefresh",
+        "awsCredentialExport",
+        "cleanupPeriodDays",
+        "disableAllHooks",
+        "disabledMcpjsonServers",
+        "enableAllProjectMcpServers",
+        "enabledMcpjsonServers",
+        "env",
+        "forceLoginMethod",
+        "forceLoginOrgUUID",
+        "hooks",
+        "includeCoAuthoredBy",
+        "model",
+        "outputStyle",
+        "permissions",
+        "statusLine",
+    ];
+
+    /// Keys allowed inside `permissions`
+    pub const KNOWN_PERMISSIONS_KEYS: &[&str] = &[
+        "additionalDirectories",
+        "allow",
+        "ask",
+        "defaultMode",
+        "deny",
+        "disableBypassPermissionsMode",
+    ];
+}
+
+/// Project MCP server definitions, next to `.claude/`
+pub const MCP_JSON_FILE: &str = ".mcp.json";
+
+/// Unmodeled settings key listing `.mcp.json` servers to keep off
+const DISABLED_MCP_SERVERS_KEY: &str = "disabledMcpjsonServers";
+
+/// Server names defined in a project's `.mcp.json`, sorted
+///
+/// # Returns
+///
+/// Returns `None` if the project has no `.mcp.json`
+///
+/// # Errors
+///
+/// Returns error if the file can't be read or has no `mcpServers` object
+pub fn mcp_json_servers(project_dir: impl AsRef<Path>) -> Result<Option<Vec<String>>> {
+    let path = project_dir.as_ref().join(MCP_JSON_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let json: Value =
+        jsonc::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+    let servers = json
+        .get("mcpServers")
+        .and_then(Value::as_object)
+        .with_context(|| format!("{} has no \"mcpServers\" object", path.display()))?;
+    let mut names: Vec<String> = servers.keys().cloned().collect();
+    names.sort();
+    Ok(Some(names))
+}
+
+/// Project settings, relative to the project root (and to the home
+/// directory for user settings)
+pub const PROJECT_SETTINGS_FILE: &str = ".claude/settings.json";
+
+/// Personal project settings, usually git-ignored
+pub const LOCAL_SETTINGS_FILE: &str = ".claude/settings.local.json";
+
+/// Where a settings file sits in Claude Code's hierarchy
+///
+/// Ordered from lowest to highest precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SettingsScope {
+    /// `~/.claude/settings.json`
+    User,
+    /// `.claude/settings.json`
+    Project,
+    /// `.claude/settings.local.json`
+    Local,
+}
+
+impl SettingsScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettingsScope::User => "user",
+            SettingsScope::Project => "project",
+            SettingsScope::Local => "local",
+        }
+    }
+}
+
+impl fmt::Display for SettingsScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// One settings file considered during resolution
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SettingsLayer {
+    pub scope: SettingsScope,
+    pub path: PathBuf,
+    /// Whether the file exists; missing layers are skipped
+    pub exists: bool,
+}
+
+/// One effective value and the layer it came from
+///
+/// Merged lists (permission rules, enabled MCP servers, hooks) have one
+/// entry per item, so a key can appear more than once.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SettingSource {
+    /// Dotted key path (e.g., `permissions.defaultMode`, `env.RUST_LOG`)
+    pub key: String,
+    pub value: Value,
+    pub scope: SettingsScope,
+}
+
+/// Settings merged from every layer, with provenance
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedSettings {
+    /// The effective settings
+    pub settings: ClaudeSettings,
+
+    /// Layers in the order they were merged
+    pub layers: Vec<SettingsLayer>,
+
+    /// Effective values sorted by key
+    pub sources: Vec<SettingSource>,
+}
+
+/// Replace a key (and anything nested under it) with a new value
+fn set_source(sources: &mut Vec<SettingSource>, key: String, value: &Value, scope: SettingsScope) {
+    let nested = format!("{}.", key);
+    sources.retain(|source| source.key != key && !source.key.starts_with(&nested));
+    sources.push(SettingSource {
+        key,
+        value: value.clone(),
+        scope,
+    });
+}
+
+/// Add list items that no earlier layer contributed
+fn add_sources(sources: &mut Vec<SettingSource>, key: &str, items: &[Value], scope: SettingsScope) {
+    for item in items {
+        if !sources
+            .iter()
+            .any(|source| source.key == key && source.value == *item)
+        {
+            sources.push(SettingSource {
+                key: key.to_string(),
+                value: item.clone(),
+                scope,
+            });
+        }
+    }
+}
+
+/// Record an unmodeled value the way `merge_extra` merges it: objects are
+/// merged one level deep, anything else replaces the earlier value
+fn set_extra_source(
+    sources: &mut Vec<SettingSource>,
+    key: String,
+    value: &Value,
+    scope: SettingsScope,
+) {
+    match value {
+        Value::Object(fields) if !fields.is_empty() => {
+            sources.retain(|source| source.key != key);
+            for (field, field_value) in fields {
+                set_source(sources, format!("{}.{}", key, field), field_value, scope);
+            }
+        }
+        _ => set_source(sources, key, value, scope),
+    }
+}
+
+/// Record the values one layer contributes, mirroring [`ClaudeSettings::merge`]
+fn record_sources(sources: &mut Vec<SettingSource>, raw: &Value, scope: SettingsScope) {
+    let Some(object) = raw.as_object() else {
+        return;
+    };
+    for (key, value) in object {
+        match (key.as_str(), value) {
+            // Merging never turns the flag back off
+            ("enableAllProjectMcpServers", Value::Bool(false)) => {}
+            ("enabledMcpjsonServers", Value::Array(items)) => {
+                add_sources(sources, key, items, scope)
+            }
+            ("hooks", Value::Object(events)) => {
+                for (event, configs) in events {
+                    if let Value::Array(configs) = configs {
+                        for config in configs {
+                            sources.push(SettingSource {
+                                key: format!("hooks.{}", event),
+                                value: config.clone(),
+                                scope,
+                            });
+                        }
+                    }
+                }
+            }
+            ("permissions", Value::Object(permissions)) => {
+                for (field, field_value) in permissions {
+                    let field_key = format!("permissions.{}", field);
+                    match (field.as_str(), field_value) {
+                        ("allow" | "deny" | "ask", Value::Array(items)) => {
+                            add_sources(sources, &field_key, items, scope)
+                        }
+                        ("defaultMode", Value::String(mode)) if mode.is_empty() => {}
+                        _ => set_extra_source(sources, field_key, field_value, scope),
+                    }
+                }
+            }
+            _ => set_extra_source(sources, key.clone(), value, scope),
+        }
+    }
 }
 
 /// Root settings structure for Claude Code
@@ -148,19 +499,52 @@ pub struct ClaudeSettings {
     /// Hook configurations by event type
     #[serde(default)]
     pub hooks: HashMap<HookEvent, Vec<HookConfig>>,
+
+    /// Keys not modeled above (e.g., `env`, `model`, `statusLine`)
+    ///
+    /// Preserved as-is so reading and rewriting a file never drops user
+    /// configuration.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Permission settings for tool usage
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// When a tool call matches rules in more than one list, `deny` wins over
+/// `ask`, and `ask` wins over `allow`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Permissions {
     /// List of allowed tool patterns (e.g., "Edit:*", "Write:*")
     #[serde(default)]
     pub allow: Vec<String>,
 
+    /// Tool patterns that are always refused (e.g., "Bash(rm:*)")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+
+    /// Tool patterns that always prompt for confirmation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ask: Vec<String>,
+
     /// Default permission mode
     #[serde(default)]
     pub default_mode: String,
+
+    /// Permission keys not modeled above (e.g., `additionalDirectories`)
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Permissions {
+    /// The rule lists by key, strictest first
+    pub fn rule_lists(&self) -> [(&'static str, &[String]); 3] {
+        [
+            ("deny", &self.deny),
+            ("ask", &self.ask),
+            ("allow", &self.allow),
+        ]
+    }
 }
 
 /// Hook configuration for a specific event
@@ -188,6 +572,9 @@ pub struct Hook {
 impl ClaudeSettings {
     /// Read settings from a JSON file
     ///
+    /// Comments and trailing commas are accepted; [`write`](Self::write)
+    /// drops them.
+    ///
     /// # Arguments
     ///
     /// * `path` - Path to settings.json file
@@ -196,14 +583,116 @@ impl ClaudeSettings {
     ///
     /// Returns error if file cannot be read or JSON is invalid
     pub fn read(path: impl AsRef<Path>) -> Result<Self> {
-        let content = fs::read_to_string(path.as_ref()).context("Failed to read settings file")?;
+        Self::read_with(&Fs::read_only(), path)
+    }
+
+    /// Read settings from a JSON file through a filesystem handle
+    ///
+    /// # Arguments
+    ///
+    /// * `fs` - Filesystem handle; reading never writes
+    /// * `path` - Path to settings.json file
+    ///
+    /// # Errors
+    ///
+    /// Same as [`read`](Self::read)
+    pub fn read_with<M: Mode>(fs: &Fs<M>, path: impl AsRef<Path>) -> Result<Self> {
+        let content = fs
+            .read_to_string(path.as_ref())
+            .context("Failed to read settings file")?;
 
         let settings: ClaudeSettings =
-            serde_json::from_str(&content).context("Failed to parse settings JSON")?;
+            jsonc::from_str(&content).context("Failed to parse settings JSON")?;
 
         Ok(settings)
     }
 
+    /// Resolve a project's effective settings from every layer
+    ///
+    /// Merges `~/.claude/settings.json`, `.claude/settings.json`, and
+    /// `.claude/settings.local.json`, in that order, so local settings
+    /// take precedence over project settings, which take precedence over
+    /// user settings. Missing files are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_dir` - Project root containing `.claude/`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if an existing layer can't be read or parsed
+    pub fn resolve(project_dir: impl AsRef<Path>) -> Result<ResolvedSettings> {
+        let project_dir = project_dir.as_ref();
+        let mut layers = Vec::new();
+        if let Some(home) = dirs::home_dir() {
+            let user_path = home.join(PROJECT_SETTINGS_FILE);
+            // In the home directory, user and project settings are one file
+            if user_path != project_dir.join(PROJECT_SETTINGS_FILE) {
+                layers.push((SettingsScope::User, user_path));
+            }
+        }
+        layers.push((
+            SettingsScope::Project,
+            project_dir.join(PROJECT_SETTINGS_FILE),
+        ));
+        layers.push((SettingsScope::Local, project_dir.join(LOCAL_SETTINGS_FILE)));
+        Self::resolve_layers(layers)
+    }
+
+    /// Merge settings files in order, later files taking precedence
+    ///
+    /// # Arguments
+    ///
+    /// * `layers` - Scope and path of each file, lowest precedence first
+    ///
+    /// # Errors
+    ///
+    /// Returns error if an existing layer can't be read or parsed
+    pub fn resolve_layers(
+        layers: impl IntoIterator<Item = (SettingsScope, PathBuf)>,
+    ) -> Result<ResolvedSettings> {
+        let mut settings = ClaudeSettings::default();
+        let mut resolved_layers = Vec::new();
+        let mut sources = Vec::new();
+
+        for (scope, path) in layers {
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    resolved_layers.push(SettingsLayer {
+                        scope,
+                        path,
+                        exists: false,
+                    });
+                    continue;
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to read {}", path.display()))
+                }
+            };
+            let raw: Value = jsonc::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            let layer: ClaudeSettings = serde_json::from_value(raw.clone())
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+            record_sources(&mut sources, &raw, scope);
+            settings.merge(layer);
+            resolved_layers.push(SettingsLayer {
+                scope,
+                path,
+                exists: true,
+            });
+        }
+
+        // Stable, so list items keep their merge order
+        sources.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(ResolvedSettings {
+            settings,
+            layers: resolved_layers,
+            sources,
+        })
+    }
+
     /// Write settings to a JSON file with pretty formatting
     ///
     /// Uses atomic write (temp file + rename) to prevent corruption if write fails.
@@ -221,12 +710,14 @@ impl ClaudeSettings {
     pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
         use tempfile::NamedTempFile;
 
+        let fs = Fs::read_write();
         let path = path.as_ref();
         let json = serde_json::to_string_pretty(self).context("Failed to serialize settings")?;
 
         // Create parent directories if they don't exist
         if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent).context("Failed to create parent directories")?;
+            fs.create_dir_all(parent)
+                .context("Failed to create parent directories")?;
         }
 
         // Create temp file in same directory (important for atomic rename)
@@ -245,9 +736,9 @@ impl ClaudeSettings {
             .sync_all()
             .context("Failed to sync temporary file")?;
 
-        // Atomic persist to final location (auto-cleanup on failure)
-        temp_file
-            .persist(path)
+        // Atomic persist to final location (auto-cleanup on failure),
+        // copying instead if the rename crosses a device boundary
+        fs.persist(temp_file, path)
             .context("Failed to persist temporary file")?;
 
         Ok(())
@@ -287,10 +778,7 @@ impl ClaudeSettings {
 
         // Validate matcher is valid regex if present
         if let Some(ref matcher) = hook_config.matcher {
-            regex::Regex::new(matcher).context(format!(
-                "Invalid matcher regex in {} hook: {}",
-                event, matcher
-            ))?;
+            validate_matcher(event, matcher)?;
         }
 
         // All validations passed, add the hook
@@ -299,6 +787,50 @@ impl ClaudeSettings {
         Ok(())
     }
 
+    /// Enable a server from the project's `.mcp.json`
+    ///
+    /// Also drops the server from `disabledMcpjsonServers`, which would
+    /// otherwise keep it off.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if the settings changed
+    pub fn add_mcp_server(&mut self, name: &str) -> bool {
+        let mut changed = false;
+        if !self.enabled_mcpjson_servers.iter().any(|s| s == name) {
+            self.enabled_mcpjson_servers.push(name.to_string());
+            changed = true;
+        }
+        if let Some(Value::Array(disabled)) = self.extra.get_mut(DISABLED_MCP_SERVERS_KEY) {
+            let before = disabled.len();
+            disabled.retain(|server| server.as_str() != Some(name));
+            changed |= disabled.len() != before;
+        }
+        changed
+    }
+
+    /// Stop enabling a server
+    ///
+    /// # Returns
+    ///
+    /// Returns true if the server was enabled
+    pub fn remove_mcp_server(&mut self, name: &str) -> bool {
+        let before = self.enabled_mcpjson_servers.len();
+        self.enabled_mcpjson_servers.retain(|server| server != name);
+        self.enabled_mcpjson_servers.len() != before
+    }
+
+    /// Servers listed in `disabledMcpjsonServers`
+    pub fn disabled_mcp_servers(&self) -> Vec<String> {
+        match self.extra.get(DISABLED_MCP_SERVERS_KEY) {
+            Some(Value::Array(servers)) => servers
+                .iter()
+                .filter_map(|server| server.as_str().map(str::to_string))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
     /// Remove hooks matching a command pattern
     ///
     /// # Arguments
@@ -351,17 +883,15 @@ impl ClaudeSettings {
         // Merge permissions
         if let Some(other_perms) = other.permissions {
             if let Some(ref mut perms) = self.permissions {
-                // Merge allow patterns (deduplicate with HashSet)
-                let existing_allow: HashSet<_> = perms.allow.iter().cloned().collect();
-                for allow in other_perms.allow {
-                    if !existing_allow.contains(&allow) {
-                        perms.allow.push(allow);
-                    }
-                }
+                // Merge rule lists (deduplicate with HashSet)
+                merge_rules(&mut perms.allow, other_perms.allow);
+                merge_rules(&mut perms.deny, other_perms.deny);
+                merge_rules(&mut perms.ask, other_perms.ask);
                 // Other's default_mode takes precedence if non-empty
                 if !other_perms.default_mode.is_empty() {
                     perms.default_mode = other_perms.default_mode;
                 }
+                merge_extra(&mut perms.extra, other_perms.extra);
             } else {
                 self.permissions = Some(other_perms);
             }
@@ -371,6 +901,85 @@ impl ClaudeSettings {
         for (event, configs) in other.hooks {
             self.hooks.entry(event).or_default().extend(configs);
         }
+
+        // Merge unmodeled keys
+        merge_extra(&mut self.extra, other.extra);
+    }
+
+    /// Unmodeled keys that Claude Code doesn't document
+    ///
+    /// Returns dotted key paths (e.g., `statusLn`, `permissions.denny`).
+    /// These are usually typos; they are preserved on write but have no
+    /// effect.
+    pub fn unrecognized_keys(&self) -> Vec<String> {
+        use constants::*;
+
+        let mut keys: Vec<String> = self
+            .extra
+            .keys()
+            .filter(|key| !KNOWN_SETTINGS_KEYS.contains(&key.as_str()))
+            .cloned()
+            .collect();
+
+        if let Some(ref permissions) = self.permissions {
+            keys.extend(
+                permissions
+                    .extra
+                    .keys()
+                    .filter(|key| !KNOWN_PERMISSIONS_KEYS.contains(&key.as_str()))
+                    .map(|key| format!("permissions.{}", key)),
+            );
+        }
+
+        keys.sort();
+        keys
+    }
+
+    /// Validate, then report unrecognized keys as warnings
+    ///
+    /// Runs [`validate`](Self::validate) and returns one warning per
+    /// unrecognized key, with a "did you mean" suggestion when a known key
+    /// is close. Unrecognized keys are never an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if [`validate`](Self::validate) fails
+    pub fn validate_strict(&self) -> Result<Vec<String>> {
+        use constants::*;
+
+        self.validate()?;
+
+        let mut warnings = Vec::new();
+        if let Some(ref permissions) = self.permissions {
+            // Rules in several lists aren't an error, but only the
+            // strictest list has any effect
+            let lists = permissions.rule_lists();
+            for (i, (weaker, weaker_rules)) in lists.iter().enumerate() {
+                for (stronger, stronger_rules) in &lists[..i] {
+                    for rule in weaker_rules.iter().filter(|r| stronger_rules.contains(r)) {
+                        warnings.push(format!(
+                            "Rule '{}' is in both permissions.{} and permissions.{}; {} takes precedence",
+                            rule, stronger, weaker, stronger
+                        ));
+                    }
+                }
+            }
+        }
+
+        warnings.extend(self.unrecognized_keys().into_iter().map(|key| {
+            let (known, name) = match key.strip_prefix("permissions.") {
+                Some(name) => (KNOWN_PERMISSIONS_KEYS, name),
+                None => (KNOWN_SETTINGS_KEYS, key.as_str()),
+            };
+            match find_closest_match(name, known) {
+                Some(closest) => format!(
+                    "Unrecognized key '{}' (preserved). Did you mean '{}'?",
+                    key, closest
+                ),
+                None => format!("Unrecognized key '{}' (preserved)", key),
+            }
+        }));
+        Ok(warnings)
     }
 
     /// Validate the settings structure
@@ -412,6 +1021,12 @@ impl ClaudeSettings {
                     );
                 }
             }
+
+            for (list, rules) in permissions.rule_lists() {
+                for rule in rules {
+                    validate_permission_rule(list, rule)?;
+                }
+            }
         }
 
         // Validate hooks
@@ -419,10 +1034,7 @@ impl ClaudeSettings {
             for config in configs {
                 // Validate matcher is valid regex if present
                 if let Some(ref matcher) = config.matcher {
-                    regex::Regex::new(matcher).context(format!(
-                        "Invalid matcher regex in {} hook: {}",
-                        event, matcher
-                    ))?;
+                    validate_matcher(*event, matcher)?;
                 }
 
                 // Validate hooks array not empty
@@ -638,12 +1250,42 @@ mod tests {
             .contains(&"playwright".to_string()));
     }
 
+    #[test]
+    fn test_mcp_servers_add_remove_and_mcp_json() {
+        let mut settings: ClaudeSettings = serde_json::from_str(
+            r#"{"enabledMcpjsonServers": ["mysql"], "disabledMcpjsonServers": ["github", "slack"]}"#,
+        )
+        .unwrap();
+        assert!(!settings.add_mcp_server("mysql"));
+        assert!(settings.add_mcp_server("github"));
+        assert_eq!(settings.enabled_mcpjson_servers, vec!["mysql", "github"]);
+        assert_eq!(settings.disabled_mcp_servers(), vec!["slack"]);
+        assert!(settings.remove_mcp_server("mysql"));
+        assert!(!settings.remove_mcp_server("mysql"));
+
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(mcp_json_servers(temp_dir.path()).unwrap(), None);
+        fs::write(
+            temp_dir.path().join(MCP_JSON_FILE),
+            r#"{"mcpServers": {"playwright": {"command": "npx"}, "github": {"type": "http"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            mcp_json_servers(temp_dir.path()).unwrap(),
+            Some(vec!["github".to_string(), "playwright".to_string()])
+        );
+        fs::write(temp_dir.path().join(MCP_JSON_FILE), "{}").unwrap();
+        assert!(mcp_json_servers(temp_dir.path()).is_err());
+    }
+
     #[test]
     fn test_merge_permissions() {
         let mut base = ClaudeSettings {
             permissions: Some(Permissions {
                 allow: vec!["Edit:*".to_string()],
                 default_mode: "ask".to_string(),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -652,6 +1294,7 @@ mod tests {
             permissions: Some(Permissions {
                 allow: vec!["Write:*".to_string()],
                 default_mode: "acceptEdits".to_string(),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -788,6 +1431,7 @@ mod tests {
             permissions: Some(Permissions {
                 allow: vec!["Edit:*".to_string()],
                 default_mode: "invalid_mode".to_string(),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -811,6 +1455,7 @@ mod tests {
                 permissions: Some(Permissions {
                     allow: vec!["Edit:*".to_string()],
                     default_mode: mode.to_string(),
+                    ..Default::default()
                 }),
                 ..Default::default()
             };
@@ -830,6 +1475,7 @@ mod tests {
             permissions: Some(Permissions {
                 allow: vec!["Edit:*".to_string()],
                 default_mode: String::new(),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -848,12 +1494,248 @@ mod tests {
         assert!(error_msg.contains("UserPromptSubmit"));
     }
 
+    #[test]
+    fn test_hook_event_round_trip() {
+        for event in HookEvent::ALL {
+            assert_eq!(HookEvent::from_str(event.as_str()).unwrap(), *event);
+        }
+
+        let error_msg = HookEvent::from_str("SesionStart").unwrap_err().to_string();
+        assert!(error_msg.contains("Did you mean 'SessionStart'"));
+    }
+
+    #[test]
+    fn test_parse_extended_hook_events() {
+        let json = r#"{
+            "hooks": {
+                "PreToolUse": [{
+                    "matcher": "Bash",
+                    "hooks": [{"type": "command", "command": "guard.sh"}]
+                }],
+                "SessionStart": [{
+                    "matcher": "startup|resume",
+                    "hooks": [{"type": "command", "command": "load-context.sh"}]
+                }],
+                "SubagentStop": [{
+                    "hooks": [{"type": "command", "command": "summarize.sh"}]
+                }]
+            }
+        }"#;
+
+        let settings: ClaudeSettings = serde_json::from_str(json).unwrap();
+        assert!(settings.validate().is_ok());
+        assert_eq!(settings.hooks[&HookEvent::PreToolUse].len(), 1);
+
+        // Serializes back under the same event names
+        let output = serde_json::to_value(&settings).unwrap();
+        assert!(output["hooks"]["SessionStart"].is_array());
+        assert!(output["hooks"]["SubagentStop"].is_array());
+    }
+
+    #[test]
+    fn test_session_start_matcher_must_match_a_source() {
+        let mut settings = ClaudeSettings::default();
+        let result = settings.add_hook(
+            HookEvent::SessionStart,
+            HookConfig {
+                matcher: Some("startp".to_string()),
+                hooks: vec![Hook {
+                    r#type: "command".to_string(),
+                    command: "load-context.sh".to_string(),
+                }],
+            },
+        );
+
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("never matches"));
+        assert!(settings.hooks.is_empty());
+    }
+
+    #[test]
+    fn test_merge_extended_hook_events() {
+        let hook = |command: &str| HookConfig {
+            matcher: None,
+            hooks: vec![Hook {
+                r#type: "command".to_string(),
+                command: command.to_string(),
+            }],
+        };
+
+        let mut base = ClaudeSettings::default();
+        base.add_hook(HookEvent::Notification, hook("notify.sh"))
+            .unwrap();
+
+        let mut other = ClaudeSettings::default();
+        other
+            .add_hook(HookEvent::Notification, hook("notify-2.sh"))
+            .unwrap();
+        other
+            .add_hook(HookEvent::SessionEnd, hook("cleanup.sh"))
+            .unwrap();
+
+        base.merge(other);
+        assert_eq!(base.hooks[&HookEvent::Notification].len(), 2);
+        assert_eq!(base.hooks[&HookEvent::SessionEnd].len(), 1);
+    }
+
+    #[test]
+    fn test_unmodeled_keys_survive_round_trip() {
+        let json = r#"{
+            "model": "opus",
+            "env": {"RUST_LOG": "debug"},
+            "statusLine": {"type": "command", "command": "status.sh"},
+            "permissions": {
+                "allow": ["Edit:*"],
+                "deny": ["Bash(rm:*)"]
+            },
+            "hooks": {}
+        }"#;
+
+        let settings: ClaudeSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(settings.extra["model"], "opus");
+
+        let output = serde_json::to_value(&settings).unwrap();
+        assert_eq!(output["env"]["RUST_LOG"], "debug");
+        assert_eq!(output["statusLine"]["command"], "status.sh");
+        assert_eq!(output["permissions"]["deny"][0], "Bash(rm:*)");
+        assert!(settings.unrecognized_keys().is_empty());
+    }
+
+    #[test]
+    fn test_merge_preserves_unmodeled_keys() {
+        let mut base: ClaudeSettings = serde_json::from_str(
+            r#"{"model": "sonnet", "env": {"A": "1"}, "cleanupPeriodDays": 30}"#,
+        )
+        .unwrap();
+        let other: ClaudeSettings =
+            serde_json::from_str(r#"{"model": "opus", "env": {"B": "2"}}"#).unwrap();
+
+        base.merge(other);
+        assert_eq!(base.extra["model"], "opus");
+        assert_eq!(base.extra["env"]["A"], "1");
+        assert_eq!(base.extra["env"]["B"], "2");
+        assert_eq!(base.extra["cleanupPeriodDays"], 30);
+    }
+
+    #[test]
+    fn test_merge_deduplicates_deny_and_ask() {
+        let mut base: ClaudeSettings = serde_json::from_str(
+            r#"{"permissions": {"deny": ["Bash(rm:*)"], "ask": ["Bash(git push:*)"]}}"#,
+        )
+        .unwrap();
+        let other: ClaudeSettings = serde_json::from_str(
+            r#"{"permissions": {"deny": ["Bash(rm:*)", "Read(./.env)"], "ask": ["Bash(git push:*)"]}}"#,
+        )
+        .unwrap();
+
+        base.merge(other);
+
+        let perms = base.permissions.as_ref().unwrap();
+        assert_eq!(perms.deny, vec!["Bash(rm:*)", "Read(./.env)"]);
+        assert_eq!(perms.ask, vec!["Bash(git push:*)"]);
+        let output = serde_json::to_value(&base).unwrap();
+        assert_eq!(output["permissions"]["deny"][1], "Read(./.env)");
+        assert!(output["permissions"].get("additionalDirectories").is_none());
+    }
+
+    #[test]
+    fn test_validate_permission_rules() {
+        let malformed: ClaudeSettings =
+            serde_json::from_str(r#"{"permissions": {"deny": ["Bash(rm:*"]}}"#).unwrap();
+        let error_msg = malformed.validate().unwrap_err().to_string();
+        assert!(error_msg.contains("permissions.deny rule 'Bash(rm:*'"));
+
+        let conflicting: ClaudeSettings = serde_json::from_str(
+            r#"{"permissions": {"allow": ["Edit:*", "Bash(rm:*)"], "deny": ["Bash(rm:*)"]}}"#,
+        )
+        .unwrap();
+        let warnings = conflicting.validate_strict().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("permissions.deny and permissions.allow"));
+    }
+
+    #[test]
+    fn test_resolve_layers_precedence_and_sources() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let user = temp_dir.path().join("user.json");
+        let project = temp_dir.path().join("settings.json");
+        let local = temp_dir.path().join("settings.local.json");
+        fs::write(
+            &user,
+            r#"{"model": "opus", "env": {"A": "1", "B": "1"},
+                "permissions": {"allow": ["Edit:*"], "defaultMode": "plan"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            &project,
+            r#"{"env": {"B": "2"}, "permissions": {"allow": ["Edit:*", "Write:*"]}}"#,
+        )
+        .unwrap();
+
+        let resolved = ClaudeSettings::resolve_layers([
+            (SettingsScope::User, user),
+            (SettingsScope::Project, project),
+            (SettingsScope::Local, local),
+        ])
+        .unwrap();
+
+        assert!(!resolved.layers[2].exists);
+        let perms = resolved.settings.permissions.as_ref().unwrap();
+        assert_eq!(perms.allow, vec!["Edit:*", "Write:*"]);
+        assert_eq!(perms.default_mode, "plan");
+        assert_eq!(resolved.settings.extra["env"]["B"], "2");
+
+        let scope_of = |key: &str, value: &str| {
+            resolved
+                .sources
+                .iter()
+                .find(|source| source.key == key && source.value == value)
+                .map(|source| source.scope)
+        };
+        assert_eq!(scope_of("env.A", "1"), Some(SettingsScope::User));
+        assert_eq!(scope_of("env.B", "2"), Some(SettingsScope::Project));
+        assert_eq!(scope_of("env.B", "1"), None);
+        assert_eq!(
+            scope_of("permissions.allow", "Edit:*"),
+            Some(SettingsScope::User)
+        );
+        assert_eq!(
+            scope_of("permissions.allow", "Write:*"),
+            Some(SettingsScope::Project)
+        );
+        assert_eq!(
+            scope_of("permissions.defaultMode", "plan"),
+            Some(SettingsScope::User)
+        );
+    }
+
+    #[test]
+    fn test_validate_strict_warns_on_unrecognized_keys() {
+        let settings: ClaudeSettings =
+            serde_json::from_str(r#"{"statusLin": {}, "permissions": {"allow": [], "denny": []}}"#)
+                .unwrap();
+
+        assert!(settings.validate().is_ok());
+        let warnings = settings.validate_strict().unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("'permissions.denny'"));
+        assert!(warnings[0].contains("Did you mean 'deny'"));
+        assert!(warnings[1].contains("Did you mean 'statusLine'"));
+
+        // Unrecognized keys are still written back
+        let output = serde_json::to_value(&settings).unwrap();
+        assert!(output.get("statusLin").is_some());
+    }
+
     #[test]
     fn test_suggestion_permission_mode_typo() {
         let settings = ClaudeSettings {
             permissions: Some(Permissions {
                 allow: vec!["Edit:*".to_string()],
                 default_mode: "aceptEdits".to_string(), // Missing 'c' in accept
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -0,0 +1,360 @@
+//! Structural validation of settings.json
+//!
+//! Deserializing into [`ClaudeSettings`](crate::settings::ClaudeSettings)
+//! stops at the first type mismatch with a message that doesn't say where
+//! the value is. [`validate`] walks the raw JSON instead and reports every
+//! value of the wrong type or outside its allowed values, each with its
+//! location (e.g., `hooks.PostToolUse[0].hooks[1].type`).
+//!
+//! Only keys Claude Code documents are checked; anything else is left to
+//! [`ClaudeSettings::validate_strict`](crate::settings::ClaudeSettings::validate_strict).
+
+use crate::settings::constants::{VALID_HOOK_TYPES, VALID_PERMISSION_MODES};
+use crate::settings::HookEvent;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// One step into a JSON document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Location of a value in a JSON document
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonPath(pub Vec<PathSegment>);
+
+impl JsonPath {
+    /// The path as an RFC 6901 JSON pointer (e.g., `/hooks/Stop/0/hooks`)
+    pub fn pointer(&self) -> String {
+        self.0
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::Key(key) => format!("/{}", key.replace('~', "~0").replace('/', "~1")),
+                PathSegment::Index(index) => format!("/{}", index),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str("(root)");
+        }
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) if i == 0 => write!(f, "{}", key)?,
+                PathSegment::Key(key) => write!(f, ".{}", key)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A value that doesn't match the settings schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    pub path: JsonPath,
+    /// What the schema allows (e.g., `string`, `one of: command`)
+    pub expected: String,
+    /// What the document has (e.g., `number`, `"comand"`, `nothing`)
+    pub found: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, found {}",
+            self.path, self.expected, self.found
+        )
+    }
+}
+
+/// Short description of a JSON value for error messages
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(_) => "boolean".to_string(),
+        Value::Number(_) => "number".to_string(),
+        Value::String(s) => format!("\"{}\"", s),
+        Value::Array(_) => "array".to_string(),
+        Value::Object(_) => "object".to_string(),
+    }
+}
+
+/// Walks a document, collecting errors at the current path
+#[derive(Default)]
+struct Checker {
+    path: Vec<PathSegment>,
+    errors: Vec<SchemaError>,
+}
+
+impl Checker {
+    fn error(&mut self, expected: impl Into<String>, found: String) {
+        self.errors.push(SchemaError {
+            path: JsonPath(self.path.clone()),
+            expected: expected.into(),
+            found,
+        });
+    }
+
+    /// Run `check` one level down
+    fn at(&mut self, segment: PathSegment, check: impl FnOnce(&mut Self)) {
+        self.path.push(segment);
+        check(self);
+        self.path.pop();
+    }
+
+    fn key(&mut self, object: &Map<String, Value>, key: &str, check: fn(&mut Self, &Value)) {
+        if let Some(value) = object.get(key) {
+            self.at(PathSegment::Key(key.to_string()), |c| check(c, value));
+        }
+    }
+
+    fn required(&mut self, object: &Map<String, Value>, key: &str, check: fn(&mut Self, &Value)) {
+        match object.get(key) {
+            Some(value) => self.at(PathSegment::Key(key.to_string()), |c| check(c, value)),
+            None => self.at(PathSegment::Key(key.to_string()), |c| {
+                c.error("a value", "nothing".to_string())
+            }),
+        }
+    }
+
+    fn object<'a>(&mut self, value: &'a Value) -> Option<&'a Map<String, Value>> {
+        let object = value.as_object();
+        if object.is_none() {
+            self.error("object", describe(value));
+        }
+        object
+    }
+
+    fn array<'a>(&mut self, value: &'a Value) -> Option<&'a Vec<Value>> {
+        let array = value.as_array();
+        if array.is_none() {
+            self.error("array", describe(value));
+        }
+        array
+    }
+
+    fn string(&mut self, value: &Value) {
+        if !value.is_string() {
+            self.error("string", describe(value));
+        }
+    }
+
+    fn boolean(&mut self, value: &Value) {
+        if !value.is_boolean() {
+            self.error("boolean", describe(value));
+        }
+    }
+
+    fn number(&mut self, value: &Value) {
+        if !value.is_number() {
+            self.error("number", describe(value));
+        }
+    }
+
+    fn string_array(&mut self, value: &Value) {
+        if let Some(items) = self.array(value) {
+            for (index, item) in items.iter().enumerate() {
+                self.at(PathSegment::Index(index), |c| c.string(item));
+            }
+        }
+    }
+
+    fn one_of(&mut self, value: &Value, allowed: &[&str]) {
+        match value.as_str() {
+            Some(s) if allowed.contains(&s) => {}
+            _ => self.error(format!("one of: {}", allowed.join(", ")), describe(value)),
+        }
+    }
+}
+
+fn check_permissions(c: &mut Checker, value: &Value) {
+    let Some(permissions) = c.object(value) else {
+        return;
+    };
+    for list in ["allow", "deny", "ask", "additionalDirectories"] {
+        c.key(permissions, list, Checker::string_array);
+    }
+    c.key(permissions, "defaultMode", |c, mode| {
+        // An empty mode means "not set"
+        if mode != "" {
+            c.one_of(mode, VALID_PERMISSION_MODES);
+        }
+    });
+    c.key(permissions, "disableBypassPermissionsMode", Checker::string);
+}
+
+fn check_hook(c: &mut Checker, value: &Value) {
+    let Some(hook) = c.object(value) else {
+        return;
+    };
+    c.required(hook, "type", |c, kind| c.one_of(kind, VALID_HOOK_TYPES));
+    c.required(hook, "command", Checker::string);
+    c.key(hook, "timeout", Checker::number);
+}
+
+fn check_hook_config(c: &mut Checker, value: &Value) {
+    let Some(config) = c.object(value) else {
+        return;
+    };
+    c.key(config, "matcher", Checker::string);
+    c.required(config, "hooks", |c, hooks| {
+        if let Some(hooks) = c.array(hooks) {
+            for (index, hook) in hooks.iter().enumerate() {
+                c.at(PathSegment::Index(index), |c| check_hook(c, hook));
+            }
+        }
+    });
+}
+
+fn check_hooks(c: &mut Checker, value: &Value) {
+    let Some(events) = c.object(value) else {
+        return;
+    };
+    let event_names: Vec<&str> = HookEvent::ALL.iter().map(|e| e.as_str()).collect();
+    for (event, configs) in events {
+        c.at(PathSegment::Key(event.clone()), |c| {
+            if !event_names.contains(&event.as_str()) {
+                c.error(
+                    format!("an event, one of: {}", event_names.join(", ")),
+                    format!("\"{}\"", event),
+                );
+                return;
+            }
+            if let Some(configs) = c.array(configs) {
+                for (index, config) in configs.iter().enumerate() {
+                    c.at(PathSegment::Index(index), |c| check_hook_config(c, config));
+                }
+            }
+        });
+    }
+}
+
+/// Check a settings.json document against the settings schema
+///
+/// # Arguments
+///
+/// * `value` - The parsed document
+///
+/// # Returns
+///
+/// Returns every mismatch found, in document order; empty if the document
+/// matches
+pub fn validate(value: &Value) -> Vec<SchemaError> {
+    let mut c = Checker::default();
+    let Some(root) = c.object(value) else {
+        return c.errors;
+    };
+
+    for key in [
+        "enableAllProjectMcpServers",
+        "includeCoAuthoredBy",
+        "disableAllHooks",
+    ] {
+        c.key(root, key, Checker::boolean);
+    }
+    for key in ["enabledMcpjsonServers", "disabledMcpjsonServers"] {
+        c.key(root, key, Checker::string_array);
+    }
+    for key in [
+        "apiKeyHelper",
+        "awsAuthRefresh",
+        "awsCredentialExport",
+        "forceLoginMethod",
+        "forceLoginOrgUUID",
+        "model",
+        "outputStyle",
+    ] {
+        c.key(root, key, Checker::string);
+    }
+    c.key(root, "cleanupPeriodDays", Checker::number);
+    c.key(root, "env", |c, env| {
+        if let Some(env) = c.object(env) {
+            for (name, value) in env {
+                c.at(PathSegment::Key(name.clone()), |c| c.string(value));
+            }
+        }
+    });
+    c.key(root, "statusLine", |c, status_line| {
+        if let Some(status_line) = c.object(status_line) {
+            c.key(status_line, "type", Checker::string);
+            c.key(status_line, "command", Checker::string);
+        }
+    });
+    c.key(root, "permissions", check_permissions);
+    c.key(root, "hooks", check_hooks);
+
+    c.errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_reports_location_of_each_mismatch() {
+        let settings = json!({
+            "permissions": {"allow": ["Edit:*", 3], "defaultMode": "acceptEdit"},
+            "hooks": {
+                "PostToolUse": [{
+                    "matcher": "Edit",
+                    "hooks": [
+                        {"type": "command", "command": "a"},
+                        {"type": "comand", "command": "b"}
+                    ]
+                }],
+                "Stopp": [],
+                "Stop": [{"hooks": [{"type": "command"}]}]
+            },
+            "env": {"DEBUG": true}
+        });
+
+        let errors = validate(&settings);
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert!(messages.contains(&"permissions.allow[1]: expected string, found number".into()));
+        assert!(messages.contains(
+            &"hooks.PostToolUse[0].hooks[1].type: expected one of: command, found \"comand\""
+                .into()
+        ));
+        assert!(messages
+            .contains(&"hooks.Stop[0].hooks[0].command: expected a value, found nothing".into()));
+        assert!(messages.contains(&"env.DEBUG: expected string, found boolean".into()));
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("permissions.defaultMode: expected one of: ask")));
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("hooks.Stopp: expected an event")));
+        assert_eq!(errors.len(), 6);
+
+        let hook_type = errors.iter().find(|e| e.found == "\"comand\"").unwrap();
+        assert_eq!(
+            hook_type.path.pointer(),
+            "/hooks/PostToolUse/0/hooks/1/type"
+        );
+    }
+
+    #[test]
+    fn test_accepts_valid_settings() {
+        let settings = json!({
+            "enableAllProjectMcpServers": false,
+            "enabledMcpjsonServers": [],
+            "permissions": {"allow": ["Edit:*"], "defaultMode": ""},
+            "hooks": {"UserPromptSubmit": [{"hooks": [{"type": "command", "command": "x", "timeout": 30}]}]},
+            "model": "opus",
+            "customKey": {"anything": 1}
+        });
+        assert!(validate(&settings).is_empty());
+        assert_eq!(
+            validate(&json!([]))[0].to_string(),
+            "(root): expected object, found array"
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Skill manifests
+//!
+//! A skill's metadata comes from two files in its directory. The front
+//! matter of `SKILL.md` is what Claude Code itself reads: `name` and
+//! `description`. An optional `skill.toml` next to it holds what only
+//! Catalyst uses:
+//!
+//! ```toml
+//! version = "1.2.0"
+//! author = "Catalyst contributors"
+//! # One line for `catalyst skills list` and the interactive init prompt
+//! summary = "Rust development best practices and patterns"
+//!
+//! # Defaults for the skill's entry in skill-rules.json
+//! [activation]
+//! keywords = ["rust"]
+//! intent_patterns = ["rust development"]
+//! path_patterns = ["**/*.rs", "Cargo.toml"]
+//! ```
+//!
+//! `version` and `author` may also be given in the front matter; skill.toml
+//! wins when both are set. Other tables in skill.toml, such as
+//! `[healthcheck]`, belong to other parts of Catalyst and are ignored here.
+
+use crate::front_matter::{parse_fields, split_front_matter};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The skill's instructions, with front matter
+pub const SKILL_FILE: &str = "SKILL.md";
+
+/// SKILL.md before template variables are filled in
+pub const SKILL_TEMPLATE_FILE: &str = "SKILL.md.tmpl";
+
+/// Catalyst's metadata for a skill
+pub const MANIFEST_FILE: &str = "skill.toml";
+
+/// Front matter fields Claude Code and Catalyst understand in SKILL.md
+const FRONT_MATTER_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "allowed-tools",
+    "license",
+    "version",
+    "author",
+];
+
+/// Everything known about a skill from its SKILL.md and skill.toml
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkillManifest {
+    pub name: String,
+    /// Tells Claude when to use the skill; often a paragraph
+    pub description: String,
+    /// One-line description from skill.toml
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Default activation rule; None if skill.toml has no `[activation]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activation: Option<Activation>,
+}
+
+/// Default trigger patterns for a skill's skill-rules.json entry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Activation {
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub intent_patterns: Vec<String>,
+    #[serde(default)]
+    pub path_patterns: Vec<String>,
+}
+
+/// The parts of skill.toml read here
+#[derive(Debug, Default, Deserialize)]
+struct ManifestFile {
+    summary: Option<String>,
+    version: Option<String>,
+    author: Option<String>,
+    activation: Option<Activation>,
+}
+
+impl SkillManifest {
+    /// Build a manifest from the contents of SKILL.md and skill.toml
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SKILL.md has no front matter, or no `name` or
+    /// `description` in it, or if skill.toml isn't valid TOML or has a
+    /// field of the wrong type
+    pub fn parse(skill_md: &str, skill_toml: Option<&str>) -> Result<Self> {
+        let Some((block, _body)) =
+            split_front_matter(skill_md).map_err(|e| anyhow::anyhow!("{}: {}", SKILL_FILE, e))?
+        else {
+            bail!("{} has no front matter", SKILL_FILE);
+        };
+        // Unknown fields are Claude Code's business, not a manifest error
+        let mut fields = parse_fields(&block, FRONT_MATTER_FIELDS, &mut Vec::new());
+        let mut required = |key: &str| match fields.remove(key) {
+            Some(value) if !value.is_empty() => Ok(value),
+            _ => Err(anyhow::anyhow!(
+                "{} front matter has no '{}'",
+                SKILL_FILE,
+                key
+            )),
+        };
+        let name = required("name")?;
+        let description = required("description")?;
+
+        let file: ManifestFile = match skill_toml {
+            Some(content) => {
+                toml::from_str(content).with_context(|| format!("Invalid {}", MANIFEST_FILE))?
+            }
+            None => ManifestFile::default(),
+        };
+
+        Ok(Self {
+            name,
+            description,
+            summary: file.summary,
+            version: file.version.or_else(|| fields.remove("version")),
+            author: file.author.or_else(|| fields.remove("author")),
+            activation: file.activation,
+        })
+    }
+
+    /// Read the manifest of the skill in `skill_dir`
+    ///
+    /// Falls back to `SKILL.md.tmpl` for a skill whose SKILL.md is a
+    /// template.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if neither SKILL.md nor SKILL.md.tmpl can be read,
+    /// skill.toml exists but can't be read, or either is malformed (see
+    /// [`SkillManifest::parse`])
+    pub fn load(skill_dir: &Path) -> Result<Self> {
+        let skill_md = fs::read_to_string(skill_dir.join(SKILL_FILE))
+            .or_else(|_| fs::read_to_string(skill_dir.join(SKILL_TEMPLATE_FILE)))
+            .with_context(|| format!("Failed to read {}", skill_dir.join(SKILL_FILE).display()))?;
+        let manifest_path = skill_dir.join(MANIFEST_FILE);
+        let skill_toml = match fs::read_to_string(&manifest_path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read {}", manifest_path.display()))
+            }
+        };
+        Self::parse(&skill_md, skill_toml.as_deref())
+    }
+
+    /// One line describing the skill: the summary, or else the first
+    /// sentence of the description
+    pub fn summary(&self) -> &str {
+        if let Some(summary) = &self.summary {
+            return summary;
+        }
+        match self.description.find(". ") {
+            Some(end) => &self.description[..end],
+            None => self.description.trim_end_matches('.'),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SKILL_MD: &str = "---\nname: demo\ndescription: Demo patterns. Use when demoing.\nversion: 0.1.0\n---\n# Demo\n";
+
+    #[test]
+    fn test_parse_merges_front_matter_and_skill_toml() {
+        let manifest = SkillManifest::parse(SKILL_MD, None).unwrap();
+        assert_eq!(manifest.name, "demo");
+        assert_eq!(manifest.version.as_deref(), Some("0.1.0"));
+        assert_eq!(manifest.summary(), "Demo patterns");
+        assert!(manifest.activation.is_none());
+
+        let toml = "version = \"0.2.0\"\nsummary = \"Short\"\n\n[activation]\nkeywords = [\"demo\"]\n\n[healthcheck]\nfiles = [\"x\"]\n";
+        let manifest = SkillManifest::parse(SKILL_MD, Some(toml)).unwrap();
+        assert_eq!(manifest.version.as_deref(), Some("0.2.0"));
+        assert_eq!(manifest.summary(), "Short");
+        assert_eq!(manifest.activation.unwrap().keywords, vec!["demo"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_incomplete_manifests() {
+        let error = SkillManifest::parse("---\nname: demo\n---\n", None).unwrap_err();
+        assert!(error.to_string().contains("'description'"));
+        assert!(SkillManifest::parse("# No front matter", None).is_err());
+        assert!(SkillManifest::parse(SKILL_MD, Some("[activation]\nkeyword = []")).is_err());
+    }
+}
@@ -111,7 +111,11 @@ Write-Host "📦 Installing binaries to $binDir..."
 $coreBinaries = @(
     "target\release\skill-activation-prompt.exe",
     "target\release\file-analyzer.exe",
-    "target\release\cargo-check.exe"
+    "target\release\cargo-check.exe",
+    "target\release\path-guard.exe",
+    "target\release\test-runner.exe",
+    "target\release\prompt-logger.exe",
+    "target\release\session-digest.exe"
 )
 
 foreach ($binary in $coreBinaries) {
@@ -136,6 +140,9 @@ if ($Sqlite) {
     }
 }
 
+# Record how Catalyst was installed so `catalyst status` suggests matching fixes
+Set-Content -Path "$binDir\.install-method" -Value "script"
+
 Write-Host ""
 Write-Host "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━"
 Write-Host "✅ Installation Complete!"
@@ -87,7 +87,7 @@ fi
 # Copy core binaries
 echo
 echo "📦 Installing binaries to $BIN_DIR..."
-for binary in target/release/skill-activation-prompt target/release/file-analyzer target/release/cargo-check; do
+for binary in target/release/skill-activation-prompt target/release/file-analyzer target/release/cargo-check target/release/path-guard target/release/test-runner target/release/prompt-logger target/release/session-digest; do
     if [ -f "$binary" ]; then
         cp "$binary" "$BIN_DIR/"
         chmod +x "$BIN_DIR/$(basename $binary)"
@@ -104,6 +104,9 @@ if [ "$BUILD_SQLITE" = true ]; then
     fi
 fi
 
+# Record how Catalyst was installed so `catalyst status` suggests matching fixes
+echo "script" > "$BIN_DIR/.install-method"
+
 echo
 echo "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━"
 echo "✅ Installation Complete!"
@@ -0,0 +1,33 @@
+# Homebrew formula for Catalyst
+#
+# Copy into a tap (Formula/catalyst.rb) and set `url`/`sha256` for the
+# release being published. The formula writes an `.install-method` marker next
+# to the binaries so `catalyst status` and `catalyst doctor` suggest
+# `brew upgrade catalyst` instead of ./install.sh.
+class Catalyst < Formula
+  desc "Skill auto-activation and hook tooling for Claude Code"
+  homepage "https://github.com/dwalleck/catalyst"
+  url "https://github.com/dwalleck/catalyst/archive/refs/tags/v0.1.0.tar.gz"
+  sha256 "0000000000000000000000000000000000000000000000000000000000000000"
+  license "MIT"
+  head "https://github.com/dwalleck/catalyst.git", branch: "main"
+
+  depends_on "rust" => :build
+
+  def install
+    system "cargo", "install", "--features", "sqlite", *std_cargo_args(path: "catalyst-cli")
+    (bin/".install-method").write "homebrew\n"
+  end
+
+  def caveats
+    <<~EOS
+      Hook wrappers look for binaries in ~/.claude-hooks/bin. Link them there with:
+        mkdir -p ~/.claude-hooks/bin
+        ln -sf #{opt_bin}/* ~/.claude-hooks/bin/
+    EOS
+  end
+
+  test do
+    assert_match version.to_s, shell_output("#{bin}/catalyst --version")
+  end
+end